@@ -0,0 +1,123 @@
+#ifndef WORKDAYS_H
+#define WORKDAYS_H
+
+/* Generated by cbindgen from src/ffi.rs; do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// The call succeeded.
+#define WORKDAYS_OK 0
+
+// A required pointer argument was null.
+#define WORKDAYS_ERR_NULL_POINTER -1
+
+// A year, month, and day did not form a valid date.
+#define WORKDAYS_ERR_INVALID_DATE -2
+
+// The configuration could not be parsed or was not valid UTF-8.
+#define WORKDAYS_ERR_INVALID_CONFIG -3
+
+// A negative number of work days was requested.
+#define WORKDAYS_ERR_NEGATIVE_DAYS -4
+
+// The calendar has no work days.
+#define WORKDAYS_ERR_NO_WORK_DAYS -5
+
+// No working date was found before the search horizon was exhausted.
+#define WORKDAYS_ERR_NO_WORKING_DAYS_IN_HORIZON -6
+
+// The result would fall outside the range of representable dates.
+#define WORKDAYS_ERR_DATE_OUT_OF_RANGE -7
+
+// Any other calendar error.
+#define WORKDAYS_ERR_OTHER -8
+
+// The library panicked; the calendar should be considered unusable.
+#define WORKDAYS_ERR_PANIC -99
+
+// An opaque handle to a work calendar.
+typedef struct workdays_calendar_t workdays_calendar_t;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Creates a calendar working Monday to Friday with no holidays.
+//
+// The returned handle must be released with [`workdays_calendar_destroy`]. Returns
+// null only if the library panicked.
+struct workdays_calendar_t *workdays_calendar_create(void);
+
+// Releases a calendar. Passing null is allowed and does nothing.
+//
+// # Safety
+//
+// `calendar` must be null or a handle returned by this library that has not already
+// been destroyed.
+void workdays_calendar_destroy(struct workdays_calendar_t *calendar);
+
+// Creates a calendar from a YAML or JSON configuration.
+//
+// On success stores the new handle in `*out`. On failure stores null in `*out` and,
+// if `error_buf` is not null, writes the NUL-terminated error message into it,
+// truncated to `error_buf_len` bytes including the terminator.
+//
+// # Safety
+//
+// `config` must be a NUL-terminated string, `out` must be valid for writes, and
+// `error_buf` must be null or valid for writes of `error_buf_len` bytes.
+int32_t workdays_calendar_from_config(const char *config,
+                                      struct workdays_calendar_t **out,
+                                      char *error_buf,
+                                      size_t error_buf_len);
+
+// Computes the date on which the `days`-th work day from the start date falls, and
+// stores it in `*out_year`, `*out_month`, and `*out_day`.
+//
+// # Safety
+//
+// `calendar` must be a live handle and the out pointers must be valid for writes.
+int32_t workdays_compute_end_date(const struct workdays_calendar_t *calendar,
+                                  int32_t year,
+                                  int32_t month,
+                                  int32_t day,
+                                  int64_t days,
+                                  int32_t *out_year,
+                                  int32_t *out_month,
+                                  int32_t *out_day);
+
+// Counts the working dates between two dates, inclusive, and stores the count in
+// `*out_days`. The count is zero when the start is after the end.
+//
+// # Safety
+//
+// `calendar` must be a live handle and `out_days` must be valid for writes.
+int32_t workdays_between(const struct workdays_calendar_t *calendar,
+                         int32_t start_year,
+                         int32_t start_month,
+                         int32_t start_day,
+                         int32_t end_year,
+                         int32_t end_month,
+                         int32_t end_day,
+                         int64_t *out_days);
+
+// Stores whether a date is a working date in `*out_is_working`.
+//
+// # Safety
+//
+// `calendar` must be a live handle and `out_is_working` must be valid for writes.
+int32_t workdays_is_working(const struct workdays_calendar_t *calendar,
+                            int32_t year,
+                            int32_t month,
+                            int32_t day,
+                            bool *out_is_working);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* WORKDAYS_H */
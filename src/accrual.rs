@@ -0,0 +1,354 @@
+//! Accrual and proration of amounts over worked periods.
+
+use crate::{system::month_bounds, CalendarSystem, Gregorian, WorkCalendar};
+use chrono::{Datelike, NaiveDate};
+
+/// The number of decimal places [`WorkCalendar::accrue`] rounds to.
+pub const ACCRUAL_DECIMALS: i32 = 4;
+
+/// How an entitlement accrues with time worked, for [`WorkCalendar::accrue`].
+///
+/// A policy of 1.25 days per 21 working days is `PerWorkDay(1.25 / 21.0)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AccrualRate {
+    /// This amount for every working date.
+    PerWorkDay(f64),
+    /// This amount for every full work week, and pro rata for part of one. A work week
+    /// is as many working dates as the calendar has work days per week.
+    PerWorkWeek(f64),
+    /// This amount for every calendar month, prorated by the share of the month's
+    /// working dates that fall in the range.
+    PerMonth(f64),
+}
+
+/// The result of [`WorkCalendar::prorate_month`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProrationResult {
+    /// The number of working dates in the month on which the engagement was active.
+    pub worked_days: i64,
+    /// The number of working dates in the month.
+    pub total_days: i64,
+    /// `worked_days / total_days`, or 0 if the month has no working dates. The two
+    /// counts are the exact fraction, for callers that round it their own way.
+    pub fraction: f64,
+    /// The prorated amount, unrounded.
+    pub amount: f64,
+}
+
+impl WorkCalendar {
+    /// Computes the amount accrued at `rate` over the working dates from `start` to
+    /// `end`, inclusive.
+    ///
+    /// Holidays and other non-working dates accrue nothing. With
+    /// [`AccrualRate::PerMonth`], each month the range touches contributes
+    ///
+    /// ```text
+    /// amount × (working dates of the month in the range) / (working dates in the month)
+    /// ```
+    ///
+    /// so a whole month accrues the full amount however many working dates it has, and
+    /// a month with no working dates accrues nothing. With
+    /// [`AccrualRate::PerWorkWeek`], the amount is divided by the calendar's work days
+    /// per week, and a calendar with no work days accrues nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The first date of the range.
+    /// * `end` - The last date of the range.
+    /// * `rate` - The accrual rate.
+    ///
+    /// # Returns
+    ///
+    /// The accrued amount rounded to [`ACCRUAL_DECIMALS`] decimal places, or 0 if
+    /// `start` is after `end`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{AccrualRate, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let start = NaiveDate::from_ymd_opt(2023, 9, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2023, 9, 29).unwrap();
+    ///
+    /// // September 2023 has 21 working dates.
+    /// assert_eq!(calendar.accrue(start, end, AccrualRate::PerWorkDay(1.25 / 21.0)), 1.25);
+    /// assert_eq!(calendar.accrue(start, end, AccrualRate::PerWorkWeek(0.5)), 2.1);
+    /// assert_eq!(calendar.accrue(start, end, AccrualRate::PerMonth(1.25)), 1.25);
+    /// ```
+    pub fn accrue(&self, start: NaiveDate, end: NaiveDate, rate: AccrualRate) -> f64 {
+        let accrued = match rate {
+            AccrualRate::PerWorkDay(amount) => amount * self.work_days_between(start, end) as f64,
+            // Each run of a single work week accrues by its own week length.
+            AccrualRate::PerWorkWeek(amount) => self
+                .week_segments(start, end)
+                .map(|(week, first, last)| match week.days_per_week() {
+                    0 => 0.0,
+                    per_week => {
+                        amount * self.work_days_between(first, last) as f64 / per_week as f64
+                    }
+                })
+                .sum(),
+            AccrualRate::PerMonth(amount) => amount * self.months_worked(start, end),
+        };
+        round(accrued)
+    }
+
+    /// Prorates a monthly amount by the working dates on which an engagement was
+    /// active in a month.
+    ///
+    /// The active range runs from `active_from` to `active_to`, inclusive, with `None`
+    /// meaning the start or end of the month, and is clipped to the month. Holidays
+    /// count in neither the worked days nor the month's total, so a full month always
+    /// yields exactly `amount`, however many holidays it has.
+    ///
+    /// # Arguments
+    ///
+    /// * `year` - The year of the month.
+    /// * `month` - The month, from 1 to 12.
+    /// * `active_from` - The first active date, if after the start of the month.
+    /// * `active_to` - The last active date, if before the end of the month.
+    /// * `amount` - The amount for a full month.
+    ///
+    /// # Returns
+    ///
+    /// The worked and total working dates, their ratio, and the prorated amount. A
+    /// month with no working dates, or that does not exist, prorates to 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// // Joined on Monday, September 11, 2023.
+    /// let joined = NaiveDate::from_ymd_opt(2023, 9, 11);
+    /// let result = calendar.prorate_month(2023, 9, joined, None, 8000.0);
+    /// assert_eq!((result.worked_days, result.total_days), (15, 21));
+    /// assert_eq!((result.amount * 100.0).round() / 100.0, 5714.29);
+    /// ```
+    pub fn prorate_month(
+        &self,
+        year: i32,
+        month: u32,
+        active_from: Option<NaiveDate>,
+        active_to: Option<NaiveDate>,
+        amount: f64,
+    ) -> ProrationResult {
+        let total_days = self.work_days_in_month(year, month);
+        let worked_days = match month_bounds(&Gregorian, year, month) {
+            Some((first, last)) => self.work_days_between(
+                active_from.map_or(first, |date| date.max(first)),
+                active_to.map_or(last, |date| date.min(last)),
+            ),
+            None => 0,
+        };
+        let (fraction, amount) = if worked_days == 0 {
+            (0.0, 0.0)
+        } else if worked_days == total_days {
+            (1.0, amount)
+        } else {
+            let fraction = worked_days as f64 / total_days as f64;
+            (fraction, amount * worked_days as f64 / total_days as f64)
+        };
+        ProrationResult {
+            worked_days,
+            total_days,
+            fraction,
+            amount,
+        }
+    }
+
+    /// Returns the number of months worked from `start` to `end`, with partial months
+    /// counted by their share of working dates.
+    fn months_worked(&self, start: NaiveDate, end: NaiveDate) -> f64 {
+        let mut months = 0.0;
+        let mut cursor = start;
+        while cursor <= end {
+            let (year, month) = (cursor.year(), cursor.month());
+            let month_end = Gregorian
+                .days_in_month(year, month)
+                .and_then(|days| cursor.with_day(days))
+                .unwrap_or(cursor);
+            let in_month = self.work_days_in_month(year, month);
+            if in_month > 0 {
+                months +=
+                    self.work_days_between(cursor, month_end.min(end)) as f64 / in_month as f64;
+            }
+            cursor = match month_end.succ_opt() {
+                Some(next) => next,
+                None => break,
+            };
+        }
+        months
+    }
+}
+
+fn round(value: f64) -> f64 {
+    let scale = 10f64.powi(ACCRUAL_DECIMALS);
+    (value * scale).round() / scale
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::date;
+    use chrono::Weekday;
+
+    #[test]
+    fn test_accrue_mid_month_to_mid_month() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2023, 11, 23));
+        calendar.add_holiday(date(2023, 12, 25));
+        // Wednesday 2023-11-15 to Friday 2023-12-15: 11 of November's 21 working dates
+        // and 11 of December's 20.
+        let (start, end) = (date(2023, 11, 15), date(2023, 12, 15));
+        assert_eq!(calendar.work_days_between(start, end), 22);
+
+        assert_eq!(
+            calendar.accrue(start, end, AccrualRate::PerWorkDay(1.25 / 21.0)),
+            1.3095
+        );
+        assert_eq!(
+            calendar.accrue(start, end, AccrualRate::PerWorkWeek(1.0)),
+            4.4
+        );
+        // 1.5 × (11/21 + 11/20)
+        assert_eq!(
+            calendar.accrue(start, end, AccrualRate::PerMonth(1.5)),
+            1.6107
+        );
+    }
+
+    #[test]
+    fn test_accrue_whole_months_and_empty_ranges() {
+        let calendar = WorkCalendar::new();
+        let year = (date(2023, 1, 1), date(2023, 12, 31));
+        assert_eq!(
+            calendar.accrue(year.0, year.1, AccrualRate::PerMonth(1.25)),
+            15.0
+        );
+        assert_eq!(
+            calendar.accrue(year.0, year.1, AccrualRate::PerWorkWeek(1.0)),
+            52.0
+        );
+        assert_eq!(
+            calendar.accrue(year.0, year.1, AccrualRate::PerWorkDay(0.1)),
+            26.0
+        );
+
+        // A weekend accrues nothing, and neither does an inverted range.
+        let rates = [
+            AccrualRate::PerWorkDay(1.0),
+            AccrualRate::PerWorkWeek(1.0),
+            AccrualRate::PerMonth(1.0),
+        ];
+        for rate in rates {
+            assert_eq!(
+                calendar.accrue(date(2023, 9, 2), date(2023, 9, 3), rate),
+                0.0
+            );
+            assert_eq!(
+                calendar.accrue(date(2023, 9, 8), date(2023, 9, 1), rate),
+                0.0
+            );
+        }
+        // The last month of the date range ends the walk without overflowing.
+        let last = calendar.accrue(NaiveDate::MAX, NaiveDate::MAX, AccrualRate::PerMonth(1.0));
+        assert!((0.0..=1.0).contains(&last));
+    }
+
+    #[test]
+    fn test_accrue_without_work_days() {
+        let mut calendar = WorkCalendar::new();
+        for n in 0..7 {
+            calendar.remove_work_day(&Weekday::try_from(n).unwrap());
+        }
+        let (start, end) = (date(2023, 1, 1), date(2023, 12, 31));
+        for rate in [
+            AccrualRate::PerWorkDay(1.0),
+            AccrualRate::PerWorkWeek(1.0),
+            AccrualRate::PerMonth(1.0),
+        ] {
+            assert_eq!(calendar.accrue(start, end, rate), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_round() {
+        assert_eq!(round(1.0 / 3.0), 0.3333);
+        assert_eq!(round(2.0 / 3.0), 0.6667);
+        assert_eq!(round(-2.0 / 3.0), -0.6667);
+        assert_eq!(round(5.0), 5.0);
+    }
+
+    #[test]
+    fn test_prorate_month() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2023, 9, 4));
+        calendar.add_holiday(date(2023, 9, 11));
+        // September 2023 has 21 weekdays, less two holidays.
+        let full = calendar.prorate_month(2023, 9, None, None, 8000.0);
+        assert_eq!(
+            full,
+            ProrationResult {
+                worked_days: 19,
+                total_days: 19,
+                fraction: 1.0,
+                amount: 8000.0,
+            }
+        );
+        // Bounds outside the month are clipped to it.
+        assert_eq!(
+            calendar.prorate_month(2023, 9, Some(date(2023, 1, 1)), Some(date(2024, 1, 1)), 0.1),
+            ProrationResult {
+                amount: 0.1,
+                ..full
+            }
+        );
+
+        // Joining on a holiday counts from the next working date, the same as joining
+        // the day after.
+        let joined = calendar.prorate_month(2023, 9, Some(date(2023, 9, 11)), None, 8000.0);
+        assert_eq!(
+            joined,
+            calendar.prorate_month(2023, 9, Some(date(2023, 9, 12)), None, 8000.0)
+        );
+        assert_eq!((joined.worked_days, joined.total_days), (14, 19));
+        assert_eq!(joined.fraction, 14.0 / 19.0);
+        assert_eq!(joined.amount, 8000.0 * 14.0 / 19.0);
+
+        // Joining and leaving mid-month.
+        let stint = calendar.prorate_month(
+            2023,
+            9,
+            Some(date(2023, 9, 13)),
+            Some(date(2023, 9, 22)),
+            1900.0,
+        );
+        assert_eq!((stint.worked_days, stint.amount), (8, 800.0));
+
+        // Inactive for the whole month.
+        let inactive = calendar.prorate_month(2023, 9, Some(date(2023, 10, 2)), None, 8000.0);
+        assert_eq!(
+            (inactive.worked_days, inactive.fraction, inactive.amount),
+            (0, 0.0, 0.0)
+        );
+        let inverted = calendar.prorate_month(
+            2023,
+            9,
+            Some(date(2023, 9, 20)),
+            Some(date(2023, 9, 10)),
+            8000.0,
+        );
+        assert_eq!(inverted.amount, 0.0);
+        assert_eq!(
+            calendar
+                .prorate_month(2023, 13, None, None, 8000.0)
+                .total_days,
+            0
+        );
+    }
+}
@@ -0,0 +1,683 @@
+//! Business-day adjustment conventions for dates that fall on non-working days.
+
+use crate::system::month_bounds;
+use crate::{Gregorian, WorkCalendar, WorkCalendarError, SEARCH_HORIZON_DAYS};
+use chrono::{Datelike, Months, NaiveDate};
+
+/// How a date that falls on a non-working day is moved to a working date.
+///
+/// Working dates are never moved, whatever the convention.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AdjustmentConvention {
+    /// Leave the date where it is.
+    Unadjusted,
+    /// Move to the next working date.
+    Following,
+    /// Move to the next working date, unless that is in the next month, in which case
+    /// move to the previous working date instead.
+    ModifiedFollowing,
+    /// Move to the previous working date.
+    Preceding,
+    /// Move to the previous working date, unless that is in the previous month, in
+    /// which case move to the next working date instead.
+    ModifiedPreceding,
+}
+
+/// The name market conventions often use for [`AdjustmentConvention`].
+pub type RollConvention = AdjustmentConvention;
+
+impl WorkCalendar {
+    /// Moves a date to a working date according to `convention`.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The date to adjust.
+    /// * `convention` - Which way to move the date if it is not a working date.
+    ///
+    /// # Errors
+    ///
+    /// * [`WorkCalendarError::NoWorkDaysDefined`] if `date` must move and the calendar
+    ///   has no work days.
+    /// * [`WorkCalendarError::NoWorkingDaysInHorizon`] if no working date is found
+    ///   within [`SEARCH_HORIZON_DAYS`](crate::SEARCH_HORIZON_DAYS).
+    /// * [`WorkCalendarError::DateOutOfRange`] if the result cannot be represented.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{AdjustmentConvention, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let saturday = NaiveDate::from_ymd_opt(2023, 9, 30).unwrap();
+    /// assert_eq!(
+    ///     calendar.adjust(saturday, AdjustmentConvention::Following).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2023, 10, 2).unwrap()
+    /// );
+    /// assert_eq!(
+    ///     calendar.adjust(saturday, AdjustmentConvention::ModifiedFollowing).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2023, 9, 29).unwrap()
+    /// );
+    /// ```
+    pub fn adjust(
+        &self,
+        date: NaiveDate,
+        convention: AdjustmentConvention,
+    ) -> Result<NaiveDate, WorkCalendarError> {
+        if self.is_working_date(&date) {
+            return Ok(date);
+        }
+        let same_month = |other: &NaiveDate| other.month() == date.month();
+        match convention {
+            AdjustmentConvention::Unadjusted => Ok(date),
+            AdjustmentConvention::Following => self.try_add_work_days(date, 1),
+            AdjustmentConvention::Preceding => self.try_add_work_days(date, -1),
+            AdjustmentConvention::ModifiedFollowing => self
+                .try_add_work_days(date, 1)
+                .ok()
+                .filter(same_month)
+                .map_or_else(|| self.try_add_work_days(date, -1), Ok),
+            AdjustmentConvention::ModifiedPreceding => self
+                .try_add_work_days(date, -1)
+                .ok()
+                .filter(same_month)
+                .map_or_else(|| self.try_add_work_days(date, 1), Ok),
+        }
+    }
+
+    /// Adds a tenor of `months` calendar months to `date`, then adjusts the result
+    /// according to `convention`.
+    ///
+    /// Without the end-of-month rule, the date moves by whole months and is clamped to
+    /// the last day of shorter months, so January 31 plus one month is the last day of
+    /// February. With `eom` set and `date` the last working date of its month, the
+    /// result is instead the last working date of the target month, so February 28,
+    /// 2023 plus one month is March 31 rather than March 28. The rule looks at working
+    /// dates, not calendar days: a Friday the 29th before a weekend is the last working
+    /// date of its month, while a Saturday the 31st is not.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The start date.
+    /// * `months` - The number of months to add, negative to go back.
+    /// * `eom` - Whether to apply the end-of-month rule.
+    /// * `convention` - How to move a result that is not a working date.
+    ///
+    /// # Errors
+    ///
+    /// * [`WorkCalendarError::DateOutOfRange`] if the shifted date cannot be
+    ///   represented.
+    /// * The errors of [`WorkCalendar::adjust`] for the adjustment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{AdjustmentConvention, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let convention = AdjustmentConvention::ModifiedFollowing;
+    /// // Friday, September 29, 2023 is the last working date of September.
+    /// let start = NaiveDate::from_ymd_opt(2023, 9, 29).unwrap();
+    /// assert_eq!(
+    ///     calendar.add_tenor_months(start, 1, true, convention).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2023, 10, 31).unwrap()
+    /// );
+    /// // October 29 is a Sunday, which moves to Monday.
+    /// assert_eq!(
+    ///     calendar.add_tenor_months(start, 1, false, convention).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2023, 10, 30).unwrap()
+    /// );
+    /// ```
+    pub fn add_tenor_months(
+        &self,
+        date: NaiveDate,
+        months: i32,
+        eom: bool,
+        convention: AdjustmentConvention,
+    ) -> Result<NaiveDate, WorkCalendarError> {
+        let out_of_range = || WorkCalendarError::DateOutOfRange {
+            calendar: self.name().map(String::from),
+        };
+        let tenor = Months::new(months.unsigned_abs());
+        let shifted = if months < 0 {
+            date.checked_sub_months(tenor)
+        } else {
+            date.checked_add_months(tenor)
+        }
+        .ok_or_else(out_of_range)?;
+
+        let month_end = self.nth_work_day_of_month(date.year(), date.month(), -1);
+        let target = if eom && month_end == Some(date) {
+            let (first, last) = month_bounds(&Gregorian, shifted.year(), shifted.month())
+                .ok_or_else(out_of_range)?;
+            // A month without working dates leaves its last day to the convention.
+            self.nth_working_date_within(first, last, -1)
+                .unwrap_or(last)
+        } else {
+            shifted
+        };
+        self.adjust(target, convention)
+    }
+}
+
+impl WorkCalendar {
+    /// Computes an FX-style settlement date: `offset_days` working days of this (the
+    /// base) calendar after `trade_date`, then moved forward until it is a working date
+    /// of both this calendar and `other`.
+    ///
+    /// The offset is counted on this calendar alone, as with
+    /// [`WorkCalendar::checked_add_work_days`], so `trade_date` itself is never one of
+    /// the `offset_days`. The result is then adjusted with
+    /// [`adjust_joint`] and [`AdjustmentConvention::Following`].
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The second currency's calendar.
+    /// * `trade_date` - The date the trade was made.
+    /// * `offset_days` - The number of base-calendar working days to settlement, such
+    ///   as 2 for spot.
+    ///
+    /// # Errors
+    ///
+    /// The errors of [`WorkCalendar::next_work_day`] for the offset, and of
+    /// [`adjust_joint`] for the adjustment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let usd = WorkCalendar::new();
+    /// let mut jpy = WorkCalendar::new();
+    /// jpy.add_holiday(NaiveDate::from_ymd_opt(2025, 5, 5).unwrap()); // Monday
+    /// jpy.add_holiday(NaiveDate::from_ymd_opt(2025, 5, 6).unwrap());
+    ///
+    /// // Thursday + 2 USD days is Monday, which is a JPY holiday, as is Tuesday.
+    /// let trade = NaiveDate::from_ymd_opt(2025, 5, 1).unwrap();
+    /// assert_eq!(
+    ///     usd.joint_settlement_date(&jpy, trade, 2).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2025, 5, 7).unwrap()
+    /// );
+    /// ```
+    pub fn joint_settlement_date(
+        &self,
+        other: &WorkCalendar,
+        trade_date: NaiveDate,
+        offset_days: u32,
+    ) -> Result<NaiveDate, WorkCalendarError> {
+        let spot = self.try_add_work_days(trade_date, offset_days.into())?;
+        adjust_joint(spot, &[self, other], AdjustmentConvention::Following)
+    }
+}
+
+/// Moves a date to one that is a working date on every calendar in `calendars`,
+/// according to `convention`.
+///
+/// This is [`WorkCalendar::adjust`] with a date counted as a working date only if all
+/// the calendars agree, so each step checks every calendar at once: moving off one
+/// calendar's holiday onto another's keeps moving. The modified conventions compare
+/// the jointly adjusted date's month with the original date's month.
+///
+/// # Arguments
+///
+/// * `date` - The date to adjust.
+/// * `calendars` - The calendars that must all treat the result as a working date.
+/// * `convention` - Which way to move the date if it is not a common working date.
+///
+/// # Errors
+///
+/// * [`WorkCalendarError::NoWorkDaysDefined`] if `date` must move and `calendars` is
+///   empty or one of them has no work days.
+/// * [`WorkCalendarError::NoWorkingDaysInHorizon`] if no common working date is found
+///   within [`SEARCH_HORIZON_DAYS`], as with calendars whose work weeks do not overlap.
+/// * [`WorkCalendarError::DateOutOfRange`] if the result cannot be represented.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::{adjust_joint, AdjustmentConvention, WorkCalendar};
+/// use chrono::NaiveDate;
+///
+/// let mut us = WorkCalendar::new();
+/// us.add_holiday(NaiveDate::from_ymd_opt(2025, 12, 29).unwrap()); // Monday
+/// let mut jp = WorkCalendar::new();
+/// jp.add_holiday(NaiveDate::from_ymd_opt(2025, 12, 30).unwrap());
+/// jp.add_holiday(NaiveDate::from_ymd_opt(2025, 12, 31).unwrap());
+///
+/// let saturday = NaiveDate::from_ymd_opt(2025, 12, 27).unwrap();
+/// assert_eq!(
+///     adjust_joint(saturday, &[&us, &jp], AdjustmentConvention::Following).unwrap(),
+///     NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()
+/// );
+/// assert_eq!(
+///     adjust_joint(saturday, &[&us, &jp], AdjustmentConvention::ModifiedFollowing).unwrap(),
+///     NaiveDate::from_ymd_opt(2025, 12, 26).unwrap()
+/// );
+/// ```
+pub fn adjust_joint(
+    date: NaiveDate,
+    calendars: &[&WorkCalendar],
+    convention: AdjustmentConvention,
+) -> Result<NaiveDate, WorkCalendarError> {
+    let is_common = |date: &NaiveDate| {
+        !calendars.is_empty()
+            && calendars
+                .iter()
+                .all(|calendar| calendar.is_working_date(date))
+    };
+    if is_common(&date) || convention == AdjustmentConvention::Unadjusted {
+        return Ok(date);
+    }
+    if calendars.is_empty()
+        || !calendars
+            .iter()
+            .all(|calendar| calendar.has_any_work_days())
+    {
+        return Err(WorkCalendarError::NoWorkDaysDefined { calendar: None });
+    }
+    let roll = |forward: bool| {
+        let mut current = date;
+        loop {
+            let next = if forward {
+                current.succ_opt()
+            } else {
+                current.pred_opt()
+            };
+            current = next.ok_or(WorkCalendarError::DateOutOfRange { calendar: None })?;
+            if is_common(&current) {
+                return Ok(current);
+            }
+            if (current - date).num_days().abs() >= SEARCH_HORIZON_DAYS {
+                return Err(WorkCalendarError::NoWorkingDaysInHorizon {
+                    calendar: None,
+                    searched_until: current,
+                });
+            }
+        }
+    };
+    let same_month = |other: &NaiveDate| other.month() == date.month();
+    match convention {
+        AdjustmentConvention::Unadjusted => Ok(date),
+        AdjustmentConvention::Following => roll(true),
+        AdjustmentConvention::Preceding => roll(false),
+        AdjustmentConvention::ModifiedFollowing => roll(true)
+            .ok()
+            .filter(same_month)
+            .map_or_else(|| roll(false), Ok),
+        AdjustmentConvention::ModifiedPreceding => roll(false)
+            .ok()
+            .filter(same_month)
+            .map_or_else(|| roll(true), Ok),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{date, random_calendar, TestRng};
+    use chrono::Weekday;
+
+    #[test]
+    fn test_adjust_conventions() {
+        use AdjustmentConvention::*;
+
+        let mut calendar = WorkCalendar::new();
+        for holiday in [date(2023, 7, 3), date(2024, 4, 1), date(2024, 5, 31)] {
+            calendar.add_holiday(holiday);
+        }
+        let cases = [
+            // A working date never moves.
+            (date(2023, 9, 29), Following, date(2023, 9, 29)),
+            (date(2023, 9, 29), Preceding, date(2023, 9, 29)),
+            // Saturday at the end of a month.
+            (date(2023, 9, 30), Unadjusted, date(2023, 9, 30)),
+            (date(2023, 9, 30), Following, date(2023, 10, 2)),
+            (date(2023, 9, 30), ModifiedFollowing, date(2023, 9, 29)),
+            (date(2023, 9, 30), Preceding, date(2023, 9, 29)),
+            (date(2023, 9, 30), ModifiedPreceding, date(2023, 9, 29)),
+            // Saturday at the start of a month, before a Monday holiday.
+            (date(2023, 7, 1), Following, date(2023, 7, 4)),
+            (date(2023, 7, 1), ModifiedFollowing, date(2023, 7, 4)),
+            (date(2023, 7, 1), Preceding, date(2023, 6, 30)),
+            (date(2023, 7, 1), ModifiedPreceding, date(2023, 7, 4)),
+            (date(2023, 7, 3), Preceding, date(2023, 6, 30)),
+            // A Friday holiday ending the month: the next working date is in June, so
+            // modified following goes back to Thursday.
+            (date(2024, 5, 31), Following, date(2024, 6, 3)),
+            (date(2024, 5, 31), ModifiedFollowing, date(2024, 5, 30)),
+            (date(2024, 5, 31), Preceding, date(2024, 5, 30)),
+            (date(2024, 5, 31), ModifiedPreceding, date(2024, 5, 30)),
+            // A Monday holiday starting the month: modified preceding goes forward.
+            (date(2024, 4, 1), Preceding, date(2024, 3, 29)),
+            (date(2024, 4, 1), ModifiedPreceding, date(2024, 4, 2)),
+        ];
+        for (input, convention, expected) in cases {
+            assert_eq!(
+                calendar.adjust(input, convention),
+                Ok(expected),
+                "{} {:?}",
+                input,
+                convention
+            );
+        }
+    }
+
+    #[test]
+    fn test_next_and_previous_work_day() {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("Ops");
+        calendar.add_holiday(date(2024, 5, 31));
+        calendar.add_holiday(date(2024, 6, 3));
+        // Both step strictly past `date`, even from a working date.
+        assert_eq!(
+            calendar.next_work_day(date(2024, 5, 30)),
+            Ok(date(2024, 6, 4))
+        );
+        assert_eq!(
+            calendar.previous_work_day(date(2024, 6, 4)),
+            Ok(date(2024, 5, 30))
+        );
+        assert_eq!(
+            calendar.previous_work_day(date(2024, 6, 2)),
+            Ok(date(2024, 5, 30))
+        );
+        assert_eq!(
+            calendar.adjust(date(2024, 6, 4), RollConvention::Preceding),
+            Ok(date(2024, 6, 4))
+        );
+
+        for n in 0..7 {
+            calendar.remove_work_day(&Weekday::try_from(n).unwrap());
+        }
+        let error = Err(WorkCalendarError::NoWorkDaysDefined {
+            calendar: Some("Ops".to_string()),
+        });
+        assert_eq!(calendar.next_work_day(date(2024, 5, 30)), error);
+        assert_eq!(calendar.previous_work_day(date(2024, 5, 30)), error);
+        assert_eq!(
+            WorkCalendar::new().previous_work_day(NaiveDate::MIN),
+            Err(WorkCalendarError::DateOutOfRange { calendar: None })
+        );
+    }
+
+    /// Holidays that interleave so that stepping off one calendar's holiday lands on
+    /// the other's, USD/JPY style.
+    fn usd_jpy() -> (WorkCalendar, WorkCalendar) {
+        let mut usd = WorkCalendar::new();
+        usd.set_name("USD");
+        let mut jpy = WorkCalendar::new();
+        jpy.set_name("JPY");
+        for day in [5, 7] {
+            jpy.add_holiday(date(2025, 5, day));
+        }
+        for day in [6, 8] {
+            usd.add_holiday(date(2025, 5, day));
+        }
+        (usd, jpy)
+    }
+
+    #[test]
+    fn test_joint_settlement_interleaved_holidays() {
+        let (usd, jpy) = usd_jpy();
+        let cases = [
+            // Thursday + 2 USD days is Monday the 5th, and JPY and USD holidays
+            // alternate from then until Friday the 9th.
+            (date(2025, 5, 1), 2, date(2025, 5, 9)),
+            // Friday + 2 USD days skips the USD holiday on the 6th to the 7th.
+            (date(2025, 5, 2), 2, date(2025, 5, 9)),
+            // After the holidays, the offset alone decides.
+            (date(2025, 5, 9), 2, date(2025, 5, 13)),
+            (date(2025, 5, 1), 0, date(2025, 5, 1)),
+        ];
+        for (trade, offset, expected) in cases {
+            assert_eq!(
+                usd.joint_settlement_date(&jpy, trade, offset),
+                Ok(expected),
+                "{} + {}",
+                trade,
+                offset
+            );
+        }
+        // With JPY as the base, Thursday + 2 JPY days is the 6th, a USD holiday.
+        assert_eq!(
+            jpy.joint_settlement_date(&usd, date(2025, 5, 1), 2),
+            Ok(date(2025, 5, 9))
+        );
+    }
+
+    #[test]
+    fn test_adjust_joint_conventions() {
+        use AdjustmentConvention::*;
+
+        let (usd, jpy) = usd_jpy();
+        let both = [&usd, &jpy];
+        let cases = [
+            (date(2025, 5, 2), Following, date(2025, 5, 2)),
+            (date(2025, 5, 5), Unadjusted, date(2025, 5, 5)),
+            (date(2025, 5, 5), Following, date(2025, 5, 9)),
+            (date(2025, 5, 8), Preceding, date(2025, 5, 2)),
+            (date(2025, 5, 5), ModifiedFollowing, date(2025, 5, 9)),
+            (date(2025, 5, 8), ModifiedPreceding, date(2025, 5, 2)),
+        ];
+        for (input, convention, expected) in cases {
+            assert_eq!(
+                adjust_joint(input, &both, convention),
+                Ok(expected),
+                "{} {:?}",
+                input,
+                convention
+            );
+        }
+
+        // Holidays from the 29th to the end of the month push a modified-following
+        // adjustment back into the month.
+        let mut usd = usd.clone();
+        usd.add_holiday(date(2025, 5, 29));
+        let mut jpy = jpy.clone();
+        jpy.add_holiday(date(2025, 5, 30));
+        let both = [&usd, &jpy];
+        assert_eq!(
+            adjust_joint(date(2025, 5, 29), &both, Following),
+            Ok(date(2025, 6, 2))
+        );
+        assert_eq!(
+            adjust_joint(date(2025, 5, 29), &both, ModifiedFollowing),
+            Ok(date(2025, 5, 28))
+        );
+        // The 1st is a Sunday; preceding would leave June.
+        assert_eq!(
+            adjust_joint(date(2025, 6, 1), &both, ModifiedPreceding),
+            Ok(date(2025, 6, 2))
+        );
+    }
+
+    #[test]
+    fn test_adjust_joint_errors() {
+        let monday = date(2023, 9, 25);
+        let saturday = date(2023, 9, 23);
+        assert_eq!(
+            adjust_joint(monday, &[], AdjustmentConvention::Following),
+            Err(WorkCalendarError::NoWorkDaysDefined { calendar: None })
+        );
+        assert_eq!(
+            adjust_joint(monday, &[], AdjustmentConvention::Unadjusted),
+            Ok(monday)
+        );
+
+        let mut early = WorkCalendar::new();
+        early.set_work_days("Mon,Tue").unwrap();
+        let mut late = WorkCalendar::new();
+        late.set_work_days("Thu,Fri").unwrap();
+        assert_eq!(
+            adjust_joint(saturday, &[&early, &late], AdjustmentConvention::Following),
+            Err(WorkCalendarError::NoWorkingDaysInHorizon {
+                calendar: None,
+                searched_until: saturday + chrono::Duration::days(SEARCH_HORIZON_DAYS)
+            })
+        );
+
+        let mut idle = WorkCalendar::new();
+        for n in 0..7 {
+            idle.remove_work_day(&Weekday::try_from(n).unwrap());
+        }
+        assert_eq!(
+            adjust_joint(saturday, &[&early, &idle], AdjustmentConvention::Preceding),
+            Err(WorkCalendarError::NoWorkDaysDefined { calendar: None })
+        );
+        assert_eq!(
+            WorkCalendar::new().joint_settlement_date(&idle, monday, 2),
+            Err(WorkCalendarError::NoWorkDaysDefined { calendar: None })
+        );
+    }
+
+    #[test]
+    fn test_add_tenor_months_end_of_month() {
+        use AdjustmentConvention::*;
+
+        let calendar = WorkCalendar::new();
+        // (start, months, with the end-of-month rule, without it)
+        let cases = [
+            // January 31 lands on the last day of February, leap year or not.
+            (date(2023, 1, 31), 1, date(2023, 2, 28), date(2023, 2, 28)),
+            (date(2024, 1, 31), 1, date(2024, 2, 29), date(2024, 2, 29)),
+            // The last day of a short February only pins in the end-of-month rule.
+            (date(2023, 2, 28), 1, date(2023, 3, 31), date(2023, 3, 28)),
+            (date(2024, 2, 29), 1, date(2024, 3, 29), date(2024, 3, 29)),
+            // February 28, 2024 is not the end of a leap-year February.
+            (date(2024, 2, 28), 1, date(2024, 3, 28), date(2024, 3, 28)),
+            // Year tenors across leap days, both ways.
+            (date(2024, 2, 29), 12, date(2025, 2, 28), date(2025, 2, 28)),
+            (date(2024, 2, 29), -12, date(2023, 2, 28), date(2023, 2, 28)),
+            (date(2023, 2, 28), 12, date(2024, 2, 29), date(2024, 2, 28)),
+            (date(2023, 2, 28), -12, date(2022, 2, 28), date(2022, 2, 28)),
+            // Back from the end of March to February.
+            (date(2023, 3, 31), -1, date(2023, 2, 28), date(2023, 2, 28)),
+            (date(2024, 3, 29), -1, date(2024, 2, 29), date(2024, 2, 29)),
+            // The last working date need not be the last calendar day.
+            (date(2023, 9, 29), 1, date(2023, 10, 31), date(2023, 10, 30)),
+            (date(2023, 4, 28), 1, date(2023, 5, 31), date(2023, 5, 29)),
+            // Friday, March 31, 2023 to Sunday, April 30, which moves back.
+            (date(2023, 3, 31), 1, date(2023, 4, 28), date(2023, 4, 28)),
+            // Not the last working date of January.
+            (date(2023, 1, 30), 1, date(2023, 2, 28), date(2023, 2, 28)),
+            (date(2023, 1, 27), 1, date(2023, 2, 27), date(2023, 2, 27)),
+            (date(2023, 1, 31), 0, date(2023, 1, 31), date(2023, 1, 31)),
+        ];
+        for (start, months, with_eom, without_eom) in cases {
+            assert_eq!(
+                calendar.add_tenor_months(start, months, true, ModifiedFollowing),
+                Ok(with_eom),
+                "{} {:+}M EOM",
+                start,
+                months
+            );
+            assert_eq!(
+                calendar.add_tenor_months(start, months, false, ModifiedFollowing),
+                Ok(without_eom),
+                "{} {:+}M",
+                start,
+                months
+            );
+        }
+
+        // A Saturday month-end is not a working date, so the rule does not apply.
+        let saturday = date(2023, 9, 30);
+        assert_eq!(
+            calendar.add_tenor_months(saturday, 1, true, Unadjusted),
+            Ok(date(2023, 10, 30))
+        );
+        assert_eq!(
+            calendar.add_tenor_months(date(2023, 9, 29), 1, false, Following),
+            Ok(date(2023, 10, 30))
+        );
+    }
+
+    #[test]
+    fn test_add_tenor_months_holidays() {
+        use AdjustmentConvention::*;
+
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2023, 3, 31));
+        calendar.add_holiday(date(2023, 6, 30));
+        // Thursday, March 30 is now the last working date of March.
+        assert_eq!(
+            calendar.add_tenor_months(date(2023, 1, 31), 2, true, Following),
+            Ok(date(2023, 3, 30))
+        );
+        assert_eq!(
+            calendar.add_tenor_months(date(2023, 3, 30), 3, true, Unadjusted),
+            Ok(date(2023, 6, 29))
+        );
+        assert_eq!(
+            calendar.add_tenor_months(date(2023, 3, 30), 3, false, Following),
+            Ok(date(2023, 7, 3))
+        );
+        assert_eq!(
+            calendar.add_tenor_months(date(2023, 3, 30), 3, false, ModifiedFollowing),
+            Ok(date(2023, 6, 29))
+        );
+    }
+
+    #[test]
+    fn test_add_tenor_months_end_of_month_randomized() {
+        let mut rng = TestRng(0x5e0d_71c4_a2b9_8f36);
+        for _ in 0..100 {
+            let calendar = random_calendar(&mut rng);
+            let start = date(2020, 1, 1) + chrono::Duration::days(rng.below(3000) as i64);
+            let months = rng.below(61) as i32 - 30;
+            let month_end = |d: NaiveDate| calendar.nth_work_day_of_month(d.year(), d.month(), -1);
+            let start = match month_end(start) {
+                Some(start) => start,
+                None => continue,
+            };
+            let result = calendar
+                .add_tenor_months(start, months, true, AdjustmentConvention::Unadjusted)
+                .unwrap();
+            let shifted = if months < 0 {
+                start - Months::new(months.unsigned_abs())
+            } else {
+                start + Months::new(months.unsigned_abs())
+            };
+            let expected = month_end(shifted).unwrap_or_else(|| {
+                month_bounds(&Gregorian, shifted.year(), shifted.month())
+                    .unwrap()
+                    .1
+            });
+            assert_eq!(result, expected, "{} {:+}M", start, months);
+        }
+    }
+
+    #[test]
+    fn test_adjust_errors() {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("Ops");
+        calendar.add_holiday(NaiveDate::MAX);
+        assert_eq!(
+            calendar.adjust(NaiveDate::MAX, AdjustmentConvention::Following),
+            Err(WorkCalendarError::DateOutOfRange {
+                calendar: Some("Ops".to_string())
+            })
+        );
+        assert_eq!(
+            calendar.adjust(NaiveDate::MAX, AdjustmentConvention::ModifiedFollowing),
+            Ok(calendar.checked_sub_work_days(NaiveDate::MAX, 1).unwrap())
+        );
+
+        for n in 0..7 {
+            calendar.remove_work_day(&Weekday::try_from(n).unwrap());
+        }
+        let monday = date(2023, 9, 25);
+        assert_eq!(
+            calendar.adjust(monday, AdjustmentConvention::Unadjusted),
+            Ok(monday)
+        );
+        assert_eq!(
+            calendar.adjust(monday, AdjustmentConvention::Preceding),
+            Err(WorkCalendarError::NoWorkDaysDefined {
+                calendar: Some("Ops".to_string())
+            })
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! `proptest` strategies for generating random-but-valid calendars.
+//!
+//! Enabled with the `arbitrary` feature. Generated calendars always have at least one
+//! work day and draw holidays from the years 2000 through 2050. Shrinking moves toward
+//! the default calendar: Monday to Friday with no holidays.
+
+use crate::{CalendarSnapshot, WorkCalendar};
+use chrono::{NaiveDate, Weekday};
+use proptest::prelude::*;
+
+/// Bitmask of the default work week (Monday to Friday), indexed by `num_days_from_monday`.
+const DEFAULT_WEEK_MASK: u8 = 0b0011111;
+
+/// Earliest year holidays are drawn from.
+const MIN_YEAR: i32 = 2000;
+
+/// Latest year holidays are drawn from.
+const MAX_YEAR: i32 = 2050;
+
+/// Returns a strategy for dates between January 1 of `MIN_YEAR` and December 31 of `MAX_YEAR`.
+///
+/// Dates shrink toward January 1 of `MIN_YEAR`.
+pub fn date_strategy() -> impl Strategy<Value = NaiveDate> {
+    let first = NaiveDate::from_ymd_opt(MIN_YEAR, 1, 1).unwrap();
+    let last = NaiveDate::from_ymd_opt(MAX_YEAR, 12, 31).unwrap();
+    let span = last.signed_duration_since(first).num_days() as u64;
+    (0..=span).prop_map(move |offset| first + chrono::Days::new(offset))
+}
+
+/// Returns a strategy for non-empty sets of work days.
+///
+/// The set is generated as a difference from Monday to Friday, so it shrinks toward
+/// the default work week.
+pub fn work_days_strategy() -> impl Strategy<Value = Vec<Weekday>> {
+    (0u8..128)
+        .prop_filter("at least one work day", |diff| {
+            diff ^ DEFAULT_WEEK_MASK != 0
+        })
+        .prop_map(|diff| {
+            let mask = diff ^ DEFAULT_WEEK_MASK;
+            (0..7)
+                .filter(|bit| mask & (1 << bit) != 0)
+                .map(|bit| Weekday::try_from(bit as u8).unwrap())
+                .collect()
+        })
+}
+
+impl Arbitrary for WorkCalendar {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            work_days_strategy(),
+            proptest::collection::vec(date_strategy(), 0..32),
+            proptest::collection::vec(date_strategy(), 0..8),
+        )
+            .prop_map(|(work_days, holidays, working_dates)| {
+                let mut calendar = WorkCalendar::new();
+                calendar.work_days = work_days.into_iter().collect();
+                for date in holidays {
+                    calendar.add_holiday(date);
+                }
+                for date in working_dates {
+                    calendar.add_working_date(date);
+                }
+                calendar
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for CalendarSnapshot {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        any::<WorkCalendar>()
+            .prop_map(|calendar| calendar.snapshot())
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Datelike;
+
+    proptest! {
+        #[test]
+        fn generated_calendars_have_work_days(calendar in any::<WorkCalendar>()) {
+            prop_assert!((0..7).any(|n| calendar.is_work_day(&Weekday::try_from(n).unwrap())));
+            prop_assert!(calendar.holidays.iter().all(|d| (MIN_YEAR..=MAX_YEAR).contains(&d.year())));
+        }
+
+        #[test]
+        fn end_date_agrees_with_work_days_between(
+            calendar in any::<WorkCalendar>(),
+            start in date_strategy(),
+            days in 1i64..200,
+        ) {
+            let (end, duration) = calendar.compute_end_date(start, days).unwrap();
+            prop_assert_eq!(calendar.work_days_between(start, end), days);
+            prop_assert_eq!(end.signed_duration_since(start), duration);
+        }
+    }
+}
@@ -0,0 +1,388 @@
+//! Precomputed per-year bitsets of working dates.
+
+use crate::{CalendarLike, EndDate, WorkCalendar, WorkCalendarError, SEARCH_HORIZON_DAYS};
+use chrono::{Datelike, NaiveDate};
+use std::ops::RangeInclusive;
+
+/// Number of 64-bit words needed to hold one bit per day of a leap year.
+const WORDS_PER_YEAR: usize = 6;
+
+/// One bit per day of the year (bit `n` is day-of-year `n + 1`), set for working dates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct YearBits([u64; WORDS_PER_YEAR]);
+
+impl YearBits {
+    /// Counts the set bits in `from..=to` (zero-based day-of-year indices).
+    fn count(&self, from: u32, to: u32) -> u32 {
+        (0..WORDS_PER_YEAR as u32)
+            .map(|word| {
+                let mask = range_mask(word, from, to);
+                (self.0[word as usize] & mask).count_ones()
+            })
+            .sum()
+    }
+
+    /// Returns the index of the `n`th (one-based) set bit at or after `from`.
+    fn nth_from(&self, from: u32, mut n: u32) -> Option<u32> {
+        for word in from / 64..WORDS_PER_YEAR as u32 {
+            let mut bits = self.0[word as usize] & range_mask(word, from, u32::MAX);
+            let ones = bits.count_ones();
+            if ones < n {
+                n -= ones;
+                continue;
+            }
+            for _ in 1..n {
+                bits &= bits - 1;
+            }
+            return Some(word * 64 + bits.trailing_zeros());
+        }
+        None
+    }
+
+    /// Returns the index of the last set bit, if any.
+    fn last(&self) -> Option<u32> {
+        (0..WORDS_PER_YEAR as u32)
+            .rev()
+            .find(|word| self.0[*word as usize] != 0)
+            .map(|word| word * 64 + 63 - self.0[word as usize].leading_zeros())
+    }
+}
+
+/// Returns the bits of `word` that fall within `from..=to`.
+fn range_mask(word: u32, from: u32, to: u32) -> u64 {
+    let low = word * 64;
+    let high = low + 63;
+    if to < low || from > high {
+        return 0;
+    }
+    let start = from.saturating_sub(low);
+    let end = to.min(high) - low;
+    (u64::MAX >> (63 - end)) & (u64::MAX << start)
+}
+
+/// A precomputed, immutable bitset of working dates for a range of years.
+///
+/// Building the cache evaluates the calendar once for every day of the requested
+/// years. Afterwards, [`YearCache::is_working_date`] is a single bit test and range
+/// counts are popcounts.
+///
+/// The cache captures the calendar as it was when [`YearCache::build`] was called;
+/// later changes to the calendar are not reflected, so rebuild the cache after editing
+/// it. Queries outside the cached years fall back to that captured calendar, so every
+/// query returns the same answer as the calendar would have.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::{WorkCalendar, YearCache};
+/// use chrono::NaiveDate;
+///
+/// let mut calendar = WorkCalendar::new();
+/// calendar.add_holiday(NaiveDate::from_ymd_opt(2024, 12, 25).unwrap());
+///
+/// let cache = YearCache::build(&calendar, 2024..=2025);
+/// assert!(!cache.is_working_date(&NaiveDate::from_ymd_opt(2024, 12, 25).unwrap()));
+///
+/// let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+/// let end = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+/// assert_eq!(cache.work_days_between(start, end), calendar.work_days_between(start, end));
+/// ```
+#[derive(Debug, Clone)]
+pub struct YearCache {
+    first_year: i32,
+    years: Vec<YearBits>,
+    calendar: WorkCalendar,
+}
+
+impl YearCache {
+    /// Builds a cache of `calendar`'s working dates for the given years.
+    ///
+    /// # Arguments
+    ///
+    /// * `calendar` - The calendar to capture.
+    /// * `years` - The years to precompute, e.g. `2024..=2030`.
+    pub fn build(calendar: &WorkCalendar, years: RangeInclusive<i32>) -> Self {
+        let first_year = *years.start();
+        let years = years
+            .map(|year| {
+                let mut bits = YearBits([0; WORDS_PER_YEAR]);
+                let mut date = NaiveDate::from_ymd_opt(year, 1, 1);
+                while let Some(day) = date.filter(|d| d.year() == year) {
+                    if calendar.is_working_date(&day) {
+                        let index = day.ordinal0();
+                        bits.0[(index / 64) as usize] |= 1 << (index % 64);
+                    }
+                    date = day.succ_opt();
+                }
+                bits
+            })
+            .collect();
+        YearCache {
+            first_year,
+            years,
+            calendar: calendar.clone(),
+        }
+    }
+
+    /// Returns the range of years held in the cache.
+    pub fn years(&self) -> RangeInclusive<i32> {
+        self.first_year..=self.first_year + self.years.len() as i32 - 1
+    }
+
+    /// Returns the calendar captured when the cache was built.
+    pub fn calendar(&self) -> &WorkCalendar {
+        &self.calendar
+    }
+
+    fn year_bits(&self, year: i32) -> Option<&YearBits> {
+        let index = usize::try_from(year.checked_sub(self.first_year)?).ok()?;
+        self.years.get(index)
+    }
+
+    /// Checks if a given date is a working date.
+    ///
+    /// Returns the same result as [`WorkCalendar::is_working_date`].
+    pub fn is_working_date(&self, date: &NaiveDate) -> bool {
+        match self.year_bits(date.year()) {
+            Some(bits) => {
+                let index = date.ordinal0();
+                bits.0[(index / 64) as usize] & (1 << (index % 64)) != 0
+            }
+            None => self.calendar.is_working_date(date),
+        }
+    }
+
+    /// Calculates the number of work days between two dates (inclusive).
+    ///
+    /// Returns the same result as [`WorkCalendar::work_days_between`].
+    pub fn work_days_between(&self, start_date: NaiveDate, end_date: NaiveDate) -> i64 {
+        if start_date > end_date {
+            return 0;
+        }
+        let mut total = 0;
+        for year in start_date.year()..=end_date.year() {
+            let from = if year == start_date.year() {
+                start_date
+            } else {
+                NaiveDate::from_ymd_opt(year, 1, 1).unwrap()
+            };
+            let to = if year == end_date.year() {
+                end_date
+            } else {
+                NaiveDate::from_ymd_opt(year, 12, 31).unwrap()
+            };
+            total += match self.year_bits(year) {
+                Some(bits) => i64::from(bits.count(from.ordinal0(), to.ordinal0())),
+                None => self.calendar.work_days_between(from, to),
+            };
+        }
+        total
+    }
+
+    /// Computes the end date and calendar span given a start date and number of work days.
+    ///
+    /// Returns the same result as [`WorkCalendar::compute_end`].
+    pub fn compute_end(
+        &self,
+        start_date: NaiveDate,
+        days_worked: i64,
+    ) -> Result<EndDate, WorkCalendarError> {
+        if days_worked <= 0
+            || !self.calendar.has_any_work_days()
+            || self.year_bits(start_date.year()).is_none()
+        {
+            return self.calendar.compute_end(start_date, days_worked);
+        }
+
+        let horizon_error = |last_work_date: NaiveDate| {
+            let searched_until = last_work_date + chrono::Duration::days(SEARCH_HORIZON_DAYS);
+            Err(WorkCalendarError::NoWorkingDaysInHorizon {
+                calendar: self.calendar.name().map(String::from),
+                searched_until,
+            })
+        };
+
+        let mut remaining = days_worked;
+        let mut from = start_date;
+        let mut last_work_date = start_date;
+        loop {
+            let bits = match self.year_bits(from.year()) {
+                Some(bits) => bits,
+                // Past the cached years, answer the whole query with the calendar.
+                // Resuming it from `from` would restart the search horizon there and
+                // miss a run of non-working dates that began inside the cache.
+                None => return self.calendar.compute_end(start_date, days_worked),
+            };
+
+            let year_end = NaiveDate::from_ymd_opt(from.year(), 12, 31).unwrap();
+            let available = i64::from(bits.count(from.ordinal0(), year_end.ordinal0()));
+            if available > 0 {
+                let first = bits.nth_from(from.ordinal0(), 1).unwrap();
+                let first = from.with_ordinal0(first).unwrap();
+                if (first - last_work_date).num_days() > SEARCH_HORIZON_DAYS {
+                    return horizon_error(last_work_date);
+                }
+            }
+            if available >= remaining {
+                let index = bits.nth_from(from.ordinal0(), remaining as u32).unwrap();
+                let date = from.with_ordinal0(index).unwrap();
+                return Ok(EndDate {
+                    date,
+                    calendar_days: (date - start_date).num_days() as u64,
+                });
+            }
+            remaining -= available;
+            if available > 0 {
+                last_work_date = from.with_ordinal0(bits.last().unwrap()).unwrap();
+            }
+            from = match year_end.succ_opt() {
+                Some(next) => next,
+                None => {
+                    return Err(WorkCalendarError::DateOutOfRange {
+                        calendar: self.calendar.name().map(String::from),
+                    })
+                }
+            };
+            if (from - last_work_date).num_days() > SEARCH_HORIZON_DAYS {
+                return horizon_error(last_work_date);
+            }
+        }
+    }
+}
+
+impl CalendarLike for YearCache {
+    fn name(&self) -> Option<&str> {
+        self.calendar.name()
+    }
+
+    fn is_working_date(&self, date: &NaiveDate) -> bool {
+        YearCache::is_working_date(self, date)
+    }
+
+    fn has_work_days(&self) -> bool {
+        self.calendar.has_any_work_days()
+    }
+
+    fn work_days_between(&self, start_date: NaiveDate, end_date: NaiveDate) -> i64 {
+        YearCache::work_days_between(self, start_date, end_date)
+    }
+
+    fn compute_end(
+        &self,
+        start_date: NaiveDate,
+        days_worked: i64,
+    ) -> Result<EndDate, WorkCalendarError> {
+        YearCache::compute_end(self, start_date, days_worked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn sample_calendar() -> WorkCalendar {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_work_days("Mon,Tue,Thu,Fri,Sat").unwrap();
+        for (y, m, d) in [(2023, 12, 25), (2024, 2, 29), (2024, 12, 31), (2025, 1, 1)] {
+            calendar.add_holiday(NaiveDate::from_ymd_opt(y, m, d).unwrap());
+        }
+        calendar
+    }
+
+    #[test]
+    fn test_range_mask() {
+        assert_eq!(range_mask(0, 0, 63), u64::MAX);
+        assert_eq!(range_mask(0, 1, 2), 0b110);
+        assert_eq!(range_mask(1, 0, 63), 0);
+        assert_eq!(range_mask(1, 64, 64), 1);
+        assert_eq!(range_mask(4, 300, u32::MAX), u64::MAX << 44);
+        assert_eq!(range_mask(5, 300, u32::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_is_working_date_matches_calendar() {
+        let calendar = sample_calendar();
+        let cache = YearCache::build(&calendar, 2024..=2025);
+        assert_eq!(cache.years(), 2024..=2025);
+        let mut date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+        while date.year() < 2028 {
+            assert_eq!(
+                cache.is_working_date(&date),
+                calendar.is_working_date(&date)
+            );
+            date += Duration::days(1);
+        }
+    }
+
+    #[test]
+    fn test_range_queries_match_calendar() {
+        let calendar = sample_calendar();
+        let cache = YearCache::build(&calendar, 2024..=2025);
+        let base = NaiveDate::from_ymd_opt(2023, 10, 1).unwrap();
+        for start_offset in (0..1000).step_by(37) {
+            let start_date = base + Duration::days(start_offset);
+            for length in (0..900).step_by(53) {
+                let end_date = start_date + Duration::days(length);
+                assert_eq!(
+                    cache.work_days_between(start_date, end_date),
+                    calendar.work_days_between(start_date, end_date)
+                );
+            }
+            for days_worked in (0..700).step_by(29) {
+                assert_eq!(
+                    cache.compute_end(start_date, days_worked),
+                    calendar.compute_end(start_date, days_worked)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_cache_is_a_snapshot() {
+        let mut calendar = sample_calendar();
+        let cache = YearCache::build(&calendar, 2024..=2024);
+        let date = NaiveDate::from_ymd_opt(2024, 7, 4).unwrap();
+        calendar.add_holiday(date);
+        assert!(cache.is_working_date(&date));
+        assert!(!YearCache::build(&calendar, 2024..=2024).is_working_date(&date));
+    }
+
+    #[test]
+    fn test_compute_end_horizon() {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_work_days("Mon").unwrap();
+        let start_date = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap();
+        let mut holiday = start_date;
+        while holiday.year() < 2036 {
+            calendar.add_holiday(holiday);
+            holiday += Duration::days(7);
+        }
+        let cache = YearCache::build(&calendar, 2023..=2040);
+        assert_eq!(
+            cache.compute_end(start_date, 1),
+            calendar.compute_end(start_date, 1)
+        );
+    }
+
+    #[test]
+    fn test_compute_end_horizon_across_cache_edge() {
+        // A Monday, followed by holiday blocks starting inside the cached years and
+        // ending after them, just within and just beyond the search horizon.
+        let start_date = NaiveDate::from_ymd_opt(2024, 5, 27).unwrap();
+        for weeks in [520, 522, 523, 540] {
+            let mut calendar = WorkCalendar::new();
+            calendar.set_work_days("Mon").unwrap();
+            for week in 1..=weeks {
+                calendar.add_holiday(start_date + Duration::weeks(week));
+            }
+            let cache = YearCache::build(&calendar, 2023..=2025);
+            for days_worked in 1..=3 {
+                assert_eq!(
+                    cache.compute_end(start_date, days_worked),
+                    calendar.compute_end(start_date, days_worked)
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! The query trait shared by every calendar type.
+
+use crate::{EndDate, WorkCalendarError, SEARCH_HORIZON_DAYS};
+use chrono::{Datelike, Days, Duration, NaiveDate, Weekday};
+
+/// Read-only queries shared by every calendar type in this crate.
+///
+/// Implementors only need to say which dates are working dates; the arithmetic
+/// methods have default implementations that walk the calendar one day at a time,
+/// which the crate's own types override with faster equivalents. Functions that
+/// accept `&impl CalendarLike` therefore work with [`WorkCalendar`](crate::WorkCalendar),
+/// [`FrozenCalendar`](crate::FrozenCalendar), [`CompactCalendar`](crate::CompactCalendar),
+/// [`YearCache`](crate::YearCache), and downstream types alike.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::{CalendarLike, WorkCalendar};
+/// use chrono::NaiveDate;
+///
+/// fn first_week_load(calendar: &impl CalendarLike, start: NaiveDate) -> i64 {
+///     calendar.work_days_between(start, start + chrono::Duration::days(6))
+/// }
+///
+/// let calendar = WorkCalendar::new();
+/// let start = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap();
+/// assert_eq!(first_week_load(&calendar, start), 5);
+/// assert_eq!(first_week_load(&calendar.freeze(), start), 5);
+/// ```
+pub trait CalendarLike {
+    /// Returns the calendar's name, if it has one.
+    fn name(&self) -> Option<&str> {
+        None
+    }
+
+    /// Checks if a given date is a working date.
+    fn is_working_date(&self, date: &NaiveDate) -> bool;
+
+    /// Returns `true` if at least one weekday is a work day.
+    ///
+    /// This is a cheap check used to reject calendars that can never yield a working
+    /// date before searching for one.
+    fn has_work_days(&self) -> bool;
+
+    /// Calculates the number of work days between two dates (inclusive).
+    fn work_days_between(&self, start_date: NaiveDate, end_date: NaiveDate) -> i64 {
+        let mut work_days = 0;
+        let mut current_date = start_date;
+        while current_date <= end_date {
+            if self.is_working_date(&current_date) {
+                work_days += 1;
+            }
+            current_date = match current_date.succ_opt() {
+                Some(next) => next,
+                None => break,
+            };
+        }
+        work_days
+    }
+
+    /// Computes the end date and calendar span given a start date and number of work days.
+    ///
+    /// See [`WorkCalendar::compute_end`](crate::WorkCalendar::compute_end) for the
+    /// semantics and errors.
+    fn compute_end(
+        &self,
+        start_date: NaiveDate,
+        days_worked: i64,
+    ) -> Result<EndDate, WorkCalendarError> {
+        compute_end_by_weeks(
+            self,
+            |date| self.is_working_date(date),
+            |_| true,
+            0,
+            |_| None,
+            start_date,
+            days_worked,
+        )
+    }
+
+    /// Computes the end date and calendar duration given a start date and number of work days.
+    ///
+    /// See [`WorkCalendar::compute_end_date`](crate::WorkCalendar::compute_end_date).
+    fn compute_end_date(
+        &self,
+        start_date: NaiveDate,
+        days_worked: i64,
+    ) -> Result<(NaiveDate, Duration), WorkCalendarError> {
+        let end = self.compute_end(start_date, days_worked)?;
+        Ok((end.date, Duration::days(end.calendar_days as i64)))
+    }
+}
+
+/// Shared implementation of `compute_end`.
+///
+/// Walks forward from `start_date` one day at a time, counting the dates for which
+/// `is_working_date` holds, except that when `days_per_week`
+/// is non-zero it skips whole weeks that contain no holiday on a work day, since such
+/// weeks hold exactly `days_per_week` working dates. `next_holiday_on_work_day` returns
+/// the first holiday after the given date whose weekday is a work day.
+pub(crate) fn compute_end_by_weeks<C: CalendarLike + ?Sized>(
+    calendar: &C,
+    is_working_date: impl Fn(&NaiveDate) -> bool,
+    is_work_day: impl Fn(Weekday) -> bool,
+    days_per_week: i64,
+    next_holiday_on_work_day: impl Fn(NaiveDate) -> Option<NaiveDate>,
+    start_date: NaiveDate,
+    days_worked: i64,
+) -> Result<EndDate, WorkCalendarError> {
+    let name = || calendar.name().map(String::from);
+
+    if days_worked < 0 {
+        return Err(WorkCalendarError::NegativeDaysWorked { calendar: name() });
+    }
+
+    if !calendar.has_work_days() {
+        return Err(WorkCalendarError::NoWorkDaysDefined { calendar: name() });
+    }
+
+    let mut current_date = start_date;
+    let mut remaining_days = days_worked;
+    let mut last_work_date = start_date;
+    let mut calendar_days = 0;
+
+    // If the start date is a work day, count it
+    if is_working_date(&current_date) {
+        remaining_days -= 1;
+    } else if days_worked == 0 {
+        return Err(WorkCalendarError::NonWorkingStart {
+            calendar: name(),
+            date: start_date,
+        });
+    }
+
+    while remaining_days > 0 {
+        // Skip whole weeks while at least one work day remains for the final partial
+        // week and no holiday on a work day falls inside the skipped span.
+        let mut weeks = if days_per_week > 0 {
+            (remaining_days - 1) / days_per_week
+        } else {
+            0
+        };
+        if weeks > 0 {
+            if let Some(holiday) = next_holiday_on_work_day(current_date) {
+                weeks = weeks.min(((holiday - current_date).num_days() - 1) / 7);
+            }
+        }
+        if weeks > 0 {
+            if let Some(next) = current_date.checked_add_days(Days::new(7 * weeks as u64)) {
+                current_date = next;
+                calendar_days += 7 * weeks as u64;
+                remaining_days -= weeks * days_per_week;
+                last_work_date = (0..7)
+                    .map(|back| current_date - Duration::days(back))
+                    .find(|date| is_work_day(date.weekday()))
+                    .unwrap_or(current_date);
+                continue;
+            }
+        }
+
+        current_date = current_date
+            .checked_add_days(Days::new(1))
+            .ok_or_else(|| WorkCalendarError::DateOutOfRange { calendar: name() })?;
+        calendar_days += 1;
+
+        if is_working_date(&current_date) {
+            remaining_days -= 1;
+            last_work_date = current_date;
+        } else if (current_date - last_work_date).num_days() >= SEARCH_HORIZON_DAYS {
+            return Err(WorkCalendarError::NoWorkingDaysInHorizon {
+                calendar: name(),
+                searched_until: current_date,
+            });
+        }
+    }
+
+    Ok(EndDate {
+        date: current_date,
+        calendar_days,
+    })
+}
@@ -0,0 +1,366 @@
+//! Combined queries over several calendars, such as one per country.
+
+use crate::{CalendarLike, WorkCalendarError, SEARCH_HORIZON_DAYS};
+use chrono::NaiveDate;
+use std::fmt;
+
+/// A set of borrowed calendars answering questions about all of them at once.
+///
+/// A date is a common working date if it is a working date on every calendar in the
+/// set. An empty set has no working dates. The set implements [`CalendarLike`] with
+/// the common working dates, so [`CalendarLike::compute_end`] counts days on which
+/// every calendar works.
+///
+/// This makes a set a zero-copy alternative to [`WorkCalendar::merge`]: a date that is
+/// non-working in any calendar is non-working in the set, which gives the same
+/// working dates as merging the calendars into one, without copying their holidays.
+/// Each query consults the calendars on demand and allocates nothing, so building a
+/// set of two large calendars for a single question costs one small `Vec`.
+///
+/// [`WorkCalendar::merge`]: crate::WorkCalendar::merge
+///
+/// # Examples
+///
+/// ```
+/// use workdays::{CalendarLike, CalendarSet, WorkCalendar};
+/// use chrono::NaiveDate;
+///
+/// let mut us = WorkCalendar::new();
+/// us.add_holiday(NaiveDate::from_ymd_opt(2025, 7, 4).unwrap()); // Friday
+/// let mut gb = WorkCalendar::new();
+/// gb.add_holiday(NaiveDate::from_ymd_opt(2025, 7, 7).unwrap()); // Monday
+///
+/// let set = CalendarSet::from(vec![&us, &gb]);
+/// let thursday = NaiveDate::from_ymd_opt(2025, 7, 3).unwrap();
+/// assert_eq!(
+///     set.next_common_work_day(thursday).unwrap(),
+///     NaiveDate::from_ymd_opt(2025, 7, 8).unwrap()
+/// );
+/// assert!(set.any_working(&NaiveDate::from_ymd_opt(2025, 7, 4).unwrap()));
+///
+/// // The set answers like a merged calendar, which copies both holiday lists.
+/// let mut merged = us.clone();
+/// merged.merge(&gb);
+/// let end = set.compute_end(thursday, 3).unwrap();
+/// assert_eq!(end, merged.compute_end(thursday, 3).unwrap());
+/// ```
+#[derive(Clone, Default)]
+pub struct CalendarSet<'a> {
+    calendars: Vec<&'a dyn CalendarLike>,
+}
+
+impl<'a> CalendarSet<'a> {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        CalendarSet::default()
+    }
+
+    /// Adds a calendar to the set.
+    pub fn push(&mut self, calendar: &'a dyn CalendarLike) {
+        self.calendars.push(calendar);
+    }
+
+    /// Returns the calendars in the order they were added.
+    pub fn calendars(&self) -> &[&'a dyn CalendarLike] {
+        &self.calendars
+    }
+
+    /// Returns the number of calendars in the set.
+    pub fn len(&self) -> usize {
+        self.calendars.len()
+    }
+
+    /// Returns `true` if the set has no calendars.
+    pub fn is_empty(&self) -> bool {
+        self.calendars.is_empty()
+    }
+
+    /// Checks if `date` is a working date on every calendar in the set.
+    ///
+    /// Returns `false` for an empty set.
+    pub fn all_working(&self, date: &NaiveDate) -> bool {
+        !self.calendars.is_empty()
+            && self
+                .calendars
+                .iter()
+                .all(|calendar| calendar.is_working_date(date))
+    }
+
+    /// Checks if `date` is a working date on at least one calendar in the set.
+    pub fn any_working(&self, date: &NaiveDate) -> bool {
+        self.calendars
+            .iter()
+            .any(|calendar| calendar.is_working_date(date))
+    }
+
+    /// Returns the first date after `from` that is a working date on every calendar.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The date to search after.
+    ///
+    /// # Errors
+    ///
+    /// * [`WorkCalendarError::NoWorkDaysDefined`] if the set is empty or a calendar in
+    ///   it has no work days.
+    /// * [`WorkCalendarError::NoWorkingDaysInHorizon`] if [`SEARCH_HORIZON_DAYS`]
+    ///   consecutive days pass without a common working date, as with calendars whose
+    ///   work weeks do not overlap.
+    /// * [`WorkCalendarError::DateOutOfRange`] if the result cannot be represented.
+    pub fn next_common_work_day(&self, from: NaiveDate) -> Result<NaiveDate, WorkCalendarError> {
+        if !self.has_work_days() {
+            return Err(WorkCalendarError::NoWorkDaysDefined { calendar: None });
+        }
+        let mut date = from;
+        loop {
+            date = date
+                .succ_opt()
+                .ok_or(WorkCalendarError::DateOutOfRange { calendar: None })?;
+            if self.all_working(&date) {
+                return Ok(date);
+            }
+            if (date - from).num_days() >= SEARCH_HORIZON_DAYS {
+                return Err(WorkCalendarError::NoWorkingDaysInHorizon {
+                    calendar: None,
+                    searched_until: date,
+                });
+            }
+        }
+    }
+
+    /// Counts the dates from `start` to `end`, inclusive, that are working dates on
+    /// every calendar.
+    ///
+    /// Returns zero if `start` is after `end` or the set is empty.
+    pub fn common_work_days_between(&self, start: NaiveDate, end: NaiveDate) -> i64 {
+        // `NaiveDate::iter_days` stops before `NaiveDate::MAX`, so step by hand.
+        std::iter::successors(Some(start), |date| date.succ_opt())
+            .take_while(|date| *date <= end)
+            .filter(|date| self.all_working(date))
+            .count() as i64
+    }
+}
+
+impl<'a, C: CalendarLike> From<Vec<&'a C>> for CalendarSet<'a> {
+    fn from(calendars: Vec<&'a C>) -> Self {
+        calendars
+            .into_iter()
+            .map(|calendar| calendar as &dyn CalendarLike)
+            .collect()
+    }
+}
+
+impl<'a> FromIterator<&'a dyn CalendarLike> for CalendarSet<'a> {
+    fn from_iter<I: IntoIterator<Item = &'a dyn CalendarLike>>(calendars: I) -> Self {
+        CalendarSet {
+            calendars: calendars.into_iter().collect(),
+        }
+    }
+}
+
+impl fmt::Debug for CalendarSet<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CalendarSet")
+            .field(
+                "calendars",
+                &self
+                    .calendars
+                    .iter()
+                    .map(|calendar| calendar.name())
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl CalendarLike for CalendarSet<'_> {
+    fn is_working_date(&self, date: &NaiveDate) -> bool {
+        self.all_working(date)
+    }
+
+    fn has_work_days(&self) -> bool {
+        !self.calendars.is_empty()
+            && self
+                .calendars
+                .iter()
+                .all(|calendar| calendar.has_work_days())
+    }
+
+    fn work_days_between(&self, start_date: NaiveDate, end_date: NaiveDate) -> i64 {
+        self.common_work_days_between(start_date, end_date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{count_allocations, date, random_calendar, TestRng};
+    use crate::WorkCalendar;
+    use chrono::Duration;
+
+    #[test]
+    fn test_matches_manual_intersection() {
+        let mut rng = TestRng(0x93b4_0e6f_27ca_d158);
+        for _ in 0..50 {
+            let calendars: Vec<WorkCalendar> = (0..1 + rng.below(3))
+                .map(|_| random_calendar(&mut rng))
+                .collect();
+            let set = CalendarSet::from(calendars.iter().collect::<Vec<_>>());
+            let start = date(2022, 1, 1) + Duration::days(rng.below(1000) as i64);
+            let end = start + Duration::days(rng.below(120) as i64);
+
+            let mut expected = 0;
+            let mut date = start;
+            while date <= end {
+                let working: Vec<bool> = calendars
+                    .iter()
+                    .map(|calendar| calendar.is_working_date(&date))
+                    .collect();
+                assert_eq!(set.all_working(&date), working.iter().all(|&w| w));
+                assert_eq!(set.any_working(&date), working.iter().any(|&w| w));
+                expected += working.iter().all(|&w| w) as i64;
+                date += Duration::days(1);
+            }
+            assert_eq!(set.common_work_days_between(start, end), expected);
+
+            if let Ok(next) = set.next_common_work_day(start) {
+                assert!(next > start);
+                assert!(set.all_working(&next));
+                assert_eq!(
+                    set.common_work_days_between(start + Duration::days(1), next),
+                    1
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_disjoint_work_weeks() {
+        let mut a = WorkCalendar::new();
+        a.set_work_days("Mon,Tue").unwrap();
+        let mut b = WorkCalendar::new();
+        b.set_work_days("Wed,Thu").unwrap();
+        let mut c = WorkCalendar::new();
+        c.set_work_days("Fri,Sat,Sun").unwrap();
+        let frozen = c.freeze();
+
+        let mut set = CalendarSet::new();
+        set.push(&a);
+        set.push(&b);
+        set.push(&frozen);
+        assert_eq!(set.len(), 3);
+        let start = date(2025, 1, 1);
+        assert_eq!(
+            set.next_common_work_day(start),
+            Err(WorkCalendarError::NoWorkingDaysInHorizon {
+                calendar: None,
+                searched_until: start + Duration::days(SEARCH_HORIZON_DAYS)
+            })
+        );
+        assert!((0..7).all(|offset| set.any_working(&(start + Duration::days(offset)))));
+        assert_eq!(set.common_work_days_between(start, date(2025, 12, 31)), 0);
+    }
+
+    #[test]
+    fn test_empty_set_and_compute_end() {
+        let empty = CalendarSet::new();
+        assert!(empty.is_empty());
+        assert!(!empty.all_working(&date(2025, 1, 1)));
+        assert_eq!(
+            empty.next_common_work_day(date(2025, 1, 1)),
+            Err(WorkCalendarError::NoWorkDaysDefined { calendar: None })
+        );
+
+        let mut us = WorkCalendar::new();
+        us.add_holiday(date(2025, 7, 4));
+        let mut gb = WorkCalendar::new();
+        gb.add_holiday(date(2025, 7, 7));
+        let set = CalendarSet::from(vec![&us, &gb]);
+        // Thursday the 3rd, then Tuesday the 8th.
+        assert_eq!(
+            set.compute_end(date(2025, 7, 3), 2).unwrap().date,
+            date(2025, 7, 8)
+        );
+        assert_eq!(
+            set.next_common_work_day(date(2025, 7, 3)),
+            Ok(date(2025, 7, 8))
+        );
+    }
+
+    #[test]
+    fn test_matches_eager_merge_randomized() {
+        let mut rng = TestRng(0x47c2_9ad0_e5b1_836f);
+        for _ in 0..40 {
+            let calendars: Vec<WorkCalendar> = (0..2 + rng.below(2))
+                .map(|_| random_calendar(&mut rng))
+                .collect();
+            let mut merged = calendars[0].clone();
+            for calendar in &calendars[1..] {
+                merged.merge(calendar);
+            }
+            let set = CalendarSet::from(calendars.iter().collect::<Vec<_>>());
+
+            let start = date(2020, 1, 1) + Duration::days(rng.below(3000) as i64);
+            let end = start + Duration::days(rng.below(400) as i64);
+            for offset in 0..=(end - start).num_days() {
+                let date = start + Duration::days(offset);
+                assert_eq!(
+                    set.is_working_date(&date),
+                    merged.is_working_date(&date),
+                    "{}",
+                    date
+                );
+            }
+            assert_eq!(
+                set.work_days_between(start, end),
+                merged.work_days_between(start, end)
+            );
+            let days = rng.below(60) as i64;
+            let via_set = set.compute_end_date(start, days);
+            let via_merge = CalendarLike::compute_end_date(&merged, start, days);
+            // A merged calendar whose work weeks do not overlap reports no work days
+            // up front, even if working-date overrides remain, where the set still
+            // finds those dates.
+            match via_merge {
+                Ok(expected) => assert_eq!(via_set, Ok(expected), "{} + {}", start, days),
+                Err(WorkCalendarError::NoWorkDaysDefined { .. }) => {}
+                Err(_) => assert!(via_set.is_err(), "{} + {}", start, days),
+            }
+            if let Ok(next) = merged.next_work_day(start) {
+                assert_eq!(set.next_common_work_day(start), Ok(next));
+            }
+        }
+    }
+
+    #[test]
+    fn test_queries_do_not_allocate() {
+        let mut us = WorkCalendar::new();
+        let mut gb = WorkCalendar::new();
+        for offset in 0..20_000 {
+            let holiday = date(1990, 1, 1) + Duration::days(offset * 3);
+            if offset % 2 == 0 {
+                us.add_holiday(holiday);
+            } else {
+                gb.add_holiday(holiday);
+            }
+        }
+        let set = CalendarSet::from(vec![&us, &gb]);
+        let (start, end) = (date(2023, 1, 1), date(2023, 12, 31));
+        let expected = (
+            set.work_days_between(start, end),
+            set.compute_end(start, 100),
+        );
+
+        let (allocations, results) = count_allocations(|| {
+            let on_holiday = set.is_working_date(&date(1990, 1, 1));
+            let in_range = set.work_days_between(start, end);
+            let end_date = set.compute_end(start, 100);
+            let next = set.next_common_work_day(start);
+            (on_holiday, in_range, end_date, next)
+        });
+        assert_eq!(allocations, 0);
+        assert!(!results.0);
+        assert_eq!((results.1, results.2), expected);
+        assert!(results.3.is_ok());
+    }
+}
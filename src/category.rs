@@ -0,0 +1,539 @@
+//! Holiday categories and computations restricted to some of them.
+
+use crate::{EndDate, HolidayRule, WorkCalendar, WorkCalendarError};
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+
+/// Selects which categories of holidays a computation observes.
+///
+/// Holidays added without a category are observed by every filter; only categorized
+/// holidays and recurring rules are ever filtered out. The default observes every
+/// category, which is how the unfiltered computations behave.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::CategoryFilter;
+///
+/// let settlement = CategoryFilter::all();
+/// let staffing = CategoryFilter::exclude(&["optional"]);
+/// assert!(settlement.includes(Some("optional")));
+/// assert!(!staffing.includes(Some("optional")));
+/// assert!(staffing.includes(Some("public")));
+/// assert!(CategoryFilter::only(&["public"]).includes(None));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum CategoryFilter {
+    /// Observe holidays of every category.
+    #[default]
+    All,
+    /// Observe only holidays in these categories.
+    Only(BTreeSet<String>),
+    /// Observe holidays in every category but these.
+    Exclude(BTreeSet<String>),
+}
+
+impl CategoryFilter {
+    /// Observes holidays of every category.
+    pub fn all() -> Self {
+        CategoryFilter::All
+    }
+
+    /// Observes only holidays in `categories`, plus uncategorized ones.
+    pub fn only(categories: &[&str]) -> Self {
+        CategoryFilter::Only(categories.iter().map(|c| c.to_string()).collect())
+    }
+
+    /// Observes holidays in every category except `categories`.
+    pub fn exclude(categories: &[&str]) -> Self {
+        CategoryFilter::Exclude(categories.iter().map(|c| c.to_string()).collect())
+    }
+
+    /// Checks if a holiday with `category` is observed. Uncategorized holidays
+    /// (`None`) always are.
+    pub fn includes(&self, category: Option<&str>) -> bool {
+        match (self, category) {
+            (CategoryFilter::All, _) | (_, None) => true,
+            (CategoryFilter::Only(categories), Some(category)) => categories.contains(category),
+            (CategoryFilter::Exclude(categories), Some(category)) => !categories.contains(category),
+        }
+    }
+}
+
+/// The categories of a calendar's holidays and recurring rules. Holidays and rules
+/// without an entry are uncategorized.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct HolidayCategories {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    dates: BTreeMap<NaiveDate, Arc<str>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    rules: Vec<RuleCategory>,
+}
+
+/// A recurring rule's category. Rules cannot be map keys in JSON, so these are kept in
+/// a list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct RuleCategory {
+    rule: HolidayRule,
+    category: Arc<str>,
+}
+
+impl HolidayCategories {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.dates.is_empty() && self.rules.is_empty()
+    }
+
+    /// Returns the categorized holiday dates in ascending order.
+    pub(crate) fn dates(&self) -> impl Iterator<Item = (&NaiveDate, &str)> {
+        self.dates
+            .iter()
+            .map(|(date, category)| (date, &**category))
+    }
+
+    /// Returns the categorized rules in the order they were categorized.
+    pub(crate) fn rules(&self) -> impl Iterator<Item = (&HolidayRule, &str)> {
+        self.rules
+            .iter()
+            .map(|entry| (&entry.rule, &*entry.category))
+    }
+
+    fn rule(&self, rule: &HolidayRule) -> Option<&str> {
+        self.rules
+            .iter()
+            .find(|entry| entry.rule == *rule)
+            .map(|entry| &*entry.category)
+    }
+
+    fn set_rule(&mut self, rule: HolidayRule, category: Option<Arc<str>>) {
+        self.rules.retain(|entry| entry.rule != rule);
+        if let Some(category) = category {
+            self.rules.push(RuleCategory { rule, category });
+        }
+    }
+}
+
+/// Clears the category of a holiday date, if it has one.
+pub(crate) fn clear_date(categories: &mut Arc<HolidayCategories>, date: &NaiveDate) {
+    if categories.dates.contains_key(date) {
+        Arc::make_mut(categories).dates.remove(date);
+    }
+}
+
+/// Drops the categories of holiday dates for which `keep` returns `false`.
+pub(crate) fn retain_dates(
+    categories: &mut Arc<HolidayCategories>,
+    keep: impl Fn(&NaiveDate) -> bool,
+) {
+    if categories.dates.keys().any(|date| !keep(date)) {
+        Arc::make_mut(categories).dates.retain(|date, _| keep(date));
+    }
+}
+
+/// Clears the category of a recurring rule, if it has one.
+pub(crate) fn clear_rule(categories: &mut Arc<HolidayCategories>, rule: &HolidayRule) {
+    if categories.rule(rule).is_some() {
+        Arc::make_mut(categories).set_rule(*rule, None);
+    }
+}
+
+impl WorkCalendar {
+    /// Adds a holiday in a category, such as `"public"` or `"optional"`.
+    ///
+    /// The holiday counts in every unfiltered computation, exactly as if it had been
+    /// added with [`WorkCalendar::add_holiday`]; the filtered variants such as
+    /// [`WorkCalendar::work_days_between_filtered`] can leave it out. Adding a date
+    /// again replaces its category, and [`WorkCalendar::add_holiday`] makes it
+    /// uncategorized.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The holiday.
+    /// * `category` - The holiday's category.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{CategoryFilter, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// let bank_holiday = NaiveDate::from_ymd_opt(2025, 8, 25).unwrap();
+    /// calendar.add_holiday_with_category(bank_holiday, "optional");
+    ///
+    /// let staffing = CategoryFilter::exclude(&["optional"]);
+    /// assert!(!calendar.is_working_date(&bank_holiday));
+    /// assert!(calendar.is_working_date_filtered(&bank_holiday, &staffing));
+    /// assert_eq!(calendar.holiday_category(&bank_holiday), Some("optional"));
+    /// ```
+    pub fn add_holiday_with_category(&mut self, date: NaiveDate, category: &str) {
+        self.add_holiday(date);
+        Arc::make_mut(&mut self.holiday_categories)
+            .dates
+            .insert(date, Arc::from(category));
+    }
+
+    /// Adds a recurring holiday rule in a category.
+    ///
+    /// Like [`WorkCalendar::add_holiday_with_category`], the rule counts in every
+    /// unfiltered computation. Adding the rule again replaces its category, and
+    /// [`WorkCalendar::add_recurring_holiday`] makes it uncategorized.
+    ///
+    /// # Arguments
+    ///
+    /// * `rule` - The rule to add.
+    /// * `category` - The rule's category.
+    pub fn add_recurring_holiday_with_category(&mut self, rule: HolidayRule, category: &str) {
+        self.add_recurring_holiday(rule);
+        Arc::make_mut(&mut self.holiday_categories).set_rule(rule, Some(Arc::from(category)));
+    }
+
+    /// Returns the category of the holiday on `date`, or `None` if the date is not a
+    /// holiday added with a category.
+    pub fn holiday_category(&self, date: &NaiveDate) -> Option<&str> {
+        self.holiday_categories.dates.get(date).map(|c| &**c)
+    }
+
+    /// Returns the category of a recurring rule, or `None` if it has none.
+    pub fn recurring_holiday_category(&self, rule: &HolidayRule) -> Option<&str> {
+        self.holiday_categories.rule(rule)
+    }
+
+    /// Like [`WorkCalendar::is_holiday`], observing only the holidays `filter` accepts.
+    pub fn is_holiday_filtered(&self, date: &NaiveDate, filter: &CategoryFilter) -> bool {
+        if *filter == CategoryFilter::All || !self.is_holiday(date) {
+            return self.is_holiday(date);
+        }
+        let year = date.year();
+        (self.holidays.contains(date) && filter.includes(self.holiday_category(date)))
+            || self
+                .holiday_layers()
+                .iter()
+                .any(|layer| layer.date_in(year) == Some(*date))
+            || self.recurring_holidays().iter().any(|rule| {
+                rule.date_in(year) == Some(*date)
+                    && filter.includes(self.recurring_holiday_category(rule))
+            })
+    }
+
+    /// Like [`WorkCalendar::is_working_date`], observing only the holidays `filter`
+    /// accepts.
+    pub fn is_working_date_filtered(&self, date: &NaiveDate, filter: &CategoryFilter) -> bool {
+        self.is_working_date_with(date, &|date| self.is_holiday_filtered(date, filter))
+    }
+
+    /// Like [`WorkCalendar::work_days_between`], observing only the holidays `filter`
+    /// accepts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{CategoryFilter, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// calendar.add_holiday_with_category(NaiveDate::from_ymd_opt(2025, 12, 25).unwrap(), "public");
+    /// calendar.add_holiday_with_category(NaiveDate::from_ymd_opt(2025, 12, 26).unwrap(), "optional");
+    ///
+    /// let start = NaiveDate::from_ymd_opt(2025, 12, 22).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2025, 12, 26).unwrap();
+    /// assert_eq!(calendar.work_days_between(start, end), 3);
+    /// assert_eq!(
+    ///     calendar.work_days_between_filtered(start, end, &CategoryFilter::exclude(&["optional"])),
+    ///     4
+    /// );
+    /// ```
+    pub fn work_days_between_filtered(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        filter: &CategoryFilter,
+    ) -> i64 {
+        self.work_days_between_with(start_date, end_date, &|date| {
+            self.is_holiday_filtered(date, filter)
+        })
+    }
+
+    /// Like [`WorkCalendar::compute_end`], observing only the holidays `filter`
+    /// accepts.
+    ///
+    /// # Errors
+    ///
+    /// The errors of [`WorkCalendar::compute_end`].
+    pub fn compute_end_filtered(
+        &self,
+        start_date: NaiveDate,
+        days_worked: i64,
+        filter: &CategoryFilter,
+    ) -> Result<EndDate, WorkCalendarError> {
+        self.compute_end_with(start_date, days_worked, &|date| {
+            self.is_holiday_filtered(date, filter)
+        })
+    }
+
+    /// Returns this calendar without the holidays and rules `filter` rejects, or the
+    /// calendar itself if it rejects none.
+    pub(crate) fn with_filter(&self, filter: &CategoryFilter) -> Cow<'_, WorkCalendar> {
+        let categories = &self.holiday_categories;
+        let rejected_dates: Vec<NaiveDate> = categories
+            .dates
+            .iter()
+            .filter(|(_, category)| !filter.includes(Some(category)))
+            .map(|(date, _)| *date)
+            .collect();
+        let rejected_rules: Vec<HolidayRule> = categories
+            .rules
+            .iter()
+            .filter(|entry| !filter.includes(Some(&entry.category)))
+            .map(|entry| entry.rule)
+            .collect();
+        if rejected_dates.is_empty() && rejected_rules.is_empty() {
+            return Cow::Borrowed(self);
+        }
+        let mut filtered = self.clone();
+        for date in &rejected_dates {
+            filtered.remove_holiday(date);
+        }
+        for rule in &rejected_rules {
+            filtered.remove_recurring_holiday(rule);
+        }
+        Cow::Owned(filtered)
+    }
+
+    /// Returns the categories the calendar should have after merging `other` into it.
+    ///
+    /// A holiday or rule that either calendar has uncategorized stays uncategorized,
+    /// since that calendar observes it under every filter. Otherwise this calendar's
+    /// category wins.
+    pub(crate) fn merged_categories(&self, other: &WorkCalendar) -> HolidayCategories {
+        let uncategorized_date = |calendar: &WorkCalendar, date: &NaiveDate| {
+            calendar.holidays.contains(date) && calendar.holiday_category(date).is_none()
+        };
+        let uncategorized_rule = |calendar: &WorkCalendar, rule: &HolidayRule| {
+            calendar.recurring_holidays().contains(rule)
+                && calendar.recurring_holiday_category(rule).is_none()
+        };
+        let mut merged = HolidayCategories::default();
+        for (date, category) in self
+            .holiday_categories
+            .dates
+            .iter()
+            .chain(&other.holiday_categories.dates)
+        {
+            if !uncategorized_date(self, date) && !uncategorized_date(other, date) {
+                merged
+                    .dates
+                    .entry(*date)
+                    .or_insert_with(|| category.clone());
+            }
+        }
+        for entry in self
+            .holiday_categories
+            .rules
+            .iter()
+            .chain(&other.holiday_categories.rules)
+        {
+            let rule = &entry.rule;
+            if !uncategorized_rule(self, rule)
+                && !uncategorized_rule(other, rule)
+                && merged.rule(rule).is_none()
+            {
+                merged.rules.push(entry.clone());
+            }
+        }
+        merged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{date, random_calendar, random_rule, TestRng};
+    use chrono::Duration;
+
+    fn bank() -> WorkCalendar {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("Bank");
+        // Monday, December 22 through Friday, December 26, 2025.
+        calendar.add_holiday(date(2025, 12, 22));
+        calendar.add_holiday_with_category(date(2025, 12, 25), "public");
+        calendar.add_holiday_with_category(date(2025, 12, 26), "optional");
+        calendar.add_recurring_holiday_with_category(
+            HolidayRule::FixedDate { month: 12, day: 24 },
+            "optional",
+        );
+        calendar
+    }
+
+    #[test]
+    fn test_filtered_computations() {
+        let calendar = bank();
+        let start = date(2025, 12, 22);
+        let end = date(2025, 12, 26);
+        let staffing = CategoryFilter::exclude(&["optional"]);
+        let settlement = CategoryFilter::only(&["public"]);
+        let optional_only = CategoryFilter::only(&["optional"]);
+
+        assert_eq!(calendar.work_days_between(start, end), 1);
+        assert_eq!(
+            calendar.work_days_between_filtered(start, end, &CategoryFilter::all()),
+            1
+        );
+        assert_eq!(
+            calendar.work_days_between_filtered(start, end, &staffing),
+            3
+        );
+        assert_eq!(
+            calendar.work_days_between_filtered(start, end, &settlement),
+            3
+        );
+        assert_eq!(
+            calendar.work_days_between_filtered(start, end, &optional_only),
+            2
+        );
+
+        assert!(calendar.is_holiday_filtered(&date(2025, 12, 22), &staffing));
+        assert!(calendar.is_holiday_filtered(&date(2025, 12, 25), &staffing));
+        assert!(!calendar.is_holiday_filtered(&date(2025, 12, 24), &staffing));
+        assert!(calendar.is_working_date_filtered(&date(2025, 12, 26), &staffing));
+        assert!(!calendar.is_working_date(&date(2025, 12, 26)));
+
+        assert_eq!(
+            calendar.compute_end(start, 2).unwrap().date,
+            date(2025, 12, 29)
+        );
+        assert_eq!(
+            calendar
+                .compute_end_filtered(start, 2, &staffing)
+                .unwrap()
+                .date,
+            date(2025, 12, 24)
+        );
+        assert_eq!(
+            calendar.compute_end_filtered(start, -1, &staffing),
+            Err(WorkCalendarError::NegativeDaysWorked {
+                calendar: Some("Bank".to_string())
+            })
+        );
+    }
+
+    #[test]
+    fn test_categories_follow_mutations() {
+        let mut calendar = bank();
+        let christmas = date(2025, 12, 25);
+        assert_eq!(calendar.holiday_category(&christmas), Some("public"));
+        calendar.add_holiday_with_category(christmas, "optional");
+        assert_eq!(calendar.holiday_category(&christmas), Some("optional"));
+        calendar.add_holiday(christmas);
+        assert_eq!(calendar.holiday_category(&christmas), None);
+        calendar.add_holiday_with_category(christmas, "public");
+        calendar.remove_holiday(&christmas);
+        assert_eq!(calendar.holiday_category(&christmas), None);
+        assert_eq!(calendar.holiday_category(&date(2025, 12, 22)), None);
+
+        let eve = HolidayRule::FixedDate { month: 12, day: 24 };
+        assert_eq!(calendar.recurring_holiday_category(&eve), Some("optional"));
+        calendar.add_recurring_holiday(eve);
+        assert_eq!(calendar.recurring_holiday_category(&eve), None);
+        calendar.add_recurring_holiday_with_category(eve, "public");
+        calendar.remove_recurring_holiday(&eve);
+        assert_eq!(calendar.recurring_holiday_category(&eve), None);
+        assert!(calendar.holiday_categories.rules.is_empty());
+    }
+
+    #[test]
+    fn test_categories_round_trip() {
+        let calendar = bank();
+        let json = serde_json::to_string(&calendar).unwrap();
+        let yaml = serde_yaml::to_string(&calendar).unwrap();
+        assert_eq!(
+            serde_json::from_str::<WorkCalendar>(&json).unwrap(),
+            calendar
+        );
+        assert_eq!(
+            serde_yaml::from_str::<WorkCalendar>(&yaml).unwrap(),
+            calendar
+        );
+        assert_eq!(calendar.compact().expand(), calendar);
+        assert_eq!(calendar.clone().freeze().thaw(), calendar);
+
+        let plain = serde_json::to_string(&WorkCalendar::new()).unwrap();
+        assert!(!plain.contains("holiday_categories"));
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut a = bank();
+        let mut b = WorkCalendar::new();
+        // Uncategorized in `b`, so observed by every filter after the merge.
+        b.add_holiday(date(2025, 12, 26));
+        b.add_holiday_with_category(date(2025, 12, 25), "optional");
+        b.add_holiday_with_category(date(2025, 12, 31), "optional");
+        a.merge(&b);
+        assert_eq!(a.holiday_category(&date(2025, 12, 26)), None);
+        assert_eq!(a.holiday_category(&date(2025, 12, 25)), Some("public"));
+        assert_eq!(a.holiday_category(&date(2025, 12, 31)), Some("optional"));
+        assert_eq!(
+            a.recurring_holiday_category(&HolidayRule::FixedDate { month: 12, day: 24 }),
+            Some("optional")
+        );
+    }
+
+    #[test]
+    fn test_all_filter_matches_unfiltered() {
+        let mut rng = TestRng(0x5d1f_83a2_c07e_4b19);
+        let base = date(2020, 1, 1);
+        for _ in 0..50 {
+            let mut calendar = random_calendar(&mut rng);
+            for _ in 0..rng.below(20) {
+                let category = ["public", "optional", "bank"][rng.below(3) as usize];
+                calendar.add_holiday_with_category(
+                    base + Duration::days(rng.below(3000) as i64),
+                    category,
+                );
+            }
+            for _ in 0..rng.below(3) {
+                let category = ["public", "optional"][rng.below(2) as usize];
+                calendar.add_recurring_holiday_with_category(random_rule(&mut rng), category);
+            }
+            let start = base + Duration::days(rng.below(3000) as i64);
+            let end = start + Duration::days(rng.below(400) as i64);
+            let all = CategoryFilter::all();
+            assert_eq!(
+                calendar.work_days_between_filtered(start, end, &all),
+                calendar.work_days_between(start, end)
+            );
+            // Excluding a category matches removing its holidays and rules by hand.
+            let filter = CategoryFilter::exclude(&["optional"]);
+            let mut expected = calendar.clone();
+            let optional: Vec<NaiveDate> = calendar
+                .holiday_categories
+                .dates
+                .iter()
+                .filter(|(_, category)| &***category == "optional")
+                .map(|(date, _)| *date)
+                .collect();
+            for date in &optional {
+                expected.remove_holiday(date);
+            }
+            for entry in &calendar.holiday_categories.rules {
+                if &*entry.category == "optional" {
+                    expected.remove_recurring_holiday(&entry.rule);
+                }
+            }
+            assert_eq!(
+                calendar.work_days_between_filtered(start, end, &filter),
+                expected.work_days_between(start, end)
+            );
+            let days = rng.below(200) as i64;
+            assert_eq!(
+                calendar.compute_end_filtered(start, days, &filter),
+                expected.compute_end(start, days)
+            );
+        }
+    }
+}
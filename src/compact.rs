@@ -0,0 +1,318 @@
+//! A memory-compact, read-only form of `WorkCalendar`.
+
+use crate::{
+    calendar_like::compute_end_by_weeks, category::HolidayCategories, count_work_weekdays,
+    half_day::PartialHolidays, hours::HoursPerDay, rules::RuleSet, week::WeekChanges, CalendarLike,
+    EndDate, WorkCalendar, WorkCalendarError,
+};
+use chrono::{Datelike, NaiveDate, Weekday};
+use std::{
+    collections::HashSet,
+    ops::{Bound, RangeBounds},
+    sync::Arc,
+};
+
+/// A read-only calendar storing holidays in a sorted `Vec`.
+///
+/// A `CompactCalendar` uses a few bytes per holiday instead of a tree node, which
+/// matters when millions of holidays are held in memory. Membership is a binary
+/// search and range queries are slices of the sorted array. Create one with
+/// [`WorkCalendar::compact`] and convert back with [`CompactCalendar::expand`] to edit.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::WorkCalendar;
+/// use chrono::NaiveDate;
+///
+/// let mut calendar = WorkCalendar::new();
+/// calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 8, 23).unwrap());
+///
+/// let compact = calendar.compact();
+/// let start = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap();
+/// let end = NaiveDate::from_ymd_opt(2023, 8, 25).unwrap();
+/// assert_eq!(compact.work_days_between(start, end), 4);
+/// assert_eq!(compact.expand(), calendar);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactCalendar {
+    name: Option<String>,
+    work_days: HashSet<Weekday>,
+    work_days_effective: Arc<WeekChanges>,
+    holidays: Box<[NaiveDate]>,
+    working_dates: Box<[NaiveDate]>,
+    partial_holidays: Arc<PartialHolidays>,
+    recurring_holidays: RuleSet,
+    holiday_categories: Arc<HolidayCategories>,
+    ordinal_epoch: NaiveDate,
+    hours_per_day: HoursPerDay,
+}
+
+impl CompactCalendar {
+    /// Returns the calendar's name, if one has been set.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Checks if a given day is a work day.
+    ///
+    /// Like [`WorkCalendar::is_work_day`], this ignores effective-dated work weeks.
+    pub fn is_work_day(&self, day: &Weekday) -> bool {
+        self.work_days.contains(day)
+    }
+
+    /// Checks if `date`'s weekday is a work day in the work week in effect on it.
+    fn is_work_day_on(&self, date: &NaiveDate) -> bool {
+        match self.work_days_effective.range(..=*date).next_back() {
+            Some((_, week)) => week.contains(date.weekday()),
+            None => self.is_work_day(&date.weekday()),
+        }
+    }
+
+    /// Checks if a given date is a holiday.
+    pub fn is_holiday(&self, date: &NaiveDate) -> bool {
+        self.holidays.binary_search(date).is_ok() || self.recurring_holidays.contains(date)
+    }
+
+    /// Returns the number of holidays, not counting recurring holidays.
+    pub fn holiday_count(&self) -> usize {
+        self.holidays.len()
+    }
+
+    /// Returns the holidays falling within `range`, in ascending order.
+    pub fn holidays_in_range<R: RangeBounds<NaiveDate>>(&self, range: R) -> &[NaiveDate] {
+        sorted_range(&self.holidays, range)
+    }
+
+    /// Checks if a given date has a working-date override.
+    pub fn is_working_date_override(&self, date: &NaiveDate) -> bool {
+        self.working_dates.binary_search(date).is_ok()
+    }
+
+    /// Calculates the number of work days between two dates (inclusive).
+    ///
+    /// Returns the same result as [`WorkCalendar::work_days_between`].
+    pub fn work_days_between(&self, start_date: NaiveDate, end_date: NaiveDate) -> i64 {
+        if !self.work_days_effective.is_empty() {
+            return std::iter::successors(Some(start_date), |date| date.succ_opt())
+                .take_while(|date| *date <= end_date)
+                .filter(|date| self.is_working_date(date))
+                .count() as i64;
+        }
+        let holidays_on_work_days = self
+            .holidays_in_range(start_date..=end_date)
+            .iter()
+            .filter(|date| self.is_work_day(&date.weekday()))
+            .count() as i64;
+        let overrides_on_other_days = sorted_range(&self.working_dates, start_date..=end_date)
+            .iter()
+            .filter(|date| !self.is_work_day(&date.weekday()) && !self.is_holiday(date))
+            .count() as i64;
+        let rule_holidays_on_work_days =
+            self.recurring_holidays
+                .count_in_range(start_date, end_date, |date| {
+                    self.is_work_day(&date.weekday()) && self.holidays.binary_search(date).is_err()
+                });
+        count_work_weekdays(
+            |day| self.is_work_day(&day),
+            self.work_days.len() as i64,
+            start_date,
+            end_date,
+        ) - holidays_on_work_days
+            - rule_holidays_on_work_days
+            + overrides_on_other_days
+    }
+
+    /// Checks if a given date is a working date.
+    ///
+    /// Returns the same result as [`WorkCalendar::is_working_date`].
+    pub fn is_working_date(&self, date: &NaiveDate) -> bool {
+        (self.is_work_day_on(date) || self.is_working_date_override(date)) && !self.is_holiday(date)
+    }
+
+    /// Computes the end date and calendar span given a start date and number of work days.
+    ///
+    /// Returns the same result as [`WorkCalendar::compute_end`].
+    pub fn compute_end(
+        &self,
+        start_date: NaiveDate,
+        days_worked: i64,
+    ) -> Result<EndDate, WorkCalendarError> {
+        let days_per_week = if self.work_days_effective.is_empty() {
+            self.work_days.len() as i64
+        } else {
+            0
+        };
+        compute_end_by_weeks(
+            self,
+            |date| self.is_working_date(date),
+            |day| self.is_work_day(&day),
+            days_per_week,
+            |date| {
+                let after = (Bound::Excluded(date), Bound::Unbounded);
+                let holiday = self
+                    .holidays_in_range(after)
+                    .iter()
+                    .find(|holiday| self.is_work_day(&holiday.weekday()));
+                let working = sorted_range(&self.working_dates, after)
+                    .iter()
+                    .find(|working| !self.is_work_day(&working.weekday()));
+                let rule = self
+                    .recurring_holidays
+                    .next_after(date, |holiday| self.is_work_day(&holiday.weekday()));
+                [holiday.copied(), working.copied(), rule]
+                    .into_iter()
+                    .flatten()
+                    .min()
+            },
+            start_date,
+            days_worked,
+        )
+    }
+
+    /// Converts back into an editable `WorkCalendar`.
+    pub fn expand(&self) -> WorkCalendar {
+        WorkCalendar {
+            name: self.name.as_deref().map(Arc::from),
+            work_days: self.work_days.iter().copied().collect(),
+            work_days_effective: self.work_days_effective.clone(),
+            holidays: Arc::new(self.holidays.iter().copied().collect()),
+            working_dates: Arc::new(self.working_dates.iter().copied().collect()),
+            partial_holidays: self.partial_holidays.clone(),
+            recurring_holidays: self.recurring_holidays.clone(),
+            holiday_categories: self.holiday_categories.clone(),
+            ordinal_epoch: self.ordinal_epoch,
+            hours_per_day: self.hours_per_day,
+            month_memo: Default::default(),
+        }
+    }
+}
+
+/// Returns the elements of a sorted slice falling within `range`.
+fn sorted_range<R: RangeBounds<NaiveDate>>(dates: &[NaiveDate], range: R) -> &[NaiveDate] {
+    let start = match range.start_bound() {
+        Bound::Included(date) => dates.partition_point(|d| d < date),
+        Bound::Excluded(date) => dates.partition_point(|d| d <= date),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(date) => dates.partition_point(|d| d <= date),
+        Bound::Excluded(date) => dates.partition_point(|d| d < date),
+        Bound::Unbounded => dates.len(),
+    };
+    if start < end {
+        &dates[start..end]
+    } else {
+        &[]
+    }
+}
+
+impl CalendarLike for CompactCalendar {
+    fn name(&self) -> Option<&str> {
+        CompactCalendar::name(self)
+    }
+
+    fn is_working_date(&self, date: &NaiveDate) -> bool {
+        CompactCalendar::is_working_date(self, date)
+    }
+
+    fn has_work_days(&self) -> bool {
+        !self.work_days.is_empty()
+            || self
+                .work_days_effective
+                .values()
+                .any(|week| !week.is_empty())
+    }
+
+    fn work_days_between(&self, start_date: NaiveDate, end_date: NaiveDate) -> i64 {
+        CompactCalendar::work_days_between(self, start_date, end_date)
+    }
+
+    fn compute_end(
+        &self,
+        start_date: NaiveDate,
+        days_worked: i64,
+    ) -> Result<EndDate, WorkCalendarError> {
+        CompactCalendar::compute_end(self, start_date, days_worked)
+    }
+}
+
+impl From<&WorkCalendar> for CompactCalendar {
+    fn from(calendar: &WorkCalendar) -> Self {
+        // BTreeSet iteration is already sorted and free of duplicates.
+        CompactCalendar {
+            name: calendar.name.as_deref().map(String::from),
+            work_days: calendar.work_days.as_set().clone(),
+            work_days_effective: calendar.work_days_effective.clone(),
+            holidays: calendar.holidays.iter().copied().collect(),
+            working_dates: calendar.working_dates.iter().copied().collect(),
+            partial_holidays: calendar.partial_holidays.clone(),
+            recurring_holidays: calendar.recurring_holidays.clone(),
+            holiday_categories: calendar.holiday_categories.clone(),
+            ordinal_epoch: calendar.ordinal_epoch,
+            hours_per_day: calendar.hours_per_day,
+        }
+    }
+}
+
+impl From<CompactCalendar> for WorkCalendar {
+    fn from(compact: CompactCalendar) -> Self {
+        compact.expand()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_compact_round_trip_large() {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_work_days("Mon,Tue,Wed,Thu,Sat").unwrap();
+        let base = NaiveDate::from_ymd_opt(1200, 1, 1).unwrap();
+        // Roughly 300k holidays: two of every three days across ~1,200 years.
+        for offset in (0..450_000).filter(|n| n % 3 != 0) {
+            calendar.add_holiday(base + Duration::days(offset));
+        }
+
+        let compact = calendar.compact();
+        assert_eq!(compact.holiday_count(), 300_000);
+        assert_eq!(compact.expand(), calendar);
+
+        for offset in (0..460_000).step_by(997) {
+            let date = base + Duration::days(offset);
+            assert_eq!(compact.is_holiday(&date), calendar.is_holiday(&date));
+            let end = date + Duration::days(offset % 5000);
+            assert_eq!(
+                compact.work_days_between(date, end),
+                calendar.work_days_between(date, end)
+            );
+            assert_eq!(
+                compact.holidays_in_range(date..end),
+                calendar.holidays_in_range(date..end).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_compact_range_bounds() {
+        let mut calendar = WorkCalendar::new();
+        let dates: Vec<NaiveDate> = (1..=5)
+            .map(|d| NaiveDate::from_ymd_opt(2023, 1, d * 2).unwrap())
+            .collect();
+        for date in &dates {
+            calendar.add_holiday(*date);
+        }
+        let compact = calendar.compact();
+
+        assert_eq!(compact.holidays_in_range(..), &dates[..]);
+        assert_eq!(compact.holidays_in_range(dates[1]..dates[3]), &dates[1..3]);
+        assert_eq!(compact.holidays_in_range(dates[1]..=dates[3]), &dates[1..4]);
+        assert_eq!(
+            compact.holidays_in_range((Bound::Excluded(dates[1]), Bound::Unbounded)),
+            &dates[2..]
+        );
+        assert!(compact.holidays_in_range(dates[3]..dates[1]).is_empty());
+    }
+}
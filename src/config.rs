@@ -0,0 +1,3191 @@
+//! The YAML/JSON configuration format read by `WorkCalendar::from_str`, and
+//! [`WorkCalendarConfig`] for building and writing it.
+//!
+//! Weekday names and holiday dates are parsed as they are deserialized, straight from
+//! the input where the format allows borrowing, so no intermediate list of strings is
+//! built. Only weekday names and dates that fail to parse are copied, since they are
+//! needed for the error message, and to write them back unchanged.
+
+use crate::{
+    hours::HoursPerDay,
+    parse_weekday, parse_weekday_item,
+    week::{WeekChanges, WorkWeek},
+    DayHalf, Holiday, HolidayLayer, HolidayRule, Locale, WorkCalendar, WorkCalendarError,
+    DEFAULT_ORDINAL_EPOCH,
+};
+use chrono::{NaiveDate, Weekday};
+use serde::{
+    de::{
+        self,
+        value::{EnumAccessDeserializer, MapAccessDeserializer},
+        DeserializeSeed, EnumAccess, MapAccess, SeqAccess, Unexpected, Visitor,
+    },
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{
+    cell::Cell,
+    collections::{BTreeMap, BTreeSet, HashSet},
+    fmt, fs,
+    path::Path,
+    str::FromStr,
+    sync::Arc,
+};
+
+/// A calendar configuration in the format read by [`WorkCalendar::from_str`].
+///
+/// A configuration can be parsed from YAML or JSON, built up with the `with_*`
+/// methods, or taken from an existing calendar, and written back out with
+/// [`WorkCalendarConfig::to_yaml`] or [`WorkCalendarConfig::to_json`]. Converting it
+/// into a [`WorkCalendar`] with [`TryFrom`] applies the same checks as `from_str`.
+///
+/// The writers emit the canonical keyed form: keys in a fixed order, weekdays by full
+/// name from Monday to Sunday, and dates as sorted `YYYY-MM-DD` strings, with runs of
+/// three or more consecutive holidays or extra work days as `start..end`. Keys that
+/// were never set are left out, so they keep their defaults when read back. Written
+/// text always parses back to an equal configuration, so `from_str` accepts it
+/// exactly when the conversion accepts the configuration itself. The conversion
+/// rejects only an empty work week, a rule that can never produce a date, and a
+/// holiday layer whose window ends before it starts.
+///
+/// # Examples
+///
+/// ```
+/// use std::str::FromStr;
+/// use workdays::{WorkCalendar, WorkCalendarConfig};
+/// use chrono::{NaiveDate, Weekday};
+///
+/// let config = WorkCalendarConfig::new()
+///     .with_name("Platform team")
+///     .with_work_days([Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu])
+///     .with_holidays([NaiveDate::from_ymd_opt(2025, 12, 25).unwrap()]);
+///
+/// let yaml = config.to_yaml();
+/// assert_eq!(
+///     yaml,
+///     "name: Platform team\n\
+///      work_days:\n\
+///      - Monday\n\
+///      - Tuesday\n\
+///      - Wednesday\n\
+///      - Thursday\n\
+///      holidays:\n\
+///      - 2025-12-25\n"
+/// );
+///
+/// let calendar = WorkCalendar::from_str(&yaml).unwrap();
+/// assert_eq!(calendar, WorkCalendar::try_from(config).unwrap());
+/// assert!(!calendar.is_work_day(&Weekday::Fri));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkCalendarConfig {
+    name: Option<String>,
+    work_days: Option<WeekdayList>,
+    work_days_effective: Option<BTreeMap<NaiveDate, WeekdayList>>,
+    holidays: Option<HolidayList>,
+    extra_work_days: Option<HolidayList>,
+    /// Whether `extra_work_days` was read under its `working_dates` alias, which
+    /// warnings then name.
+    working_dates_alias: bool,
+    partial_holidays: Option<PartialHolidayList>,
+    recurring_holidays: Option<RuleList>,
+    holiday_layers: Option<LayerList>,
+    holiday_categories: Option<CategoryList>,
+    ordinal_epoch: Option<NaiveDate>,
+    hours_per_day: Option<HoursPerDay>,
+}
+
+impl WorkCalendarConfig {
+    /// Creates an empty configuration, which describes the default calendar: Monday to
+    /// Friday with no holidays.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the calendar's name.
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Sets the work days, replacing any set before.
+    ///
+    /// Repeated days count once. An empty list is written out, but the calendar it
+    /// describes is rejected, as by [`WorkCalendar::from_str`].
+    pub fn with_work_days(mut self, days: impl IntoIterator<Item = Weekday>) -> Self {
+        self.work_days = Some(WeekdayList::from_days(days));
+        self
+    }
+
+    /// Sets the work days in effect from `from`, replacing any set for that date
+    /// before; see [`WorkCalendar::set_work_days_effective`].
+    pub fn with_work_days_effective(
+        mut self,
+        from: NaiveDate,
+        days: impl IntoIterator<Item = Weekday>,
+    ) -> Self {
+        self.work_days_effective
+            .get_or_insert_with(BTreeMap::new)
+            .insert(from, WeekdayList::from_days(days));
+        self
+    }
+
+    /// Adds holidays.
+    pub fn with_holidays(mut self, dates: impl IntoIterator<Item = NaiveDate>) -> Self {
+        self.holidays
+            .get_or_insert_with(HolidayList::default)
+            .dates
+            .extend(dates);
+        self
+    }
+
+    /// Adds dates that are working dates whatever their weekday; see
+    /// [`WorkCalendar::add_working_date`].
+    pub fn with_extra_work_days(mut self, dates: impl IntoIterator<Item = NaiveDate>) -> Self {
+        self.extra_work_days
+            .get_or_insert_with(HolidayList::default)
+            .dates
+            .extend(dates);
+        self
+    }
+
+    /// Adds a half-day holiday with `half` off, replacing any set for that date before;
+    /// see [`WorkCalendar::add_partial_holiday_half`].
+    pub fn with_partial_holiday(mut self, date: NaiveDate, half: DayHalf) -> Self {
+        self.partial_holidays
+            .get_or_insert_with(PartialHolidayList::default)
+            .holidays
+            .insert(date, half);
+        self
+    }
+
+    /// Adds recurring holiday rules; see [`WorkCalendar::add_recurring_holiday`].
+    ///
+    /// Rules already added are skipped. A rule that can never produce a date, such as
+    /// one for month 13, is written out, but the calendar it describes is rejected.
+    pub fn with_recurring_holidays(mut self, rules: impl IntoIterator<Item = HolidayRule>) -> Self {
+        let list = &mut self
+            .recurring_holidays
+            .get_or_insert_with(RuleList::default)
+            .0;
+        for rule in rules {
+            if !list.contains(&rule) {
+                list.push(rule);
+            }
+        }
+        self
+    }
+
+    /// Adds holiday layers; see [`WorkCalendar::add_holiday_effective`].
+    ///
+    /// Layers already added are skipped. A layer whose window ends before it starts,
+    /// or whose rule can never produce a date, is written out, but the calendar it
+    /// describes is rejected.
+    pub fn with_holiday_layers(mut self, layers: impl IntoIterator<Item = HolidayLayer>) -> Self {
+        let list = &mut self.holiday_layers.get_or_insert_with(LayerList::default).0;
+        for layer in layers {
+            if !list.contains(&layer) {
+                list.push(layer);
+            }
+        }
+        self
+    }
+
+    /// Adds a holiday in a category, replacing any category set for that date before;
+    /// see [`WorkCalendar::add_holiday_with_category`].
+    pub fn with_holiday_category(mut self, date: NaiveDate, category: &str) -> Self {
+        self.holiday_categories
+            .get_or_insert_with(CategoryList::default)
+            .dates
+            .insert(date, category.to_string());
+        self
+    }
+
+    /// Adds a recurring holiday rule in a category, replacing any category set for that
+    /// rule before; see [`WorkCalendar::add_recurring_holiday_with_category`].
+    pub fn with_recurring_holiday_category(mut self, rule: HolidayRule, category: &str) -> Self {
+        let rules = &mut self
+            .holiday_categories
+            .get_or_insert_with(CategoryList::default)
+            .rules;
+        rules.retain(|(r, _)| *r != rule);
+        rules.push((rule, category.to_string()));
+        self
+    }
+
+    /// Sets the date work-day ordinals count from; see
+    /// [`WorkCalendar::set_ordinal_epoch`].
+    pub fn with_ordinal_epoch(mut self, epoch: NaiveDate) -> Self {
+        self.ordinal_epoch = Some(epoch);
+        self
+    }
+
+    /// Sets the working hours in a full working day; see
+    /// [`WorkCalendar::set_hours_per_day`].
+    pub fn with_hours_per_day(mut self, hours: f64) -> Self {
+        self.hours_per_day = Some(HoursPerDay(hours));
+        self
+    }
+
+    /// Writes the configuration as YAML in the canonical form.
+    pub fn to_yaml(&self) -> String {
+        serde_yaml::to_string(self).expect("a configuration always serializes")
+    }
+
+    /// Writes the configuration as pretty-printed JSON in the canonical form.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("a configuration always serializes")
+    }
+}
+
+/// An entry of a configuration skipped by [`WorkCalendar::from_str_lossy`]: one that
+/// is not a weekday name or a date, or an extra work day that is also a holiday.
+///
+/// [`WorkCalendar::from_str`] rejects the same entry: one that is not a date under
+/// `holidays` or `extra_work_days` with a [`WorkCalendarError::ConfigSyntax`] giving
+/// its position, and any other with a [`WorkCalendarError::InvalidConfig`] whose
+/// message is this warning's text. Converting a [`WorkCalendarConfig`] with
+/// [`TryFrom`] reports every such entry as an `InvalidConfig`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigWarning {
+    /// The key the entry was listed under, as written: `work_days`,
+    /// `work_days_effective`, `holidays`, `extra_work_days` or its alias
+    /// `working_dates`, or `partial_holidays`.
+    pub key: &'static str,
+    /// For `work_days_effective`, the date from which the listed work week applies.
+    pub effective_from: Option<NaiveDate>,
+    /// The entry as written.
+    pub entry: String,
+    /// Why the entry was skipped.
+    pub kind: ConfigWarningKind,
+}
+
+/// Why [`WorkCalendar::from_str_lossy`] skipped a configuration entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigWarningKind {
+    /// The entry is not a weekday name or a date.
+    Invalid,
+    /// The `extra_work_days` date is also listed under `holidays`, which takes
+    /// precedence.
+    AlsoHoliday,
+}
+
+impl fmt::Display for ConfigWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.kind == ConfigWarningKind::AlsoHoliday {
+            return write!(f, "{:?} in {} is also in holidays", self.entry, self.key);
+        }
+        let expected = if self.key.starts_with("work_days") {
+            "weekday"
+        } else {
+            "date"
+        };
+        write!(f, "{:?} is not a {} in {}", self.entry, expected, self.key)?;
+        if let Some(from) = self.effective_from {
+            write!(f, " for {}", from)?;
+        }
+        Ok(())
+    }
+}
+
+impl WorkCalendarConfig {
+    /// Returns the entries that are not weekday names or dates, and the extra work
+    /// days that are also holidays, by key in the order the keys are written and then
+    /// in input order.
+    fn skipped_entries(&self) -> Vec<ConfigWarning> {
+        let warning = |key, effective_from, entry: &String| ConfigWarning {
+            key,
+            effective_from,
+            entry: entry.clone(),
+            kind: ConfigWarningKind::Invalid,
+        };
+        let extra_key = if self.working_dates_alias {
+            "working_dates"
+        } else {
+            "extra_work_days"
+        };
+        let holidays = self.holidays.as_ref().map(|list| &list.dates);
+        let also_holidays = self
+            .extra_work_days
+            .iter()
+            .flat_map(|list| &list.dates)
+            .filter(|date| holidays.map_or(false, |holidays| holidays.contains(date)))
+            .map(|date| ConfigWarning {
+                key: extra_key,
+                effective_from: None,
+                entry: date.to_string(),
+                kind: ConfigWarningKind::AlsoHoliday,
+            });
+        let dates = |key, list: &Option<HolidayList>| {
+            list.iter()
+                .flat_map(|list| &list.invalid)
+                .map(move |entry| warning(key, None, entry))
+                .collect::<Vec<_>>()
+        };
+        let work_days = self.work_days.iter().flat_map(|list| &list.invalid);
+        let effective = self
+            .work_days_effective
+            .iter()
+            .flatten()
+            .flat_map(|(from, list)| list.invalid.iter().map(move |name| (*from, name)));
+        let partial = self.partial_holidays.iter().flat_map(|list| &list.invalid);
+        work_days
+            .map(|name| warning("work_days", None, name))
+            .chain(effective.map(|(from, name)| warning("work_days_effective", Some(from), name)))
+            .chain(dates("holidays", &self.holidays))
+            .chain(dates(extra_key, &self.extra_work_days))
+            .chain(also_holidays)
+            .chain(partial.map(|(date, _)| warning("partial_holidays", None, date)))
+            .collect()
+    }
+}
+
+/// Parses a configuration in any form [`WorkCalendar::from_str`] accepts, without
+/// checking that it describes a usable calendar.
+impl FromStr for WorkCalendarConfig {
+    type Err = WorkCalendarError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_document(s, true)
+    }
+}
+
+/// Takes the parts of a calendar the configuration format can hold.
+///
+/// Holiday layers and categories have no place in the format and are left out, as are
+/// recurring holidays that can never produce a date and extra work days that are also
+/// holidays; everything else converts back to an equal calendar. Lists that are empty
+/// and an
+/// ordinal epoch that is the default are not set.
+impl From<&WorkCalendar> for WorkCalendarConfig {
+    fn from(calendar: &WorkCalendar) -> Self {
+        let non_empty = |dates: &BTreeSet<NaiveDate>| {
+            (!dates.is_empty()).then(|| HolidayList::from_dates(dates.clone()))
+        };
+        WorkCalendarConfig {
+            name: calendar.name().map(String::from),
+            work_days: Some(WeekdayList::from_days(calendar.work_days.sorted())),
+            work_days_effective: (!calendar.work_days_effective.is_empty()).then(|| {
+                calendar
+                    .work_days_effective
+                    .iter()
+                    .map(|(from, week)| (*from, WeekdayList::from_days(week.sorted())))
+                    .collect()
+            }),
+            holidays: non_empty(&calendar.holidays),
+            extra_work_days: non_empty(
+                &calendar
+                    .working_dates
+                    .difference(&calendar.holidays)
+                    .copied()
+                    .collect(),
+            ),
+            working_dates_alias: false,
+            partial_holidays: (!calendar.partial_holidays.is_empty()).then(|| PartialHolidayList {
+                holidays: (*calendar.partial_holidays).clone(),
+                invalid: Vec::new(),
+            }),
+            recurring_holidays: Some(RuleList(
+                calendar
+                    .recurring_holidays()
+                    .iter()
+                    .filter(|rule| invalid_rule_reason(rule).is_none())
+                    .copied()
+                    .collect(),
+            ))
+            .filter(|list| !list.0.is_empty()),
+            holiday_layers: Some(LayerList(
+                calendar
+                    .holiday_layers()
+                    .iter()
+                    .filter(|layer| match layer.holiday {
+                        Holiday::Rule(rule) => invalid_rule_reason(&rule).is_none(),
+                        Holiday::Date(_) => true,
+                    })
+                    .copied()
+                    .collect(),
+            ))
+            .filter(|list| !list.0.is_empty()),
+            holiday_categories: Some(CategoryList {
+                dates: calendar
+                    .holiday_categories
+                    .dates()
+                    .map(|(date, category)| (*date, category.to_string()))
+                    .collect(),
+                rules: calendar
+                    .holiday_categories
+                    .rules()
+                    .filter(|(rule, _)| invalid_rule_reason(rule).is_none())
+                    .map(|(rule, category)| (*rule, category.to_string()))
+                    .collect(),
+            })
+            .filter(|list| !list.dates.is_empty() || !list.rules.is_empty()),
+            ordinal_epoch: Some(calendar.ordinal_epoch)
+                .filter(|epoch| *epoch != DEFAULT_ORDINAL_EPOCH),
+            hours_per_day: Some(calendar.hours_per_day).filter(|hours| !hours.is_default()),
+        }
+    }
+}
+
+/// The written form of a [`WorkCalendarConfig`].
+#[derive(Serialize)]
+struct ConfigRepr<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    work_days: Option<Vec<&'a str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    work_days_effective: Option<BTreeMap<NaiveDate, Vec<&'a str>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    holidays: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extra_work_days: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    partial_holidays: Option<Vec<PartialHolidayRepr>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    recurring_holidays: Option<Vec<RuleRepr>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    holiday_layers: Option<Vec<LayerRepr>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    holiday_categories: Option<CategoriesRepr<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ordinal_epoch: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hours_per_day: Option<f64>,
+}
+
+/// A written `recurring_holidays` entry: `day` for a fixed date, or `weekday` and
+/// `nth` for an nth weekday.
+#[derive(Serialize)]
+struct RuleRepr {
+    month: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    day: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weekday: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nth: Option<i8>,
+}
+
+impl From<&HolidayRule> for RuleRepr {
+    fn from(rule: &HolidayRule) -> Self {
+        match *rule {
+            HolidayRule::FixedDate { month, day } => RuleRepr {
+                month,
+                day: Some(day),
+                weekday: None,
+                nth: None,
+            },
+            HolidayRule::NthWeekday {
+                month,
+                weekday,
+                nth,
+            } => RuleRepr {
+                month,
+                day: None,
+                weekday: Some(Locale::English.weekday_name(weekday)),
+                nth: Some(nth),
+            },
+        }
+    }
+}
+
+/// A written `holiday_layers` entry: `date` for a single date or `rule` for a
+/// recurring rule, with the window in which it applies.
+#[derive(Serialize)]
+struct LayerRepr {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule: Option<RuleRepr>,
+    effective_from: NaiveDate,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    effective_to: Option<NaiveDate>,
+}
+
+impl From<&HolidayLayer> for LayerRepr {
+    fn from(layer: &HolidayLayer) -> Self {
+        let (date, rule) = match &layer.holiday {
+            Holiday::Date(date) => (Some(*date), None),
+            Holiday::Rule(rule) => (None, Some(RuleRepr::from(rule))),
+        };
+        LayerRepr {
+            date,
+            rule,
+            effective_from: layer.effective_from,
+            effective_to: layer.effective_to,
+        }
+    }
+}
+
+/// The written `holiday_categories` map: categorized dates by date, and categorized
+/// rules in the order they were categorized.
+#[derive(Serialize)]
+struct CategoriesRepr<'a> {
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    dates: BTreeMap<NaiveDate, &'a str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    rules: Vec<RuleCategoryRepr<'a>>,
+}
+
+/// A written `holiday_categories.rules` entry.
+#[derive(Serialize)]
+struct RuleCategoryRepr<'a> {
+    rule: RuleRepr,
+    category: &'a str,
+}
+
+/// A written `partial_holidays` entry.
+#[derive(Serialize)]
+struct PartialHolidayRepr {
+    /// The date, or the entry as written if it is not one.
+    date: String,
+    half: DayHalf,
+}
+
+impl Serialize for WorkCalendarConfig {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ConfigRepr {
+            name: self.name.as_deref(),
+            work_days: self.work_days.as_ref().map(WeekdayList::names),
+            work_days_effective: self.work_days_effective.as_ref().map(|changes| {
+                changes
+                    .iter()
+                    .map(|(from, list)| (*from, list.names()))
+                    .collect()
+            }),
+            holidays: self.holidays.as_ref().map(HolidayList::entries),
+            extra_work_days: self.extra_work_days.as_ref().map(HolidayList::entries),
+            partial_holidays: self.partial_holidays.as_ref().map(|list| {
+                list.holidays
+                    .iter()
+                    .map(|(date, &half)| PartialHolidayRepr {
+                        date: date.to_string(),
+                        half,
+                    })
+                    .chain(list.invalid.iter().map(|(date, half)| PartialHolidayRepr {
+                        date: date.clone(),
+                        half: *half,
+                    }))
+                    .collect()
+            }),
+            recurring_holidays: self
+                .recurring_holidays
+                .as_ref()
+                .map(|list| list.0.iter().map(RuleRepr::from).collect()),
+            holiday_layers: self
+                .holiday_layers
+                .as_ref()
+                .map(|list| list.0.iter().map(LayerRepr::from).collect()),
+            holiday_categories: self.holiday_categories.as_ref().map(|list| CategoriesRepr {
+                dates: list
+                    .dates
+                    .iter()
+                    .map(|(date, category)| (*date, category.as_str()))
+                    .collect(),
+                rules: list
+                    .rules
+                    .iter()
+                    .map(|(rule, category)| RuleCategoryRepr {
+                        rule: RuleRepr::from(rule),
+                        category,
+                    })
+                    .collect(),
+            }),
+            ordinal_epoch: self.ordinal_epoch,
+            hours_per_day: self.hours_per_day.map(|hours| hours.0),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// The index and, if it was a string, the value of the first entry of a list of
+/// holiday dates that is not a date.
+type BadEntry = Cell<Option<(usize, Option<String>)>>;
+
+/// Parses a whole configuration document, treating input that starts with `{` as
+/// JSON and anything else as YAML.
+///
+/// The document is either the keyed form, or the shorthand of a bare list of holiday
+/// dates, which keeps the default work week. Syntax and type errors the parser can
+/// place become [`WorkCalendarError::ConfigSyntax`], with the offending entry when it
+/// is in a list of holiday dates; the rest become [`WorkCalendarError::ConfigParse`].
+/// Entries of the keyed `holidays` and `extra_work_days` lists that are not dates are
+/// such errors too, unless `lossy` is set, in which case they are kept for
+/// [`convert`] to report.
+pub(crate) fn parse_document(
+    input: &str,
+    lossy: bool,
+) -> Result<WorkCalendarConfig, WorkCalendarError> {
+    let bad_entry = BadEntry::default();
+    let seed = DocumentSeed {
+        bad_entry: &bad_entry,
+        lossy,
+    };
+    let result = if input.trim_start().starts_with('{') {
+        let mut deserializer = serde_json::Deserializer::from_str(input);
+        seed.deserialize(&mut deserializer)
+            .and_then(|config| deserializer.end().map(|()| config))
+            .map_err(WorkCalendarError::from)
+    } else {
+        seed.deserialize(serde_yaml::Deserializer::from_str(input))
+            .map_err(WorkCalendarError::from)
+    };
+    result.map_err(|mut error| {
+        if let WorkCalendarError::ConfigSyntax { index, value, .. } = &mut error {
+            if let Some((i, v)) = bad_entry.take() {
+                *index = Some(i);
+                *value = v;
+            }
+        }
+        error
+    })
+}
+
+/// Deserializes a whole configuration document, recording a bad entry of a list of
+/// holiday dates.
+struct DocumentSeed<'a> {
+    bad_entry: &'a BadEntry,
+    lossy: bool,
+}
+
+impl<'de> DeserializeSeed<'de> for DocumentSeed<'_> {
+    type Value = WorkCalendarConfig;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for DocumentSeed<'_> {
+    type Value = WorkCalendarConfig;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a map of calendar settings or a list of holiday dates")
+    }
+
+    // An empty YAML document.
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Ok(WorkCalendarConfig::default())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        let bad_entry = (!self.lossy).then_some(self.bad_entry);
+        ConfigVisitor { bad_entry }.visit_map(map)
+    }
+
+    // Unlike the keyed `holidays` list read lossily, the shorthand always rejects
+    // entries that are not dates, since a list of anything else is not a calendar.
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut dates = BTreeSet::new();
+        let mut index = 0;
+        while let Some(date) = seq.next_element_seed(StrictDate {
+            index,
+            bad_entry: self.bad_entry,
+        })? {
+            dates.insert(date);
+            index += 1;
+        }
+        Ok(WorkCalendarConfig {
+            holidays: Some(HolidayList::from_dates(dates)),
+            ..WorkCalendarConfig::default()
+        })
+    }
+}
+
+/// The keys of the keyed form. Unknown keys are ignored.
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "snake_case")]
+enum ConfigKey {
+    Name,
+    WorkDays,
+    WorkDaysEffective,
+    Holidays,
+    ExtraWorkDays,
+    WorkingDates,
+    PartialHolidays,
+    RecurringHolidays,
+    HolidayLayers,
+    HolidayCategories,
+    OrdinalEpoch,
+    HoursPerDay,
+    #[serde(other)]
+    Unknown,
+}
+
+impl ConfigKey {
+    /// Returns the key's name, under which `working_dates` counts as
+    /// `extra_work_days`, or `None` for an unknown key.
+    fn name(&self) -> Option<&'static str> {
+        Some(match self {
+            ConfigKey::Name => "name",
+            ConfigKey::WorkDays => "work_days",
+            ConfigKey::WorkDaysEffective => "work_days_effective",
+            ConfigKey::Holidays => "holidays",
+            ConfigKey::ExtraWorkDays | ConfigKey::WorkingDates => "extra_work_days",
+            ConfigKey::PartialHolidays => "partial_holidays",
+            ConfigKey::RecurringHolidays => "recurring_holidays",
+            ConfigKey::HolidayLayers => "holiday_layers",
+            ConfigKey::HolidayCategories => "holiday_categories",
+            ConfigKey::OrdinalEpoch => "ordinal_epoch",
+            ConfigKey::HoursPerDay => "hours_per_day",
+            ConfigKey::Unknown => return None,
+        })
+    }
+}
+
+/// Deserializes the keyed form. With `bad_entry`, an entry of `holidays` or
+/// `extra_work_days` that is not a date is an error, recorded there; without it, such
+/// entries are kept as written.
+struct ConfigVisitor<'a> {
+    bad_entry: Option<&'a BadEntry>,
+}
+
+impl<'de> Visitor<'de> for ConfigVisitor<'_> {
+    type Value = WorkCalendarConfig;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a map of calendar settings")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut config = WorkCalendarConfig::default();
+        let mut seen = Vec::new();
+        while let Some(key) = map.next_key::<ConfigKey>()? {
+            if let Some(name) = key.name() {
+                if seen.contains(&name) {
+                    return Err(de::Error::duplicate_field(name));
+                }
+                seen.push(name);
+            }
+            let dates = DateListSeed {
+                bad_entry: self.bad_entry,
+            };
+            match key {
+                ConfigKey::Name => config.name = map.next_value()?,
+                ConfigKey::WorkDays => config.work_days = map.next_value()?,
+                ConfigKey::WorkDaysEffective => config.work_days_effective = map.next_value()?,
+                ConfigKey::Holidays => config.holidays = map.next_value_seed(dates)?,
+                ConfigKey::ExtraWorkDays | ConfigKey::WorkingDates => {
+                    config.extra_work_days = map.next_value_seed(dates)?;
+                    config.working_dates_alias = matches!(key, ConfigKey::WorkingDates);
+                }
+                ConfigKey::PartialHolidays => config.partial_holidays = map.next_value()?,
+                ConfigKey::RecurringHolidays => config.recurring_holidays = map.next_value()?,
+                ConfigKey::HolidayLayers => config.holiday_layers = map.next_value()?,
+                ConfigKey::HolidayCategories => config.holiday_categories = map.next_value()?,
+                ConfigKey::OrdinalEpoch => config.ordinal_epoch = map.next_value()?,
+                ConfigKey::HoursPerDay => config.hours_per_day = map.next_value()?,
+                ConfigKey::Unknown => {
+                    map.next_value::<de::IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// Reads the keyed form, keeping entries that are not weekday names or dates for
+/// [`TryFrom`] to report.
+impl<'de> Deserialize<'de> for WorkCalendarConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(ConfigVisitor { bad_entry: None })
+    }
+}
+
+/// An entry of the shorthand holiday list, which must be a `YYYY-MM-DD` date.
+struct StrictDate<'a> {
+    index: usize,
+    bad_entry: &'a BadEntry,
+}
+
+impl<'de> DeserializeSeed<'de> for StrictDate<'_> {
+    type Value = NaiveDate;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let (index, bad_entry) = (self.index, self.bad_entry);
+        deserializer.deserialize_str(self).map_err(|error| {
+            // A string that is not a date has already recorded itself, with its value.
+            let recorded = bad_entry.take();
+            bad_entry.set(recorded.or(Some((index, None))));
+            error
+        })
+    }
+}
+
+impl<'de> Visitor<'de> for StrictDate<'_> {
+    type Value = NaiveDate;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a YYYY-MM-DD date")
+    }
+
+    fn visit_str<E: de::Error>(self, date: &str) -> Result<Self::Value, E> {
+        parse_date(date).ok_or_else(|| {
+            self.bad_entry
+                .set(Some((self.index, Some(date.to_string()))));
+            E::invalid_value(Unexpected::Str(date), &self)
+        })
+    }
+}
+
+/// The parsed `work_days` list.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct WeekdayList {
+    days: HashSet<Weekday>,
+    /// Entries that are not weekday names, in input order.
+    invalid: Vec<String>,
+    /// Whether the list had any entries, valid or not.
+    has_entries: bool,
+}
+
+impl WeekdayList {
+    fn from_days(days: impl IntoIterator<Item = Weekday>) -> Self {
+        let days: HashSet<Weekday> = days.into_iter().collect();
+        WeekdayList {
+            has_entries: !days.is_empty(),
+            days,
+            invalid: Vec::new(),
+        }
+    }
+
+    /// Returns the entries to write: the days by full name from Monday to Sunday, then
+    /// the entries that are not weekday names, unchanged.
+    fn names(&self) -> Vec<&str> {
+        let mut days: Vec<Weekday> = self.days.iter().copied().collect();
+        days.sort_by_key(Weekday::num_days_from_monday);
+        days.into_iter()
+            .map(|day| Locale::English.weekday_name(day))
+            .chain(self.invalid.iter().map(String::as_str))
+            .collect()
+    }
+}
+
+impl<'de> Deserialize<'de> for WeekdayList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ListVisitor;
+
+        impl<'de> Visitor<'de> for ListVisitor {
+            type Value = WeekdayList;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a list of weekday names")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut list = WeekdayList::default();
+                while let Some(WeekdayName(days)) = seq.next_element()? {
+                    list.has_entries = true;
+                    match days {
+                        Ok(days) => list.days.extend(days),
+                        Err(name) => list.invalid.push(name),
+                    }
+                }
+                Ok(list)
+            }
+        }
+
+        deserializer.deserialize_seq(ListVisitor)
+    }
+}
+
+/// A single `work_days` entry: the weekday or the days of a keyword such as
+/// `weekdays`, or the name if it is neither.
+struct WeekdayName(Result<&'static [Weekday], String>);
+
+impl<'de> Deserialize<'de> for WeekdayName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct NameVisitor;
+
+        impl<'de> Visitor<'de> for NameVisitor {
+            type Value = WeekdayName;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a weekday name or keyword")
+            }
+
+            fn visit_str<E>(self, name: &str) -> Result<Self::Value, E> {
+                Ok(WeekdayName(
+                    parse_weekday_item(name).ok_or_else(|| name.to_string()),
+                ))
+            }
+        }
+
+        deserializer.deserialize_str(NameVisitor)
+    }
+}
+
+/// A parsed `holidays` or `extra_work_days` list, with ranges expanded to their
+/// dates.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct HolidayList {
+    dates: BTreeSet<NaiveDate>,
+    /// Entries that are not dates or ranges of them, as written, in input order.
+    invalid: Vec<String>,
+}
+
+impl HolidayList {
+    fn from_dates(dates: BTreeSet<NaiveDate>) -> Self {
+        HolidayList {
+            dates,
+            invalid: Vec::new(),
+        }
+    }
+
+    /// Returns the written entries: each date, with runs of three or more
+    /// consecutive dates written as one `start..end` range, then the entries that are
+    /// not dates, unchanged.
+    fn entries(&self) -> Vec<String> {
+        let mut entries = Vec::new();
+        let mut dates = self.dates.iter().copied().peekable();
+        while let Some(start) = dates.next() {
+            let mut end = start;
+            while let Some(next) = dates.next_if(|&next| end.succ_opt() == Some(next)) {
+                end = next;
+            }
+            match (end - start).num_days() {
+                0 => entries.push(start.to_string()),
+                1 => entries.extend([start.to_string(), end.to_string()]),
+                _ => entries.push(format!("{}..{}", start, end)),
+            }
+        }
+        entries.extend(self.invalid.iter().cloned());
+        entries
+    }
+}
+
+/// Deserializes an optional `holidays` or `extra_work_days` list. With `bad_entry`,
+/// the first entry that is not a date or a range of dates is an error, recorded there.
+#[derive(Clone, Copy)]
+struct DateListSeed<'a> {
+    bad_entry: Option<&'a BadEntry>,
+}
+
+impl<'de> DeserializeSeed<'de> for DateListSeed<'_> {
+    type Value = Option<HolidayList>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_option(self)
+    }
+}
+
+impl<'de> Visitor<'de> for DateListSeed<'_> {
+    type Value = Option<HolidayList>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a list of dates")
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut list = HolidayList::default();
+        let mut index = 0;
+        while let Some(HolidayEntry(range)) = seq.next_element_seed(EntrySeed {
+            index,
+            bad_entry: self.bad_entry,
+        })? {
+            match range {
+                Ok((start, end)) => {
+                    let range = std::iter::successors(Some(start), |date| date.succ_opt());
+                    list.dates.extend(range.take_while(|&date| date <= end));
+                }
+                Err(entry) => list.invalid.push(entry),
+            }
+            index += 1;
+        }
+        Ok(Some(list))
+    }
+}
+
+/// An entry of a date list: a date, a `start..end` string, or a `{ start, end }` map,
+/// giving the inclusive range of dates it covers if its dates are valid, and the
+/// entry as written otherwise.
+struct HolidayEntry(Result<(NaiveDate, NaiveDate), String>);
+
+/// The fields of a `{ start, end }` holiday range.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RangeFields {
+    start: HolidayDate,
+    end: HolidayDate,
+}
+
+impl HolidayEntry {
+    /// Checks that a range whose dates are valid does not end before it starts.
+    fn new<E: de::Error>(
+        start: Option<NaiveDate>,
+        end: Option<NaiveDate>,
+        written: impl FnOnce() -> String,
+    ) -> Result<Self, E> {
+        match start.zip(end) {
+            Some((start, end)) if end < start => Err(E::custom(format_args!(
+                "holiday range {}..{} ends before it starts",
+                start, end
+            ))),
+            range => Ok(HolidayEntry(range.ok_or_else(written))),
+        }
+    }
+}
+
+/// Deserializes the entry of a date list at `index`. With `bad_entry`, an entry whose
+/// dates are not valid is an error, and the index and value of any bad entry are
+/// recorded there.
+struct EntrySeed<'a> {
+    index: usize,
+    bad_entry: Option<&'a BadEntry>,
+}
+
+impl EntrySeed<'_> {
+    /// Passes a parsed entry through, or rejects it if its dates are not valid and
+    /// bad entries are errors.
+    fn check<E: de::Error>(self, entry: HolidayEntry) -> Result<HolidayEntry, E> {
+        match (&entry.0, self.bad_entry) {
+            (Err(written), Some(bad_entry)) => {
+                bad_entry.set(Some((self.index, Some(written.clone()))));
+                Err(E::invalid_value(Unexpected::Str(written), &self))
+            }
+            _ => Ok(entry),
+        }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for EntrySeed<'_> {
+    type Value = HolidayEntry;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let (index, bad_entry) = (self.index, self.bad_entry);
+        deserializer.deserialize_any(self).map_err(|error| {
+            if let Some(bad_entry) = bad_entry {
+                // An entry that is not a date has already recorded itself, with its value.
+                let recorded = bad_entry.take();
+                bad_entry.set(recorded.or(Some((index, None))));
+            }
+            error
+        })
+    }
+}
+
+impl<'de> Visitor<'de> for EntrySeed<'_> {
+    type Value = HolidayEntry;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a date string or a date range")
+    }
+
+    fn visit_str<E: de::Error>(self, entry: &str) -> Result<Self::Value, E> {
+        let (start, end) = parse_range(entry);
+        self.check(HolidayEntry::new(start, end, || entry.to_string())?)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        let RangeFields { start, end } = RangeFields::deserialize(MapAccessDeserializer::new(map))?;
+        self.check(HolidayEntry::new(
+            start.0.as_ref().ok().copied(),
+            end.0.as_ref().ok().copied(),
+            || format!("{}..{}", start, end),
+        )?)
+    }
+}
+
+/// Parses the start and end of a date list entry written as a string: a single date,
+/// which is both, or a `start..end` range.
+fn parse_range(entry: &str) -> (Option<NaiveDate>, Option<NaiveDate>) {
+    let parse = |date: &str| parse_date(date.trim());
+    match entry.split_once("..") {
+        Some((start, end)) => (parse(start), parse(end)),
+        None => {
+            let date = parse(entry);
+            (date, date)
+        }
+    }
+}
+
+/// Parses a configuration date, written `YYYY-MM-DD` or `YYYY/MM/DD`.
+fn parse_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(date, "%Y/%m/%d"))
+        .ok()
+}
+
+/// A single date, or the entry as written if it is not one.
+struct HolidayDate(Result<NaiveDate, String>);
+
+impl fmt::Display for HolidayDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0 {
+            Ok(date) => date.fmt(f),
+            Err(written) => f.write_str(written),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for HolidayDate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DateVisitor;
+
+        impl<'de> Visitor<'de> for DateVisitor {
+            type Value = HolidayDate;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a date string")
+            }
+
+            fn visit_str<E>(self, date: &str) -> Result<Self::Value, E> {
+                Ok(HolidayDate(
+                    parse_date(date).ok_or_else(|| date.to_string()),
+                ))
+            }
+        }
+
+        deserializer.deserialize_str(DateVisitor)
+    }
+}
+
+/// A parsed `partial_holidays` list, mapping each date to the half that is off.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct PartialHolidayList {
+    holidays: BTreeMap<NaiveDate, DayHalf>,
+    /// Entries whose date is not a date, as written, in input order.
+    invalid: Vec<(String, DayHalf)>,
+}
+
+/// A `partial_holidays` entry: a bare date has its afternoon off.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PartialHolidayEntry {
+    Date(HolidayDate),
+    Half { date: HolidayDate, half: DayHalf },
+}
+
+impl<'de> Deserialize<'de> for PartialHolidayList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut list = PartialHolidayList::default();
+        for entry in Vec::<PartialHolidayEntry>::deserialize(deserializer)? {
+            let (HolidayDate(date), half) = match entry {
+                PartialHolidayEntry::Date(date) => (date, DayHalf::Afternoon),
+                PartialHolidayEntry::Half { date, half } => (date, half),
+            };
+            match date {
+                Ok(date) => {
+                    list.holidays.insert(date, half);
+                }
+                Err(written) => list.invalid.push((written, half)),
+            }
+        }
+        Ok(list)
+    }
+}
+
+/// A parsed `recurring_holidays` list, in input order without repeats.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct RuleList(Vec<HolidayRule>);
+
+impl<'de> Deserialize<'de> for RuleList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut rules = Vec::new();
+        for RuleEntry(rule) in Vec::<RuleEntry>::deserialize(deserializer)? {
+            if !rules.contains(&rule) {
+                rules.push(rule);
+            }
+        }
+        Ok(RuleList(rules))
+    }
+}
+
+/// A parsed `holiday_layers` list, in input order without repeats.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct LayerList(Vec<HolidayLayer>);
+
+impl<'de> Deserialize<'de> for LayerList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut layers = Vec::new();
+        for entry in Vec::<LayerFields>::deserialize(deserializer)? {
+            let holiday = match (entry.date, entry.rule) {
+                (Some(date), None) => Holiday::Date(date),
+                (None, Some(RuleEntry(rule))) => Holiday::Rule(rule),
+                _ => {
+                    return Err(de::Error::custom(
+                        "a holiday layer needs exactly one of date or rule",
+                    ))
+                }
+            };
+            let layer = HolidayLayer {
+                holiday,
+                effective_from: entry.effective_from,
+                effective_to: entry.effective_to,
+            };
+            if !layers.contains(&layer) {
+                layers.push(layer);
+            }
+        }
+        Ok(LayerList(layers))
+    }
+}
+
+/// The fields of a `holiday_layers` entry. The rule is read like a
+/// `recurring_holidays` entry.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LayerFields {
+    date: Option<NaiveDate>,
+    rule: Option<RuleEntry>,
+    effective_from: NaiveDate,
+    effective_to: Option<NaiveDate>,
+}
+
+/// A parsed `holiday_categories` map. Rules keep their input order, which is the
+/// order a calendar reports them in.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct CategoryList {
+    dates: BTreeMap<NaiveDate, String>,
+    rules: Vec<(HolidayRule, String)>,
+}
+
+impl<'de> Deserialize<'de> for CategoryList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        /// The form a serialized calendar writes, too.
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Fields {
+            #[serde(default)]
+            dates: BTreeMap<NaiveDate, String>,
+            #[serde(default)]
+            rules: Vec<RuleCategoryFields>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct RuleCategoryFields {
+            rule: RuleEntry,
+            category: String,
+        }
+
+        let fields = Fields::deserialize(deserializer)?;
+        let mut rules: Vec<(HolidayRule, String)> = Vec::new();
+        for RuleCategoryFields {
+            rule: RuleEntry(rule),
+            category,
+        } in fields.rules
+        {
+            rules.retain(|(r, _)| *r != rule);
+            rules.push((rule, category));
+        }
+        Ok(CategoryList {
+            dates: fields.dates,
+            rules,
+        })
+    }
+}
+
+/// A `recurring_holidays` entry: `{ month, day }` for a fixed date or
+/// `{ month, weekday, nth }` for an nth weekday. The form a serialized calendar writes,
+/// with the rule's kind as a tag or a single key, is accepted too.
+struct RuleEntry(HolidayRule);
+
+/// The fields of a rule, which must be those of exactly one kind.
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleFields {
+    month: Option<u32>,
+    day: Option<u32>,
+    weekday: Option<RuleWeekday>,
+    nth: Option<i8>,
+}
+
+impl RuleFields {
+    fn into_rule<E: de::Error>(self) -> Result<HolidayRule, E> {
+        let month = self
+            .month
+            .ok_or_else(|| de::Error::missing_field("month"))?;
+        match (self.day, self.weekday, self.nth) {
+            (Some(day), None, None) => Ok(HolidayRule::FixedDate { month, day }),
+            (None, Some(RuleWeekday(weekday)), Some(nth)) => Ok(HolidayRule::NthWeekday {
+                month,
+                weekday,
+                nth,
+            }),
+            _ => Err(de::Error::custom(
+                "a recurring holiday needs either day, or weekday and nth",
+            )),
+        }
+    }
+}
+
+/// A weekday name in a rule.
+struct RuleWeekday(Weekday);
+
+impl<'de> Deserialize<'de> for RuleWeekday {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct NameVisitor;
+
+        impl<'de> Visitor<'de> for NameVisitor {
+            type Value = RuleWeekday;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a weekday name")
+            }
+
+            fn visit_str<E: de::Error>(self, name: &str) -> Result<Self::Value, E> {
+                parse_weekday(name)
+                    .map(RuleWeekday)
+                    .ok_or_else(|| de::Error::invalid_value(Unexpected::Str(name), &self))
+            }
+        }
+
+        deserializer.deserialize_str(NameVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for RuleEntry {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EntryVisitor;
+
+        impl<'de> Visitor<'de> for EntryVisitor {
+            type Value = RuleEntry;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a recurring holiday rule")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut fields = RuleFields::default();
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "month" => fields.month = Some(map.next_value()?),
+                        "day" => fields.day = Some(map.next_value()?),
+                        "weekday" => fields.weekday = Some(map.next_value()?),
+                        "nth" => fields.nth = Some(map.next_value()?),
+                        "FixedDate" | "NthWeekday" => fields = map.next_value()?,
+                        _ => {
+                            return Err(de::Error::unknown_field(
+                                &key,
+                                &["month", "day", "weekday", "nth"],
+                            ))
+                        }
+                    }
+                }
+                fields.into_rule().map(RuleEntry)
+            }
+
+            fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+                HolidayRule::deserialize(EnumAccessDeserializer::new(data)).map(RuleEntry)
+            }
+        }
+
+        deserializer.deserialize_any(EntryVisitor)
+    }
+}
+
+/// Returns why `rule` can never produce a date, if it cannot.
+fn invalid_rule_reason(rule: &HolidayRule) -> Option<String> {
+    match *rule {
+        HolidayRule::FixedDate { month, day } => {
+            // 2000 is a leap year, so February 29 is accepted.
+            NaiveDate::from_ymd_opt(2000, month, day)
+                .is_none()
+                .then(|| format!("month {} has no day {}", month, day))
+        }
+        HolidayRule::NthWeekday { month, nth, .. } => {
+            if !(1..=12).contains(&month) {
+                Some(format!("month {} does not exist", month))
+            } else if nth == 0 || !(-5..=5).contains(&nth) {
+                Some(format!("nth must be 1 to 5 or -1 to -5, not {}", nth))
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Converts a parsed configuration into a calendar.
+///
+/// `work_days` entries are day names or the keywords accepted by
+/// [`parse_weekdays`](crate::parse_weekdays), such as `weekdays` or `weekend`. A
+/// missing `work_days` key keeps the default Monday to Friday week, while an
+/// explicitly empty `work_days` list is an error since it could never yield a work
+/// day. The same holds for each list in `work_days_effective`, which maps a date to
+/// the work week in effect from it.
+///
+/// Dates are written `YYYY-MM-DD` or `YYYY/MM/DD`. Entries of `holidays` and
+/// `extra_work_days` are dates, or inclusive ranges of them written as
+/// `"2024-12-23..2025-01-03"` or `{ start: 2024-12-23, end: 2025-01-03 }`
+/// ([`WorkCalendar::add_holiday_range`]). A range that ends before it starts is a
+/// parse error.
+///
+/// `extra_work_days`, also accepted as `working_dates`, lists dates that are working
+/// dates whatever their weekday, such as a make-up Saturday. A date that is also in
+/// `holidays` stays a holiday, as with [`WorkCalendar::add_working_date`], so an
+/// ad-hoc closure is written as a holiday. `partial_holidays` lists half-day holidays,
+/// either as bare dates, which have the afternoon off, or as
+/// `{ date: 2025-12-31, half: morning }` entries naming the half that is off
+/// ([`WorkCalendar::add_partial_holiday_half`]). `ordinal_epoch` sets the date
+/// work-day ordinals count from ([`WorkCalendar::set_ordinal_epoch`]), and
+/// `hours_per_day` the working hours in a full working day
+/// ([`WorkCalendar::set_hours_per_day`]), which must be finite and positive.
+///
+/// `recurring_holidays` lists rules for [`WorkCalendar::add_recurring_holiday`]:
+/// `{ month: 12, day: 25 }` for a date every year, or
+/// `{ month: 11, weekday: Thursday, nth: 4 }` for the nth weekday of a month, with a
+/// negative `nth` counting from the end. February 29 is a holiday only in leap years,
+/// and a fifth weekday only in months that have one. A rule that can never produce a
+/// date, such as February 30 or a sixth Monday, is an error.
+///
+/// `holiday_layers` lists holidays that apply only within a window
+/// ([`WorkCalendar::add_holiday_effective`]): `{ date: 2025-06-09, effective_from:
+/// 2025-01-01 }` for a single date, or `{ rule: { month: 6, day: 19 }, effective_from:
+/// 2022-01-01, effective_to: 2030-12-31 }` for a rule, with `effective_to` left out for
+/// no end. A window that ends before it starts is an error. `holiday_categories` has a
+/// `dates` map from a holiday to its category and a `rules` list of
+/// `{ rule, category }` entries ([`WorkCalendar::add_holiday_with_category`],
+/// [`WorkCalendar::add_recurring_holiday_with_category`]); a categorized date or rule
+/// is a holiday even if it is not listed under `holidays` or `recurring_holidays`.
+///
+/// A configuration that does not describe a calendar fails with a
+/// [`WorkCalendarError::InvalidConfig`] saying why. This includes any entry of
+/// `work_days`, `work_days_effective`, `holidays`, `extra_work_days` or
+/// `partial_holidays` that is not a weekday name or a date, which the message quotes
+/// along with its key; [`WorkCalendar::from_str_lossy`] skips such entries instead.
+impl TryFrom<WorkCalendarConfig> for WorkCalendar {
+    type Error = WorkCalendarError;
+
+    fn try_from(config: WorkCalendarConfig) -> Result<Self, Self::Error> {
+        convert(config, false).map(|(calendar, _)| calendar)
+    }
+}
+
+/// Converts a configuration into a calendar. Entries that are not weekday names or
+/// dates, and extra work days that are also holidays, are an error unless `lossy` is
+/// set, in which case they are skipped and returned as warnings.
+fn convert(
+    config: WorkCalendarConfig,
+    lossy: bool,
+) -> Result<(WorkCalendar, Vec<ConfigWarning>), WorkCalendarError> {
+    let warnings = config.skipped_entries();
+    let mut calendar = WorkCalendar::new();
+    calendar.name = config.name.map(Arc::from);
+
+    if let Some(list) = config.work_days {
+        if !list.has_entries {
+            return Err(invalid_config(&calendar, "work_days must not be empty"));
+        }
+        if list.days.is_empty() {
+            return Err(invalid_config(
+                &calendar,
+                &format!(
+                    "work_days specified but none were valid: [{}]",
+                    list.invalid.join(", ")
+                ),
+            ));
+        }
+        calendar.work_days = WorkWeek::from(list.days);
+    }
+
+    if let Some(changes) = config.work_days_effective {
+        let mut weeks = WeekChanges::new();
+        for (from, list) in changes {
+            if !list.has_entries {
+                return Err(invalid_config(
+                    &calendar,
+                    &format!("work_days_effective for {} must not be empty", from),
+                ));
+            }
+            if list.days.is_empty() {
+                return Err(invalid_config(
+                    &calendar,
+                    &format!(
+                        "work_days_effective for {} specified but none were valid: [{}]",
+                        from,
+                        list.invalid.join(", ")
+                    ),
+                ));
+            }
+            weeks.insert(from, WorkWeek::from(list.days));
+        }
+        calendar.work_days_effective = Arc::new(weeks);
+    }
+
+    // After the work weeks, whose errors list every invalid name when none is valid.
+    if let (Some(warning), false) = (warnings.first(), lossy) {
+        return Err(invalid_config(&calendar, &warning.to_string()));
+    }
+
+    if let Some(HolidayList { dates, .. }) = config.holidays {
+        calendar.holidays = Arc::new(dates);
+    }
+
+    // Reached with extra work days that are also holidays only when lossy, so the
+    // holidays win.
+    if let Some(HolidayList { mut dates, .. }) = config.extra_work_days {
+        dates.retain(|date| !calendar.holidays.contains(date));
+        calendar.working_dates = Arc::new(dates);
+    }
+
+    if let Some(PartialHolidayList { holidays, .. }) = config.partial_holidays {
+        calendar.partial_holidays = Arc::new(holidays);
+    }
+
+    if let Some(RuleList(rules)) = config.recurring_holidays {
+        for rule in rules {
+            if let Some(reason) = invalid_rule_reason(&rule) {
+                return Err(invalid_config(
+                    &calendar,
+                    &format!("invalid recurring holiday {:?}: {}", rule, reason),
+                ));
+            }
+            calendar.add_recurring_holiday(rule);
+        }
+    }
+
+    if let Some(LayerList(layers)) = config.holiday_layers {
+        for layer in layers {
+            if let Holiday::Rule(rule) = layer.holiday {
+                if let Some(reason) = invalid_rule_reason(&rule) {
+                    return Err(invalid_config(
+                        &calendar,
+                        &format!("invalid holiday layer rule {:?}: {}", rule, reason),
+                    ));
+                }
+            }
+            if let Err(error) = calendar.add_holiday_effective(
+                layer.holiday,
+                layer.effective_from,
+                layer.effective_to,
+            ) {
+                return Err(invalid_config(
+                    &calendar,
+                    &format!("invalid holiday layer: {}", error),
+                ));
+            }
+        }
+    }
+
+    // After the holidays and rules, which would otherwise clear the categories.
+    if let Some(categories) = config.holiday_categories {
+        for (date, category) in categories.dates {
+            calendar.add_holiday_with_category(date, &category);
+        }
+        for (rule, category) in categories.rules {
+            if let Some(reason) = invalid_rule_reason(&rule) {
+                return Err(invalid_config(
+                    &calendar,
+                    &format!("invalid categorized rule {:?}: {}", rule, reason),
+                ));
+            }
+            calendar.add_recurring_holiday_with_category(rule, &category);
+        }
+    }
+
+    if let Some(epoch) = config.ordinal_epoch {
+        calendar.ordinal_epoch = epoch;
+    }
+
+    if let Some(HoursPerDay(hours)) = config.hours_per_day {
+        if calendar.set_hours_per_day(hours).is_err() {
+            return Err(invalid_config(
+                &calendar,
+                &format!("hours_per_day must be finite and positive: {}", hours),
+            ));
+        }
+    }
+
+    Ok((calendar, warnings))
+}
+
+/// The error for a configuration that parsed but does not describe a calendar.
+fn invalid_config(calendar: &WorkCalendar, message: &str) -> WorkCalendarError {
+    WorkCalendarError::InvalidConfig {
+        calendar: calendar.name().map(String::from),
+        message: message.to_string(),
+    }
+}
+
+impl WorkCalendar {
+    /// Writes the calendar as YAML in the configuration format read by
+    /// [`WorkCalendar::from_str`].
+    ///
+    /// The output is the canonical form of [`WorkCalendarConfig::to_yaml`]: keys in a
+    /// fixed order, weekdays by full name from Monday to Sunday, and holidays as sorted
+    /// `YYYY-MM-DD` dates, with runs of three or more as `start..end`. Equal calendars
+    /// always write the same text.
+    ///
+    /// Reading the text back gives an equal calendar, with three exceptions. Recurring
+    /// rules that can never produce a date, such as February 30, are left out; they
+    /// mark no holidays, so the calendar read back counts the same working dates.
+    /// Extra work days that are also holidays are left out too, since the holiday
+    /// wins and `from_str` rejects a date listed under both. A calendar with no work
+    /// days is written, but `from_str` rejects it.
+    ///
+    /// Serializing a calendar with serde writes a related form, with weekdays
+    /// abbreviated and rules tagged by kind, that keeps such rules; it suits snapshots
+    /// rather than files people edit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// calendar.set_work_days("Fri,Mon,Wed").unwrap();
+    /// calendar.add_holiday(NaiveDate::from_ymd_opt(2025, 12, 25).unwrap());
+    /// calendar.add_holiday(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap());
+    ///
+    /// let yaml = calendar.to_yaml_string();
+    /// assert_eq!(
+    ///     yaml,
+    ///     "work_days:\n\
+    ///      - Monday\n\
+    ///      - Wednesday\n\
+    ///      - Friday\n\
+    ///      holidays:\n\
+    ///      - 2025-01-01\n\
+    ///      - 2025-12-25\n"
+    /// );
+    /// assert_eq!(WorkCalendar::from_str(&yaml).unwrap(), calendar);
+    /// ```
+    pub fn to_yaml_string(&self) -> String {
+        WorkCalendarConfig::from(self).to_yaml()
+    }
+
+    /// Writes the calendar as pretty-printed JSON in the configuration format read by
+    /// [`WorkCalendar::from_str`].
+    ///
+    /// The JSON counterpart of [`WorkCalendar::to_yaml_string`], with the same keys
+    /// and the same round trip.
+    pub fn to_json_string(&self) -> String {
+        WorkCalendarConfig::from(self).to_json()
+    }
+
+    /// Creates a `WorkCalendar` from a YAML or JSON string like
+    /// [`WorkCalendar::from_str`], skipping entries that are not weekday names or dates
+    /// instead of failing.
+    ///
+    /// Each skipped entry of `work_days`, `work_days_effective`, `holidays`,
+    /// `extra_work_days` or `partial_holidays` is returned as a [`ConfigWarning`], in
+    /// the order the keys are written. So is each `extra_work_days` date that is also
+    /// listed under `holidays`, which `from_str` rejects; the holiday is kept. Everything else is checked as strictly as by
+    /// `from_str`, so a `work_days` list with no valid weekday is still an error.
+    ///
+    /// # Errors
+    ///
+    /// The errors of [`WorkCalendar::from_str`] other than those for invalid entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::Weekday;
+    ///
+    /// let config = "work_days: [Monday, Wendsday, Friday]\nholidays: [2023-12-25, 2023-13-40]";
+    /// let (calendar, warnings) = WorkCalendar::from_str_lossy(config).unwrap();
+    /// assert!(calendar.is_work_day(&Weekday::Fri));
+    /// assert_eq!(warnings.len(), 2);
+    /// assert_eq!(warnings[0].to_string(), r#""Wendsday" is not a weekday in work_days"#);
+    /// assert_eq!(warnings[1].to_string(), r#""2023-13-40" is not a date in holidays"#);
+    /// ```
+    pub fn from_str_lossy(s: &str) -> Result<(Self, Vec<ConfigWarning>), WorkCalendarError> {
+        convert(parse_document(s, true)?, true)
+    }
+
+    /// Loads a calendar from a file.
+    ///
+    /// Files ending in `.txt` hold one holiday per line, written as in a `holidays`
+    /// list: a date (`YYYY-MM-DD` or `YYYY/MM/DD`) or an inclusive `start..end` range.
+    /// They keep the default work week; blank lines and lines starting with `#` are
+    /// skipped, and any other line is an error. Every other file is read with
+    /// [`WorkCalendar::from_str`], so it may be YAML or JSON in the keyed form, or a
+    /// bare list of holiday dates.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file to load.
+    ///
+    /// # Errors
+    ///
+    /// * [`WorkCalendarError::InvalidCalendarFile`] if the file cannot be read, or a
+    ///   line of a `.txt` file is not a date or a range of dates.
+    /// * [`WorkCalendarError::CalendarFileConfig`] if any other file is not a valid
+    ///   configuration, holding the error [`WorkCalendar::from_str`] gives for it, so
+    ///   a [`WorkCalendarError::ConfigSyntax`] keeps its position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let path = std::env::temp_dir().join("workdays-from-path-example.txt");
+    /// std::fs::write(&path, "# Company holidays\n2025-12-24\n2025-12-31\n").unwrap();
+    /// let calendar = WorkCalendar::from_path(&path).unwrap();
+    /// assert!(calendar.is_holiday(&NaiveDate::from_ymd_opt(2025, 12, 31).unwrap()));
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, WorkCalendarError> {
+        let path = path.as_ref();
+        let invalid = |message: String| WorkCalendarError::InvalidCalendarFile {
+            path: path.display().to_string(),
+            message,
+        };
+        let text = fs::read_to_string(path).map_err(|e| invalid(e.to_string()))?;
+        let is_text = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map_or(false, |ext| ext.eq_ignore_ascii_case("txt"));
+        if !is_text {
+            return WorkCalendar::from_str(&text).map_err(|e| {
+                WorkCalendarError::CalendarFileConfig {
+                    path: path.display().to_string(),
+                    source: Box::new(e),
+                }
+            });
+        }
+
+        let mut calendar = WorkCalendar::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (start, end) = match parse_range(line) {
+                (Some(start), Some(end)) => (start, end),
+                _ => {
+                    return Err(invalid(format!(
+                        "line {} is not a date or a range of dates: {}",
+                        index + 1,
+                        line
+                    )))
+                }
+            };
+            calendar
+                .add_holiday_range(start, end)
+                .map_err(|e| invalid(format!("line {}: {}", index + 1, e)))?;
+        }
+        Ok(calendar)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{random_calendar, TestRng};
+    use chrono::Duration;
+
+    const JSON: &str = r#"{
+        "name": "Ops",
+        "work_days": ["Mon", "tuesday", "Funday", "SAT", "mon"],
+        "holidays": ["2024-01-01", "not a date", "2024-12-25", "2024-01-01"]
+    }"#;
+
+    fn expected() -> WorkCalendar {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("Ops");
+        calendar.set_work_days("Mon,Tue,Sat").unwrap();
+        calendar.add_holiday(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap());
+        calendar.add_holiday(NaiveDate::from_ymd_opt(2024, 12, 25).unwrap());
+        calendar
+    }
+
+    #[test]
+    fn test_borrowed_and_owned_input_agree() {
+        // `from_str` hands the visitors borrowed strings; `from_reader` and YAML hand
+        // them transient buffers.
+        let borrowed: WorkCalendarConfig = serde_json::from_str(JSON).unwrap();
+        let owned: WorkCalendarConfig = serde_json::from_reader(JSON.as_bytes()).unwrap();
+        let yaml: WorkCalendarConfig = serde_yaml::from_str(JSON).unwrap();
+        for config in [borrowed, owned, yaml] {
+            let (calendar, warnings) = convert(config.clone(), true).unwrap();
+            assert_eq!(calendar, expected());
+            assert_eq!(warnings.len(), 2);
+            assert_eq!(
+                WorkCalendar::try_from(config).unwrap_err().to_string(),
+                "\"Funday\" is not a weekday in work_days (calendar 'Ops')"
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_work_days_are_reported_in_order() {
+        let input = r#"{"name": "Ops", "work_days": ["Funday", "Caturday"]}"#;
+        let borrowed: WorkCalendarConfig = serde_json::from_str(input).unwrap();
+        let owned: WorkCalendarConfig = serde_json::from_reader(input.as_bytes()).unwrap();
+        for config in [borrowed, owned] {
+            assert_eq!(
+                WorkCalendar::try_from(config).unwrap_err().to_string(),
+                "work_days specified but none were valid: [Funday, Caturday] (calendar 'Ops')"
+            );
+        }
+
+        let config: WorkCalendarConfig = serde_json::from_str(input).unwrap();
+        assert!(matches!(
+            WorkCalendar::try_from(config),
+            Err(WorkCalendarError::InvalidConfig { calendar: Some(name), .. }) if name == "Ops"
+        ));
+
+        let empty: WorkCalendarConfig = serde_json::from_str(r#"{"work_days": []}"#).unwrap();
+        assert_eq!(
+            WorkCalendar::try_from(empty).unwrap_err().to_string(),
+            "work_days must not be empty"
+        );
+    }
+
+    #[test]
+    fn test_work_days_effective() {
+        let input = r#"{
+            "name": "Ops",
+            "work_days_effective": {"2025-07-01": ["Mon", "Tue", "Wed", "Thu"]}
+        }"#;
+        let mut expected = WorkCalendar::new();
+        expected.set_name("Ops");
+        expected
+            .set_work_days_effective(
+                NaiveDate::from_ymd_opt(2025, 7, 1).unwrap(),
+                "Mon,Tue,Wed,Thu",
+            )
+            .unwrap();
+        let json: WorkCalendarConfig = serde_json::from_str(input).unwrap();
+        let yaml: WorkCalendarConfig = serde_yaml::from_str(input).unwrap();
+        for config in [json, yaml] {
+            assert_eq!(WorkCalendar::try_from(config).unwrap(), expected);
+        }
+
+        let invalid = r#"{"name": "Ops", "work_days_effective": {"2025-07-01": ["Funday"]}}"#;
+        let config: WorkCalendarConfig = serde_json::from_str(invalid).unwrap();
+        assert_eq!(
+            WorkCalendar::try_from(config).unwrap_err().to_string(),
+            "work_days_effective for 2025-07-01 specified but none were valid: [Funday] \
+                 (calendar 'Ops')"
+        );
+        let empty = r#"{"work_days_effective": {"2025-07-01": []}}"#;
+        let config: WorkCalendarConfig = serde_json::from_str(empty).unwrap();
+        assert_eq!(
+            WorkCalendar::try_from(config).unwrap_err().to_string(),
+            "work_days_effective for 2025-07-01 must not be empty"
+        );
+    }
+
+    #[test]
+    fn test_extra_work_days() {
+        let date = |d| NaiveDate::from_ymd_opt(2025, 6, d).unwrap();
+        let input = r#"{
+            "holidays": ["2025-06-09", "2025-06-21"],
+            "extra_work_days": ["2025-06-14"]
+        }"#;
+        let json: WorkCalendarConfig = serde_json::from_str(input).unwrap();
+        let yaml: WorkCalendarConfig = serde_yaml::from_str(input).unwrap();
+        for config in [json, yaml] {
+            let calendar = WorkCalendar::try_from(config).unwrap();
+            assert!(calendar.is_working_date_override(&date(14)));
+            assert!(calendar.is_working_date(&date(14)));
+            assert!(!calendar.is_working_date(&date(21)));
+
+            // Mon 9 is a holiday and Sat 14 a working day, so the two weeks from the
+            // 9th hold 10 working dates.
+            assert_eq!(calendar.work_days_between(date(9), date(22)), 10);
+            assert_eq!(calendar.compute_end(date(9), 5).unwrap().date, date(14));
+            assert_eq!(calendar.compute_end(date(9), 6).unwrap().date, date(16));
+            assert_eq!(calendar.checked_sub_work_days(date(16), 1), Some(date(14)));
+        }
+
+        // Serialized calendars write the overrides back under the same key.
+        let mut calendar = WorkCalendar::new();
+        calendar.add_working_date(date(14));
+        let yaml = serde_yaml::to_string(&calendar).unwrap();
+        assert!(yaml.contains("extra_work_days"), "{}", yaml);
+        let parsed: WorkCalendarConfig = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(WorkCalendar::try_from(parsed).unwrap(), calendar);
+        let legacy = r#"{"work_days": [], "holidays": [], "working_dates": ["2025-06-14"]}"#;
+        let legacy: WorkCalendar = serde_json::from_str(legacy).unwrap();
+        assert!(legacy.is_working_date(&date(14)));
+
+        // Configs accept the same key as an alias, and write the canonical one.
+        let aliased = "holidays: [2025-06-09, 2025-06-21]\n\
+                       working_dates: [2025-06-14]\n";
+        let calendar = WorkCalendar::from_str(aliased).unwrap();
+        assert_eq!(calendar, WorkCalendar::from_str(input).unwrap());
+        assert_eq!(
+            WorkCalendar::from_str(r#"{"working_dates": ["2025-06-14"]}"#).unwrap(),
+            WorkCalendar::from_str(r#"{"extra_work_days": ["2025-06-14"]}"#).unwrap()
+        );
+        assert!(calendar.to_yaml_string().contains("extra_work_days"));
+        assert!(matches!(
+            WorkCalendar::from_str("working_dates: [June 14]"),
+            Err(WorkCalendarError::ConfigSyntax { index: Some(0), value: Some(value), .. })
+                if value == "June 14"
+        ));
+        assert!(matches!(
+            WorkCalendar::from_str("extra_work_days: []\nworking_dates: []"),
+            Err(WorkCalendarError::ConfigSyntax { .. })
+        ));
+    }
+
+    #[test]
+    fn test_extra_work_day_that_is_a_holiday() {
+        let date = |d| NaiveDate::from_ymd_opt(2025, 6, d).unwrap();
+        let input = r#"{
+            "holidays": ["2025-06-09", "2025-06-21"],
+            "extra_work_days": ["2025-06-14", "2025-06-21"]
+        }"#;
+        let message = r#""2025-06-21" in extra_work_days is also in holidays"#;
+        let is_conflict = |error: &WorkCalendarError| matches!(error, WorkCalendarError::InvalidConfig { message: m, .. } if m == message);
+        assert!(is_conflict(&WorkCalendar::from_str(input).unwrap_err()));
+        let config: WorkCalendarConfig = serde_json::from_str(input).unwrap();
+        assert!(is_conflict(&WorkCalendar::try_from(config).unwrap_err()));
+
+        let path = std::env::temp_dir().join(format!(
+            "workdays-config-conflict-{}.yaml",
+            std::process::id()
+        ));
+        fs::write(&path, input).unwrap();
+        let result = WorkCalendar::from_path(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            result,
+            Err(WorkCalendarError::CalendarFileConfig { source, .. })
+                if is_conflict(&source)
+        ));
+
+        // Lossy parsing keeps the holiday and warns about the override.
+        let (calendar, warnings) = WorkCalendar::from_str_lossy(input).unwrap();
+        assert_eq!(
+            warnings,
+            vec![ConfigWarning {
+                key: "extra_work_days",
+                effective_from: None,
+                entry: "2025-06-21".to_string(),
+                kind: ConfigWarningKind::AlsoHoliday,
+            }]
+        );
+        assert_eq!(warnings[0].to_string(), message);
+        assert!(calendar.is_working_date_override(&date(14)));
+        assert!(!calendar.is_working_date_override(&date(21)));
+        assert!(!calendar.is_working_date(&date(21)));
+        let aliased = input.replace("extra_work_days", "working_dates");
+        assert_eq!(
+            WorkCalendar::from_str(&aliased).unwrap_err().to_string(),
+            r#""2025-06-21" in working_dates is also in holidays"#
+        );
+
+        // A calendar holding both writes only the holiday, so the text reads back.
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(21));
+        calendar.add_working_date(date(21));
+        let yaml = calendar.to_yaml_string();
+        assert!(!yaml.contains("extra_work_days"), "{}", yaml);
+        let mut expected = calendar.clone();
+        expected.remove_working_date(&date(21));
+        assert_eq!(WorkCalendar::from_str(&yaml).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_work_day_keywords() {
+        let input = r#"{"work_days": ["weekdays", "sat"], "work_days_effective": {"2025-07-01": ["Weekend"]}}"#;
+        let mut expected = WorkCalendar::new();
+        expected.set_work_days("Mon,Tue,Wed,Thu,Fri,Sat").unwrap();
+        expected
+            .set_work_days_effective(NaiveDate::from_ymd_opt(2025, 7, 1).unwrap(), "Sat,Sun")
+            .unwrap();
+        let json: WorkCalendarConfig = serde_json::from_str(input).unwrap();
+        let yaml: WorkCalendarConfig = serde_yaml::from_str(input).unwrap();
+        for config in [json, yaml] {
+            assert_eq!(WorkCalendar::try_from(config).unwrap(), expected);
+        }
+
+        // The serialized calendar lists the days, and reads back the same.
+        let yaml = serde_yaml::to_string(&expected).unwrap();
+        assert!(
+            yaml.contains("work_days:\n- Mon\n- Tue\n- Wed\n- Thu\n- Fri\n- Sat\n"),
+            "{}",
+            yaml
+        );
+        let config: WorkCalendarConfig = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(WorkCalendar::try_from(config).unwrap(), expected);
+
+        let unknown: WorkCalendarConfig =
+            serde_json::from_str(r#"{"work_days": ["workdays"]}"#).unwrap();
+        assert_eq!(
+            WorkCalendar::try_from(unknown).unwrap_err().to_string(),
+            "work_days specified but none were valid: [workdays]"
+        );
+    }
+
+    #[test]
+    fn test_bare_date_list_shorthand() {
+        let date = |m, d| NaiveDate::from_ymd_opt(2024, m, d).unwrap();
+        let mut expected = WorkCalendar::new();
+        expected.add_holiday(date(1, 1));
+        expected.add_holiday(date(12, 25));
+        for input in [
+            "- 2024-12-25\n- 2024-01-01\n",
+            "[2024-01-01, 2024-12-25, 2024-01-01]",
+            r#"["2024-01-01", "2024-12-25"]"#,
+        ] {
+            assert_eq!(
+                input.parse::<WorkCalendar>().unwrap(),
+                expected,
+                "{}",
+                input
+            );
+        }
+        assert_eq!("[]".parse::<WorkCalendar>().unwrap(), WorkCalendar::new());
+        assert_eq!("".parse::<WorkCalendar>().unwrap(), WorkCalendar::new());
+
+        // The writer always uses the keyed form, which reads back the same.
+        let yaml = serde_yaml::to_string(&expected).unwrap();
+        assert!(yaml.contains("holidays:"), "{}", yaml);
+        assert_eq!(yaml.parse::<WorkCalendar>().unwrap(), expected);
+
+        // Anything but dates in the shorthand is an error.
+        for input in [
+            "- 2024-12-25\n- not a date\n",
+            "- 2024-12-25\n- holidays: [2024-01-01]\n",
+            "- [2024-12-25]\n",
+            r#"["2024-12-25", 7]"#,
+            "2024-12-25",
+        ] {
+            assert!(input.parse::<WorkCalendar>().is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_from_path() {
+        let dir = std::env::temp_dir();
+        let path = |ext: &str| dir.join(format!("workdays-config-{}.{}", std::process::id(), ext));
+        let date = |m, d| NaiveDate::from_ymd_opt(2025, m, d).unwrap();
+        let mut expected = WorkCalendar::new();
+        expected.add_holiday(date(12, 24));
+        expected.add_holiday(date(12, 31));
+
+        let files = [
+            ("txt", "# Office closures\n2025-12-24\n\n  2025-12-31  \n"),
+            ("yaml", "- 2025-12-24\n- 2025-12-31\n"),
+            ("json", r#"{"holidays": ["2025-12-24", "2025-12-31"]}"#),
+        ];
+        for (ext, contents) in files {
+            fs::write(path(ext), contents).unwrap();
+            assert_eq!(WorkCalendar::from_path(path(ext)), Ok(expected.clone()));
+        }
+        // Lines take the same dates and ranges as a `holidays` list.
+        fs::write(path("txt"), "2025/12/24\n2025-12-31..2025/12/31\n").unwrap();
+        assert_eq!(WorkCalendar::from_path(path("txt")), Ok(expected.clone()));
+
+        fs::write(path("txt"), "2025-12-24\nwork_days: [Mon]\n").unwrap();
+        assert_eq!(
+            WorkCalendar::from_path(path("txt")),
+            Err(WorkCalendarError::InvalidCalendarFile {
+                path: path("txt").display().to_string(),
+                message: "line 2 is not a date or a range of dates: work_days: [Mon]".to_string(),
+            })
+        );
+        fs::write(path("txt"), "2025-12-31..2025-12-24\n").unwrap();
+        assert!(matches!(
+            WorkCalendar::from_path(path("txt")),
+            Err(WorkCalendarError::InvalidCalendarFile { message, .. })
+                if message.starts_with("line 1: ")
+        ));
+
+        // Configuration errors keep their structure, with the file as context.
+        let source = "holidays:\n  - 2025-12-24\n  - 2025-12-32\n";
+        fs::write(path("yaml"), source).unwrap();
+        let error = WorkCalendar::from_path(path("yaml")).unwrap_err();
+        let syntax = WorkCalendar::from_str(source).unwrap_err();
+        assert!(matches!(
+            &syntax,
+            WorkCalendarError::ConfigSyntax {
+                line: 3,
+                index: Some(1),
+                ..
+            }
+        ));
+        assert_eq!(
+            error,
+            WorkCalendarError::CalendarFileConfig {
+                path: path("yaml").display().to_string(),
+                source: Box::new(syntax.clone()),
+            }
+        );
+        assert_eq!(
+            std::error::Error::source(&error).map(ToString::to_string),
+            Some(syntax.to_string())
+        );
+        assert!(error
+            .render(source)
+            .ends_with("3 |   - 2025-12-32\n  |     ^"));
+        fs::write(path("json"), r#"{"name": "Ops", "work_days": []}"#).unwrap();
+        let error = WorkCalendar::from_path(path("json")).unwrap_err();
+        assert_eq!(error.calendar(), Some("Ops"));
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Could not load calendar file {}: work_days must not be empty (calendar 'Ops')",
+                path("json").display()
+            )
+        );
+
+        for (ext, _) in files {
+            fs::remove_file(path(ext)).unwrap();
+        }
+        assert!(matches!(
+            WorkCalendar::from_path(path("yaml")),
+            Err(WorkCalendarError::InvalidCalendarFile { .. })
+        ));
+    }
+
+    #[test]
+    fn test_non_string_entries_are_rejected() {
+        assert!(serde_json::from_str::<WorkCalendarConfig>(r#"{"work_days": [1]}"#).is_err());
+        assert!(
+            serde_json::from_str::<WorkCalendarConfig>(r#"{"holidays": "2024-01-01"}"#).is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_errors_carry_positions() {
+        let syntax = |message: &str, line, column, index, value: Option<&str>| {
+            Err(WorkCalendarError::ConfigSyntax {
+                message: message.to_string(),
+                line,
+                column,
+                index,
+                value: value.map(String::from),
+            })
+        };
+        let cases = [
+            (
+                "name: Ops\nwork_days:\n  - Mon\nholidays: 2024-01-01\n",
+                syntax(
+                    "holidays: invalid type: string \"2024-01-01\", expected a list of dates",
+                    4,
+                    11,
+                    None,
+                    None,
+                ),
+            ),
+            (
+                "{\n  \"name\": \"Ops\",\n  \"holidays\": [\"2024-01-01\", 7]\n}",
+                syntax(
+                    "invalid type: integer `7`, expected a date string or a date range",
+                    3,
+                    30,
+                    Some(1),
+                    None,
+                ),
+            ),
+            (
+                "{\"name\": \"Ops\",}",
+                syntax("trailing comma", 1, 16, None, None),
+            ),
+            (
+                "name: Ops\n  work_days: [Mon]\n",
+                syntax(
+                    "mapping values are not allowed in this context",
+                    2,
+                    12,
+                    None,
+                    None,
+                ),
+            ),
+            (
+                "- 2024-12-25\n-\t7\n",
+                syntax(
+                    "found character that cannot start any token, while scanning for the \
+                     next token",
+                    2,
+                    2,
+                    None,
+                    None,
+                ),
+            ),
+            // Entries of the shorthand list report their index and value.
+            (
+                "- 2025-12-24\n- 2025-12-25\n- 2025-12-32\n",
+                syntax(
+                    ".[2]: invalid value: string \"2025-12-32\", expected a YYYY-MM-DD date",
+                    3,
+                    3,
+                    Some(2),
+                    Some("2025-12-32"),
+                ),
+            ),
+            (
+                "- 2025-12-24\n- holidays: [2024-01-01]\n",
+                syntax(
+                    ".[1]: invalid type: map, expected a YYYY-MM-DD date",
+                    2,
+                    3,
+                    Some(1),
+                    None,
+                ),
+            ),
+            (
+                "[\"2024-12-25\", \"Christmas\"]",
+                syntax(
+                    ".[1]: invalid value: string \"Christmas\", expected a YYYY-MM-DD date",
+                    1,
+                    16,
+                    Some(1),
+                    Some("Christmas"),
+                ),
+            ),
+            (
+                "name: a\n---\nname: b\n",
+                Err(WorkCalendarError::ConfigParse(
+                    "deserializing from YAML containing more than one document is not \
+                     supported"
+                        .to_string(),
+                )),
+            ),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                parse_document(input, false).map(|_| ()),
+                expected,
+                "{:?}",
+                input
+            );
+        }
+
+        // The error reaches `from_str` callers intact.
+        let error = WorkCalendar::from_str("- 2025-12-32\n").unwrap_err();
+        assert!(matches!(
+            error,
+            WorkCalendarError::ConfigSyntax { index: Some(0), .. }
+        ));
+    }
+
+    #[test]
+    fn test_parser_errors_convert() {
+        // The conversions give what `from_str` reports for the same input.
+        let yaml = "name: Ops\nwork_days: 7\n";
+        let error = serde_yaml::from_str::<WorkCalendarConfig>(yaml).unwrap_err();
+        assert_eq!(
+            WorkCalendarError::from(error),
+            WorkCalendar::from_str(yaml).unwrap_err()
+        );
+        let json = "{\"name\": \"Ops\",\n \"holidays\": [\"2024-01-01\", 7]}";
+        let error = serde_json::from_str::<WorkCalendarConfig>(json).unwrap_err();
+        assert!(matches!(
+            WorkCalendarError::from(error),
+            WorkCalendarError::ConfigSyntax { line: 2, .. }
+        ));
+
+        // Errors without a position have only their message.
+        let error = serde_yaml::from_value::<WorkCalendarConfig>(7.into()).unwrap_err();
+        let message = error.to_string();
+        assert_eq!(
+            WorkCalendarError::from(error),
+            WorkCalendarError::ConfigParse(message)
+        );
+
+        // A caller's own parsing composes with `?`.
+        fn load(json: &str) -> Result<WorkCalendar, WorkCalendarError> {
+            let config: WorkCalendarConfig = serde_json::from_str(json)?;
+            WorkCalendar::try_from(config)
+        }
+        assert_eq!(
+            load(
+                r#"{"name": "Ops", "work_days": ["Mon", "Tue", "Sat"], "holidays": ["2024-01-01", "2024-12-25"]}"#
+            ),
+            Ok(expected())
+        );
+        assert!(matches!(
+            load(JSON),
+            Err(WorkCalendarError::InvalidConfig { .. })
+        ));
+        assert!(matches!(
+            load("{\"work_days\": []}"),
+            Err(WorkCalendarError::InvalidConfig { .. })
+        ));
+        assert!(matches!(
+            load("{\"work_days\": 7}"),
+            Err(WorkCalendarError::ConfigSyntax { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_render() {
+        let source = "name: Ops\nwork_days:\n  - Mon\nholidays: 2024-01-01\n";
+        let error = parse_document(source, false).unwrap_err();
+        assert_eq!(
+            error.render(source),
+            "Invalid configuration at line 4, column 11: holidays: invalid type: string \
+             \"2024-01-01\", expected a list of dates\n\
+             \x20 |\n\
+             4 | holidays: 2024-01-01\n\
+             \x20 |           ^"
+        );
+
+        // Tabs before the column are kept so the caret lines up.
+        let source = "{\n\t\"holidays\": 7\n}";
+        let rendered = parse_document(source, false).unwrap_err().render(source);
+        assert!(
+            rendered.ends_with("2 | \t\"holidays\": 7\n  | \t            ^"),
+            "{}",
+            rendered
+        );
+
+        // Wider line numbers widen the gutter.
+        let source = format!("{}holidays: 7\n", "# comment\n".repeat(11));
+        let rendered = parse_document(&source, false).unwrap_err().render(&source);
+        assert!(
+            rendered.ends_with("\n   |\n12 | holidays: 7\n   |           ^"),
+            "{}",
+            rendered
+        );
+
+        // Errors without a position, or with one outside the source, render as text.
+        let error = WorkCalendarError::ConfigParse("bad".to_string());
+        assert_eq!(error.render("anything"), error.to_string());
+        let error = parse_document("{\"name\": 7}", false).unwrap_err();
+        assert_eq!(error.render(""), error.to_string());
+    }
+
+    fn random_days(rng: &mut TestRng) -> Vec<Weekday> {
+        // Occasionally empty, and with repeats, to exercise the rejected and
+        // deduplicated cases.
+        (0..rng.below(9))
+            .map(|_| Weekday::try_from(rng.below(7) as u8).unwrap())
+            .collect()
+    }
+
+    fn random_config(rng: &mut TestRng) -> WorkCalendarConfig {
+        let base = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        let random_date = |rng: &mut TestRng| base + Duration::days(rng.below(3000) as i64);
+        let mut config = WorkCalendarConfig::new();
+        if rng.below(2) == 0 {
+            let names = ["Ops", "Platform team", "null", "Ops: EMEA", "- 7", "Zürich"];
+            config = config.with_name(names[rng.below(names.len() as u64) as usize]);
+        }
+        if rng.below(4) != 0 {
+            config = config.with_work_days(random_days(rng));
+        }
+        for _ in 0..rng.below(3) {
+            let from = random_date(rng);
+            config = config.with_work_days_effective(from, random_days(rng));
+        }
+        if rng.below(3) != 0 {
+            let holidays: Vec<NaiveDate> = (0..rng.below(20)).map(|_| random_date(rng)).collect();
+            config = config.with_holidays(holidays);
+        }
+        for _ in 0..rng.below(3) {
+            // Runs, some of them adjacent or overlapping, for the writer to join.
+            let start = random_date(rng);
+            let days = rng.below(20) as i64;
+            config = config.with_holidays((0..=days).map(|day| start + Duration::days(day)));
+        }
+        if rng.below(3) == 0 {
+            let extra: Vec<NaiveDate> = (0..rng.below(5)).map(|_| random_date(rng)).collect();
+            config = config.with_extra_work_days(extra);
+        }
+        for _ in 0..rng.below(3) {
+            let half = if rng.below(2) == 0 {
+                DayHalf::Morning
+            } else {
+                DayHalf::Afternoon
+            };
+            config = config.with_partial_holiday(random_date(rng), half);
+        }
+        if rng.below(3) == 0 {
+            config = config.with_recurring_holidays(random_rules(rng));
+        }
+        if rng.below(4) == 0 {
+            // Some windows end before they start, and some rules never apply.
+            let layers: Vec<HolidayLayer> = (0..rng.below(4))
+                .map(|_| {
+                    let holiday = if rng.below(2) == 0 {
+                        Holiday::Date(random_date(rng))
+                    } else {
+                        Holiday::Rule(
+                            random_rules(rng)
+                                .pop()
+                                .unwrap_or(HolidayRule::FixedDate { month: 6, day: 19 }),
+                        )
+                    };
+                    let effective_from = random_date(rng);
+                    let effective_to = (rng.below(2) == 0)
+                        .then(|| effective_from + Duration::days(rng.below(1000) as i64 - 50));
+                    HolidayLayer {
+                        holiday,
+                        effective_from,
+                        effective_to,
+                    }
+                })
+                .collect();
+            config = config.with_holiday_layers(layers);
+        }
+        for _ in 0..rng.below(3) {
+            let category = ["public", "optional", "régional: nord"][rng.below(3) as usize];
+            config = if rng.below(2) == 0 {
+                config.with_holiday_category(random_date(rng), category)
+            } else {
+                match random_rules(rng).pop() {
+                    Some(rule) => config.with_recurring_holiday_category(rule, category),
+                    None => config,
+                }
+            };
+        }
+        if rng.below(4) == 0 {
+            config = config.with_ordinal_epoch(random_date(rng));
+        }
+        config
+    }
+
+    fn random_rules(rng: &mut TestRng) -> Vec<HolidayRule> {
+        // Months, days and nths slightly out of range, and repeats, to exercise the
+        // rejected and deduplicated cases.
+        (0..rng.below(5))
+            .map(|_| {
+                let month = rng.below(13) as u32 + 1;
+                if rng.below(2) == 0 {
+                    HolidayRule::FixedDate {
+                        month,
+                        day: rng.below(31) as u32 + 1,
+                    }
+                } else {
+                    HolidayRule::NthWeekday {
+                        month,
+                        weekday: Weekday::try_from(rng.below(7) as u8).unwrap(),
+                        nth: rng.below(13) as i8 - 6,
+                    }
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_written_configs_round_trip_randomized() {
+        let mut rng = TestRng(0x7b3e_15c9_a0d4_f628);
+        for _ in 0..300 {
+            let config = random_config(&mut rng);
+            let converted = WorkCalendar::try_from(config.clone());
+            for text in [config.to_yaml(), config.to_json()] {
+                assert_eq!(
+                    text.parse::<WorkCalendarConfig>().as_ref(),
+                    Ok(&config),
+                    "{}",
+                    text
+                );
+                match (&converted, WorkCalendar::from_str(&text)) {
+                    (Ok(expected), Ok(calendar)) => assert_eq!(&calendar, expected, "{}", text),
+                    (Err(expected), Err(error)) => assert_eq!(&error, expected),
+                    (expected, actual) => {
+                        panic!("{:?} but read {:?} from {}", expected, actual, text)
+                    }
+                }
+            }
+            // Only an empty work week, a rule that never applies, or a window that ends
+            // before it starts is rejected.
+            let layers = config.holiday_layers.iter().flat_map(|list| &list.0);
+            let has_invalid_rule = config
+                .recurring_holidays
+                .iter()
+                .flat_map(|list| &list.0)
+                .chain(layers.clone().filter_map(|layer| match &layer.holiday {
+                    Holiday::Rule(rule) => Some(rule),
+                    Holiday::Date(_) => None,
+                }))
+                .chain(
+                    config
+                        .holiday_categories
+                        .iter()
+                        .flat_map(|list| list.rules.iter().map(|(rule, _)| rule)),
+                )
+                .any(|rule| invalid_rule_reason(rule).is_some())
+                || layers.clone().any(|layer| {
+                    layer
+                        .effective_to
+                        .map_or(false, |to| to < layer.effective_from)
+                });
+            let has_empty_week = config
+                .work_days
+                .iter()
+                .chain(
+                    config
+                        .work_days_effective
+                        .iter()
+                        .flat_map(|changes| changes.values()),
+                )
+                .any(|list| list.days.is_empty());
+            assert_eq!(
+                converted.is_err(),
+                has_empty_week || has_invalid_rule,
+                "{:?}",
+                config
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_calendar_randomized() {
+        let mut rng = TestRng(0x90c2_6f1d_e85a_3b47);
+        for _ in 0..100 {
+            let mut calendar = random_calendar(&mut rng);
+            if rng.below(2) == 0 {
+                calendar.set_name("Ops");
+                calendar.add_partial_holiday(NaiveDate::from_ymd_opt(2024, 12, 24).unwrap());
+                calendar.set_ordinal_epoch(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap());
+            }
+            let config = WorkCalendarConfig::from(&calendar);
+            let restored = WorkCalendar::from_str(&config.to_yaml()).unwrap();
+            assert_eq!(restored.name(), calendar.name());
+            assert_eq!(restored.work_days, calendar.work_days);
+            assert_eq!(restored.work_days_effective, calendar.work_days_effective);
+            assert_eq!(restored.holidays, calendar.holidays);
+            let working_dates: BTreeSet<_> = calendar
+                .working_dates
+                .difference(&calendar.holidays)
+                .copied()
+                .collect();
+            assert_eq!(*restored.working_dates, working_dates);
+            assert_eq!(restored.partial_holidays, calendar.partial_holidays);
+            let applicable: Vec<_> = calendar
+                .recurring_holidays()
+                .iter()
+                .filter(|rule| invalid_rule_reason(rule).is_none())
+                .copied()
+                .collect();
+            assert_eq!(restored.recurring_holidays(), applicable.as_slice());
+            assert_eq!(restored.ordinal_epoch, calendar.ordinal_epoch);
+            assert_eq!(WorkCalendarConfig::from(&restored), config);
+        }
+
+        // The rest of an empty calendar is default.
+        let mut calendar = WorkCalendar::new();
+        calendar.add_recurring_holiday(HolidayRule::FixedDate { month: 12, day: 25 });
+        assert_eq!(
+            WorkCalendarConfig::from(&calendar).to_json(),
+            "{\n  \"work_days\": [\n    \"Monday\",\n    \"Tuesday\",\n    \"Wednesday\",\n    \
+             \"Thursday\",\n    \"Friday\"\n  ],\n  \"recurring_holidays\": [\n    {\n      \
+             \"month\": 12,\n      \"day\": 25\n    }\n  ]\n}"
+        );
+        assert_eq!(WorkCalendarConfig::new().to_yaml(), "{}\n");
+        assert_eq!(WorkCalendar::from_str("{}").unwrap(), WorkCalendar::new());
+    }
+
+    #[test]
+    fn test_to_string_round_trips_randomized() {
+        let mut rng = TestRng(0x2d84_c1f7_6b09_e35a);
+        for _ in 0..100 {
+            let mut calendar = random_calendar(&mut rng);
+            if rng.below(2) == 0 {
+                calendar.set_name("Ops: EMEA");
+                let date = NaiveDate::from_ymd_opt(2024, 12, 24).unwrap();
+                calendar.add_partial_holiday_half(date, DayHalf::Morning);
+                calendar.add_holiday_with_category(date, "optional");
+                let rule = HolidayRule::FixedDate { month: 12, day: 26 };
+                calendar.add_recurring_holiday_with_category(rule, "public");
+                calendar.set_ordinal_epoch(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap());
+                calendar.set_hours_per_day(7.5).unwrap();
+            }
+
+            let mut expected = calendar.clone();
+            for date in calendar.holidays.iter() {
+                expected.remove_working_date(date);
+            }
+            for rule in calendar.recurring_holidays() {
+                if invalid_rule_reason(rule).is_some() {
+                    expected.remove_recurring_holiday(rule);
+                }
+            }
+            for layer in calendar.holiday_layers() {
+                if matches!(layer.holiday, Holiday::Rule(rule) if invalid_rule_reason(&rule).is_some())
+                {
+                    expected.remove_holiday_layer(layer);
+                }
+            }
+
+            for text in [calendar.to_yaml_string(), calendar.to_json_string()] {
+                let restored = WorkCalendar::from_str(&text).unwrap();
+                assert_eq!(restored, expected, "{}", text);
+                assert_eq!(restored.fingerprint(), expected.fingerprint());
+            }
+            assert_eq!(expected.to_yaml_string(), calendar.to_yaml_string());
+        }
+
+        // Built in a different order, the same calendar writes the same text.
+        let dates = [(2025, 12, 25), (2025, 1, 1), (2025, 7, 4)]
+            .map(|(y, m, d)| NaiveDate::from_ymd_opt(y, m, d).unwrap());
+        let mut forward = WorkCalendar::new();
+        let mut backward = WorkCalendar::new();
+        forward.set_work_days("Mon,Tue,Sat").unwrap();
+        backward.set_work_days("Sat,Tue,Mon").unwrap();
+        for date in dates {
+            forward.add_holiday(date);
+        }
+        for date in dates.iter().rev() {
+            backward.add_holiday(*date);
+        }
+        assert_eq!(forward.to_yaml_string(), backward.to_yaml_string());
+        assert_eq!(forward.to_json_string(), backward.to_json_string());
+        assert_eq!(
+            forward.to_yaml_string(),
+            "work_days:\n- Monday\n- Tuesday\n- Saturday\nholidays:\n- 2025-01-01\n\
+             - 2025-07-04\n- 2025-12-25\n"
+        );
+    }
+
+    #[test]
+    fn test_to_string_empty_calendar() {
+        let calendar = WorkCalendar::new();
+        let yaml = calendar.to_yaml_string();
+        assert_eq!(
+            yaml,
+            "work_days:\n- Monday\n- Tuesday\n- Wednesday\n- Thursday\n- Friday\n"
+        );
+        assert_eq!(WorkCalendar::from_str(&yaml).unwrap(), calendar);
+        let json = calendar.to_json_string();
+        assert_eq!(WorkCalendar::from_str(&json).unwrap(), calendar);
+        assert_eq!(
+            serde_json::from_str::<WorkCalendarConfig>(&json).unwrap(),
+            WorkCalendarConfig::from(&calendar)
+        );
+    }
+
+    #[test]
+    fn test_layers_and_categories() {
+        let juneteenth = HolidayRule::FixedDate { month: 6, day: 19 };
+        let yaml = "\
+holidays:
+- 2025-12-25
+holiday_layers:
+- rule: { month: 6, day: 19 }
+  effective_from: 2022-01-01
+- date: 2025-06-09
+  effective_from: 2025-01-01
+  effective_to: 2025-12-31
+holiday_categories:
+  dates:
+    2025-12-25: public
+    2025-12-24: optional
+  rules:
+  - rule: { month: 6, day: 19 }
+    category: public
+";
+        let calendar = WorkCalendar::from_str(yaml).unwrap();
+        let mut expected = WorkCalendar::new();
+        expected.add_holiday(NaiveDate::from_ymd_opt(2025, 12, 25).unwrap());
+        expected
+            .add_holiday_effective(
+                juneteenth,
+                NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
+                None,
+            )
+            .unwrap();
+        expected
+            .add_holiday_effective(
+                NaiveDate::from_ymd_opt(2025, 6, 9).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 12, 31),
+            )
+            .unwrap();
+        expected
+            .add_holiday_with_category(NaiveDate::from_ymd_opt(2025, 12, 24).unwrap(), "optional");
+        expected
+            .add_holiday_with_category(NaiveDate::from_ymd_opt(2025, 12, 25).unwrap(), "public");
+        expected.add_recurring_holiday_with_category(juneteenth, "public");
+        assert_eq!(calendar, expected);
+
+        // The serde form of a calendar's categories is read too.
+        let mut categorized = expected.clone();
+        for layer in expected.holiday_layers() {
+            categorized.remove_holiday_layer(layer);
+        }
+        let json = serde_json::to_string(&categorized).unwrap();
+        assert!(json.contains("\"FixedDate\""));
+        assert_eq!(
+            WorkCalendarConfig::from_str(&json)
+                .unwrap()
+                .holiday_categories,
+            WorkCalendarConfig::from(&categorized).holiday_categories
+        );
+
+        let invalid = [
+            (
+                "holiday_layers: [{ date: 2025-06-09, effective_from: 2025-07-01, effective_to: 2025-01-01 }]",
+                "invalid holiday layer: Effective window must not end before it starts; got \
+                 2025-07-01 to 2025-01-01",
+            ),
+            (
+                "holiday_layers: [{ rule: { month: 2, day: 30 }, effective_from: 2025-01-01 }]",
+                "invalid holiday layer rule FixedDate { month: 2, day: 30 }: month 2 has no day 30",
+            ),
+        ];
+        for (yaml, message) in invalid {
+            assert_eq!(
+                WorkCalendar::from_str(yaml),
+                Err(WorkCalendarError::InvalidConfig {
+                    calendar: None,
+                    message: message.to_string(),
+                })
+            );
+        }
+        assert!(matches!(
+            WorkCalendar::from_str(
+                "holiday_layers: [{ date: 2025-06-09, rule: { month: 6, day: 9 }, effective_from: 2025-01-01 }]"
+            ),
+            Err(WorkCalendarError::ConfigSyntax { .. })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_entries_are_errors() {
+        // Bad entries of the date lists are syntax errors with their position, index
+        // and value; the others are reported once the document has parsed.
+        let cases = [
+            (
+                "work_days: [Monday, Wendsday]",
+                "\"Wendsday\" is not a weekday in work_days",
+                None,
+            ),
+            (
+                "work_days_effective: { 2025-07-01: [Monday, Frday] }",
+                "\"Frday\" is not a weekday in work_days_effective for 2025-07-01",
+                None,
+            ),
+            (
+                "holidays: [2023-12-25, 2023-13-40]",
+                "\"2023-13-40\" is not a date in holidays",
+                Some((1, 24, 1)),
+            ),
+            (
+                "holidays: [Christmas]",
+                "\"Christmas\" is not a date in holidays",
+                Some((1, 12, 0)),
+            ),
+            (
+                "holidays: [2024-02-30..2024-03-05]",
+                "\"2024-02-30..2024-03-05\" is not a date in holidays",
+                Some((1, 12, 0)),
+            ),
+            (
+                "holidays: [{ start: 2024-12-23, end: 2025-01-32 }]",
+                "\"2024-12-23..2025-01-32\" is not a date in holidays",
+                Some((1, 12, 0)),
+            ),
+            (
+                "holidays: [12/25/2023]",
+                "\"12/25/2023\" is not a date in holidays",
+                Some((1, 12, 0)),
+            ),
+            (
+                "extra_work_days:\n  - 2025-06-14\n  - not a date\n",
+                "\"not a date\" is not a date in extra_work_days",
+                Some((3, 5, 1)),
+            ),
+            // Warnings name the key as written.
+            (
+                "working_dates:\n  - 2025-06-14\n  - not a date\n",
+                "\"not a date\" is not a date in working_dates",
+                Some((3, 5, 1)),
+            ),
+            (
+                "partial_holidays: [{ date: 2025-12-32, half: morning }]",
+                "\"2025-12-32\" is not a date in partial_holidays",
+                None,
+            ),
+            // Lossy parsing warns in the order keys are written.
+            (
+                "holidays: [nope]\nwork_days: [Monday, Sunnday]",
+                "\"Sunnday\" is not a weekday in work_days",
+                Some((1, 12, 0)),
+            ),
+        ];
+        for (yaml, message, position) in cases {
+            let (_, warnings) = WorkCalendar::from_str_lossy(yaml).unwrap();
+            assert_eq!(warnings[0].to_string(), message);
+            let error = WorkCalendar::from_str(yaml).unwrap_err();
+            match position {
+                None => assert_eq!(
+                    error,
+                    WorkCalendarError::InvalidConfig {
+                        calendar: None,
+                        message: message.to_string(),
+                    },
+                    "{}",
+                    yaml
+                ),
+                Some((line, column, index)) => {
+                    let entry = warnings
+                        .iter()
+                        .find(|warning| !warning.key.starts_with("work_days"))
+                        .map(|warning| warning.entry.clone());
+                    assert!(
+                        matches!(
+                            &error,
+                            WorkCalendarError::ConfigSyntax { line: l, column: c, index: i, value, .. }
+                                if (*l, *c, *i) == (line, column, Some(index)) && *value == entry
+                        ),
+                        "{}: {:?}",
+                        yaml,
+                        error
+                    );
+                }
+            }
+        }
+        // Converting a configuration parsed on its own has no position to report.
+        let config: WorkCalendarConfig = "holidays: [Christmas]".parse().unwrap();
+        assert_eq!(
+            WorkCalendar::try_from(config),
+            Err(WorkCalendarError::InvalidConfig {
+                calendar: None,
+                message: "\"Christmas\" is not a date in holidays".to_string(),
+            })
+        );
+        let config: WorkCalendarConfig = "working_dates: [June 14]".parse().unwrap();
+        assert_eq!(
+            WorkCalendar::try_from(config),
+            Err(WorkCalendarError::InvalidConfig {
+                calendar: None,
+                message: "\"June 14\" is not a date in working_dates".to_string(),
+            })
+        );
+        assert!(matches!(
+            WorkCalendar::from_str("working_dates: [June 14]"),
+            Err(WorkCalendarError::ConfigSyntax { message, .. })
+                if message.starts_with("working_dates[0]: ")
+        ));
+
+        let yaml = "work_days: [Monday, Wendsday, Friday]\n\
+                    work_days_effective: { 2025-07-01: [Tuesday, Thrusday] }\n\
+                    holidays: [2023-12-25, 2023-13-40, 2023/12/26]\n\
+                    partial_holidays: [2023-12-24, 2023-12-33]\n";
+        let (calendar, warnings) = WorkCalendar::from_str_lossy(yaml).unwrap();
+        let mut expected = WorkCalendar::new();
+        expected.set_work_days("Mon,Fri").unwrap();
+        expected
+            .set_work_days_effective(NaiveDate::from_ymd_opt(2025, 7, 1).unwrap(), "Tue")
+            .unwrap();
+        expected.add_holiday(NaiveDate::from_ymd_opt(2023, 12, 25).unwrap());
+        expected.add_holiday(NaiveDate::from_ymd_opt(2023, 12, 26).unwrap());
+        expected.add_partial_holiday(NaiveDate::from_ymd_opt(2023, 12, 24).unwrap());
+        assert_eq!(calendar, expected);
+        assert_eq!(
+            warnings,
+            [
+                ("work_days", None, "Wendsday"),
+                (
+                    "work_days_effective",
+                    NaiveDate::from_ymd_opt(2025, 7, 1),
+                    "Thrusday"
+                ),
+                ("holidays", None, "2023-13-40"),
+                ("partial_holidays", None, "2023-12-33"),
+            ]
+            .map(|(key, effective_from, entry)| ConfigWarning {
+                key,
+                effective_from,
+                entry: entry.to_string(),
+                kind: ConfigWarningKind::Invalid,
+            })
+        );
+
+        // Lossy parsing skips entries but keeps every other check.
+        assert_eq!(
+            WorkCalendar::from_str_lossy("work_days: [Wendsday]").unwrap_err(),
+            WorkCalendarError::InvalidConfig {
+                calendar: None,
+                message: "work_days specified but none were valid: [Wendsday]".to_string(),
+            }
+        );
+        assert!(matches!(
+            WorkCalendar::from_str_lossy("holidays: 7"),
+            Err(WorkCalendarError::ConfigSyntax { .. })
+        ));
+
+        // Slashes are accepted wherever a date is, and a valid calendar parses as before.
+        let slashed = "holidays: [2023/12/25, 2023/12/27..2023/12/29]\n\
+                       extra_work_days: [{ start: 2023/12/30, end: 2023-12-30 }]\n";
+        let dashed = "holidays: [2023-12-25, 2023-12-27..2023-12-29]\n\
+                      extra_work_days: [2023-12-30]\n";
+        let calendar = WorkCalendar::from_str(dashed).unwrap();
+        assert_eq!(WorkCalendar::from_str(slashed).unwrap(), calendar);
+        assert_eq!(
+            WorkCalendar::from_str_lossy(dashed).unwrap(),
+            (calendar, vec![])
+        );
+        assert_eq!(
+            WorkCalendar::from_str("- 2023/12/25").unwrap(),
+            WorkCalendar::from_str("- 2023-12-25").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parsed_configs_write_canonically() {
+        let config: WorkCalendarConfig = JSON.parse().unwrap();
+        let yaml = config.to_yaml();
+        assert_eq!(
+            yaml,
+            "name: Ops\n\
+             work_days:\n\
+             - Monday\n\
+             - Tuesday\n\
+             - Saturday\n\
+             - Funday\n\
+             holidays:\n\
+             - 2024-01-01\n\
+             - 2024-12-25\n\
+             - not a date\n"
+        );
+        assert_eq!(yaml.parse::<WorkCalendarConfig>(), Ok(config.clone()));
+        assert_eq!(convert(config, true).unwrap().0, expected());
+
+        let config: WorkCalendarConfig = "work_days: [weekend]\npartial_holidays: [2025-12-24]\n"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            config.to_yaml(),
+            "work_days:\n\
+             - Saturday\n\
+             - Sunday\n\
+             partial_holidays:\n\
+             - date: 2025-12-24\n  \
+             half: afternoon\n"
+        );
+        assert!(matches!(
+            "work_days: 7".parse::<WorkCalendarConfig>(),
+            Err(WorkCalendarError::ConfigSyntax { .. })
+        ));
+    }
+
+    #[test]
+    fn test_recurring_holidays() {
+        let thanksgiving = HolidayRule::NthWeekday {
+            month: 11,
+            weekday: Weekday::Thu,
+            nth: 4,
+        };
+        let leap_day = HolidayRule::FixedDate { month: 2, day: 29 };
+        let last_friday = HolidayRule::NthWeekday {
+            month: 8,
+            weekday: Weekday::Fri,
+            nth: -1,
+        };
+        let mut expected = WorkCalendar::new();
+        for rule in [thanksgiving, leap_day, last_friday] {
+            expected.add_recurring_holiday(rule);
+        }
+
+        let yaml = "recurring_holidays:\n\
+                    - { month: 11, weekday: thu, nth: 4 }\n\
+                    - month: 2\n  day: 29\n\
+                    - !NthWeekday { month: 8, weekday: Fri, nth: -1 }\n\
+                    - { month: 11, weekday: Thursday, nth: 4 }\n";
+        let json = r#"{"recurring_holidays": [
+            {"month": 11, "weekday": "Thursday", "nth": 4},
+            {"FixedDate": {"month": 2, "day": 29}},
+            {"NthWeekday": {"month": 8, "weekday": "Fri", "nth": -1}}
+        ]}"#;
+        for input in [yaml, json] {
+            let calendar = WorkCalendar::from_str(input).unwrap();
+            assert_eq!(calendar, expected, "{}", input);
+            let config: WorkCalendarConfig = input.parse().unwrap();
+            assert_eq!(
+                WorkCalendarConfig::from(&calendar).recurring_holidays,
+                config.recurring_holidays,
+                "{}",
+                input
+            );
+        }
+        assert_eq!(
+            WorkCalendarConfig::from(&expected).to_yaml(),
+            "work_days:\n\
+             - Monday\n\
+             - Tuesday\n\
+             - Wednesday\n\
+             - Thursday\n\
+             - Friday\n\
+             recurring_holidays:\n\
+             - month: 11\n  weekday: Thursday\n  nth: 4\n\
+             - month: 2\n  day: 29\n\
+             - month: 8\n  weekday: Friday\n  nth: -1\n"
+        );
+
+        // February 29 only lands in leap years, and a fifth weekday only where the
+        // month has one.
+        assert!(expected.is_holiday(&NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()));
+        assert!(!expected.is_holiday(&NaiveDate::from_ymd_opt(2023, 2, 28).unwrap()));
+        assert!(expected.is_holiday(&NaiveDate::from_ymd_opt(2024, 11, 28).unwrap()));
+        let calendar =
+            WorkCalendar::from_str("recurring_holidays: [{month: 5, weekday: Mon, nth: 5}]")
+                .unwrap();
+        assert!(calendar.is_holiday(&NaiveDate::from_ymd_opt(2023, 5, 29).unwrap()));
+        assert!([6, 13, 20, 27]
+            .iter()
+            .all(|&day| !calendar.is_holiday(&NaiveDate::from_ymd_opt(2024, 5, day).unwrap())));
+    }
+
+    #[test]
+    fn test_invalid_recurring_holidays() {
+        for (input, message) in [
+            (
+                "recurring_holidays: [{month: 2, day: 30}]",
+                "month 2 has no day 30",
+            ),
+            (
+                "recurring_holidays: [{month: 13, day: 1}]",
+                "month 13 has no day 1",
+            ),
+            (
+                "recurring_holidays: [{month: 0, weekday: Mon, nth: 1}]",
+                "month 0 does not exist",
+            ),
+            (
+                "recurring_holidays: [{month: 5, weekday: Mon, nth: 6}]",
+                "nth must be 1 to 5 or -1 to -5, not 6",
+            ),
+            (
+                "recurring_holidays: [{month: 5, weekday: Mon, nth: 0}]",
+                "nth must be 1 to 5 or -1 to -5, not 0",
+            ),
+        ] {
+            let error = WorkCalendar::from_str(input).unwrap_err().to_string();
+            assert!(error.contains(message), "{}: {}", input, error);
+        }
+
+        // Shape errors are parse errors, with a position.
+        for input in [
+            "recurring_holidays: [{month: 5}]",
+            "recurring_holidays: [{day: 5}]",
+            "recurring_holidays: [{month: 5, day: 1, nth: 1}]",
+            "recurring_holidays: [{month: 5, weekday: Someday, nth: 1}]",
+            "recurring_holidays: [{month: 5, weekday: Mon}]",
+            "recurring_holidays: [{month: 5, date: 1}]",
+            "recurring_holidays: [{month: -5, day: 1}]",
+            "recurring_holidays: [12-25]",
+        ] {
+            assert!(
+                matches!(
+                    input.parse::<WorkCalendarConfig>(),
+                    Err(WorkCalendarError::ConfigSyntax { .. })
+                ),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_holiday_ranges() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let mut expected = WorkCalendar::new();
+        expected
+            .add_holiday_range(date(2024, 12, 23), date(2025, 1, 3))
+            .unwrap();
+        expected.add_holiday(date(2024, 7, 4));
+        expected
+            .add_holiday_range(date(2024, 8, 5), date(2024, 8, 16))
+            .unwrap();
+        expected.add_holiday(date(2024, 11, 28));
+        expected.add_holiday(date(2024, 11, 29));
+        expected.add_working_date(date(2024, 6, 1));
+        expected.add_working_date(date(2024, 6, 2));
+        expected.add_working_date(date(2024, 6, 8));
+
+        let yaml = "holidays:\n\
+                    - 2024-12-23..2025-01-03\n\
+                    - 2024-07-04\n\
+                    - { start: 2024-08-05, end: 2024-08-16 }\n\
+                    - 2024-12-25\n\
+                    - 2024-11-28 .. 2024-11-29\n\
+                    extra_work_days: [2024-06-01..2024-06-02, 2024-06-08]\n";
+        let json = r#"{
+            "holidays": [
+                "2024-07-04", "2024-11-28..2024-11-29",
+                {"start": "2024-12-23", "end": "2025-01-03"},
+                {"start": "2024-08-05", "end": "2024-08-16"}
+            ],
+            "extra_work_days": ["2024-06-01", {"start": "2024-06-02", "end": "2024-06-02"},
+                "2024-06-08..2024-06-08"]
+        }"#;
+        for input in [yaml, json] {
+            assert_eq!(
+                WorkCalendar::from_str(input).unwrap(),
+                expected,
+                "{}",
+                input
+            );
+        }
+
+        // Runs of three or more dates are written back as ranges.
+        let config = WorkCalendarConfig::from(&expected);
+        assert_eq!(
+            config.to_yaml(),
+            "work_days:\n\
+             - Monday\n\
+             - Tuesday\n\
+             - Wednesday\n\
+             - Thursday\n\
+             - Friday\n\
+             holidays:\n\
+             - 2024-07-04\n\
+             - 2024-08-05..2024-08-16\n\
+             - 2024-11-28\n\
+             - 2024-11-29\n\
+             - 2024-12-23..2025-01-03\n\
+             extra_work_days:\n\
+             - 2024-06-01\n\
+             - 2024-06-02\n\
+             - 2024-06-08\n"
+        );
+
+        // A range over several years reads and writes as one entry.
+        let calendar = WorkCalendar::from_str("holidays: [2020-01-01..2029-12-31]").unwrap();
+        assert_eq!(calendar.holidays_in_range(..).count(), 3653);
+        assert_eq!(
+            WorkCalendarConfig::from(&calendar).holidays,
+            "holidays: [2020-01-01..2029-12-31]"
+                .parse::<WorkCalendarConfig>()
+                .unwrap()
+                .holidays
+        );
+        assert!(WorkCalendarConfig::from(&calendar)
+            .to_json()
+            .contains("\"2020-01-01..2029-12-31\""));
+
+        // An inverted range is rejected where it is written.
+        for (input, line, column) in [
+            ("holidays:\n- 2024-07-04\n- 2025-01-03..2024-12-23\n", 3, 3),
+            (
+                "extra_work_days:\n- {start: 2025-01-03, end: 2024-12-23}\n",
+                2,
+                3,
+            ),
+            ("{\"holidays\": [\"2025-01-03..2024-12-23\"]}", 1, 38),
+        ] {
+            match input.parse::<WorkCalendarConfig>() {
+                Err(WorkCalendarError::ConfigSyntax {
+                    message,
+                    line: found_line,
+                    column: found_column,
+                    ..
+                }) => {
+                    assert!(
+                        message
+                            .contains("holiday range 2025-01-03..2024-12-23 ends before it starts"),
+                        "{}",
+                        message
+                    );
+                    assert_eq!((found_line, found_column), (line, column), "{}", input);
+                }
+                other => panic!("{:?} from {}", other, input),
+            }
+        }
+        for input in [
+            "holidays: [{start: 2024-01-01}]",
+            "holidays: [{start: 2024-01-01, end: 2024-01-02, half: morning}]",
+            "holidays: [[2024-01-01]]",
+        ] {
+            assert!(input.parse::<WorkCalendarConfig>().is_err(), "{}", input);
+        }
+    }
+}
@@ -0,0 +1,548 @@
+//! Ordered iterators over a calendar's holidays, work days, and working dates.
+
+use crate::{WorkCalendar, SEARCH_HORIZON_DAYS};
+use chrono::{NaiveDate, Weekday};
+use std::collections::{btree_set, BTreeSet};
+use std::iter::{Copied, FusedIterator};
+
+/// A view of a calendar's individually added holidays, returned by
+/// [`WorkCalendar::holidays`].
+///
+/// Iterating a `Holidays`, or a reference to one, yields the dates in ascending order.
+/// Recurring holidays and holiday layers are rules rather than stored dates, so they
+/// are not included; use [`WorkCalendar::is_holiday`] to test a date against every
+/// kind.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::WorkCalendar;
+/// use chrono::{Datelike, NaiveDate};
+///
+/// let mut calendar = WorkCalendar::new();
+/// calendar.add_holiday(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap());
+/// calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 12, 25).unwrap());
+/// calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 7, 4).unwrap());
+///
+/// let holidays = calendar.holidays();
+/// assert_eq!(holidays.len(), 3);
+///
+/// let in_2023: Vec<NaiveDate> = holidays
+///     .into_iter()
+///     .filter(|date| date.year() == 2023)
+///     .collect();
+/// assert_eq!(in_2023.len(), 2);
+///
+/// let names = ["Independence Day", "Christmas Day", "New Year's Day"];
+/// let labels: Vec<String> = holidays
+///     .into_iter()
+///     .zip(names)
+///     .map(|(date, name)| format!("{} {}", date, name))
+///     .collect();
+/// assert_eq!(labels[0], "2023-07-04 Independence Day");
+/// assert_eq!(labels[2], "2024-01-01 New Year's Day");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Holidays<'a> {
+    dates: &'a BTreeSet<NaiveDate>,
+}
+
+impl<'a> Holidays<'a> {
+    /// Returns an iterator over the holidays in ascending order.
+    pub fn iter(&self) -> HolidayIter<'a> {
+        HolidayIter {
+            inner: self.dates.iter().copied(),
+        }
+    }
+
+    /// Returns the number of holidays.
+    pub fn len(&self) -> usize {
+        self.dates.len()
+    }
+
+    /// Checks if there are no holidays.
+    pub fn is_empty(&self) -> bool {
+        self.dates.is_empty()
+    }
+
+    /// Checks if `date` is one of the holidays.
+    pub fn contains(&self, date: &NaiveDate) -> bool {
+        self.dates.contains(date)
+    }
+
+    /// Returns the earliest holiday, if any.
+    pub fn first(&self) -> Option<NaiveDate> {
+        self.dates.iter().next().copied()
+    }
+
+    /// Returns the latest holiday, if any.
+    pub fn last(&self) -> Option<NaiveDate> {
+        self.dates.iter().next_back().copied()
+    }
+}
+
+impl<'a> IntoIterator for Holidays<'a> {
+    type Item = NaiveDate;
+    type IntoIter = HolidayIter<'a>;
+
+    fn into_iter(self) -> HolidayIter<'a> {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &Holidays<'a> {
+    type Item = NaiveDate;
+    type IntoIter = HolidayIter<'a>;
+
+    fn into_iter(self) -> HolidayIter<'a> {
+        self.iter()
+    }
+}
+
+/// An iterator over a calendar's holidays in ascending order, returned by
+/// [`WorkCalendar::iter_holidays`] and [`Holidays::iter`].
+#[derive(Debug, Clone)]
+pub struct HolidayIter<'a> {
+    inner: Copied<btree_set::Iter<'a, NaiveDate>>,
+}
+
+impl Iterator for HolidayIter<'_> {
+    type Item = NaiveDate;
+
+    fn next(&mut self) -> Option<NaiveDate> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for HolidayIter<'_> {
+    fn next_back(&mut self) -> Option<NaiveDate> {
+        self.inner.next_back()
+    }
+}
+
+impl ExactSizeIterator for HolidayIter<'_> {}
+
+impl FusedIterator for HolidayIter<'_> {}
+
+/// An iterator over a calendar's work days from Monday to Sunday, returned by
+/// [`WorkCalendar::iter_work_days`].
+#[derive(Debug, Clone)]
+pub struct WorkWeekdays {
+    /// The weekdays not yet yielded, with bit `n` for the day `n` days after Monday.
+    remaining: u8,
+}
+
+impl WorkWeekdays {
+    /// Returns the weekday for bit `n` of the mask.
+    fn day(n: u32) -> Weekday {
+        match Weekday::try_from(n as u8) {
+            Ok(day) => day,
+            Err(_) => unreachable!("a weekday mask has seven bits"),
+        }
+    }
+}
+
+impl Iterator for WorkWeekdays {
+    type Item = Weekday;
+
+    fn next(&mut self) -> Option<Weekday> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let n = self.remaining.trailing_zeros();
+        self.remaining &= !(1 << n);
+        Some(Self::day(n))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.remaining.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for WorkWeekdays {
+    fn next_back(&mut self) -> Option<Weekday> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let n = 7 - self.remaining.leading_zeros();
+        self.remaining &= !(1 << n);
+        Some(Self::day(n))
+    }
+}
+
+impl ExactSizeIterator for WorkWeekdays {}
+
+impl FusedIterator for WorkWeekdays {}
+
+/// An endless iterator over a calendar's working dates in ascending order, returned by
+/// [`WorkCalendar::iter_working_dates`].
+///
+/// The iterator ends instead of searching forever: it yields nothing for a calendar
+/// with no work days, and stops after [`SEARCH_HORIZON_DAYS`] consecutive non-working
+/// dates or at `NaiveDate::MAX`.
+#[derive(Debug, Clone)]
+pub struct WorkingDates<'a> {
+    calendar: &'a WorkCalendar,
+    /// The first date not yet examined, or `None` once the iterator has ended.
+    next: Option<NaiveDate>,
+}
+
+impl Iterator for WorkingDates<'_> {
+    type Item = NaiveDate;
+
+    fn next(&mut self) -> Option<NaiveDate> {
+        let mut gap = 0;
+        while let Some(date) = self.next {
+            self.next = date.succ_opt();
+            if self.calendar.is_working_date(&date) {
+                return Some(date);
+            }
+            gap += 1;
+            if gap >= SEARCH_HORIZON_DAYS {
+                self.next = None;
+            }
+        }
+        None
+    }
+}
+
+impl FusedIterator for WorkingDates<'_> {}
+
+/// An iterator over a calendar's working dates in an inclusive range, returned by
+/// [`WorkCalendar::counted_work_days`].
+///
+/// It yields the dates in ascending order, or in descending order with
+/// [`Iterator::rev`], and each end can be advanced independently.
+#[derive(Debug, Clone)]
+pub struct WorkingDateRange<'a> {
+    calendar: &'a WorkCalendar,
+    /// The first and last dates not yet examined, or `None` once they have crossed.
+    remaining: Option<(NaiveDate, NaiveDate)>,
+}
+
+impl<'a> WorkingDateRange<'a> {
+    pub(crate) fn new(calendar: &'a WorkCalendar, start: NaiveDate, end: NaiveDate) -> Self {
+        WorkingDateRange {
+            calendar,
+            remaining: (start <= end).then_some((start, end)),
+        }
+    }
+}
+
+impl Iterator for WorkingDateRange<'_> {
+    type Item = NaiveDate;
+
+    fn next(&mut self) -> Option<NaiveDate> {
+        while let Some((first, last)) = self.remaining {
+            // Once the ends meet, the last date is examined and the range is empty.
+            self.remaining = first.succ_opt().filter(|_| first < last).map(|f| (f, last));
+            if self.calendar.is_working_date(&first) {
+                return Some(first);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.remaining {
+            Some((first, last)) => (0, Some((last - first).num_days() as usize + 1)),
+            None => (0, Some(0)),
+        }
+    }
+}
+
+impl DoubleEndedIterator for WorkingDateRange<'_> {
+    fn next_back(&mut self) -> Option<NaiveDate> {
+        while let Some((first, last)) = self.remaining {
+            self.remaining = last.pred_opt().filter(|_| first < last).map(|l| (first, l));
+            if self.calendar.is_working_date(&last) {
+                return Some(last);
+            }
+        }
+        None
+    }
+}
+
+impl FusedIterator for WorkingDateRange<'_> {}
+
+impl WorkCalendar {
+    /// Returns a view of the individually added holidays, which can be iterated in
+    /// ascending order or queried for its length.
+    ///
+    /// See [`Holidays`] for what is and is not included.
+    pub fn holidays(&self) -> Holidays<'_> {
+        Holidays {
+            dates: &self.holidays,
+        }
+    }
+
+    /// Returns an iterator over the individually added holidays in ascending order.
+    ///
+    /// This is `self.holidays().iter()`; see [`Holidays`] for what is included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 12, 25).unwrap());
+    /// calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 7, 4).unwrap());
+    ///
+    /// let latest = calendar.iter_holidays().next_back();
+    /// assert_eq!(latest, NaiveDate::from_ymd_opt(2023, 12, 25));
+    /// ```
+    pub fn iter_holidays(&self) -> HolidayIter<'_> {
+        self.holidays().iter()
+    }
+
+    /// Returns an iterator over the work days from Monday to Sunday.
+    ///
+    /// These are the calendar's base work days; changes scheduled with
+    /// [`WorkCalendar::set_work_days_effective`] are not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::Weekday;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// calendar.add_work_day(Weekday::Sun);
+    /// calendar.remove_work_day(&Weekday::Wed);
+    ///
+    /// let days: Vec<Weekday> = calendar.iter_work_days().collect();
+    /// assert_eq!(
+    ///     days,
+    ///     [Weekday::Mon, Weekday::Tue, Weekday::Thu, Weekday::Fri, Weekday::Sun]
+    /// );
+    /// assert_eq!(calendar.iter_work_days().rev().next(), Some(Weekday::Sun));
+    /// ```
+    pub fn iter_work_days(&self) -> WorkWeekdays {
+        WorkWeekdays {
+            remaining: self.work_days.mask(),
+        }
+    }
+
+    /// Returns an iterator over the working dates on or after `start`, in ascending
+    /// order.
+    ///
+    /// These are the dates for which [`WorkCalendar::is_working_date`] holds. The
+    /// iterator is endless for an ordinary calendar, so bound it with
+    /// [`Iterator::take`] or [`Iterator::take_while`], or use
+    /// [`WorkCalendar::counted_work_days`] for the working dates between two dates. It
+    /// yields nothing if the calendar has no work days, and ends after
+    /// [`SEARCH_HORIZON_DAYS`] consecutive non-working dates, so it never searches
+    /// forever.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::{Datelike, NaiveDate};
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 9, 4).unwrap()); // Monday
+    ///
+    /// let saturday = NaiveDate::from_ymd_opt(2023, 9, 2).unwrap();
+    /// let sprint: Vec<u32> = calendar
+    ///     .iter_working_dates(saturday)
+    ///     .take(3)
+    ///     .map(|date| date.day())
+    ///     .collect();
+    /// assert_eq!(sprint, [5, 6, 7]);
+    /// ```
+    pub fn iter_working_dates(&self, start: NaiveDate) -> WorkingDates<'_> {
+        WorkingDates {
+            calendar: self,
+            next: Some(start).filter(|_| self.has_any_work_days()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{random_calendar, TestRng};
+    use crate::AdjustmentConvention;
+    use chrono::Duration;
+
+    #[test]
+    fn test_iterators_are_ordered_randomized() {
+        let mut rng = TestRng(0x61d4_f08c_3ab9_2e75);
+        for _ in 0..50 {
+            let calendar = random_calendar(&mut rng);
+
+            let holidays: Vec<NaiveDate> = calendar.iter_holidays().collect();
+            assert!(holidays.windows(2).all(|pair| pair[0] < pair[1]));
+            assert_eq!(holidays.len(), calendar.holidays().len());
+            assert_eq!(calendar.iter_holidays().len(), holidays.len());
+            let mut reversed: Vec<NaiveDate> = calendar.iter_holidays().rev().collect();
+            reversed.reverse();
+            assert_eq!(reversed, holidays);
+            assert_eq!(
+                holidays,
+                calendar.holidays_in_range(..).collect::<Vec<NaiveDate>>()
+            );
+            assert_eq!(calendar.holidays().first(), holidays.first().copied());
+            assert_eq!(calendar.holidays().last(), holidays.last().copied());
+
+            let days: Vec<Weekday> = calendar.iter_work_days().collect();
+            assert_eq!(days, calendar.work_days.sorted());
+            assert_eq!(calendar.iter_work_days().len(), days.len());
+            let mut reversed: Vec<Weekday> = calendar.iter_work_days().rev().collect();
+            reversed.reverse();
+            assert_eq!(reversed, days);
+        }
+    }
+
+    #[test]
+    fn test_mixed_ends_and_exact_size() {
+        let mut calendar = WorkCalendar::new();
+        for day in [1, 5, 9, 13] {
+            calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 3, day).unwrap());
+        }
+        let mut holidays = calendar.iter_holidays();
+        assert_eq!(
+            holidays.next().map(|d| d.to_string()).unwrap(),
+            "2023-03-01"
+        );
+        assert_eq!(
+            holidays.next_back().map(|d| d.to_string()).unwrap(),
+            "2023-03-13"
+        );
+        assert_eq!(holidays.len(), 2);
+        let rest: Vec<NaiveDate> = holidays.clone().collect();
+        assert_eq!(rest.len(), 2);
+        assert_eq!(holidays.count(), 2);
+
+        let mut days = calendar.iter_work_days();
+        assert_eq!(days.len(), 5);
+        assert_eq!(days.next(), Some(Weekday::Mon));
+        assert_eq!(days.next_back(), Some(Weekday::Fri));
+        assert_eq!(days.next_back(), Some(Weekday::Thu));
+        assert_eq!(days.len(), 2);
+        assert_eq!(days.next(), Some(Weekday::Tue));
+        assert_eq!(days.next(), Some(Weekday::Wed));
+        assert_eq!(days.next(), None);
+        assert_eq!(days.next_back(), None);
+
+        let mut idle = WorkCalendar::new();
+        for day in calendar.iter_work_days() {
+            idle.remove_work_day(&day);
+        }
+        assert_eq!(idle.iter_work_days().next(), None);
+        assert!(WorkCalendar::new().holidays().is_empty());
+    }
+
+    #[test]
+    fn test_working_dates_match_stepping_randomized() {
+        let mut rng = TestRng(0x9c3a_51e7_d2b0_48f6);
+        let base = NaiveDate::from_ymd_opt(2019, 6, 1).unwrap();
+        for _ in 0..100 {
+            let calendar = random_calendar(&mut rng);
+            let start = base + Duration::days(rng.below(3000) as i64);
+
+            let dates: Vec<NaiveDate> = calendar.iter_working_dates(start).take(40).collect();
+            assert_eq!(
+                dates.first().copied(),
+                calendar.adjust(start, AdjustmentConvention::Following).ok()
+            );
+            for pair in dates.windows(2) {
+                assert_eq!(calendar.next_work_day(pair[0]), Ok(pair[1]));
+            }
+            if calendar.is_working_date(&start) {
+                for (n, date) in dates.iter().enumerate() {
+                    assert_eq!(
+                        calendar.compute_end(start, n as i64 + 1).unwrap().date,
+                        *date
+                    );
+                }
+            }
+
+            let end = start + Duration::days(rng.below(400) as i64);
+            let forward: Vec<NaiveDate> = calendar.counted_work_days(start, end).collect();
+            let mut backward: Vec<NaiveDate> =
+                calendar.counted_work_days(start, end).rev().collect();
+            backward.reverse();
+            assert_eq!(backward, forward);
+            assert_eq!(
+                forward,
+                calendar
+                    .iter_working_dates(start)
+                    .take_while(|date| *date <= end)
+                    .collect::<Vec<NaiveDate>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_working_dates_end() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2023, 8, 23));
+
+        let mut range = calendar.counted_work_days(date(2023, 8, 21), date(2023, 8, 27));
+        assert_eq!(range.size_hint(), (0, Some(7)));
+        assert_eq!(range.next(), Some(date(2023, 8, 21)));
+        assert_eq!(range.next_back(), Some(date(2023, 8, 25)));
+        assert_eq!(range.next_back(), Some(date(2023, 8, 24)));
+        assert_eq!(range.next(), Some(date(2023, 8, 22)));
+        assert_eq!(range.next(), None);
+        assert_eq!(range.next_back(), None);
+        assert_eq!(
+            calendar
+                .counted_work_days(date(2023, 8, 27), date(2023, 8, 21))
+                .next(),
+            None
+        );
+        assert_eq!(
+            calendar
+                .counted_work_days(NaiveDate::MAX, NaiveDate::MAX)
+                .rev()
+                .count(),
+            calendar.is_working_date(&NaiveDate::MAX) as usize
+        );
+
+        // Storable in a struct and ending at the last representable date.
+        struct Sprint<'a> {
+            days: WorkingDates<'a>,
+        }
+        let mut sprint = Sprint {
+            days: calendar.iter_working_dates(NaiveDate::MAX - Duration::days(10)),
+        };
+        assert!(sprint.days.by_ref().count() <= 11);
+        assert_eq!(sprint.days.next(), None);
+
+        // A calendar with no work days yields nothing, even with a forced working date.
+        let mut idle = WorkCalendar::new();
+        for day in calendar.iter_work_days() {
+            idle.remove_work_day(&day);
+        }
+        idle.add_working_date(date(2023, 8, 26));
+        assert_eq!(idle.iter_working_dates(date(2023, 8, 21)).next(), None);
+        assert_eq!(
+            idle.counted_work_days(date(2023, 8, 21), date(2023, 8, 27))
+                .collect::<Vec<NaiveDate>>(),
+            [date(2023, 8, 26)]
+        );
+
+        // A run of holidays longer than the search horizon ends the iterator.
+        let mut closed = WorkCalendar::new();
+        let first = date(2024, 1, 1);
+        closed
+            .add_holiday_range(first, first + Duration::days(SEARCH_HORIZON_DAYS))
+            .unwrap();
+        let mut dates = closed.iter_working_dates(date(2023, 12, 28));
+        assert_eq!(dates.next(), Some(date(2023, 12, 28)));
+        assert_eq!(dates.next(), Some(date(2023, 12, 29)));
+        assert_eq!(dates.next(), None);
+        assert_eq!(dates.next(), None);
+    }
+}
@@ -0,0 +1,157 @@
+//! Precomputed prefix sums for answering many range-count queries.
+
+use crate::WorkCalendar;
+use chrono::{Datelike, NaiveDate};
+use std::ops::RangeInclusive;
+
+/// Answers [`WorkCalendar::work_days_between`] queries in constant time.
+///
+/// Building the counter evaluates the calendar once for every day of the horizon and
+/// stores a running total, so each query inside the horizon is a single subtraction.
+/// Use it when issuing many queries against one calendar; create one with
+/// [`WorkCalendar::counter`].
+///
+/// Like [`YearCache`](crate::YearCache), the counter captures the calendar as it was
+/// when it was built. Parts of a query outside the horizon are answered by that
+/// captured calendar, so every query returns the same answer as the calendar would
+/// have.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::WorkCalendar;
+/// use chrono::NaiveDate;
+///
+/// let mut calendar = WorkCalendar::new();
+/// calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 8, 23).unwrap());
+/// let counter = calendar.counter(2020..=2030);
+///
+/// let start = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap();
+/// let end = NaiveDate::from_ymd_opt(2023, 8, 25).unwrap();
+/// assert_eq!(counter.work_days_between(start, end), 4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RangeCounter {
+    first: NaiveDate,
+    /// `prefix[i]` is the number of working dates in `first..first + i`.
+    prefix: Box<[u32]>,
+    calendar: WorkCalendar,
+}
+
+impl RangeCounter {
+    pub(crate) fn new(calendar: &WorkCalendar, years: RangeInclusive<i32>) -> Self {
+        let first = NaiveDate::from_ymd_opt(*years.start(), 1, 1);
+        let last = NaiveDate::from_ymd_opt(*years.end(), 12, 31);
+        let mut prefix = vec![0];
+        if let (Some(first), Some(last)) = (first, last) {
+            let mut total = 0;
+            let mut date = Some(first);
+            while let Some(day) = date.filter(|d| *d <= last) {
+                if calendar.is_working_date(&day) {
+                    total += 1;
+                }
+                prefix.push(total);
+                date = day.succ_opt();
+            }
+        }
+        RangeCounter {
+            first: first.unwrap_or(NaiveDate::MIN),
+            prefix: prefix.into_boxed_slice(),
+            calendar: calendar.clone(),
+        }
+    }
+
+    /// Returns the range of years covered by the precomputed totals.
+    pub fn horizon(&self) -> RangeInclusive<i32> {
+        match self.last() {
+            Some(last) => self.first.year()..=last.year(),
+            None => self.first.year()..=self.first.year() - 1,
+        }
+    }
+
+    /// Returns the calendar captured when the counter was built.
+    pub fn calendar(&self) -> &WorkCalendar {
+        &self.calendar
+    }
+
+    /// Returns the last date covered by the precomputed totals.
+    fn last(&self) -> Option<NaiveDate> {
+        let days = self.prefix.len() as i64 - 1;
+        if days == 0 {
+            return None;
+        }
+        Some(self.first + chrono::Duration::days(days - 1))
+    }
+
+    /// Calculates the number of work days between two dates (inclusive).
+    ///
+    /// Returns the same result as [`WorkCalendar::work_days_between`].
+    pub fn work_days_between(&self, start_date: NaiveDate, end_date: NaiveDate) -> i64 {
+        if start_date > end_date {
+            return 0;
+        }
+        let last = match self.last() {
+            Some(last) => last,
+            None => return self.calendar.work_days_between(start_date, end_date),
+        };
+
+        let mut total = 0;
+        if start_date < self.first {
+            let before = end_date.min(self.first.pred_opt().unwrap());
+            total += self.calendar.work_days_between(start_date, before);
+        }
+        if end_date > last {
+            let after = start_date.max(last.succ_opt().unwrap());
+            total += self.calendar.work_days_between(after, end_date);
+        }
+        let from = start_date.max(self.first);
+        let to = end_date.min(last);
+        if from <= to {
+            let from = (from - self.first).num_days() as usize;
+            let to = (to - self.first).num_days() as usize + 1;
+            total += i64::from(self.prefix[to] - self.prefix[from]);
+        }
+        total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{random_calendar, TestRng};
+    use chrono::Duration;
+
+    #[test]
+    fn test_counter_matches_calendar_randomized() {
+        let mut rng = TestRng(0x2545_f491_4f6c_dd1d);
+        let calendar = random_calendar(&mut rng);
+        let counter = calendar.counter(2020..=2024);
+        // Queries start up to two years before the horizon and end up to two years
+        // after it, so some fall entirely outside and some straddle its edges.
+        let base = NaiveDate::from_ymd_opt(2018, 1, 1).unwrap();
+        for _ in 0..10_000 {
+            let start_date = base + Duration::days(rng.below(365 * 9) as i64);
+            let end_date = base + Duration::days(rng.below(365 * 9) as i64);
+            assert_eq!(
+                counter.work_days_between(start_date, end_date),
+                calendar.work_days_between(start_date, end_date),
+                "{} ..= {}",
+                start_date,
+                end_date
+            );
+        }
+    }
+
+    #[test]
+    fn test_counter_horizon() {
+        let calendar = WorkCalendar::new();
+        assert_eq!(calendar.counter(2020..=2024).horizon(), 2020..=2024);
+
+        #[allow(clippy::reversed_empty_ranges)]
+        let empty = calendar.counter(2024..=2020);
+        assert!(empty.horizon().is_empty());
+        let start = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap();
+        let end = NaiveDate::from_ymd_opt(2023, 8, 27).unwrap();
+        assert_eq!(empty.work_days_between(start, end), 5);
+    }
+}
@@ -0,0 +1,267 @@
+//! Day-count conventions for accrual fractions between two dates.
+
+use crate::WorkCalendar;
+use chrono::{Datelike, NaiveDate};
+
+/// A day-count convention, for [`WorkCalendar::day_count_fraction`].
+///
+/// These count calendar days, not working dates; holidays and weekends count like any
+/// other day. Business-day calendars come in when the dates are scheduled, e.g. by
+/// [`WorkCalendar::adjust`], before the fraction is taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DayCount {
+    /// Actual/360: the number of days divided by 360.
+    Act360,
+    /// Actual/365 (Fixed): the number of days divided by 365.
+    Act365Fixed,
+    /// 30/360 US (the Bond Basis with the SIA end-of-February rules). With `D1` and
+    /// `D2` the days of the month of the start and end dates, applied in order:
+    ///
+    /// 1. If both dates are the last day of February, `D2` becomes 30.
+    /// 2. If the start date is the last day of February, `D1` becomes 30.
+    /// 3. If `D2` is 31 and `D1` is 30 or 31, `D2` becomes 30.
+    /// 4. If `D1` is 31, it becomes 30.
+    ///
+    /// The fraction is then `(360 × ΔY + 30 × ΔM + D2 − D1) / 360`.
+    Thirty360US,
+    /// Actual/Actual (ISDA): the days falling in leap years divided by 366, plus the
+    /// days falling in other years divided by 365.
+    ActAct,
+}
+
+impl DayCount {
+    /// Computes the year fraction from `start` to `end` under this convention.
+    ///
+    /// The start date is counted and the end date is not, so equal dates give 0. If
+    /// `end` is before `start`, the result is the negated fraction from `end` to
+    /// `start`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::DayCount;
+    /// use chrono::NaiveDate;
+    ///
+    /// let start = NaiveDate::from_ymd_opt(2007, 9, 30).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2008, 3, 31).unwrap();
+    /// assert_eq!(DayCount::Thirty360US.fraction(start, end), 0.5);
+    /// assert_eq!(DayCount::Act360.fraction(start, end), 183.0 / 360.0);
+    /// ```
+    pub fn fraction(self, start: NaiveDate, end: NaiveDate) -> f64 {
+        if end < start {
+            return -self.fraction(end, start);
+        }
+        match self {
+            DayCount::Act360 => (end - start).num_days() as f64 / 360.0,
+            DayCount::Act365Fixed => (end - start).num_days() as f64 / 365.0,
+            DayCount::Thirty360US => thirty_360_us_days(start, end) as f64 / 360.0,
+            DayCount::ActAct => act_act_isda(start, end),
+        }
+    }
+}
+
+/// Returns whether `date` is the last day of February.
+fn is_last_of_february(date: NaiveDate) -> bool {
+    date.month() == 2 && date.succ_opt().map_or(false, |next| next.month() == 3)
+}
+
+/// Counts the days from `start` to `end` under 30/360 US.
+fn thirty_360_us_days(start: NaiveDate, end: NaiveDate) -> i64 {
+    let mut d1 = start.day();
+    let mut d2 = end.day();
+    if is_last_of_february(start) {
+        if is_last_of_february(end) {
+            d2 = 30;
+        }
+        d1 = 30;
+    }
+    if d2 == 31 && d1 >= 30 {
+        d2 = 30;
+    }
+    if d1 == 31 {
+        d1 = 30;
+    }
+    360 * i64::from(end.year() - start.year())
+        + 30 * (i64::from(end.month()) - i64::from(start.month()))
+        + (i64::from(d2) - i64::from(d1))
+}
+
+/// Computes the Actual/Actual (ISDA) fraction from `start` to `end`, with
+/// `start <= end`.
+fn act_act_isda(start: NaiveDate, end: NaiveDate) -> f64 {
+    let year_length = |year: i32| {
+        if NaiveDate::from_ymd_opt(year, 2, 29).is_some() {
+            366.0
+        } else {
+            365.0
+        }
+    };
+    // The share of `date`'s year that has passed before it.
+    let elapsed = |date: NaiveDate| f64::from(date.ordinal0()) / year_length(date.year());
+    f64::from(end.year() - start.year()) + elapsed(end) - elapsed(start)
+}
+
+impl WorkCalendar {
+    /// Computes the year fraction from `start` to `end` under a day-count convention.
+    ///
+    /// This is [`DayCount::fraction`]: the conventions count calendar days, so the
+    /// calendar's holidays do not change the result. It sits alongside
+    /// [`WorkCalendar::adjust`] because the two are usually chained: adjust the
+    /// scheduled dates to working dates, then take the fraction between them.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The start of the accrual period, counted.
+    /// * `end` - The end of the accrual period, not counted.
+    /// * `convention` - The day-count convention.
+    ///
+    /// # Returns
+    ///
+    /// The year fraction, negative if `end` is before `start`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{AdjustmentConvention, DayCount, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// // The period ends on Saturday, September 30, 2023, which moves to Friday.
+    /// let start = NaiveDate::from_ymd_opt(2023, 6, 30).unwrap();
+    /// let end = calendar
+    ///     .adjust(
+    ///         NaiveDate::from_ymd_opt(2023, 9, 30).unwrap(),
+    ///         AdjustmentConvention::ModifiedFollowing,
+    ///     )
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     calendar.day_count_fraction(start, end, DayCount::Act360),
+    ///     91.0 / 360.0
+    /// );
+    /// assert_eq!(
+    ///     calendar.day_count_fraction(start, end, DayCount::Thirty360US),
+    ///     89.0 / 360.0
+    /// );
+    /// ```
+    pub fn day_count_fraction(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+        convention: DayCount,
+    ) -> f64 {
+        convention.fraction(start, end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{date, TestRng};
+    use chrono::Duration;
+
+    #[test]
+    fn test_thirty_360_us_fixtures() {
+        // (start, end, 30/360 US day count)
+        let fixtures = [
+            (date(2007, 1, 15), date(2007, 1, 30), 15),
+            (date(2007, 1, 15), date(2007, 2, 15), 30),
+            (date(2007, 1, 15), date(2007, 7, 15), 180),
+            (date(2007, 9, 30), date(2008, 3, 31), 180),
+            (date(2007, 9, 30), date(2007, 10, 31), 30),
+            (date(2008, 9, 30), date(2009, 9, 30), 360),
+            (date(2007, 1, 31), date(2007, 2, 28), 28),
+            (date(2007, 1, 31), date(2007, 3, 31), 60),
+            (date(2007, 3, 31), date(2007, 4, 30), 30),
+            (date(2006, 8, 31), date(2007, 2, 28), 178),
+            (date(2007, 2, 28), date(2007, 3, 31), 30),
+            (date(2007, 2, 28), date(2007, 8, 31), 180),
+            (date(2008, 2, 29), date(2009, 2, 28), 360),
+            (date(2007, 2, 28), date(2008, 2, 29), 360),
+            (date(2007, 2, 26), date(2008, 2, 29), 363),
+            (date(2008, 2, 28), date(2008, 3, 31), 33),
+            (date(2008, 2, 28), date(2008, 2, 29), 1),
+            (date(2007, 12, 31), date(2008, 1, 31), 30),
+            (date(2007, 12, 30), date(2008, 1, 31), 30),
+            (date(2007, 12, 29), date(2008, 1, 31), 32),
+            (date(2007, 1, 31), date(2007, 1, 31), 0),
+        ];
+        for (start, end, days) in fixtures {
+            assert_eq!(thirty_360_us_days(start, end), days, "{} to {}", start, end);
+            assert_eq!(
+                DayCount::Thirty360US.fraction(start, end),
+                days as f64 / 360.0
+            );
+        }
+    }
+
+    #[test]
+    fn test_actual_fixtures() {
+        let (start, end) = (date(2023, 1, 1), date(2023, 7, 1));
+        assert_eq!(DayCount::Act360.fraction(start, end), 181.0 / 360.0);
+        assert_eq!(DayCount::Act365Fixed.fraction(start, end), 181.0 / 365.0);
+        assert_eq!(DayCount::ActAct.fraction(start, end), 181.0 / 365.0);
+
+        // The ISDA 1998 Actual/Actual paper's examples.
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-12;
+        let fixtures = [
+            (
+                date(2003, 11, 1),
+                date(2004, 5, 1),
+                61.0 / 365.0 + 121.0 / 366.0,
+            ),
+            (date(1999, 2, 1), date(1999, 7, 1), 150.0 / 365.0),
+            (
+                date(1999, 7, 1),
+                date(2000, 7, 1),
+                184.0 / 365.0 + 182.0 / 366.0,
+            ),
+            (date(2002, 8, 15), date(2003, 7, 15), 334.0 / 365.0),
+            (date(2000, 1, 30), date(2000, 6, 30), 152.0 / 366.0),
+            (
+                date(1999, 11, 30),
+                date(2000, 4, 30),
+                32.0 / 365.0 + 120.0 / 366.0,
+            ),
+        ];
+        for (start, end, expected) in fixtures {
+            let actual = DayCount::ActAct.fraction(start, end);
+            assert!(close(actual, expected), "{} to {}: {}", start, end, actual);
+        }
+        assert!(close(
+            DayCount::ActAct.fraction(date(2003, 11, 1), date(2004, 5, 1)),
+            0.497_724_380_567
+        ));
+        // Whole years are 1 each, leap or not.
+        assert_eq!(
+            DayCount::ActAct.fraction(date(2000, 1, 1), date(2010, 1, 1)),
+            10.0
+        );
+        assert_eq!(
+            DayCount::ActAct.fraction(NaiveDate::MIN, NaiveDate::MAX),
+            f64::from(NaiveDate::MAX.year() - NaiveDate::MIN.year()) + 364.0 / 365.0
+        );
+    }
+
+    #[test]
+    fn test_reversed_dates_negate_randomized() {
+        let mut rng = TestRng(0x0db8_5e27_c941_f36a);
+        let calendar = WorkCalendar::new();
+        for _ in 0..500 {
+            let start = date(2000, 1, 1) + Duration::days(rng.below(10_000) as i64);
+            let end = start + Duration::days(rng.below(2000) as i64);
+            for convention in [
+                DayCount::Act360,
+                DayCount::Act365Fixed,
+                DayCount::Thirty360US,
+                DayCount::ActAct,
+            ] {
+                let fraction = calendar.day_count_fraction(start, end, convention);
+                assert!(fraction >= 0.0);
+                assert_eq!(
+                    calendar.day_count_fraction(end, start, convention),
+                    -fraction
+                );
+            }
+        }
+    }
+}
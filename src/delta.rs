@@ -0,0 +1,326 @@
+//! Calendars written as changes to a named base calendar.
+
+use crate::{CalendarResolver, HolidayRule, WorkCalendar, WorkCalendarError};
+use chrono::{NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DeltaRepr {
+    base: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    work_days: Option<Vec<Weekday>>,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    add_holidays: BTreeSet<NaiveDate>,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    remove_holidays: BTreeSet<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    add_recurring_holidays: Vec<HolidayRule>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    remove_recurring_holidays: Vec<HolidayRule>,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    add_extra_work_days: BTreeSet<NaiveDate>,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    remove_extra_work_days: BTreeSet<NaiveDate>,
+}
+
+/// Returns the items of `from` that are not in `other`, in `from`'s order.
+fn missing<'a, T: PartialEq + Clone + 'a>(
+    from: impl IntoIterator<Item = &'a T>,
+    other: &[T],
+) -> Vec<T> {
+    from.into_iter()
+        .filter(|item| !other.contains(item))
+        .cloned()
+        .collect()
+}
+
+impl WorkCalendar {
+    /// Writes the calendar as a YAML document of its differences from `base`.
+    ///
+    /// The document names the base calendar with `base: <base_name>` and lists only
+    /// what differs from it:
+    ///
+    /// * `name` - the calendar's name, if it has one and it differs from the base's.
+    /// * `work_days` - the whole work week, if it differs.
+    /// * `add_holidays` and `remove_holidays` - explicit holiday dates.
+    /// * `add_recurring_holidays` and `remove_recurring_holidays` - recurring rules.
+    /// * `add_extra_work_days` and `remove_extra_work_days` - working-date overrides
+    ///   ([`WorkCalendar::add_working_date`]).
+    ///
+    /// Other settings, such as effective work weeks, holiday layers, partial holidays,
+    /// categories, and the ordinal epoch, are not part of the delta and come from the
+    /// base when it is loaded with [`WorkCalendar::from_config_delta`]. Loading the
+    /// document and writing it again against the same base gives the same document.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The calendar to compare against.
+    /// * `base_name` - The name the base is loaded by, such as its name in a
+    ///   [`CalendarRegistry`](crate::CalendarRegistry).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{HolidayRule, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let columbus_day = HolidayRule::NthWeekday {
+    ///     month: 10,
+    ///     weekday: chrono::Weekday::Mon,
+    ///     nth: 2,
+    /// };
+    /// let mut federal = WorkCalendar::new();
+    /// federal.add_recurring_holiday(HolidayRule::FixedDate { month: 7, day: 4 });
+    /// federal.add_recurring_holiday(columbus_day);
+    ///
+    /// let mut company = federal.clone();
+    /// company.remove_recurring_holiday(&columbus_day);
+    /// company.add_holiday(NaiveDate::from_ymd_opt(2025, 12, 26).unwrap());
+    ///
+    /// let delta = company.to_config_delta(&federal, "us_federal");
+    /// assert_eq!(
+    ///     delta,
+    ///     "base: us_federal\n\
+    ///      add_holidays:\n\
+    ///      - 2025-12-26\n\
+    ///      remove_recurring_holidays:\n\
+    ///      - !NthWeekday\n  month: 10\n  weekday: Mon\n  nth: 2\n"
+    /// );
+    /// ```
+    pub fn to_config_delta(&self, base: &WorkCalendar, base_name: &str) -> String {
+        let work_days = (self.work_days != base.work_days).then(|| self.work_days.sorted());
+        let repr = DeltaRepr {
+            base: base_name.to_string(),
+            name: self
+                .name()
+                .filter(|name| Some(*name) != base.name())
+                .map(String::from),
+            work_days,
+            add_holidays: self.holidays.difference(&base.holidays).copied().collect(),
+            remove_holidays: base.holidays.difference(&self.holidays).copied().collect(),
+            add_recurring_holidays: missing(self.recurring_holidays(), base.recurring_holidays()),
+            remove_recurring_holidays: missing(
+                base.recurring_holidays(),
+                self.recurring_holidays(),
+            ),
+            add_extra_work_days: self
+                .working_dates
+                .difference(&base.working_dates)
+                .copied()
+                .collect(),
+            remove_extra_work_days: base
+                .working_dates
+                .difference(&self.working_dates)
+                .copied()
+                .collect(),
+        };
+        serde_yaml::to_string(&repr).expect("a calendar delta always serializes")
+    }
+
+    /// Loads a calendar written by [`WorkCalendar::to_config_delta`], looking up its
+    /// base with `resolver`.
+    ///
+    /// The result is a copy of the base with the document's changes applied: its
+    /// work week is replaced if `work_days` is given, then dates and rules are removed
+    /// and added. It keeps the base's name unless the document has a `name`. The
+    /// document may be YAML or JSON, and unknown keys are rejected.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The delta document.
+    /// * `resolver` - Looks up the base calendar, such as a
+    ///   [`CalendarRegistry`](crate::CalendarRegistry) or a closure.
+    ///
+    /// # Errors
+    ///
+    /// * [`WorkCalendarError::ConfigParse`] if the document is malformed, has unknown
+    ///   keys, or has an empty `work_days` list.
+    /// * [`WorkCalendarError::UnknownCalendar`] if `resolver` does not know the base.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{CalendarRegistry, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut registry = CalendarRegistry::new();
+    /// let mut federal = WorkCalendar::new();
+    /// federal.add_holiday(NaiveDate::from_ymd_opt(2025, 10, 13).unwrap());
+    /// registry.register("us_federal", federal).unwrap();
+    ///
+    /// let config = "base: us_federal\nremove_holidays: [2025-10-13]\nadd_holidays: [2025-12-26]\n";
+    /// let calendar = WorkCalendar::from_config_delta(config, &registry).unwrap();
+    /// assert!(calendar.is_working_date(&NaiveDate::from_ymd_opt(2025, 10, 13).unwrap()));
+    /// assert!(calendar.is_holiday(&NaiveDate::from_ymd_opt(2025, 12, 26).unwrap()));
+    /// ```
+    pub fn from_config_delta(
+        config: &str,
+        resolver: &impl CalendarResolver,
+    ) -> Result<WorkCalendar, WorkCalendarError> {
+        let parse_error =
+            |error: &dyn std::fmt::Display| WorkCalendarError::ConfigParse(error.to_string());
+        let delta: DeltaRepr = if config.trim_start().starts_with('{') {
+            serde_json::from_str(config).map_err(|e| parse_error(&e))?
+        } else {
+            serde_yaml::from_str(config).map_err(|e| parse_error(&e))?
+        };
+
+        let mut calendar = match resolver.resolve(&delta.base) {
+            Some(base) => (*base).clone(),
+            None => return Err(WorkCalendarError::UnknownCalendar(delta.base)),
+        };
+        if let Some(name) = delta.name {
+            calendar.set_name(name);
+        }
+        if let Some(days) = delta.work_days {
+            if days.is_empty() {
+                return Err(WorkCalendarError::ConfigParse(
+                    "work_days must not be empty".to_string(),
+                ));
+            }
+            calendar.work_days = days.into_iter().collect();
+            calendar.month_memo.invalidate();
+        }
+        for date in &delta.remove_holidays {
+            calendar.remove_holiday(date);
+        }
+        for date in delta.add_holidays {
+            calendar.add_holiday(date);
+        }
+        for rule in &delta.remove_recurring_holidays {
+            calendar.remove_recurring_holiday(rule);
+        }
+        for rule in delta.add_recurring_holidays {
+            calendar.add_recurring_holiday(rule);
+        }
+        for date in &delta.remove_extra_work_days {
+            calendar.remove_working_date(date);
+        }
+        for date in delta.add_extra_work_days {
+            calendar.add_working_date(date);
+        }
+        Ok(calendar)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{date, random_calendar, random_rule, TestRng};
+    use crate::CalendarRegistry;
+    use chrono::Duration;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_round_trip_randomized() {
+        let mut rng = TestRng(0x7a41_e0c9_3d5b_862f);
+        for _ in 0..100 {
+            let base = random_calendar(&mut rng);
+            let mut calendar = base.clone();
+            let start = date(2020, 1, 1);
+            for _ in 0..rng.below(6) {
+                let day = start + Duration::days(rng.below(3000) as i64);
+                match rng.below(4) {
+                    0 => calendar.add_holiday(day),
+                    1 => calendar.add_working_date(day),
+                    2 => calendar.add_recurring_holiday(random_rule(&mut rng)),
+                    _ => {}
+                }
+            }
+            for day in base.holidays_in_range(..).filter(|_| rng.below(4) == 0) {
+                calendar.remove_holiday(&day);
+            }
+            for rule in base
+                .recurring_holidays()
+                .iter()
+                .filter(|_| rng.below(2) == 0)
+            {
+                calendar.remove_recurring_holiday(rule);
+            }
+            if rng.below(3) == 0 {
+                calendar.set_work_days("Mon,Tue,Wed,Thu").unwrap();
+            }
+
+            let shared = Arc::new(base.clone());
+            let resolver = |name: &str| Some(shared.clone()).filter(|_| name == "base");
+            let delta = calendar.to_config_delta(&base, "base");
+            let loaded = WorkCalendar::from_config_delta(&delta, &resolver).unwrap();
+            assert_eq!(loaded.to_config_delta(&base, "base"), delta);
+            for offset in 0..3000 {
+                let day = start + Duration::days(offset);
+                assert_eq!(
+                    loaded.is_working_date(&day),
+                    calendar.is_working_date(&day),
+                    "{}\n{}",
+                    day,
+                    delta
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_only_differences_are_written() {
+        let mut base = WorkCalendar::new();
+        base.set_name("US");
+        base.add_holiday(date(2025, 7, 4));
+        base.add_working_date(date(2025, 3, 1));
+        assert_eq!(base.to_config_delta(&base, "us"), "base: us\n");
+
+        let mut calendar = base.clone();
+        calendar.set_name("Acme");
+        calendar.set_work_days("Sun,Mon,Tue,Wed,Thu").unwrap();
+        calendar.remove_working_date(&date(2025, 3, 1));
+        calendar.add_working_date(date(2025, 3, 8));
+        let delta = calendar.to_config_delta(&base, "us");
+        assert_eq!(
+            delta,
+            "base: us\n\
+             name: Acme\n\
+             work_days:\n- Mon\n- Tue\n- Wed\n- Thu\n- Sun\n\
+             add_extra_work_days:\n- 2025-03-08\n\
+             remove_extra_work_days:\n- 2025-03-01\n"
+        );
+
+        let mut registry = CalendarRegistry::new();
+        registry.register("us", base).unwrap();
+        let loaded = WorkCalendar::from_config_delta(&delta, &registry).unwrap();
+        assert_eq!(loaded, calendar);
+
+        // Without a `name`, the base's name is kept.
+        let loaded = WorkCalendar::from_config_delta(r#"{"base": "us"}"#, &registry).unwrap();
+        assert_eq!(loaded.name(), Some("US"));
+    }
+
+    #[test]
+    fn test_errors() {
+        let registry = CalendarRegistry::new();
+        assert_eq!(
+            WorkCalendar::from_config_delta("base: us_federal\n", &registry),
+            Err(WorkCalendarError::UnknownCalendar("us_federal".to_string()))
+        );
+        assert_eq!(
+            WorkCalendarError::UnknownCalendar("us_federal".to_string()).to_string(),
+            "No calendar registered as 'us_federal'"
+        );
+
+        let mut registry = CalendarRegistry::new();
+        registry.register("us", WorkCalendar::new()).unwrap();
+        let parse_error = |config: &str| match WorkCalendar::from_config_delta(config, &registry) {
+            Err(WorkCalendarError::ConfigParse(message)) => message,
+            other => panic!("unexpected result {:?} for {}", other, config),
+        };
+        let message = parse_error("base: us\nadd_holiday: [2025-01-01]\n");
+        assert!(message.contains("add_holiday"), "{}", message);
+        let message = parse_error("add_holidays: [2025-01-01]\n");
+        assert!(message.contains("base"), "{}", message);
+        assert_eq!(
+            parse_error("base: us\nwork_days: []\n"),
+            "work_days must not be empty"
+        );
+    }
+}
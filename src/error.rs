@@ -0,0 +1,450 @@
+//! Error type returned by calendar computations.
+
+use chrono::{Datelike, NaiveDate, NaiveTime};
+use std::fmt;
+
+/// Errors returned by `WorkCalendar` computations.
+///
+/// Variants raised by a specific calendar carry that calendar's name (if it has one)
+/// so multi-calendar systems can tell which calendar produced the error.
+///
+/// New variants may be added without a major release, so a `match` on this type
+/// needs a wildcard arm.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WorkCalendarError {
+    /// A negative number of work days was requested.
+    NegativeDaysWorked { calendar: Option<String> },
+    /// The calendar has no work days, so no date can ever be a working date.
+    NoWorkDaysDefined { calendar: Option<String> },
+    /// No working date was found before the search horizon was exhausted.
+    NoWorkingDaysInHorizon {
+        calendar: Option<String>,
+        searched_until: NaiveDate,
+    },
+    /// The result would fall outside the range of representable dates.
+    DateOutOfRange { calendar: Option<String> },
+    /// A global-calendar function was called before `set_global_calendar`.
+    GlobalCalendarNotSet,
+    /// One or more weekday names could not be parsed; holds the offending items.
+    InvalidWeekdays(Vec<String>),
+    /// Reading input failed at the given one-based line.
+    Io { line: u64, message: String },
+    /// A relative date phrase did not match the accepted grammar; holds the input.
+    InvalidRelativeDate(String),
+    /// A year bitmap did not have one entry per day of its year.
+    InvalidBitmap {
+        year: i32,
+        expected: usize,
+        found: usize,
+    },
+    /// A schedule's anchor date falls on a weekday that is not a work day.
+    InvalidAnchor {
+        calendar: Option<String>,
+        anchor: NaiveDate,
+    },
+    /// A sprint length of zero or fewer work days was requested.
+    InvalidSprintLength {
+        calendar: Option<String>,
+        length: i64,
+    },
+    /// Two tasks in a schedule share an id; holds the id.
+    DuplicateTask(String),
+    /// A task depends on an id that is not in the schedule.
+    UnknownDependency { task: String, dependency: String },
+    /// Task dependencies form a cycle; holds the ids along it, starting and ending
+    /// with the same id.
+    DependencyCycle(Vec<String>),
+    /// A schedule buffer fraction was negative, too large, or not a number; holds the
+    /// fraction as written.
+    InvalidBufferFraction(String),
+    /// Business hours did not open before they close.
+    InvalidBusinessHours { open: NaiveTime, close: NaiveTime },
+    /// A negative service-level duration was requested.
+    NegativeSla { calendar: Option<String> },
+    /// A negative number of shifts was requested.
+    NegativeShifts { calendar: Option<String> },
+    /// A number of hours was negative or not finite; holds the hours as written.
+    InvalidHours(String),
+    /// A calendar's hours per day was not positive and finite; holds the hours as
+    /// written.
+    InvalidHoursPerDay(String),
+    /// A holiday layer's effective window ended before it started.
+    InvalidEffectiveWindow { from: NaiveDate, to: NaiveDate },
+    /// A date expression was malformed; holds the offending token, its byte offset in
+    /// the expression, and what was expected there.
+    InvalidExpression {
+        token: String,
+        position: usize,
+        reason: &'static str,
+    },
+    /// A date expression named an anchor that was not supplied; holds the name and
+    /// its byte offset in the expression.
+    UnknownAnchor { name: String, position: usize },
+    /// A calendar name was registered twice under [`DuplicatePolicy::Error`]; holds the
+    /// name.
+    ///
+    /// [`DuplicatePolicy::Error`]: crate::DuplicatePolicy::Error
+    DuplicateCalendar(String),
+    /// No calendar is registered under the name; holds the name.
+    UnknownCalendar(String),
+    /// A calendar file could not be read or parsed; holds its path and the reason.
+    InvalidCalendarFile { path: String, message: String },
+    /// A calendar file was read but is not a valid configuration; holds its path and
+    /// the error from parsing it, which is also the error's
+    /// [`source`](std::error::Error::source).
+    CalendarFileConfig {
+        path: String,
+        source: Box<WorkCalendarError>,
+    },
+    /// A layered calendar already has a layer with the name; holds the name.
+    DuplicateLayer(String),
+    /// A configuration document could not be parsed or failed validation; holds the
+    /// reason.
+    ConfigParse(String),
+    /// Zero work days were requested from a date that is not a working date, so there
+    /// is no work end date; holds that date.
+    NonWorkingStart {
+        calendar: Option<String>,
+        date: NaiveDate,
+    },
+    /// A tenor such as `3M` was malformed; holds the tenor as written and what was
+    /// wrong with it.
+    InvalidTenor { tenor: String, reason: &'static str },
+    /// A configuration document could not be parsed at a known position; holds the
+    /// parser's message, the one-based line and column, and, when the problem is an
+    /// entry of a list of holiday dates, the entry's zero-based index and its value if
+    /// it was a string. [`WorkCalendarError::render`] shows the position in the
+    /// source.
+    ConfigSyntax {
+        message: String,
+        line: usize,
+        column: usize,
+        index: Option<usize>,
+        value: Option<String>,
+    },
+    /// A holiday range ended before it started.
+    InvalidHolidayRange {
+        calendar: Option<String>,
+        start: NaiveDate,
+        end: NaiveDate,
+    },
+    /// A configuration parsed but does not describe a calendar, such as one with an
+    /// empty `work_days` list; holds the reason.
+    InvalidConfig {
+        calendar: Option<String>,
+        message: String,
+    },
+    /// A list of work days named no valid weekday; holds the list as written.
+    NoValidWorkDays {
+        calendar: Option<String>,
+        input: String,
+    },
+    /// An iCalendar document was malformed or described holidays a calendar cannot
+    /// hold; holds the one-based line and the reason.
+    InvalidIcs { line: usize, message: String },
+}
+
+impl WorkCalendarError {
+    /// Returns the name of the calendar that produced the error, if known.
+    pub fn calendar(&self) -> Option<&str> {
+        match self {
+            WorkCalendarError::NegativeDaysWorked { calendar }
+            | WorkCalendarError::NoWorkDaysDefined { calendar }
+            | WorkCalendarError::DateOutOfRange { calendar }
+            | WorkCalendarError::NoWorkingDaysInHorizon { calendar, .. }
+            | WorkCalendarError::InvalidAnchor { calendar, .. }
+            | WorkCalendarError::InvalidSprintLength { calendar, .. }
+            | WorkCalendarError::NegativeSla { calendar }
+            | WorkCalendarError::NegativeShifts { calendar }
+            | WorkCalendarError::NonWorkingStart { calendar, .. }
+            | WorkCalendarError::InvalidHolidayRange { calendar, .. }
+            | WorkCalendarError::NoValidWorkDays { calendar, .. }
+            | WorkCalendarError::InvalidConfig { calendar, .. } => calendar.as_deref(),
+            WorkCalendarError::CalendarFileConfig { source, .. } => source.calendar(),
+            WorkCalendarError::GlobalCalendarNotSet
+            | WorkCalendarError::InvalidWeekdays(_)
+            | WorkCalendarError::InvalidRelativeDate(_)
+            | WorkCalendarError::Io { .. }
+            | WorkCalendarError::InvalidBitmap { .. }
+            | WorkCalendarError::DuplicateTask(_)
+            | WorkCalendarError::UnknownDependency { .. }
+            | WorkCalendarError::DependencyCycle(_)
+            | WorkCalendarError::InvalidBufferFraction(_)
+            | WorkCalendarError::InvalidBusinessHours { .. }
+            | WorkCalendarError::InvalidHours(_)
+            | WorkCalendarError::InvalidHoursPerDay(_)
+            | WorkCalendarError::InvalidEffectiveWindow { .. }
+            | WorkCalendarError::InvalidExpression { .. }
+            | WorkCalendarError::UnknownAnchor { .. }
+            | WorkCalendarError::DuplicateCalendar(_)
+            | WorkCalendarError::UnknownCalendar(_)
+            | WorkCalendarError::InvalidCalendarFile { .. }
+            | WorkCalendarError::DuplicateLayer(_)
+            | WorkCalendarError::ConfigParse(_)
+            | WorkCalendarError::InvalidTenor { .. }
+            | WorkCalendarError::ConfigSyntax { .. }
+            | WorkCalendarError::InvalidIcs { .. } => None,
+        }
+    }
+}
+
+impl WorkCalendarError {
+    /// Formats the error for a terminal or log, quoting the line of `source` it points
+    /// to with a caret under the column.
+    ///
+    /// Only [`WorkCalendarError::ConfigSyntax`], on its own or as the source of a
+    /// [`WorkCalendarError::CalendarFileConfig`], has a position; every other error, or
+    /// one whose line is not in `source`, renders as its [`Display`](fmt::Display)
+    /// text.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The text that failed to parse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use workdays::WorkCalendar;
+    ///
+    /// let source = "- 2025-12-24\n- 2025-12-32\n";
+    /// let error = WorkCalendar::from_str(source).unwrap_err();
+    /// assert_eq!(
+    ///     error.render(source),
+    ///     "Invalid configuration at line 2, column 3: .[1]: invalid value: string \"2025-12-32\", \
+    ///      expected a YYYY-MM-DD date\n  |\n2 | - 2025-12-32\n  |   ^"
+    /// );
+    /// ```
+    pub fn render(&self, source: &str) -> String {
+        let syntax = match self {
+            WorkCalendarError::CalendarFileConfig { source, .. } => source,
+            error => error,
+        };
+        let (line, column) = match syntax {
+            WorkCalendarError::ConfigSyntax { line, column, .. } => (*line, *column),
+            _ => return self.to_string(),
+        };
+        let text = match line.checked_sub(1).and_then(|n| source.lines().nth(n)) {
+            Some(text) => text,
+            None => return self.to_string(),
+        };
+        let number = line.to_string();
+        let gutter = " ".repeat(number.len());
+        // Keep tabs before the caret so it lines up however wide they display.
+        let indent: String = text
+            .chars()
+            .take(column.saturating_sub(1))
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        format!(
+            "{}\n{} |\n{} | {}\n{} | {}^",
+            self, gutter, number, text, gutter, indent
+        )
+    }
+}
+
+impl fmt::Display for WorkCalendarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorkCalendarError::NegativeDaysWorked { .. } => {
+                write!(f, "days_worked must be non-negative")?
+            }
+            WorkCalendarError::NoWorkDaysDefined { .. } => write!(f, "No work days defined")?,
+            WorkCalendarError::NoWorkingDaysInHorizon { searched_until, .. } => write!(
+                f,
+                "No working days found before {} (search horizon exhausted)",
+                searched_until
+            )?,
+            WorkCalendarError::DateOutOfRange { .. } => write!(f, "Date out of range")?,
+            WorkCalendarError::GlobalCalendarNotSet => write!(
+                f,
+                "No global calendar set; call workdays::set_global_calendar first"
+            )?,
+            WorkCalendarError::InvalidWeekdays(items) => {
+                write!(f, "Invalid weekday names: {}", items.join(", "))?
+            }
+            WorkCalendarError::InvalidRelativeDate(input) => write!(
+                f,
+                "Unrecognized relative date '{}'; expected one of: {}",
+                input,
+                crate::relative::GRAMMAR
+            )?,
+            WorkCalendarError::Io { line, message } => {
+                write!(f, "Read error at line {}: {}", line, message)?
+            }
+            WorkCalendarError::InvalidBitmap {
+                year,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Bitmap for {} has {} entries; expected {}",
+                year, found, expected
+            )?,
+            WorkCalendarError::InvalidAnchor { anchor, .. } => write!(
+                f,
+                "Anchor date {} ({}) is not on a work day",
+                anchor,
+                anchor.weekday()
+            )?,
+            WorkCalendarError::InvalidSprintLength { length, .. } => write!(
+                f,
+                "Sprint length must be at least one work day; got {}",
+                length
+            )?,
+            WorkCalendarError::DuplicateTask(id) => write!(f, "Duplicate task id '{}'", id)?,
+            WorkCalendarError::UnknownDependency { task, dependency } => write!(
+                f,
+                "Task '{}' depends on unknown task '{}'",
+                task, dependency
+            )?,
+            WorkCalendarError::DependencyCycle(chain) => {
+                write!(f, "Dependency cycle: {}", chain.join(" -> "))?
+            }
+            WorkCalendarError::InvalidBufferFraction(fraction) => write!(
+                f,
+                "Buffer fraction must be between 0 and {}; got {}",
+                crate::planning::MAX_BUFFER_FRACTION,
+                fraction
+            )?,
+            WorkCalendarError::InvalidBusinessHours { open, close } => write!(
+                f,
+                "Business hours must open before they close; got {} to {}",
+                open, close
+            )?,
+            WorkCalendarError::NegativeSla { .. } => {
+                write!(f, "SLA duration must be non-negative")?
+            }
+            WorkCalendarError::NegativeShifts { .. } => {
+                write!(f, "Shift count must be non-negative")?
+            }
+            WorkCalendarError::InvalidHours(hours) => {
+                write!(f, "Hours must be finite and non-negative; got {}", hours)?
+            }
+            WorkCalendarError::InvalidHoursPerDay(hours) => write!(
+                f,
+                "Hours per day must be finite and positive; got {}",
+                hours
+            )?,
+            WorkCalendarError::InvalidEffectiveWindow { from, to } => write!(
+                f,
+                "Effective window must not end before it starts; got {} to {}",
+                from, to
+            )?,
+            WorkCalendarError::InvalidExpression {
+                token,
+                position,
+                reason,
+            } => write!(
+                f,
+                "Invalid date expression at position {} ('{}'): {}",
+                position, token, reason
+            )?,
+            WorkCalendarError::UnknownAnchor { name, position } => {
+                write!(f, "Unknown anchor '{}' at position {}", name, position)?
+            }
+            WorkCalendarError::DuplicateCalendar(name) => {
+                write!(f, "Calendar '{}' is already registered", name)?
+            }
+            WorkCalendarError::UnknownCalendar(name) => {
+                write!(f, "No calendar registered as '{}'", name)?
+            }
+            WorkCalendarError::InvalidCalendarFile { path, message } => {
+                write!(f, "Could not load calendar file {}: {}", path, message)?
+            }
+            // The source already names its calendar.
+            WorkCalendarError::CalendarFileConfig { path, source } => {
+                return write!(f, "Could not load calendar file {}: {}", path, source)
+            }
+            WorkCalendarError::DuplicateLayer(name) => {
+                write!(f, "Layer '{}' is already in the stack", name)?
+            }
+            WorkCalendarError::ConfigParse(message) => {
+                write!(f, "Invalid configuration: {}", message)?
+            }
+            WorkCalendarError::NonWorkingStart { date, .. } => write!(
+                f,
+                "Zero work days from {}, which is not a working date, has no end date",
+                date
+            )?,
+            WorkCalendarError::InvalidTenor { tenor, reason } => {
+                write!(f, "Invalid tenor '{}': {}", tenor, reason)?
+            }
+            WorkCalendarError::ConfigSyntax {
+                message,
+                line,
+                column,
+                ..
+            } => write!(
+                f,
+                "Invalid configuration at line {}, column {}: {}",
+                line, column, message
+            )?,
+            WorkCalendarError::InvalidHolidayRange { start, end, .. } => write!(
+                f,
+                "Holiday range must not end before it starts; got {} to {}",
+                start, end
+            )?,
+            WorkCalendarError::InvalidConfig { message, .. } => write!(f, "{}", message)?,
+            WorkCalendarError::NoValidWorkDays { .. } => write!(f, "No valid work days provided")?,
+            WorkCalendarError::InvalidIcs { line, message } => {
+                write!(f, "Invalid iCalendar data at line {}: {}", line, message)?
+            }
+        }
+        if let Some(name) = self.calendar() {
+            write!(f, " (calendar '{}')", name)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for WorkCalendarError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WorkCalendarError::CalendarFileConfig { source, .. } => Some(&**source),
+            _ => None,
+        }
+    }
+}
+
+impl WorkCalendarError {
+    /// Builds the error for a parser message, as [`WorkCalendarError::ConfigSyntax`]
+    /// when the parser knows where the problem is.
+    fn from_parser(message: String, location: Option<(usize, usize)>) -> Self {
+        match location {
+            Some((line, column)) => {
+                // Both parsers write the position into the message; it is kept in fields.
+                let position = format!(" at line {} column {}", line, column);
+                WorkCalendarError::ConfigSyntax {
+                    message: message.replacen(&position, "", 1),
+                    line,
+                    column,
+                    index: None,
+                    value: None,
+                }
+            }
+            None => WorkCalendarError::ConfigParse(message),
+        }
+    }
+}
+
+/// Converts a YAML error into [`WorkCalendarError::ConfigSyntax`] if it has a
+/// position, and [`WorkCalendarError::ConfigParse`] otherwise.
+impl From<serde_yaml::Error> for WorkCalendarError {
+    fn from(error: serde_yaml::Error) -> Self {
+        let location = error
+            .location()
+            .map(|location| (location.line(), location.column()));
+        Self::from_parser(error.to_string(), location)
+    }
+}
+
+/// Converts a JSON error into [`WorkCalendarError::ConfigSyntax`] if it has a
+/// position, and [`WorkCalendarError::ConfigParse`] otherwise.
+impl From<serde_json::Error> for WorkCalendarError {
+    fn from(error: serde_json::Error) -> Self {
+        let location = (error.line() > 0).then(|| (error.line(), error.column()));
+        Self::from_parser(error.to_string(), location)
+    }
+}
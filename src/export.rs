@@ -0,0 +1,208 @@
+//! JSON export of the working dates in a range.
+
+use crate::WorkCalendar;
+use chrono::{Datelike, NaiveDate};
+use std::io::{self, Write};
+
+/// How [`WorkCalendar::write_working_dates_json`] writes each date.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum JsonDateStyle {
+    /// A bare ISO 8601 string: `"2023-09-04"`.
+    #[default]
+    Dates,
+    /// An object with the date and its abbreviated weekday name:
+    /// `{"date":"2023-09-04","weekday":"Mon"}`.
+    DatesWithWeekday,
+}
+
+impl WorkCalendar {
+    /// Returns the working dates from `start` to `end`, inclusive, as a JSON array of
+    /// ISO 8601 date strings in ascending order.
+    ///
+    /// For long ranges, [`WorkCalendar::write_working_dates_json`] writes the same
+    /// output without building it in memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 9, 4).unwrap());
+    /// let json = calendar.working_dates_json(
+    ///     NaiveDate::from_ymd_opt(2023, 9, 1).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2023, 9, 5).unwrap(),
+    /// );
+    /// assert_eq!(json, r#"["2023-09-01","2023-09-05"]"#);
+    /// ```
+    pub fn working_dates_json(&self, start: NaiveDate, end: NaiveDate) -> String {
+        let mut buffer = Vec::new();
+        self.write_working_dates_json(&mut buffer, start, end, JsonDateStyle::Dates)
+            .expect("writing to a Vec cannot fail");
+        String::from_utf8(buffer).expect("JSON output is ASCII")
+    }
+
+    /// Writes the working dates from `start` to `end`, inclusive, to `writer` as a
+    /// JSON array in ascending order.
+    ///
+    /// Dates are written one at a time, so multi-year ranges need no more memory than
+    /// a short one. Each date is written with a separate call, so wrap unbuffered
+    /// writers such as files in a [`std::io::BufWriter`].
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Where to write the JSON.
+    /// * `start` - The first date of the range.
+    /// * `end` - The last date of the range.
+    /// * `style` - Whether to write bare date strings or objects with weekday names.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` once the whole array is written. An empty range writes `[]`.
+    ///
+    /// # Errors
+    ///
+    /// Any error from `writer`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{JsonDateStyle, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let mut out = Vec::new();
+    /// calendar
+    ///     .write_working_dates_json(
+    ///         &mut out,
+    ///         NaiveDate::from_ymd_opt(2023, 9, 8).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2023, 9, 11).unwrap(),
+    ///         JsonDateStyle::DatesWithWeekday,
+    ///     )
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(out).unwrap(),
+    ///     r#"[{"date":"2023-09-08","weekday":"Fri"},{"date":"2023-09-11","weekday":"Mon"}]"#
+    /// );
+    /// ```
+    pub fn write_working_dates_json<W: Write>(
+        &self,
+        mut writer: W,
+        start: NaiveDate,
+        end: NaiveDate,
+        style: JsonDateStyle,
+    ) -> io::Result<()> {
+        writer.write_all(b"[")?;
+        let dates = std::iter::successors(Some(start), |date| date.succ_opt())
+            .take_while(|date| *date <= end)
+            .filter(|date| self.is_working_date(date));
+        for (i, date) in dates.enumerate() {
+            if i > 0 {
+                writer.write_all(b",")?;
+            }
+            match style {
+                JsonDateStyle::Dates => write!(writer, "\"{}\"", date)?,
+                JsonDateStyle::DatesWithWeekday => write!(
+                    writer,
+                    "{{\"date\":\"{}\",\"weekday\":\"{}\"}}",
+                    date,
+                    date.weekday()
+                )?,
+            }
+        }
+        writer.write_all(b"]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{date, random_calendar, TestRng};
+    use chrono::{Duration, Weekday};
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Entry {
+        date: NaiveDate,
+        weekday: Weekday,
+    }
+
+    fn working_dates(calendar: &WorkCalendar, start: NaiveDate, end: NaiveDate) -> Vec<NaiveDate> {
+        let days = (end - start).num_days().max(-1) + 1;
+        (0..days)
+            .map(|i| start + Duration::days(i))
+            .filter(|date| calendar.is_working_date(date))
+            .collect()
+    }
+
+    #[test]
+    fn test_working_dates_json() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2023, 12, 25));
+        calendar.add_holiday(date(2024, 1, 1));
+        assert_eq!(
+            calendar.working_dates_json(date(2023, 12, 22), date(2024, 1, 2)),
+            r#"["2023-12-22","2023-12-26","2023-12-27","2023-12-28","2023-12-29","2024-01-02"]"#
+        );
+        assert_eq!(
+            calendar.working_dates_json(date(2023, 12, 23), date(2023, 12, 25)),
+            "[]"
+        );
+        assert_eq!(
+            calendar.working_dates_json(date(2024, 1, 2), date(2023, 12, 22)),
+            "[]"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut rng = TestRng(0x91d4_0c3e_7ab2_58f6);
+        for _ in 0..20 {
+            let calendar = random_calendar(&mut rng);
+            let start = date(2019, 1, 1) + Duration::days(rng.below(3650) as i64);
+            let end = start + Duration::days(rng.below(800) as i64);
+            let expected = working_dates(&calendar, start, end);
+
+            let dates: Vec<NaiveDate> =
+                serde_json::from_str(&calendar.working_dates_json(start, end)).unwrap();
+            assert_eq!(dates, expected);
+
+            let mut out = Vec::new();
+            calendar
+                .write_working_dates_json(&mut out, start, end, JsonDateStyle::DatesWithWeekday)
+                .unwrap();
+            let entries: Vec<Entry> = serde_json::from_slice(&out).unwrap();
+            let expected: Vec<Entry> = expected
+                .into_iter()
+                .map(|date| Entry {
+                    date,
+                    weekday: date.weekday(),
+                })
+                .collect();
+            assert_eq!(entries, expected);
+        }
+    }
+
+    #[test]
+    fn test_write_error() {
+        struct Full;
+        impl Write for Full {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::Other, "disk full"))
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+        let error = WorkCalendar::new()
+            .write_working_dates_json(
+                Full,
+                date(2023, 9, 1),
+                date(2023, 9, 30),
+                JsonDateStyle::Dates,
+            )
+            .unwrap_err();
+        assert_eq!(error.to_string(), "disk full");
+    }
+}
@@ -0,0 +1,400 @@
+//! Evaluation of relative date expressions such as `kickoff + 10wd - 3d`.
+
+use crate::{WorkCalendar, WorkCalendarError};
+use chrono::{Days, NaiveDate};
+use std::collections::HashMap;
+
+/// The units a term can count in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    /// Working dates, counted by the calendar.
+    WorkDays,
+    /// Calendar days.
+    Days,
+}
+
+/// A position in an expression being parsed.
+struct Cursor<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn is_done(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    /// Consumes the longest prefix whose characters all satisfy `accept`.
+    fn take_while(&mut self, accept: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c: char| !accept(c)).unwrap_or(rest.len());
+        self.position += len;
+        &rest[..len]
+    }
+
+    /// Returns the token at the cursor, for error messages: a run of word characters,
+    /// or else the single character there.
+    fn token(&self) -> &'a str {
+        let rest = self.rest();
+        match rest.find(|c: char| !is_word(c)) {
+            Some(0) => rest.chars().next().map_or("", |c| &rest[..c.len_utf8()]),
+            Some(len) => &rest[..len],
+            None => rest,
+        }
+    }
+
+    fn error(&self, reason: &'static str) -> WorkCalendarError {
+        WorkCalendarError::InvalidExpression {
+            token: self.token().to_string(),
+            position: self.position,
+            reason,
+        }
+    }
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+impl WorkCalendar {
+    /// Evaluates a relative date expression such as `kickoff + 10wd - 3d`.
+    ///
+    /// An expression is an anchor followed by any number of terms, applied from left
+    /// to right:
+    ///
+    /// * An anchor is a name looked up in `anchors` (letters, digits, and
+    ///   underscores, not starting with a digit) or an ISO 8601 date literal
+    ///   (`YYYY-MM-DD`).
+    /// * A term is `+` or `-`, a non-negative integer, and a unit: `wd` for work days,
+    ///   counted like [`WorkCalendar::checked_add_work_days`], or `d` for calendar
+    ///   days. Units are case-insensitive.
+    ///
+    /// Whitespace is allowed between tokens, including between a number and its unit.
+    ///
+    /// # Arguments
+    ///
+    /// * `expr` - The expression to evaluate.
+    /// * `anchors` - The named dates the expression may start from.
+    ///
+    /// # Errors
+    ///
+    /// * [`WorkCalendarError::UnknownAnchor`] if the anchor is a name missing from
+    ///   `anchors`.
+    /// * [`WorkCalendarError::InvalidExpression`] if the expression is malformed. It
+    ///   holds the offending token and its byte offset in `expr`.
+    /// * The errors of [`WorkCalendar::next_work_day`] if a work-day term cannot be
+    ///   resolved, and [`WorkCalendarError::DateOutOfRange`] if a calendar-day term
+    ///   leaves the representable date range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use workdays::{WorkCalendar, WorkCalendarError};
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let mut anchors = HashMap::new();
+    /// anchors.insert("kickoff".to_string(), NaiveDate::from_ymd_opt(2025, 6, 27).unwrap());
+    ///
+    /// assert_eq!(
+    ///     calendar.eval_expr("kickoff + 10wd", &anchors).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2025, 7, 11).unwrap()
+    /// );
+    /// assert_eq!(
+    ///     calendar.eval_expr("2025-07-01 - 3d + 1wd", &anchors).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2025, 6, 30).unwrap()
+    /// );
+    /// assert_eq!(
+    ///     calendar.eval_expr("launch + 2wd", &anchors),
+    ///     Err(WorkCalendarError::UnknownAnchor { name: "launch".to_string(), position: 0 })
+    /// );
+    /// ```
+    pub fn eval_expr(
+        &self,
+        expr: &str,
+        anchors: &HashMap<String, NaiveDate>,
+    ) -> Result<NaiveDate, WorkCalendarError> {
+        let mut cursor = Cursor {
+            input: expr,
+            position: 0,
+        };
+        cursor.skip_whitespace();
+        let mut date = parse_anchor(&mut cursor, anchors)?;
+        loop {
+            cursor.skip_whitespace();
+            if cursor.is_done() {
+                return Ok(date);
+            }
+            let sign = match cursor.rest().chars().next() {
+                Some('+') => 1,
+                Some('-') => -1,
+                _ => return Err(cursor.error("expected '+' or '-'")),
+            };
+            cursor.position += 1;
+            cursor.skip_whitespace();
+            let (count, unit) = parse_term(&mut cursor)?;
+            date = self.apply_term(date, sign * count, unit)?;
+        }
+    }
+
+    fn apply_term(
+        &self,
+        date: NaiveDate,
+        count: i64,
+        unit: Unit,
+    ) -> Result<NaiveDate, WorkCalendarError> {
+        match unit {
+            Unit::WorkDays => self.try_add_work_days(date, count),
+            Unit::Days => {
+                let days = Days::new(count.unsigned_abs());
+                let moved = if count < 0 {
+                    date.checked_sub_days(days)
+                } else {
+                    date.checked_add_days(days)
+                };
+                moved.ok_or_else(|| WorkCalendarError::DateOutOfRange {
+                    calendar: self.name().map(String::from),
+                })
+            }
+        }
+    }
+}
+
+/// Parses the anchor at the start of an expression.
+fn parse_anchor(
+    cursor: &mut Cursor,
+    anchors: &HashMap<String, NaiveDate>,
+) -> Result<NaiveDate, WorkCalendarError> {
+    let start = cursor.position;
+    match cursor.rest().chars().next() {
+        Some(c) if c.is_ascii_digit() => {
+            let literal = cursor
+                .rest()
+                .get(..10)
+                .and_then(|text| NaiveDate::parse_from_str(text, "%Y-%m-%d").ok());
+            match literal {
+                Some(date) => {
+                    cursor.position += 10;
+                    Ok(date)
+                }
+                None => Err(cursor.error("expected a YYYY-MM-DD date")),
+            }
+        }
+        Some(c) if is_word(c) => {
+            let name = cursor.take_while(is_word);
+            anchors
+                .get(name)
+                .copied()
+                .ok_or_else(|| WorkCalendarError::UnknownAnchor {
+                    name: name.to_string(),
+                    position: start,
+                })
+        }
+        _ => Err(cursor.error("expected an anchor name or date")),
+    }
+}
+
+/// Parses a term's count and unit, after its sign.
+fn parse_term(cursor: &mut Cursor) -> Result<(i64, Unit), WorkCalendarError> {
+    if !cursor.rest().starts_with(|c: char| c.is_ascii_digit()) {
+        return Err(cursor.error("expected a number"));
+    }
+    let number_cursor = Cursor {
+        input: cursor.input,
+        position: cursor.position,
+    };
+    let digits = cursor.take_while(|c| c.is_ascii_digit());
+    let count: i64 = digits
+        .parse()
+        .map_err(|_| number_cursor.error("number is too large"))?;
+    cursor.skip_whitespace();
+    let unit_start = cursor.position;
+    let unit = cursor.take_while(is_word);
+    let unit = if unit.eq_ignore_ascii_case("wd") {
+        Unit::WorkDays
+    } else if unit.eq_ignore_ascii_case("d") {
+        Unit::Days
+    } else {
+        cursor.position = unit_start;
+        return Err(if unit.is_empty() {
+            number_cursor.error("expected a unit ('wd' or 'd') after the number")
+        } else {
+            cursor.error("unknown unit; expected 'wd' or 'd'")
+        });
+    };
+    Ok((count, unit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::date;
+
+    fn anchors() -> HashMap<String, NaiveDate> {
+        let mut anchors = HashMap::new();
+        // Friday, June 27, 2025.
+        anchors.insert("kickoff".to_string(), date(2025, 6, 27));
+        // Wednesday, July 9, 2025.
+        anchors.insert("code_freeze".to_string(), date(2025, 7, 9));
+        anchors.insert("launch2".to_string(), date(2025, 7, 5));
+        anchors
+    }
+
+    #[test]
+    fn test_eval_expr() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2025, 7, 4));
+        let anchors = anchors();
+        let cases = [
+            ("kickoff", date(2025, 6, 27)),
+            ("  kickoff  ", date(2025, 6, 27)),
+            ("kickoff + 0wd", date(2025, 6, 27)),
+            ("kickoff + 1wd", date(2025, 6, 30)),
+            ("kickoff + 10wd", date(2025, 7, 14)),
+            ("kickoff+10wd", date(2025, 7, 14)),
+            ("kickoff + 10 WD", date(2025, 7, 14)),
+            ("kickoff + 3d", date(2025, 6, 30)),
+            ("code_freeze - 3d", date(2025, 7, 6)),
+            ("code_freeze - 3wd", date(2025, 7, 3)),
+            ("code_freeze - 4wd", date(2025, 7, 2)),
+            ("launch2 + 2wd + 1d", date(2025, 7, 9)),
+            ("launch2 + 1d + 2wd", date(2025, 7, 8)),
+            // Zero work days from a weekend stay on it.
+            ("launch2 - 0wd", date(2025, 7, 5)),
+            ("kickoff + 10wd - 3d", date(2025, 7, 11)),
+            ("2025-07-01", date(2025, 7, 1)),
+            ("2025-07-01 + 3wd", date(2025, 7, 7)),
+            ("2025-07-01-1d", date(2025, 6, 30)),
+            ("2025-07-01 + 007d", date(2025, 7, 8)),
+        ];
+        for (expr, expected) in cases {
+            assert_eq!(
+                calendar.eval_expr(expr, &anchors),
+                Ok(expected),
+                "{:?}",
+                expr
+            );
+        }
+    }
+
+    #[test]
+    fn test_errors_point_at_the_offending_token() {
+        let calendar = WorkCalendar::new();
+        let anchors = anchors();
+        let invalid = |token: &str, position, reason| WorkCalendarError::InvalidExpression {
+            token: token.to_string(),
+            position,
+            reason,
+        };
+        let cases = [
+            (
+                "launch + 2wd",
+                WorkCalendarError::UnknownAnchor {
+                    name: "launch".to_string(),
+                    position: 0,
+                },
+            ),
+            (
+                "  Kickoff",
+                WorkCalendarError::UnknownAnchor {
+                    name: "Kickoff".to_string(),
+                    position: 2,
+                },
+            ),
+            ("", invalid("", 0, "expected an anchor name or date")),
+            ("+ 3wd", invalid("+", 0, "expected an anchor name or date")),
+            (
+                "2025-02-30 + 1d",
+                invalid("2025", 0, "expected a YYYY-MM-DD date"),
+            ),
+            ("2025-7-1", invalid("2025", 0, "expected a YYYY-MM-DD date")),
+            (
+                "kickoff + 10wk",
+                invalid("wk", 12, "unknown unit; expected 'wd' or 'd'"),
+            ),
+            (
+                "kickoff + 10 days",
+                invalid("days", 13, "unknown unit; expected 'wd' or 'd'"),
+            ),
+            (
+                "kickoff + 10",
+                invalid("10", 10, "expected a unit ('wd' or 'd') after the number"),
+            ),
+            (
+                "kickoff + 10 + 1d",
+                invalid("10", 10, "expected a unit ('wd' or 'd') after the number"),
+            ),
+            ("kickoff + wd", invalid("wd", 10, "expected a number")),
+            ("kickoff + -3d", invalid("-", 10, "expected a number")),
+            (
+                "kickoff + 1.5d",
+                invalid("1", 10, "expected a unit ('wd' or 'd') after the number"),
+            ),
+            ("kickoff 3d", invalid("3d", 8, "expected '+' or '-'")),
+            ("kickoff * 3d", invalid("*", 8, "expected '+' or '-'")),
+            ("kickoff + 3d x", invalid("x", 13, "expected '+' or '-'")),
+            (
+                "kickoff + 99999999999999999999d",
+                invalid("99999999999999999999d", 10, "number is too large"),
+            ),
+        ];
+        for (expr, expected) in cases {
+            assert_eq!(
+                calendar.eval_expr(expr, &anchors),
+                Err(expected),
+                "{:?}",
+                expr
+            );
+        }
+        assert_eq!(
+            calendar
+                .eval_expr("kickoff + 10wk", &anchors)
+                .unwrap_err()
+                .to_string(),
+            "Invalid date expression at position 12 ('wk'): unknown unit; expected 'wd' or 'd'"
+        );
+        assert_eq!(
+            calendar
+                .eval_expr("launch", &anchors)
+                .unwrap_err()
+                .to_string(),
+            "Unknown anchor 'launch' at position 0"
+        );
+    }
+
+    #[test]
+    fn test_calendar_errors() {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("Ops");
+        let anchors = HashMap::new();
+        let out_of_range = Err(WorkCalendarError::DateOutOfRange {
+            calendar: Some("Ops".to_string()),
+        });
+        assert_eq!(
+            calendar.eval_expr("2025-07-01 + 9223372036854775807d", &anchors),
+            out_of_range
+        );
+        assert_eq!(
+            calendar.eval_expr("2025-07-01 - 999999999d", &anchors),
+            out_of_range
+        );
+        calendar.set_work_days("Sat").unwrap();
+        calendar.remove_work_day(&chrono::Weekday::Sat);
+        assert_eq!(
+            calendar.eval_expr("2025-07-01 + 1wd", &anchors),
+            Err(WorkCalendarError::NoWorkDaysDefined {
+                calendar: Some("Ops".to_string())
+            })
+        );
+    }
+}
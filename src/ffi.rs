@@ -0,0 +1,400 @@
+//! C bindings, available with the `ffi` feature.
+//!
+//! Every function returns one of the `WORKDAYS_*` status codes and writes its result
+//! through an out pointer. Panics are caught at the boundary and reported as
+//! [`WORKDAYS_ERR_PANIC`], so no unwinding ever crosses into the caller. The matching
+//! C header is `include/workdays.h`, generated by cbindgen from this module.
+//!
+//! The crate builds only as a Rust library by default. To produce a shared or static
+//! library for C callers, choose the crate type when building:
+//!
+//! ```text
+//! cargo rustc --release --features ffi --crate-type cdylib
+//! cargo rustc --release --features ffi --crate-type staticlib
+//! ```
+
+#![allow(non_camel_case_types)]
+
+use crate::{WorkCalendar, WorkCalendarError};
+use chrono::{Datelike, NaiveDate};
+use std::{
+    ffi::{c_char, CStr},
+    panic::{catch_unwind, UnwindSafe},
+    ptr,
+    str::FromStr,
+};
+
+/// The call succeeded.
+pub const WORKDAYS_OK: i32 = 0;
+/// A required pointer argument was null.
+pub const WORKDAYS_ERR_NULL_POINTER: i32 = -1;
+/// A year, month, and day did not form a valid date.
+pub const WORKDAYS_ERR_INVALID_DATE: i32 = -2;
+/// The configuration could not be parsed or was not valid UTF-8.
+pub const WORKDAYS_ERR_INVALID_CONFIG: i32 = -3;
+/// A negative number of work days was requested.
+pub const WORKDAYS_ERR_NEGATIVE_DAYS: i32 = -4;
+/// The calendar has no work days.
+pub const WORKDAYS_ERR_NO_WORK_DAYS: i32 = -5;
+/// No working date was found before the search horizon was exhausted.
+pub const WORKDAYS_ERR_NO_WORKING_DAYS_IN_HORIZON: i32 = -6;
+/// The result would fall outside the range of representable dates.
+pub const WORKDAYS_ERR_DATE_OUT_OF_RANGE: i32 = -7;
+/// Any other calendar error.
+pub const WORKDAYS_ERR_OTHER: i32 = -8;
+/// The library panicked; the calendar should be considered unusable.
+pub const WORKDAYS_ERR_PANIC: i32 = -99;
+
+/// An opaque handle to a work calendar.
+pub struct workdays_calendar_t(WorkCalendar);
+
+/// Creates a calendar working Monday to Friday with no holidays.
+///
+/// The returned handle must be released with [`workdays_calendar_destroy`]. Returns
+/// null only if the library panicked.
+#[no_mangle]
+pub extern "C" fn workdays_calendar_create() -> *mut workdays_calendar_t {
+    catch_unwind(|| Box::into_raw(Box::new(workdays_calendar_t(WorkCalendar::new()))))
+        .unwrap_or(ptr::null_mut())
+}
+
+/// Releases a calendar. Passing null is allowed and does nothing.
+///
+/// # Safety
+///
+/// `calendar` must be null or a handle returned by this library that has not already
+/// been destroyed.
+#[no_mangle]
+pub unsafe extern "C" fn workdays_calendar_destroy(calendar: *mut workdays_calendar_t) {
+    if !calendar.is_null() {
+        let calendar = unsafe { Box::from_raw(calendar) };
+        let _ = catch_unwind(move || drop(calendar));
+    }
+}
+
+/// Creates a calendar from a YAML or JSON configuration.
+///
+/// On success stores the new handle in `*out`. On failure stores null in `*out` and,
+/// if `error_buf` is not null, writes the NUL-terminated error message into it,
+/// truncated to `error_buf_len` bytes including the terminator.
+///
+/// # Safety
+///
+/// `config` must be a NUL-terminated string, `out` must be valid for writes, and
+/// `error_buf` must be null or valid for writes of `error_buf_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn workdays_calendar_from_config(
+    config: *const c_char,
+    out: *mut *mut workdays_calendar_t,
+    error_buf: *mut c_char,
+    error_buf_len: usize,
+) -> i32 {
+    if config.is_null() || out.is_null() {
+        return WORKDAYS_ERR_NULL_POINTER;
+    }
+    unsafe { *out = ptr::null_mut() };
+    let config = unsafe { CStr::from_ptr(config) };
+    let result = catch_unwind(|| {
+        let config = config.to_str().map_err(|error| error.to_string())?;
+        WorkCalendar::from_str(config).map_err(|error| error.to_string())
+    });
+    match result {
+        Ok(Ok(calendar)) => {
+            unsafe { *out = Box::into_raw(Box::new(workdays_calendar_t(calendar))) };
+            WORKDAYS_OK
+        }
+        Ok(Err(message)) => {
+            unsafe { write_message(&message, error_buf, error_buf_len) };
+            WORKDAYS_ERR_INVALID_CONFIG
+        }
+        Err(_) => WORKDAYS_ERR_PANIC,
+    }
+}
+
+/// Computes the date on which the `days`-th work day from the start date falls, and
+/// stores it in `*out_year`, `*out_month`, and `*out_day`.
+///
+/// # Safety
+///
+/// `calendar` must be a live handle and the out pointers must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn workdays_compute_end_date(
+    calendar: *const workdays_calendar_t,
+    year: i32,
+    month: i32,
+    day: i32,
+    days: i64,
+    out_year: *mut i32,
+    out_month: *mut i32,
+    out_day: *mut i32,
+) -> i32 {
+    if calendar.is_null() || out_year.is_null() || out_month.is_null() || out_day.is_null() {
+        return WORKDAYS_ERR_NULL_POINTER;
+    }
+    let calendar = unsafe { &(*calendar).0 };
+    guard(|| {
+        let start = to_date(year, month, day)?;
+        let end = calendar.compute_end(start, days).map_err(status)?;
+        unsafe {
+            *out_year = end.date.year();
+            *out_month = end.date.month() as i32;
+            *out_day = end.date.day() as i32;
+        }
+        Ok(())
+    })
+}
+
+/// Counts the working dates between two dates, inclusive, and stores the count in
+/// `*out_days`. The count is zero when the start is after the end.
+///
+/// # Safety
+///
+/// `calendar` must be a live handle and `out_days` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn workdays_between(
+    calendar: *const workdays_calendar_t,
+    start_year: i32,
+    start_month: i32,
+    start_day: i32,
+    end_year: i32,
+    end_month: i32,
+    end_day: i32,
+    out_days: *mut i64,
+) -> i32 {
+    if calendar.is_null() || out_days.is_null() {
+        return WORKDAYS_ERR_NULL_POINTER;
+    }
+    let calendar = unsafe { &(*calendar).0 };
+    guard(|| {
+        let start = to_date(start_year, start_month, start_day)?;
+        let end = to_date(end_year, end_month, end_day)?;
+        let days = calendar.work_days_between(start, end);
+        unsafe { *out_days = days };
+        Ok(())
+    })
+}
+
+/// Stores whether a date is a working date in `*out_is_working`.
+///
+/// # Safety
+///
+/// `calendar` must be a live handle and `out_is_working` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn workdays_is_working(
+    calendar: *const workdays_calendar_t,
+    year: i32,
+    month: i32,
+    day: i32,
+    out_is_working: *mut bool,
+) -> i32 {
+    if calendar.is_null() || out_is_working.is_null() {
+        return WORKDAYS_ERR_NULL_POINTER;
+    }
+    let calendar = unsafe { &(*calendar).0 };
+    guard(|| {
+        let is_working = calendar.is_working_date(&to_date(year, month, day)?);
+        unsafe { *out_is_working = is_working };
+        Ok(())
+    })
+}
+
+/// Runs `body`, converting its outcome, or a panic, into a status code.
+fn guard(body: impl FnOnce() -> Result<(), i32> + UnwindSafe) -> i32 {
+    match catch_unwind(body) {
+        Ok(Ok(())) => WORKDAYS_OK,
+        Ok(Err(code)) => code,
+        Err(_) => WORKDAYS_ERR_PANIC,
+    }
+}
+
+fn to_date(year: i32, month: i32, day: i32) -> Result<NaiveDate, i32> {
+    let month = u32::try_from(month).map_err(|_| WORKDAYS_ERR_INVALID_DATE)?;
+    let day = u32::try_from(day).map_err(|_| WORKDAYS_ERR_INVALID_DATE)?;
+    NaiveDate::from_ymd_opt(year, month, day).ok_or(WORKDAYS_ERR_INVALID_DATE)
+}
+
+fn status(error: WorkCalendarError) -> i32 {
+    match error {
+        WorkCalendarError::NegativeDaysWorked { .. } => WORKDAYS_ERR_NEGATIVE_DAYS,
+        WorkCalendarError::NoWorkDaysDefined { .. } => WORKDAYS_ERR_NO_WORK_DAYS,
+        WorkCalendarError::NoWorkingDaysInHorizon { .. } => WORKDAYS_ERR_NO_WORKING_DAYS_IN_HORIZON,
+        WorkCalendarError::DateOutOfRange { .. } => WORKDAYS_ERR_DATE_OUT_OF_RANGE,
+        _ => WORKDAYS_ERR_OTHER,
+    }
+}
+
+/// Copies `message` into `buf` as a NUL-terminated string, truncating it to fit
+/// without splitting a UTF-8 sequence.
+unsafe fn write_message(message: &str, buf: *mut c_char, len: usize) {
+    if buf.is_null() || len == 0 {
+        return;
+    }
+    let mut end = message.len().min(len - 1);
+    while !message.is_char_boundary(end) {
+        end -= 1;
+    }
+    unsafe {
+        ptr::copy_nonoverlapping(message.as_ptr().cast::<c_char>(), buf, end);
+        *buf.add(end) = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Declared as C callers see them in `include/workdays.h`, so the tests go through
+    // the exported symbols rather than the Rust paths.
+    mod abi {
+        use super::c_char;
+
+        /// The opaque type as C sees it.
+        #[repr(C)]
+        pub struct workdays_calendar_t {
+            _private: [u8; 0],
+        }
+
+        extern "C" {
+            pub fn workdays_calendar_create() -> *mut workdays_calendar_t;
+            pub fn workdays_calendar_destroy(calendar: *mut workdays_calendar_t);
+            pub fn workdays_calendar_from_config(
+                config: *const c_char,
+                out: *mut *mut workdays_calendar_t,
+                error_buf: *mut c_char,
+                error_buf_len: usize,
+            ) -> i32;
+            pub fn workdays_compute_end_date(
+                calendar: *const workdays_calendar_t,
+                year: i32,
+                month: i32,
+                day: i32,
+                days: i64,
+                out_year: *mut i32,
+                out_month: *mut i32,
+                out_day: *mut i32,
+            ) -> i32;
+            pub fn workdays_between(
+                calendar: *const workdays_calendar_t,
+                start_year: i32,
+                start_month: i32,
+                start_day: i32,
+                end_year: i32,
+                end_month: i32,
+                end_day: i32,
+                out_days: *mut i64,
+            ) -> i32;
+            pub fn workdays_is_working(
+                calendar: *const workdays_calendar_t,
+                year: i32,
+                month: i32,
+                day: i32,
+                out_is_working: *mut bool,
+            ) -> i32;
+        }
+    }
+
+    fn end_date(
+        calendar: *const abi::workdays_calendar_t,
+        days: i64,
+    ) -> Result<(i32, i32, i32), i32> {
+        let (mut year, mut month, mut day) = (0, 0, 0);
+        let status = unsafe {
+            abi::workdays_compute_end_date(
+                calendar, 2023, 12, 22, days, &mut year, &mut month, &mut day,
+            )
+        };
+        match status {
+            WORKDAYS_OK => Ok((year, month, day)),
+            code => Err(code),
+        }
+    }
+
+    #[test]
+    fn test_config_round_trip_through_c_symbols() {
+        let config = b"{\"holidays\": [\"2023-12-25\"]}\0";
+        let mut calendar = ptr::null_mut();
+        let status = unsafe {
+            abi::workdays_calendar_from_config(
+                config.as_ptr().cast(),
+                &mut calendar,
+                ptr::null_mut(),
+                0,
+            )
+        };
+        assert_eq!(status, WORKDAYS_OK);
+        assert!(!calendar.is_null());
+
+        assert_eq!(end_date(calendar, 2), Ok((2023, 12, 26)));
+        assert_eq!(end_date(calendar, -1), Err(WORKDAYS_ERR_NEGATIVE_DAYS));
+
+        let mut days = 0;
+        let status =
+            unsafe { abi::workdays_between(calendar, 2023, 12, 18, 2023, 12, 31, &mut days) };
+        assert_eq!((status, days), (WORKDAYS_OK, 9));
+
+        let mut is_working = true;
+        let status = unsafe { abi::workdays_is_working(calendar, 2023, 12, 25, &mut is_working) };
+        assert_eq!((status, is_working), (WORKDAYS_OK, false));
+        let status = unsafe { abi::workdays_is_working(calendar, 2023, 2, 30, &mut is_working) };
+        assert_eq!(status, WORKDAYS_ERR_INVALID_DATE);
+
+        unsafe { abi::workdays_calendar_destroy(calendar) };
+    }
+
+    #[test]
+    fn test_config_errors_are_written_and_truncated() {
+        let config = b"{\"name\": \"Ops\", \"work_days\": []}\0";
+        let mut calendar = ptr::null_mut();
+        let mut buf = [1 as c_char; 16];
+        let status = unsafe {
+            abi::workdays_calendar_from_config(
+                config.as_ptr().cast(),
+                &mut calendar,
+                buf.as_mut_ptr(),
+                buf.len(),
+            )
+        };
+        assert_eq!(status, WORKDAYS_ERR_INVALID_CONFIG);
+        assert!(calendar.is_null());
+        let message = unsafe { CStr::from_ptr(buf.as_ptr()) };
+        assert_eq!(message.to_str().unwrap(), "work_days must ");
+    }
+
+    #[test]
+    fn test_null_arguments_are_rejected() {
+        let calendar = unsafe { abi::workdays_calendar_create() };
+        let mut days = 0;
+        unsafe {
+            assert_eq!(
+                abi::workdays_between(ptr::null(), 2024, 1, 1, 2024, 1, 2, &mut days),
+                WORKDAYS_ERR_NULL_POINTER
+            );
+            assert_eq!(
+                abi::workdays_is_working(calendar, 2024, 1, 1, ptr::null_mut()),
+                WORKDAYS_ERR_NULL_POINTER
+            );
+            assert_eq!(
+                abi::workdays_calendar_from_config(
+                    ptr::null(),
+                    &mut ptr::null_mut(),
+                    ptr::null_mut(),
+                    0
+                ),
+                WORKDAYS_ERR_NULL_POINTER
+            );
+            abi::workdays_calendar_destroy(calendar);
+            abi::workdays_calendar_destroy(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_write_message_keeps_utf8_boundaries() {
+        let mut buf = [1 as c_char; 4];
+        unsafe { write_message("añb", buf.as_mut_ptr(), buf.len()) };
+        let message = unsafe { CStr::from_ptr(buf.as_ptr()) };
+        assert_eq!(message.to_str().unwrap(), "añ");
+        unsafe { write_message("añb", buf.as_mut_ptr(), 3) };
+        let message = unsafe { CStr::from_ptr(buf.as_ptr()) };
+        assert_eq!(message.to_str().unwrap(), "a");
+    }
+}
@@ -0,0 +1,391 @@
+//! A stable content fingerprint for calendars.
+
+use crate::rules::{Holiday, HolidayLayer, HolidayRule};
+use crate::{DayHalf, WorkCalendar};
+use chrono::{Datelike, NaiveDate};
+
+/// The version of the canonical encoding, written first so that a future change to the
+/// encoding cannot collide with fingerprints computed by this one.
+const ENCODING_VERSION: u8 = 1;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// A 64-bit FNV-1a hash of the bytes written to it.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Fnv1a(FNV_OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_u8(&mut self, value: u8) {
+        self.write(&[value]);
+    }
+
+    fn write_len(&mut self, len: usize) {
+        self.write(&(len as u64).to_le_bytes());
+    }
+
+    fn write_date(&mut self, date: &NaiveDate) {
+        self.write(&date.num_days_from_ce().to_le_bytes());
+    }
+
+    fn write_str(&mut self, value: &str) {
+        self.write_len(value.len());
+        self.write(value.as_bytes());
+    }
+
+    /// Writes a length-prefixed list of encoded entries in sorted order, so the result
+    /// does not depend on the order the entries were added in.
+    fn write_sorted(&mut self, mut entries: Vec<Vec<u8>>) {
+        entries.sort_unstable();
+        self.write_len(entries.len());
+        for entry in &entries {
+            self.write_len(entry.len());
+            self.write(entry);
+        }
+    }
+}
+
+fn encode_date(date: &NaiveDate, out: &mut Vec<u8>) {
+    out.extend_from_slice(&date.num_days_from_ce().to_le_bytes());
+}
+
+fn encode_rule(rule: &HolidayRule, out: &mut Vec<u8>) {
+    match *rule {
+        HolidayRule::FixedDate { month, day } => {
+            out.push(0);
+            out.extend_from_slice(&month.to_le_bytes());
+            out.extend_from_slice(&day.to_le_bytes());
+        }
+        HolidayRule::NthWeekday {
+            month,
+            weekday,
+            nth,
+        } => {
+            out.push(1);
+            out.extend_from_slice(&month.to_le_bytes());
+            out.push(weekday.num_days_from_monday() as u8);
+            out.extend_from_slice(&nth.to_le_bytes());
+        }
+    }
+}
+
+fn encode_layer(layer: &HolidayLayer) -> Vec<u8> {
+    let mut out = Vec::new();
+    match &layer.holiday {
+        Holiday::Date(date) => {
+            out.push(0);
+            encode_date(date, &mut out);
+        }
+        Holiday::Rule(rule) => {
+            out.push(1);
+            encode_rule(rule, &mut out);
+        }
+    }
+    encode_date(&layer.effective_from, &mut out);
+    match &layer.effective_to {
+        Some(to) => {
+            out.push(1);
+            encode_date(to, &mut out);
+        }
+        None => out.push(0),
+    }
+    out
+}
+
+impl WorkCalendar {
+    /// Returns a fingerprint of the calendar's contents, for keying caches of results
+    /// derived from it.
+    ///
+    /// The fingerprint covers everything that decides which dates are working dates and
+    /// how they are counted: the work days and their scheduled changes, holidays,
+    /// partial holidays, extra working dates, recurring rules and layers, holiday
+    /// categories, the ordinal epoch, and the hours per day when not the default. The
+    /// name is not included, so a renamed copy has the same fingerprint. Equal
+    /// calendars always have equal fingerprints, however they were built: the order in
+    /// which holidays, rules and work days were added does not matter.
+    ///
+    /// # Algorithm
+    ///
+    /// The contents are written as a versioned, canonical byte stream (sets in
+    /// ascending order, rules and layers sorted by their encoding, integers in
+    /// little-endian form, dates as days since the Common Era) and hashed with 64-bit
+    /// FNV-1a. Neither the encoding nor the hash depends on the platform, the process,
+    /// or [`std::hash::Hasher`] implementations, so a fingerprint can be stored and
+    /// compared later. The value for a given calendar will not change in patch
+    /// releases; a change to the encoding, if ever needed, comes with a minor release
+    /// and a new encoding version.
+    ///
+    /// The fingerprint is not a cryptographic hash. Different calendars are very
+    /// unlikely to collide, but the value must not be relied on to detect deliberate
+    /// tampering.
+    ///
+    /// # Returns
+    ///
+    /// The 64-bit fingerprint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let new_year = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+    /// let christmas = NaiveDate::from_ymd_opt(2025, 12, 25).unwrap();
+    ///
+    /// let mut a = WorkCalendar::new();
+    /// a.add_holiday(new_year);
+    /// a.add_holiday(christmas);
+    ///
+    /// let mut b = WorkCalendar::new();
+    /// b.add_holiday(christmas);
+    /// b.add_holiday(new_year);
+    /// assert_eq!(a.fingerprint(), b.fingerprint());
+    ///
+    /// b.remove_holiday(&christmas);
+    /// assert_ne!(a.fingerprint(), b.fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        let mut hash = Fnv1a::new();
+        hash.write_u8(ENCODING_VERSION);
+        hash.write_u8(self.work_days.mask());
+
+        hash.write_len(self.work_days_effective.len());
+        for (from, week) in self.work_days_effective.iter() {
+            hash.write_date(from);
+            hash.write_u8(week.mask());
+        }
+
+        for dates in [&self.holidays, &self.working_dates] {
+            hash.write_len(dates.len());
+            for date in dates.iter() {
+                hash.write_date(date);
+            }
+        }
+
+        hash.write_len(self.partial_holidays.len());
+        for (date, half) in self.partial_holidays.iter() {
+            hash.write_date(date);
+            hash.write_u8(match half {
+                DayHalf::Morning => 0,
+                DayHalf::Afternoon => 1,
+            });
+        }
+
+        hash.write_sorted(
+            self.recurring_holidays
+                .rules()
+                .iter()
+                .map(|rule| {
+                    let mut out = Vec::new();
+                    encode_rule(rule, &mut out);
+                    out
+                })
+                .collect(),
+        );
+        hash.write_sorted(
+            self.recurring_holidays
+                .layers()
+                .iter()
+                .map(encode_layer)
+                .collect(),
+        );
+
+        let dates: Vec<_> = self.holiday_categories.dates().collect();
+        hash.write_len(dates.len());
+        for (date, category) in dates {
+            hash.write_date(date);
+            hash.write_str(category);
+        }
+        hash.write_sorted(
+            self.holiday_categories
+                .rules()
+                .map(|(rule, category)| {
+                    let mut out = Vec::new();
+                    encode_rule(rule, &mut out);
+                    out.extend_from_slice(&(category.len() as u64).to_le_bytes());
+                    out.extend_from_slice(category.as_bytes());
+                    out
+                })
+                .collect(),
+        );
+
+        hash.write_date(&self.ordinal_epoch);
+        // Written only when set, so calendars without it keep their fingerprints.
+        if !self.hours_per_day.is_default() {
+            hash.write(&self.hours_per_day.0.to_bits().to_le_bytes());
+        }
+        hash.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{date, random_calendar, TestRng};
+    use chrono::Weekday;
+    use std::str::FromStr;
+
+    fn thanksgiving() -> HolidayRule {
+        HolidayRule::NthWeekday {
+            month: 11,
+            weekday: Weekday::Thu,
+            nth: 4,
+        }
+    }
+
+    fn christmas() -> HolidayRule {
+        HolidayRule::FixedDate { month: 12, day: 25 }
+    }
+
+    #[test]
+    fn test_fnv1a_reference_values() {
+        let hash = |bytes: &[u8]| {
+            let mut hash = Fnv1a::new();
+            hash.write(bytes);
+            hash.0
+        };
+        assert_eq!(hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(hash(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn test_same_calendar_built_differently() {
+        let mut forward = WorkCalendar::new();
+        forward.add_work_day(Weekday::Sat);
+        forward.add_holiday(date(2024, 1, 1));
+        forward.add_holiday(date(2024, 7, 4));
+        forward.add_working_date(date(2024, 3, 10));
+        forward.add_recurring_holiday(thanksgiving());
+        forward.add_recurring_holiday(christmas());
+
+        let mut backward = WorkCalendar::new();
+        backward.add_recurring_holiday(christmas());
+        backward.add_recurring_holiday(thanksgiving());
+        backward.add_working_date(date(2024, 3, 10));
+        backward.add_holiday(date(2024, 7, 4));
+        backward.add_holiday(date(2024, 1, 1));
+        backward.remove_work_day(&Weekday::Mon);
+        backward.add_work_day(Weekday::Sat);
+        backward.add_work_day(Weekday::Mon);
+
+        let mut edited = forward.clone();
+        edited.add_holiday(date(2024, 5, 27));
+        edited.remove_holiday(&date(2024, 5, 27));
+        edited.set_name("Renamed");
+
+        let json = serde_json::to_string(&forward).unwrap();
+        let from_json: WorkCalendar = serde_json::from_str(&json).unwrap();
+        let yaml = serde_yaml::to_string(&backward).unwrap();
+        let from_yaml: WorkCalendar = serde_yaml::from_str(&yaml).unwrap();
+
+        let expected = forward.fingerprint();
+        for calendar in [&backward, &edited, &from_json, &from_yaml] {
+            assert_eq!(calendar.fingerprint(), expected, "{:?}", calendar);
+        }
+
+        let mut changed = forward.clone();
+        changed.add_holiday(date(2024, 12, 31));
+        assert_ne!(changed.fingerprint(), expected);
+
+        // The configuration format has no recurring rules, so compare without them.
+        let configured = WorkCalendar::from_str(
+            "work_days: [Sat, Fri, Thu, Wed, Tue, Mon]\n\
+             holidays: [2024-07-04, 2024-01-01]\n\
+             extra_work_days: [2024-03-10]\n",
+        )
+        .unwrap();
+        let mut without_rules = forward.clone();
+        without_rules.remove_recurring_holiday(&christmas());
+        without_rules.remove_recurring_holiday(&thanksgiving());
+        assert_eq!(configured.fingerprint(), without_rules.fingerprint());
+        assert_ne!(configured.fingerprint(), expected);
+    }
+
+    #[test]
+    fn test_every_component_changes_the_fingerprint() {
+        let base = WorkCalendar::new();
+        let mut variants = Vec::new();
+
+        let mut calendar = base.clone();
+        calendar.remove_work_day(&Weekday::Fri);
+        variants.push(calendar);
+        let mut calendar = base.clone();
+        calendar
+            .set_work_days_effective(date(2024, 1, 1), "Mon,Tue,Wed,Thu")
+            .unwrap();
+        variants.push(calendar);
+        let mut calendar = base.clone();
+        calendar.add_holiday(date(2024, 1, 2));
+        variants.push(calendar);
+        let mut calendar = base.clone();
+        calendar.add_working_date(date(2024, 1, 2));
+        variants.push(calendar);
+        let mut calendar = base.clone();
+        calendar.add_partial_holiday_half(date(2024, 1, 2), DayHalf::Morning);
+        variants.push(calendar);
+        let mut calendar = base.clone();
+        calendar.add_partial_holiday_half(date(2024, 1, 2), DayHalf::Afternoon);
+        variants.push(calendar);
+        let mut calendar = base.clone();
+        calendar.add_recurring_holiday(christmas());
+        variants.push(calendar);
+        let mut calendar = base.clone();
+        calendar.add_recurring_holiday_with_category(christmas(), "public");
+        variants.push(calendar);
+        let mut calendar = base.clone();
+        calendar.add_holiday_with_category(date(2024, 1, 2), "public");
+        variants.push(calendar);
+        let mut calendar = base.clone();
+        calendar.set_ordinal_epoch(date(2000, 1, 3));
+        variants.push(calendar);
+
+        let mut seen = vec![base.fingerprint()];
+        for calendar in &variants {
+            let fingerprint = calendar.fingerprint();
+            assert!(!seen.contains(&fingerprint), "{:?}", calendar);
+            seen.push(fingerprint);
+        }
+    }
+
+    #[test]
+    fn test_equal_calendars_have_equal_fingerprints_randomized() {
+        let mut rng = TestRng(0x5e0b_a7c3_19f2_d846);
+        for _ in 0..100 {
+            let calendar = random_calendar(&mut rng);
+            let json = serde_json::to_string(&calendar).unwrap();
+            let restored: WorkCalendar = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored, calendar);
+            assert_eq!(restored.fingerprint(), calendar.fingerprint());
+
+            let mut changed = calendar.clone();
+            let extra = (0..)
+                .map(|offset| date(2031, 1, 1) + chrono::Duration::days(offset))
+                .find(|day| !calendar.holidays().contains(day))
+                .unwrap();
+            changed.add_holiday(extra);
+            assert_ne!(changed.fingerprint(), calendar.fingerprint());
+        }
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable() {
+        // Stored fingerprints must stay valid; update these only with a new encoding
+        // version.
+        assert_eq!(WorkCalendar::new().fingerprint(), 8906314110405420975);
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2024, 12, 25));
+        calendar.add_recurring_holiday(thanksgiving());
+        assert_eq!(calendar.fingerprint(), 6033313566424599314);
+    }
+}
@@ -0,0 +1,339 @@
+//! 52/53-week fiscal calendars with 4-4-5 style periods.
+
+use crate::{CalendarSystem, Gregorian, WorkCalendar};
+use chrono::{Datelike, Days, Month, NaiveDate, Weekday};
+
+/// How the 13 weeks of each fiscal quarter are split into three periods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FiscalPattern {
+    /// Periods of 4, 4, and 5 weeks.
+    FourFourFive,
+    /// Periods of 4, 5, and 4 weeks.
+    FourFiveFour,
+    /// Periods of 5, 4, and 4 weeks.
+    FiveFourFour,
+}
+
+impl FiscalPattern {
+    fn weeks(self) -> [u32; 3] {
+        match self {
+            FiscalPattern::FourFourFive => [4, 4, 5],
+            FiscalPattern::FourFiveFour => [4, 5, 4],
+            FiscalPattern::FiveFourFour => [5, 4, 4],
+        }
+    }
+}
+
+/// The day a 52/53-week fiscal year ends on.
+///
+/// Every fiscal year ends on the same weekday, so most years have 52 weeks and the
+/// ones where the rule's date drifts past a week boundary have 53.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FiscalYearEnd {
+    /// The last `weekday` in `month`, such as the last Saturday in July.
+    Last { weekday: Weekday, month: Month },
+    /// The `weekday` nearest the last day of `month`, which may fall in the first
+    /// days of the next month, such as the Saturday nearest January 31.
+    Nearest { weekday: Weekday, month: Month },
+}
+
+/// A 52/53-week fiscal calendar, with each year split into four 13-week quarters of
+/// three periods each.
+///
+/// Fiscal year `N` is the one ending in (or, for [`FiscalYearEnd::Nearest`], just
+/// after) `month` of calendar year `N`. Calendars that name a year after the calendar
+/// year it starts in, such as the NRF retail calendar, number their years one lower.
+/// In a 53-week year the extra week goes to the twelfth period.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::{FiscalCalendar, FiscalPattern, FiscalYearEnd, WorkCalendar};
+/// use chrono::{Month, NaiveDate, Weekday};
+///
+/// // Ends on the last Saturday in September.
+/// let fiscal = FiscalCalendar::new(
+///     FiscalPattern::FourFourFive,
+///     FiscalYearEnd::Last { weekday: Weekday::Sat, month: Month::September },
+/// );
+/// let date = NaiveDate::from_ymd_opt(2023, 11, 20).unwrap();
+/// assert_eq!(fiscal.fiscal_period_of(date), Some((2024, 1, 2)));
+/// assert_eq!(
+///     fiscal.period_range(2024, 2),
+///     Some((
+///         NaiveDate::from_ymd_opt(2023, 10, 29).unwrap(),
+///         NaiveDate::from_ymd_opt(2023, 11, 25).unwrap(),
+///     ))
+/// );
+///
+/// let mut calendar = WorkCalendar::new();
+/// calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 11, 23).unwrap());
+/// assert_eq!(fiscal.work_days_in_period(&calendar, 2024, 2), 19);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FiscalCalendar {
+    pattern: FiscalPattern,
+    year_end: FiscalYearEnd,
+}
+
+impl FiscalCalendar {
+    /// Creates a fiscal calendar.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - How each quarter is split into periods.
+    /// * `year_end` - The rule that anchors each fiscal year's last day.
+    pub fn new(pattern: FiscalPattern, year_end: FiscalYearEnd) -> Self {
+        FiscalCalendar { pattern, year_end }
+    }
+
+    /// Returns the last day of fiscal year `year`, or `None` if it cannot be
+    /// represented.
+    pub fn year_end(&self, year: i32) -> Option<NaiveDate> {
+        let (weekday, month, nearest) = match self.year_end {
+            FiscalYearEnd::Last { weekday, month } => (weekday, month, false),
+            FiscalYearEnd::Nearest { weekday, month } => (weekday, month, true),
+        };
+        let month = month.number_from_month();
+        let month_end =
+            NaiveDate::from_ymd_opt(year, month, Gregorian.days_in_month(year, month)?)?;
+        let back =
+            (month_end.weekday().num_days_from_monday() + 7 - weekday.num_days_from_monday()) % 7;
+        if nearest && back > 3 {
+            month_end.checked_add_days(Days::new(u64::from(7 - back)))
+        } else {
+            month_end.checked_sub_days(Days::new(u64::from(back)))
+        }
+    }
+
+    /// Returns the first and last days of fiscal year `year`, or `None` if they cannot
+    /// be represented.
+    pub fn year_range(&self, year: i32) -> Option<(NaiveDate, NaiveDate)> {
+        let start = self.year_end(year.checked_sub(1)?)?.succ_opt()?;
+        Some((start, self.year_end(year)?))
+    }
+
+    /// Returns the number of weeks in fiscal year `year`: 52, or 53 in a leap-week
+    /// year.
+    pub fn weeks_in_year(&self, year: i32) -> Option<u32> {
+        let (start, end) = self.year_range(year)?;
+        u32::try_from(((end - start).num_days() + 1) / 7).ok()
+    }
+
+    /// Returns the fiscal year, quarter (1 to 4), and period (1 to 12) containing
+    /// `date`, or `None` near the ends of the representable date range.
+    pub fn fiscal_period_of(&self, date: NaiveDate) -> Option<(i32, u32, u32)> {
+        let mut year = date.year();
+        while date > self.year_end(year)? {
+            year += 1;
+        }
+        while date <= self.year_end(year - 1)? {
+            year -= 1;
+        }
+        let (start, _) = self.year_range(year)?;
+        let week = u32::try_from((date - start).num_days() / 7).ok()?;
+        let mut weeks_before = 0;
+        for period in 1..=12 {
+            weeks_before += self.period_weeks(year, period)?;
+            if week < weeks_before {
+                return Some((year, (period - 1) / 3 + 1, period));
+            }
+        }
+        None
+    }
+
+    /// Returns the first and last days of `period` (1 to 12) of fiscal year `year`, or
+    /// `None` if the period does not exist or cannot be represented.
+    pub fn period_range(&self, year: i32, period: u32) -> Option<(NaiveDate, NaiveDate)> {
+        if !(1..=12).contains(&period) {
+            return None;
+        }
+        let (year_start, _) = self.year_range(year)?;
+        let mut weeks_before = 0;
+        for earlier in 1..period {
+            weeks_before += self.period_weeks(year, earlier)?;
+        }
+        let start = year_start.checked_add_days(Days::new(u64::from(weeks_before) * 7))?;
+        let days = u64::from(self.period_weeks(year, period)?) * 7;
+        Some((start, start.checked_add_days(Days::new(days - 1))?))
+    }
+
+    /// Returns the number of working dates of `calendar` in `period` of fiscal year
+    /// `year`, or 0 if the period does not exist.
+    pub fn work_days_in_period(&self, calendar: &WorkCalendar, year: i32, period: u32) -> i64 {
+        match self.period_range(year, period) {
+            Some((start, end)) => calendar.work_days_between(start, end),
+            None => 0,
+        }
+    }
+
+    /// Returns the number of weeks in `period`, including the leap week if any.
+    fn period_weeks(&self, year: i32, period: u32) -> Option<u32> {
+        let weeks = self.pattern.weeks()[((period - 1) % 3) as usize];
+        if period == 12 && self.weeks_in_year(year)? == 53 {
+            Some(weeks + 1)
+        } else {
+            Some(weeks)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::date;
+
+    /// The NRF 4-5-4 retail calendar, whose years end on the Saturday nearest
+    /// January 31 and are named after the calendar year they start in.
+    fn nrf() -> FiscalCalendar {
+        FiscalCalendar::new(
+            FiscalPattern::FourFiveFour,
+            FiscalYearEnd::Nearest {
+                weekday: Weekday::Sat,
+                month: Month::January,
+            },
+        )
+    }
+
+    #[test]
+    fn test_nrf_published_years() {
+        let fiscal = nrf();
+        // NRF fiscal 2022, 2023 (a 53-week year), and 2024.
+        let years = [
+            (2023, date(2022, 1, 30), date(2023, 1, 28), 52),
+            (2024, date(2023, 1, 29), date(2024, 2, 3), 53),
+            (2025, date(2024, 2, 4), date(2025, 2, 1), 52),
+        ];
+        for (year, start, end, weeks) in years {
+            assert_eq!(fiscal.year_range(year), Some((start, end)), "{}", year);
+            assert_eq!(fiscal.weeks_in_year(year), Some(weeks), "{}", year);
+        }
+
+        // NRF fiscal 2023's published months, with the leap week in January.
+        let months = [
+            (date(2023, 1, 29), date(2023, 2, 25)),
+            (date(2023, 2, 26), date(2023, 4, 1)),
+            (date(2023, 4, 2), date(2023, 4, 29)),
+            (date(2023, 4, 30), date(2023, 5, 27)),
+            (date(2023, 5, 28), date(2023, 7, 1)),
+            (date(2023, 7, 2), date(2023, 7, 29)),
+            (date(2023, 7, 30), date(2023, 8, 26)),
+            (date(2023, 8, 27), date(2023, 9, 30)),
+            (date(2023, 10, 1), date(2023, 10, 28)),
+            (date(2023, 10, 29), date(2023, 11, 25)),
+            (date(2023, 11, 26), date(2023, 12, 30)),
+            (date(2023, 12, 31), date(2024, 2, 3)),
+        ];
+        for (period, range) in (1..).zip(months) {
+            assert_eq!(fiscal.period_range(2024, period), Some(range), "{}", period);
+        }
+        // And fiscal 2024's last month, back to four weeks.
+        assert_eq!(
+            fiscal.period_range(2025, 12),
+            Some((date(2025, 1, 5), date(2025, 2, 1)))
+        );
+    }
+
+    #[test]
+    fn test_last_weekday_rule() {
+        // Years ending on the last Saturday in July, with a 53-week fiscal 2021.
+        let fiscal = FiscalCalendar::new(
+            FiscalPattern::FourFourFive,
+            FiscalYearEnd::Last {
+                weekday: Weekday::Sat,
+                month: Month::July,
+            },
+        );
+        let ends = [
+            (2020, date(2020, 7, 25), 52),
+            (2021, date(2021, 7, 31), 53),
+            (2022, date(2022, 7, 30), 52),
+            (2023, date(2023, 7, 29), 52),
+            (2024, date(2024, 7, 27), 52),
+        ];
+        for (year, end, weeks) in ends {
+            assert_eq!(fiscal.year_end(year), Some(end));
+            assert_eq!(fiscal.weeks_in_year(year), Some(weeks));
+        }
+        // Quarters are 13 weeks, with the leap week making a six-week last period.
+        assert_eq!(fiscal.period_range(2021, 3).unwrap().1, date(2020, 10, 24));
+        assert_eq!(
+            fiscal.period_range(2021, 12),
+            Some((date(2021, 6, 20), date(2021, 7, 31)))
+        );
+        assert_eq!(
+            fiscal.period_range(2022, 12),
+            Some((date(2022, 6, 26), date(2022, 7, 30)))
+        );
+    }
+
+    #[test]
+    fn test_periods_tile_years() {
+        let patterns = [
+            FiscalPattern::FourFourFive,
+            FiscalPattern::FourFiveFour,
+            FiscalPattern::FiveFourFour,
+        ];
+        let year_ends = [
+            FiscalYearEnd::Last {
+                weekday: Weekday::Sun,
+                month: Month::December,
+            },
+            FiscalYearEnd::Nearest {
+                weekday: Weekday::Fri,
+                month: Month::June,
+            },
+            FiscalYearEnd::Nearest {
+                weekday: Weekday::Sat,
+                month: Month::December,
+            },
+        ];
+        for pattern in patterns {
+            for year_end in year_ends {
+                let fiscal = FiscalCalendar::new(pattern, year_end);
+                let mut expected_start = fiscal.year_range(2015).unwrap().0;
+                for year in 2015..=2035 {
+                    let (year_start, year_end) = fiscal.year_range(year).unwrap();
+                    assert_eq!(year_start, expected_start);
+                    for period in 1..=12 {
+                        let (start, end) = fiscal.period_range(year, period).unwrap();
+                        assert_eq!(start, expected_start);
+                        for probe in [start, end] {
+                            assert_eq!(
+                                fiscal.fiscal_period_of(probe),
+                                Some((year, (period - 1) / 3 + 1, period))
+                            );
+                        }
+                        expected_start = end.succ_opt().unwrap();
+                    }
+                    assert_eq!(expected_start, year_end.succ_opt().unwrap());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_work_days_in_period() {
+        let fiscal = nrf();
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2023, 12, 25));
+        calendar.add_holiday(date(2024, 1, 1));
+        // The five-week January of a leap-week year, less New Year's Day.
+        assert_eq!(fiscal.work_days_in_period(&calendar, 2024, 12), 24);
+        assert_eq!(fiscal.work_days_in_period(&calendar, 2024, 1), 20);
+        assert_eq!(fiscal.work_days_in_period(&calendar, 2024, 0), 0);
+        assert_eq!(fiscal.work_days_in_period(&calendar, 2024, 13), 0);
+        let total: i64 = (1..=12)
+            .map(|period| fiscal.work_days_in_period(&calendar, 2024, period))
+            .sum();
+        assert_eq!(total, 53 * 5 - 2);
+    }
+
+    #[test]
+    fn test_range_limits() {
+        let fiscal = nrf();
+        assert_eq!(fiscal.fiscal_period_of(NaiveDate::MAX), None);
+        assert_eq!(fiscal.fiscal_period_of(NaiveDate::MIN), None);
+        assert_eq!(fiscal.period_range(i32::MIN, 1), None);
+    }
+}
@@ -0,0 +1,380 @@
+//! An immutable, query-optimized form of `WorkCalendar`.
+
+use crate::{
+    calendar_like::compute_end_by_weeks, category::HolidayCategories, count_work_weekdays,
+    half_day::PartialHolidays, hours::HoursPerDay, rules::RuleSet, week::WeekChanges, CalendarLike,
+    EndDate, WorkCalendar, WorkCalendarError,
+};
+use chrono::{Datelike, NaiveDate, Weekday};
+use std::{ops::RangeInclusive, sync::Arc};
+
+/// An immutable calendar with precomputed indexes for fast queries.
+///
+/// Freezing a calendar precomputes:
+///
+/// - a bitmask of work weekdays,
+/// - sorted arrays of holidays and working-date overrides, plus the subsets that
+///   differ from the weekly pattern,
+/// - per-year prefix sums of working days over a horizon of years.
+///
+/// Membership is a bit test plus a binary search, [`FrozenCalendar::work_days_between`]
+/// is arithmetic plus two binary searches, and [`FrozenCalendar::work_days_in_year`] is
+/// a subtraction inside the horizon. Use [`WorkCalendar::freeze`] to create one and
+/// [`FrozenCalendar::thaw`] to get an editable calendar back.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::WorkCalendar;
+/// use chrono::NaiveDate;
+///
+/// let mut calendar = WorkCalendar::new();
+/// calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 8, 23).unwrap());
+/// let frozen = calendar.freeze_with_horizon(2020..=2030);
+///
+/// let start = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap();
+/// let end = NaiveDate::from_ymd_opt(2023, 8, 25).unwrap();
+/// assert_eq!(frozen.work_days_between(start, end), 4);
+/// assert_eq!(frozen.compute_end(start, 4).unwrap().date, end);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrozenCalendar {
+    name: Option<String>,
+    weekday_mask: u8,
+    days_per_week: i64,
+    work_days_effective: Arc<WeekChanges>,
+    holidays: Box<[NaiveDate]>,
+    work_day_holidays: Box<[NaiveDate]>,
+    working_dates: Box<[NaiveDate]>,
+    /// Working-date overrides that fall on other weekdays and are not holidays.
+    extra_working_dates: Box<[NaiveDate]>,
+    partial_holidays: Arc<PartialHolidays>,
+    recurring_holidays: RuleSet,
+    holiday_categories: Arc<HolidayCategories>,
+    ordinal_epoch: NaiveDate,
+    hours_per_day: HoursPerDay,
+    first_year: i32,
+    /// `year_prefix[i]` is the number of working days from the start of `first_year`
+    /// up to (not including) the start of `first_year + i`.
+    year_prefix: Box<[i64]>,
+}
+
+impl FrozenCalendar {
+    pub(crate) fn new(calendar: WorkCalendar, years: RangeInclusive<i32>) -> Self {
+        let weekday_mask = calendar.work_days.mask();
+        let holidays: Box<[NaiveDate]> = calendar.holidays.iter().copied().collect();
+        let work_day_holidays = holidays
+            .iter()
+            .filter(|date| weekday_mask & 1 << date.weekday().num_days_from_monday() != 0)
+            .copied()
+            .collect();
+        let working_dates: Box<[NaiveDate]> = calendar.working_dates.iter().copied().collect();
+        let extra_working_dates = working_dates
+            .iter()
+            .filter(|date| {
+                weekday_mask & 1 << date.weekday().num_days_from_monday() == 0
+                    && !calendar.is_holiday(date)
+            })
+            .copied()
+            .collect();
+        let mut frozen = FrozenCalendar {
+            name: calendar.name.as_deref().map(String::from),
+            weekday_mask,
+            days_per_week: calendar.work_days.days_per_week(),
+            work_days_effective: calendar.work_days_effective.clone(),
+            holidays,
+            work_day_holidays,
+            working_dates,
+            extra_working_dates,
+            partial_holidays: calendar.partial_holidays.clone(),
+            recurring_holidays: calendar.recurring_holidays.clone(),
+            holiday_categories: calendar.holiday_categories.clone(),
+            ordinal_epoch: calendar.ordinal_epoch,
+            hours_per_day: calendar.hours_per_day,
+            first_year: *years.start(),
+            year_prefix: Box::new([]),
+        };
+
+        let mut total = 0;
+        let mut year_prefix = vec![0];
+        for year in years {
+            total += frozen.count_year(year);
+            year_prefix.push(total);
+        }
+        frozen.year_prefix = year_prefix.into_boxed_slice();
+        frozen
+    }
+
+    /// Returns the range of years with precomputed totals.
+    pub fn horizon(&self) -> RangeInclusive<i32> {
+        self.first_year..=self.first_year + self.year_prefix.len() as i32 - 2
+    }
+
+    /// Checks if a given day is a work day.
+    ///
+    /// Like [`WorkCalendar::is_work_day`], this ignores effective-dated work weeks.
+    pub fn is_work_day(&self, day: &Weekday) -> bool {
+        self.weekday_mask & 1 << day.num_days_from_monday() != 0
+    }
+
+    /// Checks if `date`'s weekday is a work day in the work week in effect on it.
+    fn is_work_day_on(&self, date: &NaiveDate) -> bool {
+        match self.work_days_effective.range(..=*date).next_back() {
+            Some((_, week)) => week.contains(date.weekday()),
+            None => self.is_work_day(&date.weekday()),
+        }
+    }
+
+    /// Checks if a given date is a holiday.
+    pub fn is_holiday(&self, date: &NaiveDate) -> bool {
+        self.holidays.binary_search(date).is_ok() || self.recurring_holidays.contains(date)
+    }
+
+    /// Checks if a given date has a working-date override.
+    pub fn is_working_date_override(&self, date: &NaiveDate) -> bool {
+        self.working_dates.binary_search(date).is_ok()
+    }
+
+    /// Checks if a given date is a working date.
+    ///
+    /// Returns the same result as [`WorkCalendar::is_working_date`].
+    pub fn is_working_date(&self, date: &NaiveDate) -> bool {
+        (self.is_work_day_on(date) || self.is_working_date_override(date)) && !self.is_holiday(date)
+    }
+
+    /// Calculates the number of work days between two dates (inclusive).
+    ///
+    /// Returns the same result as [`WorkCalendar::work_days_between`].
+    pub fn work_days_between(&self, start_date: NaiveDate, end_date: NaiveDate) -> i64 {
+        if start_date > end_date {
+            return 0;
+        }
+        if !self.work_days_effective.is_empty() {
+            return std::iter::successors(Some(start_date), |date| date.succ_opt())
+                .take_while(|date| *date <= end_date)
+                .filter(|date| self.is_working_date(date))
+                .count() as i64;
+        }
+        let count_in = |dates: &[NaiveDate]| {
+            let first = dates.partition_point(|d| *d < start_date);
+            let last = dates.partition_point(|d| *d <= end_date);
+            (last - first) as i64
+        };
+        count_work_weekdays(
+            |day| self.is_work_day(&day),
+            self.days_per_week,
+            start_date,
+            end_date,
+        ) - count_in(&self.work_day_holidays)
+            - self
+                .recurring_holidays
+                .count_in_range(start_date, end_date, |date| {
+                    self.is_work_day(&date.weekday()) && self.holidays.binary_search(date).is_err()
+                })
+            + count_in(&self.extra_working_dates)
+    }
+
+    /// Returns the number of working days in `year`.
+    pub fn work_days_in_year(&self, year: i32) -> i64 {
+        let index = year
+            .checked_sub(self.first_year)
+            .and_then(|i| usize::try_from(i).ok());
+        match index {
+            Some(i) if i + 1 < self.year_prefix.len() => {
+                self.year_prefix[i + 1] - self.year_prefix[i]
+            }
+            _ => self.count_year(year),
+        }
+    }
+
+    /// Returns the number of working days in the years `years`.
+    pub fn work_days_in_years(&self, years: RangeInclusive<i32>) -> i64 {
+        let (first, last) = (*years.start(), *years.end());
+        if first > last {
+            return 0;
+        }
+        let horizon = self.horizon();
+        if horizon.contains(&first) && horizon.contains(&last) {
+            let from = (first - self.first_year) as usize;
+            let to = (last - self.first_year) as usize + 1;
+            return self.year_prefix[to] - self.year_prefix[from];
+        }
+        years.map(|year| self.work_days_in_year(year)).sum()
+    }
+
+    fn count_year(&self, year: i32) -> i64 {
+        match (
+            NaiveDate::from_ymd_opt(year, 1, 1),
+            NaiveDate::from_ymd_opt(year, 12, 31),
+        ) {
+            (Some(first), Some(last)) => self.work_days_between(first, last),
+            _ => 0,
+        }
+    }
+
+    /// Computes the end date and calendar span given a start date and number of work days.
+    ///
+    /// Returns the same result as [`WorkCalendar::compute_end`].
+    pub fn compute_end(
+        &self,
+        start_date: NaiveDate,
+        days_worked: i64,
+    ) -> Result<EndDate, WorkCalendarError> {
+        let days_per_week = if self.work_days_effective.is_empty() {
+            self.days_per_week
+        } else {
+            0
+        };
+        compute_end_by_weeks(
+            self,
+            |date| self.is_working_date(date),
+            |day| self.is_work_day(&day),
+            days_per_week,
+            |date| {
+                let next_in =
+                    |dates: &[NaiveDate]| dates.get(dates.partition_point(|d| *d <= date)).copied();
+                let rule = self
+                    .recurring_holidays
+                    .next_after(date, |holiday| self.is_work_day(&holiday.weekday()));
+                [
+                    next_in(&self.work_day_holidays),
+                    next_in(&self.extra_working_dates),
+                    rule,
+                ]
+                .into_iter()
+                .flatten()
+                .min()
+            },
+            start_date,
+            days_worked,
+        )
+    }
+
+    /// Converts back into an editable `WorkCalendar`.
+    pub fn thaw(self) -> WorkCalendar {
+        let work_days = (0..7)
+            .filter(|n| self.weekday_mask & 1 << n != 0)
+            .map(|n| Weekday::try_from(n).unwrap())
+            .collect();
+        WorkCalendar {
+            name: self.name.as_deref().map(Arc::from),
+            work_days,
+            work_days_effective: self.work_days_effective,
+            holidays: Arc::new(self.holidays.iter().copied().collect()),
+            working_dates: Arc::new(self.working_dates.iter().copied().collect()),
+            partial_holidays: self.partial_holidays,
+            recurring_holidays: self.recurring_holidays,
+            holiday_categories: self.holiday_categories,
+            ordinal_epoch: self.ordinal_epoch,
+            hours_per_day: self.hours_per_day,
+            month_memo: Default::default(),
+        }
+    }
+}
+
+impl CalendarLike for FrozenCalendar {
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn is_working_date(&self, date: &NaiveDate) -> bool {
+        FrozenCalendar::is_working_date(self, date)
+    }
+
+    fn has_work_days(&self) -> bool {
+        self.weekday_mask != 0
+            || self
+                .work_days_effective
+                .values()
+                .any(|week| !week.is_empty())
+    }
+
+    fn work_days_between(&self, start_date: NaiveDate, end_date: NaiveDate) -> i64 {
+        FrozenCalendar::work_days_between(self, start_date, end_date)
+    }
+
+    fn compute_end(
+        &self,
+        start_date: NaiveDate,
+        days_worked: i64,
+    ) -> Result<EndDate, WorkCalendarError> {
+        FrozenCalendar::compute_end(self, start_date, days_worked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{random_calendar, TestRng};
+    use chrono::Duration;
+
+    #[test]
+    fn test_frozen_matches_live_randomized() {
+        let mut rng = TestRng(0x853c_49e6_748f_ea9b);
+        let base = NaiveDate::from_ymd_opt(2019, 6, 1).unwrap();
+        for _ in 0..200 {
+            let calendar = random_calendar(&mut rng);
+            let frozen = calendar.clone().freeze_with_horizon(2018..=2030);
+            for _ in 0..10 {
+                let start_date = base + Duration::days(rng.below(3000) as i64);
+                let end_date = base + Duration::days(rng.below(3000) as i64);
+                let days_worked = rng.below(800) as i64;
+                assert_eq!(
+                    frozen.is_working_date(&start_date),
+                    calendar.is_working_date(&start_date)
+                );
+                assert_eq!(
+                    frozen.work_days_between(start_date, end_date),
+                    calendar.work_days_between(start_date, end_date)
+                );
+                assert_eq!(
+                    frozen.compute_end(start_date, days_worked),
+                    calendar.compute_end(start_date, days_worked)
+                );
+            }
+            for year in 2015..2035 {
+                let first = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
+                let last = NaiveDate::from_ymd_opt(year, 12, 31).unwrap();
+                assert_eq!(
+                    frozen.work_days_in_year(year),
+                    calendar.work_days_between(first, last)
+                );
+            }
+            assert_eq!(
+                frozen.work_days_in_years(2019..=2027),
+                (2019..=2027)
+                    .map(|y| frozen.work_days_in_year(y))
+                    .sum::<i64>()
+            );
+            assert_eq!(frozen.thaw(), calendar);
+        }
+    }
+
+    #[test]
+    fn test_freeze_default_horizon() {
+        let mut calendar = WorkCalendar::new();
+        assert!(calendar.clone().freeze().horizon().is_empty());
+        calendar.add_holiday(NaiveDate::from_ymd_opt(2021, 1, 1).unwrap());
+        calendar.add_holiday(NaiveDate::from_ymd_opt(2024, 12, 25).unwrap());
+        assert_eq!(calendar.freeze().horizon(), 2021..=2024);
+    }
+
+    #[test]
+    fn test_generic_algorithms_through_trait() {
+        fn end_via_trait(calendar: &impl CalendarLike, start: NaiveDate, days: i64) -> NaiveDate {
+            calendar.compute_end(start, days).unwrap().date
+        }
+
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 8, 23).unwrap());
+        let start = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap();
+        let expected = calendar.compute_end(start, 12).unwrap().date;
+        assert_eq!(end_via_trait(&calendar, start, 12), expected);
+        assert_eq!(end_via_trait(&calendar.compact(), start, 12), expected);
+        assert_eq!(
+            end_via_trait(&crate::YearCache::build(&calendar, 2023..=2023), start, 12),
+            expected
+        );
+        assert_eq!(end_via_trait(&calendar.freeze(), start, 12), expected);
+    }
+}
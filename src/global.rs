@@ -0,0 +1,126 @@
+//! An opt-in, process-wide default calendar.
+//!
+//! Call [`set_global_calendar`] once at startup (or whenever the company calendar
+//! changes) and use the free functions in this module anywhere without threading a
+//! `&WorkCalendar` through every layer. Setting the calendar again atomically replaces
+//! it: readers that already called [`global`] keep the calendar they received, and
+//! every later call sees the new one.
+
+use crate::{WorkCalendar, WorkCalendarError};
+use chrono::{Duration, NaiveDate};
+use std::sync::{Arc, RwLock};
+
+static GLOBAL_CALENDAR: RwLock<Option<Arc<WorkCalendar>>> = RwLock::new(None);
+
+/// Sets (or replaces) the process-wide calendar.
+///
+/// # Arguments
+///
+/// * `calendar` - The calendar used by [`global`] and the free functions in this module.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::WorkCalendar;
+///
+/// workdays::set_global_calendar(WorkCalendar::new());
+/// assert!(workdays::global().is_ok());
+/// ```
+pub fn set_global_calendar(calendar: WorkCalendar) {
+    let calendar = Arc::new(calendar);
+    *GLOBAL_CALENDAR
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(calendar);
+}
+
+/// Returns the process-wide calendar.
+///
+/// # Errors
+///
+/// Returns [`WorkCalendarError::GlobalCalendarNotSet`] if [`set_global_calendar`] has
+/// never been called.
+pub fn global() -> Result<Arc<WorkCalendar>, WorkCalendarError> {
+    GLOBAL_CALENDAR
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+        .ok_or(WorkCalendarError::GlobalCalendarNotSet)
+}
+
+/// Computes an end date with the process-wide calendar.
+///
+/// See [`WorkCalendar::compute_end_date`].
+pub fn compute_end_date(
+    start_date: NaiveDate,
+    days_worked: i64,
+) -> Result<(NaiveDate, Duration), WorkCalendarError> {
+    global()?.compute_end_date(start_date, days_worked)
+}
+
+/// Counts work days between two dates (inclusive) with the process-wide calendar.
+///
+/// See [`WorkCalendar::work_days_between`].
+pub fn work_days_between(
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<i64, WorkCalendarError> {
+    Ok(global()?.work_days_between(start_date, end_date))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    // The global calendar is shared by every test in the process, so all checks that
+    // touch it live in this one test.
+    #[test]
+    fn test_global_calendar() {
+        let start_date = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap(); // Monday
+        let end_date = NaiveDate::from_ymd_opt(2023, 8, 27).unwrap(); // Sunday
+
+        assert_eq!(global(), Err(WorkCalendarError::GlobalCalendarNotSet));
+        assert_eq!(
+            compute_end_date(start_date, 5),
+            Err(WorkCalendarError::GlobalCalendarNotSet)
+        );
+
+        set_global_calendar(WorkCalendar::new());
+        assert_eq!(work_days_between(start_date, end_date), Ok(5));
+        let (end, _) = compute_end_date(start_date, 5).unwrap();
+        assert_eq!(end, NaiveDate::from_ymd_opt(2023, 8, 25).unwrap());
+
+        // Readers running while the calendar is replaced see either the old calendar
+        // (5 work days) or the new one (7 work days), never anything in between.
+        let mut seven_days = WorkCalendar::new();
+        seven_days
+            .set_work_days("Mon,Tue,Wed,Thu,Fri,Sat,Sun")
+            .unwrap();
+        let readers: Vec<_> = (0..8)
+            .map(|_| {
+                thread::spawn(move || {
+                    for _ in 0..1000 {
+                        let count = work_days_between(start_date, end_date).unwrap();
+                        assert!(count == 5 || count == 7);
+                    }
+                })
+            })
+            .collect();
+        let writer = thread::spawn(move || {
+            for i in 0..100 {
+                if i % 2 == 0 {
+                    set_global_calendar(seven_days.clone());
+                } else {
+                    set_global_calendar(WorkCalendar::new());
+                }
+            }
+            set_global_calendar(seven_days);
+        });
+        for reader in readers {
+            reader.join().unwrap();
+        }
+        writer.join().unwrap();
+
+        assert_eq!(work_days_between(start_date, end_date), Ok(7));
+    }
+}
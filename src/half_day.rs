@@ -0,0 +1,446 @@
+//! Half-day holidays and work measured in half days.
+
+use crate::{WorkCalendar, WorkCalendarError, SEARCH_HORIZON_DAYS};
+use chrono::NaiveDate;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{collections::BTreeMap, sync::Arc};
+
+/// The dates on which half the day is a holiday, with the half that is off.
+pub(crate) type PartialHolidays = BTreeMap<NaiveDate, DayHalf>;
+
+/// One half of a working day.
+///
+/// Serialized in lowercase, as `morning` or `afternoon`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DayHalf {
+    /// The half before midday.
+    Morning,
+    /// The half after midday.
+    Afternoon,
+}
+
+impl DayHalf {
+    /// Returns the other half of the day.
+    pub fn other(self) -> DayHalf {
+        match self {
+            DayHalf::Morning => DayHalf::Afternoon,
+            DayHalf::Afternoon => DayHalf::Morning,
+        }
+    }
+}
+
+/// A serialized half-day holiday.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PartialHoliday {
+    date: NaiveDate,
+    half: DayHalf,
+}
+
+/// A deserialized half-day holiday: a bare date has its afternoon off.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PartialHolidayEntry {
+    Date(NaiveDate),
+    Half(PartialHoliday),
+}
+
+/// Writes half-day holidays as a list of `{date, half}` entries in date order.
+pub(crate) fn serialize_partial_holidays<S: Serializer>(
+    holidays: &Arc<PartialHolidays>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(
+        holidays
+            .iter()
+            .map(|(&date, &half)| PartialHoliday { date, half }),
+    )
+}
+
+/// Reads half-day holidays written by [`serialize_partial_holidays`], also accepting
+/// bare dates for afternoons off.
+pub(crate) fn deserialize_partial_holidays<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Arc<PartialHolidays>, D::Error> {
+    let entries = Vec::<PartialHolidayEntry>::deserialize(deserializer)?;
+    Ok(Arc::new(
+        entries
+            .into_iter()
+            .map(|entry| match entry {
+                PartialHolidayEntry::Date(date) => (date, DayHalf::Afternoon),
+                PartialHolidayEntry::Half(PartialHoliday { date, half }) => (date, half),
+            })
+            .collect(),
+    ))
+}
+
+impl WorkCalendar {
+    /// Marks `date` as a half-day holiday, on which the morning is worked and the
+    /// afternoon is off.
+    ///
+    /// Same as [`WorkCalendar::add_partial_holiday_half`] with [`DayHalf::Afternoon`].
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The date whose afternoon is off.
+    pub fn add_partial_holiday(&mut self, date: NaiveDate) {
+        self.add_partial_holiday_half(date, DayHalf::Afternoon);
+    }
+
+    /// Marks `date` as a half-day holiday on which `half` is off and the other half is
+    /// worked, replacing any half set for the date before.
+    ///
+    /// The date is still a working date for whole-day computations such as
+    /// [`WorkCalendar::work_days_between`]; only half-day computations such as
+    /// [`WorkCalendar::compute_end_half_days`] count it as half a day. A full holiday
+    /// on the same date takes precedence.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The date that is half a holiday.
+    /// * `half` - The half of the day that is off.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{DayHalf, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// let date = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+    /// calendar.add_partial_holiday_half(date, DayHalf::Morning);
+    /// assert_eq!(calendar.holiday_half(&date), Some(DayHalf::Morning));
+    /// assert_eq!(calendar.holiday_half(&date.pred_opt().unwrap()), None);
+    /// ```
+    pub fn add_partial_holiday_half(&mut self, date: NaiveDate, half: DayHalf) {
+        Arc::make_mut(&mut self.partial_holidays).insert(date, half);
+    }
+
+    /// Removes a half-day holiday from the calendar.
+    pub fn remove_partial_holiday(&mut self, date: &NaiveDate) {
+        Arc::make_mut(&mut self.partial_holidays).remove(date);
+    }
+
+    /// Checks if `date` was marked as a half-day holiday with
+    /// [`WorkCalendar::add_partial_holiday`] or
+    /// [`WorkCalendar::add_partial_holiday_half`].
+    pub fn is_partial_holiday(&self, date: &NaiveDate) -> bool {
+        self.partial_holidays.contains_key(date)
+    }
+
+    /// Returns the half of `date` that is off if it was marked as a half-day holiday,
+    /// and `None` otherwise.
+    pub fn holiday_half(&self, date: &NaiveDate) -> Option<DayHalf> {
+        self.partial_holidays.get(date).copied()
+    }
+
+    /// Returns the half-day holidays and the half that is off on each, in date order.
+    pub fn partial_holidays(&self) -> impl Iterator<Item = (NaiveDate, DayHalf)> + '_ {
+        self.partial_holidays
+            .iter()
+            .map(|(&date, &half)| (date, half))
+    }
+
+    /// Returns the number of half days worked on `date`: two on a working date, one on
+    /// a working date that is a half-day holiday, and zero otherwise.
+    pub fn half_days_on(&self, date: &NaiveDate) -> i64 {
+        match (self.is_working_date(date), self.is_partial_holiday(date)) {
+            (false, _) => 0,
+            (true, true) => 1,
+            (true, false) => 2,
+        }
+    }
+
+    /// Computes when work measured in half days finishes.
+    ///
+    /// Counting starts with `start_date` as [`WorkCalendar::compute_end`] does, and a
+    /// half-day holiday supplies only the half that is not off. For an even number of half days and
+    /// no half-day holidays in the way, the result is [`WorkCalendar::compute_end`]'s
+    /// date for half as many days, finishing in the afternoon. Like zero whole days, zero
+    /// half days finish on `start_date` if it is a working date and are an error
+    /// otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_date` - The first date on which work may be done.
+    /// * `half_days` - The number of half days of work.
+    ///
+    /// # Returns
+    ///
+    /// The date the work finishes on and the half of that day in which it finishes:
+    /// [`DayHalf::Morning`] for midday, [`DayHalf::Afternoon`] for the end of the day.
+    ///
+    /// # Errors
+    ///
+    /// The errors of [`WorkCalendar::compute_end`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{DayHalf, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// let christmas_eve = NaiveDate::from_ymd_opt(2025, 12, 24).unwrap(); // Wednesday
+    /// calendar.add_partial_holiday(christmas_eve);
+    /// calendar.add_holiday(NaiveDate::from_ymd_opt(2025, 12, 25).unwrap());
+    ///
+    /// let monday = NaiveDate::from_ymd_opt(2025, 12, 22).unwrap();
+    /// // Three half days end at midday on Tuesday.
+    /// assert_eq!(
+    ///     calendar.compute_end_half_days(monday, 3).unwrap(),
+    ///     (NaiveDate::from_ymd_opt(2025, 12, 23).unwrap(), DayHalf::Morning)
+    /// );
+    /// // Christmas Eve supplies only its morning.
+    /// assert_eq!(
+    ///     calendar.compute_end_half_days(monday, 5).unwrap(),
+    ///     (christmas_eve, DayHalf::Morning)
+    /// );
+    /// assert_eq!(
+    ///     calendar.compute_end_half_days(monday, 6).unwrap(),
+    ///     (NaiveDate::from_ymd_opt(2025, 12, 26).unwrap(), DayHalf::Morning)
+    /// );
+    /// ```
+    pub fn compute_end_half_days(
+        &self,
+        start_date: NaiveDate,
+        half_days: i64,
+    ) -> Result<(NaiveDate, DayHalf), WorkCalendarError> {
+        let name = || self.name().map(String::from);
+        if half_days < 0 {
+            return Err(WorkCalendarError::NegativeDaysWorked { calendar: name() });
+        }
+        if half_days == 0 || self.partial_holidays.is_empty() {
+            let end = self.compute_end(start_date, half_days / 2 + half_days % 2)?;
+            let half = if half_days % 2 == 1 {
+                DayHalf::Morning
+            } else {
+                DayHalf::Afternoon
+            };
+            return Ok((end.date, half));
+        }
+        if !self.has_any_work_days() {
+            return Err(WorkCalendarError::NoWorkDaysDefined { calendar: name() });
+        }
+
+        let mut remaining = half_days;
+        let mut current_date = start_date;
+        let mut last_work_date = start_date;
+        loop {
+            let available = self.half_days_on(&current_date);
+            if available > 0 {
+                if remaining <= available {
+                    let half = match self.holiday_half(&current_date) {
+                        Some(off) => off.other(),
+                        None if remaining == 2 => DayHalf::Afternoon,
+                        None => DayHalf::Morning,
+                    };
+                    return Ok((current_date, half));
+                }
+                remaining -= available;
+                last_work_date = current_date;
+            } else if (current_date - last_work_date).num_days() >= SEARCH_HORIZON_DAYS {
+                return Err(WorkCalendarError::NoWorkingDaysInHorizon {
+                    calendar: name(),
+                    searched_until: current_date,
+                });
+            }
+            current_date = current_date
+                .succ_opt()
+                .ok_or_else(|| WorkCalendarError::DateOutOfRange { calendar: name() })?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{date, random_calendar, TestRng};
+    use chrono::Duration;
+
+    #[test]
+    fn test_matches_whole_days() {
+        let mut rng = TestRng(0x61a9_d03e_5c47_b218);
+        let base = date(2019, 6, 1);
+        for _ in 0..100 {
+            let calendar = random_calendar(&mut rng);
+            // A half-day holiday on a date that is not worked changes nothing, but
+            // takes the day-by-day path.
+            let mut walked = calendar.clone();
+            walked.add_partial_holiday(date(1900, 1, 6));
+            walked.add_holiday(date(1900, 1, 6));
+            for _ in 0..5 {
+                let start = base + Duration::days(rng.below(3000) as i64);
+                let days = rng.below(300) as i64;
+                let whole = calendar.compute_end(start, days).map(|end| end.date);
+                let even = whole.clone().map(|date| (date, DayHalf::Afternoon));
+                assert_eq!(calendar.compute_end_half_days(start, 2 * days), even);
+                assert_eq!(walked.compute_end_half_days(start, 2 * days), even);
+                if days > 0 {
+                    let odd = whole.map(|date| (date, DayHalf::Morning));
+                    assert_eq!(calendar.compute_end_half_days(start, 2 * days - 1), odd);
+                    assert_eq!(walked.compute_end_half_days(start, 2 * days - 1), odd);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_partial_holidays() {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("Ops");
+        let eve = date(2025, 12, 24); // Wednesday
+        calendar.add_partial_holiday(eve);
+        calendar.add_partial_holiday(date(2025, 12, 27)); // Saturday
+        calendar.add_holiday(date(2025, 12, 25));
+        assert!(calendar.is_partial_holiday(&eve));
+        assert!(calendar.is_working_date(&eve));
+        assert_eq!(calendar.half_days_on(&eve), 1);
+        assert_eq!(calendar.half_days_on(&date(2025, 12, 23)), 2);
+        assert_eq!(calendar.half_days_on(&date(2025, 12, 25)), 0);
+        assert_eq!(calendar.half_days_on(&date(2025, 12, 27)), 0);
+        assert_eq!(calendar.holiday_half(&eve), Some(DayHalf::Afternoon));
+        assert_eq!(
+            calendar.partial_holidays().collect::<Vec<_>>(),
+            [
+                (eve, DayHalf::Afternoon),
+                (date(2025, 12, 27), DayHalf::Afternoon)
+            ]
+        );
+
+        // Starting on the half-day holiday.
+        let cases = [
+            (0, (eve, DayHalf::Afternoon)),
+            (1, (eve, DayHalf::Morning)),
+            (2, (date(2025, 12, 26), DayHalf::Morning)),
+            (3, (date(2025, 12, 26), DayHalf::Afternoon)),
+            (4, (date(2025, 12, 29), DayHalf::Morning)),
+        ];
+        for (half_days, expected) in cases {
+            assert_eq!(
+                calendar.compute_end_half_days(eve, half_days),
+                Ok(expected),
+                "{}",
+                half_days
+            );
+        }
+        // Starting the day before, an odd count ends at midday on the half day.
+        assert_eq!(
+            calendar.compute_end_half_days(date(2025, 12, 23), 3),
+            Ok((eve, DayHalf::Morning))
+        );
+        // Starting on a weekend.
+        assert_eq!(
+            calendar.compute_end_half_days(date(2025, 12, 20), 5),
+            Ok((date(2025, 12, 24), DayHalf::Morning))
+        );
+        assert_eq!(
+            calendar.compute_end_half_days(eve, -1),
+            Err(WorkCalendarError::NegativeDaysWorked {
+                calendar: Some("Ops".to_string())
+            })
+        );
+
+        calendar.remove_partial_holiday(&eve);
+        assert_eq!(
+            calendar.compute_end_half_days(eve, 2),
+            Ok((eve, DayHalf::Afternoon))
+        );
+    }
+
+    #[test]
+    fn test_morning_off() {
+        let mut calendar = WorkCalendar::new();
+        let new_years_eve = date(2025, 12, 31); // Wednesday
+        calendar.add_partial_holiday_half(new_years_eve, DayHalf::Morning);
+        assert_eq!(
+            calendar.holiday_half(&new_years_eve),
+            Some(DayHalf::Morning)
+        );
+        assert_eq!(calendar.half_days_on(&new_years_eve), 1);
+
+        // Only the afternoon is worked, so work that reaches the date ends at its close.
+        let tuesday = date(2025, 12, 30);
+        let cases = [
+            (1, (tuesday, DayHalf::Morning)),
+            (2, (tuesday, DayHalf::Afternoon)),
+            (3, (new_years_eve, DayHalf::Afternoon)),
+            (4, (date(2026, 1, 1), DayHalf::Morning)),
+            (5, (date(2026, 1, 1), DayHalf::Afternoon)),
+        ];
+        for (half_days, expected) in cases {
+            assert_eq!(
+                calendar.compute_end_half_days(tuesday, half_days),
+                Ok(expected),
+                "{}",
+                half_days
+            );
+        }
+        assert_eq!(
+            calendar.compute_end_half_days(new_years_eve, 1),
+            Ok((new_years_eve, DayHalf::Afternoon))
+        );
+
+        // Setting the other half replaces the first.
+        calendar.add_partial_holiday_half(new_years_eve, DayHalf::Afternoon);
+        assert_eq!(
+            calendar.compute_end_half_days(new_years_eve, 1),
+            Ok((new_years_eve, DayHalf::Morning))
+        );
+        assert_eq!(calendar.partial_holidays().count(), 1);
+    }
+
+    #[test]
+    fn test_errors_match_whole_days() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_partial_holiday(date(2025, 1, 1));
+        for day in ["Mon", "Tue", "Wed", "Thu", "Fri"] {
+            calendar.remove_work_day(&crate::parse_weekday(day).unwrap());
+        }
+        assert_eq!(
+            calendar.compute_end_half_days(date(2025, 1, 1), 3),
+            Err(WorkCalendarError::NoWorkDaysDefined { calendar: None })
+        );
+
+        let mut calendar = WorkCalendar::new();
+        calendar.add_partial_holiday(date(2025, 1, 1));
+        let last = NaiveDate::MAX;
+        assert_eq!(
+            calendar.compute_end_half_days(last, 3).unwrap_err(),
+            calendar.compute_end(last, 2).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_partial_holiday(date(2025, 12, 24));
+        calendar.add_partial_holiday_half(date(2025, 12, 31), DayHalf::Morning);
+        let json = serde_json::to_string(&calendar).unwrap();
+        assert!(
+            json.contains(r#"{"date":"2025-12-31","half":"morning"}"#),
+            "{}",
+            json
+        );
+        assert_eq!(
+            serde_json::from_str::<WorkCalendar>(&json).unwrap(),
+            calendar
+        );
+        assert_eq!(calendar.compact().expand(), calendar);
+        assert_eq!(calendar.clone().freeze().thaw(), calendar);
+
+        let yaml = serde_yaml::to_string(&calendar).unwrap();
+        assert_eq!(yaml.parse::<WorkCalendar>().unwrap(), calendar);
+
+        // Bare dates have the afternoon off.
+        let config = "partial_holidays:\n- 2025-12-24\n- { date: 2025-12-31, half: morning }";
+        assert_eq!(config.parse::<WorkCalendar>().unwrap(), calendar);
+        let stored = r#"{"work_days": [], "holidays": [], "partial_holidays": ["2025-12-24"]}"#;
+        let stored: WorkCalendar = serde_json::from_str(stored).unwrap();
+        assert_eq!(
+            stored.holiday_half(&date(2025, 12, 24)),
+            Some(DayHalf::Afternoon)
+        );
+    }
+}
@@ -0,0 +1,652 @@
+//! Hour-based effort scheduling over per-weekday daily capacity.
+
+use crate::{WorkCalendar, WorkCalendarError, SEARCH_HORIZON_DAYS};
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+/// Leftover effort at most this many hours over a day's capacity finishes on that day
+/// rather than spilling into the next.
+pub const HOURS_EPSILON: f64 = 1e-6;
+
+/// The working hours per day of a new calendar; see
+/// [`WorkCalendar::set_hours_per_day`].
+pub const DEFAULT_HOURS_PER_DAY: f64 = 8.0;
+
+/// A calendar's working hours per day, compared by bit pattern so that calendars and
+/// configurations holding it can be `Eq`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(crate) struct HoursPerDay(pub(crate) f64);
+
+impl HoursPerDay {
+    pub(crate) fn is_default(&self) -> bool {
+        *self == HoursPerDay::default()
+    }
+}
+
+impl Default for HoursPerDay {
+    fn default() -> Self {
+        HoursPerDay(DEFAULT_HOURS_PER_DAY)
+    }
+}
+
+impl PartialEq for HoursPerDay {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_bits() == other.0.to_bits()
+    }
+}
+
+impl Eq for HoursPerDay {}
+
+/// Deserializes a calendar's hours per day, rejecting values
+/// [`WorkCalendar::set_hours_per_day`] would.
+pub(crate) fn deserialize_hours_per_day<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<HoursPerDay, D::Error> {
+    let hours = f64::deserialize(deserializer)?;
+    check_hours_per_day(hours).map_err(de::Error::custom)?;
+    Ok(HoursPerDay(hours))
+}
+
+/// The result of [`CapacityCalendar::compute_end_by_hours`] and
+/// [`WorkCalendar::compute_end_date_hours`].
+///
+/// The work ends partway through `date` when `hours_on_end_date` is less than that
+/// date's working hours, and at the end of it otherwise.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HoursResult {
+    /// The date on which the effort is used up.
+    pub date: NaiveDate,
+    /// The hours of effort spent on `date`.
+    pub hours_on_end_date: f64,
+    /// The number of calendar days from the start date to `date`.
+    pub calendar_days: u64,
+}
+
+/// A work calendar with a number of working hours on each weekday.
+///
+/// A date has its weekday's capacity if the calendar treats it as a working date, and
+/// none otherwise, so holidays have no capacity and half-day holidays half of it. Like
+/// [`RangeCounter`](crate::RangeCounter), a capacity calendar captures the calendar as
+/// it was when the capacity calendar was created.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::{CapacityCalendar, WorkCalendar};
+/// use chrono::{NaiveDate, Weekday};
+///
+/// let calendar = WorkCalendar::new();
+/// let mut capacity = CapacityCalendar::new(&calendar, 8.0).unwrap();
+/// capacity.set_capacity(Weekday::Fri, 6.0).unwrap();
+///
+/// // 8 + 8 + 8 + 8 + 6 hours in the week of September 4, 2023, then 2 more.
+/// let monday = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+/// let end = capacity.compute_end_by_hours(monday, 40.0).unwrap();
+/// assert_eq!(end.date, NaiveDate::from_ymd_opt(2023, 9, 11).unwrap());
+/// assert_eq!(end.hours_on_end_date, 2.0);
+/// assert_eq!(end.calendar_days, 7);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CapacityCalendar {
+    calendar: WorkCalendar,
+    /// Hours per day, indexed by `Weekday::num_days_from_monday`.
+    capacity: [f64; 7],
+}
+
+impl CapacityCalendar {
+    /// Creates a capacity calendar over `calendar` with `hours_per_day` hours on every
+    /// working date.
+    ///
+    /// # Errors
+    ///
+    /// [`WorkCalendarError::InvalidHours`] if `hours_per_day` is negative or not
+    /// finite.
+    pub fn new(calendar: &WorkCalendar, hours_per_day: f64) -> Result<Self, WorkCalendarError> {
+        check_hours(hours_per_day)?;
+        Ok(CapacityCalendar {
+            calendar: calendar.clone(),
+            capacity: [hours_per_day; 7],
+        })
+    }
+
+    /// Sets the working hours on one weekday. A weekday with zero hours is skipped
+    /// like a holiday.
+    ///
+    /// # Errors
+    ///
+    /// [`WorkCalendarError::InvalidHours`] if `hours` is negative or not finite.
+    pub fn set_capacity(&mut self, weekday: Weekday, hours: f64) -> Result<(), WorkCalendarError> {
+        check_hours(hours)?;
+        self.capacity[weekday.num_days_from_monday() as usize] = hours;
+        Ok(())
+    }
+
+    /// Returns the calendar captured when the capacity calendar was created.
+    pub fn calendar(&self) -> &WorkCalendar {
+        &self.calendar
+    }
+
+    /// Returns the working hours on `date`, which is zero on non-working dates and half
+    /// the weekday's capacity on half-day holidays.
+    pub fn capacity_on(&self, date: NaiveDate) -> f64 {
+        capacity_on(&self.calendar, &self.capacity, date)
+    }
+
+    /// Computes the date on which `effort_hours` of work, starting on `start`, is
+    /// used up.
+    ///
+    /// Each date's capacity is consumed in order, starting with `start`'s. Effort that
+    /// exceeds a day's capacity by no more than [`HOURS_EPSILON`] finishes on that
+    /// day. When every working date has 8 hours and the effort is a multiple of 8,
+    /// the end date matches [`WorkCalendar::compute_end`] for the same number of days.
+    /// Zero effort ends on `start`.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The starting date.
+    /// * `effort_hours` - The effort in hours.
+    ///
+    /// # Errors
+    ///
+    /// * [`WorkCalendarError::InvalidHours`] if `effort_hours` is negative or not
+    ///   finite.
+    /// * [`WorkCalendarError::NoWorkDaysDefined`] if no work day of the calendar has
+    ///   any capacity.
+    /// * [`WorkCalendarError::NoWorkingDaysInHorizon`] if no date has capacity within
+    ///   [`SEARCH_HORIZON_DAYS`] of the last one that did, for example because the
+    ///   work week in effect has only days without capacity.
+    /// * The errors of [`WorkCalendar::next_work_day`] if the end cannot be reached.
+    pub fn compute_end_by_hours(
+        &self,
+        start: NaiveDate,
+        effort_hours: f64,
+    ) -> Result<HoursResult, WorkCalendarError> {
+        compute_end_by_hours(&self.calendar, &self.capacity, start, effort_hours)
+    }
+}
+
+/// Returns the working hours on `date` of `calendar` with `capacity` hours on each
+/// weekday; see [`CapacityCalendar::capacity_on`].
+fn capacity_on(calendar: &WorkCalendar, capacity: &[f64; 7], date: NaiveDate) -> f64 {
+    let hours = capacity[date.weekday().num_days_from_monday() as usize];
+    match calendar.half_days_on(&date) {
+        0 => 0.0,
+        1 => hours / 2.0,
+        _ => hours,
+    }
+}
+
+/// Computes the end of `effort_hours` of work over `calendar` with `capacity` hours on
+/// each weekday; see [`CapacityCalendar::compute_end_by_hours`].
+fn compute_end_by_hours(
+    calendar: &WorkCalendar,
+    capacity: &[f64; 7],
+    start: NaiveDate,
+    effort_hours: f64,
+) -> Result<HoursResult, WorkCalendarError> {
+    check_hours(effort_hours)?;
+    if effort_hours <= HOURS_EPSILON {
+        return Ok(HoursResult {
+            date: start,
+            hours_on_end_date: 0.0,
+            calendar_days: 0,
+        });
+    }
+    let has_capacity = calendar.work_weeks().any(|week| {
+        week.iter()
+            .any(|day| capacity[day.num_days_from_monday() as usize] > 0.0)
+    });
+    if !has_capacity {
+        return Err(WorkCalendarError::NoWorkDaysDefined {
+            calendar: calendar.name().map(String::from),
+        });
+    }
+    let mut remaining = effort_hours;
+    let mut date = start;
+    let mut last_with_capacity = start;
+    loop {
+        let hours = capacity_on(calendar, capacity, date);
+        if hours > 0.0 {
+            if remaining <= hours + HOURS_EPSILON {
+                return Ok(HoursResult {
+                    date,
+                    hours_on_end_date: remaining.min(hours),
+                    calendar_days: (date - start).num_days() as u64,
+                });
+            }
+            remaining -= hours;
+            last_with_capacity = date;
+        } else if (date - last_with_capacity).num_days() >= SEARCH_HORIZON_DAYS {
+            return Err(WorkCalendarError::NoWorkingDaysInHorizon {
+                calendar: calendar.name().map(String::from),
+                searched_until: date,
+            });
+        }
+        date = calendar.try_add_work_days(date, 1)?;
+    }
+}
+
+impl WorkCalendar {
+    /// Returns the working hours in a full working day, [`DEFAULT_HOURS_PER_DAY`]
+    /// unless set with [`WorkCalendar::set_hours_per_day`].
+    pub fn hours_per_day(&self) -> f64 {
+        self.hours_per_day.0
+    }
+
+    /// Sets the working hours in a full working day, used by the hour-based methods
+    /// such as [`WorkCalendar::compute_end_date_hours`].
+    ///
+    /// Half-day holidays have half this many hours. To give weekdays different hours,
+    /// use a [`CapacityCalendar`].
+    ///
+    /// # Arguments
+    ///
+    /// * `hours` - The hours in a working day.
+    ///
+    /// # Errors
+    ///
+    /// [`WorkCalendarError::InvalidHoursPerDay`] if `hours` is not positive or not
+    /// finite.
+    pub fn set_hours_per_day(&mut self, hours: f64) -> Result<(), WorkCalendarError> {
+        check_hours_per_day(hours)?;
+        self.hours_per_day = HoursPerDay(hours);
+        Ok(())
+    }
+
+    /// Computes the date on which `hours_worked` hours of work, starting on
+    /// `start_date`, are used up, with [`WorkCalendar::hours_per_day`] hours on each
+    /// working date.
+    ///
+    /// This is [`CapacityCalendar::compute_end_by_hours`] with the same hours on every
+    /// weekday. A start date that is not a working date contributes no hours, so the
+    /// work begins on the next working date. Hours that fill whole days end at the end
+    /// of the last one, with a full day's hours on the end date, and match
+    /// [`WorkCalendar::compute_end`] for that many days from a working date. Zero hours
+    /// end on `start_date`.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_date` - The starting date.
+    /// * `hours_worked` - The effort in hours.
+    ///
+    /// # Errors
+    ///
+    /// The errors of [`CapacityCalendar::compute_end_by_hours`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let thursday = NaiveDate::from_ymd_opt(2023, 8, 24).unwrap();
+    ///
+    /// // Two and a half days: Thursday, Friday, and half of Monday.
+    /// let end = calendar.compute_end_date_hours(thursday, 20.0).unwrap();
+    /// assert_eq!(end.date, NaiveDate::from_ymd_opt(2023, 8, 28).unwrap());
+    /// assert_eq!(end.hours_on_end_date, 4.0);
+    /// assert_eq!(end.calendar_days, 4);
+    /// ```
+    pub fn compute_end_date_hours(
+        &self,
+        start_date: NaiveDate,
+        hours_worked: f64,
+    ) -> Result<HoursResult, WorkCalendarError> {
+        let capacity = [self.hours_per_day(); 7];
+        compute_end_by_hours(self, &capacity, start_date, hours_worked)
+    }
+
+    /// Calculates the working hours from `start_date` to `end_date`, inclusive.
+    ///
+    /// Each date [`WorkCalendar::work_days_between`] counts has
+    /// [`WorkCalendar::hours_per_day`] hours, or half as many if it is a half-day
+    /// holiday. An `end_date` before `start_date` has no hours.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_date` - The starting date.
+    /// * `end_date` - The ending date.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// calendar.set_hours_per_day(7.5).unwrap();
+    /// calendar.add_partial_holiday(NaiveDate::from_ymd_opt(2023, 8, 25).unwrap());
+    ///
+    /// let monday = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap();
+    /// let sunday = NaiveDate::from_ymd_opt(2023, 8, 27).unwrap();
+    /// assert_eq!(calendar.work_hours_between(monday, sunday), 4.5 * 7.5);
+    /// ```
+    pub fn work_hours_between(&self, start_date: NaiveDate, end_date: NaiveDate) -> f64 {
+        if start_date > end_date {
+            return 0.0;
+        }
+        let half_day_holidays = self
+            .partial_holidays
+            .range(start_date..=end_date)
+            .filter(|(date, _)| self.is_working_date(date))
+            .count() as i64;
+        let half_days = 2 * self.work_days_between(start_date, end_date) - half_day_holidays;
+        half_days as f64 * self.hours_per_day() / 2.0
+    }
+}
+
+fn check_hours(hours: f64) -> Result<(), WorkCalendarError> {
+    if hours.is_finite() && hours >= 0.0 {
+        Ok(())
+    } else {
+        Err(WorkCalendarError::InvalidHours(hours.to_string()))
+    }
+}
+
+fn check_hours_per_day(hours: f64) -> Result<(), WorkCalendarError> {
+    if hours.is_finite() && hours > 0.0 {
+        Ok(())
+    } else {
+        Err(WorkCalendarError::InvalidHoursPerDay(hours.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{date, random_calendar, TestRng};
+    use crate::WorkCalendarConfig;
+    use chrono::Duration;
+    use std::str::FromStr;
+
+    fn team() -> CapacityCalendar {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("Team");
+        calendar.add_holiday(date(2023, 9, 4));
+        let mut capacity = CapacityCalendar::new(&calendar, 8.0).unwrap();
+        capacity.set_capacity(Weekday::Fri, 6.0).unwrap();
+        capacity
+    }
+
+    #[test]
+    fn test_compute_end_by_hours() {
+        let capacity = team();
+        assert_eq!(capacity.capacity_on(date(2023, 9, 4)), 0.0);
+        assert_eq!(capacity.capacity_on(date(2023, 9, 5)), 8.0);
+        assert_eq!(capacity.capacity_on(date(2023, 9, 8)), 6.0);
+        assert_eq!(capacity.capacity_on(date(2023, 9, 9)), 0.0);
+
+        let cases = [
+            (date(2023, 9, 5), 1.5, date(2023, 9, 5), 1.5, 0),
+            (date(2023, 9, 5), 8.0, date(2023, 9, 5), 8.0, 0),
+            (date(2023, 9, 5), 8.5, date(2023, 9, 6), 0.5, 1),
+            // Tuesday to Thursday is 24 hours, then Friday's 6.
+            (date(2023, 9, 5), 30.0, date(2023, 9, 8), 6.0, 3),
+            (date(2023, 9, 5), 30.25, date(2023, 9, 11), 0.25, 6),
+            // Starting on the holiday and on a weekend.
+            (date(2023, 9, 4), 4.0, date(2023, 9, 5), 4.0, 1),
+            (date(2023, 9, 2), 12.0, date(2023, 9, 6), 4.0, 4),
+            // A leftover within the epsilon does not spill.
+            (
+                date(2023, 9, 5),
+                8.0 + HOURS_EPSILON / 2.0,
+                date(2023, 9, 5),
+                8.0,
+                0,
+            ),
+            (date(2023, 9, 5), 0.1 + 0.2 + 7.7, date(2023, 9, 5), 8.0, 0),
+            (date(2023, 9, 2), 0.0, date(2023, 9, 2), 0.0, 0),
+        ];
+        for (start, effort, end, hours, calendar_days) in cases {
+            let result = capacity.compute_end_by_hours(start, effort).unwrap();
+            assert_eq!(result.date, end, "{} + {}h", start, effort);
+            assert!(
+                (result.hours_on_end_date - hours).abs() < 1e-9,
+                "{} + {}h: {} hours on the end date",
+                start,
+                effort,
+                result.hours_on_end_date
+            );
+            assert_eq!(
+                result.calendar_days, calendar_days,
+                "{} + {}h",
+                start, effort
+            );
+        }
+    }
+
+    #[test]
+    fn test_zero_capacity_days_are_skipped() {
+        let mut capacity = team();
+        capacity.set_capacity(Weekday::Wed, 0.0).unwrap();
+        let result = capacity
+            .compute_end_by_hours(date(2023, 9, 5), 12.0)
+            .unwrap();
+        assert_eq!(result.date, date(2023, 9, 7));
+        assert_eq!(result.hours_on_end_date, 4.0);
+        assert_eq!(result.calendar_days, 2);
+    }
+
+    #[test]
+    fn test_half_day_holidays() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_partial_holiday_half(date(2023, 9, 6), crate::DayHalf::Morning);
+        let capacity = CapacityCalendar::new(&calendar, 8.0).unwrap();
+        assert_eq!(capacity.capacity_on(date(2023, 9, 6)), 4.0);
+        let result = capacity
+            .compute_end_by_hours(date(2023, 9, 5), 14.0)
+            .unwrap();
+        assert_eq!(result.date, date(2023, 9, 7));
+        assert_eq!(result.hours_on_end_date, 2.0);
+    }
+
+    #[test]
+    fn test_matches_compute_end() {
+        let mut rng = TestRng(0x03f7_a9c2_64d1_8be5);
+        for _ in 0..50 {
+            let calendar = random_calendar(&mut rng);
+            if calendar.work_days.is_empty() {
+                continue;
+            }
+            let capacity = CapacityCalendar::new(&calendar, 8.0).unwrap();
+            let start = date(2022, 1, 1) + Duration::days(rng.below(1000) as i64);
+            let days = rng.below(300) as i64;
+            let expected = calendar.compute_end(start, days).unwrap();
+            let result = capacity
+                .compute_end_by_hours(start, 8.0 * days as f64)
+                .unwrap();
+            assert_eq!(result.date, expected.date, "{} + {} days", start, days);
+            assert_eq!(result.calendar_days, expected.calendar_days);
+            if days > 0 {
+                assert_eq!(result.hours_on_end_date, 8.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let calendar = WorkCalendar::new();
+        for hours in [-1.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(
+                CapacityCalendar::new(&calendar, hours).unwrap_err(),
+                WorkCalendarError::InvalidHours(hours.to_string())
+            );
+        }
+        let mut capacity = team();
+        assert_eq!(
+            capacity.set_capacity(Weekday::Mon, -8.0),
+            Err(WorkCalendarError::InvalidHours("-8".to_string()))
+        );
+        assert_eq!(
+            capacity
+                .compute_end_by_hours(date(2023, 9, 5), -0.5)
+                .unwrap_err()
+                .to_string(),
+            "Hours must be finite and non-negative; got -0.5"
+        );
+
+        for day in [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ] {
+            capacity.set_capacity(day, 0.0).unwrap();
+        }
+        assert_eq!(
+            capacity.compute_end_by_hours(date(2023, 9, 5), 1.0),
+            Err(WorkCalendarError::NoWorkDaysDefined {
+                calendar: Some("Team".to_string())
+            })
+        );
+
+        // Earlier work weeks have capacity, but the one in effect does not.
+        let mut calendar = WorkCalendar::new();
+        calendar
+            .set_work_days_effective(date(2024, 1, 1), "Sat")
+            .unwrap();
+        let mut capacity = CapacityCalendar::new(&calendar, 8.0).unwrap();
+        capacity.set_capacity(Weekday::Sat, 0.0).unwrap();
+        let start = date(2024, 6, 3);
+        assert!(matches!(
+            capacity.compute_end_by_hours(start, 4.0),
+            Err(WorkCalendarError::NoWorkingDaysInHorizon { calendar: None, searched_until })
+                if (searched_until - start).num_days() >= SEARCH_HORIZON_DAYS
+        ));
+    }
+
+    #[test]
+    fn test_hours_per_day() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2023, 9, 4));
+        assert_eq!(calendar.hours_per_day(), DEFAULT_HOURS_PER_DAY);
+        calendar.set_hours_per_day(7.5).unwrap();
+
+        let cases = [
+            // Zero hours end on the start date, even a holiday.
+            (date(2023, 9, 5), 0.0, date(2023, 9, 5), 0.0, 0),
+            (date(2023, 9, 4), 0.0, date(2023, 9, 4), 0.0, 0),
+            // Exact day boundaries end at the end of the last day.
+            (date(2023, 9, 5), 7.5, date(2023, 9, 5), 7.5, 0),
+            (date(2023, 9, 5), 15.0, date(2023, 9, 6), 7.5, 1),
+            (date(2023, 9, 7), 15.0, date(2023, 9, 8), 7.5, 1),
+            (date(2023, 9, 7), 15.5, date(2023, 9, 11), 0.5, 4),
+            // A holiday start begins on the next working date.
+            (date(2023, 9, 4), 7.5, date(2023, 9, 5), 7.5, 1),
+            (date(2023, 9, 2), 10.0, date(2023, 9, 6), 2.5, 4),
+        ];
+        for (start, hours, end, on_end, days) in cases {
+            let result = calendar.compute_end_date_hours(start, hours).unwrap();
+            assert_eq!(
+                (result.date, result.hours_on_end_date, result.calendar_days),
+                (end, on_end, days),
+                "{} hours from {}",
+                hours,
+                start
+            );
+        }
+
+        for days in 1..=20 {
+            let start = date(2023, 9, 5);
+            let hours = calendar
+                .compute_end_date_hours(start, days as f64 * 7.5)
+                .unwrap();
+            assert_eq!(hours.date, calendar.compute_end(start, days).unwrap().date);
+        }
+
+        for hours in [0.0, -0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(
+                calendar.set_hours_per_day(hours),
+                Err(WorkCalendarError::InvalidHoursPerDay(hours.to_string()))
+            );
+        }
+        assert_eq!(
+            calendar.set_hours_per_day(0.0).unwrap_err().to_string(),
+            "Hours per day must be finite and positive; got 0"
+        );
+        assert_eq!(calendar.hours_per_day(), 7.5);
+    }
+
+    #[test]
+    fn test_work_hours_between() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2023, 9, 4));
+        calendar.add_partial_holiday(date(2023, 9, 8));
+        // A half-day holiday on a weekend has no hours to halve.
+        calendar.add_partial_holiday(date(2023, 9, 9));
+
+        assert_eq!(
+            calendar.work_hours_between(date(2023, 9, 4), date(2023, 9, 4)),
+            0.0
+        );
+        assert_eq!(
+            calendar.work_hours_between(date(2023, 9, 5), date(2023, 9, 5)),
+            8.0
+        );
+        assert_eq!(
+            calendar.work_hours_between(date(2023, 9, 8), date(2023, 9, 8)),
+            4.0
+        );
+        assert_eq!(
+            calendar.work_hours_between(date(2023, 9, 4), date(2023, 9, 10)),
+            28.0
+        );
+        assert_eq!(
+            calendar.work_hours_between(date(2023, 9, 10), date(2023, 9, 4)),
+            0.0
+        );
+
+        let mut rng = TestRng(0x5b1e_0c97_d2a4_3f86);
+        for _ in 0..200 {
+            let mut calendar = random_calendar(&mut rng);
+            calendar
+                .set_hours_per_day((1 + rng.below(10)) as f64)
+                .unwrap();
+            let start = date(2023, 1, 1) + Duration::days(rng.below(730) as i64);
+            let end = start + Duration::days(rng.below(120) as i64);
+            let expected: f64 = start
+                .iter_days()
+                .take_while(|day| *day <= end)
+                .map(|day| calendar.half_days_on(&day) as f64 * calendar.hours_per_day() / 2.0)
+                .sum();
+            assert_eq!(calendar.work_hours_between(start, end), expected);
+        }
+    }
+
+    #[test]
+    fn test_hours_per_day_config() {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_hours_per_day(7.5).unwrap();
+        let yaml = WorkCalendarConfig::from(&calendar).to_yaml();
+        assert!(yaml.contains("hours_per_day: 7.5"));
+        assert_eq!(WorkCalendar::from_str(&yaml).unwrap(), calendar);
+        assert!(!WorkCalendarConfig::from(&WorkCalendar::new())
+            .to_yaml()
+            .contains("hours_per_day"));
+        assert_ne!(calendar.fingerprint(), WorkCalendar::new().fingerprint());
+
+        let config = WorkCalendarConfig::new().with_hours_per_day(6.0);
+        assert_eq!(WorkCalendar::try_from(config).unwrap().hours_per_day(), 6.0);
+        for (yaml, hours) in [("hours_per_day: -2", "-2"), ("hours_per_day: 0", "0")] {
+            assert_eq!(
+                WorkCalendar::from_str(yaml).unwrap_err(),
+                WorkCalendarError::InvalidConfig {
+                    calendar: None,
+                    message: format!("hours_per_day must be finite and positive: {}", hours),
+                }
+            );
+        }
+        let json = serde_json::to_string(&calendar).unwrap();
+        assert_eq!(
+            serde_json::from_str::<WorkCalendar>(&json).unwrap(),
+            calendar
+        );
+        assert!(serde_json::from_str::<WorkCalendar>(r#"{"hours_per_day": -1.0}"#).is_err());
+        let zero = json.replace("7.5", "0.0");
+        assert_ne!(zero, json);
+        assert!(serde_json::from_str::<WorkCalendar>(&zero).is_err());
+    }
+}
@@ -0,0 +1,414 @@
+//! HTML tables of a calendar's months, for embedding in wiki pages and email reports.
+
+use crate::{Locale, NonWorkingReason, WorkCalendar};
+use chrono::{Datelike, NaiveDate, Weekday};
+
+/// How [`WorkCalendar::render_month_html`] and [`WorkCalendar::render_year_html`]
+/// lay out and style their tables.
+///
+/// The default starts weeks on Monday, styles cells inline, marks no date as today,
+/// and writes English names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HtmlOptions {
+    /// The weekday of each table's first column.
+    pub week_start: Weekday,
+    /// Whether to give each element a `style` attribute with the default look, so the
+    /// output displays well without a stylesheet. Email clients often drop `<style>`
+    /// elements, so the styles are written on the elements themselves. Without them,
+    /// style the output through its classes.
+    pub inline_style: bool,
+    /// The date to mark with the `today` class, if any.
+    pub today: Option<NaiveDate>,
+    /// The language of month and weekday names.
+    pub locale: Locale,
+}
+
+impl Default for HtmlOptions {
+    fn default() -> Self {
+        HtmlOptions {
+            week_start: Weekday::Mon,
+            inline_style: true,
+            today: None,
+            locale: Locale::English,
+        }
+    }
+}
+
+/// Inline styles for the table parts and for each cell class.
+const TABLE_STYLE: &str = "border-collapse:collapse;text-align:center;margin:0 1em 1em 0";
+const CAPTION_STYLE: &str = "font-weight:bold;padding:4px";
+const CELL_STYLE: &str = "padding:2px 6px;border:1px solid #ddd";
+const CLASS_STYLES: [(&str, &str); 5] = [
+    ("workday", "background:#e8f5e9"),
+    ("weekend", "background:#eee;color:#777"),
+    ("holiday", "background:#ffebee;color:#b71c1c"),
+    ("override-working", "background:#e3f2fd"),
+    ("today", "font-weight:bold;outline:2px solid #1565c0"),
+];
+
+/// Escapes text for use in element content and quoted attribute values.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+impl WorkCalendar {
+    /// Renders one month as a self-contained HTML `<table>`.
+    ///
+    /// The table has a caption with the month and year, a header row of weekday
+    /// abbreviations, and one row per week. Each date's cell holds a `<time>` element
+    /// and has these classes:
+    ///
+    /// | Class              | Meaning                                                |
+    /// |--------------------|--------------------------------------------------------|
+    /// | `workday`          | a working date on one of the week's work days          |
+    /// | `override-working` | a working date only because of an override             |
+    /// | `weekend`          | not a work day of the week in effect                   |
+    /// | `holiday`          | a holiday, on a work day or not                        |
+    /// | `today`            | the date given as [`HtmlOptions::today`]               |
+    ///
+    /// Cells of non-working dates and overrides carry a `title` describing the date.
+    /// The calendar keeps no holiday names, so a holiday added with a category is
+    /// titled with it, as in `Holiday (optional)`. Cells
+    /// before the first and after the last date of the month are empty. The output
+    /// is valid HTML5, needs no external assets, and depends only on the calendar and
+    /// `options`.
+    ///
+    /// # Arguments
+    ///
+    /// * `year` - The year.
+    /// * `month` - The month, from 1 (January) to 12 (December).
+    /// * `options` - The layout and styling.
+    ///
+    /// # Returns
+    ///
+    /// The table, or `None` if `month` is not a valid month or the month cannot be
+    /// represented.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{HtmlOptions, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// calendar.add_holiday_with_category(NaiveDate::from_ymd_opt(2024, 2, 19).unwrap(), "public");
+    /// let options = HtmlOptions {
+    ///     inline_style: false,
+    ///     ..HtmlOptions::default()
+    /// };
+    /// let html = calendar.render_month_html(2024, 2, &options).unwrap();
+    /// assert!(html.starts_with("<table class=\"workdays-month\">\n<caption>February 2024</caption>"));
+    /// assert!(html.contains(
+    ///     "<td class=\"holiday\" title=\"Holiday (public)\"><time datetime=\"2024-02-19\">19</time></td>"
+    /// ));
+    /// assert!(calendar.render_month_html(2024, 13, &options).is_none());
+    /// ```
+    pub fn render_month_html(
+        &self,
+        year: i32,
+        month: u32,
+        options: &HtmlOptions,
+    ) -> Option<String> {
+        let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+        let caption = format!("{} {}", options.locale.month_name(month)?, year);
+        let style = |style: &str| {
+            if options.inline_style {
+                format!(" style=\"{}\"", style)
+            } else {
+                String::new()
+            }
+        };
+
+        let mut html = format!(
+            "<table class=\"workdays-month\"{}>\n<caption{}>{}</caption>\n<thead><tr>",
+            style(TABLE_STYLE),
+            style(CAPTION_STYLE),
+            escape(&caption)
+        );
+        for offset in 0..7 {
+            let day = weekday_after(options.week_start, offset);
+            html.push_str(&format!(
+                "<th scope=\"col\" abbr=\"{}\"{}>{}</th>",
+                escape(options.locale.weekday_name(day)),
+                style(CELL_STYLE),
+                escape(options.locale.weekday_abbreviation(day))
+            ));
+        }
+        html.push_str("</tr></thead>\n<tbody>\n");
+
+        let lead = (first.weekday().num_days_from_monday() + 7
+            - options.week_start.num_days_from_monday())
+            % 7;
+        let mut column = 0;
+        html.push_str("<tr>");
+        for _ in 0..lead {
+            html.push_str(&format!("<td{}></td>", style(CELL_STYLE)));
+            column += 1;
+        }
+        let dates = std::iter::successors(Some(first), |date| date.succ_opt())
+            .take_while(|date| date.month() == month);
+        for date in dates {
+            if column == 7 {
+                html.push_str("</tr>\n<tr>");
+                column = 0;
+            }
+            html.push_str(&self.day_cell_html(date, options));
+            column += 1;
+        }
+        while column < 7 {
+            html.push_str(&format!("<td{}></td>", style(CELL_STYLE)));
+            column += 1;
+        }
+        html.push_str("</tr>\n</tbody>\n</table>\n");
+        Some(html)
+    }
+
+    /// Renders a whole year as twelve month tables, as
+    /// [`WorkCalendar::render_month_html`] does, inside a
+    /// `<div class="workdays-year">`.
+    ///
+    /// # Arguments
+    ///
+    /// * `year` - The year.
+    /// * `options` - The layout and styling, applied to every month.
+    ///
+    /// # Returns
+    ///
+    /// The fragment, or `None` if the year cannot be represented.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{HtmlOptions, WorkCalendar};
+    ///
+    /// let html = WorkCalendar::new()
+    ///     .render_year_html(2024, &HtmlOptions::default())
+    ///     .unwrap();
+    /// assert_eq!(html.matches("<table").count(), 12);
+    /// assert!(html.contains("<caption style=\"font-weight:bold;padding:4px\">December 2024</caption>"));
+    /// ```
+    pub fn render_year_html(&self, year: i32, options: &HtmlOptions) -> Option<String> {
+        let mut html = if options.inline_style {
+            "<div class=\"workdays-year\" style=\"display:flex;flex-wrap:wrap;align-items:flex-start\">\n"
+                .to_string()
+        } else {
+            "<div class=\"workdays-year\">\n".to_string()
+        };
+        for month in 1..=12 {
+            html.push_str(&self.render_month_html(year, month, options)?);
+        }
+        html.push_str("</div>\n");
+        Some(html)
+    }
+
+    /// Renders the table cell for `date`.
+    fn day_cell_html(&self, date: NaiveDate, options: &HtmlOptions) -> String {
+        let reason = self.non_working_reason(&date);
+        let mut classes = Vec::new();
+        let title = match reason {
+            None if self.work_week_on(date).contains(date.weekday()) => {
+                classes.push("workday");
+                None
+            }
+            None => {
+                classes.push("override-working");
+                Some("Extra working date".to_string())
+            }
+            Some(NonWorkingReason::Weekend) => {
+                classes.push("weekend");
+                Some("Weekend".to_string())
+            }
+            Some(NonWorkingReason::Holiday) | Some(NonWorkingReason::WeekendAndHoliday) => {
+                if reason == Some(NonWorkingReason::WeekendAndHoliday) {
+                    classes.push("weekend");
+                }
+                classes.push("holiday");
+                Some(match self.holiday_category(&date) {
+                    Some(category) => format!("Holiday ({})", category),
+                    None => "Holiday".to_string(),
+                })
+            }
+        };
+        if options.today == Some(date) {
+            classes.push("today");
+        }
+
+        let mut cell = format!("<td class=\"{}\"", classes.join(" "));
+        if let Some(title) = title {
+            cell.push_str(&format!(" title=\"{}\"", escape(&title)));
+        }
+        if options.inline_style {
+            let mut style = CELL_STYLE.to_string();
+            for (class, class_style) in CLASS_STYLES {
+                if classes.contains(&class) {
+                    style.push(';');
+                    style.push_str(class_style);
+                }
+            }
+            cell.push_str(&format!(" style=\"{}\"", style));
+        }
+        cell.push_str(&format!(
+            "><time datetime=\"{}\">{}</time></td>",
+            date.format("%Y-%m-%d"),
+            date.day()
+        ));
+        cell
+    }
+}
+
+/// Returns the weekday `offset` days after `start`.
+fn weekday_after(start: Weekday, offset: u32) -> Weekday {
+    (0..offset).fold(start, |day, _| day.succ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::date;
+
+    fn plain() -> HtmlOptions {
+        HtmlOptions {
+            inline_style: false,
+            ..HtmlOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_month_golden() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2023, 12, 25));
+        calendar.add_holiday_with_category(date(2023, 12, 26), "<bank> & \"co\"");
+        calendar.add_holiday(date(2023, 12, 31));
+        calendar.add_working_date(date(2023, 12, 16));
+        let options = HtmlOptions {
+            today: Some(date(2023, 12, 20)),
+            ..plain()
+        };
+        let html = calendar.render_month_html(2023, 12, &options).unwrap();
+
+        let cell = |d: u32, class: &str, title: Option<&str>| {
+            let title = title.map_or(String::new(), |title| format!(" title=\"{}\"", title));
+            format!(
+                "<td class=\"{}\"{}><time datetime=\"2023-12-{:02}\">{}</time></td>",
+                class, title, d, d
+            )
+        };
+        let day = |d: u32| {
+            let weekday = date(2023, 12, d).weekday();
+            match d {
+                16 => cell(d, "override-working", Some("Extra working date")),
+                20 => cell(d, "workday today", None),
+                25 => cell(d, "holiday", Some("Holiday")),
+                26 => cell(
+                    d,
+                    "holiday",
+                    Some("Holiday (&lt;bank&gt; &amp; &quot;co&quot;)"),
+                ),
+                31 => cell(d, "weekend holiday", Some("Holiday")),
+                _ if weekday == Weekday::Sat || weekday == Weekday::Sun => {
+                    cell(d, "weekend", Some("Weekend"))
+                }
+                _ => cell(d, "workday", None),
+            }
+        };
+        // December 1, 2023 is a Friday.
+        let mut expected = String::from(
+            "<table class=\"workdays-month\">\n<caption>December 2023</caption>\n<thead><tr>\
+             <th scope=\"col\" abbr=\"Monday\">Mon</th><th scope=\"col\" abbr=\"Tuesday\">Tue</th>\
+             <th scope=\"col\" abbr=\"Wednesday\">Wed</th><th scope=\"col\" abbr=\"Thursday\">Thu</th>\
+             <th scope=\"col\" abbr=\"Friday\">Fri</th><th scope=\"col\" abbr=\"Saturday\">Sat</th>\
+             <th scope=\"col\" abbr=\"Sunday\">Sun</th></tr></thead>\n<tbody>\n\
+             <tr><td></td><td></td><td></td><td></td>",
+        );
+        for d in 1..=31 {
+            if d > 1 && date(2023, 12, d).weekday() == Weekday::Mon {
+                expected.push_str("</tr>\n<tr>");
+            }
+            expected.push_str(&day(d));
+        }
+        expected.push_str("</tr>\n</tbody>\n</table>\n");
+        assert_eq!(html, expected);
+    }
+
+    #[test]
+    fn test_week_start_and_padding() {
+        let calendar = WorkCalendar::new();
+        let options = HtmlOptions {
+            week_start: Weekday::Sun,
+            ..plain()
+        };
+        // September 2024 starts on a Sunday and spans five Sunday-first weeks.
+        let html = calendar.render_month_html(2024, 9, &options).unwrap();
+        assert!(html.contains("<thead><tr><th scope=\"col\" abbr=\"Sunday\">Sun</th>"));
+        assert!(html.contains("<tbody>\n<tr><td class=\"weekend\""));
+        assert_eq!(html.matches("<tr>").count(), 6);
+        assert!(html.ends_with(
+            "<time datetime=\"2024-09-30\">30</time></td><td></td><td></td><td></td><td></td>\
+             <td></td></tr>\n</tbody>\n</table>\n"
+        ));
+
+        for month in 1..=12 {
+            for start in [Weekday::Mon, Weekday::Wed, Weekday::Sun] {
+                let options = HtmlOptions {
+                    week_start: start,
+                    ..plain()
+                };
+                let html = calendar.render_month_html(2025, month, &options).unwrap();
+                let rows = html.split("<tr>").skip(2);
+                for row in rows {
+                    assert_eq!(row.matches("<td").count(), 7, "{}", row);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_inline_style_and_year() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2024, 7, 4));
+        let html = calendar
+            .render_month_html(2024, 7, &HtmlOptions::default())
+            .unwrap();
+        assert!(html.contains(
+            "<td class=\"holiday\" title=\"Holiday\" style=\"padding:2px 6px;border:1px solid #ddd;\
+             background:#ffebee;color:#b71c1c\"><time datetime=\"2024-07-04\">4</time></td>"
+        ));
+        assert!(!calendar
+            .render_month_html(2024, 7, &plain())
+            .unwrap()
+            .contains("style="));
+
+        let year = calendar.render_year_html(2024, &plain()).unwrap();
+        assert!(year.starts_with("<div class=\"workdays-year\">\n<table"));
+        assert!(year.ends_with("</table>\n</div>\n"));
+        assert_eq!(year.matches("<time ").count(), 366);
+        assert_eq!(year, calendar.render_year_html(2024, &plain()).unwrap());
+
+        assert!(calendar.render_month_html(2024, 0, &plain()).is_none());
+        assert!(calendar.render_year_html(i32::MAX, &plain()).is_none());
+    }
+
+    #[test]
+    fn test_locale() {
+        let options = HtmlOptions {
+            locale: Locale::German,
+            ..plain()
+        };
+        let html = WorkCalendar::new()
+            .render_month_html(2024, 3, &options)
+            .unwrap();
+        assert!(html.contains("<caption>März 2024</caption>"), "{}", html);
+        assert!(html.contains("abbr=\"Montag\""));
+    }
+}
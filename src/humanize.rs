@@ -0,0 +1,379 @@
+//! Descriptions of calendars, work-day durations and date spans, in English or another
+//! [`Locale`].
+
+use crate::{count_work_weekdays, Locale, WorkCalendar};
+use chrono::{Datelike, NaiveDate};
+use std::fmt;
+
+impl WorkCalendar {
+    /// Describes a number of work days in work weeks and days, using this calendar's
+    /// week length.
+    ///
+    /// A work week is as many work days as the calendar works per week, so with a
+    /// Monday-to-Thursday week, 9 work days is "2 work weeks and 1 work day". Zero
+    /// components are left out, and negative durations are prefixed with "minus". A
+    /// calendar with no work days describes everything in work days.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// assert_eq!(calendar.format_work_duration(17), "3 work weeks and 2 work days");
+    /// assert_eq!(calendar.format_work_duration(5), "1 work week");
+    /// assert_eq!(calendar.format_work_duration(2), "2 work days");
+    /// assert_eq!(calendar.format_work_duration(0), "0 work days");
+    /// ```
+    pub fn format_work_duration(&self, work_days: i64) -> String {
+        self.format_work_duration_localized(work_days, Locale::English)
+    }
+
+    /// Like [`WorkCalendar::format_work_duration`], but in `locale`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{Locale, WorkCalendar};
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// assert_eq!(
+    ///     calendar.format_work_duration_localized(17, Locale::German),
+    ///     "3 Arbeitswochen und 2 Arbeitstage"
+    /// );
+    /// ```
+    pub fn format_work_duration_localized(&self, work_days: i64, locale: Locale) -> String {
+        let words = locale.words();
+        let total = work_days.unsigned_abs();
+        let per_week = self.work_days.days_per_week().unsigned_abs();
+        let (weeks, days) = match per_week {
+            0 => (0, total),
+            n => (total / n, total % n),
+        };
+        let parts = [
+            (weeks > 0).then(|| locale.count(weeks, &words.work_week)),
+            (days > 0 || weeks == 0).then(|| locale.count(days, &words.work_day)),
+        ];
+        let duration = locale.join(parts.into_iter().flatten().collect());
+        if work_days < 0 {
+            format!("{} {}", words.minus, duration)
+        } else {
+            duration
+        }
+    }
+
+    /// Describes the dates from `start_date` to `end_date`, inclusive, as a sentence.
+    ///
+    /// Every date in the span is counted once, as a working date, a weekend day, or a
+    /// holiday. A holiday here is a date that would have been working but for a
+    /// holiday; a holiday falling on a non-work day counts as a weekend day. Zero
+    /// counts are left out, and an empty span (`start_date` after `end_date`) spans 0
+    /// calendar days.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 12, 25).unwrap());
+    /// let start = NaiveDate::from_ymd_opt(2023, 12, 4).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2023, 12, 26).unwrap();
+    /// assert_eq!(
+    ///     calendar.describe_span(start, end),
+    ///     "3 work weeks and 1 work day, spanning 23 calendar days \
+    ///      (6 weekend days and 1 holiday)"
+    /// );
+    /// ```
+    pub fn describe_span(&self, start_date: NaiveDate, end_date: NaiveDate) -> String {
+        self.describe_span_localized(start_date, end_date, Locale::English)
+    }
+
+    /// Like [`WorkCalendar::describe_span`], but in `locale`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{Locale, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 12, 25).unwrap());
+    /// let start = NaiveDate::from_ymd_opt(2023, 12, 4).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2023, 12, 26).unwrap();
+    /// assert_eq!(
+    ///     calendar.describe_span_localized(start, end, Locale::French),
+    ///     "3 semaines ouvrées et 1 jour ouvré, sur 23 jours calendaires \
+    ///      (6 jours de week-end et 1 jour férié)"
+    /// );
+    /// ```
+    pub fn describe_span_localized(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        locale: Locale,
+    ) -> String {
+        let words = locale.words();
+        let total = if start_date <= end_date {
+            (end_date - start_date).num_days() + 1
+        } else {
+            0
+        };
+        let working = self.work_days_between(start_date, end_date);
+        // Dates that would be working if there were no holidays.
+        let potential: i64 = self
+            .week_segments(start_date, end_date)
+            .map(|(week, first, last)| {
+                count_work_weekdays(|day| week.contains(day), week.days_per_week(), first, last)
+                    + self
+                        .working_dates_in(first, last)
+                        .filter(|date| !week.contains(date.weekday()))
+                        .count() as i64
+            })
+            .sum();
+
+        let mut sentence = format!(
+            "{}, {} {}",
+            self.format_work_duration_localized(working, locale),
+            words.spanning,
+            locale.count(total.unsigned_abs(), &words.calendar_day)
+        );
+        let others = [
+            (total - potential, &words.weekend_day),
+            (potential - working, &words.holiday),
+        ];
+        let others: Vec<String> = others
+            .iter()
+            .filter(|(count, _)| *count > 0)
+            .map(|(count, noun)| locale.count(count.unsigned_abs(), noun))
+            .collect();
+        if !others.is_empty() {
+            sentence.push_str(&format!(" ({})", locale.join(others)));
+        }
+        sentence
+    }
+
+    /// Returns the one-line summary written by the calendar's
+    /// [`Display`](std::fmt::Display) implementation, in `locale`.
+    ///
+    /// The calendar's name is written as configured.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{Locale, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// calendar.set_name("Berlin");
+    /// calendar.add_holiday(NaiveDate::from_ymd_opt(2025, 10, 3).unwrap());
+    /// assert_eq!(calendar.to_string(), "Berlin: Mon, Tue, Wed, Thu, Fri; 1 holiday");
+    /// assert_eq!(
+    ///     calendar.describe_localized(Locale::German),
+    ///     "Berlin: Mo, Di, Mi, Do, Fr; 1 Feiertag"
+    /// );
+    /// ```
+    pub fn describe_localized(&self, locale: Locale) -> String {
+        let mut summary = String::new();
+        self.write_summary(&mut summary, locale)
+            .expect("writing to a String cannot fail");
+        summary
+    }
+
+    /// Writes the summary described by [`WorkCalendar::describe_localized`].
+    pub(crate) fn write_summary(&self, f: &mut dyn fmt::Write, locale: Locale) -> fmt::Result {
+        let words = locale.words();
+        if let Some(name) = self.name() {
+            write!(f, "{}: ", name)?;
+        }
+        let days: Vec<&str> = self
+            .sorted_work_days()
+            .into_iter()
+            .map(|day| locale.weekday_abbreviation(day))
+            .collect();
+        if days.is_empty() {
+            f.write_str(words.no_work_days)?;
+        } else {
+            f.write_str(&days.join(", "))?;
+        }
+        let holidays = self.holidays.len() as u64;
+        write!(f, "; {}", locale.count(holidays, &words.holiday))?;
+        match self.recurring_holidays().len() as u64 {
+            0 => Ok(()),
+            n => write!(f, "; {}", locale.count(n, &words.recurring_holiday)),
+        }
+    }
+
+    /// Returns the working-date overrides within `start_date..=end_date`.
+    fn working_dates_in(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> impl Iterator<Item = &NaiveDate> + '_ {
+        let range =
+            (start_date <= end_date).then(|| self.working_dates.range(start_date..=end_date));
+        range.into_iter().flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::date;
+    use crate::HolidayRule;
+    use chrono::Weekday;
+
+    #[test]
+    fn test_format_work_duration_golden() {
+        let calendar = WorkCalendar::new();
+        let cases = [
+            (0, "0 work days"),
+            (1, "1 work day"),
+            (4, "4 work days"),
+            (5, "1 work week"),
+            (6, "1 work week and 1 work day"),
+            (10, "2 work weeks"),
+            (17, "3 work weeks and 2 work days"),
+            (-1, "minus 1 work day"),
+            (-12, "minus 2 work weeks and 2 work days"),
+            (
+                i64::MIN,
+                "minus 1844674407370955161 work weeks and 3 work days",
+            ),
+        ];
+        for (days, expected) in cases {
+            assert_eq!(calendar.format_work_duration(days), expected);
+        }
+
+        let mut four_day = WorkCalendar::new();
+        four_day.remove_work_day(&Weekday::Fri);
+        assert_eq!(
+            four_day.format_work_duration(9),
+            "2 work weeks and 1 work day"
+        );
+        for n in 0..7 {
+            four_day.remove_work_day(&Weekday::try_from(n).unwrap());
+        }
+        assert_eq!(four_day.format_work_duration(9), "9 work days");
+    }
+
+    #[test]
+    fn test_describe_span_golden() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2023, 12, 25));
+        calendar.add_holiday(date(2023, 12, 30));
+        calendar.add_working_date(date(2023, 12, 16));
+        let cases = [
+            (
+                date(2023, 12, 4),
+                date(2023, 12, 8),
+                "1 work week, spanning 5 calendar days",
+            ),
+            (
+                date(2023, 12, 4),
+                date(2023, 12, 4),
+                "1 work day, spanning 1 calendar day",
+            ),
+            (
+                date(2023, 12, 9),
+                date(2023, 12, 10),
+                "0 work days, spanning 2 calendar days (2 weekend days)",
+            ),
+            (
+                date(2023, 12, 25),
+                date(2023, 12, 25),
+                "0 work days, spanning 1 calendar day (1 holiday)",
+            ),
+            (
+                date(2023, 12, 15),
+                date(2023, 12, 17),
+                "2 work days, spanning 3 calendar days (1 weekend day)",
+            ),
+            (
+                date(2023, 12, 18),
+                date(2023, 12, 31),
+                "1 work week and 4 work days, spanning 14 calendar days \
+                 (4 weekend days and 1 holiday)",
+            ),
+            (
+                date(2023, 12, 8),
+                date(2023, 12, 4),
+                "0 work days, spanning 0 calendar days",
+            ),
+        ];
+        for (start, end, expected) in cases {
+            assert_eq!(calendar.describe_span(start, end), expected);
+        }
+    }
+
+    #[test]
+    fn test_localized_golden() {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("Paris");
+        calendar.add_holiday(date(2023, 12, 25));
+        calendar.add_holiday(date(2023, 12, 30));
+        calendar.add_recurring_holiday(HolidayRule::FixedDate { month: 5, day: 1 });
+        let mut closed = WorkCalendar::new();
+        for n in 0..7 {
+            closed.remove_work_day(&Weekday::try_from(n).unwrap());
+        }
+        let (start, end) = (date(2023, 12, 18), date(2023, 12, 31));
+
+        let cases = [
+            (
+                Locale::English,
+                "Paris: Mon, Tue, Wed, Thu, Fri; 2 holidays; 1 recurring holiday",
+                "no work days; 0 holidays",
+                "minus 1 work week and 1 work day",
+                "1 work week and 4 work days, spanning 14 calendar days \
+                 (4 weekend days and 1 holiday)",
+            ),
+            (
+                Locale::German,
+                "Paris: Mo, Di, Mi, Do, Fr; 2 Feiertage; 1 wiederkehrender Feiertag",
+                "keine Arbeitstage; 0 Feiertage",
+                "minus 1 Arbeitswoche und 1 Arbeitstag",
+                "1 Arbeitswoche und 4 Arbeitstage, über 14 Kalendertage \
+                 (4 Wochenendtage und 1 Feiertag)",
+            ),
+            (
+                Locale::French,
+                "Paris: lun., mar., mer., jeu., ven.; 2 jours fériés; 1 jour férié récurrent",
+                "aucun jour ouvré; 0 jour férié",
+                "moins 1 semaine ouvrée et 1 jour ouvré",
+                "1 semaine ouvrée et 4 jours ouvrés, sur 14 jours calendaires \
+                 (4 jours de week-end et 1 jour férié)",
+            ),
+            (
+                Locale::Spanish,
+                "Paris: lun, mar, mié, jue, vie; 2 festivos; 1 festivo recurrente",
+                "ningún día laborable; 0 festivos",
+                "menos 1 semana laborable y 1 día laborable",
+                "1 semana laborable y 4 días laborables, en 14 días naturales \
+                 (4 días de fin de semana y 1 festivo)",
+            ),
+        ];
+        for (locale, summary, closed_summary, duration, span) in cases {
+            assert_eq!(calendar.describe_localized(locale), summary);
+            assert_eq!(closed.describe_localized(locale), closed_summary);
+            assert_eq!(
+                calendar.format_work_duration_localized(-6, locale),
+                duration
+            );
+            assert_eq!(calendar.describe_span_localized(start, end, locale), span);
+        }
+        assert_eq!(
+            calendar.to_string(),
+            calendar.describe_localized(Locale::English)
+        );
+        assert_eq!(
+            calendar.describe_span_localized(start, start, Locale::French),
+            "1 jour ouvré, sur 1 jour calendaire"
+        );
+        assert_eq!(
+            calendar.describe_span_localized(end, start, Locale::French),
+            "0 jour ouvré, sur 0 jour calendaire"
+        );
+    }
+}
@@ -0,0 +1,803 @@
+//! Holiday import from iCalendar (RFC 5545) data, available with the `ics` feature.
+//!
+//! Only the parts of the format that describe days off are read: each `VEVENT`'s
+//! `DTSTART`, `DTEND` or `DURATION`, `STATUS`, and a yearly `RRULE`. Other components,
+//! such as `VTIMEZONE` and `VALARM`, and other properties are skipped.
+
+use crate::{HolidayRule, WorkCalendar, WorkCalendarError};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime};
+
+/// Options for [`WorkCalendar::add_holidays_from_ics_with`].
+///
+/// The default imports all-day events only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct IcsOptions {
+    /// Whether events with a time of day, such as a 09:00 to 17:00 offsite, also make
+    /// their dates holidays. Each date the event touches becomes a holiday, except an
+    /// end date it reaches only at midnight. Times are read as written, without
+    /// converting between time zones.
+    pub include_timed_events: bool,
+}
+
+impl WorkCalendar {
+    /// Adds the all-day events of an iCalendar document as holidays.
+    ///
+    /// Same as [`WorkCalendar::add_holidays_from_ics_with`] with the default
+    /// [`IcsOptions`].
+    ///
+    /// # Errors
+    ///
+    /// The errors of [`WorkCalendar::add_holidays_from_ics_with`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let ics = "BEGIN:VCALENDAR\r\n\
+    ///            BEGIN:VEVENT\r\n\
+    ///            SUMMARY:Year-end closure\r\n\
+    ///            DTSTART;VALUE=DATE:20241224\r\n\
+    ///            DTEND;VALUE=DATE:20241227\r\n\
+    ///            END:VEVENT\r\n\
+    ///            END:VCALENDAR\r\n";
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// assert_eq!(calendar.add_holidays_from_ics(ics).unwrap(), 3);
+    /// assert!(calendar.is_holiday(&NaiveDate::from_ymd_opt(2024, 12, 26).unwrap()));
+    /// assert!(!calendar.is_holiday(&NaiveDate::from_ymd_opt(2024, 12, 27).unwrap()));
+    /// ```
+    pub fn add_holidays_from_ics(&mut self, ics: &str) -> Result<usize, WorkCalendarError> {
+        self.add_holidays_from_ics_with(ics, IcsOptions::default())
+    }
+
+    /// Adds the events of an iCalendar document as holidays.
+    ///
+    /// Every date an event covers becomes a holiday. As RFC 5545 specifies, `DTEND` is
+    /// exclusive, so an all-day event from `20241224` to `20241227` covers three dates,
+    /// and an all-day event without `DTEND` or `DURATION` covers one. Cancelled events
+    /// are skipped.
+    ///
+    /// A yearly recurring event (`RRULE:FREQ=YEARLY`) becomes a recurring holiday
+    /// effective from its first occurrence up to its `UNTIL` date or its last `COUNT`
+    /// occurrence, as with [`WorkCalendar::add_holiday_effective`]. It repeats either on
+    /// its start date's month and day or, with `BYDAY` and `BYMONTH`, on a weekday of a
+    /// month such as `BYDAY=4TH;BYMONTH=11`.
+    ///
+    /// The whole document is read before anything is added, so on error the calendar
+    /// is unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `ics` - The iCalendar document.
+    /// * `options` - Which events to import.
+    ///
+    /// # Returns
+    ///
+    /// The number of holidays added: each date that was not already a holiday, and each
+    /// recurring holiday that was not already in the calendar.
+    ///
+    /// # Errors
+    ///
+    /// [`WorkCalendarError::InvalidIcs`] if the document is malformed, an event has no
+    /// valid `DTSTART` or ends before it starts, or an event recurs in a way a
+    /// recurring holiday cannot express (not yearly, every other year, or with
+    /// `EXDATE` or `RDATE` exceptions).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{IcsOptions, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let ics = "BEGIN:VCALENDAR\n\
+    ///            BEGIN:VEVENT\n\
+    ///            SUMMARY:Company offsite\n\
+    ///            DTSTART:20240315T090000\n\
+    ///            DTEND:20240315T170000\n\
+    ///            END:VEVENT\n\
+    ///            BEGIN:VEVENT\n\
+    ///            SUMMARY:New Year's Day\n\
+    ///            DTSTART;VALUE=DATE:20200101\n\
+    ///            RRULE:FREQ=YEARLY\n\
+    ///            END:VEVENT\n\
+    ///            END:VCALENDAR\n";
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// let options = IcsOptions { include_timed_events: true };
+    /// assert_eq!(calendar.add_holidays_from_ics_with(ics, options).unwrap(), 2);
+    /// assert!(calendar.is_holiday(&NaiveDate::from_ymd_opt(2024, 3, 15).unwrap()));
+    /// assert!(calendar.is_holiday(&NaiveDate::from_ymd_opt(2031, 1, 1).unwrap()));
+    /// assert!(!calendar.is_holiday(&NaiveDate::from_ymd_opt(2019, 1, 1).unwrap()));
+    /// ```
+    pub fn add_holidays_from_ics_with(
+        &mut self,
+        ics: &str,
+        options: IcsOptions,
+    ) -> Result<usize, WorkCalendarError> {
+        let events = parse(ics, options)?;
+        let mut added = 0;
+        for event in events {
+            match event {
+                IcsHoliday::Dates { first, last } => {
+                    let dates = std::iter::successors(Some(first), |date| date.succ_opt())
+                        .take_while(|date| *date <= last);
+                    for date in dates {
+                        if !self.holidays.contains(&date) {
+                            self.add_holiday(date);
+                            added += 1;
+                        }
+                    }
+                }
+                IcsHoliday::Yearly { rules, from, to } => {
+                    for rule in rules {
+                        let before = self.holiday_layers().len();
+                        self.add_holiday_effective(rule, from, to)?;
+                        added += self.holiday_layers().len() - before;
+                    }
+                }
+            }
+        }
+        Ok(added)
+    }
+}
+
+/// The holidays described by one event.
+#[derive(Debug, PartialEq)]
+enum IcsHoliday {
+    /// Every date from `first` to `last`, inclusive.
+    Dates { first: NaiveDate, last: NaiveDate },
+    /// Recurring holidays in effect from `from` to `to`, inclusive.
+    Yearly {
+        rules: Vec<HolidayRule>,
+        from: NaiveDate,
+        to: Option<NaiveDate>,
+    },
+}
+
+/// A `DTSTART` or `DTEND` value.
+#[derive(Debug, Clone, Copy)]
+enum DateValue {
+    Date(NaiveDate),
+    DateTime(NaiveDateTime),
+}
+
+impl DateValue {
+    fn date(&self) -> NaiveDate {
+        match self {
+            DateValue::Date(date) => *date,
+            DateValue::DateTime(date_time) => date_time.date(),
+        }
+    }
+
+    fn date_time(&self) -> NaiveDateTime {
+        match self {
+            DateValue::Date(date) => date.and_time(NaiveTime::MIN),
+            DateValue::DateTime(date_time) => *date_time,
+        }
+    }
+}
+
+/// The properties of a `VEVENT` read so far, and the line it began on.
+#[derive(Debug, Default)]
+struct EventBuilder {
+    line: usize,
+    start: Option<DateValue>,
+    end: Option<DateValue>,
+    duration: Option<Duration>,
+    rrule: Option<(usize, String)>,
+    exceptions: Option<usize>,
+    cancelled: bool,
+}
+
+/// One unfolded content line: its one-based line number, name, parameters, and value.
+struct ContentLine<'a> {
+    line: usize,
+    name: String,
+    params: Vec<&'a str>,
+    value: &'a str,
+}
+
+fn invalid(line: usize, message: impl Into<String>) -> WorkCalendarError {
+    WorkCalendarError::InvalidIcs {
+        line,
+        message: message.into(),
+    }
+}
+
+/// Reads the holidays described by every event of `ics`.
+fn parse(ics: &str, options: IcsOptions) -> Result<Vec<IcsHoliday>, WorkCalendarError> {
+    let mut holidays = Vec::new();
+    let mut components: Vec<String> = Vec::new();
+    let mut event: Option<EventBuilder> = None;
+    let lines = unfold(ics);
+    for content in &lines {
+        let content = split_content_line(content)?;
+        match content.name.as_str() {
+            "BEGIN" => {
+                let component = content.value.trim().to_ascii_uppercase();
+                if component == "VEVENT" && event.is_none() {
+                    event = Some(EventBuilder {
+                        line: content.line,
+                        ..EventBuilder::default()
+                    });
+                }
+                components.push(component);
+            }
+            "END" => {
+                let component = content.value.trim().to_ascii_uppercase();
+                if components.pop().as_ref() != Some(&component) {
+                    return Err(invalid(
+                        content.line,
+                        format!("END:{} without a matching BEGIN", component),
+                    ));
+                }
+                if component == "VEVENT" && !components.iter().any(|c| c == "VEVENT") {
+                    if let Some(holiday) = event.take().map(|e| e.finish(options)).transpose()? {
+                        holidays.extend(holiday);
+                    }
+                }
+            }
+            _ => {
+                // Properties of components nested in an event, like alarms, are not the
+                // event's.
+                if let (Some(event), Some("VEVENT")) =
+                    (event.as_mut(), components.last().map(String::as_str))
+                {
+                    event.read(&content)?;
+                }
+            }
+        }
+    }
+    match components.last() {
+        Some(component) => Err(invalid(
+            ics.lines().count(),
+            format!("BEGIN:{} is never closed", component),
+        )),
+        None => Ok(holidays),
+    }
+}
+
+/// Joins folded lines, yielding each logical line with the number of its first line.
+fn unfold(ics: &str) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    for (index, line) in ics.lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some((_, previous))) => previous.push_str(rest),
+            _ if line.trim().is_empty() => {}
+            _ => lines.push((index + 1, line.to_string())),
+        }
+    }
+    lines
+}
+
+/// Splits a content line at the first colon outside a quoted parameter value.
+fn split_content_line(
+    (line, content): &(usize, String),
+) -> Result<ContentLine<'_>, WorkCalendarError> {
+    let mut quoted = false;
+    let colon = content.char_indices().find_map(|(i, c)| match c {
+        '"' => {
+            quoted = !quoted;
+            None
+        }
+        ':' if !quoted => Some(i),
+        _ => None,
+    });
+    let colon =
+        colon.ok_or_else(|| invalid(*line, format!("expected NAME:VALUE, got '{}'", content)))?;
+    let mut head = content[..colon].split(';');
+    let name = head.next().unwrap_or_default();
+    // Drop any group prefix, as in `item1.DTSTART`.
+    let name = name.rsplit('.').next().unwrap_or(name);
+    Ok(ContentLine {
+        line: *line,
+        name: name.trim().to_ascii_uppercase(),
+        params: head.collect(),
+        value: content[colon + 1..].trim(),
+    })
+}
+
+impl EventBuilder {
+    /// Records the event property `content`, if it is one that matters.
+    fn read(&mut self, content: &ContentLine<'_>) -> Result<(), WorkCalendarError> {
+        match content.name.as_str() {
+            "DTSTART" => self.start = Some(parse_date_value(content)?),
+            "DTEND" => self.end = Some(parse_date_value(content)?),
+            "DURATION" => {
+                let duration = parse_duration(content.value).ok_or_else(|| {
+                    invalid(
+                        content.line,
+                        format!("invalid DURATION '{}'", content.value),
+                    )
+                })?;
+                self.duration = Some(duration);
+            }
+            "RRULE" => self.rrule = Some((content.line, content.value.to_string())),
+            "EXDATE" | "RDATE" => self.exceptions = Some(content.line),
+            "STATUS" => self.cancelled = content.value.eq_ignore_ascii_case("CANCELLED"),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Turns the event into the holidays it describes, or `None` if it is skipped.
+    fn finish(self, options: IcsOptions) -> Result<Option<IcsHoliday>, WorkCalendarError> {
+        let start = self
+            .start
+            .ok_or_else(|| invalid(self.line, "event has no DTSTART"))?;
+        if self.cancelled
+            || (matches!(start, DateValue::DateTime(_)) && !options.include_timed_events)
+        {
+            return Ok(None);
+        }
+
+        let (first, last) = match start {
+            DateValue::Date(first) => {
+                let end = match (self.end, self.duration) {
+                    (Some(end), _) => Some(end.date()),
+                    (None, Some(duration)) => first.checked_add_signed(duration),
+                    (None, None) => first.succ_opt(),
+                };
+                match end.and_then(|end| end.pred_opt()) {
+                    Some(last) if last >= first => (first, last),
+                    _ => return Err(invalid(self.line, "event ends before it starts")),
+                }
+            }
+            DateValue::DateTime(start) => {
+                let end = match (self.end, self.duration) {
+                    (Some(end), _) => Some(end.date_time()),
+                    (None, Some(duration)) => start.checked_add_signed(duration),
+                    (None, None) => Some(start),
+                };
+                let end = match end {
+                    Some(end) if end >= start => end,
+                    _ => return Err(invalid(self.line, "event ends before it starts")),
+                };
+                // An event ending at midnight does not touch the date it ends on.
+                let last = if end > start && end.time() == NaiveTime::MIN {
+                    end.date().pred_opt().unwrap_or(NaiveDate::MIN)
+                } else {
+                    end.date()
+                };
+                (start.date(), last)
+            }
+        };
+
+        let (line, rrule) = match self.rrule {
+            Some(rrule) => rrule,
+            None => return Ok(Some(IcsHoliday::Dates { first, last })),
+        };
+        if let Some(line) = self.exceptions {
+            return Err(invalid(
+                line,
+                "EXDATE and RDATE are not supported on recurring events",
+            ));
+        }
+        yearly(line, &rrule, first, last).map(Some)
+    }
+}
+
+/// Maps a yearly recurrence of the event spanning `first` to `last` onto recurring
+/// holidays.
+fn yearly(
+    line: usize,
+    rrule: &str,
+    first: NaiveDate,
+    last: NaiveDate,
+) -> Result<IcsHoliday, WorkCalendarError> {
+    let unsupported = |message: String| invalid(line, message);
+    let (mut until, mut count, mut by_month, mut by_month_day, mut by_day) =
+        (None, None, None, None, None);
+    let mut yearly = false;
+    for part in rrule.split(';').filter(|part| !part.is_empty()) {
+        let (key, value) = part.split_once('=').unwrap_or((part, ""));
+        let bad_value = || unsupported(format!("unsupported RRULE part '{}'", part));
+        match key.to_ascii_uppercase().as_str() {
+            "FREQ" => yearly = value.eq_ignore_ascii_case("YEARLY"),
+            "INTERVAL" if value == "1" => {}
+            "WKST" => {}
+            "UNTIL" => {
+                let date = value.get(..8).unwrap_or(value);
+                until = Some(NaiveDate::parse_from_str(date, "%Y%m%d").map_err(|_| bad_value())?);
+            }
+            "COUNT" => match value.parse::<usize>() {
+                Ok(0) => return Err(unsupported(format!("invalid RRULE part '{}'", part))),
+                Ok(n) => count = Some(n),
+                Err(_) => return Err(bad_value()),
+            },
+            "BYMONTH" => by_month = Some(value.parse::<u32>().map_err(|_| bad_value())?),
+            "BYMONTHDAY" => by_month_day = Some(value.parse::<u32>().map_err(|_| bad_value())?),
+            "BYDAY" => by_day = Some(parse_by_day(value).ok_or_else(bad_value)?),
+            _ => return Err(bad_value()),
+        }
+    }
+    if !yearly {
+        return Err(unsupported(format!(
+            "only yearly recurrences are supported, got '{}'",
+            rrule
+        )));
+    }
+
+    let rules: Vec<HolidayRule> = match by_day {
+        Some((weekday, nth)) if first == last && by_month_day.is_none() => {
+            vec![HolidayRule::NthWeekday {
+                month: by_month.unwrap_or_else(|| first.month()),
+                weekday,
+                nth,
+            }]
+        }
+        Some(_) => {
+            return Err(unsupported(
+                "BYDAY is only supported on single-day events without BYMONTHDAY".to_string(),
+            ))
+        }
+        None if by_month.map_or(true, |month| month == first.month())
+            && by_month_day.map_or(true, |day| day == first.day()) =>
+        {
+            std::iter::successors(Some(first), |date| date.succ_opt())
+                .take_while(|date| *date <= last)
+                .map(|date| HolidayRule::FixedDate {
+                    month: date.month(),
+                    day: date.day(),
+                })
+                .collect()
+        }
+        None => {
+            return Err(unsupported(
+                "BYMONTH and BYMONTHDAY must match DTSTART".to_string(),
+            ))
+        }
+    };
+
+    let to = match (until, count) {
+        (Some(until), _) => Some(until + (last - first)),
+        // A rule with fewer than `count` occurrences up to the last representable year
+        // runs to the end of the date range.
+        (None, Some(count)) => (first.year()..=NaiveDate::MAX.year())
+            .filter_map(|year| rules[0].date_in(year))
+            .filter(|date| *date >= first)
+            .nth(count - 1)
+            .map(|date| date + (last - first)),
+        (None, None) => None,
+    };
+    if to.map_or(false, |to| to < first) {
+        return Err(unsupported("RRULE ends before DTSTART".to_string()));
+    }
+    Ok(IcsHoliday::Yearly {
+        rules,
+        from: first,
+        to,
+    })
+}
+
+/// Parses a single `BYDAY` entry with an occurrence, such as `4TH` or `-1MO`.
+fn parse_by_day(value: &str) -> Option<(chrono::Weekday, i8)> {
+    let split = value.len().checked_sub(2)?;
+    let (nth, day) = (value.get(..split)?, value.get(split..)?);
+    let nth: i8 = nth.trim_start_matches('+').parse().ok()?;
+    let weekday = match day.to_ascii_uppercase().as_str() {
+        "MO" => chrono::Weekday::Mon,
+        "TU" => chrono::Weekday::Tue,
+        "WE" => chrono::Weekday::Wed,
+        "TH" => chrono::Weekday::Thu,
+        "FR" => chrono::Weekday::Fri,
+        "SA" => chrono::Weekday::Sat,
+        "SU" => chrono::Weekday::Sun,
+        _ => return None,
+    };
+    Some((weekday, nth)).filter(|(_, nth)| (-5..=5).contains(nth) && *nth != 0)
+}
+
+/// Parses a `DTSTART` or `DTEND` value, as a date if it has `VALUE=DATE` or no time.
+fn parse_date_value(content: &ContentLine<'_>) -> Result<DateValue, WorkCalendarError> {
+    let value = content.value.trim_end_matches(['Z', 'z']);
+    let is_date = content
+        .params
+        .iter()
+        .any(|param| param.eq_ignore_ascii_case("VALUE=DATE"))
+        || value.len() == 8;
+    let parsed = if is_date {
+        NaiveDate::parse_from_str(value, "%Y%m%d")
+            .ok()
+            .map(DateValue::Date)
+    } else {
+        NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+            .ok()
+            .map(DateValue::DateTime)
+    };
+    parsed.ok_or_else(|| {
+        invalid(
+            content.line,
+            format!("invalid {} '{}'", content.name, content.value),
+        )
+    })
+}
+
+/// Parses a non-negative RFC 5545 duration such as `P3D`, `P1W`, or `PT7H30M`.
+fn parse_duration(value: &str) -> Option<Duration> {
+    let rest = value.strip_prefix('+').unwrap_or(value);
+    let rest = rest.strip_prefix('P')?;
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    let mut in_time = false;
+    let mut any = false;
+    for c in rest.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' if !in_time && number.is_empty() => in_time = true,
+            _ => {
+                let n: i64 = number.parse().ok()?;
+                number.clear();
+                any = true;
+                let part = match (c, in_time) {
+                    ('W', false) => Duration::try_weeks(n)?,
+                    ('D', false) => Duration::try_days(n)?,
+                    ('H', true) => Duration::try_hours(n)?,
+                    ('M', true) => Duration::try_minutes(n)?,
+                    ('S', true) => Duration::try_seconds(n)?,
+                    _ => return None,
+                };
+                total = total.checked_add(&part)?;
+            }
+        }
+    }
+    Some(total).filter(|_| any && number.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::date;
+    use chrono::Weekday;
+
+    /// A company holiday calendar as an HR system might export it.
+    const FIXTURE: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Example Corp//HR Holidays//EN\r
+BEGIN:VTIMEZONE\r
+TZID:America/New_York\r
+BEGIN:STANDARD\r
+DTSTART:19701101T020000\r
+TZOFFSETFROM:-0400\r
+TZOFFSETTO:-0500\r
+END:STANDARD\r
+END:VTIMEZONE\r
+BEGIN:VEVENT\r
+UID:newyear-2024@example.com\r
+SUMMARY:New Year's Day\r
+DTSTART;VALUE=DATE:20240101\r
+DTEND;VALUE=DATE:20240102\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:closure-2024@example.com\r
+SUMMARY:Year-end office closure: the office is closed from Christmas Eve\r
+  through New Year's Day\r
+DTSTART;VALUE=DATE:20241224\r
+DTEND;VALUE=DATE:20250102\r
+BEGIN:VALARM\r
+ACTION:DISPLAY\r
+TRIGGER:-P1D\r
+DURATION:P30D\r
+END:VALARM\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:independence@example.com\r
+SUMMARY:Independence Day\r
+DTSTART;VALUE=DATE:20230704\r
+RRULE:FREQ=YEARLY\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:thanksgiving@example.com\r
+SUMMARY:Thanksgiving\r
+DTSTART;VALUE=DATE:20231123\r
+DURATION:P1D\r
+RRULE:FREQ=YEARLY;BYMONTH=11;BYDAY=4TH;COUNT=3\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:offsite@example.com\r
+SUMMARY:Offsite\r
+DTSTART;TZID=America/New_York:20240612T090000\r
+DTEND;TZID=America/New_York:20240613T000000\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:cancelled@example.com\r
+SUMMARY:Founders' Day\r
+STATUS:CANCELLED\r
+DTSTART;VALUE=DATE:20240920\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+    #[test]
+    fn test_import_fixture() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2024, 12, 25));
+        // New Year's Day, nine closure dates of which one was already a holiday, and
+        // two recurring holidays.
+        assert_eq!(calendar.add_holidays_from_ics(FIXTURE), Ok(11));
+
+        assert!(calendar.is_holiday(&date(2024, 1, 1)));
+        assert!(!calendar.is_holiday(&date(2024, 1, 2)));
+        assert!(!calendar.is_holiday(&date(2024, 12, 23)));
+        for day in 24..=31 {
+            assert!(calendar.is_holiday(&date(2024, 12, day)));
+        }
+        assert!(calendar.is_holiday(&date(2025, 1, 1)));
+        assert!(!calendar.is_holiday(&date(2025, 1, 2)));
+
+        assert!(!calendar.is_holiday(&date(2022, 7, 4)));
+        assert!(calendar.is_holiday(&date(2023, 7, 4)));
+        assert!(calendar.is_holiday(&date(2040, 7, 4)));
+
+        assert!(calendar.is_holiday(&date(2023, 11, 23)));
+        assert!(calendar.is_holiday(&date(2024, 11, 28)));
+        assert!(calendar.is_holiday(&date(2025, 11, 27)));
+        assert!(!calendar.is_holiday(&date(2026, 11, 26)));
+
+        assert!(!calendar.is_holiday(&date(2024, 6, 12)));
+        assert!(!calendar.is_holiday(&date(2024, 9, 20)));
+
+        // Importing again adds nothing.
+        assert_eq!(calendar.add_holidays_from_ics(FIXTURE), Ok(0));
+    }
+
+    #[test]
+    fn test_import_timed_events() {
+        let mut calendar = WorkCalendar::new();
+        let options = IcsOptions {
+            include_timed_events: true,
+        };
+        // The dates and rules of the all-day events, and the offsite's one date.
+        assert_eq!(
+            calendar.add_holidays_from_ics_with(FIXTURE, options),
+            Ok(13)
+        );
+        // The offsite ends at midnight, so it covers only its start date.
+        assert!(calendar.is_holiday(&date(2024, 6, 12)));
+        assert!(!calendar.is_holiday(&date(2024, 6, 13)));
+
+        let ics = "BEGIN:VEVENT\nDTSTART:20240610T220000Z\nDURATION:PT4H\nEND:VEVENT\n";
+        let mut calendar = WorkCalendar::new();
+        assert_eq!(calendar.add_holidays_from_ics_with(ics, options), Ok(2));
+        assert!(calendar.is_holiday(&date(2024, 6, 11)));
+    }
+
+    #[test]
+    fn test_yearly_rules() {
+        let parse_one = |rrule: &str, start: &str, end: &str| {
+            let ics = format!(
+                "BEGIN:VEVENT\nDTSTART;VALUE=DATE:{}\nDTEND;VALUE=DATE:{}\nRRULE:{}\nEND:VEVENT\n",
+                start, end, rrule
+            );
+            parse(&ics, IcsOptions::default()).map(|mut events| events.remove(0))
+        };
+
+        assert_eq!(
+            parse_one("FREQ=YEARLY;UNTIL=20261231T000000Z", "20241224", "20241227"),
+            Ok(IcsHoliday::Yearly {
+                rules: (24..=26)
+                    .map(|day| HolidayRule::FixedDate { month: 12, day })
+                    .collect(),
+                from: date(2024, 12, 24),
+                to: Some(date(2027, 1, 2)),
+            })
+        );
+        assert_eq!(
+            parse_one("FREQ=YEARLY;BYMONTH=5;BYDAY=-1MO", "20240527", "20240528"),
+            Ok(IcsHoliday::Yearly {
+                rules: vec![HolidayRule::NthWeekday {
+                    month: 5,
+                    weekday: Weekday::Mon,
+                    nth: -1,
+                }],
+                from: date(2024, 5, 27),
+                to: None,
+            })
+        );
+        // Leap days recur only in leap years, so the second occurrence is in 2028.
+        assert_eq!(
+            parse_one("FREQ=YEARLY;COUNT=2", "20240229", "20240301"),
+            Ok(IcsHoliday::Yearly {
+                rules: vec![HolidayRule::FixedDate { month: 2, day: 29 }],
+                from: date(2024, 2, 29),
+                to: Some(date(2028, 2, 29)),
+            })
+        );
+
+        // A count past the last representable year runs to the end of the range.
+        assert_eq!(
+            parse_one("FREQ=YEARLY;COUNT=1000000", "20240229", "20240301"),
+            Ok(IcsHoliday::Yearly {
+                rules: vec![HolidayRule::FixedDate { month: 2, day: 29 }],
+                from: date(2024, 2, 29),
+                to: None,
+            })
+        );
+
+        for rrule in [
+            "FREQ=YEARLY;COUNT=0",
+            "FREQ=MONTHLY",
+            "FREQ=YEARLY;INTERVAL=2",
+            "FREQ=YEARLY;BYMONTH=3",
+            "FREQ=YEARLY;BYSETPOS=1",
+            "FREQ=YEARLY;BYDAY=MO",
+        ] {
+            assert!(
+                matches!(
+                    parse_one(rrule, "20240101", "20240102"),
+                    Err(WorkCalendarError::InvalidIcs { line: 4, .. })
+                ),
+                "{}",
+                rrule
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_documents_change_nothing() {
+        let cases = [
+            (
+                "BEGIN:VCALENDAR\nBEGIN:VEVENT\nSUMMARY:Nothing\nEND:VEVENT\nEND:VCALENDAR\n",
+                2,
+                "event has no DTSTART",
+            ),
+            (
+                "BEGIN:VEVENT\nDTSTART;VALUE=DATE:20241340\nEND:VEVENT\n",
+                2,
+                "invalid DTSTART '20241340'",
+            ),
+            (
+                "BEGIN:VEVENT\nDTSTART;VALUE=DATE:20240105\nDTEND;VALUE=DATE:20240105\nEND:VEVENT\n",
+                1,
+                "event ends before it starts",
+            ),
+            (
+                "BEGIN:VCALENDAR\nBEGIN:VEVENT\nDTSTART:20240101\nEND:VCALENDAR\n",
+                4,
+                "END:VCALENDAR without a matching BEGIN",
+            ),
+            (
+                "BEGIN:VCALENDAR\nBEGIN:VEVENT\nDTSTART:20240101\nEND:VEVENT\n",
+                4,
+                "BEGIN:VCALENDAR is never closed",
+            ),
+            ("BEGIN:VEVENT\nnonsense\n", 2, "expected NAME:VALUE, got 'nonsense'"),
+            (
+                "BEGIN:VEVENT\nDTSTART:20240101\nRRULE:FREQ=YEARLY\nEXDATE:20250101\nEND:VEVENT\n",
+                4,
+                "EXDATE and RDATE are not supported on recurring events",
+            ),
+        ];
+        for (ics, line, message) in cases {
+            let mut calendar = WorkCalendar::new();
+            let valid = "BEGIN:VEVENT\nDTSTART:20230101\nEND:VEVENT\n";
+            assert_eq!(
+                calendar.add_holidays_from_ics(&format!("{}{}", valid, ics)),
+                Err(WorkCalendarError::InvalidIcs {
+                    line: line + 3,
+                    message: message.to_string()
+                }),
+                "{}",
+                ics
+            );
+            assert_eq!(calendar, WorkCalendar::new());
+        }
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("P3D"), Some(Duration::days(3)));
+        assert_eq!(parse_duration("P2W"), Some(Duration::weeks(2)));
+        assert_eq!(
+            parse_duration("P1DT2H30M"),
+            Some(Duration::hours(26) + Duration::minutes(30))
+        );
+        assert_eq!(parse_duration("PT15S"), Some(Duration::seconds(15)));
+        for invalid in ["", "P", "PT", "3D", "-P1D", "P1H", "PT1D", "P1D2"] {
+            assert_eq!(parse_duration(invalid), None, "{}", invalid);
+        }
+    }
+}
@@ -0,0 +1,257 @@
+//! Streaming holiday import.
+
+use crate::{WorkCalendar, WorkCalendarError};
+use chrono::NaiveDate;
+use std::{io::BufRead, sync::Arc};
+
+/// The maximum number of malformed lines kept in an [`ImportReport`].
+pub const MALFORMED_SAMPLE_LIMIT: usize = 10;
+
+/// Malformed lines longer than this many bytes are truncated in the report.
+const MALFORMED_SAMPLE_LENGTH: usize = 120;
+
+/// The number of lines between progress callbacks.
+const PROGRESS_INTERVAL: u64 = 65_536;
+
+/// The layout of a holiday file read by [`WorkCalendar::add_holidays_from_reader`].
+///
+/// Dates are always ISO 8601 (`YYYY-MM-DD`). Blank lines and lines starting with `#`
+/// are skipped in every format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// One date per line.
+    Lines,
+    /// Comma-separated values with the date in the given zero-based column.
+    Csv {
+        /// The column holding the date.
+        column: usize,
+        /// Whether the first line is a header to skip.
+        header: bool,
+    },
+}
+
+/// A line that could not be parsed during an import.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MalformedLine {
+    /// The one-based line number.
+    pub line: u64,
+    /// The line's content, truncated if very long.
+    pub content: String,
+}
+
+/// The outcome of a holiday import.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    /// The number of lines read, including skipped ones.
+    pub lines: u64,
+    /// The number of holidays that were not already in the calendar.
+    pub added: u64,
+    /// The number of dates that were already holidays, from earlier lines or before
+    /// the import.
+    pub duplicates: u64,
+    /// The number of lines that could not be parsed.
+    pub malformed: u64,
+    /// The first [`MALFORMED_SAMPLE_LIMIT`] malformed lines.
+    pub malformed_samples: Vec<MalformedLine>,
+}
+
+impl ImportReport {
+    fn record_malformed(&mut self, content: &str) {
+        self.malformed += 1;
+        if self.malformed_samples.len() < MALFORMED_SAMPLE_LIMIT {
+            let mut end = content.len().min(MALFORMED_SAMPLE_LENGTH);
+            while !content.is_char_boundary(end) {
+                end -= 1;
+            }
+            self.malformed_samples.push(MalformedLine {
+                line: self.lines,
+                content: content[..end].to_string(),
+            });
+        }
+    }
+}
+
+impl WorkCalendar {
+    /// Adds holidays read line by line from `reader`.
+    ///
+    /// The input is streamed through a single reused line buffer and each date is
+    /// inserted as soon as it is parsed, so memory use grows with the number of
+    /// unique holidays rather than the size of the input. Malformed lines are
+    /// counted, and the first few are sampled in the report; they do not stop the
+    /// import.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The source to read from.
+    /// * `format` - The layout of each line.
+    ///
+    /// # Errors
+    ///
+    /// [`WorkCalendarError::Io`] if reading fails. Holidays read before the failure
+    /// remain in the calendar.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{ImportFormat, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let input = "2023-12-25\n2023-12-26\n2023-12-25\nnot a date\n";
+    /// let mut calendar = WorkCalendar::new();
+    /// let report = calendar
+    ///     .add_holidays_from_reader(input.as_bytes(), ImportFormat::Lines)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(report.added, 2);
+    /// assert_eq!(report.duplicates, 1);
+    /// assert_eq!(report.malformed, 1);
+    /// assert!(calendar.is_holiday(&NaiveDate::from_ymd_opt(2023, 12, 26).unwrap()));
+    /// ```
+    pub fn add_holidays_from_reader(
+        &mut self,
+        reader: impl BufRead,
+        format: ImportFormat,
+    ) -> Result<ImportReport, WorkCalendarError> {
+        self.add_holidays_from_reader_with_progress(reader, format, |_| {})
+    }
+
+    /// Like [`WorkCalendar::add_holidays_from_reader`], calling `progress` with the
+    /// running totals periodically and once more when the import finishes.
+    pub fn add_holidays_from_reader_with_progress(
+        &mut self,
+        mut reader: impl BufRead,
+        format: ImportFormat,
+        mut progress: impl FnMut(&ImportReport),
+    ) -> Result<ImportReport, WorkCalendarError> {
+        let mut report = ImportReport::default();
+        let mut line = String::with_capacity(64);
+        let holidays = Arc::make_mut(&mut self.holidays);
+        let result = loop {
+            line.clear();
+            match reader.read_line(&mut line) {
+                Ok(0) => break Ok(()),
+                Ok(_) => {}
+                Err(error) => {
+                    break Err(WorkCalendarError::Io {
+                        line: report.lines + 1,
+                        message: error.to_string(),
+                    })
+                }
+            }
+            report.lines += 1;
+            if report.lines % PROGRESS_INTERVAL == 0 {
+                progress(&report);
+            }
+
+            let content = line.trim_end_matches(&['\r', '\n'][..]);
+            if content.trim().is_empty() || content.starts_with('#') {
+                continue;
+            }
+            let field = match format {
+                ImportFormat::Lines => Some(content),
+                ImportFormat::Csv { header: true, .. } if report.lines == 1 => continue,
+                ImportFormat::Csv { column, .. } => content.split(',').nth(column),
+            };
+            let date =
+                field.and_then(|field| NaiveDate::parse_from_str(field.trim(), "%Y-%m-%d").ok());
+            match date {
+                Some(date) if holidays.insert(date) => report.added += 1,
+                Some(_) => report.duplicates += 1,
+                None => report.record_malformed(content),
+            }
+        };
+        if report.added > 0 {
+            self.month_memo.invalidate();
+        }
+        progress(&report);
+        result.map(|()| report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use std::io::{self, BufReader, Read};
+
+    #[test]
+    fn test_large_import_deduplicates() {
+        let base = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
+        let mut input = String::from("location,date\n");
+        for i in 0..200_000i64 {
+            if i % 1_000 == 0 {
+                input.push_str("bogus,line\n");
+            }
+            input.push_str(&format!(
+                "loc{},{}\n",
+                i % 7,
+                base + Duration::days(i % 5_000)
+            ));
+        }
+
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(base);
+        let mut calls = 0;
+        let report = calendar
+            .add_holidays_from_reader_with_progress(
+                input.as_bytes(),
+                ImportFormat::Csv {
+                    column: 1,
+                    header: true,
+                },
+                |_| calls += 1,
+            )
+            .unwrap();
+
+        assert_eq!(report.lines, 200_201);
+        assert_eq!(report.added, 4_999);
+        assert_eq!(report.duplicates, 200_000 - 4_999);
+        assert_eq!(report.malformed, 200);
+        assert_eq!(report.malformed_samples.len(), MALFORMED_SAMPLE_LIMIT);
+        assert_eq!(report.malformed_samples[0].line, 2);
+        assert_eq!(report.malformed_samples[0].content, "bogus,line");
+        assert_eq!(calls, 200_201 / PROGRESS_INTERVAL + 1);
+        assert_eq!(calendar.holidays.len(), 5_000);
+    }
+
+    #[test]
+    fn test_import_skips_comments_and_truncates_samples() {
+        let long = "x".repeat(1_000);
+        let input = format!("# holidays\n\n2024-01-01\r\n{}\n", long);
+        let mut calendar = WorkCalendar::new();
+        let report = calendar
+            .add_holidays_from_reader(input.as_bytes(), ImportFormat::Lines)
+            .unwrap();
+        assert_eq!(report.added, 1);
+        assert_eq!(report.malformed, 1);
+        assert_eq!(report.malformed_samples[0].line, 4);
+        assert_eq!(
+            report.malformed_samples[0].content.len(),
+            MALFORMED_SAMPLE_LENGTH
+        );
+    }
+
+    #[test]
+    fn test_import_read_error_keeps_earlier_holidays() {
+        struct Failing;
+        impl Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::Other, "disk on fire"))
+            }
+        }
+
+        let reader = BufReader::new("2024-01-01\n".as_bytes().chain(Failing));
+        let mut calendar = WorkCalendar::new();
+        let error = calendar
+            .add_holidays_from_reader(reader, ImportFormat::Lines)
+            .unwrap_err();
+        assert_eq!(
+            error,
+            WorkCalendarError::Io {
+                line: 2,
+                message: "disk on fire".to_string()
+            }
+        );
+        assert!(calendar.is_holiday(&NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()));
+    }
+}
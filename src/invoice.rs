@@ -0,0 +1,245 @@
+//! Invoice due dates under common payment terms.
+
+use crate::{AdjustmentConvention, CalendarSystem, Gregorian, WorkCalendar, WorkCalendarError};
+use chrono::{Datelike, Days, NaiveDate};
+
+/// The payment terms of an invoice, for [`WorkCalendar::due_date`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PaymentTerm {
+    /// Due this many calendar days after the invoice date ("net 30").
+    NetCalendarDays(u32),
+    /// Due this many working dates after the invoice date.
+    NetWorkDays(u32),
+    /// Due this many calendar days after the end of the invoice date's month
+    /// ("EOM + 30").
+    EndOfMonthPlus(u32),
+}
+
+impl WorkCalendar {
+    /// Computes when an invoice is due, moving a due date that falls on a non-working
+    /// day to the next working date.
+    ///
+    /// This is [`WorkCalendar::due_date_with`] using
+    /// [`AdjustmentConvention::Following`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{PaymentTerm, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let invoiced = NaiveDate::from_ymd_opt(2023, 9, 1).unwrap();
+    ///
+    /// // 30 calendar days later is Sunday, October 1.
+    /// assert_eq!(
+    ///     calendar.due_date(invoiced, PaymentTerm::NetCalendarDays(30)).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2023, 10, 2).unwrap()
+    /// );
+    /// assert_eq!(
+    ///     calendar.due_date(invoiced, PaymentTerm::NetWorkDays(30)).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2023, 10, 13).unwrap()
+    /// );
+    /// ```
+    pub fn due_date(
+        &self,
+        invoice_date: NaiveDate,
+        term: PaymentTerm,
+    ) -> Result<NaiveDate, WorkCalendarError> {
+        self.due_date_with(invoice_date, term, AdjustmentConvention::Following)
+    }
+
+    /// Computes when an invoice is due, moving a due date that falls on a non-working
+    /// day according to `adjustment`.
+    ///
+    /// The term gives a nominal due date, which is then adjusted. A
+    /// [`PaymentTerm::NetWorkDays`] due date is always a working date, so adjustment
+    /// leaves it alone. For [`PaymentTerm::EndOfMonthPlus`], the end of the month is
+    /// its last calendar day, so invoices dated the 1st and the 31st of a month are
+    /// due on the same day.
+    ///
+    /// # Arguments
+    ///
+    /// * `invoice_date` - The invoice date.
+    /// * `term` - The payment terms.
+    /// * `adjustment` - How a due date on a non-working day is moved.
+    ///
+    /// # Errors
+    ///
+    /// * [`WorkCalendarError::DateOutOfRange`] if the due date cannot be represented.
+    /// * Any error from [`WorkCalendar::adjust`].
+    /// * For [`PaymentTerm::NetWorkDays`], the errors of
+    ///   [`WorkCalendar::next_work_day`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{AdjustmentConvention, PaymentTerm, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let invoiced = NaiveDate::from_ymd_opt(2023, 8, 31).unwrap();
+    ///
+    /// // The end of August plus 30 days is Saturday, September 30.
+    /// let term = PaymentTerm::EndOfMonthPlus(30);
+    /// assert_eq!(
+    ///     calendar.due_date_with(invoiced, term, AdjustmentConvention::Following).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2023, 10, 2).unwrap()
+    /// );
+    /// assert_eq!(
+    ///     calendar
+    ///         .due_date_with(invoiced, term, AdjustmentConvention::ModifiedFollowing)
+    ///         .unwrap(),
+    ///     NaiveDate::from_ymd_opt(2023, 9, 29).unwrap()
+    /// );
+    /// ```
+    pub fn due_date_with(
+        &self,
+        invoice_date: NaiveDate,
+        term: PaymentTerm,
+        adjustment: AdjustmentConvention,
+    ) -> Result<NaiveDate, WorkCalendarError> {
+        let out_of_range = || WorkCalendarError::DateOutOfRange {
+            calendar: self.name().map(String::from),
+        };
+        let nominal = match term {
+            PaymentTerm::NetCalendarDays(days) => invoice_date
+                .checked_add_days(Days::new(u64::from(days)))
+                .ok_or_else(out_of_range)?,
+            PaymentTerm::NetWorkDays(days) => {
+                self.try_add_work_days(invoice_date, i64::from(days))?
+            }
+            PaymentTerm::EndOfMonthPlus(days) => {
+                let month_end = Gregorian
+                    .days_in_month(invoice_date.year(), invoice_date.month())
+                    .and_then(|last| invoice_date.with_day(last))
+                    .ok_or_else(out_of_range)?;
+                month_end
+                    .checked_add_days(Days::new(u64::from(days)))
+                    .ok_or_else(out_of_range)?
+            }
+        };
+        self.adjust(nominal, adjustment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::date;
+    use AdjustmentConvention::*;
+
+    fn calendar() -> WorkCalendar {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("AP");
+        calendar.add_holiday(date(2023, 12, 25));
+        calendar.add_holiday(date(2024, 1, 1));
+        calendar
+    }
+
+    #[test]
+    fn test_net_calendar_days() {
+        let calendar = calendar();
+        let cases = [
+            // Lands on a working date.
+            (date(2023, 9, 5), 30, Following, date(2023, 10, 5)),
+            // Lands on a Sunday.
+            (date(2023, 9, 1), 30, Following, date(2023, 10, 2)),
+            (date(2023, 9, 1), 30, Preceding, date(2023, 9, 29)),
+            (date(2023, 9, 1), 30, Unadjusted, date(2023, 10, 1)),
+            // Lands on Christmas, then rolls past it.
+            (date(2023, 11, 25), 30, Following, date(2023, 12, 26)),
+            // Lands on New Year's Day, and modified following stays in January.
+            (date(2023, 12, 2), 30, Following, date(2024, 1, 2)),
+            (date(2023, 12, 2), 30, ModifiedFollowing, date(2024, 1, 2)),
+            (date(2023, 12, 2), 30, Preceding, date(2023, 12, 29)),
+            // Net 0 on a weekend is due the next working date.
+            (date(2023, 9, 2), 0, Following, date(2023, 9, 4)),
+        ];
+        for (invoiced, days, adjustment, expected) in cases {
+            assert_eq!(
+                calendar.due_date_with(invoiced, PaymentTerm::NetCalendarDays(days), adjustment),
+                Ok(expected),
+                "{} net {} {:?}",
+                invoiced,
+                days,
+                adjustment
+            );
+        }
+        assert_eq!(
+            calendar.due_date(NaiveDate::MAX, PaymentTerm::NetCalendarDays(1)),
+            Err(WorkCalendarError::DateOutOfRange {
+                calendar: Some("AP".to_string())
+            })
+        );
+    }
+
+    #[test]
+    fn test_net_work_days() {
+        let calendar = calendar();
+        let cases = [
+            (date(2023, 9, 1), 30, date(2023, 10, 13)),
+            (date(2023, 12, 22), 1, date(2023, 12, 26)),
+            (date(2023, 12, 22), 5, date(2024, 1, 2)),
+            // Counting starts after the invoice date, even from a weekend.
+            (date(2023, 9, 2), 1, date(2023, 9, 4)),
+            (date(2023, 9, 2), 0, date(2023, 9, 2)),
+        ];
+        for (invoiced, days, expected) in cases {
+            assert_eq!(
+                calendar.due_date_with(invoiced, PaymentTerm::NetWorkDays(days), Unadjusted),
+                Ok(expected),
+                "{} net {} work days",
+                invoiced,
+                days
+            );
+        }
+        // Net 0 on a weekend is adjusted like any other due date.
+        assert_eq!(
+            calendar.due_date(date(2023, 9, 2), PaymentTerm::NetWorkDays(0)),
+            Ok(date(2023, 9, 4))
+        );
+        assert_eq!(
+            calendar.due_date(NaiveDate::MAX, PaymentTerm::NetWorkDays(1)),
+            Err(WorkCalendarError::DateOutOfRange {
+                calendar: Some("AP".to_string())
+            })
+        );
+    }
+
+    #[test]
+    fn test_end_of_month_plus() {
+        let calendar = calendar();
+        let cases = [
+            // Invoiced on the 31st: the month ends that day.
+            (date(2023, 8, 31), 30, Following, date(2023, 10, 2)),
+            (date(2023, 8, 31), 30, ModifiedFollowing, date(2023, 9, 29)),
+            (date(2023, 8, 1), 30, Following, date(2023, 10, 2)),
+            (date(2023, 1, 31), 0, Following, date(2023, 1, 31)),
+            // January's end plus 30 days skips past a short February.
+            (date(2023, 1, 31), 30, Following, date(2023, 3, 2)),
+            (date(2024, 1, 31), 30, Following, date(2024, 3, 1)),
+            // Invoiced on the 31st of a month whose successor is shorter.
+            (date(2023, 10, 31), 30, Following, date(2023, 11, 30)),
+            (date(2023, 10, 31), 55, Following, date(2023, 12, 26)),
+            (date(2023, 12, 31), 1, Following, date(2024, 1, 2)),
+            (date(2023, 12, 31), 1, Unadjusted, date(2024, 1, 1)),
+        ];
+        for (invoiced, days, adjustment, expected) in cases {
+            assert_eq!(
+                calendar.due_date_with(invoiced, PaymentTerm::EndOfMonthPlus(days), adjustment),
+                Ok(expected),
+                "{} EOM + {} {:?}",
+                invoiced,
+                days,
+                adjustment
+            );
+        }
+        assert_eq!(
+            calendar.due_date(NaiveDate::MAX, PaymentTerm::EndOfMonthPlus(1)),
+            Err(WorkCalendarError::DateOutOfRange {
+                calendar: Some("AP".to_string())
+            })
+        );
+    }
+}
@@ -0,0 +1,355 @@
+//! Queries keyed by ISO 8601 week, and grouping dates into work weeks.
+
+use crate::WorkCalendar;
+use chrono::{Datelike, IsoWeek, NaiveDate, Weekday};
+use std::collections::{BTreeMap, HashMap};
+
+impl WorkCalendar {
+    /// Returns the number of work days in ISO week `week` of ISO year `iso_year`, or 0
+    /// if that week does not exist.
+    ///
+    /// ISO weeks run Monday to Sunday, and week 1 is the week containing the year's
+    /// first Thursday, so an ISO year can start in late December and end in early
+    /// January, and some ISO years have 53 weeks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// calendar.add_holiday(NaiveDate::from_ymd_opt(2021, 1, 1).unwrap());
+    /// // 2020-W53 runs from Monday 2020-12-28 to Sunday 2021-01-03.
+    /// assert_eq!(calendar.work_days_in_iso_week(2020, 53), 4);
+    /// assert_eq!(calendar.work_days_in_iso_week(2021, 53), 0);
+    /// ```
+    pub fn work_days_in_iso_week(&self, iso_year: i32, week: u32) -> i64 {
+        match iso_week_bounds(iso_year, week) {
+            Some((monday, sunday)) => self.work_days_between(monday, sunday),
+            None => 0,
+        }
+    }
+
+    /// Returns the first working date of ISO week `week` of ISO year `iso_year`, or
+    /// `None` if the week does not exist or has no working dates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// assert_eq!(
+    ///     calendar.first_work_day_of_iso_week(2025, 1),
+    ///     NaiveDate::from_ymd_opt(2024, 12, 30)
+    /// );
+    /// ```
+    pub fn first_work_day_of_iso_week(&self, iso_year: i32, week: u32) -> Option<NaiveDate> {
+        let (monday, sunday) = iso_week_bounds(iso_year, week)?;
+        self.nth_working_date_within(monday, sunday, 1)
+    }
+
+    /// Returns the last working date of ISO week `week` of ISO year `iso_year`, or
+    /// `None` if the week does not exist or has no working dates.
+    pub fn last_work_day_of_iso_week(&self, iso_year: i32, week: u32) -> Option<NaiveDate> {
+        let (monday, sunday) = iso_week_bounds(iso_year, week)?;
+        self.nth_working_date_within(monday, sunday, -1)
+    }
+
+    /// Returns the ISO week of the `n`th working date of calendar year `year`.
+    ///
+    /// `n` counts as in [`WorkCalendar::nth_work_day_of_month`]: 1 is the first working
+    /// date of the year and -1 the last. The ISO week may belong to a different ISO
+    /// year than `year`, as when January 1 falls in the previous year's last week.
+    /// Returns `None` if `n` is zero or the year has fewer than `|n|` working dates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// // Friday 2021-01-01 is in 2020-W53.
+    /// let week = calendar.iso_week_of_nth_work_day(2021, 1).unwrap();
+    /// assert_eq!((week.year(), week.week()), (2020, 53));
+    /// ```
+    pub fn iso_week_of_nth_work_day(&self, year: i32, n: i64) -> Option<IsoWeek> {
+        let first = NaiveDate::from_ymd_opt(year, 1, 1)?;
+        let last = NaiveDate::from_ymd_opt(year, 12, 31)?;
+        self.nth_working_date_within(first, last, n)
+            .map(|date| date.iso_week())
+    }
+
+    /// Returns the first and last working dates of the ISO week containing `date`.
+    ///
+    /// The work week is the span from the week's first working date through its last,
+    /// so `date` itself need not be a working date: a Saturday belongs to the work week
+    /// of the Monday before it.
+    ///
+    /// # Returns
+    ///
+    /// The first and last working dates, equal if the week has one, or `None` if the
+    /// week has no working dates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 12, 25).unwrap());
+    /// let saturday = NaiveDate::from_ymd_opt(2023, 12, 30).unwrap();
+    /// assert_eq!(
+    ///     calendar.work_week_of(saturday),
+    ///     Some((
+    ///         NaiveDate::from_ymd_opt(2023, 12, 26).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2023, 12, 29).unwrap()
+    ///     ))
+    /// );
+    /// ```
+    pub fn work_week_of(&self, date: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+        let week = date.iso_week();
+        let (monday, sunday) = iso_week_bounds(week.year(), week.week())?;
+        Some((
+            self.nth_working_date_within(monday, sunday, 1)?,
+            self.nth_working_date_within(monday, sunday, -1)?,
+        ))
+    }
+
+    /// Groups dates by the work week they fall in, keyed by the week's first working
+    /// date.
+    ///
+    /// Each date goes into the bucket of [`WorkCalendar::work_week_of`], so weekends and
+    /// holidays join their enclosing week. Dates in weeks without working dates, such
+    /// as a week-long shutdown, are left out. Within a bucket, dates keep their input
+    /// order, duplicates included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let date = |d| NaiveDate::from_ymd_opt(2023, 10, d).unwrap();
+    /// let buckets = calendar.bucket_by_work_week([date(4), date(9), date(1), date(2)]);
+    /// // Sunday, October 1 ends the week that starts on Monday, September 25.
+    /// let keys: Vec<_> = buckets.keys().map(|d| d.to_string()).collect();
+    /// assert_eq!(keys, ["2023-09-25", "2023-10-02", "2023-10-09"]);
+    /// assert_eq!(buckets[&date(2)], [date(4), date(2)]);
+    /// ```
+    pub fn bucket_by_work_week(
+        &self,
+        dates: impl IntoIterator<Item = NaiveDate>,
+    ) -> BTreeMap<NaiveDate, Vec<NaiveDate>> {
+        let mut week_starts: HashMap<IsoWeek, Option<NaiveDate>> = HashMap::new();
+        let mut buckets: BTreeMap<NaiveDate, Vec<NaiveDate>> = BTreeMap::new();
+        for date in dates {
+            let start = *week_starts
+                .entry(date.iso_week())
+                .or_insert_with(|| self.work_week_of(date).map(|(first, _)| first));
+            if let Some(start) = start {
+                buckets.entry(start).or_default().push(date);
+            }
+        }
+        buckets
+    }
+}
+
+/// Returns the Monday and Sunday of an ISO week.
+fn iso_week_bounds(iso_year: i32, week: u32) -> Option<(NaiveDate, NaiveDate)> {
+    Some((
+        NaiveDate::from_isoywd_opt(iso_year, week, Weekday::Mon)?,
+        NaiveDate::from_isoywd_opt(iso_year, week, Weekday::Sun)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{date, random_calendar, TestRng};
+
+    #[test]
+    fn test_year_boundary_weeks() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2020, 12, 25));
+        calendar.add_holiday(date(2021, 1, 1));
+        calendar.add_holiday(date(2024, 12, 31));
+
+        // 2020 has 53 ISO weeks, and January 1, 2021 is in its last one.
+        assert_eq!(calendar.work_days_in_iso_week(2020, 52), 4);
+        assert_eq!(calendar.work_days_in_iso_week(2020, 53), 4);
+        assert_eq!(calendar.work_days_in_iso_week(2020, 54), 0);
+        assert_eq!(
+            calendar.first_work_day_of_iso_week(2020, 53),
+            Some(date(2020, 12, 28))
+        );
+        assert_eq!(
+            calendar.last_work_day_of_iso_week(2020, 53),
+            Some(date(2020, 12, 31))
+        );
+        assert_eq!(
+            calendar.first_work_day_of_iso_week(2021, 1),
+            Some(date(2021, 1, 4))
+        );
+
+        // 2021 has only 52, so asking for week 53 finds nothing.
+        assert_eq!(calendar.work_days_in_iso_week(2021, 53), 0);
+        assert_eq!(calendar.first_work_day_of_iso_week(2021, 53), None);
+        assert_eq!(calendar.first_work_day_of_iso_week(2021, 0), None);
+
+        // 2025-W01 starts in December 2024.
+        assert_eq!(calendar.work_days_in_iso_week(2025, 1), 4);
+        assert_eq!(
+            calendar.first_work_day_of_iso_week(2025, 1),
+            Some(date(2024, 12, 30))
+        );
+        assert_eq!(
+            calendar.last_work_day_of_iso_week(2025, 1),
+            Some(date(2025, 1, 3))
+        );
+
+        // 2026 is another 53-week year.
+        assert_eq!(calendar.work_days_in_iso_week(2026, 53), 5);
+        assert_eq!(
+            calendar.last_work_day_of_iso_week(2026, 53),
+            Some(date(2027, 1, 1))
+        );
+    }
+
+    #[test]
+    fn test_iso_week_of_nth_work_day() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2021, 1, 1));
+        let week = |year, n| {
+            calendar
+                .iso_week_of_nth_work_day(year, n)
+                .map(|week| (week.year(), week.week()))
+        };
+        // Without the holiday, Friday 2021-01-01 would be in 2020-W53.
+        assert_eq!(week(2021, 1), Some((2021, 1)));
+        assert_eq!(week(2022, 1), Some((2022, 1)));
+        assert_eq!(week(2023, 1), Some((2023, 1)));
+        assert_eq!(week(2027, 1), Some((2026, 53)));
+        // Tuesday 2024-12-31 is in 2025-W01.
+        assert_eq!(week(2024, -1), Some((2025, 1)));
+        assert_eq!(week(2024, 262), Some((2025, 1)));
+        assert_eq!(week(2024, 263), None);
+        assert_eq!(week(2024, 0), None);
+    }
+
+    #[test]
+    fn test_iso_weeks_partition_years() {
+        let mut rng = TestRng(0x5851_f42d_4c95_7f2d);
+        for _ in 0..10 {
+            let calendar = random_calendar(&mut rng);
+            for iso_year in 2019..=2028 {
+                let weeks = if date(iso_year, 12, 28).iso_week().week() == 53 {
+                    53
+                } else {
+                    52
+                };
+                let total: i64 = (1..=weeks)
+                    .map(|week| calendar.work_days_in_iso_week(iso_year, week))
+                    .sum();
+                let (first, _) = iso_week_bounds(iso_year, 1).unwrap();
+                let (_, last) = iso_week_bounds(iso_year, weeks).unwrap();
+                assert_eq!(total, calendar.work_days_between(first, last));
+
+                for week in 1..=weeks {
+                    let (monday, _) = iso_week_bounds(iso_year, week).unwrap();
+                    let working: Vec<NaiveDate> = monday
+                        .iter_days()
+                        .take(7)
+                        .filter(|date| calendar.is_working_date(date))
+                        .collect();
+                    assert_eq!(
+                        calendar.first_work_day_of_iso_week(iso_year, week),
+                        working.first().copied()
+                    );
+                    assert_eq!(
+                        calendar.last_work_day_of_iso_week(iso_year, week),
+                        working.last().copied()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bucket_by_work_week_shutdown() {
+        let mut calendar = WorkCalendar::new();
+        // A year-end shutdown covers every working date of 2023-W52.
+        for day in 25..=29 {
+            calendar.add_holiday(date(2023, 12, day));
+        }
+        calendar.add_holiday(date(2024, 1, 1));
+
+        assert_eq!(calendar.work_week_of(date(2023, 12, 27)), None);
+        assert_eq!(calendar.work_week_of(date(2023, 12, 31)), None);
+        // The next week starts on Tuesday after the New Year holiday.
+        assert_eq!(
+            calendar.work_week_of(date(2024, 1, 1)),
+            Some((date(2024, 1, 2), date(2024, 1, 5)))
+        );
+        // A working Saturday stretches the week to the weekend.
+        calendar.add_working_date(date(2024, 1, 6));
+        assert_eq!(
+            calendar.work_week_of(date(2024, 1, 7)),
+            Some((date(2024, 1, 2), date(2024, 1, 6)))
+        );
+
+        let events = [
+            date(2023, 12, 22),
+            date(2024, 1, 7),
+            date(2023, 12, 26),
+            date(2023, 12, 23),
+            date(2024, 1, 1),
+            date(2023, 12, 31),
+            date(2023, 12, 22),
+        ];
+        let buckets = calendar.bucket_by_work_week(events);
+        let expected: BTreeMap<NaiveDate, Vec<NaiveDate>> = [
+            (
+                date(2023, 12, 18),
+                vec![date(2023, 12, 22), date(2023, 12, 23), date(2023, 12, 22)],
+            ),
+            (date(2024, 1, 2), vec![date(2024, 1, 7), date(2024, 1, 1)]),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(buckets, expected);
+        assert!(calendar.bucket_by_work_week([]).is_empty());
+    }
+
+    #[test]
+    fn test_bucket_by_work_week_randomized() {
+        let mut rng = TestRng(0x9b3e_04d1_7c6a_f258);
+        for _ in 0..50 {
+            let calendar = random_calendar(&mut rng);
+            let dates: Vec<NaiveDate> = (0..100)
+                .map(|_| date(2020, 1, 1) + chrono::Duration::days(rng.below(3000) as i64))
+                .collect();
+            let buckets = calendar.bucket_by_work_week(dates.iter().copied());
+
+            let mut bucketed = 0;
+            for (start, members) in &buckets {
+                bucketed += members.len();
+                for member in members {
+                    let (first, last) = calendar.work_week_of(*member).unwrap();
+                    assert_eq!(first, *start);
+                    assert!(first <= last && first.iso_week() == member.iso_week());
+                    assert!(calendar.is_working_date(&first) && calendar.is_working_date(&last));
+                }
+            }
+            let skipped = dates
+                .iter()
+                .filter(|d| calendar.work_week_of(**d).is_none())
+                .count();
+            assert_eq!(bucketed + skipped, dates.len());
+        }
+    }
+}
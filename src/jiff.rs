@@ -0,0 +1,184 @@
+//! Interoperability with the `jiff` crate, available with the `jiff` feature.
+//!
+//! Each method converts its arguments to `chrono` dates, calls the matching `chrono`
+//! method, and converts the result back, so both APIs share one implementation.
+
+use crate::{WorkCalendar, WorkCalendarError};
+use chrono::{Datelike, NaiveDate};
+use jiff::{civil::Date, Span};
+
+impl WorkCalendar {
+    /// Checks if a `jiff::civil::Date` is a working date.
+    ///
+    /// Same as [`WorkCalendar::is_working_date`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use jiff::civil::date;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// assert!(calendar.is_working_jiff_date(date(2023, 8, 21)));
+    /// assert!(!calendar.is_working_jiff_date(date(2023, 8, 26)));
+    /// ```
+    pub fn is_working_jiff_date(&self, date: Date) -> bool {
+        self.is_working_date(&to_naive(date))
+    }
+
+    /// Calculates the number of work days between two `jiff::civil::Date`s
+    /// (inclusive).
+    ///
+    /// Same as [`WorkCalendar::work_days_between`].
+    pub fn work_days_between_jiff(&self, start_date: Date, end_date: Date) -> i64 {
+        self.work_days_between(to_naive(start_date), to_naive(end_date))
+    }
+
+    /// Computes the end date and calendar span for a `jiff::civil::Date` start date.
+    ///
+    /// Same as [`WorkCalendar::compute_end_date`], with the span in days.
+    ///
+    /// # Errors
+    ///
+    /// Any error from [`WorkCalendar::compute_end_date`], or
+    /// [`WorkCalendarError::DateOutOfRange`] if the end date is outside `jiff`'s range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use jiff::civil::date;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let (end, span) = calendar.compute_end_date_jiff(date(2023, 8, 21), 6).unwrap();
+    /// assert_eq!(end, date(2023, 8, 28));
+    /// assert_eq!(span.get_days(), 7);
+    /// ```
+    pub fn compute_end_date_jiff(
+        &self,
+        start_date: Date,
+        days_worked: i64,
+    ) -> Result<(Date, Span), WorkCalendarError> {
+        let (end_date, span) = self.compute_end_date(to_naive(start_date), days_worked)?;
+        let end_date = to_jiff(end_date).ok_or_else(|| self.out_of_range())?;
+        let span = Span::new()
+            .try_days(span.num_days())
+            .map_err(|_| self.out_of_range())?;
+        Ok((end_date, span))
+    }
+
+    /// Returns the first working date after a `jiff::civil::Date`.
+    ///
+    /// Same as [`WorkCalendar::next_work_day`].
+    ///
+    /// # Errors
+    ///
+    /// Any error from [`WorkCalendar::next_work_day`], or
+    /// [`WorkCalendarError::DateOutOfRange`] if the result is outside `jiff`'s range.
+    pub fn next_work_day_jiff(&self, date: Date) -> Result<Date, WorkCalendarError> {
+        let next = self.next_work_day(to_naive(date))?;
+        to_jiff(next).ok_or_else(|| self.out_of_range())
+    }
+
+    fn out_of_range(&self) -> WorkCalendarError {
+        WorkCalendarError::DateOutOfRange {
+            calendar: self.name().map(String::from),
+        }
+    }
+}
+
+/// Converts a `jiff` date to a `chrono` date. Every `jiff` date is representable.
+fn to_naive(date: Date) -> NaiveDate {
+    NaiveDate::from_ymd_opt(
+        i32::from(date.year()),
+        date.month() as u32,
+        date.day() as u32,
+    )
+    .expect("jiff's date range lies within chrono's")
+}
+
+/// Converts a `chrono` date to a `jiff` date, if `jiff` can represent it.
+fn to_jiff(date: NaiveDate) -> Option<Date> {
+    let year = i16::try_from(date.year()).ok()?;
+    Date::new(year, date.month() as i8, date.day() as i8).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{random_calendar, TestRng};
+    use chrono::Duration;
+
+    #[test]
+    fn test_conversions_round_trip() {
+        let mut date = to_naive(Date::MIN);
+        let last = to_naive(Date::MAX);
+        while date <= last {
+            let converted = to_jiff(date).unwrap();
+            assert_eq!(
+                (
+                    i32::from(converted.year()),
+                    converted.month() as u32,
+                    converted.day() as u32
+                ),
+                (date.year(), date.month(), date.day())
+            );
+            assert_eq!(to_naive(converted), date);
+            date += Duration::days(997);
+        }
+        assert_eq!(to_jiff(last + Duration::days(1)), None);
+        assert_eq!(to_jiff(NaiveDate::MIN), None);
+    }
+
+    #[test]
+    fn test_jiff_api_matches_chrono_api() {
+        let mut rng = TestRng(0xd1b5_4a32_d192_ed03);
+        for _ in 0..20 {
+            let calendar = random_calendar(&mut rng);
+            let base = NaiveDate::from_ymd_opt(2019, 6, 1).unwrap();
+            for _ in 0..200 {
+                let date = base + Duration::days(rng.below(3650) as i64);
+                let other = base + Duration::days(rng.below(3650) as i64);
+                let (jiff_date, jiff_other) = (to_jiff(date).unwrap(), to_jiff(other).unwrap());
+
+                assert_eq!(
+                    calendar.is_working_jiff_date(jiff_date),
+                    calendar.is_working_date(&date)
+                );
+                assert_eq!(
+                    calendar.work_days_between_jiff(jiff_date, jiff_other),
+                    calendar.work_days_between(date, other)
+                );
+                assert_eq!(
+                    calendar.next_work_day_jiff(jiff_date),
+                    calendar
+                        .next_work_day(date)
+                        .map(|next| to_jiff(next).unwrap())
+                );
+
+                let days_worked = rng.below(400) as i64 - 5;
+                let expected = calendar
+                    .compute_end_date(date, days_worked)
+                    .map(|(end, span)| (to_jiff(end).unwrap(), span.num_days()));
+                let actual = calendar
+                    .compute_end_date_jiff(jiff_date, days_worked)
+                    .map(|(end, span)| (end, i64::from(span.get_days())));
+                assert_eq!(actual, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_results_beyond_jiff_range() {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("Ops");
+        let error = WorkCalendarError::DateOutOfRange {
+            calendar: Some("Ops".to_string()),
+        };
+        assert_eq!(calendar.next_work_day_jiff(Date::MAX), Err(error.clone()));
+        assert_eq!(
+            calendar.compute_end_date_jiff(Date::MAX, 10).unwrap_err(),
+            error
+        );
+    }
+}
@@ -0,0 +1,424 @@
+//! The configuration format for [`LayeredCalendar`]s.
+
+use crate::{
+    layered::StackedLayer, CalendarLayer, CalendarRegistry, HolidayRule, LayerChanges,
+    LayeredCalendar, WorkCalendar, WorkCalendarError,
+};
+use chrono::{NaiveDate, Weekday};
+use serde::{Deserialize, Serialize, Serializer};
+use std::{collections::BTreeSet, sync::Arc};
+
+/// Looks up the calendars named by `ref` layers in a layered calendar document.
+///
+/// Implemented for [`CalendarRegistry`] and for any `Fn(&str) -> Option<Arc<WorkCalendar>>`
+/// closure.
+pub trait CalendarResolver {
+    /// Returns the calendar called `name`, or `None` if there is none.
+    fn resolve(&self, name: &str) -> Option<Arc<WorkCalendar>>;
+}
+
+impl CalendarResolver for CalendarRegistry {
+    fn resolve(&self, name: &str) -> Option<Arc<WorkCalendar>> {
+        self.get(name)
+    }
+}
+
+impl<F: Fn(&str) -> Option<Arc<WorkCalendar>>> CalendarResolver for F {
+    fn resolve(&self, name: &str) -> Option<Arc<WorkCalendar>> {
+        self(name)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DocumentRepr {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    layers: Vec<LayerRepr>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LayerRepr {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(rename = "ref", default, skip_serializing_if = "Option::is_none")]
+    reference: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    calendar: Option<Arc<WorkCalendar>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    holidays: Option<BTreeSet<NaiveDate>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    recurring_holidays: Option<Vec<HolidayRule>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    days_off: Option<Vec<Weekday>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    extra_work_days: Option<BTreeSet<NaiveDate>>,
+}
+
+impl LayerRepr {
+    fn has_changes(&self) -> bool {
+        self.holidays.is_some()
+            || self.recurring_holidays.is_some()
+            || self.days_off.is_some()
+            || self.extra_work_days.is_some()
+    }
+}
+
+impl LayeredCalendar {
+    /// Creates a layered calendar from a YAML or JSON document, looking up `ref`
+    /// layers with `resolver`.
+    ///
+    /// The document has an optional `name` and a list of `layers` from the bottom of
+    /// the stack to the top. Each layer is one of:
+    ///
+    /// * `ref: <name>` - a calendar looked up with `resolver`. The layer is named
+    ///   after the calendar unless it also has a `name`.
+    /// * `calendar: {...}` - a calendar written inline, in `WorkCalendar`'s serialized
+    ///   form.
+    /// * Any of `holidays`, `recurring_holidays`, `days_off` and `extra_work_days` -
+    ///   the fields of [`LayerChanges`], with `extra_work_days` holding its working
+    ///   dates. A layer with only a name makes no changes.
+    ///
+    /// Every layer other than a `ref` needs a `name`, and unknown keys are rejected.
+    /// Serializing a `LayeredCalendar` writes this format back, with `ref` layers
+    /// still written as references.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The document.
+    /// * `resolver` - Looks up the calendars named by `ref` layers, such as a
+    ///   [`CalendarRegistry`].
+    ///
+    /// # Errors
+    ///
+    /// * [`WorkCalendarError::ConfigParse`] if the document is malformed, has unknown
+    ///   keys, or has a layer that is missing a name or mixes `ref`, `calendar` and
+    ///   changes.
+    /// * [`WorkCalendarError::UnknownCalendar`] if `resolver` does not know a `ref`.
+    /// * [`WorkCalendarError::DuplicateLayer`] if two layers have the same name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{CalendarRegistry, LayeredCalendar, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut registry = CalendarRegistry::new();
+    /// registry.register("company-base", WorkCalendar::new()).unwrap();
+    ///
+    /// let config = r#"
+    /// layers:
+    ///   - ref: company-base
+    ///   - name: germany
+    ///     holidays: [2025-10-03]
+    ///   - name: crunch
+    ///     extra_work_days: [2025-10-04]
+    /// "#;
+    /// let layered = LayeredCalendar::from_config(config, &registry).unwrap();
+    /// assert_eq!(
+    ///     layered.layer_names().collect::<Vec<_>>(),
+    ///     ["company-base", "germany", "crunch"]
+    /// );
+    /// assert!(!layered.is_working_date(&NaiveDate::from_ymd_opt(2025, 10, 3).unwrap()));
+    /// assert!(layered.is_working_date(&NaiveDate::from_ymd_opt(2025, 10, 4).unwrap()));
+    ///
+    /// // The document round-trips.
+    /// let yaml = serde_yaml::to_string(&layered).unwrap();
+    /// let again = LayeredCalendar::from_config(&yaml, &registry).unwrap();
+    /// assert_eq!(again, layered);
+    /// ```
+    pub fn from_config(
+        config: &str,
+        resolver: &impl CalendarResolver,
+    ) -> Result<Self, WorkCalendarError> {
+        let parse_error =
+            |error: &dyn std::fmt::Display| WorkCalendarError::ConfigParse(error.to_string());
+        let document: DocumentRepr = if config.trim_start().starts_with('{') {
+            serde_json::from_str(config).map_err(|e| parse_error(&e))?
+        } else {
+            serde_yaml::from_str(config).map_err(|e| parse_error(&e))?
+        };
+
+        let mut layered = LayeredCalendar::new();
+        if let Some(name) = document.name {
+            layered.set_name(name);
+        }
+        for (index, repr) in document.layers.into_iter().enumerate() {
+            let label = || match (&repr.name, &repr.reference) {
+                (Some(name), _) | (None, Some(name)) => format!("layer '{}'", name),
+                (None, None) => format!("layer {}", index + 1),
+            };
+            let layer = match (&repr.reference, &repr.calendar) {
+                (Some(_), _) | (_, Some(_)) if repr.has_changes() => {
+                    return Err(WorkCalendarError::ConfigParse(format!(
+                        "{} mixes changes with `ref` or `calendar`",
+                        label()
+                    )))
+                }
+                (Some(_), Some(_)) => {
+                    return Err(WorkCalendarError::ConfigParse(format!(
+                        "{} has both `ref` and `calendar`",
+                        label()
+                    )))
+                }
+                (Some(reference), None) => match resolver.resolve(reference) {
+                    Some(calendar) => CalendarLayer::Calendar(calendar),
+                    None => return Err(WorkCalendarError::UnknownCalendar(reference.clone())),
+                },
+                (None, Some(calendar)) => CalendarLayer::Calendar(calendar.clone()),
+                (None, None) => CalendarLayer::Changes(LayerChanges {
+                    holidays: repr.holidays.clone().unwrap_or_default(),
+                    recurring_holidays: repr.recurring_holidays.clone().unwrap_or_default(),
+                    days_off: repr.days_off.iter().flatten().copied().collect(),
+                    working_dates: repr.extra_work_days.clone().unwrap_or_default(),
+                }),
+            };
+            let name = match repr.name.as_ref().or(repr.reference.as_ref()) {
+                Some(name) => name.clone(),
+                None => {
+                    return Err(WorkCalendarError::ConfigParse(format!(
+                        "{} needs a `name`",
+                        label()
+                    )))
+                }
+            };
+            layered.push_stacked(StackedLayer {
+                name,
+                layer: Arc::new(layer),
+                reference: repr.reference,
+            })?;
+        }
+        Ok(layered)
+    }
+}
+
+/// Writes the document [`LayeredCalendar::from_config`] reads.
+///
+/// Layers that came from a `ref` are written as that `ref` rather than inline, so
+/// reading the document back resolves them again.
+impl Serialize for LayeredCalendar {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let layers = self
+            .layers
+            .iter()
+            .map(|stacked| {
+                let name = Some(stacked.name.clone());
+                match (&stacked.reference, &*stacked.layer) {
+                    (Some(reference), _) => LayerRepr {
+                        name: name.filter(|name| name != reference),
+                        reference: Some(reference.clone()),
+                        ..LayerRepr::default()
+                    },
+                    (None, CalendarLayer::Calendar(calendar)) => LayerRepr {
+                        name,
+                        calendar: Some(calendar.clone()),
+                        ..LayerRepr::default()
+                    },
+                    (None, CalendarLayer::Changes(changes)) => {
+                        let mut days_off: Vec<Weekday> = changes.days_off.iter().copied().collect();
+                        days_off.sort_by_key(|day| day.num_days_from_monday());
+                        LayerRepr {
+                            name,
+                            holidays: (!changes.holidays.is_empty())
+                                .then(|| changes.holidays.clone()),
+                            recurring_holidays: (!changes.recurring_holidays.is_empty())
+                                .then(|| changes.recurring_holidays.clone()),
+                            days_off: (!days_off.is_empty()).then_some(days_off),
+                            extra_work_days: (!changes.working_dates.is_empty())
+                                .then(|| changes.working_dates.clone()),
+                            ..LayerRepr::default()
+                        }
+                    }
+                }
+            })
+            .collect();
+        DocumentRepr {
+            name: self.name().map(String::from),
+            layers,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::date;
+    use chrono::Duration;
+
+    fn registry() -> CalendarRegistry {
+        let mut registry = CalendarRegistry::new();
+        let mut base = WorkCalendar::new();
+        base.set_name("Company");
+        base.add_holiday(date(2025, 12, 25));
+        base.add_recurring_holiday(HolidayRule::FixedDate { month: 1, day: 1 });
+        registry.register("company-base", base).unwrap();
+        registry
+    }
+
+    const CONFIG: &str = r#"
+name: berlin-platform
+layers:
+  - ref: company-base
+  - name: germany
+    holidays: [2025-10-03]
+    recurring_holidays:
+      - !FixedDate { month: 5, day: 1 }
+  - name: team
+    days_off: [Friday]
+  - name: contractor
+    calendar:
+      work_days: [Monday, Tuesday, Wednesday, Thursday, Friday, Saturday]
+      holidays: [2025-11-01]
+  - name: crunch
+    extra_work_days: [2025-10-03, 2025-10-04]
+  - name: placeholder
+"#;
+
+    #[test]
+    fn test_matches_manual_stacking() {
+        let registry = registry();
+        let parsed = LayeredCalendar::from_config(CONFIG, &registry).unwrap();
+
+        let mut manual = LayeredCalendar::new();
+        manual.set_name("berlin-platform");
+        manual
+            .push_shared_layer(
+                "company-base",
+                Arc::new(CalendarLayer::Calendar(
+                    registry.get("company-base").unwrap(),
+                )),
+            )
+            .unwrap();
+        let mut germany = LayerChanges::default();
+        germany.holidays.insert(date(2025, 10, 3));
+        germany
+            .recurring_holidays
+            .push(HolidayRule::FixedDate { month: 5, day: 1 });
+        manual.push_layer("germany", germany).unwrap();
+        let mut team = LayerChanges::default();
+        team.days_off.insert(Weekday::Fri);
+        manual.push_layer("team", team).unwrap();
+        let mut contractor = WorkCalendar::new();
+        contractor.add_work_day(Weekday::Sat);
+        contractor.add_holiday(date(2025, 11, 1));
+        manual.push_layer("contractor", contractor).unwrap();
+        let mut crunch = LayerChanges::default();
+        crunch.working_dates.insert(date(2025, 10, 3));
+        crunch.working_dates.insert(date(2025, 10, 4));
+        manual.push_layer("crunch", crunch).unwrap();
+        manual
+            .push_layer("placeholder", LayerChanges::default())
+            .unwrap();
+
+        assert_eq!(parsed.name(), Some("berlin-platform"));
+        assert!(parsed.layers().eq(manual.layers()));
+        assert!(Arc::ptr_eq(
+            match &**parsed.layer("company-base").unwrap() {
+                CalendarLayer::Calendar(calendar) => calendar,
+                other => panic!("unexpected layer {:?}", other),
+            },
+            &registry.get("company-base").unwrap()
+        ));
+        let start = date(2025, 1, 1);
+        for offset in 0..400 {
+            let day = start + Duration::days(offset);
+            assert_eq!(parsed.is_working_date(&day), manual.is_working_date(&day));
+        }
+        assert!(parsed.is_working_date(&date(2025, 10, 4)));
+        assert!(!parsed.is_working_date(&date(2025, 11, 1)));
+        assert!(!parsed.is_working_date(&date(2025, 10, 10)));
+        assert_eq!(
+            parsed.work_days_between(date(2025, 9, 29), date(2025, 10, 5)),
+            manual.work_days_between(date(2025, 9, 29), date(2025, 10, 5))
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let registry = registry();
+        let parsed = LayeredCalendar::from_config(CONFIG, &registry).unwrap();
+
+        let yaml = serde_yaml::to_string(&parsed).unwrap();
+        assert!(yaml.contains("ref: company-base"), "{}", yaml);
+        assert_eq!(
+            LayeredCalendar::from_config(&yaml, &registry),
+            Ok(parsed.clone())
+        );
+        let json = serde_json::to_string(&parsed).unwrap();
+        assert_eq!(
+            LayeredCalendar::from_config(&json, &registry),
+            Ok(parsed.clone())
+        );
+
+        // A ref layer renamed in the document keeps its name and its ref.
+        let renamed = "layers:\n  - ref: company-base\n    name: base\n";
+        let parsed = LayeredCalendar::from_config(renamed, &registry).unwrap();
+        assert_eq!(parsed.layer_names().collect::<Vec<_>>(), ["base"]);
+        let yaml = serde_yaml::to_string(&parsed).unwrap();
+        assert_eq!(LayeredCalendar::from_config(&yaml, &registry), Ok(parsed));
+
+        // Programmatic calendar layers are written inline.
+        let mut manual = LayeredCalendar::new();
+        manual.push_layer("base", WorkCalendar::new()).unwrap();
+        let yaml = serde_yaml::to_string(&manual).unwrap();
+        let no_refs = |_: &str| None;
+        assert_eq!(LayeredCalendar::from_config(&yaml, &no_refs), Ok(manual));
+    }
+
+    #[test]
+    fn test_closure_resolver() {
+        let base = Arc::new(WorkCalendar::new());
+        let resolver = |name: &str| Some(base.clone()).filter(|_| name == "base");
+        let parsed = LayeredCalendar::from_config(r#"{"layers": [{"ref": "base"}]}"#, &resolver);
+        assert_eq!(parsed.unwrap().layer_names().collect::<Vec<_>>(), ["base"]);
+        assert_eq!(
+            LayeredCalendar::from_config(r#"{"layers": [{"ref": "other"}]}"#, &resolver),
+            Err(WorkCalendarError::UnknownCalendar("other".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_strict_validation() {
+        let registry = registry();
+        let parse_error = |config: &str| match LayeredCalendar::from_config(config, &registry) {
+            Err(WorkCalendarError::ConfigParse(message)) => message,
+            other => panic!("unexpected result {:?} for {}", other, config),
+        };
+
+        let message = parse_error("layers:\n  - name: a\n    holdays: [2025-01-01]\n");
+        assert!(message.contains("holdays"), "{}", message);
+        let message = parse_error("layer: []\n");
+        assert!(message.contains("layer"), "{}", message);
+        let message = parse_error("layers:\n  - holidays: [2025-01-01]\n");
+        assert_eq!(message, "layer 1 needs a `name`");
+        let message = parse_error("layers:\n  - ref: company-base\n    days_off: [Sunday]\n");
+        assert_eq!(
+            message,
+            "layer 'company-base' mixes changes with `ref` or `calendar`"
+        );
+        let message = parse_error(
+            "layers:\n  - name: x\n    ref: company-base\n    calendar: {work_days: [], holidays: []}\n",
+        );
+        assert_eq!(message, "layer 'x' has both `ref` and `calendar`");
+        let message = parse_error("layers:\n  - name: a\n    holidays: [2025-02-30]\n");
+        assert!(!message.is_empty());
+        let message = parse_error("layers:\n  - name: a\n    days_off: [Caturday]\n");
+        assert!(!message.is_empty());
+
+        assert_eq!(
+            LayeredCalendar::from_config("layers:\n  - ref: nowhere\n", &registry),
+            Err(WorkCalendarError::UnknownCalendar("nowhere".to_string()))
+        );
+        assert_eq!(
+            LayeredCalendar::from_config(
+                "layers:\n  - ref: company-base\n  - name: company-base\n",
+                &registry
+            ),
+            Err(WorkCalendarError::DuplicateLayer(
+                "company-base".to_string()
+            ))
+        );
+    }
+}
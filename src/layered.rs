@@ -0,0 +1,657 @@
+//! Calendars composed from a stack of shared layers.
+
+use crate::{
+    calendar_like::compute_end_by_weeks, week::WorkWeek, CalendarLike, EndDate, HolidayRule,
+    WorkCalendar, WorkCalendarError, SEARCH_HORIZON_DAYS,
+};
+use chrono::{Datelike, NaiveDate, Weekday};
+use std::{
+    collections::{BTreeSet, HashSet},
+    sync::Arc,
+};
+
+/// Explicit changes made by one layer of a [`LayeredCalendar`].
+///
+/// Within a layer, working dates take precedence over the layer's own holidays,
+/// recurring holidays and days off.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::LayerChanges;
+/// use chrono::{NaiveDate, Weekday};
+///
+/// // A team that takes Fridays off, except for release Friday.
+/// let mut fridays_off = LayerChanges::default();
+/// fridays_off.days_off.insert(Weekday::Fri);
+/// fridays_off
+///     .working_dates
+///     .insert(NaiveDate::from_ymd_opt(2025, 6, 27).unwrap());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LayerChanges {
+    /// Dates the layer makes non-working.
+    pub holidays: BTreeSet<NaiveDate>,
+    /// Holidays the layer makes non-working every year.
+    pub recurring_holidays: Vec<HolidayRule>,
+    /// Weekdays the layer makes non-working every week.
+    pub days_off: HashSet<Weekday>,
+    /// Dates the layer makes working, whatever the layers below say.
+    pub working_dates: BTreeSet<NaiveDate>,
+}
+
+/// One layer of a [`LayeredCalendar`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CalendarLayer {
+    /// A whole calendar. Dates it does not work are non-working, and its
+    /// working-date overrides ([`WorkCalendar::add_working_date`]) are working;
+    /// every other date is left to the layers below.
+    Calendar(Arc<WorkCalendar>),
+    /// Explicit holidays, days off and working dates.
+    Changes(LayerChanges),
+}
+
+impl CalendarLayer {
+    /// Returns `Some(true)` if the layer makes `date` working, `Some(false)` if it
+    /// makes it non-working, and `None` if it leaves it to the layers below.
+    pub fn decides(&self, date: &NaiveDate) -> Option<bool> {
+        match self {
+            CalendarLayer::Calendar(calendar) => {
+                if !calendar.is_working_date(date) {
+                    Some(false)
+                } else if calendar.is_working_date_override(date) {
+                    Some(true)
+                } else {
+                    None
+                }
+            }
+            CalendarLayer::Changes(changes) => {
+                if changes.working_dates.contains(date) {
+                    Some(true)
+                } else if changes.holidays.contains(date)
+                    || changes.days_off.contains(&date.weekday())
+                    || changes
+                        .recurring_holidays
+                        .iter()
+                        .any(|rule| rule.date_in(date.year()) == Some(*date))
+                {
+                    Some(false)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Returns the weekdays the layer makes non-working in every week, as a
+    /// [`WorkWeek`] bit mask.
+    fn weekdays_off(&self) -> u8 {
+        match self {
+            CalendarLayer::Calendar(calendar) => !calendar
+                .work_weeks()
+                .fold(0, |mask, week| mask | week.mask()),
+            CalendarLayer::Changes(changes) => changes
+                .days_off
+                .iter()
+                .fold(0, |mask, day| mask | WorkWeek::bit(*day)),
+        }
+    }
+
+    /// Checks if the layer makes any date working.
+    fn has_working_dates(&self) -> bool {
+        match self {
+            CalendarLayer::Calendar(calendar) => !calendar.working_dates.is_empty(),
+            CalendarLayer::Changes(changes) => !changes.working_dates.is_empty(),
+        }
+    }
+}
+
+impl From<WorkCalendar> for CalendarLayer {
+    fn from(calendar: WorkCalendar) -> Self {
+        CalendarLayer::Calendar(Arc::new(calendar))
+    }
+}
+
+impl From<Arc<WorkCalendar>> for CalendarLayer {
+    fn from(calendar: Arc<WorkCalendar>) -> Self {
+        CalendarLayer::Calendar(calendar)
+    }
+}
+
+impl From<LayerChanges> for CalendarLayer {
+    fn from(changes: LayerChanges) -> Self {
+        CalendarLayer::Changes(changes)
+    }
+}
+
+/// A calendar built by stacking named layers, such as a company calendar, a
+/// country's public holidays, a team's days off and one person's leave.
+///
+/// Each layer can make dates non-working or force them working, and for every date
+/// the last layer with an opinion decides. A date no layer decides is a working date,
+/// so the bottom layer is usually a full calendar that sets the work week.
+///
+/// Layers are held in `Arc`s, and cloning a layered calendar only copies the list
+/// of layer names and pointers, so thousands of per-person calendars can share the
+/// same base layers.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use workdays::{LayerChanges, LayeredCalendar, WorkCalendar};
+/// use chrono::{NaiveDate, Weekday};
+///
+/// let date = |d| NaiveDate::from_ymd_opt(2025, 6, d).unwrap();
+/// let mut company = WorkCalendar::new();
+/// company.add_holiday(date(9));
+///
+/// let mut fridays_off = LayerChanges::default();
+/// fridays_off.days_off.insert(Weekday::Fri);
+///
+/// let mut team = LayeredCalendar::new();
+/// team.push_layer("company", company).unwrap();
+/// team.push_layer("team", fridays_off).unwrap();
+///
+/// let mut pto = LayerChanges::default();
+/// pto.holidays.insert(date(11));
+/// let mut alice = team.clone();
+/// alice.push_layer("pto", pto).unwrap();
+///
+/// // June 9-15, 2025: Monday is a company holiday and Friday a team day off.
+/// assert_eq!(team.work_days_between(date(9), date(15)), 3);
+/// assert_eq!(alice.work_days_between(date(9), date(15)), 2);
+/// assert_eq!(alice.deciding_layer(&date(11)), Some("pto"));
+/// assert!(Arc::ptr_eq(team.layer("company").unwrap(), alice.layer("company").unwrap()));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LayeredCalendar {
+    name: Option<String>,
+    /// Layers from the bottom of the stack to the top.
+    pub(crate) layers: Vec<StackedLayer>,
+}
+
+/// A layer in a [`LayeredCalendar`]'s stack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct StackedLayer {
+    pub(crate) name: String,
+    pub(crate) layer: Arc<CalendarLayer>,
+    /// The registered calendar name the layer was resolved from, if it came from a
+    /// `ref` in a configuration document.
+    pub(crate) reference: Option<String>,
+}
+
+impl LayeredCalendar {
+    /// Creates a layered calendar with no layers, on which every date is a working
+    /// date.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the calendar's name, which is reported in errors it raises.
+    pub fn set_name(&mut self, name: impl Into<String>) {
+        self.name = Some(name.into());
+    }
+
+    /// Returns the calendar's name, if it has one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Adds a layer on top of the stack, where it takes precedence over every layer
+    /// already added.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name to find or remove the layer by.
+    /// * `layer` - The layer: a `WorkCalendar`, `Arc<WorkCalendar>`, [`LayerChanges`]
+    ///   or [`CalendarLayer`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorkCalendarError::DuplicateLayer`] if a layer named `name` is
+    /// already in the stack.
+    pub fn push_layer(
+        &mut self,
+        name: &str,
+        layer: impl Into<CalendarLayer>,
+    ) -> Result<(), WorkCalendarError> {
+        self.push_shared_layer(name, Arc::new(layer.into()))
+    }
+
+    /// Like [`LayeredCalendar::push_layer`], for a layer shared with other calendars.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorkCalendarError::DuplicateLayer`] if a layer named `name` is
+    /// already in the stack.
+    pub fn push_shared_layer(
+        &mut self,
+        name: &str,
+        layer: Arc<CalendarLayer>,
+    ) -> Result<(), WorkCalendarError> {
+        self.push_stacked(StackedLayer {
+            name: name.to_string(),
+            layer,
+            reference: None,
+        })
+    }
+
+    pub(crate) fn push_stacked(&mut self, layer: StackedLayer) -> Result<(), WorkCalendarError> {
+        if self.layer(&layer.name).is_some() {
+            return Err(WorkCalendarError::DuplicateLayer(layer.name));
+        }
+        self.layers.push(layer);
+        Ok(())
+    }
+
+    /// Removes and returns the layer named `name`, leaving the others in order.
+    pub fn remove_layer(&mut self, name: &str) -> Option<Arc<CalendarLayer>> {
+        let index = self.layers.iter().position(|layer| layer.name == name)?;
+        Some(self.layers.remove(index).layer)
+    }
+
+    /// Returns the layer named `name`.
+    pub fn layer(&self, name: &str) -> Option<&Arc<CalendarLayer>> {
+        self.layers
+            .iter()
+            .find(|layer| layer.name == name)
+            .map(|layer| &layer.layer)
+    }
+
+    /// Returns the layer names from the bottom of the stack to the top.
+    pub fn layer_names(&self) -> impl Iterator<Item = &str> {
+        self.layers.iter().map(|layer| layer.name.as_str())
+    }
+
+    /// Returns the layers and their names from the bottom of the stack to the top.
+    pub fn layers(&self) -> impl Iterator<Item = (&str, &Arc<CalendarLayer>)> {
+        self.layers
+            .iter()
+            .map(|layer| (layer.name.as_str(), &layer.layer))
+    }
+
+    /// Returns the name of the layer that decides whether `date` is a working date,
+    /// or `None` if no layer does and the date is working by default.
+    pub fn deciding_layer(&self, date: &NaiveDate) -> Option<&str> {
+        self.layers
+            .iter()
+            .rev()
+            .find(|layer| layer.layer.decides(date).is_some())
+            .map(|layer| layer.name.as_str())
+    }
+
+    /// Checks if `date` is a working date: what the topmost layer with an opinion
+    /// says, or working if no layer has one.
+    pub fn is_working_date(&self, date: &NaiveDate) -> bool {
+        self.layers
+            .iter()
+            .rev()
+            .find_map(|layer| layer.layer.decides(date))
+            .unwrap_or(true)
+    }
+
+    /// Calculates the number of work days between two dates (inclusive).
+    ///
+    /// See [`WorkCalendar::work_days_between`].
+    pub fn work_days_between(&self, start_date: NaiveDate, end_date: NaiveDate) -> i64 {
+        std::iter::successors(Some(start_date), |date| date.succ_opt())
+            .take_while(|date| *date <= end_date)
+            .filter(|date| self.is_working_date(date))
+            .count() as i64
+    }
+
+    /// Computes the end date and calendar span given a start date and number of work
+    /// days.
+    ///
+    /// # Errors
+    ///
+    /// The errors of [`WorkCalendar::compute_end`].
+    pub fn compute_end(
+        &self,
+        start_date: NaiveDate,
+        days_worked: i64,
+    ) -> Result<EndDate, WorkCalendarError> {
+        compute_end_by_weeks(
+            self,
+            |date| self.is_working_date(date),
+            |_| true,
+            0,
+            |_| None,
+            start_date,
+            days_worked,
+        )
+    }
+
+    /// Adds work days to a date.
+    ///
+    /// See [`WorkCalendar::checked_add_work_days`].
+    pub fn checked_add_work_days(&self, date: NaiveDate, n: i64) -> Option<NaiveDate> {
+        self.try_add_work_days(date, n).ok()
+    }
+
+    /// Subtracts work days from a date.
+    ///
+    /// See [`WorkCalendar::checked_sub_work_days`].
+    pub fn checked_sub_work_days(&self, date: NaiveDate, n: i64) -> Option<NaiveDate> {
+        self.try_add_work_days(date, n.checked_neg()?).ok()
+    }
+
+    /// Returns the first working date after `date`.
+    ///
+    /// # Errors
+    ///
+    /// The errors of [`WorkCalendar::next_work_day`].
+    pub fn next_work_day(&self, date: NaiveDate) -> Result<NaiveDate, WorkCalendarError> {
+        self.try_add_work_days(date, 1)
+    }
+
+    /// Returns the last working date before `date`.
+    ///
+    /// # Errors
+    ///
+    /// The errors of [`WorkCalendar::previous_work_day`].
+    pub fn previous_work_day(&self, date: NaiveDate) -> Result<NaiveDate, WorkCalendarError> {
+        self.try_add_work_days(date, -1)
+    }
+
+    /// Checks if some weekday is left working by every layer, or some layer forces a
+    /// date working.
+    fn has_any_work_days(&self) -> bool {
+        let off = self
+            .layers
+            .iter()
+            .fold(0, |mask, layer| mask | layer.layer.weekdays_off());
+        off & 0x7f != 0x7f
+            || self
+                .layers
+                .iter()
+                .any(|layer| layer.layer.has_working_dates())
+    }
+
+    /// Walks `n` working dates from `date`, forwards for positive `n` and backwards for
+    /// negative `n`.
+    fn try_add_work_days(&self, date: NaiveDate, n: i64) -> Result<NaiveDate, WorkCalendarError> {
+        let name = || self.name.clone();
+        if n == 0 {
+            return Ok(date);
+        }
+        if !self.has_any_work_days() {
+            return Err(WorkCalendarError::NoWorkDaysDefined { calendar: name() });
+        }
+        let mut remaining = n.unsigned_abs();
+        let mut current_date = date;
+        let mut gap = 0;
+        while remaining > 0 {
+            current_date = if n > 0 {
+                current_date.succ_opt()
+            } else {
+                current_date.pred_opt()
+            }
+            .ok_or_else(|| WorkCalendarError::DateOutOfRange { calendar: name() })?;
+            if self.is_working_date(&current_date) {
+                remaining -= 1;
+                gap = 0;
+            } else {
+                gap += 1;
+                if gap >= SEARCH_HORIZON_DAYS {
+                    return Err(WorkCalendarError::NoWorkingDaysInHorizon {
+                        calendar: name(),
+                        searched_until: current_date,
+                    });
+                }
+            }
+        }
+        Ok(current_date)
+    }
+}
+
+impl CalendarLike for LayeredCalendar {
+    fn name(&self) -> Option<&str> {
+        LayeredCalendar::name(self)
+    }
+
+    fn is_working_date(&self, date: &NaiveDate) -> bool {
+        LayeredCalendar::is_working_date(self, date)
+    }
+
+    fn has_work_days(&self) -> bool {
+        self.has_any_work_days()
+    }
+
+    fn work_days_between(&self, start_date: NaiveDate, end_date: NaiveDate) -> i64 {
+        LayeredCalendar::work_days_between(self, start_date, end_date)
+    }
+
+    fn compute_end(
+        &self,
+        start_date: NaiveDate,
+        days_worked: i64,
+    ) -> Result<EndDate, WorkCalendarError> {
+        LayeredCalendar::compute_end(self, start_date, days_worked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{date, random_calendar, TestRng};
+    use chrono::Duration;
+
+    fn company() -> WorkCalendar {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2025, 12, 25));
+        calendar.add_holiday(date(2025, 12, 26));
+        calendar
+    }
+
+    fn germany() -> LayerChanges {
+        let mut changes = LayerChanges::default();
+        changes.holidays.insert(date(2025, 10, 3));
+        changes
+            .recurring_holidays
+            .push(HolidayRule::FixedDate { month: 5, day: 1 });
+        changes
+    }
+
+    #[test]
+    fn test_later_layers_win() {
+        let mut layered = LayeredCalendar::new();
+        layered.set_name("berlin");
+        layered.push_layer("company", company()).unwrap();
+        layered.push_layer("germany", germany()).unwrap();
+        let mut crunch = LayerChanges::default();
+        crunch.working_dates.insert(date(2025, 10, 3));
+        crunch.working_dates.insert(date(2025, 10, 4));
+        layered.push_layer("crunch", crunch).unwrap();
+
+        assert!(layered.is_working_date(&date(2025, 10, 3)));
+        assert!(layered.is_working_date(&date(2025, 10, 4)));
+        assert!(!layered.is_working_date(&date(2025, 10, 5)));
+        assert!(!layered.is_working_date(&date(2026, 5, 1)));
+        assert!(!layered.is_working_date(&date(2025, 12, 25)));
+        assert_eq!(layered.deciding_layer(&date(2025, 10, 3)), Some("crunch"));
+        assert_eq!(layered.deciding_layer(&date(2025, 10, 5)), Some("company"));
+        assert_eq!(layered.deciding_layer(&date(2025, 10, 6)), None);
+
+        // A later layer closing the office again overrides the crunch Saturday.
+        let mut closed = LayerChanges::default();
+        closed.holidays.insert(date(2025, 10, 4));
+        layered.push_layer("closed", closed).unwrap();
+        assert!(!layered.is_working_date(&date(2025, 10, 4)));
+
+        // Removing layers uncovers the ones below.
+        assert!(layered.remove_layer("crunch").is_some());
+        assert!(layered.remove_layer("crunch").is_none());
+        assert!(!layered.is_working_date(&date(2025, 10, 3)));
+        assert!(layered.remove_layer("germany").is_some());
+        assert!(layered.is_working_date(&date(2025, 10, 3)));
+        assert_eq!(
+            layered.layer_names().collect::<Vec<_>>(),
+            ["company", "closed"]
+        );
+        assert_eq!(
+            layered.push_layer("company", LayerChanges::default()),
+            Err(WorkCalendarError::DuplicateLayer("company".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_within_layer_precedence() {
+        let mut changes = LayerChanges::default();
+        changes.days_off.insert(Weekday::Fri);
+        changes.holidays.insert(date(2025, 6, 24));
+        changes.working_dates.insert(date(2025, 6, 24));
+        changes.working_dates.insert(date(2025, 6, 27));
+        let layer = CalendarLayer::from(changes);
+        assert_eq!(layer.decides(&date(2025, 6, 24)), Some(true));
+        assert_eq!(layer.decides(&date(2025, 6, 27)), Some(true));
+        assert_eq!(layer.decides(&date(2025, 7, 4)), Some(false));
+        assert_eq!(layer.decides(&date(2025, 7, 3)), None);
+
+        let mut calendar = WorkCalendar::new();
+        calendar.add_working_date(date(2025, 6, 28));
+        let layer = CalendarLayer::from(calendar);
+        assert_eq!(layer.decides(&date(2025, 6, 28)), Some(true));
+        assert_eq!(layer.decides(&date(2025, 6, 29)), Some(false));
+        assert_eq!(layer.decides(&date(2025, 6, 30)), None);
+    }
+
+    #[test]
+    fn test_single_calendar_layer_matches_calendar() {
+        let mut rng = TestRng(0x2f8e_61c4_b93a_0d57);
+        let base = date(2019, 6, 1);
+        for _ in 0..40 {
+            let calendar = random_calendar(&mut rng);
+            let mut layered = LayeredCalendar::new();
+            layered.push_layer("base", calendar.clone()).unwrap();
+            for _ in 0..5 {
+                let start = base + Duration::days(rng.below(2000) as i64);
+                let end = start + Duration::days(rng.below(400) as i64);
+                let n = rng.below(200) as i64;
+                assert_eq!(
+                    layered.is_working_date(&start),
+                    calendar.is_working_date(&start)
+                );
+                assert_eq!(
+                    layered.work_days_between(start, end),
+                    calendar.work_days_between(start, end)
+                );
+                assert_eq!(
+                    layered.compute_end(start, n),
+                    calendar.compute_end(start, n)
+                );
+                assert_eq!(
+                    layered.checked_add_work_days(start, n),
+                    calendar.checked_add_work_days(start, n)
+                );
+                assert_eq!(
+                    layered.checked_sub_work_days(start, n),
+                    calendar.checked_sub_work_days(start, n)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let mut layered = LayeredCalendar::new();
+        layered.push_layer("company", company()).unwrap();
+        let mut fridays_off = LayerChanges::default();
+        fridays_off.days_off.insert(Weekday::Fri);
+        layered.push_layer("team", fridays_off).unwrap();
+
+        // Mon 2025-12-22 .. Fri 2026-01-02: Christmas and Boxing Day are off, and so
+        // are Fridays.
+        assert_eq!(
+            layered.work_days_between(date(2025, 12, 22), date(2026, 1, 2)),
+            7
+        );
+        assert_eq!(
+            layered.next_work_day(date(2025, 12, 24)),
+            Ok(date(2025, 12, 29))
+        );
+        assert_eq!(
+            layered.previous_work_day(date(2025, 12, 29)),
+            Ok(date(2025, 12, 24))
+        );
+        assert_eq!(
+            layered.checked_sub_work_days(date(2025, 12, 29), 2),
+            Some(date(2025, 12, 23))
+        );
+        let end = layered.compute_end(date(2025, 12, 22), 5).unwrap();
+        assert_eq!(end.date, date(2025, 12, 30));
+        assert_eq!(
+            CalendarLike::compute_end_date(&layered, date(2025, 12, 22), 5)
+                .unwrap()
+                .0,
+            date(2025, 12, 30)
+        );
+    }
+
+    #[test]
+    fn test_no_work_days() {
+        let mut layered = LayeredCalendar::new();
+        layered.set_name("closed");
+        assert!(CalendarLike::has_work_days(&layered));
+        let mut changes = LayerChanges::default();
+        changes.days_off.extend([
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ]);
+        layered.push_layer("all", changes).unwrap();
+        assert!(!CalendarLike::has_work_days(&layered));
+        let error = WorkCalendarError::NoWorkDaysDefined {
+            calendar: Some("closed".to_string()),
+        };
+        assert_eq!(layered.next_work_day(date(2025, 1, 1)), Err(error.clone()));
+        assert_eq!(
+            layered.previous_work_day(date(2025, 1, 1)),
+            Err(error.clone())
+        );
+        assert_eq!(layered.compute_end(date(2025, 1, 1), 3), Err(error));
+
+        // A single forced working date is found.
+        let mut one = LayerChanges::default();
+        one.working_dates.insert(date(2025, 1, 8));
+        layered.push_layer("one", one).unwrap();
+        assert_eq!(
+            layered.next_work_day(date(2025, 1, 1)),
+            Ok(date(2025, 1, 8))
+        );
+        assert!(matches!(
+            layered.next_work_day(date(2025, 1, 8)),
+            Err(WorkCalendarError::NoWorkingDaysInHorizon { .. })
+        ));
+    }
+
+    #[test]
+    fn test_shared_layers() {
+        let base: Arc<CalendarLayer> = Arc::new(company().into());
+        let region: Arc<CalendarLayer> = Arc::new(germany().into());
+        let people: Vec<LayeredCalendar> = (1..=1000)
+            .map(|day| {
+                let mut person = LayeredCalendar::new();
+                person.push_shared_layer("company", base.clone()).unwrap();
+                person.push_shared_layer("germany", region.clone()).unwrap();
+                let mut pto = LayerChanges::default();
+                pto.holidays
+                    .insert(date(2025, 1, 1) + Duration::days(day % 365));
+                person.push_layer("pto", pto).unwrap();
+                person
+            })
+            .collect();
+        assert_eq!(Arc::strong_count(&base), 1001);
+        assert!(people
+            .iter()
+            .all(|person| Arc::ptr_eq(person.layer("company").unwrap(), &base)));
+        assert!(!people[0].is_working_date(&date(2025, 1, 2)));
+        assert!(people[1].is_working_date(&date(2025, 1, 2)));
+        assert!(!people[1].is_working_date(&date(2025, 10, 3)));
+    }
+}
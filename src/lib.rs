@@ -11,6 +11,19 @@
 //! - Parse and handle work calendar configurations (YAML or JSON)
 //! - Support for custom work days and holidays
 //! - Flexible weekday parsing
+//! - An opt-in process-wide calendar ([`set_global_calendar`])
+//!
+//! ## Optional features
+//!
+//! - `arbitrary`: `proptest` strategies for generating random calendars
+//! - `jiff`: methods taking and returning `jiff::civil::Date`s (requires Rust 1.70)
+//! - `rayon`: parallel batch computations (`par_compute_end_dates`, `par_work_days_between`)
+//! - `ffi`: a C ABI over opaque calendar handles; see `include/workdays.h`
+//! - `ics`: holiday import from iCalendar (`.ics`) documents
+//! - `presets`: national holiday calendars generated from each jurisdiction's rules
+//! - `time`: methods taking and returning `time::Date`s
+//! - `tz`: time-zone-aware wrappers taking `DateTime`s, with `chrono-tz` re-exported
+//! - `wasm`: `wasm-bindgen` bindings exposing a `JsWorkCalendar` class to JavaScript
 //!
 //! ## Usage
 //!
@@ -27,24 +40,250 @@
 //! let start_date = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap();
 //! let days_worked = 20;
 //!
-//! let (end_date, calendar_duration) = calendar.compute_end_date(start_date, days_worked).unwrap();
-//! println!("End date: {}", end_date);
-//! println!("Calendar duration: {} days", calendar_duration.num_days());
+//! let end = calendar.compute_end(start_date, days_worked).unwrap();
+//! println!("End date: {}", end.date);
+//! println!("Calendar duration: {} days", end.calendar_days);
 //! ```
 
-use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use chrono::{Datelike, Days, Duration, NaiveDate, Weekday};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashSet, str::FromStr};
+use std::{
+    collections::{BTreeSet, HashSet},
+    fmt,
+    ops::{Bound, RangeBounds, RangeInclusive},
+    str::FromStr,
+    sync::Arc,
+};
+
+mod accrual;
+mod adjust;
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
+mod cache;
+mod calendar_like;
+mod calendar_set;
+mod category;
+mod compact;
+mod config;
+mod contents;
+mod counter;
+mod day_count;
+mod delta;
+mod error;
+mod export;
+mod expr;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod fingerprint;
+mod fiscal;
+mod frozen;
+mod global;
+mod half_day;
+mod hours;
+mod html;
+mod humanize;
+#[cfg(feature = "ics")]
+mod ics;
+mod import;
+mod invoice;
+mod iso;
+#[cfg(feature = "jiff")]
+mod jiff;
+mod layer_config;
+mod layered;
+mod locale;
+mod memo;
+mod non_working;
+mod ordinal;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod payroll;
+mod planning;
+#[cfg(feature = "presets")]
+mod presets;
+mod registry;
+mod relative;
+mod rules;
+mod shift;
+mod sla;
+mod span;
+mod system;
+mod team;
+mod tenor;
+#[cfg(test)]
+mod test_support;
+#[cfg(feature = "time")]
+mod time;
+mod today;
+mod translate;
+#[cfg(feature = "tz")]
+mod tz;
+mod view;
+#[cfg(feature = "wasm")]
+mod wasm;
+mod week;
+
+use category::HolidayCategories;
+use half_day::PartialHolidays;
+use hours::HoursPerDay;
+use memo::MonthMemo;
+use rules::RuleSet;
+use week::{WeekChanges, WorkWeek};
+
+pub use accrual::{AccrualRate, ProrationResult, ACCRUAL_DECIMALS};
+pub use adjust::{adjust_joint, AdjustmentConvention, RollConvention};
+pub use cache::YearCache;
+pub use calendar_like::CalendarLike;
+pub use calendar_set::CalendarSet;
+pub use category::CategoryFilter;
+#[cfg(feature = "tz")]
+pub use chrono_tz;
+pub use compact::CompactCalendar;
+pub use config::{ConfigWarning, ConfigWarningKind, WorkCalendarConfig};
+pub use contents::{HolidayIter, Holidays, WorkWeekdays, WorkingDateRange, WorkingDates};
+pub use counter::RangeCounter;
+pub use day_count::DayCount;
+pub use error::WorkCalendarError;
+pub use export::JsonDateStyle;
+pub use fiscal::{FiscalCalendar, FiscalPattern, FiscalYearEnd};
+pub use frozen::FrozenCalendar;
+pub use global::{compute_end_date, global, set_global_calendar, work_days_between};
+pub use half_day::DayHalf;
+pub use hours::{CapacityCalendar, HoursResult, DEFAULT_HOURS_PER_DAY, HOURS_EPSILON};
+pub use html::HtmlOptions;
+#[cfg(feature = "ics")]
+pub use ics::IcsOptions;
+pub use import::{ImportFormat, ImportReport, MalformedLine, MALFORMED_SAMPLE_LIMIT};
+pub use invoice::PaymentTerm;
+pub use layer_config::CalendarResolver;
+pub use layered::{CalendarLayer, LayerChanges, LayeredCalendar};
+pub use locale::Locale;
+pub use non_working::NonWorkingReason;
+pub use ordinal::DEFAULT_ORDINAL_EPOCH;
+pub use planning::{
+    BufferedResult, Rounding, ScheduledTask, TaskSpec, WindowConstraints, WorkPeriod,
+    MAX_BUFFER_FRACTION,
+};
+#[cfg(feature = "presets")]
+pub use presets::Region;
+pub use registry::{CalendarRegistry, DuplicatePolicy};
+pub use rules::{Holiday, HolidayLayer, HolidayRule, DEFAULT_RULE_CACHE_CAPACITY};
+pub use shift::ShiftCalendar;
+pub use sla::SlaClock;
+pub use span::{BoundWorkDays, WorkDays};
+pub use system::{CalendarSystem, Gregorian};
+pub use team::{availability_between, availability_on, team_capacity, CapacityReport};
+pub use today::{SystemToday, Today, TodayCalendar};
+pub use translate::{translate_duration, translate_duration_with_end};
+pub use view::CalendarView;
+#[cfg(feature = "wasm")]
+pub use wasm::JsWorkCalendar;
+
+/// The longest run of consecutive non-working days a search will scan before giving up.
+///
+/// Computations that look for working dates return
+/// [`WorkCalendarError::NoWorkingDaysInHorizon`] once this many calendar days have
+/// passed without finding one, instead of searching forever.
+pub const SEARCH_HORIZON_DAYS: i64 = 3660;
 
 /// Represents a work calendar with customizable work days and holidays.
-#[derive(Debug, Serialize, Deserialize, Default)]
+///
+/// Cloning is cheap: the name, work days, and holidays are shared between clones and
+/// copied only when a clone is modified, so handing a copy to every task or thread
+/// costs a few reference-count increments. Clones still behave as independent values.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WorkCalendar {
-    work_days: HashSet<Weekday>,
-    holidays: HashSet<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<Arc<str>>,
+    work_days: WorkWeek,
+    #[serde(default, skip_serializing_if = "is_empty_map")]
+    work_days_effective: Arc<WeekChanges>,
+    holidays: Arc<BTreeSet<NaiveDate>>,
+    #[serde(
+        default,
+        rename = "extra_work_days",
+        alias = "working_dates",
+        skip_serializing_if = "is_empty_set"
+    )]
+    working_dates: Arc<BTreeSet<NaiveDate>>,
+    #[serde(
+        default,
+        skip_serializing_if = "has_no_partial_holidays",
+        serialize_with = "half_day::serialize_partial_holidays",
+        deserialize_with = "half_day::deserialize_partial_holidays"
+    )]
+    partial_holidays: Arc<PartialHolidays>,
+    #[serde(default, skip_serializing_if = "RuleSet::is_empty")]
+    recurring_holidays: RuleSet,
+    #[serde(default, skip_serializing_if = "has_no_categories")]
+    holiday_categories: Arc<HolidayCategories>,
+    #[serde(
+        default = "default_ordinal_epoch",
+        skip_serializing_if = "is_default_ordinal_epoch"
+    )]
+    ordinal_epoch: NaiveDate,
+    #[serde(
+        default,
+        skip_serializing_if = "HoursPerDay::is_default",
+        deserialize_with = "hours::deserialize_hours_per_day"
+    )]
+    hours_per_day: HoursPerDay,
+    #[serde(skip)]
+    month_memo: MonthMemo,
+}
+
+fn is_empty_set(set: &Arc<BTreeSet<NaiveDate>>) -> bool {
+    set.is_empty()
+}
+
+fn is_empty_map(map: &Arc<WeekChanges>) -> bool {
+    map.is_empty()
+}
+
+fn has_no_partial_holidays(holidays: &Arc<PartialHolidays>) -> bool {
+    holidays.is_empty()
+}
+
+fn has_no_categories(categories: &Arc<HolidayCategories>) -> bool {
+    categories.is_empty()
+}
+
+fn default_ordinal_epoch() -> NaiveDate {
+    DEFAULT_ORDINAL_EPOCH
+}
+
+fn is_default_ordinal_epoch(epoch: &NaiveDate) -> bool {
+    *epoch == DEFAULT_ORDINAL_EPOCH
+}
+
+/// The result of [`WorkCalendar::compute_end`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EndDate {
+    /// The date on which the last work day falls.
+    pub date: NaiveDate,
+    /// The number of calendar days from the start date to `date`.
+    pub calendar_days: u64,
+}
+
+impl EndDate {
+    /// Returns the calendar span from the start date to the end date as `chrono::Days`.
+    pub fn calendar_span(&self) -> Days {
+        Days::new(self.calendar_days)
+    }
+}
+
+/// A saved copy of a `WorkCalendar`'s state, created by [`WorkCalendar::snapshot`].
+///
+/// Holidays are shared with the calendar they were taken from until either side is
+/// modified, so taking a snapshot is cheap. Snapshots can be serialized to keep a
+/// draft across process restarts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CalendarSnapshot {
+    calendar: WorkCalendar,
 }
 
 impl FromStr for WorkCalendar {
-    type Err = Box<dyn std::error::Error>;
+    type Err = WorkCalendarError;
 
     /// Creates a `WorkCalendar` from a YAML or JSON string.
     ///
@@ -52,11 +291,36 @@ impl FromStr for WorkCalendar {
     ///
     /// * `s` - A string slice that holds the configuration in YAML or JSON format.
     ///
+    /// Dates listed under `extra_work_days` are working dates whatever their weekday,
+    /// as if passed to [`WorkCalendar::add_working_date`]. A date may not be listed under
+    /// both `holidays` and `extra_work_days`. Serializing a calendar writes them under
+    /// the same key.
+    ///
+    /// As a shorthand, the whole document may be a bare list of holiday dates, which
+    /// keeps the default Monday to Friday week. Every entry of the shorthand list must
+    /// be a `YYYY-MM-DD` or `YYYY/MM/DD` date. Serializing always writes the keyed form, and
+    /// [`WorkCalendar::to_yaml_string`] and [`WorkCalendar::to_json_string`] write a
+    /// calendar back in its canonical form.
+    ///
+    /// # Errors
+    ///
+    /// A document that does not parse fails with a [`WorkCalendarError::ConfigSyntax`]
+    /// giving the line and column, and for a bad entry of a list of holiday dates its
+    /// index and value; [`WorkCalendarError::render`] quotes the offending line. This
+    /// includes any entry of the shorthand list, `holidays` or `extra_work_days` that
+    /// is not a date. A document that parses but does not describe a calendar, such as
+    /// one with an empty `work_days` list, fails with a
+    /// [`WorkCalendarError::InvalidConfig`] saying why. So does any entry of
+    /// `work_days` that is not a weekday name: the message quotes the entry and names
+    /// its key, as does an `extra_work_days` date that is also a holiday. Use
+    /// [`WorkCalendar::from_str_lossy`] to skip such entries, and bad entries of the
+    /// keyed date lists, with a warning instead.
+    ///
     /// # Examples
     ///
     /// ```
     /// use std::str::FromStr;
-    /// use workdays::WorkCalendar;
+    /// use workdays::{WorkCalendar, WorkCalendarError};
     ///
     /// let config = r#"
     /// work_days:
@@ -70,14 +334,25 @@ impl FromStr for WorkCalendar {
     /// let calendar = WorkCalendar::from_str(config).unwrap();
     /// assert!(calendar.is_work_day(&chrono::Weekday::Mon));
     /// assert!(!calendar.is_work_day(&chrono::Weekday::Thu));
+    ///
+    /// let shorthand = WorkCalendar::from_str("- 2023-12-25\n- 2023-12-26\n").unwrap();
+    /// assert!(shorthand.is_holiday(&chrono::NaiveDate::from_ymd_opt(2023, 12, 26).unwrap()));
+    /// assert!(shorthand.is_work_day(&chrono::Weekday::Fri));
+    ///
+    /// assert!(matches!(
+    ///     WorkCalendar::from_str("work_days: []"),
+    ///     Err(WorkCalendarError::InvalidConfig { .. })
+    /// ));
     /// ```
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let config: WorkCalendarConfig = if s.trim_start().starts_with('{') {
-            serde_json::from_str(s)?
-        } else {
-            serde_yaml::from_str(s)?
-        };
-        Ok(Self::from(config))
+        Self::try_from(config::parse_document(s, false)?)
+    }
+}
+
+impl Default for WorkCalendar {
+    /// Same as [`WorkCalendar::new`]: Monday to Friday with no holidays.
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -106,13 +381,248 @@ impl WorkCalendar {
         .cloned()
         .collect();
         WorkCalendar {
+            name: None,
             work_days,
-            holidays: HashSet::new(),
+            work_days_effective: Arc::new(WeekChanges::new()),
+            holidays: Arc::new(BTreeSet::new()),
+            working_dates: Arc::new(BTreeSet::new()),
+            partial_holidays: Arc::default(),
+            recurring_holidays: RuleSet::default(),
+            holiday_categories: Arc::default(),
+            ordinal_epoch: DEFAULT_ORDINAL_EPOCH,
+            hours_per_day: HoursPerDay::default(),
+            month_memo: MonthMemo::default(),
+        }
+    }
+
+    /// Sets the name used to identify this calendar in errors and reports.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The calendar's name (e.g., "US" or "team-berlin").
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// assert_eq!(calendar.name(), None);
+    /// calendar.set_name("US");
+    /// assert_eq!(calendar.name(), Some("US"));
+    /// ```
+    pub fn set_name(&mut self, name: impl Into<String>) {
+        self.name = Some(Arc::from(name.into()));
+    }
+
+    /// Returns the calendar's name, if one has been set.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Merges another calendar into this one.
+    ///
+    /// A date is non-working in the merged calendar if it is non-working in either
+    /// calendar: work days are intersected, holidays are combined, and working-date
+    /// overrides are kept only where both calendars work. Work weeks set with
+    /// [`WorkCalendar::set_work_days_effective`] are intersected on every date either
+    /// calendar changes its week. Names are joined with `+`
+    /// (e.g., "US+DE").
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The calendar to merge into this one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::{NaiveDate, Weekday};
+    ///
+    /// let mut us = WorkCalendar::new();
+    /// us.set_name("US");
+    /// us.add_holiday(NaiveDate::from_ymd_opt(2023, 7, 4).unwrap());
+    ///
+    /// let mut de = WorkCalendar::new();
+    /// de.set_name("DE");
+    /// de.remove_work_day(&Weekday::Fri);
+    /// de.add_holiday(NaiveDate::from_ymd_opt(2023, 10, 3).unwrap());
+    ///
+    /// us.merge(&de);
+    /// assert_eq!(us.name(), Some("US+DE"));
+    /// assert!(!us.is_work_day(&Weekday::Fri));
+    /// assert!(us.is_holiday(&NaiveDate::from_ymd_opt(2023, 7, 4).unwrap()));
+    /// assert!(us.is_holiday(&NaiveDate::from_ymd_opt(2023, 10, 3).unwrap()));
+    /// ```
+    pub fn merge(&mut self, other: &WorkCalendar) {
+        let working_dates: BTreeSet<NaiveDate> = self
+            .working_dates
+            .union(&other.working_dates)
+            .filter(|date| self.is_working_date(date) && other.is_working_date(date))
+            .copied()
+            .collect();
+        self.name = match (self.name.take(), &other.name) {
+            (Some(a), Some(b)) => Some(Arc::from(format!("{}+{}", a, b))),
+            (a, b) => a.or_else(|| b.clone()),
+        };
+        if !self.holiday_categories.is_empty() || !other.holiday_categories.is_empty() {
+            self.holiday_categories = Arc::new(self.merged_categories(other));
+        }
+        if !self.work_days_effective.is_empty() || !other.work_days_effective.is_empty() {
+            let changes: WeekChanges = self
+                .work_days_effective
+                .keys()
+                .chain(other.work_days_effective.keys())
+                .map(|date| {
+                    let mut week = self.work_week_on(*date).clone();
+                    week.retain_common(other.work_week_on(*date));
+                    (*date, week)
+                })
+                .collect();
+            self.work_days_effective = Arc::new(changes);
+        }
+        if !self.work_days.ptr_eq(&other.work_days) {
+            self.work_days.retain_common(&other.work_days);
+        }
+        if !Arc::ptr_eq(&self.holidays, &other.holidays) {
+            Arc::make_mut(&mut self.holidays).extend(other.holidays.iter().copied());
+        }
+        if working_dates != *self.working_dates {
+            self.working_dates = Arc::new(working_dates);
+        }
+        if !Arc::ptr_eq(&self.partial_holidays, &other.partial_holidays) {
+            Arc::make_mut(&mut self.partial_holidays).extend(
+                other
+                    .partial_holidays
+                    .iter()
+                    .map(|(&date, &half)| (date, half)),
+            );
+        }
+        for rule in other.recurring_holidays.rules() {
+            self.recurring_holidays.insert(*rule);
+        }
+        for layer in other.recurring_holidays.layers() {
+            self.recurring_holidays.insert_layer(*layer);
+        }
+        self.month_memo.invalidate();
+    }
+
+    /// Captures the current state of the calendar.
+    ///
+    /// The snapshot shares storage with the calendar, so this does not copy
+    /// the holiday set. Later edits to the calendar do not affect the snapshot.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// let snapshot = calendar.snapshot();
+    ///
+    /// let holiday = NaiveDate::from_ymd_opt(2023, 12, 25).unwrap();
+    /// calendar.add_holiday(holiday);
+    /// assert!(calendar.is_holiday(&holiday));
+    ///
+    /// calendar.restore(&snapshot);
+    /// assert!(!calendar.is_holiday(&holiday));
+    /// ```
+    pub fn snapshot(&self) -> CalendarSnapshot {
+        CalendarSnapshot {
+            calendar: self.clone(),
         }
     }
 
+    /// Reverts the calendar to the state captured in `snapshot`.
+    ///
+    /// # Arguments
+    ///
+    /// * `snapshot` - A snapshot previously returned by [`WorkCalendar::snapshot`].
+    pub fn restore(&mut self, snapshot: &CalendarSnapshot) {
+        *self = snapshot.calendar.clone();
+    }
+
+    /// Computes the end date and calendar span given a start date and number of work days.
+    ///
+    /// The start date counts as the first work day when it is a working date, so the end
+    /// date is the `days_worked`-th working date on or after `start_date`: one day from
+    /// a working date ends on that date, and one day from a weekend or holiday ends on
+    /// the next working date. Zero days end on `start_date` if it is a working date; from
+    /// any other date they are an error, since there is no working date to end on. This
+    /// is the preferred form of [`WorkCalendar::compute_end_date`]: the calendar span is
+    /// reported as a whole number of days rather than a `chrono::Duration`.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_date` - The starting date.
+    /// * `days_worked` - Number of work days to add.
+    ///
+    /// # Errors
+    ///
+    /// * [`WorkCalendarError::NegativeDaysWorked`] if `days_worked` is negative.
+    /// * [`WorkCalendarError::NoWorkDaysDefined`] if the calendar has no work days.
+    /// * [`WorkCalendarError::NonWorkingStart`] if `days_worked` is zero and
+    ///   `start_date` is not a working date.
+    /// * [`WorkCalendarError::NoWorkingDaysInHorizon`] if [`SEARCH_HORIZON_DAYS`]
+    ///   consecutive days pass without a working date (e.g., a long run of holidays).
+    /// * [`WorkCalendarError::DateOutOfRange`] if the end date would be later than
+    ///   `NaiveDate::MAX`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let start_date = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap();
+    /// let end = calendar.compute_end(start_date, 5).unwrap();
+    ///
+    /// assert_eq!(end.date, NaiveDate::from_ymd_opt(2023, 8, 25).unwrap());
+    /// assert_eq!(end.calendar_days, 4);
+    /// assert_eq!(start_date + end.calendar_span(), end.date);
+    /// ```
+    pub fn compute_end(
+        &self,
+        start_date: NaiveDate,
+        days_worked: i64,
+    ) -> Result<EndDate, WorkCalendarError> {
+        self.compute_end_with(start_date, days_worked, &|_| true)
+    }
+
+    /// Like [`WorkCalendar::compute_end`], counting a holiday only if `observed`
+    /// accepts its date.
+    pub(crate) fn compute_end_with(
+        &self,
+        start_date: NaiveDate,
+        days_worked: i64,
+        observed: &dyn Fn(&NaiveDate) -> bool,
+    ) -> Result<EndDate, WorkCalendarError> {
+        // Skipping whole weeks assumes a single work week, so a calendar with
+        // effective-dated changes walks one day at a time. Unobserved holidays still
+        // stop a skip early, which only costs a few single-day steps.
+        let days_per_week = if self.work_days_effective.is_empty() {
+            self.work_days.days_per_week()
+        } else {
+            0
+        };
+        calendar_like::compute_end_by_weeks(
+            self,
+            |date| self.is_working_date_with(date, observed),
+            |day| self.is_work_day(&day),
+            days_per_week,
+            |date| self.next_exception(date),
+            start_date,
+            days_worked,
+        )
+    }
+
     /// Computes the end date and calendar duration given a start date and number of work days.
     ///
+    /// This returns the calendar span as a `chrono::Duration`; prefer
+    /// [`WorkCalendar::compute_end`], which reports it as a whole number of days.
+    ///
     /// # Arguments
     ///
     /// * `start_date` - The starting date.
@@ -122,6 +632,10 @@ impl WorkCalendar {
     ///
     /// A tuple containing the end date and the calendar duration, or an error if the input is invalid.
     ///
+    /// # Errors
+    ///
+    /// Same as [`WorkCalendar::compute_end`].
+    ///
     /// # Examples
     ///
     /// ```
@@ -139,33 +653,81 @@ impl WorkCalendar {
         &self,
         start_date: NaiveDate,
         days_worked: i64,
-    ) -> Result<(NaiveDate, Duration), String> {
-        if days_worked < 0 {
-            return Err("days_worked must be non-negative".to_string());
-        }
-
-        if self.work_days.is_empty() {
-            return Err("No work days defined".to_string());
-        }
-
-        let mut current_date = start_date;
-        let mut remaining_days = days_worked;
+    ) -> Result<(NaiveDate, Duration), WorkCalendarError> {
+        let end = self.compute_end(start_date, days_worked)?;
+        Ok((end.date, Duration::days(end.calendar_days as i64)))
+    }
 
-        // If the start date is a work day, count it
-        if self.is_work_day(&current_date.weekday()) && !self.is_holiday(&current_date) {
-            remaining_days -= 1;
+    /// Computes the start date and calendar duration given an end date and number of
+    /// work days, walking backwards from a deadline.
+    ///
+    /// This mirrors [`WorkCalendar::compute_end_date`]: the end date counts as the last
+    /// work day when it is a working date, so the start date is the `days_worked`-th
+    /// working date on or before `end_date`. One day ending on a working date starts on
+    /// that date, and one day ending on a weekend or holiday starts on the previous
+    /// working date. Zero days start on `end_date` if it is a working date; from any
+    /// other date they are an error. For any working date `start`,
+    /// `compute_start_date(compute_end_date(start, n)?.0, n)` starts on `start`.
+    ///
+    /// # Arguments
+    ///
+    /// * `end_date` - The date by which the work must be done.
+    /// * `days_worked` - Number of work days to take away.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the start date and the calendar duration from `end_date` back
+    /// to it, which is zero or negative, so `end_date + duration` is the start date.
+    ///
+    /// # Errors
+    ///
+    /// * [`WorkCalendarError::NegativeDaysWorked`] if `days_worked` is negative.
+    /// * [`WorkCalendarError::NoWorkDaysDefined`] if the calendar has no work days.
+    /// * [`WorkCalendarError::NonWorkingStart`] if `days_worked` is zero and `end_date`
+    ///   is not a working date.
+    /// * [`WorkCalendarError::NoWorkingDaysInHorizon`] if [`SEARCH_HORIZON_DAYS`]
+    ///   consecutive days pass without a working date.
+    /// * [`WorkCalendarError::DateOutOfRange`] if the start date would be earlier than
+    ///   `NaiveDate::MIN`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::{Duration, NaiveDate};
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 9, 4).unwrap()); // Monday
+    /// let deadline = NaiveDate::from_ymd_opt(2023, 9, 8).unwrap(); // Friday
+    ///
+    /// let (start, duration) = calendar.compute_start_date(deadline, 5).unwrap();
+    /// assert_eq!(start, NaiveDate::from_ymd_opt(2023, 9, 1).unwrap());
+    /// assert_eq!(duration, Duration::days(-7));
+    /// assert_eq!(calendar.compute_end_date(start, 5).unwrap().0, deadline);
+    /// ```
+    pub fn compute_start_date(
+        &self,
+        end_date: NaiveDate,
+        days_worked: i64,
+    ) -> Result<(NaiveDate, Duration), WorkCalendarError> {
+        let name = || self.name().map(String::from);
+        if days_worked < 0 {
+            return Err(WorkCalendarError::NegativeDaysWorked { calendar: name() });
         }
-
-        while remaining_days > 0 {
-            current_date += Duration::days(1);
-
-            if self.is_work_day(&current_date.weekday()) && !self.is_holiday(&current_date) {
-                remaining_days -= 1;
-            }
+        if !self.has_any_work_days() {
+            return Err(WorkCalendarError::NoWorkDaysDefined { calendar: name() });
         }
-
-        let calendar_duration = current_date.signed_duration_since(start_date);
-        Ok((current_date, calendar_duration))
+        let start_date = if self.is_working_date(&end_date) {
+            self.try_add_work_days(end_date, 1 - days_worked.max(1))?
+        } else if days_worked == 0 {
+            return Err(WorkCalendarError::NonWorkingStart {
+                calendar: name(),
+                date: end_date,
+            });
+        } else {
+            self.try_add_work_days(end_date, -days_worked)?
+        };
+        Ok((start_date, start_date - end_date))
     }
 
     /// Adds a work day to the calendar.
@@ -186,6 +748,7 @@ impl WorkCalendar {
     /// ```
     pub fn add_work_day(&mut self, day: Weekday) {
         self.work_days.insert(day);
+        self.month_memo.invalidate();
     }
 
     /// Removes a work day from the calendar.
@@ -205,7 +768,8 @@ impl WorkCalendar {
     /// assert!(!calendar.is_work_day(&Weekday::Fri));
     /// ```
     pub fn remove_work_day(&mut self, day: &Weekday) {
-        self.work_days.remove(day);
+        self.work_days.remove(*day);
+        self.month_memo.invalidate();
     }
 
     /// Adds a holiday to the calendar.
@@ -226,7 +790,9 @@ impl WorkCalendar {
     /// assert!(calendar.is_holiday(&holiday));
     /// ```
     pub fn add_holiday(&mut self, date: NaiveDate) {
-        self.holidays.insert(date);
+        category::clear_date(&mut self.holiday_categories, &date);
+        Arc::make_mut(&mut self.holidays).insert(date);
+        self.month_memo.invalidate();
     }
 
     /// Removes a holiday from the calendar.
@@ -248,83 +814,136 @@ impl WorkCalendar {
     /// assert!(!calendar.is_holiday(&holiday));
     /// ```
     pub fn remove_holiday(&mut self, date: &NaiveDate) {
-        self.holidays.remove(date);
+        category::clear_date(&mut self.holiday_categories, date);
+        Arc::make_mut(&mut self.holidays).remove(date);
+        self.month_memo.invalidate();
     }
 
-    /// Set work days from a comma-separated string.
+    /// Adds every date from `start` to `end`, inclusive, as a holiday.
     ///
-    /// This method overrides all existing work days with the ones specified in the input string.
-    /// Day names are case-insensitive and can be full names (e.g., "Monday") or abbreviations (e.g., "Mon").
+    /// Each date is stored like one added with [`WorkCalendar::add_holiday`], so a
+    /// range costs a few bytes per day; a closure of several years is fine, but a
+    /// range spanning millennia is better expressed another way.
     ///
     /// # Arguments
     ///
-    /// * `days` - A comma-separated string of day names (e.g., "mon,tue,Wednesday,thu,friday")
+    /// * `start` - The first date of the range.
+    /// * `end` - The last date of the range.
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// * `Ok(())` if successful
-    /// * `Err(String)` if the input string contains invalid day names
+    /// [`WorkCalendarError::InvalidHolidayRange`] if `end` is before `start`.
     ///
     /// # Examples
     ///
     /// ```
     /// use workdays::WorkCalendar;
-    /// use chrono::Weekday;
+    /// use chrono::NaiveDate;
     ///
     /// let mut calendar = WorkCalendar::new();
-    /// calendar.set_work_days("Mon,Wed,Fri").unwrap();
-    /// assert!(calendar.is_work_day(&Weekday::Mon));
-    /// assert!(calendar.is_work_day(&Weekday::Wed));
-    /// assert!(calendar.is_work_day(&Weekday::Fri));
-    /// assert!(!calendar.is_work_day(&Weekday::Tue));
+    /// let start = NaiveDate::from_ymd_opt(2024, 12, 23).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2025, 1, 3).unwrap();
+    /// calendar.add_holiday_range(start, end).unwrap();
+    /// assert_eq!(calendar.holidays_in_range(..).count(), 12);
+    /// assert_eq!(calendar.work_days_between(start, end), 0);
     /// ```
-    pub fn set_work_days(&mut self, days: &str) -> Result<(), String> {
-        let new_work_days: HashSet<Weekday> = days
-            .split(',')
-            .filter_map(|day| parse_weekday(day.trim()))
-            .collect();
-
-        if new_work_days.is_empty() {
-            return Err("No valid work days provided".to_string());
-        }
-
-        self.work_days = new_work_days;
+    pub fn add_holiday_range(
+        &mut self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<(), WorkCalendarError> {
+        self.check_holiday_range(start, end)?;
+        category::retain_dates(&mut self.holiday_categories, |date| {
+            !(start..=end).contains(date)
+        });
+        // `NaiveDate::iter_days` stops before `NaiveDate::MAX`, so step by hand.
+        let dates = std::iter::successors(Some(start), |date| date.succ_opt());
+        Arc::make_mut(&mut self.holidays).extend(dates.take_while(|&date| date <= end));
+        self.month_memo.invalidate();
         Ok(())
     }
 
-    /// Checks if a given day is a work day.
+    /// Removes every holiday from `start` to `end`, inclusive, however it was added.
+    ///
+    /// Holidays outside the range are kept, even if they were added as part of a
+    /// larger range. Like [`WorkCalendar::remove_holiday`], this only affects explicit
+    /// holidays, not recurring rules or holiday layers.
     ///
     /// # Arguments
     ///
-    /// * `day` - The `Weekday` to check.
+    /// * `start` - The first date of the range.
+    /// * `end` - The last date of the range.
     ///
     /// # Returns
     ///
-    /// `true` if the day is a work day, `false` otherwise.
+    /// The number of holidays removed.
+    ///
+    /// # Errors
+    ///
+    /// [`WorkCalendarError::InvalidHolidayRange`] if `end` is before `start`.
     ///
     /// # Examples
     ///
     /// ```
     /// use workdays::WorkCalendar;
-    /// use chrono::Weekday;
+    /// use chrono::NaiveDate;
     ///
-    /// let calendar = WorkCalendar::new();
-    /// assert!(calendar.is_work_day(&Weekday::Mon));
-    /// assert!(!calendar.is_work_day(&Weekday::Sat));
+    /// let mut calendar = WorkCalendar::new();
+    /// let date = |day| NaiveDate::from_ymd_opt(2024, 7, day).unwrap();
+    /// calendar.add_holiday_range(date(1), date(12)).unwrap();
+    /// assert_eq!(calendar.remove_holiday_range(date(8), date(31)).unwrap(), 5);
+    /// assert!(calendar.is_holiday(&date(5)));
+    /// assert!(!calendar.is_holiday(&date(8)));
     /// ```
-    pub fn is_work_day(&self, day: &Weekday) -> bool {
-        self.work_days.contains(day)
+    pub fn remove_holiday_range(
+        &mut self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<usize, WorkCalendarError> {
+        self.check_holiday_range(start, end)?;
+        let removed: Vec<NaiveDate> = self.holidays.range(start..=end).copied().collect();
+        if removed.is_empty() {
+            return Ok(0);
+        }
+        category::retain_dates(&mut self.holiday_categories, |date| {
+            !(start..=end).contains(date)
+        });
+        let holidays = Arc::make_mut(&mut self.holidays);
+        for date in &removed {
+            holidays.remove(date);
+        }
+        self.month_memo.invalidate();
+        Ok(removed.len())
     }
 
-    /// Checks if a given date is a holiday.
+    fn check_holiday_range(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<(), WorkCalendarError> {
+        if end < start {
+            return Err(WorkCalendarError::InvalidHolidayRange {
+                calendar: self.name().map(String::from),
+                start,
+                end,
+            });
+        }
+        Ok(())
+    }
+
+    /// Removes every explicit holiday before `cutoff`.
+    ///
+    /// Recurring rules, holiday layers, and partial holidays are left untouched, as
+    /// are the categories of the holidays that remain, so every computation over
+    /// dates on or after `cutoff` gives the same result as before.
     ///
     /// # Arguments
     ///
-    /// * `date` - The `NaiveDate` to check.
+    /// * `cutoff` - The first date whose holidays are kept.
     ///
     /// # Returns
     ///
-    /// `true` if the date is a holiday, `false` otherwise.
+    /// The number of holidays removed.
     ///
     /// # Examples
     ///
@@ -333,24 +952,29 @@ impl WorkCalendar {
     /// use chrono::NaiveDate;
     ///
     /// let mut calendar = WorkCalendar::new();
-    /// let holiday = NaiveDate::from_ymd_opt(2023, 12, 25).unwrap();
-    /// calendar.add_holiday(holiday);
-    /// assert!(calendar.is_holiday(&holiday));
+    /// calendar.add_holiday(NaiveDate::from_ymd_opt(2015, 12, 25).unwrap());
+    /// calendar.add_holiday(NaiveDate::from_ymd_opt(2024, 12, 25).unwrap());
+    /// let cutoff = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+    /// assert_eq!(calendar.retain_holidays_from(cutoff), 1);
+    /// assert_eq!(calendar.holidays_in_range(..).count(), 1);
     /// ```
-    pub fn is_holiday(&self, date: &NaiveDate) -> bool {
-        self.holidays.contains(date)
+    pub fn retain_holidays_from(&mut self, cutoff: NaiveDate) -> usize {
+        self.retain_holidays_in(cutoff..)
     }
 
-    /// Calculates the number of work days between two dates (inclusive).
+    /// Removes every explicit holiday outside `range`.
+    ///
+    /// Like [`WorkCalendar::retain_holidays_from`], this leaves recurring rules,
+    /// holiday layers, partial holidays, and the categories of the remaining holidays
+    /// untouched.
     ///
     /// # Arguments
     ///
-    /// * `start_date` - The starting date.
-    /// * `end_date` - The ending date.
+    /// * `range` - The dates whose holidays are kept, e.g. `start..=end`.
     ///
     /// # Returns
     ///
-    /// The number of work days between the two dates (inclusive).
+    /// The number of holidays removed.
     ///
     /// # Examples
     ///
@@ -358,223 +982,3273 @@ impl WorkCalendar {
     /// use workdays::WorkCalendar;
     /// use chrono::NaiveDate;
     ///
-    /// let calendar = WorkCalendar::new();
-    /// let start_date = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap(); // Monday
-    /// let end_date = NaiveDate::from_ymd_opt(2023, 8, 25).unwrap();   // Friday
-    /// assert_eq!(calendar.work_days_between(start_date, end_date), 5);
+    /// let mut calendar = WorkCalendar::new();
+    /// for year in 2015..2030 {
+    ///     calendar.add_holiday(NaiveDate::from_ymd_opt(year, 12, 25).unwrap());
+    /// }
+    /// let start = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+    /// assert_eq!(calendar.retain_holidays_in(start..=end), 10);
+    /// assert_eq!(calendar.holidays_in_range(..).count(), 5);
     /// ```
-    pub fn work_days_between(&self, start_date: NaiveDate, end_date: NaiveDate) -> i64 {
-        let mut work_days = 0;
-        let mut current_date = start_date;
-
-        while current_date <= end_date {
-            if self.work_days.contains(&current_date.weekday()) && !self.is_holiday(&current_date) {
-                work_days += 1;
-            }
-            current_date += Duration::days(1);
+    pub fn retain_holidays_in<R: RangeBounds<NaiveDate>>(&mut self, range: R) -> usize {
+        let removed = self
+            .holidays
+            .iter()
+            .filter(|date| !range.contains(date))
+            .count();
+        if removed == 0 {
+            return 0;
         }
+        category::retain_dates(&mut self.holiday_categories, |date| range.contains(date));
+        Arc::make_mut(&mut self.holidays).retain(|date| range.contains(date));
+        self.month_memo.invalidate();
+        removed
+    }
 
-        work_days
+    /// Marks a specific date as a working date, even if its weekday is not a work day.
+    ///
+    /// A holiday on the same date takes precedence: the date stays non-working until
+    /// the holiday is removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The `NaiveDate` to treat as a working date.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// let saturday = NaiveDate::from_ymd_opt(2023, 12, 30).unwrap();
+    /// calendar.add_working_date(saturday);
+    /// assert!(calendar.is_working_date(&saturday));
+    /// ```
+    pub fn add_working_date(&mut self, date: NaiveDate) {
+        Arc::make_mut(&mut self.working_dates).insert(date);
+        self.month_memo.invalidate();
     }
-}
 
-#[derive(Debug, Serialize, Deserialize, Default)]
-struct WorkCalendarConfig {
-    work_days: Option<Vec<String>>,
-    holidays: Option<Vec<String>>,
-}
+    /// Removes a working-date override added with [`WorkCalendar::add_working_date`].
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The `NaiveDate` whose override should be removed.
+    pub fn remove_working_date(&mut self, date: &NaiveDate) {
+        Arc::make_mut(&mut self.working_dates).remove(date);
+        self.month_memo.invalidate();
+    }
+
+    /// Adds a holiday that recurs every year.
+    ///
+    /// Rule-generated holidays are honored by [`WorkCalendar::is_holiday`] and every
+    /// computation, but are not listed by [`WorkCalendar::holidays_in_range`]. Their
+    /// dates are evaluated lazily per year and cached; see
+    /// [`WorkCalendar::set_rule_cache_capacity`].
+    ///
+    /// # Arguments
+    ///
+    /// * `rule` - The rule to add. Adding a rule that is already present has no effect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{HolidayRule, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// calendar.add_recurring_holiday(HolidayRule::FixedDate { month: 12, day: 25 });
+    /// assert!(calendar.is_holiday(&NaiveDate::from_ymd_opt(2023, 12, 25).unwrap()));
+    /// assert!(calendar.is_holiday(&NaiveDate::from_ymd_opt(2031, 12, 25).unwrap()));
+    /// ```
+    pub fn add_recurring_holiday(&mut self, rule: HolidayRule) {
+        category::clear_rule(&mut self.holiday_categories, &rule);
+        if self.recurring_holidays.insert(rule) {
+            self.month_memo.invalidate();
+        }
+    }
+
+    /// Removes a recurring holiday rule.
+    ///
+    /// # Arguments
+    ///
+    /// * `rule` - The rule to remove.
+    pub fn remove_recurring_holiday(&mut self, rule: &HolidayRule) {
+        category::clear_rule(&mut self.holiday_categories, rule);
+        if self.recurring_holidays.remove(rule) {
+            self.month_memo.invalidate();
+        }
+    }
+
+    /// Returns the recurring holiday rules, in the order they were added.
+    pub fn recurring_holidays(&self) -> &[HolidayRule] {
+        self.recurring_holidays.rules()
+    }
+
+    /// Adds a holiday that only applies within an effective window.
+    ///
+    /// Use this when holiday policy changes over time, so one calendar can hold every
+    /// version of it: a holiday added from 2022 is a holiday in 2022 and later years
+    /// only, and one dropped in 2024 applies up to the end of 2023. Each date is
+    /// checked against the windows of the layers that produce it. Like recurring
+    /// holidays, layered holidays are honored by [`WorkCalendar::is_holiday`] and
+    /// every computation but are not listed by [`WorkCalendar::holidays_in_range`].
+    ///
+    /// # Arguments
+    ///
+    /// * `holiday` - A date or a [`HolidayRule`].
+    /// * `effective_from` - The first date on which the holiday applies.
+    /// * `effective_to` - The last date on which it applies, or `None` if it has no
+    ///   end.
+    ///
+    /// # Errors
+    ///
+    /// [`WorkCalendarError::InvalidEffectiveWindow`] if `effective_to` is before
+    /// `effective_from`. Adding a layer that is already present has no effect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{HolidayRule, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// let juneteenth = HolidayRule::FixedDate { month: 6, day: 19 };
+    /// let from = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+    /// calendar.add_holiday_effective(juneteenth, from, None).unwrap();
+    ///
+    /// assert!(!calendar.is_holiday(&NaiveDate::from_ymd_opt(2021, 6, 18).unwrap()));
+    /// assert!(calendar.is_holiday(&NaiveDate::from_ymd_opt(2023, 6, 19).unwrap()));
+    /// ```
+    pub fn add_holiday_effective(
+        &mut self,
+        holiday: impl Into<Holiday>,
+        effective_from: NaiveDate,
+        effective_to: Option<NaiveDate>,
+    ) -> Result<(), WorkCalendarError> {
+        if let Some(to) = effective_to.filter(|to| *to < effective_from) {
+            return Err(WorkCalendarError::InvalidEffectiveWindow {
+                from: effective_from,
+                to,
+            });
+        }
+        let layer = HolidayLayer {
+            holiday: holiday.into(),
+            effective_from,
+            effective_to,
+        };
+        if self.recurring_holidays.insert_layer(layer) {
+            self.month_memo.invalidate();
+        }
+        Ok(())
+    }
+
+    /// Removes a holiday layer.
+    ///
+    /// # Arguments
+    ///
+    /// * `layer` - The layer to remove.
+    pub fn remove_holiday_layer(&mut self, layer: &HolidayLayer) {
+        if self.recurring_holidays.remove_layer(layer) {
+            self.month_memo.invalidate();
+        }
+    }
+
+    /// Returns the holiday layers, in the order they were added.
+    pub fn holiday_layers(&self) -> &[HolidayLayer] {
+        self.recurring_holidays.layers()
+    }
+
+    /// Sets how many years of evaluated rule dates the calendar keeps cached.
+    ///
+    /// When the cache is full, the least recently used year is evicted, so queries
+    /// spanning thousands of years use bounded memory. A capacity of 0 disables the
+    /// cache. The default is [`DEFAULT_RULE_CACHE_CAPACITY`]. Every query that checks
+    /// rule dates goes through the cache, and any change to the rules or layers empties
+    /// it, so results are the same for every capacity.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The maximum number of cached years.
+    pub fn set_rule_cache_capacity(&mut self, capacity: usize) {
+        self.recurring_holidays.set_cache_capacity(capacity);
+    }
+
+    /// Checks if a given date has a working-date override.
+    ///
+    /// This only reports the override; use [`WorkCalendar::is_working_date`] to decide
+    /// whether the date is actually worked.
+    pub fn is_working_date_override(&self, date: &NaiveDate) -> bool {
+        self.working_dates.contains(date)
+    }
+
+    /// Set work days from a comma-separated string.
+    ///
+    /// This method overrides all existing work days with the ones specified in the input string.
+    /// Day names are case-insensitive and can be full names (e.g., "Monday") or abbreviations (e.g., "Mon").
+    /// The keywords `weekdays`, `weekend`, and `everyday` or `all` stand for Monday to
+    /// Friday, Saturday and Sunday, and the whole week, and combine with day names.
+    ///
+    /// # Arguments
+    ///
+    /// * `days` - A comma-separated string of day names (e.g., "mon,tue,Wednesday,thu,friday")
+    ///   or keywords (e.g., "weekdays,Sat")
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if at least one day name is valid; names that are not are ignored.
+    ///
+    /// # Errors
+    ///
+    /// [`WorkCalendarError::NoValidWorkDays`] if no entry names a weekday. The work
+    /// days are left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{WorkCalendar, WorkCalendarError};
+    /// use chrono::Weekday;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// calendar.set_work_days("Mon,Wed,Fri").unwrap();
+    /// assert!(calendar.is_work_day(&Weekday::Mon));
+    /// assert!(calendar.is_work_day(&Weekday::Wed));
+    /// assert!(calendar.is_work_day(&Weekday::Fri));
+    /// assert!(!calendar.is_work_day(&Weekday::Tue));
+    ///
+    /// assert!(matches!(
+    ///     calendar.set_work_days("Funday"),
+    ///     Err(WorkCalendarError::NoValidWorkDays { .. })
+    /// ));
+    /// ```
+    pub fn set_work_days(&mut self, days: &str) -> Result<(), WorkCalendarError> {
+        self.work_days = self.parse_work_week(days)?;
+        self.month_memo.invalidate();
+        Ok(())
+    }
+
+    /// Changes the work days from `from` onwards, keeping the earlier work week for
+    /// dates before it.
+    ///
+    /// Each call starts a new segment of the calendar's history. Every date-based
+    /// computation uses the work week in effect on the date being evaluated, so a
+    /// count or end date spanning a change uses each week on its own side of it. The
+    /// work week set with [`WorkCalendar::set_work_days`] and the other weekday
+    /// methods covers all dates before the earliest change. Calling this again with
+    /// the same `from` replaces that change.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The first date the new work week applies to.
+    /// * `days` - A comma-separated string of day names, as for
+    ///   [`WorkCalendar::set_work_days`].
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if at least one day name is valid; names that are not are ignored.
+    ///
+    /// # Errors
+    ///
+    /// [`WorkCalendarError::NoValidWorkDays`] if no entry names a weekday.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::{NaiveDate, Weekday};
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// let change = NaiveDate::from_ymd_opt(2025, 7, 1).unwrap();
+    /// calendar.set_work_days_effective(change, "Mon,Tue,Wed,Thu").unwrap();
+    ///
+    /// let friday_before = NaiveDate::from_ymd_opt(2025, 6, 27).unwrap();
+    /// let friday_after = NaiveDate::from_ymd_opt(2025, 7, 4).unwrap();
+    /// assert!(calendar.is_working_date(&friday_before));
+    /// assert!(!calendar.is_working_date(&friday_after));
+    /// assert_eq!(calendar.work_days_on(friday_after).len(), 4);
+    ///
+    /// // Five days in the week of June 23, then two four-day weeks.
+    /// let start = NaiveDate::from_ymd_opt(2025, 6, 23).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2025, 7, 11).unwrap();
+    /// assert_eq!(calendar.work_days_between(start, end), 5 + 1 + 3 + 4);
+    /// ```
+    pub fn set_work_days_effective(
+        &mut self,
+        from: NaiveDate,
+        days: &str,
+    ) -> Result<(), WorkCalendarError> {
+        let week = self.parse_work_week(days)?;
+        Arc::make_mut(&mut self.work_days_effective).insert(from, week);
+        self.month_memo.invalidate();
+        Ok(())
+    }
+
+    /// Returns the work days in effect on `date`, from Monday to Sunday.
+    ///
+    /// This is the work week of the latest [`WorkCalendar::set_work_days_effective`]
+    /// change on or before `date`, or the calendar's work days if there is none.
+    pub fn work_days_on(&self, date: NaiveDate) -> Vec<Weekday> {
+        let week = self.work_week_on(date);
+        (0..7)
+            .map(|n| Weekday::try_from(n).unwrap())
+            .filter(|day| week.contains(*day))
+            .collect()
+    }
+
+    /// Checks if a given day is a work day.
+    ///
+    /// This reports the calendar's work days, which precede any change made with
+    /// [`WorkCalendar::set_work_days_effective`]; use [`WorkCalendar::work_days_on`]
+    /// for the work days on a particular date.
+    ///
+    /// # Arguments
+    ///
+    /// * `day` - The `Weekday` to check.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the day is a work day, `false` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::Weekday;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// assert!(calendar.is_work_day(&Weekday::Mon));
+    /// assert!(!calendar.is_work_day(&Weekday::Sat));
+    /// ```
+    pub fn is_work_day(&self, day: &Weekday) -> bool {
+        self.work_days.contains(*day)
+    }
+
+    /// Checks if a given date is a holiday.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The `NaiveDate` to check.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the date is a holiday, `false` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// let holiday = NaiveDate::from_ymd_opt(2023, 12, 25).unwrap();
+    /// calendar.add_holiday(holiday);
+    /// assert!(calendar.is_holiday(&holiday));
+    /// ```
+    pub fn is_holiday(&self, date: &NaiveDate) -> bool {
+        self.holidays.contains(date) || self.recurring_holidays.contains(date)
+    }
+
+    /// Checks if a given date is a working date.
+    ///
+    /// A date is a working date if it is not a holiday and either its weekday is a work
+    /// day or it has a working-date override ([`WorkCalendar::add_working_date`]).
+    /// Holidays take precedence over overrides.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The `NaiveDate` to check.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// let holiday = NaiveDate::from_ymd_opt(2023, 12, 25).unwrap(); // Monday
+    /// calendar.add_holiday(holiday);
+    /// assert!(!calendar.is_working_date(&holiday));
+    /// assert!(calendar.is_working_date(&NaiveDate::from_ymd_opt(2023, 12, 26).unwrap()));
+    /// assert!(!calendar.is_working_date(&NaiveDate::from_ymd_opt(2023, 12, 30).unwrap()));
+    ///
+    /// calendar.add_working_date(holiday);
+    /// assert!(!calendar.is_working_date(&holiday));
+    /// ```
+    pub fn is_working_date(&self, date: &NaiveDate) -> bool {
+        self.is_working_date_in(self.work_week_on(*date), date, &|_| true)
+    }
+
+    /// Like [`WorkCalendar::is_working_date`], counting a holiday only if `observed`
+    /// accepts its date.
+    pub(crate) fn is_working_date_with(
+        &self,
+        date: &NaiveDate,
+        observed: &dyn Fn(&NaiveDate) -> bool,
+    ) -> bool {
+        self.is_working_date_in(self.work_week_on(*date), date, observed)
+    }
+
+    /// Checks if `date` is a working date when `week` is the work week in effect and
+    /// only the holidays `observed` accepts count.
+    ///
+    /// This is the predicate [`WorkCalendar::work_days_between`] counts and
+    /// [`WorkCalendar::counted_work_days`] filters by. `observed` is only asked about
+    /// dates that are holidays.
+    fn is_working_date_in(
+        &self,
+        week: &WorkWeek,
+        date: &NaiveDate,
+        observed: &dyn Fn(&NaiveDate) -> bool,
+    ) -> bool {
+        (week.contains(date.weekday()) || self.is_working_date_override(date))
+            && !(self.is_holiday(date) && observed(date))
+    }
+
+    /// Calculates the number of work days between two dates (inclusive).
+    ///
+    /// Full weeks are counted arithmetically, so the time taken depends on the number
+    /// of holidays and extra working dates in the range rather than on its length. An
+    /// `end_date` before `start_date` counts zero days.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_date` - The starting date.
+    /// * `end_date` - The ending date.
+    ///
+    /// # Returns
+    ///
+    /// The number of work days between the two dates (inclusive).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let start_date = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap(); // Monday
+    /// let end_date = NaiveDate::from_ymd_opt(2023, 8, 25).unwrap();   // Friday
+    /// assert_eq!(calendar.work_days_between(start_date, end_date), 5);
+    /// ```
+    pub fn work_days_between(&self, start_date: NaiveDate, end_date: NaiveDate) -> i64 {
+        self.work_days_between_with(start_date, end_date, &|_| true)
+    }
+
+    /// Like [`WorkCalendar::work_days_between`], counting a holiday only if `observed`
+    /// accepts its date.
+    pub(crate) fn work_days_between_with(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        observed: &dyn Fn(&NaiveDate) -> bool,
+    ) -> i64 {
+        self.week_segments(start_date, end_date)
+            .map(|(week, first, last)| self.work_days_between_in(week, first, last, observed))
+            .sum()
+    }
+
+    /// Iterates over the dates [`WorkCalendar::work_days_between`] counts.
+    ///
+    /// The iterator yields exactly `work_days_between(start_date, end_date)` dates, in
+    /// ascending order: the working dates from `start_date` to `end_date`, inclusive.
+    /// It can also be walked from the end with [`Iterator::rev`]. Like
+    /// [`WorkCalendar::work_days_between`], it yields nothing if `end_date` is before
+    /// `start_date`. For working dates with no end, use
+    /// [`WorkCalendar::iter_working_dates`].
+    ///
+    /// # Arguments
+    ///
+    /// * `start_date` - The starting date.
+    /// * `end_date` - The ending date.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the counted dates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::{Datelike, NaiveDate};
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 8, 23).unwrap());
+    /// let start_date = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap(); // Monday
+    /// let end_date = NaiveDate::from_ymd_opt(2023, 8, 27).unwrap(); // Sunday
+    /// let counted: Vec<u32> = calendar
+    ///     .counted_work_days(start_date, end_date)
+    ///     .map(|date| date.day())
+    ///     .collect();
+    /// assert_eq!(counted, [21, 22, 24, 25]);
+    /// assert_eq!(calendar.work_days_between(start_date, end_date), 4);
+    /// ```
+    pub fn counted_work_days(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> WorkingDateRange<'_> {
+        WorkingDateRange::new(self, start_date, end_date)
+    }
+
+    /// Counts the working dates from `start_date` to `end_date`, inclusive, as if
+    /// `week` were in effect throughout: the dates for which
+    /// [`WorkCalendar::is_working_date_in`] holds, computed without visiting each one.
+    fn work_days_between_in(
+        &self,
+        week: &WorkWeek,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        observed: &dyn Fn(&NaiveDate) -> bool,
+    ) -> i64 {
+        if start_date > end_date {
+            return 0;
+        }
+
+        let holidays_on_work_days = self
+            .holidays_in_range(start_date..=end_date)
+            .filter(|date| week.contains(date.weekday()) && observed(date))
+            .count() as i64;
+        let overrides_on_other_days = self
+            .working_dates
+            .range(start_date..=end_date)
+            .filter(|date| {
+                !week.contains(date.weekday()) && self.is_working_date_in(week, date, observed)
+            })
+            .count() as i64;
+        let rule_holidays_on_work_days =
+            self.recurring_holidays
+                .count_in_range(start_date, end_date, |date| {
+                    week.contains(date.weekday()) && !self.holidays.contains(date) && observed(date)
+                });
+
+        count_work_weekdays(
+            |day| week.contains(day),
+            week.days_per_week(),
+            start_date,
+            end_date,
+        ) - holidays_on_work_days
+            - rule_holidays_on_work_days
+            + overrides_on_other_days
+    }
+
+    /// Returns the number of work days in a month.
+    ///
+    /// Results are memoized per `(year, month)`, so repeated queries for the same month
+    /// are cheap. Every method that changes the calendar discards the memo, so results
+    /// always reflect the calendar's current state.
+    ///
+    /// # Arguments
+    ///
+    /// * `year` - The year.
+    /// * `month` - The month, from 1 (January) to 12 (December).
+    ///
+    /// # Returns
+    ///
+    /// The number of working dates in the month, or 0 if `month` is not a valid month.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// assert_eq!(calendar.work_days_in_month(2023, 12), 21);
+    /// calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 12, 25).unwrap());
+    /// assert_eq!(calendar.work_days_in_month(2023, 12), 20);
+    /// ```
+    pub fn work_days_in_month(&self, year: i32, month: u32) -> i64 {
+        let first = match NaiveDate::from_ymd_opt(year, month, 1) {
+            Some(first) => first,
+            None => return 0,
+        };
+        self.month_memo.get_or_insert_with((year, month), || {
+            let last = first
+                .checked_add_months(chrono::Months::new(1))
+                .and_then(|next| next.pred_opt())
+                .unwrap_or(NaiveDate::MAX);
+            self.work_days_between(first, last)
+        })
+    }
+
+    /// Returns the number of work days in a year.
+    ///
+    /// This sums [`WorkCalendar::work_days_in_month`], so it shares its memo.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// assert_eq!(calendar.work_days_in_year(2023), 260);
+    /// ```
+    pub fn work_days_in_year(&self, year: i32) -> i64 {
+        (1..=12)
+            .map(|month| self.work_days_in_month(year, month))
+            .sum()
+    }
+
+    /// Returns the holidays falling within `range`, in ascending order.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The dates to search, e.g. `start..=end`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 12, 25).unwrap());
+    /// calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 7, 4).unwrap());
+    /// calendar.add_holiday(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap());
+    ///
+    /// let start = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2023, 12, 31).unwrap();
+    /// let holidays: Vec<NaiveDate> = calendar.holidays_in_range(start..=end).collect();
+    /// assert_eq!(
+    ///     holidays,
+    ///     vec![
+    ///         NaiveDate::from_ymd_opt(2023, 7, 4).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2023, 12, 25).unwrap(),
+    ///     ]
+    /// );
+    /// ```
+    pub fn holidays_in_range<R: RangeBounds<NaiveDate>>(
+        &self,
+        range: R,
+    ) -> impl DoubleEndedIterator<Item = NaiveDate> + '_ {
+        let bounds = (range.start_bound().cloned(), range.end_bound().cloned());
+        let empty = match bounds {
+            (Bound::Included(a) | Bound::Excluded(a), Bound::Included(b) | Bound::Excluded(b)) => {
+                a > b || (a == b && bounds != (Bound::Included(a), Bound::Included(b)))
+            }
+            _ => false,
+        };
+        // `BTreeSet::range` panics on inverted bounds; treat them as empty instead.
+        let range = if empty {
+            None
+        } else {
+            Some(self.holidays.range(bounds))
+        };
+        range.into_iter().flatten().copied()
+    }
+
+    /// Adds work days to a date, returning `None` if the result would fall outside the
+    /// representable date range.
+    ///
+    /// The result is the `n`th working date after `date`, not counting `date` itself; a
+    /// negative `n` moves backwards, and zero returns `date` unchanged. `None` is also
+    /// returned when no working date can be found (see [`SEARCH_HORIZON_DAYS`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The date to start from.
+    /// * `n` - Number of work days to add.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let friday = NaiveDate::from_ymd_opt(2023, 8, 25).unwrap();
+    /// assert_eq!(
+    ///     calendar.checked_add_work_days(friday, 1),
+    ///     NaiveDate::from_ymd_opt(2023, 8, 28)
+    /// );
+    /// assert_eq!(calendar.checked_add_work_days(NaiveDate::MAX, 1), None);
+    /// ```
+    pub fn checked_add_work_days(&self, date: NaiveDate, n: i64) -> Option<NaiveDate> {
+        self.checked_add_work_days_with(date, n, &|_| true)
+    }
+
+    /// Like [`WorkCalendar::checked_add_work_days`], counting a holiday only if
+    /// `observed` accepts its date.
+    pub(crate) fn checked_add_work_days_with(
+        &self,
+        date: NaiveDate,
+        n: i64,
+        observed: &dyn Fn(&NaiveDate) -> bool,
+    ) -> Option<NaiveDate> {
+        match self.step_work_days(date, n, observed) {
+            WorkDayStep::Reached(date) => Some(date),
+            WorkDayStep::OutOfRange { .. } | WorkDayStep::NotFound { .. } => None,
+        }
+    }
+
+    /// Subtracts work days from a date, returning `None` if the result would fall
+    /// outside the representable date range.
+    ///
+    /// This is [`WorkCalendar::checked_add_work_days`] with `n` negated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let monday = NaiveDate::from_ymd_opt(2023, 8, 28).unwrap();
+    /// assert_eq!(
+    ///     calendar.checked_sub_work_days(monday, 1),
+    ///     NaiveDate::from_ymd_opt(2023, 8, 25)
+    /// );
+    /// ```
+    pub fn checked_sub_work_days(&self, date: NaiveDate, n: i64) -> Option<NaiveDate> {
+        self.checked_add_work_days(date, n.checked_neg()?)
+    }
+
+    /// Adds work days to a date, clamping at the last representable working date.
+    ///
+    /// Behaves like [`WorkCalendar::checked_add_work_days`], except that when the result
+    /// would overflow, the last working date before the end of the date range (or the
+    /// first one after its start, for negative `n`) is returned instead. If no working
+    /// date can be found at all, `date` is returned unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::{Datelike, NaiveDate};
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let last = calendar.saturating_add_work_days(NaiveDate::MAX, 10);
+    /// assert!(last <= NaiveDate::MAX);
+    /// assert!(calendar.is_work_day(&last.weekday()));
+    /// ```
+    pub fn saturating_add_work_days(&self, date: NaiveDate, n: i64) -> NaiveDate {
+        match self.step_work_days(date, n, &|_| true) {
+            WorkDayStep::Reached(date) => date,
+            WorkDayStep::OutOfRange { last_working } => last_working.unwrap_or(date),
+            WorkDayStep::NotFound { .. } => date,
+        }
+    }
+
+    /// Returns a span of `n` work days on this calendar, for use with date operators.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let friday = NaiveDate::from_ymd_opt(2023, 8, 25).unwrap();
+    /// assert_eq!(friday + calendar.span(1), NaiveDate::from_ymd_opt(2023, 8, 28).unwrap());
+    /// ```
+    pub fn span(&self, n: i64) -> BoundWorkDays<'_> {
+        WorkDays(n).on(self)
+    }
+
+    /// Subtracts work days from a date, clamping at the first representable working date.
+    ///
+    /// This is [`WorkCalendar::saturating_add_work_days`] with `n` negated.
+    pub fn saturating_sub_work_days(&self, date: NaiveDate, n: i64) -> NaiveDate {
+        self.saturating_add_work_days(date, n.checked_neg().unwrap_or(i64::MAX))
+    }
+
+    /// Returns the first working date after `date`.
+    ///
+    /// # Errors
+    ///
+    /// * [`WorkCalendarError::NoWorkDaysDefined`] if the calendar has no work days.
+    /// * [`WorkCalendarError::NoWorkingDaysInHorizon`] if no working date is found
+    ///   within [`SEARCH_HORIZON_DAYS`].
+    /// * [`WorkCalendarError::DateOutOfRange`] if the result cannot be represented.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let friday = NaiveDate::from_ymd_opt(2023, 8, 25).unwrap();
+    /// assert_eq!(
+    ///     calendar.next_work_day(friday).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2023, 8, 28).unwrap()
+    /// );
+    /// ```
+    pub fn next_work_day(&self, date: NaiveDate) -> Result<NaiveDate, WorkCalendarError> {
+        self.try_add_work_days(date, 1)
+    }
+
+    /// Returns the last working date before `date`.
+    ///
+    /// To leave a working date where it is and move only other dates, use
+    /// [`WorkCalendar::adjust`] with [`AdjustmentConvention::Preceding`].
+    ///
+    /// # Errors
+    ///
+    /// * [`WorkCalendarError::NoWorkDaysDefined`] if the calendar has no work days.
+    /// * [`WorkCalendarError::NoWorkingDaysInHorizon`] if no working date is found
+    ///   within [`SEARCH_HORIZON_DAYS`].
+    /// * [`WorkCalendarError::DateOutOfRange`] if the result cannot be represented.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let monday = NaiveDate::from_ymd_opt(2023, 8, 28).unwrap();
+    /// assert_eq!(
+    ///     calendar.previous_work_day(monday).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2023, 8, 25).unwrap()
+    /// );
+    /// ```
+    pub fn previous_work_day(&self, date: NaiveDate) -> Result<NaiveDate, WorkCalendarError> {
+        self.try_add_work_days(date, -1)
+    }
+
+    /// Like [`WorkCalendar::checked_add_work_days`], but reports why no date was found.
+    fn try_add_work_days(&self, date: NaiveDate, n: i64) -> Result<NaiveDate, WorkCalendarError> {
+        self.try_add_work_days_with(date, n, &|_| true)
+    }
+
+    /// Like [`WorkCalendar::try_add_work_days`], counting a holiday only if `observed`
+    /// accepts its date.
+    pub(crate) fn try_add_work_days_with(
+        &self,
+        date: NaiveDate,
+        n: i64,
+        observed: &dyn Fn(&NaiveDate) -> bool,
+    ) -> Result<NaiveDate, WorkCalendarError> {
+        let name = || self.name().map(String::from);
+        if n != 0 && !self.has_any_work_days() {
+            return Err(WorkCalendarError::NoWorkDaysDefined { calendar: name() });
+        }
+        match self.step_work_days(date, n, observed) {
+            WorkDayStep::Reached(date) => Ok(date),
+            WorkDayStep::OutOfRange { .. } => {
+                Err(WorkCalendarError::DateOutOfRange { calendar: name() })
+            }
+            WorkDayStep::NotFound { searched_until } => {
+                Err(WorkCalendarError::NoWorkingDaysInHorizon {
+                    calendar: name(),
+                    searched_until,
+                })
+            }
+        }
+    }
+
+    /// Walks `n` working dates from `date`, forwards for positive `n` and backwards for
+    /// negative `n`, without stepping outside the representable date range. Only the
+    /// holidays `observed` accepts count.
+    fn step_work_days(
+        &self,
+        date: NaiveDate,
+        n: i64,
+        observed: &dyn Fn(&NaiveDate) -> bool,
+    ) -> WorkDayStep {
+        if n == 0 {
+            return WorkDayStep::Reached(date);
+        }
+        if !self.has_any_work_days() {
+            return WorkDayStep::NotFound {
+                searched_until: date,
+            };
+        }
+
+        let forward = n > 0;
+        let mut remaining = n.unsigned_abs();
+        let mut current_date = date;
+        let mut last_working = None;
+        let mut gap = 0;
+
+        while remaining > 0 {
+            let next = if forward {
+                current_date.succ_opt()
+            } else {
+                current_date.pred_opt()
+            };
+            current_date = match next {
+                Some(next) => next,
+                None => return WorkDayStep::OutOfRange { last_working },
+            };
+
+            if self.is_working_date_with(&current_date, observed) {
+                remaining -= 1;
+                last_working = Some(current_date);
+                gap = 0;
+            } else {
+                gap += 1;
+                if gap >= SEARCH_HORIZON_DAYS {
+                    return WorkDayStep::NotFound {
+                        searched_until: current_date,
+                    };
+                }
+            }
+        }
+
+        WorkDayStep::Reached(current_date)
+    }
+
+    /// Converts the calendar into a [`CompactCalendar`] for read-only workloads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// let holiday = NaiveDate::from_ymd_opt(2023, 12, 25).unwrap();
+    /// calendar.add_holiday(holiday);
+    ///
+    /// let compact = calendar.compact();
+    /// assert!(compact.is_holiday(&holiday));
+    /// ```
+    pub fn compact(&self) -> CompactCalendar {
+        CompactCalendar::from(self)
+    }
+
+    /// Converts the calendar into a [`FrozenCalendar`] for query-heavy workloads.
+    ///
+    /// Per-year work-day totals are precomputed for the years spanned by the calendar's
+    /// holidays; use [`WorkCalendar::freeze_with_horizon`] to choose the years.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 12, 25).unwrap());
+    ///
+    /// let frozen = calendar.clone().freeze();
+    /// assert_eq!(frozen.work_days_in_year(2023), 259);
+    /// assert_eq!(frozen.thaw(), calendar);
+    /// ```
+    pub fn freeze(self) -> FrozenCalendar {
+        let first = self.holidays.iter().next().map_or(0, |date| date.year());
+        let last = self
+            .holidays
+            .iter()
+            .next_back()
+            .map_or(-1, |date| date.year());
+        FrozenCalendar::new(self, first..=last)
+    }
+
+    /// Converts the calendar into a [`FrozenCalendar`], precomputing per-year work-day
+    /// totals for `years`.
+    ///
+    /// # Arguments
+    ///
+    /// * `years` - The years to precompute, e.g. `2020..=2040`.
+    pub fn freeze_with_horizon(self, years: RangeInclusive<i32>) -> FrozenCalendar {
+        FrozenCalendar::new(self, years)
+    }
+
+    /// Builds a [`RangeCounter`] that answers `work_days_between` queries in constant
+    /// time for dates within `horizon`.
+    ///
+    /// # Arguments
+    ///
+    /// * `horizon` - The years to precompute, e.g. `2020..=2030`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let counter = calendar.counter(2023..=2023);
+    /// let start = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2023, 12, 31).unwrap();
+    /// assert_eq!(counter.work_days_between(start, end), 260);
+    /// ```
+    pub fn counter(&self, horizon: RangeInclusive<i32>) -> RangeCounter {
+        RangeCounter::new(self, horizon)
+    }
+
+    /// Returns one entry per day of `year`, indexed by zero-based day of the year,
+    /// that is `true` for working dates.
+    ///
+    /// Leap years produce 366 entries and other years 365. Years outside the range
+    /// supported by `chrono` produce an empty vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let bitmap = calendar.working_days_bitmap(2024);
+    /// assert_eq!(bitmap.len(), 366);
+    /// assert!(bitmap[0]); // Monday, January 1
+    /// assert!(!bitmap[5]); // Saturday, January 6
+    /// ```
+    pub fn working_days_bitmap(&self, year: i32) -> Vec<bool> {
+        days_of_year(year)
+            .map(|date| self.is_working_date(&date))
+            .collect()
+    }
+
+    /// Builds a calendar whose working dates match the given per-year bitmaps exactly.
+    ///
+    /// Each bitmap is in the format produced by [`WorkCalendar::working_days_bitmap`].
+    /// The result has the default Monday–Friday work week; dates where a bitmap
+    /// disagrees with it become holidays or working-date overrides. Years without a
+    /// bitmap follow the default work week.
+    ///
+    /// # Arguments
+    ///
+    /// * `maps` - `(year, bitmap)` pairs.
+    ///
+    /// # Errors
+    ///
+    /// [`WorkCalendarError::InvalidBitmap`] if a bitmap does not have exactly one entry
+    /// per day of its year.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut bitmap = vec![false; 365];
+    /// bitmap[0] = true; // Only January 1 is worked
+    /// let calendar = WorkCalendar::from_year_bitmaps(&[(2023, &bitmap)]).unwrap();
+    /// assert!(calendar.is_working_date(&NaiveDate::from_ymd_opt(2023, 1, 1).unwrap()));
+    /// assert!(!calendar.is_working_date(&NaiveDate::from_ymd_opt(2023, 1, 2).unwrap()));
+    /// ```
+    pub fn from_year_bitmaps(maps: &[(i32, &[bool])]) -> Result<Self, WorkCalendarError> {
+        let mut calendar = WorkCalendar::new();
+        let mut holidays = BTreeSet::new();
+        let mut working_dates = BTreeSet::new();
+        for &(year, bitmap) in maps {
+            let expected = days_of_year(year).count();
+            if bitmap.len() != expected {
+                return Err(WorkCalendarError::InvalidBitmap {
+                    year,
+                    expected,
+                    found: bitmap.len(),
+                });
+            }
+            for (date, &working) in days_of_year(year).zip(bitmap) {
+                match (calendar.is_work_day(&date.weekday()), working) {
+                    (true, false) => {
+                        holidays.insert(date);
+                    }
+                    (false, true) => {
+                        working_dates.insert(date);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        calendar.holidays = Arc::new(holidays);
+        calendar.working_dates = Arc::new(working_dates);
+        Ok(calendar)
+    }
+
+    /// Returns the first date after `date` that breaks the weekly pattern: a holiday on
+    /// a work day or a working-date override on any other day.
+    fn next_exception(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let after = (Bound::Excluded(date), Bound::Unbounded);
+        let holiday = self
+            .holidays
+            .range(after)
+            .find(|holiday| self.is_work_day(&holiday.weekday()));
+        let working = self
+            .working_dates
+            .range(after)
+            .find(|working| !self.is_work_day(&working.weekday()));
+        let rule = self
+            .recurring_holidays
+            .next_after(date, |holiday| self.is_work_day(&holiday.weekday()));
+        [holiday.copied(), working.copied(), rule]
+            .into_iter()
+            .flatten()
+            .min()
+    }
+
+    /// Returns the work week in effect on `date`.
+    fn work_week_on(&self, date: NaiveDate) -> &WorkWeek {
+        week::week_on(&self.work_days, &self.work_days_effective, date)
+    }
+
+    /// Returns every work week the calendar uses: its work days, then each
+    /// effective-dated change in date order.
+    fn work_weeks(&self) -> impl Iterator<Item = &WorkWeek> {
+        std::iter::once(&self.work_days).chain(self.work_days_effective.values())
+    }
+
+    /// Checks if any of the calendar's work weeks has a work day.
+    fn has_any_work_days(&self) -> bool {
+        self.work_weeks().any(|week| !week.is_empty())
+    }
+
+    /// Splits `start_date..=end_date` into runs with one work week in effect, in order.
+    /// Returns nothing if `start_date` is after `end_date`.
+    fn week_segments(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> impl Iterator<Item = (&WorkWeek, NaiveDate, NaiveDate)> + '_ {
+        let changes = (start_date <= end_date).then(|| {
+            self.work_days_effective
+                .range((Bound::Excluded(start_date), Bound::Included(end_date)))
+        });
+        let mut first = start_date;
+        let mut week = self.work_week_on(start_date);
+        changes
+            .into_iter()
+            .flat_map(|changes| changes.map(Some).chain(std::iter::once(None)))
+            .map(move |change| match change {
+                Some((change, next)) => {
+                    // `change` is after `start_date`, so it has a predecessor.
+                    let segment = (week, first, change.pred_opt().unwrap());
+                    first = *change;
+                    week = next;
+                    segment
+                }
+                None => (week, first, end_date),
+            })
+    }
+
+    /// Parses a comma-separated list of day names into a work week.
+    fn parse_work_week(&self, input: &str) -> Result<WorkWeek, WorkCalendarError> {
+        let days: HashSet<Weekday> = input
+            .split(',')
+            .filter_map(|day| parse_weekday_item(day.trim()))
+            .flatten()
+            .copied()
+            .collect();
+        if days.is_empty() {
+            return Err(WorkCalendarError::NoValidWorkDays {
+                calendar: self.name().map(String::from),
+                input: input.to_string(),
+            });
+        }
+        Ok(WorkWeek::from(days))
+    }
+
+    /// Returns the work days in order from Monday to Sunday.
+    fn sorted_work_days(&self) -> Vec<Weekday> {
+        self.work_days.sorted()
+    }
+}
+
+impl CalendarLike for WorkCalendar {
+    fn name(&self) -> Option<&str> {
+        WorkCalendar::name(self)
+    }
+
+    fn is_working_date(&self, date: &NaiveDate) -> bool {
+        WorkCalendar::is_working_date(self, date)
+    }
+
+    fn has_work_days(&self) -> bool {
+        self.has_any_work_days()
+    }
+
+    fn work_days_between(&self, start_date: NaiveDate, end_date: NaiveDate) -> i64 {
+        WorkCalendar::work_days_between(self, start_date, end_date)
+    }
+
+    fn compute_end(
+        &self,
+        start_date: NaiveDate,
+        days_worked: i64,
+    ) -> Result<EndDate, WorkCalendarError> {
+        WorkCalendar::compute_end(self, start_date, days_worked)
+    }
+}
+
+impl fmt::Display for WorkCalendar {
+    /// Formats a one-line summary of the calendar, e.g. `US: Mon, Tue, Wed, Thu, Fri; 2 holidays`.
+    ///
+    /// [`WorkCalendar::describe_localized`] writes the same summary in another language.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_summary(f, Locale::English)
+    }
+}
+
+/// Returns the dates of `year` in order, or nothing if the year is out of range.
+fn days_of_year(year: i32) -> impl Iterator<Item = NaiveDate> {
+    let first = NaiveDate::from_ymd_opt(year, 1, 1);
+    std::iter::successors(first, |date| date.succ_opt()).take_while(move |date| date.year() == year)
+}
+
+/// Counts the dates in `start_date..=end_date` whose weekday is a work day, ignoring
+/// holidays. `days_per_week` must equal the number of weekdays accepted by `is_work_day`.
+fn count_work_weekdays(
+    is_work_day: impl Fn(Weekday) -> bool,
+    days_per_week: i64,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> i64 {
+    if start_date > end_date {
+        return 0;
+    }
+    let total_days = (end_date - start_date).num_days() + 1;
+    let full_weeks = total_days / 7;
+    let first_weekday = start_date.weekday();
+    let partial_week = (0..total_days % 7)
+        .filter(|offset| {
+            let day = (i64::from(first_weekday.num_days_from_monday()) + offset) % 7;
+            is_work_day(Weekday::try_from(day as u8).unwrap())
+        })
+        .count() as i64;
+    full_weeks * days_per_week + partial_week
+}
+
+/// Outcome of [`WorkCalendar::step_work_days`].
+enum WorkDayStep {
+    /// The requested number of working dates was reached.
+    Reached(NaiveDate),
+    /// The walk ran off the end of the date range; carries the last working date seen.
+    OutOfRange { last_working: Option<NaiveDate> },
+    /// No working date could be found within the search horizon; carries the date
+    /// the search stopped at.
+    NotFound { searched_until: NaiveDate },
+}
+
+/// Full and abbreviated names of each weekday, in lowercase.
+const WEEKDAY_NAMES: [(Weekday, &str, &str); 7] = [
+    (Weekday::Mon, "monday", "mon"),
+    (Weekday::Tue, "tuesday", "tue"),
+    (Weekday::Wed, "wednesday", "wed"),
+    (Weekday::Thu, "thursday", "thu"),
+    (Weekday::Fri, "friday", "fri"),
+    (Weekday::Sat, "saturday", "sat"),
+    (Weekday::Sun, "sunday", "sun"),
+];
+
+/// The whole week, Monday first.
+const WEEK: &[Weekday] = &[
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+/// Keywords accepted in lists of work days, in lowercase, with the days they stand for.
+const WEEKDAY_KEYWORDS: [(&str, &[Weekday]); 4] = [
+    (
+        "weekdays",
+        &[
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ],
+    ),
+    ("weekend", &[Weekday::Sat, Weekday::Sun]),
+    ("everyday", WEEK),
+    ("all", WEEK),
+];
+
+/// Returns the days a list item stands for: the one day it names, or the days of a
+/// keyword such as `weekdays`. Case is ignored.
+pub(crate) fn parse_weekday_item(item: &str) -> Option<&'static [Weekday]> {
+    if let Some(day) = parse_weekday(item) {
+        let index = day.num_days_from_monday() as usize;
+        return Some(&WEEK[index..=index]);
+    }
+    WEEKDAY_KEYWORDS
+        .iter()
+        .find(|(keyword, _)| item.eq_ignore_ascii_case(keyword))
+        .map(|(_, days)| *days)
+}
+
+/// Parses a weekday string into a `Weekday` enum.
+///
+/// This function is case-insensitive and accepts both full names (e.g., "Monday")
+/// and abbreviations (e.g., "Mon"). It does not allocate.
+///
+/// # Arguments
+///
+/// * `day` - A string slice that holds the name of the day.
+///
+/// # Returns
+///
+/// * `Some(Weekday)` if the day name is valid.
+/// * `None` if the day name is invalid.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::parse_weekday;
+/// use chrono::Weekday;
+///
+/// assert_eq!(parse_weekday("Monday"), Some(Weekday::Mon));
+/// assert_eq!(parse_weekday("tue"), Some(Weekday::Tue));
+/// assert_eq!(parse_weekday("Invalid"), None);
+/// ```
+pub fn parse_weekday(day: &str) -> Option<Weekday> {
+    WEEKDAY_NAMES
+        .iter()
+        .find(|(_, full, short)| day.eq_ignore_ascii_case(full) || day.eq_ignore_ascii_case(short))
+        .map(|(weekday, _, _)| *weekday)
+}
+
+/// Parses a comma-separated list of weekdays and weekday ranges.
+///
+/// Each item is a day name accepted by [`parse_weekday`], a range such as `Mon-Fri`,
+/// or one of the keywords `weekdays` (Monday to Friday), `weekend` (Saturday and
+/// Sunday), and `everyday` or `all` (the whole week). Ranges are inclusive and wrap
+/// around the end of the week, so `Fri-Mon` is Friday through Monday. Keywords are
+/// case-insensitive and combine with the other items, so `weekdays, Sat` is Monday to
+/// Saturday. Whitespace around items is ignored, as are empty items (e.g., from a
+/// trailing comma).
+///
+/// # Arguments
+///
+/// * `days` - The list to parse, e.g. `"Mon-Wed, Fri"`.
+///
+/// # Returns
+///
+/// The weekdays in the order they first appear, without duplicates.
+///
+/// # Errors
+///
+/// [`WorkCalendarError::InvalidWeekdays`] listing every item that could not be parsed.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::parse_weekdays;
+/// use chrono::Weekday;
+///
+/// assert_eq!(
+///     parse_weekdays("mon-wed, Fri").unwrap(),
+///     vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Fri]
+/// );
+/// assert_eq!(parse_weekdays("Sat-Sun").unwrap(), vec![Weekday::Sat, Weekday::Sun]);
+/// assert_eq!(parse_weekdays("weekdays, Sat").unwrap().len(), 6);
+/// assert!(parse_weekdays("Mon,Funday,Tue-Blursday").is_err());
+/// assert!(parse_weekdays("workdays").is_err());
+/// ```
+pub fn parse_weekdays(days: &str) -> Result<Vec<Weekday>, WorkCalendarError> {
+    let mut weekdays = Vec::with_capacity(7);
+    let mut invalid = Vec::new();
+    let mut push = |weekday: Weekday| {
+        if !weekdays.contains(&weekday) {
+            weekdays.push(weekday);
+        }
+    };
+
+    for item in days
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+    {
+        match item.split_once('-') {
+            Some((first, last)) => {
+                match parse_weekday(first.trim()).zip(parse_weekday(last.trim())) {
+                    Some((first, last)) => {
+                        let mut day = first;
+                        push(day);
+                        while day != last {
+                            day = day.succ();
+                            push(day);
+                        }
+                    }
+                    None => invalid.push(item.to_string()),
+                }
+            }
+            None => match parse_weekday_item(item) {
+                Some(days) => days.iter().copied().for_each(&mut push),
+                None => invalid.push(item.to_string()),
+            },
+        }
+    }
+
+    if invalid.is_empty() {
+        Ok(weekdays)
+    } else {
+        Err(WorkCalendarError::InvalidWeekdays(invalid))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{count_allocations, date, random_calendar, TestRng};
+
+    #[test]
+    fn test_compute_end_date_standard_week() {
+        let calendar = WorkCalendar::new();
+        let start_date = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap(); // Monday
+        let (end_date, duration) = calendar.compute_end_date(start_date, 5).unwrap();
+
+        assert_eq!(end_date, NaiveDate::from_ymd_opt(2023, 8, 25).unwrap()); // Friday
+        assert_eq!(duration.num_days(), 4);
+    }
+
+    #[test]
+    fn test_compute_end_date_with_weekend() {
+        let calendar = WorkCalendar::new();
+        let start_date = NaiveDate::from_ymd_opt(2023, 8, 18).unwrap(); // Friday
+        let (end_date, duration) = calendar.compute_end_date(start_date, 5).unwrap();
+
+        assert_eq!(end_date, NaiveDate::from_ymd_opt(2023, 8, 24).unwrap()); // Next Thursday
+        assert_eq!(duration.num_days(), 6);
+    }
+
+    #[test]
+    fn test_compute_end_date_with_holiday() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 8, 23).unwrap()); // Wednesday
+        let start_date = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap(); // Monday
+        let (end_date, duration) = calendar.compute_end_date(start_date, 5).unwrap();
+
+        assert_eq!(end_date, NaiveDate::from_ymd_opt(2023, 8, 28).unwrap()); // Next Monday
+        assert_eq!(duration.num_days(), 7);
+    }
+
+    #[test]
+    fn test_compute_end_date_custom_work_days() {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_work_days("Monday,Wednesday,Friday").unwrap();
+        let start_date = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap(); // Monday
+        let (end_date, duration) = calendar.compute_end_date(start_date, 5).unwrap();
+
+        assert_eq!(end_date, NaiveDate::from_ymd_opt(2023, 8, 30).unwrap()); // Wednesday
+        assert_eq!(duration.num_days(), 9);
+    }
+
+    #[test]
+    fn test_compute_end_date_zero_days() {
+        let calendar = WorkCalendar::new();
+        let start_date = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap(); // Monday
+        let (end_date, duration) = calendar.compute_end_date(start_date, 0).unwrap();
+
+        assert_eq!(end_date, start_date);
+        assert_eq!(duration.num_days(), 0);
+    }
+
+    #[test]
+    fn test_compute_end_from_non_working_start() {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("Ops");
+        let saturday = NaiveDate::from_ymd_opt(2023, 8, 19).unwrap();
+        let holiday = NaiveDate::from_ymd_opt(2023, 8, 23).unwrap(); // Wednesday
+        calendar.add_holiday(holiday);
+
+        // Zero days from a non-working date have no end date.
+        for start_date in [saturday, holiday] {
+            assert_eq!(
+                calendar.compute_end(start_date, 0),
+                Err(WorkCalendarError::NonWorkingStart {
+                    calendar: Some("Ops".to_string()),
+                    date: start_date
+                })
+            );
+        }
+        assert_eq!(
+            calendar.compute_end(saturday, 0).unwrap_err().to_string(),
+            "Zero work days from 2023-08-19, which is not a working date, has no end date \
+             (calendar 'Ops')"
+        );
+
+        // One day ends on the next working date.
+        let end = calendar.compute_end(saturday, 1).unwrap();
+        assert_eq!(end.date, NaiveDate::from_ymd_opt(2023, 8, 21).unwrap());
+        assert_eq!(end.calendar_days, 2);
+        let end = calendar.compute_end(holiday, 1).unwrap();
+        assert_eq!(end.date, NaiveDate::from_ymd_opt(2023, 8, 24).unwrap());
+        assert_eq!(end.calendar_days, 1);
+    }
+
+    #[test]
+    fn test_compute_end_matches_compute_end_date() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 8, 23).unwrap());
+        let start_date = NaiveDate::from_ymd_opt(2023, 8, 19).unwrap(); // Saturday
+        assert_eq!(
+            calendar.compute_end(start_date, 0).unwrap_err(),
+            calendar.compute_end_date(start_date, 0).unwrap_err()
+        );
+        for days in 1..30 {
+            let end = calendar.compute_end(start_date, days).unwrap();
+            let (end_date, duration) = calendar.compute_end_date(start_date, days).unwrap();
+            assert_eq!(end.date, end_date);
+            assert_eq!(end.calendar_days as i64, duration.num_days());
+            assert_eq!(start_date + end.calendar_span(), end.date);
+        }
+    }
+
+    #[test]
+    fn test_compute_end_out_of_range() {
+        let calendar = WorkCalendar::new();
+        let start_date = NaiveDate::MAX - Duration::days(3);
+        assert_eq!(
+            calendar.compute_end(start_date, 30),
+            Err(WorkCalendarError::DateOutOfRange { calendar: None })
+        );
+        assert_eq!(
+            calendar.work_days_between(start_date, NaiveDate::MAX),
+            calendar.work_days_between(start_date, NaiveDate::MAX - Duration::days(1))
+                + i64::from(calendar.is_work_day(&NaiveDate::MAX.weekday()))
+        );
+    }
+
+    #[test]
+    fn test_compute_end_date_negative_days() {
+        let calendar = WorkCalendar::new();
+        let start_date = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap(); // Monday
+        assert!(calendar.compute_end_date(start_date, -1).is_err());
+    }
+
+    #[test]
+    fn test_set_work_days() {
+        let mut calendar = WorkCalendar::new();
+        assert!(calendar.set_work_days("Mon,Wed,Fri").is_ok());
+        assert!(calendar.is_work_day(&Weekday::Mon));
+        assert!(calendar.is_work_day(&Weekday::Wed));
+        assert!(calendar.is_work_day(&Weekday::Fri));
+        assert!(!calendar.is_work_day(&Weekday::Tue));
+        assert!(!calendar.is_work_day(&Weekday::Thu));
+        assert!(!calendar.is_work_day(&Weekday::Sat));
+        assert!(!calendar.is_work_day(&Weekday::Sun));
+    }
+
+    #[test]
+    fn test_set_work_days_keywords() {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_work_days("weekdays,Sat").unwrap();
+        assert_eq!(calendar.work_days.days_per_week(), 6);
+        assert!(!calendar.is_work_day(&Weekday::Sun));
+        calendar.set_work_days("Weekend").unwrap();
+        assert_eq!(calendar.sorted_work_days(), [Weekday::Sat, Weekday::Sun]);
+        calendar.set_work_days("all").unwrap();
+        assert_eq!(calendar.work_days.days_per_week(), 7);
+        calendar.set_work_days("everyday").unwrap();
+        assert_eq!(calendar.work_days.days_per_week(), 7);
+        assert!(calendar.set_work_days("workdays").is_err());
+
+        // Keywords are expanded, so the serialized week lists its days in order.
+        calendar.set_work_days("weekend, weekdays").unwrap();
+        let json = serde_json::to_string(&calendar).unwrap();
+        assert!(
+            json.contains(r#""work_days":["Mon","Tue","Wed","Thu","Fri","Sat","Sun"]"#),
+            "{}",
+            json
+        );
+        assert_eq!(
+            serde_json::from_str::<WorkCalendar>(&json).unwrap(),
+            calendar
+        );
+    }
+
+    #[test]
+    fn test_add_and_remove_holiday() {
+        let mut calendar = WorkCalendar::new();
+        let holiday = NaiveDate::from_ymd_opt(2023, 12, 25).unwrap();
+        calendar.add_holiday(holiday);
+        assert!(calendar.is_holiday(&holiday));
+        calendar.remove_holiday(&holiday);
+        assert!(!calendar.is_holiday(&holiday));
+    }
+
+    #[test]
+    fn test_retain_holidays_keeps_later_counts_randomized() {
+        let mut rng = TestRng(0x2c7e_91d4_b053_f86a);
+        let base = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        for _ in 0..100 {
+            let mut calendar = random_calendar(&mut rng);
+            let cutoff = base + Duration::days(rng.below(3000) as i64);
+            let kept: Vec<NaiveDate> = calendar.holidays_in_range(cutoff..).collect();
+            let before = calendar.holidays_in_range(..cutoff).count();
+            let original = calendar.clone();
+
+            assert_eq!(calendar.retain_holidays_from(cutoff), before);
+            assert_eq!(calendar.holidays_in_range(..).collect::<Vec<_>>(), kept);
+            assert_eq!(calendar.recurring_holidays(), original.recurring_holidays());
+            for _ in 0..5 {
+                let start = cutoff + Duration::days(rng.below(1500) as i64);
+                let end = start + Duration::days(rng.below(400) as i64);
+                assert_eq!(
+                    calendar.work_days_between(start, end),
+                    original.work_days_between(start, end)
+                );
+                let days = rng.below(200) as i64 + 1;
+                if calendar.is_working_date(&start) {
+                    assert_eq!(
+                        calendar.compute_end(start, days),
+                        original.compute_end(start, days)
+                    );
+                }
+            }
+            assert_eq!(calendar.retain_holidays_from(cutoff), 0);
+        }
+    }
+
+    #[test]
+    fn test_retain_holidays_in() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let mut calendar = WorkCalendar::new();
+        let christmas = HolidayRule::FixedDate { month: 12, day: 25 };
+        calendar.add_recurring_holiday_with_category(christmas, "public");
+        calendar.add_holiday_with_category(date(2019, 7, 5), "optional");
+        calendar.add_holiday_with_category(date(2022, 7, 5), "optional");
+        calendar.add_holiday(date(2023, 3, 1));
+        calendar.add_holiday_with_category(date(2026, 7, 5), "public");
+
+        assert_eq!(
+            calendar.retain_holidays_in(date(2020, 1, 1)..date(2026, 7, 5)),
+            2
+        );
+        assert_eq!(
+            calendar.holidays_in_range(..).collect::<Vec<_>>(),
+            [date(2022, 7, 5), date(2023, 3, 1)]
+        );
+        assert_eq!(
+            calendar.holiday_category(&date(2022, 7, 5)),
+            Some("optional")
+        );
+        assert_eq!(calendar.holiday_category(&date(2019, 7, 5)), None);
+        assert_eq!(calendar.holiday_category(&date(2026, 7, 5)), None);
+        assert_eq!(
+            calendar.recurring_holiday_category(&christmas),
+            Some("public")
+        );
+        assert!(calendar.is_holiday(&date(2019, 12, 25)));
+
+        // Re-adding a removed date does not bring its old category back.
+        calendar.add_holiday(date(2019, 7, 5));
+        assert_eq!(calendar.holiday_category(&date(2019, 7, 5)), None);
+
+        assert_eq!(
+            calendar.retain_holidays_in(date(2024, 1, 1)..date(2020, 1, 1)),
+            3
+        );
+        assert_eq!(calendar.holidays_in_range(..).count(), 0);
+    }
+
+    #[test]
+    fn test_holiday_ranges() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("Ops");
+        calendar.add_holiday_with_category(date(2024, 12, 25), "public");
+        calendar.add_holiday_with_category(date(2025, 1, 6), "public");
+        calendar
+            .add_holiday_range(date(2024, 12, 23), date(2025, 1, 3))
+            .unwrap();
+        assert_eq!(calendar.holidays_in_range(..).count(), 13);
+        // The range replaces the category of a date it covers, as `add_holiday` does.
+        assert_eq!(calendar.holiday_category(&date(2024, 12, 25)), None);
+        assert_eq!(calendar.holiday_category(&date(2025, 1, 6)), Some("public"));
+
+        assert_eq!(
+            calendar.work_days_between(date(2024, 12, 16), date(2025, 1, 10)),
+            9
+        );
+        assert_eq!(
+            calendar.compute_end(date(2024, 12, 20), 2).unwrap().date,
+            date(2025, 1, 7)
+        );
+
+        // Removal takes only the dates in its range, however they were added.
+        assert_eq!(
+            calendar
+                .remove_holiday_range(date(2025, 1, 2), date(2025, 1, 6))
+                .unwrap(),
+            3
+        );
+        assert_eq!(
+            calendar
+                .holidays_in_range(date(2025, 1, 1)..)
+                .collect::<Vec<_>>(),
+            [date(2025, 1, 1)]
+        );
+        assert_eq!(calendar.holiday_category(&date(2025, 1, 6)), None);
+        assert_eq!(
+            calendar
+                .remove_holiday_range(date(2025, 2, 1), date(2025, 2, 28))
+                .unwrap(),
+            0
+        );
+
+        // A single-day range is one holiday; an inverted one changes nothing.
+        calendar
+            .add_holiday_range(date(2025, 7, 4), date(2025, 7, 4))
+            .unwrap();
+        assert!(calendar.is_holiday(&date(2025, 7, 4)));
+        let before = calendar.clone();
+        let inverted = WorkCalendarError::InvalidHolidayRange {
+            calendar: Some("Ops".to_string()),
+            start: date(2025, 8, 15),
+            end: date(2025, 8, 1),
+        };
+        assert_eq!(
+            calendar.add_holiday_range(date(2025, 8, 15), date(2025, 8, 1)),
+            Err(inverted.clone())
+        );
+        assert_eq!(
+            calendar.remove_holiday_range(date(2025, 8, 15), date(2025, 8, 1)),
+            Err(inverted.clone())
+        );
+        assert_eq!(calendar, before);
+        assert_eq!(
+            inverted.to_string(),
+            "Holiday range must not end before it starts; got 2025-08-15 to 2025-08-01 \
+             (calendar 'Ops')"
+        );
+
+        // Ranges reaching the ends of the representable dates stop there.
+        calendar
+            .add_holiday_range(NaiveDate::MAX - Duration::days(2), NaiveDate::MAX)
+            .unwrap();
+        assert_eq!(
+            calendar
+                .remove_holiday_range(NaiveDate::MAX - Duration::days(9), NaiveDate::MAX)
+                .unwrap(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_long_holiday_ranges() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let (start, end) = (date(2020, 1, 1), date(2027, 12, 31));
+        let days = (end - start).num_days() as usize + 1;
+        let mut calendar = WorkCalendar::new();
+        let (allocations, result) = count_allocations(|| calendar.add_holiday_range(start, end));
+        result.unwrap();
+        // Dates are stored in B-tree nodes of several dates each, not one apiece.
+        assert!(allocations < days / 4, "{} allocations", allocations);
+        assert_eq!(calendar.holidays_in_range(..).count(), days);
+        assert_eq!(
+            calendar.work_days_between(date(2019, 12, 1), date(2028, 1, 31)),
+            43
+        );
+        assert_eq!(
+            calendar.compute_end(date(2019, 12, 31), 2).unwrap().date,
+            date(2028, 1, 3)
+        );
+
+        // Carving a month out of the middle leaves the rest.
+        calendar
+            .remove_holiday_range(date(2025, 6, 1), date(2025, 6, 30))
+            .unwrap();
+        assert_eq!(calendar.holidays_in_range(..).count(), days - 30);
+        assert_eq!(
+            calendar.work_days_between(date(2025, 5, 1), date(2025, 7, 31)),
+            21
+        );
+        assert_eq!(
+            calendar.remove_holiday_range(start, end).unwrap(),
+            days - 30
+        );
+        assert_eq!(calendar, WorkCalendar::new());
+    }
+
+    #[test]
+    fn test_work_days_between() {
+        let calendar = WorkCalendar::new();
+        let start_date = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap(); // Monday
+        let end_date = NaiveDate::from_ymd_opt(2023, 8, 25).unwrap(); // Friday
+        assert_eq!(calendar.work_days_between(start_date, end_date), 5);
+
+        let end_date = NaiveDate::from_ymd_opt(2023, 8, 27).unwrap(); // Sunday
+        assert_eq!(calendar.work_days_between(start_date, end_date), 5);
+
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 8, 23).unwrap()); // Wednesday
+        assert_eq!(calendar.work_days_between(start_date, end_date), 4);
+
+        // An end before the start counts nothing, whatever lies between.
+        assert_eq!(calendar.work_days_between(end_date, start_date), 0);
+        assert_eq!(
+            calendar.work_days_between(end_date, end_date - Duration::days(1)),
+            0
+        );
+
+        // Holidays on non-work days take nothing away, whether explicit or from a rule,
+        // unless the date is an extra working date.
+        let saturday = NaiveDate::from_ymd_opt(2023, 8, 26).unwrap();
+        calendar.add_holiday(saturday);
+        calendar.add_recurring_holiday(HolidayRule::FixedDate { month: 8, day: 27 });
+        assert_eq!(calendar.work_days_between(start_date, end_date), 4);
+        calendar.add_working_date(saturday);
+        assert_eq!(calendar.work_days_between(start_date, end_date), 4);
+        calendar.remove_holiday(&saturday);
+        assert_eq!(calendar.work_days_between(start_date, end_date), 5);
+
+        // Over decades, weekend holidays leave the count of full weeks alone.
+        let mut calendar = WorkCalendar::new();
+        let first = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(); // Saturday
+        for week in 0..2000 {
+            calendar.add_holiday(first + Duration::weeks(week));
+        }
+        let last = first + Duration::weeks(2000) - Duration::days(1);
+        assert_eq!(calendar.work_days_between(first, last), 2000 * 5);
+    }
+
+    #[test]
+    fn test_from_str_yaml() {
+        let config = r#"
+        work_days:
+          - Monday
+          - Wednesday
+          - Friday
+        holidays:
+          - 2023-12-25
+        "#;
+        let calendar = WorkCalendar::from_str(config).unwrap();
+        assert!(calendar.is_work_day(&Weekday::Mon));
+        assert!(calendar.is_work_day(&Weekday::Wed));
+        assert!(calendar.is_work_day(&Weekday::Fri));
+        assert!(!calendar.is_work_day(&Weekday::Tue));
+        assert!(calendar.is_holiday(&NaiveDate::from_ymd_opt(2023, 12, 25).unwrap()));
+    }
+
+    #[test]
+    fn test_from_str_json() {
+        let config = r#"
+        {
+            "work_days": ["Monday", "Wednesday", "Friday"],
+            "holidays": ["2023-12-25"]
+        }
+        "#;
+        let calendar = WorkCalendar::from_str(config).unwrap();
+        assert!(calendar.is_work_day(&Weekday::Mon));
+        assert!(calendar.is_work_day(&Weekday::Wed));
+        assert!(calendar.is_work_day(&Weekday::Fri));
+        assert!(!calendar.is_work_day(&Weekday::Tue));
+        assert!(calendar.is_holiday(&NaiveDate::from_ymd_opt(2023, 12, 25).unwrap()));
+    }
+
+    #[test]
+    fn test_snapshot_and_restore() {
+        let christmas = NaiveDate::from_ymd_opt(2023, 12, 25).unwrap();
+        let new_year = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(christmas);
+        let first = calendar.snapshot();
+
+        calendar.add_holiday(new_year);
+        calendar.set_work_days("Mon,Tue").unwrap();
+        let second = calendar.snapshot();
+
+        calendar.remove_holiday(&christmas);
+        calendar.add_work_day(Weekday::Sat);
+        assert!(!calendar.is_holiday(&christmas));
+        assert!(calendar.is_work_day(&Weekday::Sat));
+
+        calendar.restore(&first);
+        assert!(calendar.is_holiday(&christmas));
+        assert!(!calendar.is_holiday(&new_year));
+        assert!(calendar.is_work_day(&Weekday::Fri));
+        assert!(!calendar.is_work_day(&Weekday::Sat));
+
+        // Editing after a restore must not leak into the snapshot.
+        calendar.add_holiday(new_year);
+        calendar.restore(&second);
+        assert!(calendar.is_holiday(&christmas));
+        assert!(calendar.is_holiday(&new_year));
+        assert!(!calendar.is_work_day(&Weekday::Wed));
+
+        calendar.restore(&first);
+        assert!(!calendar.is_holiday(&new_year));
+        assert_eq!(calendar.snapshot(), first);
+    }
+
+    #[test]
+    fn test_snapshot_serialization() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 12, 25).unwrap());
+        let snapshot = calendar.snapshot();
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let restored: CalendarSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, snapshot);
+
+        let mut other = WorkCalendar::new();
+        other.restore(&restored);
+        assert_eq!(other, calendar);
+    }
+
+    #[test]
+    fn test_name() {
+        let mut calendar = WorkCalendar::new();
+        assert_eq!(calendar.name(), None);
+        calendar.set_name("US");
+        assert_eq!(calendar.name(), Some("US"));
+        assert_eq!(calendar.clone().name(), Some("US"));
+
+        let calendar = WorkCalendar::from_str("name: DE\nwork_days: [Mon]").unwrap();
+        assert_eq!(calendar.name(), Some("DE"));
+    }
+
+    #[test]
+    fn test_name_in_errors() {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("US");
+        let start_date = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap();
+        let err = calendar.compute_end_date(start_date, -1).unwrap_err();
+        assert_eq!(
+            err,
+            WorkCalendarError::NegativeDaysWorked {
+                calendar: Some("US".to_string())
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "days_worked must be non-negative (calendar 'US')"
+        );
+
+        let err = calendar.set_work_days("Funday").unwrap_err();
+        assert_eq!(err.calendar(), Some("US"));
+        assert_eq!(
+            err.to_string(),
+            "No valid work days provided (calendar 'US')"
+        );
+    }
+
+    #[test]
+    fn test_merge_names() {
+        let mut us = WorkCalendar::new();
+        us.set_name("US");
+        let mut de = WorkCalendar::new();
+        de.set_name("DE");
+
+        let mut merged = us.clone();
+        merged.merge(&de);
+        assert_eq!(merged.name(), Some("US+DE"));
+
+        let mut unnamed = WorkCalendar::new();
+        unnamed.merge(&de);
+        assert_eq!(unnamed.name(), Some("DE"));
+
+        let mut merged = us.clone();
+        merged.merge(&WorkCalendar::new());
+        assert_eq!(merged.name(), Some("US"));
+    }
+
+    #[test]
+    fn test_display() {
+        let mut calendar = WorkCalendar::new();
+        assert_eq!(calendar.to_string(), "Mon, Tue, Wed, Thu, Fri; 0 holidays");
+
+        calendar.set_name("US");
+        calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 12, 25).unwrap());
+        assert_eq!(
+            calendar.to_string(),
+            "US: Mon, Tue, Wed, Thu, Fri; 1 holiday"
+        );
+    }
+
+    #[test]
+    fn test_default_matches_new() {
+        assert_eq!(WorkCalendar::default(), WorkCalendar::new());
+        let start_date = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap();
+        assert!(WorkCalendar::default()
+            .compute_end_date(start_date, 5)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_from_str_missing_work_days_uses_default_week() {
+        let calendar = WorkCalendar::from_str("holidays: [2023-12-25]").unwrap();
+        assert_eq!(
+            calendar.sorted_work_days(),
+            WorkCalendar::new().sorted_work_days()
+        );
+
+        let calendar = WorkCalendar::from_str("{}").unwrap();
+        assert_eq!(calendar, WorkCalendar::new());
+    }
+
+    #[test]
+    fn test_from_str_empty_work_days_is_error() {
+        assert!(WorkCalendar::from_str("work_days: []").is_err());
+        assert!(WorkCalendar::from_str(r#"{"work_days": []}"#).is_err());
+    }
+
+    #[test]
+    fn test_compute_end_date_no_working_days_in_horizon() {
+        // Only Mondays are work days, and every Monday for eleven years is a holiday.
+        let mut calendar = WorkCalendar::new();
+        calendar.set_work_days("Mon").unwrap();
+        let start_date = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap(); // Monday
+        let mut holiday = start_date;
+        while holiday.year() < 2034 {
+            calendar.add_holiday(holiday);
+            holiday += Duration::days(7);
+        }
+
+        match calendar.compute_end_date(start_date, 1) {
+            Err(WorkCalendarError::NoWorkingDaysInHorizon { searched_until, .. }) => {
+                assert_eq!(
+                    (searched_until - start_date).num_days(),
+                    SEARCH_HORIZON_DAYS
+                );
+            }
+            other => panic!("expected NoWorkingDaysInHorizon, got {:?}", other),
+        }
+
+        // The guard measures the gap between work days, not the total span.
+        calendar.remove_holiday(&NaiveDate::from_ymd_opt(2030, 1, 7).unwrap());
+        let (end_date, _) = calendar.compute_end_date(start_date, 1).unwrap();
+        assert_eq!(end_date, NaiveDate::from_ymd_opt(2030, 1, 7).unwrap());
+        let (end_date, _) = calendar.compute_end_date(start_date, 2).unwrap();
+        assert_eq!(end_date, holiday);
+    }
+
+    #[test]
+    fn test_compute_end_date_no_work_days() {
+        let mut calendar = WorkCalendar::new();
+        calendar.work_days = std::iter::empty().collect();
+        let start_date = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap();
+        assert_eq!(
+            calendar.compute_end_date(start_date, 1),
+            Err(WorkCalendarError::NoWorkDaysDefined { calendar: None })
+        );
+    }
+
+    #[test]
+    fn test_from_str_all_invalid_work_days_is_error() {
+        let config = r#"
+        work_days:
+          - Mondy
+          - Wendsday
+        "#;
+        let err = WorkCalendar::from_str(config).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "work_days specified but none were valid: [Mondy, Wendsday]"
+        );
+    }
+
+    #[test]
+    fn test_checked_add_work_days() {
+        let calendar = WorkCalendar::new();
+        let friday = NaiveDate::from_ymd_opt(2023, 8, 25).unwrap();
+        let saturday = NaiveDate::from_ymd_opt(2023, 8, 26).unwrap();
+        assert_eq!(calendar.checked_add_work_days(friday, 0), Some(friday));
+        assert_eq!(calendar.checked_add_work_days(saturday, 0), Some(saturday));
+        assert_eq!(
+            calendar.checked_add_work_days(friday, 5),
+            NaiveDate::from_ymd_opt(2023, 9, 1)
+        );
+        assert_eq!(
+            calendar.checked_add_work_days(saturday, 1),
+            NaiveDate::from_ymd_opt(2023, 8, 28)
+        );
+        assert_eq!(calendar.checked_add_work_days(saturday, -1), Some(friday));
+        assert_eq!(
+            calendar.checked_sub_work_days(friday, 5),
+            NaiveDate::from_ymd_opt(2023, 8, 18)
+        );
+        assert_eq!(calendar.checked_sub_work_days(friday, i64::MIN), None);
+    }
+
+    #[test]
+    fn test_checked_and_saturating_at_max_working() {
+        // Every day is a work day, so NaiveDate::MAX is a working date.
+        let mut calendar = WorkCalendar::new();
+        calendar
+            .set_work_days("Mon,Tue,Wed,Thu,Fri,Sat,Sun")
+            .unwrap();
+        let near_max = NaiveDate::MAX - Duration::days(2);
+
+        assert_eq!(
+            calendar.checked_add_work_days(near_max, 2),
+            Some(NaiveDate::MAX)
+        );
+        assert_eq!(calendar.checked_add_work_days(near_max, 3), None);
+        assert_eq!(calendar.checked_add_work_days(NaiveDate::MAX, 1), None);
+        assert_eq!(
+            calendar.saturating_add_work_days(near_max, 3),
+            NaiveDate::MAX
+        );
+        assert_eq!(
+            calendar.saturating_add_work_days(near_max, i64::MAX),
+            NaiveDate::MAX
+        );
+    }
+
+    #[test]
+    fn test_checked_and_saturating_at_max_non_working() {
+        // Remove NaiveDate::MAX's weekday so the last representable day is not working.
+        let mut calendar = WorkCalendar::new();
+        calendar
+            .set_work_days("Mon,Tue,Wed,Thu,Fri,Sat,Sun")
+            .unwrap();
+        calendar.remove_work_day(&NaiveDate::MAX.weekday());
+        let last_working = NaiveDate::MAX - Duration::days(1);
+        let start = NaiveDate::MAX - Duration::days(3);
+
+        assert_eq!(calendar.checked_add_work_days(start, 2), Some(last_working));
+        assert_eq!(calendar.checked_add_work_days(start, 3), None);
+        assert_eq!(calendar.saturating_add_work_days(start, 3), last_working);
+        assert_eq!(
+            calendar.saturating_add_work_days(NaiveDate::MAX, 1),
+            NaiveDate::MAX
+        );
+    }
+
+    #[test]
+    fn test_checked_and_saturating_at_min() {
+        let mut calendar = WorkCalendar::new();
+        calendar
+            .set_work_days("Mon,Tue,Wed,Thu,Fri,Sat,Sun")
+            .unwrap();
+        let near_min = NaiveDate::MIN + Duration::days(2);
+        assert_eq!(
+            calendar.checked_sub_work_days(near_min, 2),
+            Some(NaiveDate::MIN)
+        );
+        assert_eq!(calendar.checked_sub_work_days(near_min, 3), None);
+        assert_eq!(
+            calendar.saturating_sub_work_days(near_min, 3),
+            NaiveDate::MIN
+        );
+
+        calendar.remove_work_day(&NaiveDate::MIN.weekday());
+        let first_working = NaiveDate::MIN + Duration::days(1);
+        assert_eq!(
+            calendar.checked_sub_work_days(near_min, 1),
+            Some(first_working)
+        );
+        assert_eq!(calendar.checked_sub_work_days(near_min, 2), None);
+        assert_eq!(
+            calendar.saturating_sub_work_days(near_min, i64::MAX),
+            first_working
+        );
+    }
+
+    /// Day-by-day reference implementation of `compute_end` used to check the fast path.
+    fn naive_is_working_date(calendar: &WorkCalendar, date: &NaiveDate) -> bool {
+        let week = calendar
+            .work_days_effective
+            .range(..=*date)
+            .next_back()
+            .map_or(&calendar.work_days, |(_, week)| week);
+        let weekday_rule = week.contains(date.weekday());
+        let forced = calendar.working_dates.contains(date);
+        let rule_holiday = calendar
+            .recurring_holidays()
+            .iter()
+            .any(|rule| rule.date_in(date.year()) == Some(*date))
+            || calendar
+                .holiday_layers()
+                .iter()
+                .any(|layer| layer.date_in(date.year()) == Some(*date));
+        (weekday_rule || forced) && !calendar.holidays.contains(date) && !rule_holiday
+    }
+
+    fn naive_compute_end(
+        calendar: &WorkCalendar,
+        start_date: NaiveDate,
+        days_worked: i64,
+    ) -> Option<NaiveDate> {
+        let is_working = |date: &NaiveDate| naive_is_working_date(calendar, date);
+        let mut current_date = start_date;
+        let mut remaining_days = days_worked;
+        if is_working(&current_date) {
+            remaining_days -= 1;
+        } else if days_worked == 0 {
+            return None;
+        }
+        while remaining_days > 0 {
+            current_date += Duration::days(1);
+            if is_working(&current_date) {
+                remaining_days -= 1;
+            }
+        }
+        Some(current_date)
+    }
+
+    #[test]
+    fn test_compute_end_matches_naive_randomized() {
+        let mut rng = TestRng(0x2545_f491_4f6c_dd1d);
+        let base = NaiveDate::from_ymd_opt(2019, 6, 1).unwrap();
+        for _ in 0..300 {
+            let calendar = random_calendar(&mut rng);
+            for _ in 0..10 {
+                let start_date = base + Duration::days(rng.below(3000) as i64);
+                let days_worked = rng.below(700) as i64;
+                let end = calendar.compute_end(start_date, days_worked).ok();
+                assert_eq!(
+                    end.map(|end| end.date),
+                    naive_compute_end(&calendar, start_date, days_worked),
+                    "calendar {:?}, start {}, days {}",
+                    calendar,
+                    start_date,
+                    days_worked
+                );
+                if let Some(end) = end {
+                    assert_eq!(end.calendar_days as i64, (end.date - start_date).num_days());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_compute_end_one_day_week_with_holiday_cluster() {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_work_days("Wed").unwrap();
+        let start_date = NaiveDate::from_ymd_opt(2023, 1, 2).unwrap(); // Monday
+        for offset in 100..200 {
+            calendar.add_holiday(start_date + Duration::days(offset));
+        }
+        for days_worked in [1, 2, 10, 14, 15, 16, 50, 500, 5000] {
+            assert_eq!(
+                calendar
+                    .compute_end(start_date, days_worked)
+                    .ok()
+                    .map(|end| end.date),
+                naive_compute_end(&calendar, start_date, days_worked)
+            );
+        }
+    }
 
-impl From<WorkCalendarConfig> for WorkCalendar {
-    fn from(config: WorkCalendarConfig) -> Self {
+    #[test]
+    fn test_compute_end_large_count() {
         let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 12, 25).unwrap());
+        calendar.add_holiday(NaiveDate::from_ymd_opt(2100, 7, 5).unwrap());
+        let start_date = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap();
+        assert_eq!(
+            calendar
+                .compute_end(start_date, 50_000)
+                .ok()
+                .map(|end| end.date),
+            naive_compute_end(&calendar, start_date, 50_000)
+        );
+    }
 
-        if let Some(days) = config.work_days {
-            calendar.work_days = days
-                .into_iter()
-                .filter_map(|day| parse_weekday(&day))
-                .collect();
+    /// Day-by-day reference implementation of `work_days_between`.
+    fn naive_work_days_between(
+        calendar: &WorkCalendar,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> i64 {
+        let mut count = 0;
+        let mut current_date = start_date;
+        while current_date <= end_date {
+            if naive_is_working_date(calendar, &current_date) {
+                count += 1;
+            }
+            current_date += Duration::days(1);
         }
+        count
+    }
 
-        if let Some(dates) = config.holidays {
-            calendar.holidays = dates
-                .into_iter()
-                .filter_map(|date_str| NaiveDate::parse_from_str(&date_str, "%Y-%m-%d").ok())
-                .collect();
+    #[test]
+    fn test_work_days_between_matches_naive_randomized() {
+        let mut rng = TestRng(0x9e37_79b9_7f4a_7c15);
+        let base = NaiveDate::from_ymd_opt(2019, 6, 1).unwrap();
+        for _ in 0..300 {
+            let calendar = random_calendar(&mut rng);
+            for _ in 0..10 {
+                let start_date = base + Duration::days(rng.below(3000) as i64);
+                let end_date = base + Duration::days(rng.below(3000) as i64);
+                assert_eq!(
+                    calendar.work_days_between(start_date, end_date),
+                    naive_work_days_between(&calendar, start_date, end_date),
+                    "calendar {:?}, {} to {}",
+                    calendar,
+                    start_date,
+                    end_date
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_counted_work_days_match_count_randomized() {
+        let mut rng = TestRng(0x2b8e_4f17_c0d9_63a5);
+        let base = NaiveDate::from_ymd_opt(2019, 6, 1).unwrap();
+        for _ in 0..200 {
+            let calendar = random_calendar(&mut rng);
+            for _ in 0..5 {
+                let start_date = base + Duration::days(rng.below(3000) as i64);
+                let end_date = start_date + Duration::days(rng.below(800) as i64 - 100);
+                let counted: Vec<NaiveDate> =
+                    calendar.counted_work_days(start_date, end_date).collect();
+                assert_eq!(
+                    counted.len() as i64,
+                    calendar.work_days_between(start_date, end_date),
+                    "calendar {:?}, {} to {}",
+                    calendar,
+                    start_date,
+                    end_date
+                );
+                assert!(counted.windows(2).all(|pair| pair[0] < pair[1]));
+                assert!(counted
+                    .iter()
+                    .all(|date| calendar.is_working_date(date)
+                        && (start_date..=end_date).contains(date)));
+            }
         }
 
-        calendar
+        let calendar = WorkCalendar::new();
+        let last = NaiveDate::MAX;
+        assert_eq!(
+            calendar
+                .counted_work_days(last - Duration::days(6), last)
+                .count() as i64,
+            calendar.work_days_between(last - Duration::days(6), last)
+        );
     }
-}
 
-/// Parses a weekday string into a `Weekday` enum.
-///
-/// This function is case-insensitive and accepts both full names (e.g., "Monday")
-/// and abbreviations (e.g., "Mon").
-///
-/// # Arguments
-///
-/// * `day` - A string slice that holds the name of the day.
-///
-/// # Returns
-///
-/// * `Some(Weekday)` if the day name is valid.
-/// * `None` if the day name is invalid.
-///
-/// # Examples
-///
-/// ```
-/// use workdays::parse_weekday;
-/// use chrono::Weekday;
-///
-/// assert_eq!(parse_weekday("Monday"), Some(Weekday::Mon));
-/// assert_eq!(parse_weekday("tue"), Some(Weekday::Tue));
-/// assert_eq!(parse_weekday("Invalid"), None);
-/// ```
-pub fn parse_weekday(day: &str) -> Option<Weekday> {
-    match day.to_lowercase().as_str() {
-        "monday" | "mon" => Some(Weekday::Mon),
-        "tuesday" | "tue" => Some(Weekday::Tue),
-        "wednesday" | "wed" => Some(Weekday::Wed),
-        "thursday" | "thu" => Some(Weekday::Thu),
-        "friday" | "fri" => Some(Weekday::Fri),
-        "saturday" | "sat" => Some(Weekday::Sat),
-        "sunday" | "sun" => Some(Weekday::Sun),
-        _ => None,
+    #[test]
+    fn test_holidays_in_range() {
+        let mut calendar = WorkCalendar::new();
+        let dates: Vec<NaiveDate> = [(2023, 1, 2), (2023, 5, 1), (2023, 12, 25), (2024, 1, 1)]
+            .iter()
+            .map(|&(y, m, d)| NaiveDate::from_ymd_opt(y, m, d).unwrap())
+            .collect();
+        for date in dates.iter().rev() {
+            calendar.add_holiday(*date);
+        }
+
+        assert_eq!(calendar.holidays_in_range(..).collect::<Vec<_>>(), dates);
+        assert_eq!(
+            calendar
+                .holidays_in_range(dates[1]..dates[3])
+                .collect::<Vec<_>>(),
+            dates[1..3]
+        );
+        assert_eq!(calendar.holidays_in_range(dates[3]..dates[0]).count(), 0);
+        assert_eq!(calendar.holidays_in_range(dates[1]..dates[1]).count(), 0);
+        assert_eq!(
+            calendar
+                .holidays_in_range(dates[1]..=dates[1])
+                .collect::<Vec<_>>(),
+            vec![dates[1]]
+        );
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_work_days_between_does_not_scale_with_range_length() {
+        // 500 holidays spread over 50 years. Each query covers roughly 500,000 years,
+        // which a day-by-day count could not finish in reasonable time.
+        let mut calendar = WorkCalendar::new();
+        let base = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
+        for i in 0..500 {
+            calendar.add_holiday(base + Duration::days(i * 36 + 1));
+        }
+        let holidays_on_work_days = calendar
+            .holidays_in_range(..)
+            .filter(|date| calendar.is_work_day(&date.weekday()))
+            .count() as i64;
+
+        let fifty_years_end = NaiveDate::from_ymd_opt(2049, 12, 31).unwrap();
+        assert_eq!(
+            calendar.work_days_between(base, fifty_years_end),
+            naive_work_days_between(&calendar, base, fifty_years_end)
+        );
+
+        let start_date = NaiveDate::MIN + Duration::days(3);
+        let end_date = NaiveDate::MAX - Duration::days(3);
+        let no_holidays = WorkCalendar::new();
+        for _ in 0..1000 {
+            assert_eq!(
+                calendar.work_days_between(start_date, end_date),
+                no_holidays.work_days_between(start_date, end_date) - holidays_on_work_days
+            );
+        }
+    }
 
     #[test]
-    fn test_compute_end_date_standard_week() {
-        let calendar = WorkCalendar::new();
-        let start_date = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap(); // Monday
-        let (end_date, duration) = calendar.compute_end_date(start_date, 5).unwrap();
+    fn test_clone_shares_storage() {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("US");
+        calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 7, 4).unwrap());
 
-        assert_eq!(end_date, NaiveDate::from_ymd_opt(2023, 8, 25).unwrap()); // Friday
-        assert_eq!(duration.num_days(), 4);
+        let clone = calendar.clone();
+        assert!(Arc::ptr_eq(
+            calendar.name.as_ref().unwrap(),
+            clone.name.as_ref().unwrap()
+        ));
+        assert!(calendar.work_days.ptr_eq(&clone.work_days));
+        assert!(Arc::ptr_eq(&calendar.holidays, &clone.holidays));
     }
 
     #[test]
-    fn test_compute_end_date_with_weekend() {
+    fn test_clone_has_value_semantics() {
+        let july_4 = NaiveDate::from_ymd_opt(2023, 7, 4).unwrap();
+        let christmas = NaiveDate::from_ymd_opt(2023, 12, 25).unwrap();
+        let mut original = WorkCalendar::new();
+        original.set_name("US");
+        original.add_holiday(july_4);
+
+        let mut clone = original.clone();
+        clone.set_name("DE");
+        clone.add_holiday(christmas);
+        clone.remove_holiday(&july_4);
+        clone.remove_work_day(&Weekday::Fri);
+        clone.add_work_day(Weekday::Sat);
+
+        assert_eq!(original.name(), Some("US"));
+        assert!(original.is_holiday(&july_4));
+        assert!(!original.is_holiday(&christmas));
+        assert!(original.is_work_day(&Weekday::Fri));
+        assert!(!original.is_work_day(&Weekday::Sat));
+
+        let mut merged = original.clone();
+        merged.merge(&clone);
+        assert_eq!(original.name(), Some("US"));
+        assert!(!original.is_holiday(&christmas));
+        assert!(original.is_work_day(&Weekday::Fri));
+
+        // Editing the original after cloning leaves the clone untouched too.
+        let snapshot = original.clone();
+        original.add_holiday(christmas);
+        original.set_work_days("Mon").unwrap();
+        assert!(!snapshot.is_holiday(&christmas));
+        assert!(snapshot.is_work_day(&Weekday::Tue));
+    }
+
+    #[test]
+    fn test_calendar_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<WorkCalendar>();
+        assert_send_sync::<CalendarSnapshot>();
+        assert_send_sync::<FrozenCalendar>();
+        assert_send_sync::<CompactCalendar>();
+        assert_send_sync::<YearCache>();
+    }
+
+    #[test]
+    fn test_month_memo_is_used() {
         let calendar = WorkCalendar::new();
-        let start_date = NaiveDate::from_ymd_opt(2023, 8, 18).unwrap(); // Friday
-        let (end_date, duration) = calendar.compute_end_date(start_date, 5).unwrap();
+        assert_eq!(calendar.month_memo.len(), 0);
+        assert_eq!(calendar.work_days_in_month(2023, 8), 23);
+        assert_eq!(calendar.work_days_in_month(2023, 8), 23);
+        assert_eq!(calendar.month_memo.len(), 1);
+        assert_eq!(calendar.work_days_in_month(2023, 13), 0);
+        assert_eq!(calendar.month_memo.len(), 1);
+        assert_eq!(calendar.clone().month_memo.len(), 0);
+    }
 
-        assert_eq!(end_date, NaiveDate::from_ymd_opt(2023, 8, 24).unwrap()); // Next Thursday
-        assert_eq!(duration.num_days(), 6);
+    #[test]
+    fn test_month_memo_invalidated_by_every_mutation() {
+        fn check(calendar: &mut WorkCalendar, mutate: impl FnOnce(&mut WorkCalendar)) {
+            let first = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+            let december = NaiveDate::from_ymd_opt(2023, 12, 1).unwrap();
+            let last = NaiveDate::from_ymd_opt(2023, 12, 31).unwrap();
+            let before = calendar.work_days_in_month(2023, 12);
+            calendar.work_days_in_year(2023);
+
+            mutate(calendar);
+            let after = calendar.work_days_in_month(2023, 12);
+            assert_ne!(before, after);
+            assert_eq!(after, calendar.work_days_between(december, last));
+            assert_eq!(
+                calendar.work_days_in_year(2023),
+                calendar.work_days_between(first, last)
+            );
+        }
+
+        let christmas = NaiveDate::from_ymd_opt(2023, 12, 25).unwrap();
+        let mut calendar = WorkCalendar::new();
+        check(&mut calendar, |c| c.add_holiday(christmas));
+        check(&mut calendar, |c| c.remove_holiday(&christmas));
+        check(&mut calendar, |c| c.add_work_day(Weekday::Sat));
+        check(&mut calendar, |c| c.remove_work_day(&Weekday::Sat));
+        check(&mut calendar, |c| c.set_work_days("Mon,Tue").unwrap());
+        let snapshot = WorkCalendar::new().snapshot();
+        check(&mut calendar, |c| c.restore(&snapshot));
+        let mut other = WorkCalendar::new();
+        other.add_holiday(christmas);
+        check(&mut calendar, |c| c.merge(&other));
+        let new_years_eve = HolidayRule::FixedDate { month: 12, day: 29 };
+        check(&mut calendar, |c| c.add_recurring_holiday(new_years_eve));
+        check(&mut calendar, |c| {
+            c.remove_recurring_holiday(&new_years_eve)
+        });
     }
 
     #[test]
-    fn test_compute_end_date_with_holiday() {
+    fn test_work_week_summary_tracks_every_mutation() {
+        fn check(calendar: &WorkCalendar, expected: &[Weekday]) {
+            assert!(calendar.work_days.is_consistent());
+            assert_eq!(calendar.work_days.days_per_week(), expected.len() as i64);
+            for day in (0..7u8).map(|n| Weekday::try_from(n).unwrap()) {
+                assert_eq!(
+                    calendar.is_work_day(&day),
+                    expected.contains(&day),
+                    "{}",
+                    day
+                );
+            }
+        }
+
+        use Weekday::*;
         let mut calendar = WorkCalendar::new();
-        calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 8, 23).unwrap()); // Wednesday
-        let start_date = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap(); // Monday
-        let (end_date, duration) = calendar.compute_end_date(start_date, 5).unwrap();
+        check(&calendar, &[Mon, Tue, Wed, Thu, Fri]);
+        calendar.add_work_day(Sat);
+        calendar.add_work_day(Sat);
+        check(&calendar, &[Mon, Tue, Wed, Thu, Fri, Sat]);
+        calendar.remove_work_day(&Mon);
+        calendar.remove_work_day(&Mon);
+        check(&calendar, &[Tue, Wed, Thu, Fri, Sat]);
+        let snapshot = calendar.snapshot();
+        calendar.set_work_days("Sun, Wed").unwrap();
+        check(&calendar, &[Wed, Sun]);
+        assert!(calendar.set_work_days("Funday").is_err());
+        check(&calendar, &[Wed, Sun]);
 
-        assert_eq!(end_date, NaiveDate::from_ymd_opt(2023, 8, 28).unwrap()); // Next Monday
-        assert_eq!(duration.num_days(), 7);
+        let mut other = WorkCalendar::new();
+        other.set_work_days("Wed,Thu").unwrap();
+        calendar.merge(&other);
+        check(&calendar, &[Wed]);
+        calendar.restore(&snapshot);
+        check(&calendar, &[Tue, Wed, Thu, Fri, Sat]);
+
+        let json = serde_json::to_string(&calendar).unwrap();
+        let deserialized: WorkCalendar = serde_json::from_str(&json).unwrap();
+        check(&deserialized, &[Tue, Wed, Thu, Fri, Sat]);
+        let configured = WorkCalendar::from_str("work_days: [Sat, sunday]").unwrap();
+        check(&configured, &[Sat, Sun]);
+        check(&calendar.compact().expand(), &[Tue, Wed, Thu, Fri, Sat]);
+        check(
+            &calendar.clone().freeze().thaw(),
+            &[Tue, Wed, Thu, Fri, Sat],
+        );
+        let bitmap = calendar.working_days_bitmap(2024);
+        let imported = WorkCalendar::from_year_bitmaps(&[(2024, &bitmap)]).unwrap();
+        check(&imported, &[Mon, Tue, Wed, Thu, Fri]);
     }
 
     #[test]
-    fn test_compute_end_date_custom_work_days() {
+    fn test_year_bitmap_round_trip() {
+        let mut rng = TestRng(0x5851_f42d_4c95_7f2d);
+        for _ in 0..20 {
+            let calendar = random_calendar(&mut rng);
+            let bitmaps: Vec<(i32, Vec<bool>)> = (2020..=2028)
+                .map(|year| (year, calendar.working_days_bitmap(year)))
+                .collect();
+            let maps: Vec<(i32, &[bool])> = bitmaps
+                .iter()
+                .map(|(year, bitmap)| (*year, bitmap.as_slice()))
+                .collect();
+            let rebuilt = WorkCalendar::from_year_bitmaps(&maps).unwrap();
+            for (year, bitmap) in &bitmaps {
+                assert_eq!(bitmap.len(), if year % 4 == 0 { 366 } else { 365 });
+                for date in days_of_year(*year) {
+                    assert_eq!(
+                        rebuilt.is_working_date(&date),
+                        calendar.is_working_date(&date)
+                    );
+                }
+                assert_eq!(rebuilt.working_days_bitmap(*year), *bitmap);
+            }
+        }
+    }
+
+    #[test]
+    fn test_year_bitmap_wrong_length() {
+        let bitmap = vec![true; 365];
+        assert_eq!(
+            WorkCalendar::from_year_bitmaps(&[(2024, &bitmap)]),
+            Err(WorkCalendarError::InvalidBitmap {
+                year: 2024,
+                expected: 366,
+                found: 365
+            })
+        );
+        assert!(WorkCalendar::new().working_days_bitmap(i32::MAX).is_empty());
+    }
+
+    #[test]
+    fn test_working_date_overrides() {
         let mut calendar = WorkCalendar::new();
-        calendar.set_work_days("Monday,Wednesday,Friday").unwrap();
-        let start_date = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap(); // Monday
-        let (end_date, duration) = calendar.compute_end_date(start_date, 5).unwrap();
+        let saturday = NaiveDate::from_ymd_opt(2023, 8, 26).unwrap();
+        let monday = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap();
+        calendar.add_working_date(saturday);
+        assert!(calendar.is_working_date_override(&saturday));
+        assert!(calendar.is_working_date(&saturday));
+        assert_eq!(calendar.work_days_between(monday, saturday), 6);
+        assert_eq!(calendar.compute_end(monday, 6).unwrap().date, saturday);
 
-        assert_eq!(end_date, NaiveDate::from_ymd_opt(2023, 8, 30).unwrap()); // Wednesday
-        assert_eq!(duration.num_days(), 9);
+        calendar.add_holiday(saturday);
+        assert!(!calendar.is_working_date(&saturday));
+        assert_eq!(calendar.work_days_between(monday, saturday), 5);
+
+        calendar.remove_holiday(&saturday);
+        calendar.remove_working_date(&saturday);
+        assert!(!calendar.is_working_date(&saturday));
+    }
+
+    fn four_day_from_july() -> WorkCalendar {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("Ops");
+        calendar
+            .set_work_days_effective(date(2025, 7, 1), "Mon,Tue,Wed,Thu")
+            .unwrap();
+        calendar
     }
 
     #[test]
-    fn test_compute_end_date_zero_days() {
-        let calendar = WorkCalendar::new();
-        let start_date = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap(); // Monday
-        let (end_date, duration) = calendar.compute_end_date(start_date, 0).unwrap();
+    fn test_work_days_effective() {
+        use Weekday::*;
+        let mut calendar = four_day_from_july();
+        assert_eq!(
+            calendar.work_days_on(date(2025, 6, 30)),
+            [Mon, Tue, Wed, Thu, Fri]
+        );
+        assert_eq!(
+            calendar.work_days_on(date(2025, 7, 1)),
+            [Mon, Tue, Wed, Thu]
+        );
+        assert!(calendar.is_working_date(&date(2025, 6, 27)));
+        assert!(!calendar.is_working_date(&date(2025, 7, 4)));
+        // The base week still describes the earliest segment.
+        assert!(calendar.is_work_day(&Fri));
 
-        assert_eq!(end_date, start_date);
-        assert_eq!(duration.num_days(), 0);
+        // Five working dates a week before the change, four after.
+        assert_eq!(
+            calendar.work_days_between(date(2025, 6, 23), date(2025, 7, 11)),
+            13
+        );
+        assert_eq!(
+            calendar.work_days_between(date(2025, 7, 1), date(2025, 7, 31)),
+            19
+        );
+        let end = calendar.compute_end(date(2025, 6, 26), 5).unwrap();
+        assert_eq!(end.date, date(2025, 7, 2));
+        assert_eq!(end.calendar_days, 6);
+        assert_eq!(
+            calendar.compute_end(date(2025, 6, 26), 9).unwrap().date,
+            date(2025, 7, 9)
+        );
+
+        // A later change ends the four-day segment; resetting a date replaces it.
+        calendar
+            .set_work_days_effective(date(2025, 8, 1), "Mon,Tue,Wed,Thu,Fri")
+            .unwrap();
+        assert!(calendar.is_working_date(&date(2025, 8, 1)));
+        calendar
+            .set_work_days_effective(date(2025, 8, 1), "Mon,Wed")
+            .unwrap();
+        assert!(!calendar.is_working_date(&date(2025, 8, 1)));
+        assert_eq!(calendar.work_days_on(date(2030, 1, 1)), [Mon, Wed]);
+        assert_eq!(
+            calendar.set_work_days_effective(date(2025, 9, 1), "Funday"),
+            Err(WorkCalendarError::NoValidWorkDays {
+                calendar: Some("Ops".to_string()),
+                input: "Funday".to_string(),
+            })
+        );
     }
 
     #[test]
-    fn test_compute_end_date_negative_days() {
-        let calendar = WorkCalendar::new();
-        let start_date = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap(); // Monday
-        assert!(calendar.compute_end_date(start_date, -1).is_err());
+    fn test_work_days_effective_round_trips() {
+        let calendar = four_day_from_july();
+        let json = serde_json::to_string(&calendar).unwrap();
+        let yaml = serde_yaml::to_string(&calendar).unwrap();
+        assert_eq!(
+            serde_json::from_str::<WorkCalendar>(&json).unwrap(),
+            calendar
+        );
+        assert_eq!(
+            serde_yaml::from_str::<WorkCalendar>(&yaml).unwrap(),
+            calendar
+        );
+        assert_eq!(WorkCalendar::from_str(&json).unwrap(), calendar);
+        assert_eq!(WorkCalendar::from_str(&yaml).unwrap(), calendar);
+
+        // Unsegmented calendars serialize as before.
+        let plain = serde_json::to_string(&WorkCalendar::new()).unwrap();
+        assert!(!plain.contains("work_days_effective"));
+
+        let start = date(2025, 6, 1);
+        let end = date(2025, 8, 31);
+        for other in [
+            calendar.compact().expand(),
+            calendar.clone().freeze().thaw(),
+        ] {
+            assert_eq!(other, calendar);
+        }
+        assert_eq!(
+            calendar.compact().work_days_between(start, end),
+            calendar.work_days_between(start, end)
+        );
+        assert_eq!(
+            calendar.clone().freeze().work_days_between(start, end),
+            calendar.work_days_between(start, end)
+        );
     }
 
     #[test]
-    fn test_set_work_days() {
+    fn test_merge_work_days_effective() {
+        use Weekday::*;
+        let mut a = four_day_from_july();
+        let mut b = WorkCalendar::new();
+        b.set_work_days_effective(date(2025, 8, 1), "Tue,Wed,Thu,Fri")
+            .unwrap();
+        a.merge(&b);
+        assert_eq!(a.work_days_on(date(2025, 6, 30)), [Mon, Tue, Wed, Thu, Fri]);
+        assert_eq!(a.work_days_on(date(2025, 7, 1)), [Mon, Tue, Wed, Thu]);
+        assert_eq!(a.work_days_on(date(2025, 8, 1)), [Tue, Wed, Thu]);
+    }
+
+    #[test]
+    fn test_holiday_layers() {
         let mut calendar = WorkCalendar::new();
-        assert!(calendar.set_work_days("Mon,Wed,Fri").is_ok());
-        assert!(calendar.is_work_day(&Weekday::Mon));
-        assert!(calendar.is_work_day(&Weekday::Wed));
-        assert!(calendar.is_work_day(&Weekday::Fri));
-        assert!(!calendar.is_work_day(&Weekday::Tue));
-        assert!(!calendar.is_work_day(&Weekday::Thu));
-        assert!(!calendar.is_work_day(&Weekday::Sat));
-        assert!(!calendar.is_work_day(&Weekday::Sun));
+        let juneteenth = HolidayRule::FixedDate { month: 6, day: 19 };
+        let regional = HolidayRule::NthWeekday {
+            month: 3,
+            weekday: Weekday::Mon,
+            nth: 2,
+        };
+        calendar
+            .add_holiday_effective(juneteenth, date(2022, 1, 1), None)
+            .unwrap();
+        calendar
+            .add_holiday_effective(regional, date(2000, 1, 1), Some(date(2023, 12, 31)))
+            .unwrap();
+        calendar
+            .add_holiday_effective(date(2024, 3, 15), date(2024, 1, 1), None)
+            .unwrap();
+        // A one-off date outside its own window never applies.
+        calendar
+            .add_holiday_effective(date(2024, 3, 22), date(2025, 1, 1), None)
+            .unwrap();
+
+        // Juneteenth 2021 and 2022 both fall on weekdays.
+        assert!(!calendar.is_holiday(&date(2021, 6, 18)));
+        assert!(calendar.is_working_date(&date(2021, 6, 18)));
+        assert!(calendar.is_holiday(&date(2023, 6, 19)));
+        assert!(!calendar.is_working_date(&date(2023, 6, 19)));
+        // The regional holiday applies in 2023 but was dropped in 2024.
+        assert!(calendar.is_holiday(&date(2023, 3, 13)));
+        assert!(!calendar.is_holiday(&date(2024, 3, 11)));
+        assert!(calendar.is_holiday(&date(2024, 3, 15)));
+        assert!(!calendar.is_holiday(&date(2024, 3, 22)));
+        assert_eq!(
+            calendar.work_days_between(date(2023, 3, 13), date(2023, 3, 17)),
+            4
+        );
+        assert_eq!(
+            calendar.work_days_between(date(2024, 3, 11), date(2024, 3, 15)),
+            4
+        );
+        assert_eq!(
+            calendar.compute_end(date(2023, 6, 16), 2).unwrap().date,
+            date(2023, 6, 20)
+        );
+        assert_eq!(calendar.holiday_layers().len(), 4);
+        assert!(calendar.recurring_holidays().is_empty());
+
+        assert_eq!(
+            calendar.add_holiday_effective(juneteenth, date(2022, 1, 1), Some(date(2021, 1, 1))),
+            Err(WorkCalendarError::InvalidEffectiveWindow {
+                from: date(2022, 1, 1),
+                to: date(2021, 1, 1)
+            })
+        );
+        let layer = calendar.holiday_layers()[1];
+        calendar.remove_holiday_layer(&layer);
+        assert!(!calendar.is_holiday(&date(2023, 3, 13)));
     }
 
     #[test]
-    fn test_add_and_remove_holiday() {
+    fn test_holiday_layers_round_trip() {
         let mut calendar = WorkCalendar::new();
-        let holiday = NaiveDate::from_ymd_opt(2023, 12, 25).unwrap();
-        calendar.add_holiday(holiday);
-        assert!(calendar.is_holiday(&holiday));
-        calendar.remove_holiday(&holiday);
-        assert!(!calendar.is_holiday(&holiday));
+        calendar.add_recurring_holiday(HolidayRule::FixedDate { month: 12, day: 25 });
+        calendar
+            .add_holiday_effective(
+                HolidayRule::FixedDate { month: 6, day: 19 },
+                date(2022, 1, 1),
+                None,
+            )
+            .unwrap();
+        calendar
+            .add_holiday_effective(
+                date(2023, 3, 13),
+                date(2023, 1, 1),
+                Some(date(2023, 12, 31)),
+            )
+            .unwrap();
+        let json = serde_json::to_string(&calendar).unwrap();
+        let yaml = serde_yaml::to_string(&calendar).unwrap();
+        assert_eq!(
+            serde_json::from_str::<WorkCalendar>(&json).unwrap(),
+            calendar
+        );
+        assert_eq!(
+            serde_yaml::from_str::<WorkCalendar>(&yaml).unwrap(),
+            calendar
+        );
+        for other in [
+            calendar.compact().expand(),
+            calendar.clone().freeze().thaw(),
+        ] {
+            assert_eq!(other, calendar);
+        }
+        assert!(!calendar.compact().is_holiday(&date(2022, 6, 20)));
+        assert!(calendar.clone().freeze().is_holiday(&date(2023, 6, 19)));
+
+        let mut merged = WorkCalendar::new();
+        merged.merge(&calendar);
+        assert_eq!(merged.holiday_layers(), calendar.holiday_layers());
     }
 
     #[test]
-    fn test_work_days_between() {
-        let calendar = WorkCalendar::new();
-        let start_date = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap(); // Monday
-        let end_date = NaiveDate::from_ymd_opt(2023, 8, 25).unwrap(); // Friday
-        assert_eq!(calendar.work_days_between(start_date, end_date), 5);
+    fn test_merge_keeps_overrides_working_in_both() {
+        let saturday = NaiveDate::from_ymd_opt(2023, 8, 26).unwrap();
+        let sunday = NaiveDate::from_ymd_opt(2023, 8, 27).unwrap();
+        let mut a = WorkCalendar::new();
+        a.add_working_date(saturday);
+        a.add_working_date(sunday);
+        let mut b = WorkCalendar::new();
+        b.add_work_day(Weekday::Sat);
+        a.merge(&b);
+        assert!(a.is_working_date(&saturday));
+        assert!(!a.is_working_date(&sunday));
+    }
 
-        let end_date = NaiveDate::from_ymd_opt(2023, 8, 27).unwrap(); // Sunday
-        assert_eq!(calendar.work_days_between(start_date, end_date), 5);
+    #[test]
+    fn test_rule_cache_does_not_change_results() {
+        let mut rng = TestRng(0x6c07_8965_d5a3_1b9f);
+        let base = NaiveDate::from_ymd_opt(2015, 1, 1).unwrap();
+        for _ in 0..100 {
+            let cached = random_calendar(&mut rng);
+            let mut uncached = cached.clone();
+            uncached.set_rule_cache_capacity(0);
+            let mut tiny = cached.clone();
+            tiny.set_rule_cache_capacity(1);
+            for _ in 0..10 {
+                let start_date = base + Duration::days(rng.below(6000) as i64);
+                let end_date = base + Duration::days(rng.below(6000) as i64);
+                let days_worked = rng.below(600) as i64;
+                for calendar in [&uncached, &tiny] {
+                    assert_eq!(
+                        calendar.is_working_date(&start_date),
+                        cached.is_working_date(&start_date)
+                    );
+                    assert_eq!(
+                        calendar.work_days_between(start_date, end_date),
+                        cached.work_days_between(start_date, end_date)
+                    );
+                    assert_eq!(
+                        calendar.compute_end(start_date, days_worked),
+                        cached.compute_end(start_date, days_worked)
+                    );
+                }
+            }
+            assert_eq!(uncached.recurring_holidays.cached_years(), 0);
+        }
+    }
 
+    #[test]
+    fn test_rule_cache_dropped_on_rule_change() {
+        let christmas = NaiveDate::from_ymd_opt(2024, 12, 25).unwrap();
+        let boxing_day = NaiveDate::from_ymd_opt(2024, 12, 26).unwrap();
         let mut calendar = WorkCalendar::new();
-        calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 8, 23).unwrap()); // Wednesday
-        assert_eq!(calendar.work_days_between(start_date, end_date), 4);
+        calendar.add_recurring_holiday(HolidayRule::FixedDate { month: 12, day: 25 });
+        assert!(calendar.is_holiday(&christmas));
+        assert!(!calendar.is_holiday(&boxing_day));
+        assert_eq!(calendar.recurring_holidays.cached_years(), 1);
+
+        calendar.add_recurring_holiday(HolidayRule::FixedDate { month: 12, day: 26 });
+        assert_eq!(calendar.recurring_holidays.cached_years(), 0);
+        assert!(calendar.is_holiday(&boxing_day));
+
+        calendar.remove_recurring_holiday(&HolidayRule::FixedDate { month: 12, day: 25 });
+        assert_eq!(calendar.recurring_holidays.cached_years(), 0);
+        assert!(!calendar.is_holiday(&christmas));
+        assert!(calendar.is_holiday(&boxing_day));
     }
 
     #[test]
-    fn test_from_str_yaml() {
-        let config = r#"
-        work_days:
-          - Monday
-          - Wednesday
-          - Friday
-        holidays:
-          - 2023-12-25
-        "#;
-        let calendar = WorkCalendar::from_str(config).unwrap();
-        assert!(calendar.is_work_day(&Weekday::Mon));
-        assert!(calendar.is_work_day(&Weekday::Wed));
-        assert!(calendar.is_work_day(&Weekday::Fri));
-        assert!(!calendar.is_work_day(&Weekday::Tue));
-        assert!(calendar.is_holiday(&NaiveDate::from_ymd_opt(2023, 12, 25).unwrap()));
+    fn test_rule_cache_is_bounded() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_recurring_holiday(HolidayRule::NthWeekday {
+            month: 11,
+            weekday: Weekday::Thu,
+            nth: 4,
+        });
+        calendar.set_rule_cache_capacity(16);
+        let start_date = NaiveDate::from_ymd_opt(1000, 1, 1).unwrap();
+        let end_date = NaiveDate::from_ymd_opt(5999, 12, 31).unwrap();
+        let weekdays = calendar.work_days_between(start_date, end_date);
+        assert_eq!(calendar.recurring_holidays.cached_years(), 16);
+
+        let mut no_rules = calendar.clone();
+        no_rules.remove_recurring_holiday(&calendar.recurring_holidays()[0]);
+        assert_eq!(
+            no_rules.work_days_between(start_date, end_date) - weekdays,
+            5000
+        );
     }
 
     #[test]
-    fn test_from_str_json() {
-        let config = r#"
-        {
-            "work_days": ["Monday", "Wednesday", "Friday"],
-            "holidays": ["2023-12-25"]
+    fn test_compute_start_date() {
+        let date = |d| NaiveDate::from_ymd_opt(2023, 12, d).unwrap();
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("Ops");
+        calendar.add_holiday(date(25));
+        calendar.add_holiday(date(26));
+
+        // Wed 27 counts as a work day; Fri 22 is the one before the holidays.
+        assert_eq!(
+            calendar.compute_start_date(date(27), 2),
+            Ok((date(22), Duration::days(-5)))
+        );
+        assert_eq!(
+            calendar.compute_start_date(date(27), 1),
+            Ok((date(27), Duration::zero()))
+        );
+        assert_eq!(
+            calendar.compute_start_date(date(27), 0),
+            Ok((date(27), Duration::zero()))
+        );
+        // Ending on a weekend or holiday starts the walk on the working date before.
+        for end in [date(23), date(24), date(25), date(26)] {
+            assert_eq!(calendar.compute_start_date(end, 1).unwrap().0, date(22));
+            assert_eq!(calendar.compute_start_date(end, 3).unwrap().0, date(20));
+            assert_eq!(
+                calendar.compute_start_date(end, 0),
+                Err(WorkCalendarError::NonWorkingStart {
+                    calendar: Some("Ops".to_string()),
+                    date: end,
+                })
+            );
         }
-        "#;
-        let calendar = WorkCalendar::from_str(config).unwrap();
-        assert!(calendar.is_work_day(&Weekday::Mon));
-        assert!(calendar.is_work_day(&Weekday::Wed));
-        assert!(calendar.is_work_day(&Weekday::Fri));
-        assert!(!calendar.is_work_day(&Weekday::Tue));
-        assert!(calendar.is_holiday(&NaiveDate::from_ymd_opt(2023, 12, 25).unwrap()));
+        assert_eq!(
+            calendar.compute_start_date(date(27), -1),
+            Err(WorkCalendarError::NegativeDaysWorked {
+                calendar: Some("Ops".to_string())
+            })
+        );
+        assert_eq!(
+            calendar.compute_start_date(NaiveDate::MIN, 3),
+            Err(WorkCalendarError::DateOutOfRange {
+                calendar: Some("Ops".to_string())
+            })
+        );
+
+        let mut idle = WorkCalendar::new();
+        for day in WEEK {
+            idle.remove_work_day(day);
+        }
+        assert_eq!(
+            idle.compute_start_date(date(27), 0),
+            Err(WorkCalendarError::NoWorkDaysDefined { calendar: None })
+        );
+
+        // Only Sundays are worked; December 31, 2023 is a Sunday.
+        let mut sundays = WorkCalendar::new();
+        sundays.set_work_days("Sun").unwrap();
+        sundays.add_holiday(date(24));
+        assert_eq!(
+            sundays.compute_start_date(date(31), 2),
+            Ok((date(17), Duration::days(-14)))
+        );
+        assert_eq!(
+            sundays.compute_start_date(date(30), 1),
+            Ok((date(17), Duration::days(-13)))
+        );
+    }
+
+    #[test]
+    fn test_compute_start_date_inverts_compute_end_date_randomized() {
+        let mut rng = TestRng(0x1a9f_e62c_745b_08d3);
+        let base = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        for _ in 0..200 {
+            let calendar = random_calendar(&mut rng);
+            let date = base + Duration::days(rng.below(3000) as i64);
+            let days_worked = rng.below(300) as i64;
+            let end = match calendar.compute_end_date(date, days_worked) {
+                Ok((end, _)) => end,
+                Err(_) => continue,
+            };
+            let (start, duration) = calendar.compute_start_date(end, days_worked).unwrap();
+            assert_eq!(end + duration, start);
+            if calendar.is_working_date(&date) {
+                assert_eq!(start, date);
+            } else if days_worked > 0 {
+                // The walk back stops at the first working date after `date`.
+                assert_eq!(Ok(start), calendar.next_work_day(date));
+            }
+            if days_worked > 0 {
+                assert_eq!(calendar.work_days_between(start, end), days_worked);
+            }
+
+            let (start, _) = match calendar.compute_start_date(date, days_worked) {
+                Ok(start) => start,
+                Err(_) => continue,
+            };
+            if calendar.is_working_date(&date) {
+                assert_eq!(
+                    calendar.compute_end_date(start, days_worked).unwrap().0,
+                    date
+                );
+            }
+        }
+    }
+
+    /// Runs a fixed set of queries from `seed` and records every answer, so two
+    /// calendars can be compared across the whole query surface.
+    fn query_suite(calendar: &WorkCalendar, seed: u64) -> Vec<String> {
+        let mut rng = TestRng(seed);
+        let base = NaiveDate::from_ymd_opt(2015, 1, 1).unwrap();
+        let filter = CategoryFilter::exclude(&["optional"]);
+        let mut answers = Vec::new();
+        for _ in 0..20 {
+            let start_date = base + Duration::days(rng.below(6000) as i64);
+            let end_date = base + Duration::days(rng.below(6000) as i64);
+            let days = rng.below(400) as i64;
+            let (year, month) = (start_date.year(), start_date.month());
+            answers.push(format!(
+                "{:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?}",
+                calendar.is_holiday(&start_date),
+                calendar.is_working_date(&start_date),
+                calendar.non_working_reason(&start_date),
+                calendar.work_days_between(start_date, end_date),
+                calendar.compute_end(start_date, days),
+                calendar.checked_add_work_days(start_date, -days),
+                calendar.next_work_day(start_date),
+                calendar.work_days_in_month(year, month),
+                calendar.work_days_in_year(year),
+                calendar.nth_work_day_of_month(year, month, -2),
+                calendar.work_day_ordinal(start_date),
+                calendar.work_week_of(start_date),
+                calendar.adjust(start_date, AdjustmentConvention::ModifiedFollowing),
+                calendar.is_working_date_filtered(&start_date, &filter),
+                calendar.work_days_between_filtered(start_date, end_date, &filter),
+            ));
+        }
+        answers
+    }
+
+    /// Answers every query of [`query_suite`] on a fresh clone with the rule cache
+    /// disabled, so no memoized result from an earlier query can be reused.
+    fn uncached_query_suite(calendar: &WorkCalendar, seed: u64) -> Vec<String> {
+        let mut fresh = calendar.clone();
+        fresh.set_rule_cache_capacity(0);
+        query_suite(&fresh, seed)
+    }
+
+    #[test]
+    fn test_query_suite_matches_with_and_without_caches() {
+        let mut rng = TestRng(0x3f85_c0a2_7e19_d64b);
+        for _ in 0..30 {
+            let mut calendar = random_calendar(&mut rng);
+            if rng.below(2) == 0 {
+                calendar.set_rule_cache_capacity(rng.below(3) as usize + 1);
+            }
+            let seed = rng.0;
+            // Warm both caches with unrelated queries first.
+            query_suite(&calendar, !seed);
+            assert_eq!(
+                query_suite(&calendar, seed),
+                uncached_query_suite(&calendar, seed)
+            );
+        }
+    }
+
+    #[test]
+    fn test_no_stale_answers_after_mutation() {
+        type Mutation = fn(&mut WorkCalendar, NaiveDate);
+        let thanksgiving = HolidayRule::NthWeekday {
+            month: 11,
+            weekday: Weekday::Thu,
+            nth: 4,
+        };
+        let mutations: [(&str, Mutation); 20] = [
+            ("add_work_day", |c, _| c.add_work_day(Weekday::Sat)),
+            ("remove_work_day", |c, _| c.remove_work_day(&Weekday::Wed)),
+            ("set_work_days", |c, _| {
+                c.set_work_days("Tue,Thu,Sun").unwrap()
+            }),
+            ("set_work_days_effective", |c, date| {
+                c.set_work_days_effective(date, "Mon,Fri").unwrap()
+            }),
+            ("add_holiday", |c, date| c.add_holiday(date)),
+            ("remove_holiday", |c, date| {
+                let first = c.iter_holidays().find(|holiday| *holiday >= date);
+                if let Some(holiday) = first {
+                    c.remove_holiday(&holiday);
+                }
+            }),
+            ("retain_holidays_in", |c, date| {
+                c.retain_holidays_in(..date);
+            }),
+            ("add_working_date", |c, date| c.add_working_date(date)),
+            ("remove_working_date", |c, date| {
+                let first = c.working_dates.range(date..).next().copied();
+                if let Some(working) = first {
+                    c.remove_working_date(&working);
+                }
+            }),
+            ("add_recurring_holiday", |c, date| {
+                c.add_recurring_holiday(HolidayRule::FixedDate {
+                    month: date.month(),
+                    day: date.day().min(28),
+                })
+            }),
+            ("remove_recurring_holiday", |c, _| {
+                let rule = c.recurring_holidays().first().copied();
+                if let Some(rule) = rule {
+                    c.remove_recurring_holiday(&rule);
+                }
+            }),
+            ("add_holiday_effective", |c, date| {
+                let rule = HolidayRule::FixedDate { month: 3, day: 17 };
+                c.add_holiday_effective(rule, date, None).unwrap()
+            }),
+            ("remove_holiday_layer", |c, _| {
+                let layer = c.holiday_layers().first().copied();
+                if let Some(layer) = layer {
+                    c.remove_holiday_layer(&layer);
+                }
+            }),
+            ("add_holiday_with_category", |c, date| {
+                c.add_holiday_with_category(date, "optional")
+            }),
+            ("add_recurring_holiday_with_category", |c, _| {
+                let rule = HolidayRule::FixedDate { month: 7, day: 4 };
+                c.add_recurring_holiday_with_category(rule, "optional")
+            }),
+            ("set_ordinal_epoch", |c, date| c.set_ordinal_epoch(date)),
+            ("set_rule_cache_capacity", |c, _| {
+                c.set_rule_cache_capacity(2)
+            }),
+            ("merge", |c, date| {
+                let mut other = WorkCalendar::new();
+                other.add_holiday(date);
+                other.add_recurring_holiday(HolidayRule::FixedDate { month: 5, day: 1 });
+                c.merge(&other);
+            }),
+            ("restore", |c, date| {
+                let mut other = c.clone();
+                other.add_holiday(date);
+                other.add_recurring_holiday(HolidayRule::FixedDate { month: 8, day: 8 });
+                let snapshot = other.snapshot();
+                c.restore(&snapshot);
+            }),
+            ("add_partial_holiday", |c, date| c.add_partial_holiday(date)),
+        ];
+
+        let mut rng = TestRng(0xd2a6_41f7_0b8e_c395);
+        let base = NaiveDate::from_ymd_opt(2015, 1, 1).unwrap();
+        for _ in 0..5 {
+            let mut calendar = random_calendar(&mut rng);
+            calendar.add_recurring_holiday(thanksgiving);
+            for (name, mutate) in mutations {
+                let seed = rng.0;
+                query_suite(&calendar, seed);
+                let date = base + Duration::days(rng.below(6000) as i64);
+                mutate(&mut calendar, date);
+                assert_eq!(
+                    query_suite(&calendar, seed),
+                    uncached_query_suite(&calendar, seed),
+                    "stale answers after {}",
+                    name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_weekday_matches_lowercasing_parser() {
+        fn lowercasing_parser(day: &str) -> Option<Weekday> {
+            match day.to_lowercase().as_str() {
+                "monday" | "mon" => Some(Weekday::Mon),
+                "tuesday" | "tue" => Some(Weekday::Tue),
+                "wednesday" | "wed" => Some(Weekday::Wed),
+                "thursday" | "thu" => Some(Weekday::Thu),
+                "friday" | "fri" => Some(Weekday::Fri),
+                "saturday" | "sat" => Some(Weekday::Sat),
+                "sunday" | "sun" => Some(Weekday::Sun),
+                _ => None,
+            }
+        }
+
+        let table = [
+            ("monday", Some(Weekday::Mon)),
+            ("MONDAY", Some(Weekday::Mon)),
+            ("Mon", Some(Weekday::Mon)),
+            ("mOn", Some(Weekday::Mon)),
+            ("tuesday", Some(Weekday::Tue)),
+            ("TUE", Some(Weekday::Tue)),
+            ("Wednesday", Some(Weekday::Wed)),
+            ("wed", Some(Weekday::Wed)),
+            ("thursday", Some(Weekday::Thu)),
+            ("Thu", Some(Weekday::Thu)),
+            ("FRIDAY", Some(Weekday::Fri)),
+            ("fri", Some(Weekday::Fri)),
+            ("Saturday", Some(Weekday::Sat)),
+            ("sat", Some(Weekday::Sat)),
+            ("sunday", Some(Weekday::Sun)),
+            ("SUN", Some(Weekday::Sun)),
+            ("", None),
+            ("mo", None),
+            ("mond", None),
+            ("tues", None),
+            ("thurs", None),
+            (" mon", None),
+            ("mon ", None),
+            ("monday!", None),
+            ("İmon", None),
+            ("Ｍon", None),
+        ];
+        for (input, expected) in table {
+            assert_eq!(parse_weekday(input), expected, "{:?}", input);
+            assert_eq!(
+                parse_weekday(input),
+                lowercasing_parser(input),
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_weekdays() {
+        use Weekday::*;
+        let table: [(&str, &[Weekday]); 13] = [
+            ("", &[]),
+            ("Mon", &[Mon]),
+            ("mon,Tue , wednesday", &[Mon, Tue, Wed]),
+            ("Mon-Fri", &[Mon, Tue, Wed, Thu, Fri]),
+            ("fri - mon", &[Fri, Sat, Sun, Mon]),
+            ("Wed-Wed,", &[Wed]),
+            ("Sun,Mon-Tue,Mon", &[Sun, Mon, Tue]),
+            (" , Sat ,", &[Sat]),
+            ("weekdays", &[Mon, Tue, Wed, Thu, Fri]),
+            ("WEEKEND", &[Sat, Sun]),
+            ("Sun, weekdays, Sat", &[Sun, Mon, Tue, Wed, Thu, Fri, Sat]),
+            ("everyday", &[Mon, Tue, Wed, Thu, Fri, Sat, Sun]),
+            ("weekend,All", &[Sat, Sun, Mon, Tue, Wed, Thu, Fri]),
+        ];
+        for (input, expected) in table {
+            assert_eq!(parse_weekdays(input).unwrap(), expected, "{:?}", input);
+        }
+
+        assert_eq!(
+            parse_weekdays("Mon, Funday, Tue-Blursday, Fri"),
+            Err(WorkCalendarError::InvalidWeekdays(vec![
+                "Funday".to_string(),
+                "Tue-Blursday".to_string()
+            ]))
+        );
+        assert_eq!(
+            parse_weekdays("weekdays, workdays, weekdays-Sat"),
+            Err(WorkCalendarError::InvalidWeekdays(vec![
+                "workdays".to_string(),
+                "weekdays-Sat".to_string()
+            ]))
+        );
     }
 }
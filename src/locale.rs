@@ -0,0 +1,406 @@
+//! Day, month and report words in the languages the descriptions support.
+
+use chrono::Weekday;
+
+/// A language for calendar descriptions such as
+/// [`WorkCalendar::describe_localized`](crate::WorkCalendar::describe_localized).
+///
+/// Only the words the crate writes itself are translated; calendar names and other
+/// configured text are written as given.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::Locale;
+/// use chrono::Weekday;
+///
+/// let locale = Locale::from_tag("de-AT").unwrap();
+/// assert_eq!(locale, Locale::German);
+/// assert_eq!(locale.weekday_name(Weekday::Thu), "Donnerstag");
+/// assert_eq!(locale.month_name(3), Some("März"));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Locale {
+    /// English, the language of [`Display`](std::fmt::Display) and the unlocalized
+    /// descriptions.
+    #[default]
+    English,
+    /// German.
+    German,
+    /// French.
+    French,
+    /// Spanish.
+    Spanish,
+}
+
+/// A noun with its singular and plural forms.
+pub(crate) struct Noun {
+    pub(crate) one: &'static str,
+    pub(crate) other: &'static str,
+}
+
+/// The words and phrases a description is built from.
+pub(crate) struct Words {
+    pub(crate) weekdays: [&'static str; 7],
+    pub(crate) weekday_abbreviations: [&'static str; 7],
+    pub(crate) months: [&'static str; 12],
+    pub(crate) work_day: Noun,
+    pub(crate) work_week: Noun,
+    pub(crate) calendar_day: Noun,
+    pub(crate) weekend_day: Noun,
+    pub(crate) holiday: Noun,
+    pub(crate) recurring_holiday: Noun,
+    pub(crate) no_work_days: &'static str,
+    pub(crate) minus: &'static str,
+    pub(crate) and: &'static str,
+    /// Joins a duration to its calendar span, as in "2 work days, spanning 3 calendar
+    /// days".
+    pub(crate) spanning: &'static str,
+    /// Whether zero takes the singular, as in French.
+    pub(crate) zero_is_singular: bool,
+}
+
+const ENGLISH: Words = Words {
+    weekdays: [
+        "Monday",
+        "Tuesday",
+        "Wednesday",
+        "Thursday",
+        "Friday",
+        "Saturday",
+        "Sunday",
+    ],
+    weekday_abbreviations: ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"],
+    months: [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ],
+    work_day: Noun {
+        one: "work day",
+        other: "work days",
+    },
+    work_week: Noun {
+        one: "work week",
+        other: "work weeks",
+    },
+    calendar_day: Noun {
+        one: "calendar day",
+        other: "calendar days",
+    },
+    weekend_day: Noun {
+        one: "weekend day",
+        other: "weekend days",
+    },
+    holiday: Noun {
+        one: "holiday",
+        other: "holidays",
+    },
+    recurring_holiday: Noun {
+        one: "recurring holiday",
+        other: "recurring holidays",
+    },
+    no_work_days: "no work days",
+    minus: "minus",
+    and: "and",
+    spanning: "spanning",
+    zero_is_singular: false,
+};
+
+const GERMAN: Words = Words {
+    weekdays: [
+        "Montag",
+        "Dienstag",
+        "Mittwoch",
+        "Donnerstag",
+        "Freitag",
+        "Samstag",
+        "Sonntag",
+    ],
+    weekday_abbreviations: ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"],
+    months: [
+        "Januar",
+        "Februar",
+        "März",
+        "April",
+        "Mai",
+        "Juni",
+        "Juli",
+        "August",
+        "September",
+        "Oktober",
+        "November",
+        "Dezember",
+    ],
+    work_day: Noun {
+        one: "Arbeitstag",
+        other: "Arbeitstage",
+    },
+    work_week: Noun {
+        one: "Arbeitswoche",
+        other: "Arbeitswochen",
+    },
+    calendar_day: Noun {
+        one: "Kalendertag",
+        other: "Kalendertage",
+    },
+    weekend_day: Noun {
+        one: "Wochenendtag",
+        other: "Wochenendtage",
+    },
+    holiday: Noun {
+        one: "Feiertag",
+        other: "Feiertage",
+    },
+    recurring_holiday: Noun {
+        one: "wiederkehrender Feiertag",
+        other: "wiederkehrende Feiertage",
+    },
+    no_work_days: "keine Arbeitstage",
+    minus: "minus",
+    and: "und",
+    spanning: "über",
+    zero_is_singular: false,
+};
+
+const FRENCH: Words = Words {
+    weekdays: [
+        "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche",
+    ],
+    weekday_abbreviations: ["lun.", "mar.", "mer.", "jeu.", "ven.", "sam.", "dim."],
+    months: [
+        "janvier",
+        "février",
+        "mars",
+        "avril",
+        "mai",
+        "juin",
+        "juillet",
+        "août",
+        "septembre",
+        "octobre",
+        "novembre",
+        "décembre",
+    ],
+    work_day: Noun {
+        one: "jour ouvré",
+        other: "jours ouvrés",
+    },
+    work_week: Noun {
+        one: "semaine ouvrée",
+        other: "semaines ouvrées",
+    },
+    calendar_day: Noun {
+        one: "jour calendaire",
+        other: "jours calendaires",
+    },
+    weekend_day: Noun {
+        one: "jour de week-end",
+        other: "jours de week-end",
+    },
+    holiday: Noun {
+        one: "jour férié",
+        other: "jours fériés",
+    },
+    recurring_holiday: Noun {
+        one: "jour férié récurrent",
+        other: "jours fériés récurrents",
+    },
+    no_work_days: "aucun jour ouvré",
+    minus: "moins",
+    and: "et",
+    spanning: "sur",
+    zero_is_singular: true,
+};
+
+const SPANISH: Words = Words {
+    weekdays: [
+        "lunes",
+        "martes",
+        "miércoles",
+        "jueves",
+        "viernes",
+        "sábado",
+        "domingo",
+    ],
+    weekday_abbreviations: ["lun", "mar", "mié", "jue", "vie", "sáb", "dom"],
+    months: [
+        "enero",
+        "febrero",
+        "marzo",
+        "abril",
+        "mayo",
+        "junio",
+        "julio",
+        "agosto",
+        "septiembre",
+        "octubre",
+        "noviembre",
+        "diciembre",
+    ],
+    work_day: Noun {
+        one: "día laborable",
+        other: "días laborables",
+    },
+    work_week: Noun {
+        one: "semana laborable",
+        other: "semanas laborables",
+    },
+    calendar_day: Noun {
+        one: "día natural",
+        other: "días naturales",
+    },
+    weekend_day: Noun {
+        one: "día de fin de semana",
+        other: "días de fin de semana",
+    },
+    holiday: Noun {
+        one: "festivo",
+        other: "festivos",
+    },
+    recurring_holiday: Noun {
+        one: "festivo recurrente",
+        other: "festivos recurrentes",
+    },
+    no_work_days: "ningún día laborable",
+    minus: "menos",
+    and: "y",
+    spanning: "en",
+    zero_is_singular: false,
+};
+
+impl Locale {
+    /// Every supported locale.
+    pub const ALL: [Locale; 4] = [
+        Locale::English,
+        Locale::German,
+        Locale::French,
+        Locale::Spanish,
+    ];
+
+    /// Returns the locale for a language tag such as `"de"`, `"fr-CA"` or `"es_MX"`.
+    ///
+    /// Only the language part of the tag is used, and case is ignored.
+    ///
+    /// # Returns
+    ///
+    /// The locale, or `None` if the language is not supported.
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let language = tag.split(['-', '_']).next()?;
+        Locale::ALL
+            .into_iter()
+            .find(|locale| language.eq_ignore_ascii_case(locale.tag()))
+    }
+
+    /// Returns the locale's two-letter language code, e.g. `"de"`.
+    pub fn tag(self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::German => "de",
+            Locale::French => "fr",
+            Locale::Spanish => "es",
+        }
+    }
+
+    /// Returns the full name of `day`, e.g. `"Montag"`.
+    pub fn weekday_name(self, day: Weekday) -> &'static str {
+        self.words().weekdays[day.num_days_from_monday() as usize]
+    }
+
+    /// Returns the abbreviated name of `day`, e.g. `"Mo"`.
+    pub fn weekday_abbreviation(self, day: Weekday) -> &'static str {
+        self.words().weekday_abbreviations[day.num_days_from_monday() as usize]
+    }
+
+    /// Returns the name of `month`, numbered from 1 for January, or `None` if `month`
+    /// is not between 1 and 12.
+    pub fn month_name(self, month: u32) -> Option<&'static str> {
+        let index = month.checked_sub(1)? as usize;
+        self.words().months.get(index).copied()
+    }
+
+    pub(crate) fn words(self) -> &'static Words {
+        match self {
+            Locale::English => &ENGLISH,
+            Locale::German => &GERMAN,
+            Locale::French => &FRENCH,
+            Locale::Spanish => &SPANISH,
+        }
+    }
+
+    /// Writes `count` followed by the singular or plural form of `noun`.
+    pub(crate) fn count(self, count: u64, noun: &Noun) -> String {
+        let singular = count == 1 || (count == 0 && self.words().zero_is_singular);
+        let word = if singular { noun.one } else { noun.other };
+        format!("{} {}", count, word)
+    }
+
+    /// Joins parts as "a", "a and b", or "a, b and c".
+    pub(crate) fn join(self, parts: Vec<String>) -> String {
+        match parts.split_last() {
+            None => String::new(),
+            Some((last, [])) => last.clone(),
+            Some((last, rest)) => format!("{} {} {}", rest.join(", "), self.words().and, last),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_tag() {
+        assert_eq!(Locale::from_tag("en"), Some(Locale::English));
+        assert_eq!(Locale::from_tag("DE"), Some(Locale::German));
+        assert_eq!(Locale::from_tag("fr-CA"), Some(Locale::French));
+        assert_eq!(Locale::from_tag("es_MX"), Some(Locale::Spanish));
+        assert_eq!(Locale::from_tag("nl"), None);
+        assert_eq!(Locale::from_tag(""), None);
+        for locale in Locale::ALL {
+            assert_eq!(Locale::from_tag(locale.tag()), Some(locale));
+        }
+    }
+
+    #[test]
+    fn test_names_golden() {
+        let cases = [
+            (Locale::English, "Wednesday", "Sun", "December"),
+            (Locale::German, "Mittwoch", "So", "Dezember"),
+            (Locale::French, "mercredi", "dim.", "décembre"),
+            (Locale::Spanish, "miércoles", "dom", "diciembre"),
+        ];
+        for (locale, wednesday, sunday, december) in cases {
+            assert_eq!(locale.weekday_name(Weekday::Wed), wednesday);
+            assert_eq!(locale.weekday_abbreviation(Weekday::Sun), sunday);
+            assert_eq!(locale.month_name(12), Some(december));
+            assert_eq!(locale.month_name(0), None);
+            assert_eq!(locale.month_name(13), None);
+        }
+        // English abbreviations match chrono's.
+        for n in 0..7 {
+            let day = Weekday::try_from(n).unwrap();
+            assert_eq!(Locale::English.weekday_abbreviation(day), day.to_string());
+        }
+    }
+
+    #[test]
+    fn test_join() {
+        let parts = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+        assert_eq!(Locale::English.join(parts(&[])), "");
+        assert_eq!(Locale::English.join(parts(&["a"])), "a");
+        assert_eq!(Locale::English.join(parts(&["a", "b"])), "a and b");
+        assert_eq!(Locale::German.join(parts(&["a", "b", "c"])), "a, b und c");
+        assert_eq!(Locale::French.count(0, &FRENCH.holiday), "0 jour férié");
+        assert_eq!(Locale::German.count(0, &GERMAN.holiday), "0 Feiertage");
+    }
+}
@@ -0,0 +1,64 @@
+//! Memoized per-month work-day counts.
+
+use std::{collections::HashMap, fmt, sync::Mutex};
+
+/// A cache of work-day counts keyed by `(year, month)`.
+///
+/// The memo is an implementation detail of `WorkCalendar`: it is never serialized,
+/// it does not take part in equality, and clones start empty so cloning stays cheap.
+/// Every method that changes which dates are working must call
+/// [`MonthMemo::invalidate`].
+#[derive(Default)]
+pub(crate) struct MonthMemo(Mutex<HashMap<(i32, u32), i64>>);
+
+impl MonthMemo {
+    /// Returns the memoized count for `key`, computing and storing it if absent.
+    pub(crate) fn get_or_insert_with(&self, key: (i32, u32), count: impl FnOnce() -> i64) -> i64 {
+        if let Some(count) = self.lock().get(&key) {
+            return *count;
+        }
+        // Compute without holding the lock; a racing thread computes the same value.
+        let count = count();
+        self.lock().insert(key, count);
+        count
+    }
+
+    /// Forgets every memoized count.
+    pub(crate) fn invalidate(&mut self) {
+        self.0
+            .get_mut()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clear();
+    }
+
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<(i32, u32), i64>> {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Clone for MonthMemo {
+    fn clone(&self) -> Self {
+        MonthMemo::default()
+    }
+}
+
+impl PartialEq for MonthMemo {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for MonthMemo {}
+
+impl fmt::Debug for MonthMemo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("MonthMemo")
+    }
+}
@@ -0,0 +1,267 @@
+//! Non-working dates and why each one is not worked.
+
+use crate::WorkCalendar;
+use chrono::{Datelike, NaiveDate};
+use std::ops::{Bound, RangeBounds};
+
+/// Why a date is not a working date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NonWorkingReason {
+    /// The date's weekday is not a work day and it has no working-date override.
+    Weekend,
+    /// The date would be worked but is a holiday.
+    Holiday,
+    /// The date's weekday is not a work day, and it is also a holiday.
+    WeekendAndHoliday,
+}
+
+impl WorkCalendar {
+    /// Returns why `date` is not a working date, or `None` if it is one.
+    ///
+    /// A date with a working-date override ([`WorkCalendar::add_working_date`]) is not
+    /// a weekend, so an override that loses to a holiday gives
+    /// [`NonWorkingReason::Holiday`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{NonWorkingReason, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// let christmas = NaiveDate::from_ymd_opt(2022, 12, 25).unwrap(); // Sunday
+    /// calendar.add_holiday(christmas);
+    /// assert_eq!(
+    ///     calendar.non_working_reason(&christmas),
+    ///     Some(NonWorkingReason::WeekendAndHoliday)
+    /// );
+    /// assert_eq!(calendar.non_working_reason(&christmas.succ_opt().unwrap()), None);
+    /// ```
+    pub fn non_working_reason(&self, date: &NaiveDate) -> Option<NonWorkingReason> {
+        let weekend = !self.work_week_on(*date).contains(date.weekday())
+            && !self.is_working_date_override(date);
+        match (weekend, self.is_holiday(date)) {
+            (false, false) => None,
+            (true, false) => Some(NonWorkingReason::Weekend),
+            (false, true) => Some(NonWorkingReason::Holiday),
+            (true, true) => Some(NonWorkingReason::WeekendAndHoliday),
+        }
+    }
+
+    /// Calculates the number of non-working dates between two dates (inclusive).
+    ///
+    /// Together with [`WorkCalendar::work_days_between`] this accounts for every
+    /// calendar day of the range.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_date` - The starting date.
+    /// * `end_date` - The ending date.
+    ///
+    /// # Returns
+    ///
+    /// The number of non-working dates, or zero if `end_date` is before `start_date`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let start_date = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap(); // Monday
+    /// let end_date = NaiveDate::from_ymd_opt(2023, 9, 3).unwrap(); // Sunday
+    /// assert_eq!(calendar.non_work_days_between(start_date, end_date), 4);
+    /// ```
+    pub fn non_work_days_between(&self, start_date: NaiveDate, end_date: NaiveDate) -> i64 {
+        if start_date > end_date {
+            return 0;
+        }
+        (end_date - start_date).num_days() + 1 - self.work_days_between(start_date, end_date)
+    }
+
+    /// Iterates over the non-working dates in `range`, in ascending order.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The dates to visit, e.g. `start..=end`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// let start = NaiveDate::from_ymd_opt(2023, 12, 22).unwrap(); // Friday
+    /// calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 12, 25).unwrap());
+    /// let closed: Vec<NaiveDate> = calendar
+    ///     .non_working_dates(start..start + chrono::Duration::days(5))
+    ///     .collect();
+    /// assert_eq!(closed.len(), 3);
+    /// ```
+    pub fn non_working_dates<R: RangeBounds<NaiveDate>>(
+        &self,
+        range: R,
+    ) -> impl Iterator<Item = NaiveDate> + '_ {
+        self.non_working_dates_with_reasons(range)
+            .map(|(date, _)| date)
+    }
+
+    /// Iterates over the non-working dates in `range` in ascending order, each with
+    /// the reason it is not worked.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The dates to visit, e.g. `start..=end`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{NonWorkingReason, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// let monday = NaiveDate::from_ymd_opt(2023, 12, 25).unwrap();
+    /// calendar.add_holiday(monday);
+    /// let saturday = NaiveDate::from_ymd_opt(2023, 12, 23).unwrap();
+    /// let reasons: Vec<NonWorkingReason> = calendar
+    ///     .non_working_dates_with_reasons(saturday..=monday)
+    ///     .map(|(_, reason)| reason)
+    ///     .collect();
+    /// assert_eq!(
+    ///     reasons,
+    ///     [
+    ///         NonWorkingReason::Weekend,
+    ///         NonWorkingReason::Weekend,
+    ///         NonWorkingReason::Holiday
+    ///     ]
+    /// );
+    /// ```
+    pub fn non_working_dates_with_reasons<R: RangeBounds<NaiveDate>>(
+        &self,
+        range: R,
+    ) -> impl Iterator<Item = (NaiveDate, NonWorkingReason)> + '_ {
+        let first = match range.start_bound() {
+            Bound::Included(date) => Some(*date),
+            Bound::Excluded(date) => date.succ_opt(),
+            Bound::Unbounded => Some(NaiveDate::MIN),
+        };
+        let last = match range.end_bound() {
+            Bound::Included(date) => Some(*date),
+            Bound::Excluded(date) => date.pred_opt(),
+            Bound::Unbounded => Some(NaiveDate::MAX),
+        };
+        let (first, last) = match (first, last) {
+            (Some(first), Some(last)) => (Some(first), last),
+            _ => (None, NaiveDate::MIN),
+        };
+        // `NaiveDate::iter_days` stops before `NaiveDate::MAX`, so step by hand.
+        std::iter::successors(first, |date| date.succ_opt())
+            .take_while(move |date| *date <= last)
+            .filter_map(move |date| Some((date, self.non_working_reason(&date)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{date, random_calendar, TestRng};
+    use chrono::Duration;
+
+    #[test]
+    fn test_complements_work_days_randomized() {
+        let mut rng = TestRng(0x1f6a_c3d8_705e_94b2);
+        let base = date(2019, 6, 1);
+        for _ in 0..200 {
+            let calendar = random_calendar(&mut rng);
+            for _ in 0..5 {
+                let start = base + Duration::days(rng.below(3000) as i64);
+                let end = start + Duration::days(rng.below(400) as i64 - 20);
+                let total = ((end - start).num_days() + 1).max(0);
+                let non_work = calendar.non_work_days_between(start, end);
+                assert_eq!(calendar.work_days_between(start, end) + non_work, total);
+
+                let dates: Vec<(NaiveDate, NonWorkingReason)> = calendar
+                    .non_working_dates_with_reasons(start..=end)
+                    .collect();
+                assert_eq!(dates.len() as i64, non_work, "{:?}", calendar);
+                for (date, reason) in dates {
+                    assert!(!calendar.is_working_date(&date));
+                    let holiday = matches!(
+                        reason,
+                        NonWorkingReason::Holiday | NonWorkingReason::WeekendAndHoliday
+                    );
+                    assert_eq!(holiday, calendar.is_holiday(&date));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_reasons() {
+        let mut calendar = WorkCalendar::new();
+        calendar
+            .set_work_days_effective(date(2025, 6, 9), "Mon,Tue,Wed,Thu")
+            .unwrap();
+        calendar.add_holiday(date(2025, 6, 4)); // Wednesday
+        calendar.add_holiday(date(2025, 6, 7)); // Saturday
+        calendar.add_working_date(date(2025, 6, 8)); // Sunday
+        calendar.add_working_date(date(2025, 6, 14)); // Saturday
+        calendar.add_holiday(date(2025, 6, 14));
+
+        let found: Vec<(NaiveDate, NonWorkingReason)> = calendar
+            .non_working_dates_with_reasons(date(2025, 6, 2)..date(2025, 6, 16))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (date(2025, 6, 4), NonWorkingReason::Holiday),
+                (date(2025, 6, 7), NonWorkingReason::WeekendAndHoliday),
+                // Fridays are not worked from the 9th.
+                (date(2025, 6, 13), NonWorkingReason::Weekend),
+                (date(2025, 6, 14), NonWorkingReason::Holiday),
+                (date(2025, 6, 15), NonWorkingReason::Weekend),
+            ]
+        );
+        assert_eq!(
+            calendar.non_work_days_between(date(2025, 6, 2), date(2025, 6, 15)),
+            5
+        );
+    }
+
+    #[test]
+    fn test_range_bounds() {
+        let calendar = WorkCalendar::new();
+        let saturday = date(2023, 9, 2);
+        let sunday = date(2023, 9, 3);
+        let first = |dates: &mut dyn Iterator<Item = NaiveDate>| dates.next();
+        assert_eq!(
+            first(&mut calendar.non_working_dates(saturday..)),
+            Some(saturday)
+        );
+        assert_eq!(
+            first(&mut calendar.non_working_dates((Bound::Excluded(saturday), Bound::Unbounded))),
+            Some(sunday)
+        );
+        assert_eq!(calendar.non_working_dates(saturday..saturday).count(), 0);
+        assert_eq!(calendar.non_working_dates(sunday..=saturday).count(), 0);
+        assert_eq!(calendar.non_work_days_between(sunday, saturday), 0);
+        assert_eq!(
+            calendar.non_working_dates(..=NaiveDate::MIN).count(),
+            calendar.non_working_reason(&NaiveDate::MIN).is_some() as usize
+        );
+        assert_eq!(
+            calendar
+                .non_working_dates((Bound::Excluded(NaiveDate::MAX), Bound::Unbounded))
+                .count(),
+            0
+        );
+        assert_eq!(
+            calendar
+                .non_working_dates(NaiveDate::MAX - Duration::days(6)..)
+                .count(),
+            2
+        );
+    }
+}
@@ -0,0 +1,198 @@
+//! Business day numbers counted from a fixed epoch.
+
+use crate::{WorkCalendar, WorkCalendarError};
+use chrono::NaiveDate;
+
+/// The epoch work-day ordinals count from unless a calendar sets another: 1970-01-01.
+pub const DEFAULT_ORDINAL_EPOCH: NaiveDate = match NaiveDate::from_ymd_opt(1970, 1, 1) {
+    Some(date) => date,
+    None => panic!("1970-01-01 is a valid date"),
+};
+
+impl WorkCalendar {
+    /// Returns the epoch work-day ordinals count from, [`DEFAULT_ORDINAL_EPOCH`] unless
+    /// set with [`WorkCalendar::set_ordinal_epoch`].
+    pub fn ordinal_epoch(&self) -> NaiveDate {
+        self.ordinal_epoch
+    }
+
+    /// Sets the epoch work-day ordinals count from.
+    ///
+    /// Ordinals are only comparable between calendars that share an epoch.
+    ///
+    /// # Arguments
+    ///
+    /// * `epoch` - The date from which working dates are numbered.
+    pub fn set_ordinal_epoch(&mut self, epoch: NaiveDate) {
+        self.ordinal_epoch = epoch;
+    }
+
+    /// Returns the business day number of `date`.
+    ///
+    /// The first working date on or after the epoch is number 0, the next is 1, and so
+    /// on; working dates before the epoch count down from -1. For working dates `a` and
+    /// `b` with `a <= b`, `work_day_ordinal(b) - work_day_ordinal(a)` is
+    /// `work_days_between(a, b) - 1`. The count uses
+    /// [`WorkCalendar::work_days_between`], so dates far from the epoch cost no more
+    /// than dates near it.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The date to number.
+    ///
+    /// # Returns
+    ///
+    /// The ordinal of `date`, or `None` if it is not a working date.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+    /// // January 1, 1970 was a Thursday.
+    /// assert_eq!(calendar.work_day_ordinal(date(1970, 1, 1)), Some(0));
+    /// assert_eq!(calendar.work_day_ordinal(date(1970, 1, 4)), None);
+    /// assert_eq!(calendar.work_day_ordinal(date(1970, 1, 5)), Some(2));
+    /// assert_eq!(calendar.work_day_ordinal(date(1969, 12, 31)), Some(-1));
+    /// ```
+    pub fn work_day_ordinal(&self, date: NaiveDate) -> Option<i64> {
+        if !self.is_working_date(&date) {
+            return None;
+        }
+        let epoch = self.ordinal_epoch;
+        Some(if date >= epoch {
+            self.work_days_between(epoch, date) - 1
+        } else {
+            // `date` is before the epoch, so the epoch has a predecessor.
+            -self.work_days_between(date, epoch.pred_opt().unwrap())
+        })
+    }
+
+    /// Returns the working date numbered `ordinal`, the inverse of
+    /// [`WorkCalendar::work_day_ordinal`].
+    ///
+    /// # Arguments
+    ///
+    /// * `ordinal` - The business day number.
+    ///
+    /// # Errors
+    ///
+    /// * [`WorkCalendarError::NoWorkDaysDefined`] if the calendar has no work days.
+    /// * [`WorkCalendarError::NoWorkingDaysInHorizon`] if a run of
+    ///   [`SEARCH_HORIZON_DAYS`](crate::SEARCH_HORIZON_DAYS) days without a working
+    ///   date lies between the epoch and the date.
+    /// * [`WorkCalendarError::DateOutOfRange`] if the date cannot be represented.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let date = NaiveDate::from_ymd_opt(2025, 6, 13).unwrap();
+    /// let ordinal = calendar.work_day_ordinal(date).unwrap();
+    /// assert_eq!(calendar.date_from_ordinal(ordinal).unwrap(), date);
+    /// ```
+    pub fn date_from_ordinal(&self, ordinal: i64) -> Result<NaiveDate, WorkCalendarError> {
+        let epoch = self.ordinal_epoch;
+        let out_of_range = || WorkCalendarError::DateOutOfRange {
+            calendar: self.name().map(String::from),
+        };
+        if ordinal < 0 {
+            // Fewer days than that precede the epoch, so don't walk back to find out.
+            let days_before_epoch = (epoch - NaiveDate::MIN).num_days() as u64;
+            if self.has_any_work_days() && ordinal.unsigned_abs() > days_before_epoch {
+                return Err(out_of_range());
+            }
+            return self.try_add_work_days(epoch, ordinal);
+        }
+        let days_worked = ordinal.checked_add(1).ok_or_else(out_of_range)?;
+        Ok(self.compute_end(epoch, days_worked)?.date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{date, random_calendar, TestRng};
+    use chrono::Duration;
+
+    #[test]
+    fn test_round_trip_randomized() {
+        let mut rng = TestRng(0x5d0c_8a3e_f172_b649);
+        for _ in 0..100 {
+            let mut calendar = random_calendar(&mut rng);
+            if !calendar.has_any_work_days() {
+                continue;
+            }
+            calendar.set_ordinal_epoch(date(2019, 1, 1) + Duration::days(rng.below(3000) as i64));
+            let dates: Vec<NaiveDate> = (0..6)
+                .map(|_| date(2018, 1, 1) + Duration::days(rng.below(5000) as i64))
+                .filter(|date| calendar.is_working_date(date))
+                .collect();
+            for &a in &dates {
+                let ordinal = calendar.work_day_ordinal(a).unwrap();
+                assert_eq!(calendar.date_from_ordinal(ordinal), Ok(a), "{:?}", calendar);
+                for &b in dates.iter().filter(|&&b| b >= a) {
+                    assert_eq!(
+                        calendar.work_day_ordinal(b).unwrap() - ordinal,
+                        calendar.work_days_between(a, b) - 1
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_epoch() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2025, 1, 1));
+        assert_eq!(calendar.ordinal_epoch(), date(1970, 1, 1));
+
+        // A non-working epoch numbers the next working date 0.
+        calendar.set_ordinal_epoch(date(2025, 1, 1));
+        assert_eq!(calendar.work_day_ordinal(date(2025, 1, 1)), None);
+        assert_eq!(calendar.work_day_ordinal(date(2025, 1, 2)), Some(0));
+        assert_eq!(calendar.work_day_ordinal(date(2024, 12, 31)), Some(-1));
+        assert_eq!(calendar.date_from_ordinal(0), Ok(date(2025, 1, 2)));
+        assert_eq!(calendar.date_from_ordinal(-1), Ok(date(2024, 12, 31)));
+        assert_eq!(calendar.date_from_ordinal(2), Ok(date(2025, 1, 6)));
+
+        // Decades away, in both directions.
+        for far in [date(2160, 3, 4), date(1790, 3, 4)] {
+            let ordinal = calendar.work_day_ordinal(far).unwrap();
+            assert_eq!(calendar.date_from_ordinal(ordinal), Ok(far));
+        }
+
+        let json = serde_json::to_string(&calendar).unwrap();
+        assert_eq!(
+            serde_json::from_str::<WorkCalendar>(&json).unwrap(),
+            calendar
+        );
+        assert_eq!(calendar.compact().expand(), calendar);
+        assert_eq!(calendar.clone().freeze().thaw(), calendar);
+        let parsed: WorkCalendar = "holidays: [2025-01-01]\nordinal_epoch: 2025-01-01"
+            .parse()
+            .unwrap();
+        assert_eq!(parsed, calendar);
+        let default = serde_json::to_string(&WorkCalendar::new()).unwrap();
+        assert!(!default.contains("ordinal_epoch"), "{}", default);
+    }
+
+    #[test]
+    fn test_errors() {
+        let calendar = WorkCalendar::new();
+        assert_eq!(
+            calendar.date_from_ordinal(i64::MAX),
+            Err(WorkCalendarError::DateOutOfRange { calendar: None })
+        );
+        assert_eq!(
+            calendar.date_from_ordinal(i64::MIN),
+            Err(WorkCalendarError::DateOutOfRange { calendar: None })
+        );
+    }
+}
@@ -0,0 +1,100 @@
+//! Parallel batch computations, available with the `rayon` feature.
+
+use crate::{EndDate, FrozenCalendar, WorkCalendar, WorkCalendarError};
+use chrono::NaiveDate;
+use rayon::prelude::*;
+
+impl WorkCalendar {
+    /// Computes [`WorkCalendar::compute_end`] for every `(start_date, days_worked)` pair
+    /// in parallel.
+    ///
+    /// The calendar is frozen once and the resulting index is shared by all worker
+    /// threads. Results are returned in input order and are identical to calling
+    /// `compute_end` on each pair in turn.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let start = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap();
+    /// let ends = calendar.par_compute_end_dates(&[(start, 5), (start, 6)]);
+    /// assert_eq!(ends[0].as_ref().unwrap().date, NaiveDate::from_ymd_opt(2023, 8, 25).unwrap());
+    /// assert_eq!(ends[1].as_ref().unwrap().date, NaiveDate::from_ymd_opt(2023, 8, 28).unwrap());
+    /// ```
+    pub fn par_compute_end_dates(
+        &self,
+        inputs: &[(NaiveDate, i64)],
+    ) -> Vec<Result<EndDate, WorkCalendarError>> {
+        self.clone().freeze().par_compute_end_dates(inputs)
+    }
+
+    /// Computes [`WorkCalendar::work_days_between`] for every `(start_date, end_date)`
+    /// pair in parallel.
+    ///
+    /// Results are returned in input order and are identical to calling
+    /// `work_days_between` on each pair in turn.
+    pub fn par_work_days_between(&self, ranges: &[(NaiveDate, NaiveDate)]) -> Vec<i64> {
+        self.clone().freeze().par_work_days_between(ranges)
+    }
+}
+
+impl FrozenCalendar {
+    /// Computes [`FrozenCalendar::compute_end`] for every `(start_date, days_worked)`
+    /// pair in parallel, returning results in input order.
+    pub fn par_compute_end_dates(
+        &self,
+        inputs: &[(NaiveDate, i64)],
+    ) -> Vec<Result<EndDate, WorkCalendarError>> {
+        inputs
+            .par_iter()
+            .map(|&(start_date, days_worked)| self.compute_end(start_date, days_worked))
+            .collect()
+    }
+
+    /// Computes [`FrozenCalendar::work_days_between`] for every `(start_date, end_date)`
+    /// pair in parallel, returning results in input order.
+    pub fn par_work_days_between(&self, ranges: &[(NaiveDate, NaiveDate)]) -> Vec<i64> {
+        ranges
+            .par_iter()
+            .map(|&(start_date, end_date)| self.work_days_between(start_date, end_date))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{random_calendar, TestRng};
+    use chrono::Duration;
+
+    #[test]
+    fn test_parallel_matches_sequential() {
+        let mut rng = TestRng(0x9e37_79b9_7f4a_7c15);
+        let calendar = random_calendar(&mut rng);
+        let base = NaiveDate::from_ymd_opt(2019, 1, 1).unwrap();
+        let dates: Vec<(NaiveDate, NaiveDate, i64)> = (0..300_000)
+            .map(|_| {
+                let start_date = base + Duration::days(rng.below(3650) as i64);
+                let end_date = base + Duration::days(rng.below(3650) as i64);
+                (start_date, end_date, rng.below(120) as i64 - 5)
+            })
+            .collect();
+
+        let inputs: Vec<(NaiveDate, i64)> = dates.iter().map(|d| (d.0, d.2)).collect();
+        let ends = calendar.par_compute_end_dates(&inputs);
+        assert_eq!(ends.len(), inputs.len());
+        for (&(start_date, days_worked), end) in inputs.iter().zip(&ends) {
+            assert_eq!(*end, calendar.compute_end(start_date, days_worked));
+        }
+
+        let ranges: Vec<(NaiveDate, NaiveDate)> = dates.iter().map(|d| (d.0, d.1)).collect();
+        let counts = calendar.par_work_days_between(&ranges);
+        assert_eq!(counts.len(), ranges.len());
+        for (&(start_date, end_date), count) in ranges.iter().zip(&counts) {
+            assert_eq!(*count, calendar.work_days_between(start_date, end_date));
+        }
+    }
+}
@@ -0,0 +1,556 @@
+//! Payday schedules.
+
+use crate::{
+    AdjustmentConvention, CalendarSystem, Gregorian, WorkCalendar, WorkCalendarError,
+    SEARCH_HORIZON_DAYS,
+};
+use chrono::{Datelike, Duration, NaiveDate};
+use std::collections::BTreeSet;
+
+impl WorkCalendar {
+    /// Generates semi-monthly paydays, on the 15th and the last day of each month,
+    /// moved to working dates by `adjustment`.
+    ///
+    /// Only adjusted dates within `start..=end` are returned, so a nominal payday just
+    /// outside the range is included if it is adjusted into it, and vice versa. The
+    /// result is sorted, and paydays that adjust to the same date appear once.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The first date to include.
+    /// * `end` - The last date to include.
+    /// * `adjustment` - How paydays on non-working days are moved.
+    ///
+    /// # Returns
+    ///
+    /// The adjusted paydays in ascending order, or an empty vector if `start` is after
+    /// `end`.
+    ///
+    /// # Errors
+    ///
+    /// Any error from [`WorkCalendar::adjust`] for a nominal payday within the range,
+    /// for example [`WorkCalendarError::NoWorkDaysDefined`] if it must move and the
+    /// calendar has no work days. A nominal payday outside the range that cannot be
+    /// adjusted is left out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{AdjustmentConvention, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let paydays = calendar
+    ///     .paydays_semi_monthly(
+    ///         NaiveDate::from_ymd_opt(2023, 9, 1).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2023, 10, 31).unwrap(),
+    ///         AdjustmentConvention::Preceding,
+    ///     )
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     paydays,
+    ///     [
+    ///         NaiveDate::from_ymd_opt(2023, 9, 15).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2023, 9, 29).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2023, 10, 13).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2023, 10, 31).unwrap(),
+    ///     ]
+    /// );
+    /// ```
+    pub fn paydays_semi_monthly(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+        adjustment: AdjustmentConvention,
+    ) -> Result<Vec<NaiveDate>, WorkCalendarError> {
+        if start > end {
+            return Ok(Vec::new());
+        }
+        let (from, to) = reachable_window(start, end, adjustment);
+        let nominal = (month_index(from)..=month_index(to))
+            .filter_map(month_start)
+            .flat_map(|first| {
+                let length = Gregorian.days_in_month(first.year(), first.month());
+                [
+                    first.with_day(15),
+                    length.and_then(|day| first.with_day(day)),
+                ]
+            })
+            .flatten()
+            .filter(|payday| (from..=to).contains(payday));
+        self.adjust_paydays(nominal, (start, end), adjustment)
+    }
+
+    /// Generates biweekly paydays every 14 days from `anchor`, moved to working dates by
+    /// `adjustment`.
+    ///
+    /// The cadence follows the nominal dates, `anchor` plus a multiple of 14 days, so
+    /// adjusting one payday never shifts the ones after it. The schedule extends in both
+    /// directions from `anchor`, which may lie outside `range`. Only adjusted dates
+    /// within `range` are returned, sorted, with dates that adjust to the same day
+    /// appearing once.
+    ///
+    /// # Arguments
+    ///
+    /// * `anchor` - A known nominal payday, which fixes the weekday and cadence.
+    /// * `range` - The first and last dates to include.
+    /// * `adjustment` - How paydays on non-working days are moved.
+    ///
+    /// # Errors
+    ///
+    /// * [`WorkCalendarError::InvalidAnchor`] if `anchor` is not on one of the
+    ///   calendar's work days. A holiday on a work day is a valid anchor.
+    /// * Any error from [`WorkCalendar::adjust`] for a nominal payday within `range`.
+    ///   A nominal payday outside `range` that cannot be adjusted is left out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{AdjustmentConvention, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let mut calendar = WorkCalendar::new();
+    /// calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 11, 24).unwrap());
+    /// let paydays = calendar
+    ///     .paydays_biweekly(
+    ///         NaiveDate::from_ymd_opt(2023, 11, 10).unwrap(),
+    ///         (
+    ///             NaiveDate::from_ymd_opt(2023, 11, 1).unwrap(),
+    ///             NaiveDate::from_ymd_opt(2023, 12, 31).unwrap(),
+    ///         ),
+    ///         AdjustmentConvention::Preceding,
+    ///     )
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     paydays,
+    ///     [
+    ///         NaiveDate::from_ymd_opt(2023, 11, 10).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2023, 11, 23).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2023, 12, 8).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2023, 12, 22).unwrap(),
+    ///     ]
+    /// );
+    /// ```
+    pub fn paydays_biweekly(
+        &self,
+        anchor: NaiveDate,
+        range: (NaiveDate, NaiveDate),
+        adjustment: AdjustmentConvention,
+    ) -> Result<Vec<NaiveDate>, WorkCalendarError> {
+        if !self.work_week_on(anchor).contains(anchor.weekday()) {
+            return Err(WorkCalendarError::InvalidAnchor {
+                calendar: self.name().map(String::from),
+                anchor,
+            });
+        }
+        let (start, end) = range;
+        if start > end {
+            return Ok(Vec::new());
+        }
+        let (from, to) = reachable_window(start, end, adjustment);
+        let period = |date: NaiveDate| (date - anchor).num_days().div_euclid(14);
+        let nominal = (period(from)..=period(to))
+            .filter_map(|k| anchor.checked_add_signed(Duration::days(14 * k)))
+            .filter(|nominal| (from..=to).contains(nominal));
+        self.adjust_paydays(nominal, (start, end), adjustment)
+    }
+
+    /// Adjusts each nominal payday and returns the distinct results within `range`,
+    /// sorted. A nominal payday outside `range` that cannot be adjusted is skipped,
+    /// since it has no date to land on; one inside `range` is an error.
+    fn adjust_paydays(
+        &self,
+        nominal: impl Iterator<Item = NaiveDate>,
+        range: (NaiveDate, NaiveDate),
+        adjustment: AdjustmentConvention,
+    ) -> Result<Vec<NaiveDate>, WorkCalendarError> {
+        let range = range.0..=range.1;
+        let mut paydays = BTreeSet::new();
+        for nominal in nominal {
+            match self.adjust(nominal, adjustment) {
+                Ok(payday) if range.contains(&payday) => {
+                    paydays.insert(payday);
+                }
+                Ok(_) => {}
+                Err(error) if range.contains(&nominal) => return Err(error),
+                Err(_) => {}
+            }
+        }
+        Ok(paydays.into_iter().collect())
+    }
+}
+
+/// Returns the first and last nominal dates that `adjustment` can move into
+/// `start..=end`, as far as representable.
+///
+/// Following and preceding moves can cross up to [`SEARCH_HORIZON_DAYS`], while the
+/// modified conventions move the other way only within the month.
+fn reachable_window(
+    start: NaiveDate,
+    end: NaiveDate,
+    adjustment: AdjustmentConvention,
+) -> (NaiveDate, NaiveDate) {
+    const MONTH: i64 = 30;
+    let (before, after) = match adjustment {
+        AdjustmentConvention::Unadjusted => (0, 0),
+        AdjustmentConvention::Following => (SEARCH_HORIZON_DAYS, 0),
+        AdjustmentConvention::Preceding => (0, SEARCH_HORIZON_DAYS),
+        AdjustmentConvention::ModifiedFollowing => (MONTH, SEARCH_HORIZON_DAYS),
+        AdjustmentConvention::ModifiedPreceding => (SEARCH_HORIZON_DAYS, MONTH),
+    };
+    (
+        start
+            .checked_sub_signed(Duration::days(before))
+            .unwrap_or(NaiveDate::MIN),
+        end.checked_add_signed(Duration::days(after))
+            .unwrap_or(NaiveDate::MAX),
+    )
+}
+
+/// Numbers months consecutively, so that adjacent months differ by one.
+fn month_index(date: NaiveDate) -> i64 {
+    i64::from(date.year()) * 12 + i64::from(date.month0())
+}
+
+/// Returns the first day of the month numbered by [`month_index`], if representable.
+fn month_start(index: i64) -> Option<NaiveDate> {
+    let year = i32::try_from(index.div_euclid(12)).ok()?;
+    NaiveDate::from_ymd_opt(year, index.rem_euclid(12) as u32 + 1, 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::date;
+    use AdjustmentConvention::*;
+
+    #[test]
+    fn test_semi_monthly_february() {
+        let calendar = WorkCalendar::new();
+        assert_eq!(
+            calendar
+                .paydays_semi_monthly(date(2024, 2, 1), date(2024, 2, 29), Unadjusted)
+                .unwrap(),
+            [date(2024, 2, 15), date(2024, 2, 29)]
+        );
+        // 2021-02-28 is a Sunday.
+        assert_eq!(
+            calendar
+                .paydays_semi_monthly(date(2021, 2, 1), date(2021, 2, 28), Preceding)
+                .unwrap(),
+            [date(2021, 2, 15), date(2021, 2, 26)]
+        );
+        assert_eq!(
+            calendar
+                .paydays_semi_monthly(date(2021, 2, 1), date(2021, 2, 28), Unadjusted)
+                .unwrap(),
+            [date(2021, 2, 15), date(2021, 2, 28)]
+        );
+    }
+
+    #[test]
+    fn test_semi_monthly_holiday_rolls_back_across_weekend() {
+        let mut calendar = WorkCalendar::new();
+        // Presidents' Day, Monday 2021-02-15.
+        calendar.add_holiday(date(2021, 2, 15));
+        assert_eq!(
+            calendar
+                .paydays_semi_monthly(date(2021, 2, 1), date(2021, 3, 31), Preceding)
+                .unwrap(),
+            [
+                date(2021, 2, 12),
+                date(2021, 2, 26),
+                date(2021, 3, 15),
+                date(2021, 3, 31)
+            ]
+        );
+        assert_eq!(
+            calendar
+                .paydays_semi_monthly(date(2021, 2, 1), date(2021, 2, 28), Following)
+                .unwrap(),
+            // Sunday 2021-01-31 follows into February.
+            [date(2021, 2, 1), date(2021, 2, 16)]
+        );
+    }
+
+    #[test]
+    fn test_semi_monthly_range_applies_to_adjusted_dates() {
+        let calendar = WorkCalendar::new();
+        // 2023-09-30 is a Saturday, paid on Friday the 29th.
+        assert_eq!(
+            calendar
+                .paydays_semi_monthly(date(2023, 9, 29), date(2023, 9, 29), Preceding)
+                .unwrap(),
+            [date(2023, 9, 29)]
+        );
+        assert_eq!(
+            calendar
+                .paydays_semi_monthly(date(2023, 9, 30), date(2023, 10, 2), Preceding)
+                .unwrap(),
+            []
+        );
+        // Following moves it into October instead.
+        assert_eq!(
+            calendar
+                .paydays_semi_monthly(date(2023, 10, 1), date(2023, 10, 2), Following)
+                .unwrap(),
+            [date(2023, 10, 2)]
+        );
+        assert_eq!(
+            calendar
+                .paydays_semi_monthly(date(2023, 10, 2), date(2023, 9, 1), Following)
+                .unwrap(),
+            []
+        );
+    }
+
+    #[test]
+    fn test_semi_monthly_collisions_are_deduplicated() {
+        let mut calendar = WorkCalendar::new();
+        for day in 16..=30 {
+            calendar.add_holiday(date(2023, 11, day));
+        }
+        assert_eq!(
+            calendar
+                .paydays_semi_monthly(date(2023, 11, 1), date(2023, 12, 31), Preceding)
+                .unwrap(),
+            [date(2023, 11, 15), date(2023, 12, 15), date(2023, 12, 29)]
+        );
+    }
+
+    #[test]
+    fn test_semi_monthly_without_work_days() {
+        let mut calendar = WorkCalendar::new();
+        for n in 0..7 {
+            calendar.remove_work_day(&chrono::Weekday::try_from(n).unwrap());
+        }
+        assert_eq!(
+            calendar.paydays_semi_monthly(date(2023, 1, 1), date(2023, 12, 31), Preceding),
+            Err(WorkCalendarError::NoWorkDaysDefined { calendar: None })
+        );
+        assert_eq!(
+            calendar
+                .paydays_semi_monthly(date(2023, 1, 1), date(2023, 12, 31), Unadjusted)
+                .unwrap()
+                .len(),
+            24
+        );
+    }
+
+    #[test]
+    fn test_paydays_adjusted_from_far_outside_the_range() {
+        let mut calendar = WorkCalendar::new();
+        // The office is closed from March to the end of June.
+        calendar
+            .add_holiday_range(date(2023, 3, 1), date(2023, 6, 30))
+            .unwrap();
+        // Every payday in the closure rolls forward to Monday July 3rd, months after
+        // its nominal date.
+        let range = (date(2023, 7, 3), date(2023, 7, 3));
+        assert_eq!(
+            calendar.paydays_semi_monthly(range.0, range.1, Following),
+            Ok(vec![date(2023, 7, 3)])
+        );
+        assert_eq!(
+            calendar.paydays_biweekly(date(2023, 1, 6), range, Following),
+            Ok(vec![date(2023, 7, 3)])
+        );
+        // Modified following rolls June paydays back past the whole closure instead.
+        let range = (date(2023, 2, 28), date(2023, 2, 28));
+        assert_eq!(
+            calendar.paydays_semi_monthly(range.0, range.1, ModifiedFollowing),
+            Ok(vec![date(2023, 2, 28)])
+        );
+
+        // A payday outside the range that cannot be adjusted is left out, here the
+        // last one of 2032, which has no working date before it for ten years.
+        let mut calendar = WorkCalendar::new();
+        calendar.set_work_days("Mon").unwrap();
+        calendar
+            .add_holiday_range(date(2022, 12, 1), date(2033, 12, 31))
+            .unwrap();
+        let range = (date(2023, 1, 2), date(2023, 1, 2));
+        assert_eq!(
+            calendar.paydays_semi_monthly(range.0, range.1, Preceding),
+            Ok(vec![])
+        );
+        assert_eq!(
+            calendar.paydays_biweekly(date(2023, 1, 2), range, Preceding),
+            Ok(vec![])
+        );
+    }
+
+    #[test]
+    fn test_semi_monthly_ignores_paydays_that_cannot_reach_the_range() {
+        // Nearly two decades of holidays end well before the range, so paydays that
+        // would have to roll across them never matter.
+        let mut calendar = WorkCalendar::new();
+        calendar
+            .add_holiday_range(date(2005, 1, 1), date(2022, 12, 31))
+            .unwrap();
+        for adjustment in [
+            Unadjusted,
+            Following,
+            Preceding,
+            ModifiedFollowing,
+            ModifiedPreceding,
+        ] {
+            assert_eq!(
+                calendar.paydays_semi_monthly(date(2023, 6, 1), date(2023, 6, 30), adjustment),
+                Ok(vec![date(2023, 6, 15), date(2023, 6, 30)]),
+                "{:?}",
+                adjustment
+            );
+        }
+
+        // A payday in the range that cannot be adjusted is still an error.
+        let mut calendar = WorkCalendar::new();
+        calendar
+            .add_holiday_range(date(2013, 1, 1), date(2023, 12, 31))
+            .unwrap();
+        assert!(matches!(
+            calendar.paydays_semi_monthly(date(2023, 1, 1), date(2023, 1, 31), Preceding),
+            Err(WorkCalendarError::NoWorkingDaysInHorizon { .. })
+        ));
+    }
+
+    #[test]
+    fn test_biweekly_ignores_paydays_that_cannot_reach_the_range() {
+        let mut calendar = WorkCalendar::new();
+        calendar
+            .add_holiday_range(date(2005, 1, 1), date(2022, 12, 31))
+            .unwrap();
+        let june = (date(2023, 6, 1), date(2023, 6, 30));
+        for adjustment in [
+            Unadjusted,
+            Following,
+            Preceding,
+            ModifiedFollowing,
+            ModifiedPreceding,
+        ] {
+            assert_eq!(
+                calendar.paydays_biweekly(date(2023, 6, 2), june, adjustment),
+                Ok(vec![date(2023, 6, 2), date(2023, 6, 16), date(2023, 6, 30)]),
+                "{:?}",
+                adjustment
+            );
+        }
+
+        // A payday in the range that cannot be adjusted is still an error.
+        let mut calendar = WorkCalendar::new();
+        calendar
+            .add_holiday_range(date(2013, 1, 1), date(2023, 12, 31))
+            .unwrap();
+        assert!(matches!(
+            calendar.paydays_biweekly(
+                date(2023, 1, 6),
+                (date(2023, 1, 1), date(2023, 1, 31)),
+                Preceding
+            ),
+            Err(WorkCalendarError::NoWorkingDaysInHorizon { .. })
+        ));
+    }
+
+    #[test]
+    fn test_biweekly_keeps_nominal_cadence_across_years() {
+        let mut calendar = WorkCalendar::new();
+        let holidays = [
+            // Good Fridays.
+            date(2024, 3, 29),
+            date(2025, 4, 18),
+            date(2026, 4, 3),
+            // Christmas and Boxing Day.
+            date(2024, 12, 25),
+            date(2024, 12, 26),
+            date(2025, 12, 25),
+            date(2025, 12, 26),
+            date(2026, 12, 25),
+        ];
+        for holiday in holidays {
+            calendar.add_holiday(holiday);
+        }
+        let anchor = date(2024, 1, 12);
+        let range = (date(2024, 1, 1), date(2026, 12, 31));
+        let paydays = calendar.paydays_biweekly(anchor, range, Preceding).unwrap();
+
+        let moved = [
+            (date(2025, 4, 18), date(2025, 4, 17)),
+            (date(2025, 12, 26), date(2025, 12, 24)),
+            (date(2026, 4, 3), date(2026, 4, 2)),
+            (date(2026, 12, 25), date(2026, 12, 24)),
+        ];
+        let expected: Vec<NaiveDate> = anchor
+            .iter_weeks()
+            .step_by(2)
+            .take_while(|date| *date <= range.1)
+            .map(|nominal| {
+                moved
+                    .iter()
+                    .find(|(from, _)| *from == nominal)
+                    .map_or(nominal, |(_, to)| *to)
+            })
+            .collect();
+        assert_eq!(expected.len(), 78);
+        assert_eq!(paydays, expected);
+        assert_eq!(paydays.last(), Some(&date(2026, 12, 24)));
+    }
+
+    #[test]
+    fn test_biweekly_range_and_anchor() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2023, 12, 1));
+        let anchor = date(2023, 12, 15);
+        // The schedule runs backwards from the anchor too, and a payday adjusted into
+        // the range is included.
+        assert_eq!(
+            calendar.paydays_biweekly(anchor, (date(2023, 11, 30), date(2023, 11, 30)), Preceding),
+            Ok(vec![date(2023, 11, 30)])
+        );
+        assert_eq!(
+            calendar.paydays_biweekly(anchor, (date(2023, 12, 1), date(2023, 12, 14)), Preceding),
+            Ok(vec![])
+        );
+        assert_eq!(
+            calendar.paydays_biweekly(anchor, (date(2023, 12, 1), date(2023, 12, 14)), Following),
+            Ok(vec![date(2023, 12, 4)])
+        );
+        // A holiday anchor still fixes the cadence.
+        assert_eq!(
+            calendar.paydays_biweekly(
+                date(2023, 12, 1),
+                (date(2023, 12, 1), date(2023, 12, 31)),
+                Unadjusted
+            ),
+            Ok(vec![
+                date(2023, 12, 1),
+                date(2023, 12, 15),
+                date(2023, 12, 29)
+            ])
+        );
+        assert_eq!(
+            calendar.paydays_biweekly(anchor, (date(2024, 1, 1), date(2023, 1, 1)), Preceding),
+            Ok(vec![])
+        );
+    }
+
+    #[test]
+    fn test_biweekly_rejects_anchor_off_the_work_week() {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("Payroll");
+        let saturday = date(2023, 12, 16);
+        let error = calendar
+            .paydays_biweekly(saturday, (date(2023, 1, 1), date(2023, 12, 31)), Preceding)
+            .unwrap_err();
+        assert_eq!(
+            error,
+            WorkCalendarError::InvalidAnchor {
+                calendar: Some("Payroll".to_string()),
+                anchor: saturday,
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Anchor date 2023-12-16 (Sat) is not on a work day (calendar 'Payroll')"
+        );
+    }
+}
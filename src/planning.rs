@@ -0,0 +1,1109 @@
+//! Project planning helpers built on work-day arithmetic.
+
+use crate::{WorkCalendar, WorkCalendarError, SEARCH_HORIZON_DAYS};
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+use std::{collections::HashMap, ops::Bound};
+
+/// A run of consecutive working dates, from the working date `start` to the working
+/// date `end`, inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WorkPeriod {
+    /// The first working date of the period.
+    pub start: NaiveDate,
+    /// The last working date of the period.
+    pub end: NaiveDate,
+    /// The number of working dates in the period.
+    pub work_days: i64,
+}
+
+/// A task to place with [`WorkCalendar::schedule_dag`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TaskSpec {
+    /// A unique identifier for the task.
+    pub id: String,
+    /// The task's length in work days. A task of zero work days is a milestone.
+    pub duration_work_days: i64,
+    /// The tasks that must finish before this one starts.
+    pub depends_on: Vec<String>,
+    /// The earliest date the task may start, if any.
+    pub earliest_start: Option<NaiveDate>,
+}
+
+/// A task placed on the calendar by [`WorkCalendar::schedule_dag`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ScheduledTask {
+    /// The task's identifier.
+    pub id: String,
+    /// The first working date of the task.
+    pub start: NaiveDate,
+    /// The last working date of the task, or `start` for a milestone.
+    pub end: NaiveDate,
+}
+
+/// How [`WorkCalendar::buffered_end_date`] rounds a fractional buffer to whole work
+/// days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Round up to the next whole work day.
+    Up,
+    /// Round down to the previous whole work day.
+    Down,
+    /// Round to the nearest whole work day, with exact halves rounding up.
+    Nearest,
+}
+
+/// The result of [`WorkCalendar::buffered_end_date`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BufferedResult {
+    /// The end date without the buffer.
+    pub end_date: NaiveDate,
+    /// The end date with the buffer.
+    pub buffered_end_date: NaiveDate,
+    /// The buffer applied, in whole work days.
+    pub buffer_days: i64,
+}
+
+/// The largest buffer fraction [`WorkCalendar::buffered_end_date`] accepts.
+pub const MAX_BUFFER_FRACTION: f64 = 10.0;
+
+/// Restrictions on the windows [`WorkCalendar::find_window`] may return.
+///
+/// The default has no restrictions.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct WindowConstraints {
+    /// Date ranges, inclusive at both ends, that the window's calendar span must not
+    /// touch. A blackout that ends the day before the window starts, or starts the day
+    /// after it ends, does not overlap it. A range whose start is after its end is
+    /// empty.
+    pub blackouts: Vec<(NaiveDate, NaiveDate)>,
+    /// The latest date the window may end on.
+    pub finish_by: Option<NaiveDate>,
+    /// The weekday the window must start on.
+    pub start_weekday: Option<Weekday>,
+}
+
+impl WorkPeriod {
+    /// Returns the number of calendar dates in the period, counting both ends.
+    pub fn calendar_days(&self) -> i64 {
+        (self.end - self.start).num_days() + 1
+    }
+}
+
+impl WorkCalendar {
+    /// Splits a project into sprints of `sprint_len` work days each.
+    ///
+    /// The first sprint starts on the first working date on or after `start`, and each
+    /// later sprint starts on the working date after the previous one ends, so sprints
+    /// cover `total_work_days` working dates without gaps or overlap. Every sprint has
+    /// exactly `sprint_len` work days except the last, which has whatever remains.
+    /// Holidays lengthen a sprint in calendar days but never change its work days.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The project start date.
+    /// * `total_work_days` - The project's scope in work days.
+    /// * `sprint_len` - The number of work days in each sprint.
+    ///
+    /// # Returns
+    ///
+    /// The sprints in order, or an empty vector if `total_work_days` is zero.
+    ///
+    /// # Errors
+    ///
+    /// * [`WorkCalendarError::InvalidSprintLength`] if `sprint_len` is zero or negative.
+    /// * [`WorkCalendarError::NegativeDaysWorked`] if `total_work_days` is negative.
+    /// * Any error from [`WorkCalendar::compute_end`] while placing a sprint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let sprints = calendar
+    ///     .plan_sprints(NaiveDate::from_ymd_opt(2023, 9, 4).unwrap(), 25, 10)
+    ///     .unwrap();
+    /// assert_eq!(sprints.len(), 3);
+    /// assert_eq!(sprints[0].end, NaiveDate::from_ymd_opt(2023, 9, 15).unwrap());
+    /// assert_eq!(sprints[1].start, NaiveDate::from_ymd_opt(2023, 9, 18).unwrap());
+    /// assert_eq!(sprints[2].work_days, 5);
+    /// ```
+    pub fn plan_sprints(
+        &self,
+        start: NaiveDate,
+        total_work_days: i64,
+        sprint_len: i64,
+    ) -> Result<Vec<WorkPeriod>, WorkCalendarError> {
+        let name = || self.name().map(String::from);
+        if sprint_len <= 0 {
+            return Err(WorkCalendarError::InvalidSprintLength {
+                calendar: name(),
+                length: sprint_len,
+            });
+        }
+        if total_work_days < 0 {
+            return Err(WorkCalendarError::NegativeDaysWorked { calendar: name() });
+        }
+
+        let mut sprints = Vec::new();
+        let mut remaining = total_work_days;
+        let mut next_start = start;
+        while remaining > 0 {
+            let work_days = remaining.min(sprint_len);
+            let sprint_start = self.compute_end(next_start, 1)?.date;
+            let end = self.compute_end(sprint_start, work_days)?.date;
+            sprints.push(WorkPeriod {
+                start: sprint_start,
+                end,
+                work_days,
+            });
+            remaining -= work_days;
+            if remaining > 0 {
+                next_start = end
+                    .succ_opt()
+                    .ok_or(WorkCalendarError::DateOutOfRange { calendar: name() })?;
+            }
+        }
+        Ok(sprints)
+    }
+
+    /// Returns the x-axis of a burndown chart: each working date of a period of
+    /// `total_work_days` work days, paired with the work days remaining after it.
+    ///
+    /// The period starts on the first working date on or after `start`, as in
+    /// [`WorkCalendar::compute_end`]. Each entry counts its own date as done, so the
+    /// first entry has `total_work_days - 1` remaining and the last entry, on the
+    /// date `compute_end` returns, has 0. Non-working dates do not appear.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The first date of the period.
+    /// * `total_work_days` - The number of work days in the period.
+    ///
+    /// # Returns
+    ///
+    /// One `(date, remaining)` pair per work day in date order, or an empty vector if
+    /// `total_work_days` is zero.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`WorkCalendar::compute_end`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let friday = NaiveDate::from_ymd_opt(2023, 9, 1).unwrap();
+    /// assert_eq!(
+    ///     calendar.burndown(friday, 3).unwrap(),
+    ///     [
+    ///         (friday, 2),
+    ///         (NaiveDate::from_ymd_opt(2023, 9, 4).unwrap(), 1),
+    ///         (NaiveDate::from_ymd_opt(2023, 9, 5).unwrap(), 0),
+    ///     ]
+    /// );
+    /// ```
+    pub fn burndown(
+        &self,
+        start: NaiveDate,
+        total_work_days: i64,
+    ) -> Result<Vec<(NaiveDate, i64)>, WorkCalendarError> {
+        // Validates the input and fails up front if the period cannot be placed.
+        let end = self.compute_end(start, total_work_days)?.date;
+        if total_work_days == 0 {
+            return Ok(Vec::new());
+        }
+        let mut date = self.compute_end(start, 1)?.date;
+        let mut axis = Vec::with_capacity(usize::try_from(total_work_days).unwrap_or(0));
+        for remaining in (0..total_work_days).rev() {
+            axis.push((date, remaining));
+            if remaining > 0 {
+                date = self.try_add_work_days(date, 1)?;
+            }
+        }
+        debug_assert_eq!(date, end);
+        Ok(axis)
+    }
+
+    /// Schedules tasks with finish-to-start dependencies.
+    ///
+    /// A task with no dependencies starts on the first working date on or after its
+    /// `earliest_start`, or `project_start` if it has none. A task with dependencies
+    /// starts on the first working date after all of them end, or on its
+    /// `earliest_start` if that is later. Each task then ends as
+    /// [`WorkCalendar::compute_end`] would for its duration; a milestone of zero work
+    /// days ends on the day it starts, and its dependents start the working date
+    /// after.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_start` - The start date for tasks without dependencies or an
+    ///   `earliest_start`.
+    /// * `tasks` - The tasks to schedule, in any order.
+    ///
+    /// # Returns
+    ///
+    /// One scheduled task per input task, in the same order as `tasks`.
+    ///
+    /// # Errors
+    ///
+    /// * [`WorkCalendarError::DuplicateTask`] if two tasks share an id.
+    /// * [`WorkCalendarError::UnknownDependency`] if a task depends on an id that is
+    ///   not in `tasks`.
+    /// * [`WorkCalendarError::DependencyCycle`] if the dependencies form a cycle; it
+    ///   holds the ids along the cycle.
+    /// * [`WorkCalendarError::NegativeDaysWorked`] if a task has a negative duration.
+    /// * Any other error from [`WorkCalendar::compute_end`] while placing a task.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{TaskSpec, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let task = |id: &str, days, depends_on: &[&str]| TaskSpec {
+    ///     id: id.to_string(),
+    ///     duration_work_days: days,
+    ///     depends_on: depends_on.iter().map(|id| id.to_string()).collect(),
+    ///     earliest_start: None,
+    /// };
+    /// let tasks = [
+    ///     task("design", 3, &[]),
+    ///     task("build", 5, &["design"]),
+    ///     task("docs", 2, &["design"]),
+    ///     task("release", 1, &["build", "docs"]),
+    /// ];
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let monday = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+    /// let schedule = calendar.schedule_dag(monday, &tasks).unwrap();
+    /// assert_eq!(schedule[1].start, NaiveDate::from_ymd_opt(2023, 9, 7).unwrap());
+    /// assert_eq!(schedule[1].end, NaiveDate::from_ymd_opt(2023, 9, 13).unwrap());
+    /// assert_eq!(schedule[3].start, NaiveDate::from_ymd_opt(2023, 9, 14).unwrap());
+    /// ```
+    pub fn schedule_dag(
+        &self,
+        project_start: NaiveDate,
+        tasks: &[TaskSpec],
+    ) -> Result<Vec<ScheduledTask>, WorkCalendarError> {
+        let mut index = HashMap::with_capacity(tasks.len());
+        for (i, task) in tasks.iter().enumerate() {
+            if index.insert(task.id.as_str(), i).is_some() {
+                return Err(WorkCalendarError::DuplicateTask(task.id.clone()));
+            }
+        }
+        let mut dependencies = Vec::with_capacity(tasks.len());
+        for task in tasks {
+            let resolved = task
+                .depends_on
+                .iter()
+                .map(|dependency| {
+                    index.get(dependency.as_str()).copied().ok_or_else(|| {
+                        WorkCalendarError::UnknownDependency {
+                            task: task.id.clone(),
+                            dependency: dependency.clone(),
+                        }
+                    })
+                })
+                .collect::<Result<Vec<usize>, _>>()?;
+            dependencies.push(resolved);
+        }
+
+        let mut scheduled: Vec<Option<ScheduledTask>> = vec![None; tasks.len()];
+        let mut on_path = vec![false; tasks.len()];
+        for root in 0..tasks.len() {
+            // Depth-first, with an explicit stack so long chains cannot overflow the
+            // call stack. Each entry is a task and how many dependencies it has visited.
+            let mut stack = vec![(root, 0)];
+            while let Some(&mut (current, ref mut visited)) = stack.last_mut() {
+                if scheduled[current].is_some() {
+                    stack.pop();
+                    continue;
+                }
+                on_path[current] = true;
+                if let Some(&next) = dependencies[current].get(*visited) {
+                    *visited += 1;
+                    if on_path[next] {
+                        let from = stack.iter().position(|&(i, _)| i == next).unwrap_or(0);
+                        let mut chain: Vec<String> = stack[from..]
+                            .iter()
+                            .map(|&(i, _)| tasks[i].id.clone())
+                            .collect();
+                        chain.push(tasks[next].id.clone());
+                        return Err(WorkCalendarError::DependencyCycle(chain));
+                    }
+                    stack.push((next, 0));
+                    continue;
+                }
+
+                let task = &tasks[current];
+                let latest_end = dependencies[current]
+                    .iter()
+                    .filter_map(|&i| scheduled[i].as_ref().map(|done| done.end))
+                    .max();
+                let earliest = match latest_end {
+                    Some(latest_end) => self.try_add_work_days(latest_end, 1)?,
+                    None => project_start,
+                };
+                let earliest = task
+                    .earliest_start
+                    .map_or(earliest, |date| date.max(earliest));
+                let start = self.compute_end(earliest, 1)?.date;
+                let end = self.compute_end(start, task.duration_work_days)?.date;
+                scheduled[current] = Some(ScheduledTask {
+                    id: task.id.clone(),
+                    start,
+                    end,
+                });
+                on_path[current] = false;
+                stack.pop();
+            }
+        }
+        Ok(scheduled.into_iter().flatten().collect())
+    }
+
+    /// Computes an end date with a contingency buffer of `buffer_fraction` times the
+    /// estimate, rounded to whole work days.
+    ///
+    /// Both end dates are computed as by [`WorkCalendar::compute_end`], from the same
+    /// start date. The buffer is `days_worked * buffer_fraction` rounded by `rounding`,
+    /// where [`Rounding::Nearest`] rounds exact halves up. The product is first
+    /// rounded to nine decimal places so that floating-point noise, as in
+    /// `15.0 * 0.2 = 3.0000000000000004`, does not push it over a whole day.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The starting date.
+    /// * `days_worked` - The unbuffered estimate in work days.
+    /// * `buffer_fraction` - The buffer as a fraction of the estimate, such as 0.2 for
+    ///   20%.
+    /// * `rounding` - How to round a fractional buffer.
+    ///
+    /// # Errors
+    ///
+    /// * [`WorkCalendarError::InvalidBufferFraction`] if `buffer_fraction` is
+    ///   negative, greater than [`MAX_BUFFER_FRACTION`], or not a number.
+    /// * Any error from [`WorkCalendar::compute_end`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{Rounding, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let monday = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+    /// let result = calendar
+    ///     .buffered_end_date(monday, 13, 0.2, Rounding::Up)
+    ///     .unwrap();
+    /// assert_eq!(result.end_date, NaiveDate::from_ymd_opt(2023, 9, 20).unwrap());
+    /// assert_eq!(result.buffer_days, 3);
+    /// assert_eq!(result.buffered_end_date, NaiveDate::from_ymd_opt(2023, 9, 25).unwrap());
+    /// ```
+    pub fn buffered_end_date(
+        &self,
+        start: NaiveDate,
+        days_worked: i64,
+        buffer_fraction: f64,
+        rounding: Rounding,
+    ) -> Result<BufferedResult, WorkCalendarError> {
+        if !(0.0..=MAX_BUFFER_FRACTION).contains(&buffer_fraction) {
+            return Err(WorkCalendarError::InvalidBufferFraction(
+                buffer_fraction.to_string(),
+            ));
+        }
+        let end_date = self.compute_end(start, days_worked)?.date;
+        let raw = (days_worked as f64 * buffer_fraction * 1e9).round() / 1e9;
+        let buffer_days = match rounding {
+            Rounding::Up => raw.ceil(),
+            Rounding::Down => raw.floor(),
+            Rounding::Nearest => (raw + 0.5).floor(),
+        } as i64;
+        let buffered_end_date = self
+            .compute_end(start, days_worked.saturating_add(buffer_days))?
+            .date;
+        Ok(BufferedResult {
+            end_date,
+            buffered_end_date,
+            buffer_days,
+        })
+    }
+
+    /// Finds the earliest run of `work_days` working dates starting on or after
+    /// `earliest_start` that satisfies `constraints`.
+    ///
+    /// The window starts on a working date and ends as by
+    /// [`WorkCalendar::compute_end`]. A window overlaps a blackout if any calendar
+    /// date from its start to its end, weekends and holidays included, falls in the
+    /// blackout. When a candidate overlaps, the search resumes after the blackout.
+    ///
+    /// # Arguments
+    ///
+    /// * `earliest_start` - The earliest date the window may start on.
+    /// * `work_days` - The number of working dates the window must contain.
+    /// * `constraints` - Blackouts, a deadline, and a required start weekday.
+    ///
+    /// # Returns
+    ///
+    /// The window, or `None` if no window satisfies the constraints: one would have
+    /// to end after [`WindowConstraints::finish_by`], or no date on or after
+    /// `earliest_start` is a working date on [`WindowConstraints::start_weekday`].
+    ///
+    /// # Errors
+    ///
+    /// * [`WorkCalendarError::NegativeDaysWorked`] if `work_days` is negative.
+    /// * [`WorkCalendarError::NoWorkingDaysInHorizon`] if the start weekday is a work
+    ///   day but none of its dates is a working date within [`SEARCH_HORIZON_DAYS`] of
+    ///   where the search starts, or resumes after a blackout.
+    /// * Any error from [`WorkCalendar::compute_end`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{WindowConstraints, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let constraints = WindowConstraints {
+    ///     blackouts: vec![(
+    ///         NaiveDate::from_ymd_opt(2023, 9, 13).unwrap(),
+    ///         NaiveDate::from_ymd_opt(2023, 9, 15).unwrap(),
+    ///     )],
+    ///     ..WindowConstraints::default()
+    /// };
+    /// let window = calendar
+    ///     .find_window(NaiveDate::from_ymd_opt(2023, 9, 4).unwrap(), 10, constraints)
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert_eq!(window.start, NaiveDate::from_ymd_opt(2023, 9, 18).unwrap());
+    /// assert_eq!(window.end, NaiveDate::from_ymd_opt(2023, 9, 29).unwrap());
+    /// ```
+    pub fn find_window(
+        &self,
+        earliest_start: NaiveDate,
+        work_days: i64,
+        constraints: WindowConstraints,
+    ) -> Result<Option<WorkPeriod>, WorkCalendarError> {
+        if work_days < 0 {
+            return Err(WorkCalendarError::NegativeDaysWorked {
+                calendar: self.name().map(String::from),
+            });
+        }
+        let mut from = earliest_start;
+        loop {
+            let start = match self.next_window_start(from, constraints.start_weekday)? {
+                Some(start) => start,
+                None => return Ok(None),
+            };
+            let end = self.compute_end(start, work_days)?.date;
+            if constraints.finish_by.map_or(false, |by| end > by) {
+                return Ok(None);
+            }
+            let blocked_until = constraints
+                .blackouts
+                .iter()
+                .filter(|(first, last)| *first <= end && *last >= start && first <= last)
+                .map(|(_, last)| *last)
+                .max();
+            match blocked_until {
+                None => {
+                    return Ok(Some(WorkPeriod {
+                        start,
+                        end,
+                        work_days,
+                    }))
+                }
+                Some(last) => match last.succ_opt() {
+                    Some(next) => from = next,
+                    None => return Ok(None),
+                },
+            }
+        }
+    }
+
+    /// Returns the first working date on or after `from`, on `weekday` if given.
+    fn next_window_start(
+        &self,
+        from: NaiveDate,
+        weekday: Option<Weekday>,
+    ) -> Result<Option<NaiveDate>, WorkCalendarError> {
+        let weekday = match weekday {
+            Some(weekday) => weekday,
+            None => return self.compute_end(from, 1).map(|end| Some(end.date)),
+        };
+        // Search one run of a single work week at a time, since the weekday may be
+        // worked in some runs and not in others.
+        let origin = from;
+        let mut from = from;
+        loop {
+            let next_change = self
+                .work_days_effective
+                .range((Bound::Excluded(from), Bound::Unbounded))
+                .next()
+                .map(|(date, _)| *date);
+            let before_change = |date: &NaiveDate| next_change.map_or(true, |next| *date < next);
+            if self.work_week_on(from).contains(weekday) {
+                let offset = (7 + weekday.num_days_from_monday()
+                    - from.weekday().num_days_from_monday())
+                    % 7;
+                let mut date = from.checked_add_days(Days::new(u64::from(offset)));
+                while let Some(candidate) = date.filter(before_change) {
+                    if self.is_working_date(&candidate) {
+                        return Ok(Some(candidate));
+                    }
+                    if (candidate - origin).num_days() >= SEARCH_HORIZON_DAYS {
+                        return Err(WorkCalendarError::NoWorkingDaysInHorizon {
+                            calendar: self.name().map(String::from),
+                            searched_until: candidate,
+                        });
+                    }
+                    date = candidate.checked_add_days(Days::new(7));
+                }
+            } else {
+                // Only working-date overrides can fall on this weekday.
+                let found = self
+                    .working_dates
+                    .range(from..)
+                    .take_while(|date| before_change(date))
+                    .find(|date| date.weekday() == weekday && !self.is_holiday(date));
+                if let Some(date) = found {
+                    return Ok(Some(*date));
+                }
+            }
+            match next_change {
+                Some(next) => from = next,
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{date, random_calendar, TestRng};
+    use chrono::Duration;
+
+    #[test]
+    fn test_sprints_across_christmas_shutdown() {
+        let mut calendar = WorkCalendar::new();
+        for day in 25..=29 {
+            calendar.add_holiday(date(2023, 12, day));
+        }
+        calendar.add_holiday(date(2024, 1, 1));
+        // Starts on a Saturday, so the first sprint starts on Monday.
+        let sprints = calendar.plan_sprints(date(2023, 12, 2), 35, 10).unwrap();
+        let expected = [
+            (date(2023, 12, 4), date(2023, 12, 15), 10),
+            (date(2023, 12, 18), date(2024, 1, 8), 10),
+            (date(2024, 1, 9), date(2024, 1, 22), 10),
+            (date(2024, 1, 23), date(2024, 1, 29), 5),
+        ];
+        let actual: Vec<_> = sprints
+            .iter()
+            .map(|sprint| (sprint.start, sprint.end, sprint.work_days))
+            .collect();
+        assert_eq!(actual, expected);
+        let lengths: Vec<i64> = sprints.iter().map(WorkPeriod::calendar_days).collect();
+        assert_eq!(lengths, [12, 22, 14, 7]);
+
+        for sprint in &sprints {
+            assert_eq!(
+                calendar.work_days_between(sprint.start, sprint.end),
+                sprint.work_days
+            );
+        }
+        for pair in sprints.windows(2) {
+            assert_eq!(calendar.next_work_day(pair[0].end), Ok(pair[1].start));
+        }
+    }
+
+    #[test]
+    fn test_sprints_invalid_input() {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("Team");
+        let start = date(2023, 9, 4);
+        for length in [0, -3] {
+            assert_eq!(
+                calendar.plan_sprints(start, 20, length),
+                Err(WorkCalendarError::InvalidSprintLength {
+                    calendar: Some("Team".to_string()),
+                    length,
+                })
+            );
+        }
+        assert_eq!(
+            calendar.plan_sprints(start, -1, 10),
+            Err(WorkCalendarError::NegativeDaysWorked {
+                calendar: Some("Team".to_string())
+            })
+        );
+        assert_eq!(calendar.plan_sprints(start, 0, 10), Ok(vec![]));
+        assert_eq!(
+            calendar.plan_sprints(start, 3, 10),
+            Ok(vec![WorkPeriod {
+                start,
+                end: date(2023, 9, 6),
+                work_days: 3,
+            }])
+        );
+    }
+
+    #[test]
+    fn test_burndown_matches_compute_end() {
+        let mut rng = TestRng(0x8cb9_2ba7_2f3d_8dd7);
+        for _ in 0..20 {
+            let calendar = random_calendar(&mut rng);
+            let base = date(2019, 6, 1);
+            for _ in 0..20 {
+                let start = base + chrono::Duration::days(rng.below(3650) as i64);
+                let total = rng.below(120) as i64 + 1;
+                let axis = calendar.burndown(start, total).unwrap();
+                let end = calendar.compute_end_date(start, total).unwrap().0;
+
+                assert_eq!(axis.len() as i64, total);
+                assert_eq!(axis.first().unwrap().1, total - 1);
+                assert_eq!(axis.last(), Some(&(end, 0)));
+                assert!(axis.iter().all(|(date, _)| calendar.is_working_date(date)));
+                for pair in axis.windows(2) {
+                    assert_eq!(calendar.next_work_day(pair[0].0), Ok(pair[1].0));
+                    assert_eq!(pair[0].1 - 1, pair[1].1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_burndown_skips_holidays() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2023, 12, 25));
+        let axis = calendar.burndown(date(2023, 12, 21), 4).unwrap();
+        assert_eq!(
+            axis,
+            [
+                (date(2023, 12, 21), 3),
+                (date(2023, 12, 22), 2),
+                (date(2023, 12, 26), 1),
+                (date(2023, 12, 27), 0),
+            ]
+        );
+        assert_eq!(calendar.burndown(date(2023, 12, 21), 0), Ok(vec![]));
+        assert_eq!(
+            calendar.burndown(date(2023, 12, 21), -1),
+            Err(WorkCalendarError::NegativeDaysWorked { calendar: None })
+        );
+    }
+
+    fn task(id: &str, days: i64, depends_on: &[&str]) -> TaskSpec {
+        TaskSpec {
+            id: id.to_string(),
+            duration_work_days: days,
+            depends_on: depends_on.iter().map(|id| id.to_string()).collect(),
+            earliest_start: None,
+        }
+    }
+
+    #[test]
+    fn test_schedule_dag() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2023, 9, 15));
+        let mut tasks = vec![
+            task("release", 2, &["build", "docs", "review"]),
+            task("build", 5, &["design"]),
+            task("design", 3, &[]),
+            task("docs", 2, &["design"]),
+            task("review", 0, &["docs"]),
+            task("hiring", 5, &[]),
+        ];
+        tasks[5].earliest_start = Some(date(2023, 9, 9));
+        tasks[3].earliest_start = Some(date(2023, 9, 11));
+
+        let schedule = calendar.schedule_dag(date(2023, 9, 4), &tasks).unwrap();
+        let actual: Vec<_> = schedule
+            .iter()
+            .map(|task| (task.id.as_str(), task.start, task.end))
+            .collect();
+        assert_eq!(
+            actual,
+            [
+                ("release", date(2023, 9, 14), date(2023, 9, 18)),
+                ("build", date(2023, 9, 7), date(2023, 9, 13)),
+                ("design", date(2023, 9, 4), date(2023, 9, 6)),
+                ("docs", date(2023, 9, 11), date(2023, 9, 12)),
+                ("review", date(2023, 9, 13), date(2023, 9, 13)),
+                // A Saturday earliest start moves to Monday, and the holiday on Friday
+                // stretches the task into the next week.
+                ("hiring", date(2023, 9, 11), date(2023, 9, 18)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_schedule_dag_long_chain() {
+        let ids: Vec<String> = (0..50_000).map(|i| i.to_string()).collect();
+        let tasks: Vec<TaskSpec> = ids
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(i, id)| TaskSpec {
+                id: id.clone(),
+                duration_work_days: 1,
+                depends_on: i
+                    .checked_sub(1)
+                    .map(|prev| ids[prev].clone())
+                    .into_iter()
+                    .collect(),
+                earliest_start: None,
+            })
+            .collect();
+        let calendar = WorkCalendar::new();
+        let start = date(2023, 9, 4);
+        let schedule = calendar.schedule_dag(start, &tasks).unwrap();
+        assert_eq!(
+            schedule[0].end,
+            calendar.compute_end(start, ids.len() as i64).unwrap().date
+        );
+    }
+
+    #[test]
+    fn test_schedule_dag_errors() {
+        let calendar = WorkCalendar::new();
+        let start = date(2023, 9, 4);
+        let cycle = [
+            task("a", 1, &[]),
+            task("b", 1, &["a", "d"]),
+            task("c", 1, &["b"]),
+            task("d", 1, &["c"]),
+        ];
+        assert_eq!(
+            calendar.schedule_dag(start, &cycle),
+            Err(WorkCalendarError::DependencyCycle(
+                ["b", "d", "c", "b"]
+                    .iter()
+                    .map(|id| id.to_string())
+                    .collect()
+            ))
+        );
+        assert_eq!(
+            calendar
+                .schedule_dag(start, &[task("a", 1, &["a"])])
+                .unwrap_err()
+                .to_string(),
+            "Dependency cycle: a -> a"
+        );
+        assert_eq!(
+            calendar.schedule_dag(start, &[task("a", 1, &[]), task("a", 2, &[])]),
+            Err(WorkCalendarError::DuplicateTask("a".to_string()))
+        );
+        assert_eq!(
+            calendar.schedule_dag(start, &[task("a", 1, &["missing"])]),
+            Err(WorkCalendarError::UnknownDependency {
+                task: "a".to_string(),
+                dependency: "missing".to_string(),
+            })
+        );
+        assert_eq!(
+            calendar.schedule_dag(start, &[task("a", -1, &[])]),
+            Err(WorkCalendarError::NegativeDaysWorked { calendar: None })
+        );
+        assert_eq!(calendar.schedule_dag(start, &[]), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_buffered_end_date_rounding() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2023, 9, 22));
+        let monday = date(2023, 9, 4);
+        let cases = [
+            // 13 × 0.2 = 2.6
+            (13, 0.2, Rounding::Up, 3),
+            (13, 0.2, Rounding::Down, 2),
+            (13, 0.2, Rounding::Nearest, 3),
+            // 15 × 0.2 is 3.0000000000000004 in floating point.
+            (15, 0.2, Rounding::Up, 3),
+            (15, 0.2, Rounding::Nearest, 3),
+            // Exact halves round up with Nearest.
+            (5, 0.1, Rounding::Nearest, 1),
+            (5, 0.1, Rounding::Down, 0),
+            (15, 0.1, Rounding::Nearest, 2),
+            (10, 0.0, Rounding::Up, 0),
+            (0, 0.5, Rounding::Up, 0),
+            (10, 10.0, Rounding::Down, 100),
+        ];
+        for (days, fraction, rounding, buffer) in cases {
+            let result = calendar
+                .buffered_end_date(monday, days, fraction, rounding)
+                .unwrap();
+            assert_eq!(
+                result,
+                BufferedResult {
+                    end_date: calendar.compute_end(monday, days).unwrap().date,
+                    buffered_end_date: calendar.compute_end(monday, days + buffer).unwrap().date,
+                    buffer_days: buffer,
+                },
+                "{} × {} {:?}",
+                days,
+                fraction,
+                rounding
+            );
+        }
+
+        // The holiday on the 22nd pushes the buffered end date out one more day.
+        let result = calendar
+            .buffered_end_date(monday, 13, 0.2, Rounding::Up)
+            .unwrap();
+        assert_eq!(result.end_date, date(2023, 9, 20));
+        assert_eq!(result.buffered_end_date, date(2023, 9, 26));
+    }
+
+    #[test]
+    fn test_buffered_end_date_errors() {
+        let calendar = WorkCalendar::new();
+        let monday = date(2023, 9, 4);
+        for fraction in [-0.1, 10.5, f64::NAN, f64::INFINITY] {
+            assert_eq!(
+                calendar.buffered_end_date(monday, 10, fraction, Rounding::Up),
+                Err(WorkCalendarError::InvalidBufferFraction(
+                    fraction.to_string()
+                ))
+            );
+        }
+        assert_eq!(
+            calendar
+                .buffered_end_date(monday, 10, -0.5, Rounding::Up)
+                .unwrap_err()
+                .to_string(),
+            "Buffer fraction must be between 0 and 10; got -0.5"
+        );
+        assert_eq!(
+            calendar.buffered_end_date(monday, -1, 0.2, Rounding::Up),
+            Err(WorkCalendarError::NegativeDaysWorked { calendar: None })
+        );
+    }
+
+    #[test]
+    fn test_find_window_blackouts() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2023, 12, 25));
+        calendar.add_holiday(date(2024, 1, 1));
+        let blackout = (date(2023, 9, 13), date(2023, 9, 15));
+        let find = |earliest, days, blackouts: Vec<(NaiveDate, NaiveDate)>| {
+            calendar
+                .find_window(
+                    earliest,
+                    days,
+                    WindowConstraints {
+                        blackouts,
+                        ..WindowConstraints::default()
+                    },
+                )
+                .unwrap()
+                .map(|window| (window.start, window.end))
+        };
+
+        // No blackouts: starts on the first working date.
+        assert_eq!(
+            find(date(2023, 9, 2), 5, vec![]),
+            Some((date(2023, 9, 4), date(2023, 9, 8)))
+        );
+        // Ends the day before a blackout starts.
+        assert_eq!(
+            find(date(2023, 9, 4), 7, vec![blackout]),
+            Some((date(2023, 9, 4), date(2023, 9, 12)))
+        );
+        // One more day overlaps, so the window moves past the blackout.
+        assert_eq!(
+            find(date(2023, 9, 4), 8, vec![blackout]),
+            Some((date(2023, 9, 18), date(2023, 9, 27)))
+        );
+        // A blackout that ends the day before the window starts does not block it.
+        assert_eq!(
+            find(date(2023, 9, 16), 3, vec![blackout]),
+            Some((date(2023, 9, 18), date(2023, 9, 20)))
+        );
+        // A blackout over a weekend inside the window still overlaps it.
+        assert_eq!(
+            find(
+                date(2023, 9, 4),
+                10,
+                vec![(date(2023, 9, 9), date(2023, 9, 10))]
+            ),
+            Some((date(2023, 9, 11), date(2023, 9, 22)))
+        );
+        // Chained blackouts: the first jump lands in the second.
+        assert_eq!(
+            find(
+                date(2023, 9, 4),
+                5,
+                vec![
+                    blackout,
+                    (date(2023, 9, 5), date(2023, 9, 6)),
+                    (date(2023, 9, 20), date(2023, 9, 20))
+                ]
+            ),
+            Some((date(2023, 9, 21), date(2023, 9, 27)))
+        );
+        // An inverted blackout is empty.
+        assert_eq!(
+            find(
+                date(2023, 9, 4),
+                5,
+                vec![(date(2023, 9, 8), date(2023, 9, 4))]
+            ),
+            Some((date(2023, 9, 4), date(2023, 9, 8)))
+        );
+        // Straddling the year end, skipping both holidays.
+        assert_eq!(
+            find(
+                date(2023, 12, 20),
+                10,
+                vec![(date(2023, 12, 21), date(2023, 12, 21))]
+            ),
+            Some((date(2023, 12, 22), date(2024, 1, 8)))
+        );
+        assert_eq!(
+            find(date(2023, 12, 23), 0, vec![]),
+            Some((date(2023, 12, 26), date(2023, 12, 26)))
+        );
+    }
+
+    #[test]
+    fn test_find_window_deadline_and_weekday() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2023, 9, 11));
+        let blackout = (date(2023, 9, 13), date(2023, 9, 15));
+        let constraints = WindowConstraints {
+            blackouts: vec![blackout],
+            finish_by: Some(date(2023, 9, 27)),
+            start_weekday: None,
+        };
+        // Finishing exactly on the deadline is allowed.
+        let window = calendar
+            .find_window(date(2023, 9, 4), 8, constraints.clone())
+            .unwrap();
+        assert_eq!(
+            window,
+            Some(WorkPeriod {
+                start: date(2023, 9, 18),
+                end: date(2023, 9, 27),
+                work_days: 8,
+            })
+        );
+        assert_eq!(
+            calendar.find_window(date(2023, 9, 4), 9, constraints.clone()),
+            Ok(None)
+        );
+
+        // Mondays only: the 11th is a holiday, so the first Monday after the
+        // blackout is the 18th.
+        let mondays = WindowConstraints {
+            start_weekday: Some(Weekday::Mon),
+            ..constraints
+        };
+        assert_eq!(
+            calendar
+                .find_window(date(2023, 9, 5), 2, mondays.clone())
+                .unwrap()
+                .map(|window| window.start),
+            Some(date(2023, 9, 18))
+        );
+        assert_eq!(
+            calendar.find_window(date(2023, 9, 19), 2, mondays),
+            Ok(Some(WorkPeriod {
+                start: date(2023, 9, 25),
+                end: date(2023, 9, 26),
+                work_days: 2,
+            }))
+        );
+
+        // Saturdays are only reachable through working-date overrides.
+        let saturdays = WindowConstraints {
+            start_weekday: Some(Weekday::Sat),
+            ..WindowConstraints::default()
+        };
+        assert_eq!(
+            calendar.find_window(date(2023, 9, 1), 3, saturdays.clone()),
+            Ok(None)
+        );
+        calendar.add_working_date(date(2023, 10, 7));
+        assert_eq!(
+            calendar.find_window(date(2023, 9, 1), 3, saturdays),
+            Ok(Some(WorkPeriod {
+                start: date(2023, 10, 7),
+                end: date(2023, 10, 10),
+                work_days: 3,
+            }))
+        );
+
+        assert_eq!(
+            calendar.find_window(date(2023, 9, 1), -1, WindowConstraints::default()),
+            Err(WorkCalendarError::NegativeDaysWorked { calendar: None })
+        );
+
+        // With every Monday a holiday, the search for one gives up at the horizon.
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("Ops");
+        for monday in date(2023, 1, 2).iter_weeks().take(800) {
+            calendar.add_holiday(monday);
+        }
+        let mondays = WindowConstraints {
+            start_weekday: Some(Weekday::Mon),
+            ..WindowConstraints::default()
+        };
+        assert!(matches!(
+            calendar.find_window(date(2023, 1, 1), 1, mondays),
+            Err(WorkCalendarError::NoWorkingDaysInHorizon { calendar: Some(name), searched_until })
+                if name == "Ops"
+                    && (searched_until - date(2023, 1, 1)).num_days() >= SEARCH_HORIZON_DAYS
+                    && searched_until.weekday() == Weekday::Mon
+        ));
+    }
+
+    #[test]
+    fn test_find_window_matches_scan() {
+        let mut rng = TestRng(0x5ac3_e2f0_19b7_8d64);
+        for _ in 0..50 {
+            let calendar = random_calendar(&mut rng);
+            if calendar.work_days.is_empty() {
+                continue;
+            }
+            let earliest = date(2022, 1, 1) + Duration::days(rng.below(700) as i64);
+            let days = rng.below(15) as i64;
+            let blackouts = (0..rng.below(5))
+                .map(|_| {
+                    let first = earliest + Duration::days(rng.below(120) as i64);
+                    (first, first + Duration::days(rng.below(10) as i64))
+                })
+                .collect();
+            let constraints = WindowConstraints {
+                blackouts,
+                finish_by: Some(earliest + Duration::days(150)),
+                start_weekday: (rng.below(2) == 0)
+                    .then(|| Weekday::try_from(rng.below(7) as u8).unwrap()),
+            };
+
+            let by = constraints.finish_by.unwrap();
+            let expected = std::iter::successors(Some(earliest), |d| d.succ_opt())
+                .take_while(|d| *d <= by)
+                .filter(|d| calendar.is_working_date(d))
+                .filter(|d| constraints.start_weekday.map_or(true, |w| d.weekday() == w))
+                .map(|start| (start, calendar.compute_end(start, days).unwrap().date))
+                .take_while(|(_, end)| *end <= by)
+                .find(|(start, end)| {
+                    !constraints
+                        .blackouts
+                        .iter()
+                        .any(|(first, last)| first <= end && last >= start)
+                });
+            let found = calendar
+                .find_window(earliest, days, constraints)
+                .unwrap()
+                .map(|window| (window.start, window.end));
+            assert_eq!(found, expected);
+        }
+    }
+}
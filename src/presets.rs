@@ -0,0 +1,432 @@
+//! National holiday calendars computed from each jurisdiction's rules, available with
+//! the `presets` feature.
+//!
+//! Holidays are generated year by year from their rules (fixed dates, nth weekdays,
+//! Easter offsets, and weekend substitution), so any year can be asked for without a
+//! table that goes stale. One-off holidays proclaimed for a single year, such as a
+//! royal jubilee or a state funeral, are not rules and are not included; add them with
+//! [`WorkCalendar::add_holiday`].
+
+use crate::{HolidayRule, WorkCalendar};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use std::{fmt, ops::RangeInclusive};
+
+/// A jurisdiction whose public holidays can be added to a calendar.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::Region;
+/// use chrono::NaiveDate;
+///
+/// // July 4, 2026 is a Saturday, so it is observed on Friday, July 3.
+/// let holidays = Region::UsFederal.holidays(2026..=2026);
+/// assert!(holidays.contains(&NaiveDate::from_ymd_opt(2026, 7, 3).unwrap()));
+/// assert!(!holidays.contains(&NaiveDate::from_ymd_opt(2026, 7, 4).unwrap()));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Region {
+    /// United States federal holidays, as observed by federal employees: a holiday on
+    /// a Saturday is observed the Friday before, and one on a Sunday the Monday after.
+    /// Juneteenth is included from 2021 and Martin Luther King Jr. Day from 1986;
+    /// the other holidays follow their current rules in every year.
+    UsFederal,
+    /// Bank holidays in England and Wales. A holiday on a weekend is substituted by
+    /// the next weekday that is not already a holiday, so when Christmas Day falls on a
+    /// Saturday, Christmas and Boxing Day are observed on the Monday and Tuesday.
+    EnglandAndWales,
+    /// Public holidays observed throughout Germany. Holidays of individual states,
+    /// such as Epiphany or Corpus Christi, are not included. Holidays on a weekend are
+    /// not moved.
+    Germany,
+    /// Public holidays in France. Holidays on a weekend are not moved.
+    France,
+}
+
+impl Region {
+    /// Returns the holidays observed in `years`, in ascending order.
+    ///
+    /// A holiday belongs to the year it is observed in: when New Year's Day 2022, a
+    /// Saturday, is observed on Friday, December 31, 2021, that date is part of 2021.
+    ///
+    /// # Arguments
+    ///
+    /// * `years` - The years to list, inclusive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::Region;
+    /// use chrono::NaiveDate;
+    ///
+    /// let holidays = Region::EnglandAndWales.holidays(2024..=2024);
+    /// assert_eq!(holidays.len(), 8);
+    /// // Good Friday and Easter Monday.
+    /// assert_eq!(holidays[1], NaiveDate::from_ymd_opt(2024, 3, 29).unwrap());
+    /// assert_eq!(holidays[2], NaiveDate::from_ymd_opt(2024, 4, 1).unwrap());
+    /// ```
+    pub fn holidays(&self, years: RangeInclusive<i32>) -> Vec<NaiveDate> {
+        let first = (*years.start()).max(NaiveDate::MIN.year());
+        let last = (*years.end()).min(NaiveDate::MAX.year());
+        if first > last {
+            return Vec::new();
+        }
+        // Observed dates can move into the neighboring year.
+        let mut dates: Vec<NaiveDate> = (first.saturating_sub(1)..=last.saturating_add(1))
+            .flat_map(|year| self.holidays_of(year))
+            .filter(|date| (first..=last).contains(&date.year()))
+            .collect();
+        dates.sort_unstable();
+        dates.dedup();
+        dates
+    }
+
+    /// Returns the observed dates of the holidays that fall in `year` before any
+    /// substitution, in no particular order.
+    fn holidays_of(&self, year: i32) -> Vec<NaiveDate> {
+        let fixed = |month, day| NaiveDate::from_ymd_opt(year, month, day);
+        let nth = |month, weekday, nth| {
+            HolidayRule::NthWeekday {
+                month,
+                weekday,
+                nth,
+            }
+            .date_in(year)
+        };
+        let easter = |offset| {
+            easter_sunday(year).and_then(|easter| easter.checked_add_signed(Duration::days(offset)))
+        };
+        let dates = match self {
+            Region::UsFederal => vec![
+                fixed(1, 1).map(nearest_weekday),
+                nth(1, Weekday::Mon, 3).filter(|_| year >= 1986),
+                nth(2, Weekday::Mon, 3),
+                nth(5, Weekday::Mon, -1),
+                fixed(6, 19).map(nearest_weekday).filter(|_| year >= 2021),
+                fixed(7, 4).map(nearest_weekday),
+                nth(9, Weekday::Mon, 1),
+                nth(10, Weekday::Mon, 2),
+                fixed(11, 11).map(nearest_weekday),
+                nth(11, Weekday::Thu, 4),
+                fixed(12, 25).map(nearest_weekday),
+            ],
+            Region::EnglandAndWales => {
+                let christmas = fixed(12, 25).and_then(|date| next_free_weekday(date, None));
+                let boxing_day = fixed(12, 26).and_then(|date| next_free_weekday(date, christmas));
+                vec![
+                    fixed(1, 1).and_then(|date| next_free_weekday(date, None)),
+                    easter(-2),
+                    easter(1),
+                    nth(5, Weekday::Mon, 1),
+                    nth(5, Weekday::Mon, -1),
+                    nth(8, Weekday::Mon, -1),
+                    christmas,
+                    boxing_day,
+                ]
+            }
+            Region::Germany => vec![
+                fixed(1, 1),
+                easter(-2),
+                easter(1),
+                fixed(5, 1),
+                easter(39),
+                easter(50),
+                fixed(10, 3).filter(|_| year >= 1990),
+                fixed(12, 25),
+                fixed(12, 26),
+            ],
+            Region::France => vec![
+                fixed(1, 1),
+                easter(1),
+                fixed(5, 1),
+                fixed(5, 8),
+                easter(39),
+                easter(50),
+                fixed(7, 14),
+                fixed(8, 15),
+                fixed(11, 1),
+                fixed(11, 11),
+                fixed(12, 25),
+            ],
+        };
+        dates.into_iter().flatten().collect()
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Region::UsFederal => "US federal",
+            Region::EnglandAndWales => "England and Wales",
+            Region::Germany => "Germany",
+            Region::France => "France",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Moves a Saturday back to Friday and a Sunday forward to Monday.
+fn nearest_weekday(date: NaiveDate) -> NaiveDate {
+    match date.weekday() {
+        Weekday::Sat => date.pred_opt().unwrap_or(date),
+        Weekday::Sun => date.succ_opt().unwrap_or(date),
+        _ => date,
+    }
+}
+
+/// Returns the first weekday on or after `date` other than `taken`.
+fn next_free_weekday(date: NaiveDate, taken: Option<NaiveDate>) -> Option<NaiveDate> {
+    std::iter::successors(Some(date), |date| date.succ_opt())
+        .find(|date| !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && Some(*date) != taken)
+}
+
+/// Returns the date of Easter Sunday in the Gregorian calendar, computed with the
+/// anonymous Gregorian algorithm (Meeus/Jones/Butcher).
+fn easter_sunday(year: i32) -> Option<NaiveDate> {
+    let a = year.rem_euclid(19);
+    let b = year.div_euclid(100);
+    let c = year.rem_euclid(100);
+    let d = b.div_euclid(4);
+    let e = b.rem_euclid(4);
+    let f = (b + 8).div_euclid(25);
+    let g = (b - f + 1).div_euclid(3);
+    let h = (19 * a + b - d - g + 15).rem_euclid(30);
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k).rem_euclid(7);
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+impl WorkCalendar {
+    /// Creates a Monday-to-Friday calendar with a region's holidays in `years`.
+    ///
+    /// # Arguments
+    ///
+    /// * `region` - The jurisdiction whose holidays to add.
+    /// * `years` - The years to add holidays for, inclusive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{Region, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::with_holidays(Region::UsFederal, 2024..=2026);
+    /// let thanksgiving = NaiveDate::from_ymd_opt(2025, 11, 27).unwrap();
+    /// assert!(!calendar.is_working_date(&thanksgiving));
+    /// assert_eq!(calendar.holidays().len(), 33);
+    /// ```
+    pub fn with_holidays(region: Region, years: RangeInclusive<i32>) -> Self {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_region_holidays(region, years);
+        calendar
+    }
+
+    /// Adds a region's holidays in `years` to the calendar.
+    ///
+    /// Each holiday is added like one added with [`WorkCalendar::add_holiday`].
+    ///
+    /// # Arguments
+    ///
+    /// * `region` - The jurisdiction whose holidays to add.
+    /// * `years` - The years to add holidays for, inclusive.
+    ///
+    /// # Returns
+    ///
+    /// The number of dates that were not already holidays.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{Region, WorkCalendar};
+    ///
+    /// let mut calendar = WorkCalendar::with_holidays(Region::Germany, 2025..=2025);
+    /// // Six of the eleven French holidays are German holidays too.
+    /// assert_eq!(calendar.add_region_holidays(Region::France, 2025..=2025), 5);
+    /// ```
+    pub fn add_region_holidays(&mut self, region: Region, years: RangeInclusive<i32>) -> usize {
+        let mut added = 0;
+        for date in region.holidays(years) {
+            if !self.holidays.contains(&date) {
+                self.add_holiday(date);
+                added += 1;
+            }
+        }
+        added
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parses `MM-DD` dates in `year`.
+    fn dates(year: i32, days: &[&str]) -> Vec<NaiveDate> {
+        days.iter()
+            .map(|day| NaiveDate::parse_from_str(&format!("{}-{}", year, day), "%Y-%m-%d").unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_us_federal() {
+        // From the Office of Personnel Management's published lists.
+        let cases = [
+            (
+                2021,
+                vec![
+                    "01-01", "01-18", "02-15", "05-31", "06-18", "07-05", "09-06", "10-11",
+                    "11-11", "11-25", "12-24", "12-31",
+                ],
+            ),
+            (
+                2024,
+                vec![
+                    "01-01", "01-15", "02-19", "05-27", "06-19", "07-04", "09-02", "10-14",
+                    "11-11", "11-28", "12-25",
+                ],
+            ),
+            (
+                2026,
+                vec![
+                    "01-01", "01-19", "02-16", "05-25", "06-19", "07-03", "09-07", "10-12",
+                    "11-11", "11-26", "12-25",
+                ],
+            ),
+        ];
+        for (year, days) in cases {
+            assert_eq!(
+                Region::UsFederal.holidays(year..=year),
+                dates(year, &days),
+                "{}",
+                year
+            );
+        }
+        // New Year's Day 2022 was observed in 2021, so 2022 has only ten holidays.
+        assert_eq!(Region::UsFederal.holidays(2022..=2022).len(), 10);
+        assert_eq!(
+            Region::UsFederal.holidays(2020..=2020)[4],
+            dates(2020, &["07-03"])[0]
+        );
+    }
+
+    #[test]
+    fn test_england_and_wales() {
+        // From GOV.UK, leaving out one-off holidays.
+        let cases = [
+            (
+                2021,
+                vec![
+                    "01-01", "04-02", "04-05", "05-03", "05-31", "08-30", "12-27", "12-28",
+                ],
+            ),
+            (
+                2024,
+                vec![
+                    "01-01", "03-29", "04-01", "05-06", "05-27", "08-26", "12-25", "12-26",
+                ],
+            ),
+            (
+                2025,
+                vec![
+                    "01-01", "04-18", "04-21", "05-05", "05-26", "08-25", "12-25", "12-26",
+                ],
+            ),
+            (
+                2026,
+                vec![
+                    "01-01", "04-03", "04-06", "05-04", "05-25", "08-31", "12-25", "12-28",
+                ],
+            ),
+        ];
+        for (year, days) in cases {
+            assert_eq!(
+                Region::EnglandAndWales.holidays(year..=year),
+                dates(year, &days),
+                "{}",
+                year
+            );
+        }
+        // Christmas on a Sunday moves to Tuesday, after Boxing Day on the Monday.
+        let holidays = Region::EnglandAndWales.holidays(2022..=2022);
+        assert_eq!(holidays[0], dates(2022, &["01-03"])[0]);
+        assert_eq!(holidays[6..], dates(2022, &["12-26", "12-27"])[..]);
+    }
+
+    #[test]
+    fn test_germany_and_france() {
+        assert_eq!(
+            Region::Germany.holidays(2024..=2025),
+            [
+                dates(
+                    2024,
+                    &[
+                        "01-01", "03-29", "04-01", "05-01", "05-09", "05-20", "10-03", "12-25",
+                        "12-26"
+                    ]
+                ),
+                dates(
+                    2025,
+                    &[
+                        "01-01", "04-18", "04-21", "05-01", "05-29", "06-09", "10-03", "12-25",
+                        "12-26"
+                    ]
+                ),
+            ]
+            .concat()
+        );
+        assert_eq!(
+            Region::France.holidays(2024..=2024),
+            dates(
+                2024,
+                &[
+                    "01-01", "04-01", "05-01", "05-08", "05-09", "05-20", "07-14", "08-15",
+                    "11-01", "11-11", "12-25"
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_easter_sunday() {
+        for (year, month, day) in [
+            (1818, 3, 22),
+            (1943, 4, 25),
+            (2000, 4, 23),
+            (2008, 3, 23),
+            (2019, 4, 21),
+            (2024, 3, 31),
+            (2038, 4, 25),
+        ] {
+            assert_eq!(
+                easter_sunday(year),
+                NaiveDate::from_ymd_opt(year, month, day)
+            );
+        }
+    }
+
+    #[test]
+    fn test_year_ranges() {
+        #[allow(clippy::reversed_empty_ranges)]
+        let empty = 2025..=2024;
+        assert!(Region::Germany.holidays(empty).is_empty());
+        assert_eq!(
+            Region::Germany
+                .holidays(i32::MIN..=NaiveDate::MIN.year())
+                .len(),
+            Region::Germany
+                .holidays(NaiveDate::MIN.year()..=NaiveDate::MIN.year())
+                .len()
+        );
+        assert!(!Region::UsFederal
+            .holidays(NaiveDate::MAX.year()..=i32::MAX)
+            .is_empty());
+
+        let calendar = WorkCalendar::with_holidays(Region::EnglandAndWales, 2024..=2025);
+        assert_eq!(calendar.holidays().len(), 16);
+        assert_eq!(calendar.work_days_in_year(2024), 262 - 8);
+        assert_eq!(Region::EnglandAndWales.to_string(), "England and Wales");
+    }
+}
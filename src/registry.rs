@@ -0,0 +1,474 @@
+//! A set of named calendars, loaded together and shared cheaply.
+
+use crate::{WorkCalendar, WorkCalendarError};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::Path, sync::Arc};
+
+/// What [`CalendarRegistry::register`] does when the name is already taken.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DuplicatePolicy {
+    /// Reject the new calendar with [`WorkCalendarError::DuplicateCalendar`].
+    #[default]
+    Error,
+    /// Replace the registered calendar. Callers already holding the old one keep it.
+    Replace,
+}
+
+/// Calendars registered by name, such as one per country or per team.
+///
+/// Lookups return an `Arc`, so handing a calendar to another thread or storing it in
+/// a long-lived value costs a reference count rather than a copy. Names are kept in
+/// sorted order.
+///
+/// A registry serializes to a single document holding every calendar under its name,
+/// plus the default calendar's name if one is set. The duplicate policy is not part
+/// of the document; a deserialized registry uses [`DuplicatePolicy::Error`].
+///
+/// # Examples
+///
+/// ```
+/// use workdays::{CalendarRegistry, WorkCalendar};
+/// use chrono::NaiveDate;
+///
+/// let mut registry = CalendarRegistry::new();
+/// let mut uk = WorkCalendar::new();
+/// uk.add_holiday(NaiveDate::from_ymd_opt(2025, 8, 25).unwrap());
+/// registry.register("uk", uk).unwrap();
+/// registry.register("company", WorkCalendar::new()).unwrap();
+/// registry.set_default("company").unwrap();
+///
+/// assert_eq!(registry.names().collect::<Vec<_>>(), ["company", "uk"]);
+/// assert!(registry.register("uk", WorkCalendar::new()).is_err());
+///
+/// let bank_holiday = NaiveDate::from_ymd_opt(2025, 8, 25).unwrap();
+/// assert!(!registry.get("uk").unwrap().is_working_date(&bank_holiday));
+/// // Teams without their own calendar fall back to the company's.
+/// assert!(registry.get_or_default("design").unwrap().is_working_date(&bank_holiday));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RegistryRepr", into = "RegistryRepr")]
+pub struct CalendarRegistry {
+    calendars: BTreeMap<String, Arc<WorkCalendar>>,
+    default: Option<String>,
+    policy: DuplicatePolicy,
+}
+
+impl CalendarRegistry {
+    /// Creates an empty registry that rejects duplicate names.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty registry that handles duplicate names according to `policy`.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - What registering a name that is already taken does.
+    pub fn with_policy(policy: DuplicatePolicy) -> Self {
+        CalendarRegistry {
+            policy,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the registry's duplicate policy.
+    pub fn policy(&self) -> DuplicatePolicy {
+        self.policy
+    }
+
+    /// Sets the registry's duplicate policy.
+    pub fn set_policy(&mut self, policy: DuplicatePolicy) {
+        self.policy = policy;
+    }
+
+    /// Registers `calendar` under `name`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name to look the calendar up by.
+    /// * `calendar` - The calendar to register.
+    ///
+    /// # Returns
+    ///
+    /// The calendar previously registered under `name`, if [`DuplicatePolicy::Replace`]
+    /// replaced one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorkCalendarError::DuplicateCalendar`] if `name` is taken and the
+    /// policy is [`DuplicatePolicy::Error`].
+    pub fn register(
+        &mut self,
+        name: &str,
+        calendar: WorkCalendar,
+    ) -> Result<Option<Arc<WorkCalendar>>, WorkCalendarError> {
+        if self.policy == DuplicatePolicy::Error && self.calendars.contains_key(name) {
+            return Err(WorkCalendarError::DuplicateCalendar(name.to_string()));
+        }
+        Ok(self.calendars.insert(name.to_string(), Arc::new(calendar)))
+    }
+
+    /// Removes and returns the calendar registered under `name`.
+    ///
+    /// Removing the default calendar also clears the default.
+    pub fn remove(&mut self, name: &str) -> Option<Arc<WorkCalendar>> {
+        if self.default.as_deref() == Some(name) {
+            self.default = None;
+        }
+        self.calendars.remove(name)
+    }
+
+    /// Returns the calendar registered under `name`.
+    pub fn get(&self, name: &str) -> Option<Arc<WorkCalendar>> {
+        self.calendars.get(name).cloned()
+    }
+
+    /// Returns the calendar registered under `name`, or the default calendar if there
+    /// is none.
+    ///
+    /// # Returns
+    ///
+    /// `None` only if `name` is not registered and no default is set.
+    pub fn get_or_default(&self, name: &str) -> Option<Arc<WorkCalendar>> {
+        self.get(name).or_else(|| self.default_calendar())
+    }
+
+    /// Returns the default calendar, if one is set.
+    pub fn default_calendar(&self) -> Option<Arc<WorkCalendar>> {
+        self.default.as_deref().and_then(|name| self.get(name))
+    }
+
+    /// Returns the name of the default calendar, if one is set.
+    pub fn default_name(&self) -> Option<&str> {
+        self.default.as_deref()
+    }
+
+    /// Makes the calendar registered under `name` the fallback for
+    /// [`CalendarRegistry::get_or_default`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorkCalendarError::UnknownCalendar`] if no calendar is registered
+    /// under `name`.
+    pub fn set_default(&mut self, name: &str) -> Result<(), WorkCalendarError> {
+        if !self.calendars.contains_key(name) {
+            return Err(WorkCalendarError::UnknownCalendar(name.to_string()));
+        }
+        self.default = Some(name.to_string());
+        Ok(())
+    }
+
+    /// Clears the default calendar.
+    pub fn clear_default(&mut self) {
+        self.default = None;
+    }
+
+    /// Returns the registered names in sorted order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.calendars.keys().map(String::as_str)
+    }
+
+    /// Returns the registered names and calendars in name order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Arc<WorkCalendar>)> {
+        self.calendars
+            .iter()
+            .map(|(name, calendar)| (name.as_str(), calendar))
+    }
+
+    /// Returns the number of registered calendars.
+    pub fn len(&self) -> usize {
+        self.calendars.len()
+    }
+
+    /// Checks if no calendars are registered.
+    pub fn is_empty(&self) -> bool {
+        self.calendars.is_empty()
+    }
+
+    /// Registers every calendar configuration file in `dir`.
+    ///
+    /// Files ending in `.yaml`, `.yml` or `.json` are read with
+    /// [`WorkCalendar::from_path`] and registered under their file stem, so
+    /// `calendars/uk.yaml` becomes `uk`. Other files and subdirectories are ignored.
+    /// A calendar whose configuration has no `name` is given its file stem as its
+    /// name, so its errors say which calendar raised them. Files are registered in
+    /// path order, and either all of them are or, on error, none are.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory to read.
+    ///
+    /// # Returns
+    ///
+    /// The names registered, in the order they were registered.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorkCalendarError::InvalidCalendarFile`] if the directory or a file
+    /// cannot be read, [`WorkCalendarError::CalendarFileConfig`] if a file is not a
+    /// valid configuration, and
+    /// [`WorkCalendarError::DuplicateCalendar`] if a name is taken (by a registered
+    /// calendar or another file) and the policy is [`DuplicatePolicy::Error`].
+    pub fn load_dir(&mut self, dir: impl AsRef<Path>) -> Result<Vec<String>, WorkCalendarError> {
+        let dir = dir.as_ref();
+        let invalid =
+            |path: &Path, error: &dyn std::fmt::Display| WorkCalendarError::InvalidCalendarFile {
+                path: path.display().to_string(),
+                message: error.to_string(),
+            };
+
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(dir).map_err(|e| invalid(dir, &e))? {
+            let path = entry.map_err(|e| invalid(dir, &e))?.path();
+            let is_config = matches!(
+                path.extension().and_then(|ext| ext.to_str()),
+                Some("yaml" | "yml" | "json")
+            );
+            if is_config && path.is_file() {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        let mut loaded = self.clone();
+        let mut names = Vec::with_capacity(paths.len());
+        for path in paths {
+            let name = match path.file_stem().and_then(|stem| stem.to_str()) {
+                Some(name) => name.to_string(),
+                None => return Err(invalid(&path, &"file name is not valid UTF-8")),
+            };
+            let mut calendar = WorkCalendar::from_path(&path)?;
+            if calendar.name().is_none() {
+                calendar.set_name(name.as_str());
+            }
+            loaded.register(&name, calendar)?;
+            names.push(name);
+        }
+        *self = loaded;
+        Ok(names)
+    }
+}
+
+/// The serialized form of a [`CalendarRegistry`].
+#[derive(Serialize, Deserialize)]
+struct RegistryRepr {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default: Option<String>,
+    calendars: BTreeMap<String, Arc<WorkCalendar>>,
+}
+
+impl From<CalendarRegistry> for RegistryRepr {
+    fn from(registry: CalendarRegistry) -> Self {
+        RegistryRepr {
+            default: registry.default,
+            calendars: registry.calendars,
+        }
+    }
+}
+
+impl TryFrom<RegistryRepr> for CalendarRegistry {
+    type Error = String;
+
+    fn try_from(repr: RegistryRepr) -> Result<Self, Self::Error> {
+        if let Some(name) = &repr.default {
+            if !repr.calendars.contains_key(name) {
+                return Err(WorkCalendarError::UnknownCalendar(name.clone()).to_string());
+            }
+        }
+        Ok(CalendarRegistry {
+            calendars: repr.calendars,
+            default: repr.default,
+            policy: DuplicatePolicy::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{date, random_calendar, TestRng};
+    use std::path::PathBuf;
+
+    /// A fresh directory under the system temp directory, removed on drop.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(label: &str) -> Self {
+            let path = std::env::temp_dir().join(format!(
+                "workdays-registry-{}-{}",
+                label,
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            TempDir(path)
+        }
+
+        fn write(&self, file: &str, contents: &str) {
+            fs::write(self.0.join(file), contents).unwrap();
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_duplicate_policy() {
+        let mut registry = CalendarRegistry::new();
+        assert_eq!(registry.policy(), DuplicatePolicy::Error);
+        assert_eq!(registry.register("ops", WorkCalendar::new()), Ok(None));
+        let mut other = WorkCalendar::new();
+        other.set_name("other");
+        assert_eq!(
+            registry.register("ops", other.clone()),
+            Err(WorkCalendarError::DuplicateCalendar("ops".to_string()))
+        );
+        assert_eq!(registry.get("ops").unwrap().name(), None);
+
+        let held = registry.get("ops").unwrap();
+        registry.set_policy(DuplicatePolicy::Replace);
+        let replaced = registry.register("ops", other).unwrap().unwrap();
+        assert!(Arc::ptr_eq(&held, &replaced));
+        assert_eq!(registry.get("ops").unwrap().name(), Some("other"));
+        assert_eq!(held.name(), None);
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    fn test_lookups_share_the_calendar() {
+        let mut registry = CalendarRegistry::with_policy(DuplicatePolicy::Replace);
+        assert!(registry.is_empty());
+        assert!(registry.get("us").is_none());
+        assert!(registry.get_or_default("us").is_none());
+        registry.register("us", WorkCalendar::new()).unwrap();
+        registry.register("company", WorkCalendar::new()).unwrap();
+        assert!(Arc::ptr_eq(
+            &registry.get("us").unwrap(),
+            &registry.get("us").unwrap()
+        ));
+        assert_eq!(
+            registry.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            ["company", "us"]
+        );
+
+        assert_eq!(
+            registry.set_default("hq"),
+            Err(WorkCalendarError::UnknownCalendar("hq".to_string()))
+        );
+        registry.set_default("company").unwrap();
+        assert_eq!(registry.default_name(), Some("company"));
+        let company = registry.get("company").unwrap();
+        assert!(Arc::ptr_eq(
+            &registry.get_or_default("design").unwrap(),
+            &company
+        ));
+        assert!(!Arc::ptr_eq(
+            &registry.get_or_default("us").unwrap(),
+            &company
+        ));
+
+        assert!(registry.remove("company").is_some());
+        assert_eq!(registry.default_name(), None);
+        assert!(registry.get_or_default("design").is_none());
+        registry.set_default("us").unwrap();
+        registry.clear_default();
+        assert!(registry.default_calendar().is_none());
+    }
+
+    #[test]
+    fn test_load_dir() {
+        let dir = TempDir::new("load");
+        dir.write("uk.yaml", "holidays:\n  - 2025-08-25\n");
+        dir.write(
+            "us.json",
+            r#"{"name": "United States", "holidays": ["2025-07-04"]}"#,
+        );
+        dir.write("team-b.yml", "work_days: [Monday, Tuesday]\n");
+        dir.write("notes.txt", "not a calendar");
+        fs::create_dir(dir.0.join("archive.yaml")).unwrap();
+
+        let mut registry = CalendarRegistry::new();
+        let names = registry.load_dir(&dir.0).unwrap();
+        assert_eq!(names, ["team-b", "uk", "us"]);
+        assert_eq!(registry.names().collect::<Vec<_>>(), names);
+        let uk = registry.get("uk").unwrap();
+        assert_eq!(uk.name(), Some("uk"));
+        assert!(!uk.is_working_date(&date(2025, 8, 25)));
+        assert_eq!(registry.get("us").unwrap().name(), Some("United States"));
+        assert!(!registry
+            .get("team-b")
+            .unwrap()
+            .is_working_date(&date(2025, 7, 2)));
+
+        // Loading again collides with every name, so nothing changes.
+        let before = registry.clone();
+        assert_eq!(
+            registry.load_dir(&dir.0),
+            Err(WorkCalendarError::DuplicateCalendar("team-b".to_string()))
+        );
+        assert_eq!(registry, before);
+        registry.set_policy(DuplicatePolicy::Replace);
+        assert_eq!(registry.load_dir(&dir.0).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_load_dir_errors_leave_registry_unchanged() {
+        let dir = TempDir::new("errors");
+        dir.write("a.yaml", "holidays: [2025-01-01]\n");
+        dir.write("b.yaml", "work_days: [Funday]\n");
+        let mut registry = CalendarRegistry::new();
+        match registry.load_dir(&dir.0) {
+            Err(WorkCalendarError::CalendarFileConfig { path, source }) => {
+                assert!(path.ends_with("b.yaml"), "{}", path);
+                assert!(source.to_string().contains("Funday"), "{}", source);
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert!(registry.is_empty());
+
+        // Two files with the same stem collide with each other.
+        fs::remove_file(dir.0.join("b.yaml")).unwrap();
+        dir.write("a.json", r#"{"holidays": ["2025-01-01"]}"#);
+        assert_eq!(
+            registry.load_dir(&dir.0),
+            Err(WorkCalendarError::DuplicateCalendar("a".to_string()))
+        );
+        assert!(registry.is_empty());
+
+        let missing = dir.0.join("missing");
+        assert!(matches!(
+            registry.load_dir(&missing),
+            Err(WorkCalendarError::InvalidCalendarFile { .. })
+        ));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut rng = TestRng(0x4c68_a3e1_902b_77d5);
+        let mut registry = CalendarRegistry::new();
+        for i in 0..5 {
+            registry
+                .register(&format!("cal-{}", i), random_calendar(&mut rng))
+                .unwrap();
+        }
+        registry.set_default("cal-3").unwrap();
+
+        let json = serde_json::to_string(&registry).unwrap();
+        assert_eq!(
+            serde_json::from_str::<CalendarRegistry>(&json).unwrap(),
+            registry
+        );
+        let yaml = serde_yaml::to_string(&registry).unwrap();
+        assert_eq!(
+            serde_yaml::from_str::<CalendarRegistry>(&yaml).unwrap(),
+            registry
+        );
+
+        let dangling = r#"{"default": "hq", "calendars": {}}"#;
+        let error = serde_json::from_str::<CalendarRegistry>(dangling).unwrap_err();
+        assert!(error.to_string().contains("'hq'"), "{}", error);
+    }
+}
@@ -0,0 +1,269 @@
+//! Parsing of relative business-date phrases such as "in 3 business days".
+
+use crate::{WorkCalendar, WorkCalendarError};
+use chrono::NaiveDate;
+
+/// A summary of the phrases accepted by [`WorkCalendar::parse_relative`], included in
+/// its error message.
+pub(crate) const GRAMMAR: &str = "'in N business days', \
+    'N business days before <date>', 'N business days after <date>', \
+    'next business day', 'previous business day' \
+    (where 'business' may also be 'work' or 'working', and <date> is YYYY-MM-DD, \
+    'today', or 'tomorrow')";
+
+/// The date a phrase counts from.
+enum Anchor {
+    Reference,
+    Tomorrow,
+    Date(NaiveDate),
+}
+
+impl WorkCalendar {
+    /// Resolves a relative business-date phrase against `reference`, which stands for
+    /// today.
+    ///
+    /// The accepted phrases are:
+    ///
+    /// | Phrase                              | Result                                         |
+    /// |-------------------------------------|------------------------------------------------|
+    /// | `in N business days`                | the `N`th working date after `reference`       |
+    /// | `N business days after <anchor>`    | the `N`th working date after the anchor        |
+    /// | `N business days before <anchor>`   | the `N`th working date before the anchor       |
+    /// | `next business day`                 | the first working date after `reference`       |
+    /// | `previous business day`             | the last working date before `reference`       |
+    ///
+    /// `business` may also be written `work` or `working`, and `days` may be `day`.
+    /// An anchor is an ISO 8601 date (`YYYY-MM-DD`), `today`, or `tomorrow`. `N` is a
+    /// non-negative integer; zero resolves to the anchor itself, as in
+    /// [`WorkCalendar::checked_add_work_days`]. Words are matched case-insensitively
+    /// and may be separated by any amount of whitespace. Anything else is rejected
+    /// rather than guessed at.
+    ///
+    /// # Errors
+    ///
+    /// * [`WorkCalendarError::InvalidRelativeDate`] if `input` is not one of the
+    ///   phrases above. Its message summarizes the grammar.
+    /// * [`WorkCalendarError::NoWorkDaysDefined`] if the calendar has no work days.
+    /// * [`WorkCalendarError::NoWorkingDaysInHorizon`] if no working date is found
+    ///   within [`SEARCH_HORIZON_DAYS`](crate::SEARCH_HORIZON_DAYS).
+    /// * [`WorkCalendarError::DateOutOfRange`] if the result cannot be represented.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let friday = NaiveDate::from_ymd_opt(2025, 6, 27).unwrap();
+    ///
+    /// assert_eq!(
+    ///     calendar.parse_relative("in 3 business days", friday).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2025, 7, 2).unwrap()
+    /// );
+    /// assert_eq!(
+    ///     calendar.parse_relative("2 working days before 2025-06-30", friday).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2025, 6, 26).unwrap()
+    /// );
+    /// assert!(calendar.parse_relative("sometime next week", friday).is_err());
+    /// ```
+    pub fn parse_relative(
+        &self,
+        input: &str,
+        reference: NaiveDate,
+    ) -> Result<NaiveDate, WorkCalendarError> {
+        let (anchor, days) = parse_phrase(input)
+            .ok_or_else(|| WorkCalendarError::InvalidRelativeDate(input.to_string()))?;
+        let start = match anchor {
+            Anchor::Reference => reference,
+            Anchor::Tomorrow => {
+                reference
+                    .succ_opt()
+                    .ok_or_else(|| WorkCalendarError::DateOutOfRange {
+                        calendar: self.name().map(String::from),
+                    })?
+            }
+            Anchor::Date(date) => date,
+        };
+        self.try_add_work_days(start, days)
+    }
+}
+
+/// Splits a phrase into the date it counts from and a signed number of work days.
+fn parse_phrase(input: &str) -> Option<(Anchor, i64)> {
+    let words: Vec<&str> = input.split_whitespace().collect();
+    match words[..] {
+        [in_, count, kind, unit] if is(in_, "in") && is_unit(kind, unit) => {
+            Some((Anchor::Reference, parse_count(count)?))
+        }
+        [direction, kind, unit] if is_unit(kind, unit) => {
+            if is(direction, "next") {
+                Some((Anchor::Reference, 1))
+            } else if is(direction, "previous") {
+                Some((Anchor::Reference, -1))
+            } else {
+                None
+            }
+        }
+        [count, kind, unit, direction, anchor] if is_unit(kind, unit) => {
+            let count = parse_count(count)?;
+            let days = if is(direction, "after") {
+                count
+            } else if is(direction, "before") {
+                -count
+            } else {
+                return None;
+            };
+            Some((parse_anchor(anchor)?, days))
+        }
+        _ => None,
+    }
+}
+
+fn is(word: &str, keyword: &str) -> bool {
+    word.eq_ignore_ascii_case(keyword)
+}
+
+/// Matches `business days` and its accepted spellings.
+fn is_unit(kind: &str, unit: &str) -> bool {
+    ["business", "work", "working"]
+        .iter()
+        .any(|keyword| is(kind, keyword))
+        && (is(unit, "days") || is(unit, "day"))
+}
+
+/// Parses a non-negative decimal count, rejecting signs and other decorations.
+fn parse_count(word: &str) -> Option<i64> {
+    if word.bytes().all(|b| b.is_ascii_digit()) {
+        word.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn parse_anchor(word: &str) -> Option<Anchor> {
+    if is(word, "today") {
+        Some(Anchor::Reference)
+    } else if is(word, "tomorrow") {
+        Some(Anchor::Tomorrow)
+    } else {
+        NaiveDate::parse_from_str(word, "%Y-%m-%d")
+            .ok()
+            .map(Anchor::Date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::date;
+
+    #[test]
+    fn test_accepted_phrases() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2025, 7, 4));
+        // A Wednesday.
+        let reference = date(2025, 7, 2);
+        let cases = [
+            ("in 0 business days", date(2025, 7, 2)),
+            ("in 1 business day", date(2025, 7, 3)),
+            ("in 2 business days", date(2025, 7, 7)),
+            ("in 3 work days", date(2025, 7, 8)),
+            ("in 3 working days", date(2025, 7, 8)),
+            ("IN 3 Business DAYS", date(2025, 7, 8)),
+            ("  in\t3   business\n days  ", date(2025, 7, 8)),
+            ("in 010 business days", date(2025, 7, 17)),
+            ("next business day", date(2025, 7, 3)),
+            ("Next Working Day", date(2025, 7, 3)),
+            ("next business days", date(2025, 7, 3)),
+            ("previous business day", date(2025, 7, 1)),
+            ("PREVIOUS work day", date(2025, 7, 1)),
+            ("2 business days after today", date(2025, 7, 7)),
+            ("2 business days after tomorrow", date(2025, 7, 8)),
+            ("1 business day before today", date(2025, 7, 1)),
+            ("1 business day before tomorrow", date(2025, 7, 2)),
+            ("0 business days after tomorrow", date(2025, 7, 3)),
+            ("2 working days before 2025-06-30", date(2025, 6, 26)),
+            ("2 working days after 2025-06-30", date(2025, 7, 2)),
+            ("5 work days before 2025-07-08", date(2025, 6, 30)),
+            ("1 business day after 2025-07-03", date(2025, 7, 7)),
+            ("1 business day AFTER 2025-07-05", date(2025, 7, 7)),
+            ("0 business days before 2025-07-05", date(2025, 7, 5)),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                calendar.parse_relative(input, reference),
+                Ok(expected),
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_rejected_phrases() {
+        let calendar = WorkCalendar::new();
+        let reference = date(2025, 7, 2);
+        let cases = [
+            "",
+            "   ",
+            "tomorrow",
+            "next business",
+            "in 3 days",
+            "in 3 calendar days",
+            "in three business days",
+            "in -3 business days",
+            "in +3 business days",
+            "in 3.5 business days",
+            "in 3 business days please",
+            "in 99999999999999999999 business days",
+            "3 business days",
+            "3 business days from today",
+            "3 business days after yesterday",
+            "3 business days after 2025-02-30",
+            "3 business days after 07/02/2025",
+            "3 business days after next week",
+            "last business day",
+            "next business week",
+            "next weekday",
+            "in3 business days",
+            "in 3 businessdays",
+        ];
+        for input in cases {
+            let error = calendar.parse_relative(input, reference).unwrap_err();
+            assert_eq!(
+                error,
+                WorkCalendarError::InvalidRelativeDate(input.to_string()),
+                "{:?}",
+                input
+            );
+            assert!(error.to_string().contains(GRAMMAR));
+        }
+    }
+
+    #[test]
+    fn test_calendar_errors() {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("Ops");
+        assert_eq!(
+            calendar.parse_relative("next business day", NaiveDate::MAX),
+            Err(WorkCalendarError::DateOutOfRange {
+                calendar: Some("Ops".to_string())
+            })
+        );
+        assert_eq!(
+            calendar.parse_relative("in 0 business days", NaiveDate::MAX),
+            Ok(NaiveDate::MAX)
+        );
+
+        for n in 0..7 {
+            calendar.remove_work_day(&chrono::Weekday::try_from(n).unwrap());
+        }
+        assert_eq!(
+            calendar.parse_relative("next business day", date(2025, 7, 2)),
+            Err(WorkCalendarError::NoWorkDaysDefined {
+                calendar: Some("Ops".to_string())
+            })
+        );
+    }
+}
@@ -0,0 +1,598 @@
+//! Recurring holiday rules, effective-dated holiday layers, and the per-year cache of
+//! their evaluated dates.
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{
+    de::{self, MapAccess, SeqAccess, Visitor},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+/// The default number of years whose rule dates are kept in a calendar's cache.
+pub const DEFAULT_RULE_CACHE_CAPACITY: usize = 64;
+
+/// A holiday that recurs every year.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::HolidayRule;
+/// use chrono::{NaiveDate, Weekday};
+///
+/// let christmas = HolidayRule::FixedDate { month: 12, day: 25 };
+/// assert_eq!(christmas.date_in(2024), NaiveDate::from_ymd_opt(2024, 12, 25));
+///
+/// let thanksgiving = HolidayRule::NthWeekday { month: 11, weekday: Weekday::Thu, nth: 4 };
+/// assert_eq!(thanksgiving.date_in(2024), NaiveDate::from_ymd_opt(2024, 11, 28));
+///
+/// let memorial_day = HolidayRule::NthWeekday { month: 5, weekday: Weekday::Mon, nth: -1 };
+/// assert_eq!(memorial_day.date_in(2024), NaiveDate::from_ymd_opt(2024, 5, 27));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum HolidayRule {
+    /// The same month and day every year, e.g. December 25.
+    ///
+    /// Years without that date (February 29 outside leap years) have no holiday.
+    FixedDate { month: u32, day: u32 },
+    /// The `nth` occurrence of `weekday` in `month`, e.g. the fourth Thursday of
+    /// November.
+    ///
+    /// Negative values count from the end of the month, so `-1` is the last
+    /// occurrence. Years where the month has no such occurrence (a fifth Monday, say)
+    /// have no holiday.
+    NthWeekday {
+        month: u32,
+        weekday: Weekday,
+        nth: i8,
+    },
+}
+
+impl HolidayRule {
+    /// Returns the date of the holiday in `year`, or `None` if the rule does not
+    /// produce a date that year.
+    pub fn date_in(&self, year: i32) -> Option<NaiveDate> {
+        match *self {
+            HolidayRule::FixedDate { month, day } => NaiveDate::from_ymd_opt(year, month, day),
+            HolidayRule::NthWeekday {
+                month,
+                weekday,
+                nth,
+            } => match nth {
+                0 => None,
+                1.. => NaiveDate::from_weekday_of_month_opt(year, month, weekday, nth as u8),
+                _ => {
+                    let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+                    let last = first
+                        .checked_add_months(chrono::Months::new(1))?
+                        .pred_opt()?;
+                    let back = (7 + last.weekday().num_days_from_monday()
+                        - weekday.num_days_from_monday())
+                        % 7;
+                    let weeks = i64::from(nth.unsigned_abs()) - 1;
+                    let date = last - Duration::days(i64::from(back) + 7 * weeks);
+                    Some(date).filter(|date| date.month() == month)
+                }
+            },
+        }
+    }
+
+    /// Returns the month the rule falls in, masked to 0..16 so it can index a bitmask.
+    fn month(&self) -> u32 {
+        match *self {
+            HolidayRule::FixedDate { month, .. } | HolidayRule::NthWeekday { month, .. } => {
+                month & 15
+            }
+        }
+    }
+}
+
+/// A holiday given either as a single date or as a recurring rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Holiday {
+    /// A one-off holiday on a single date.
+    Date(NaiveDate),
+    /// A holiday that recurs every year.
+    Rule(HolidayRule),
+}
+
+impl From<NaiveDate> for Holiday {
+    fn from(date: NaiveDate) -> Self {
+        Holiday::Date(date)
+    }
+}
+
+impl From<HolidayRule> for Holiday {
+    fn from(rule: HolidayRule) -> Self {
+        Holiday::Rule(rule)
+    }
+}
+
+impl Holiday {
+    /// Returns the date of the holiday in `year`, if it has one.
+    fn date_in(&self, year: i32) -> Option<NaiveDate> {
+        match self {
+            Holiday::Date(date) => Some(*date).filter(|date| date.year() == year),
+            Holiday::Rule(rule) => rule.date_in(year),
+        }
+    }
+
+    /// Returns the month the holiday falls in, masked like [`HolidayRule::month`].
+    fn month(&self) -> u32 {
+        match self {
+            Holiday::Date(date) => date.month() & 15,
+            Holiday::Rule(rule) => rule.month(),
+        }
+    }
+}
+
+/// A holiday that applies only to dates within an effective window.
+///
+/// Created by [`WorkCalendar::add_holiday_effective`](crate::WorkCalendar::add_holiday_effective).
+/// The holiday's dates before `effective_from` or after `effective_to` are ordinary
+/// dates.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::{Holiday, HolidayLayer, HolidayRule};
+/// use chrono::NaiveDate;
+///
+/// let juneteenth = HolidayLayer {
+///     holiday: Holiday::Rule(HolidayRule::FixedDate { month: 6, day: 19 }),
+///     effective_from: NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
+///     effective_to: None,
+/// };
+/// assert_eq!(juneteenth.date_in(2021), None);
+/// assert_eq!(juneteenth.date_in(2022), NaiveDate::from_ymd_opt(2022, 6, 19));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "LayerRepr", into = "LayerRepr")]
+pub struct HolidayLayer {
+    /// The holiday.
+    pub holiday: Holiday,
+    /// The first date on which the holiday applies.
+    pub effective_from: NaiveDate,
+    /// The last date on which the holiday applies, or `None` if it has no end.
+    pub effective_to: Option<NaiveDate>,
+}
+
+impl HolidayLayer {
+    /// Returns the date of the holiday in `year`, or `None` if it has no date that
+    /// year or the date is outside the effective window.
+    pub fn date_in(&self, year: i32) -> Option<NaiveDate> {
+        self.holiday.date_in(year).filter(|date| {
+            *date >= self.effective_from && self.effective_to.map_or(true, |to| *date <= to)
+        })
+    }
+}
+
+/// The serialized form of a [`HolidayLayer`], which names the kind of holiday by its
+/// key: `date` for a single date or `rule` for a recurring rule. YAML cannot nest one
+/// enum in another, so the holiday is not written as a [`Holiday`] value.
+#[derive(Serialize, Deserialize)]
+struct LayerRepr {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    date: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rule: Option<HolidayRule>,
+    effective_from: NaiveDate,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    effective_to: Option<NaiveDate>,
+}
+
+impl From<HolidayLayer> for LayerRepr {
+    fn from(layer: HolidayLayer) -> Self {
+        let (date, rule) = match layer.holiday {
+            Holiday::Date(date) => (Some(date), None),
+            Holiday::Rule(rule) => (None, Some(rule)),
+        };
+        LayerRepr {
+            date,
+            rule,
+            effective_from: layer.effective_from,
+            effective_to: layer.effective_to,
+        }
+    }
+}
+
+impl TryFrom<LayerRepr> for HolidayLayer {
+    type Error = String;
+
+    fn try_from(repr: LayerRepr) -> Result<Self, Self::Error> {
+        let holiday = match (repr.date, repr.rule) {
+            (Some(date), None) => Holiday::Date(date),
+            (None, Some(rule)) => Holiday::Rule(rule),
+            _ => return Err("a holiday layer needs exactly one of date or rule".to_string()),
+        };
+        Ok(HolidayLayer {
+            holiday,
+            effective_from: repr.effective_from,
+            effective_to: repr.effective_to,
+        })
+    }
+}
+
+/// A calendar's recurring holiday rules and holiday layers together with a cache of
+/// their dates.
+///
+/// Evaluated dates are cached per year and filled lazily, the first time a query
+/// touches that year. The cache holds at most `capacity` years, evicting the least
+/// recently used, and is emptied whenever the rules change. It never takes part in
+/// equality or serialization, and clones start with an empty cache.
+///
+/// Serializes as the list of rules when there are no layers, and otherwise as a map
+/// with `rules` and `layers` lists, so calendars without layers keep their existing
+/// form.
+#[derive(Clone, Default)]
+pub(crate) struct RuleSet {
+    rules: Arc<Vec<HolidayRule>>,
+    layers: Arc<Vec<HolidayLayer>>,
+    /// Bit `m` is set if some rule or layer falls in month `m`, so most dates can be rejected
+    /// without consulting the cache.
+    months: u16,
+    cache: RuleCache,
+}
+
+impl RuleSet {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.rules.is_empty() && self.layers.is_empty()
+    }
+
+    pub(crate) fn rules(&self) -> &[HolidayRule] {
+        &self.rules
+    }
+
+    pub(crate) fn layers(&self) -> &[HolidayLayer] {
+        &self.layers
+    }
+
+    /// Adds `layer`, returning `false` if it was already present.
+    pub(crate) fn insert_layer(&mut self, layer: HolidayLayer) -> bool {
+        if self.layers.contains(&layer) {
+            return false;
+        }
+        Arc::make_mut(&mut self.layers).push(layer);
+        self.months |= 1 << layer.holiday.month();
+        self.cache.clear();
+        true
+    }
+
+    /// Removes `layer`, returning `false` if it was not present.
+    pub(crate) fn remove_layer(&mut self, layer: &HolidayLayer) -> bool {
+        if !self.layers.contains(layer) {
+            return false;
+        }
+        Arc::make_mut(&mut self.layers).retain(|l| l != layer);
+        self.recompute_months();
+        self.cache.clear();
+        true
+    }
+
+    /// Adds `rule`, returning `false` if it was already present.
+    pub(crate) fn insert(&mut self, rule: HolidayRule) -> bool {
+        if self.rules.contains(&rule) {
+            return false;
+        }
+        Arc::make_mut(&mut self.rules).push(rule);
+        self.months |= 1 << rule.month();
+        self.cache.clear();
+        true
+    }
+
+    /// Removes `rule`, returning `false` if it was not present.
+    pub(crate) fn remove(&mut self, rule: &HolidayRule) -> bool {
+        if !self.rules.contains(rule) {
+            return false;
+        }
+        Arc::make_mut(&mut self.rules).retain(|r| r != rule);
+        self.recompute_months();
+        self.cache.clear();
+        true
+    }
+
+    fn recompute_months(&mut self) {
+        let rules = self.rules.iter().map(HolidayRule::month);
+        let layers = self.layers.iter().map(|layer| layer.holiday.month());
+        self.months = rules
+            .chain(layers)
+            .fold(0, |months, month| months | 1 << month);
+    }
+
+    pub(crate) fn set_cache_capacity(&mut self, capacity: usize) {
+        self.cache.set_capacity(capacity);
+    }
+
+    /// Returns the sorted, de-duplicated dates produced by the rules and layers in
+    /// `year`.
+    pub(crate) fn dates_in_year(&self, year: i32) -> Arc<[NaiveDate]> {
+        self.cache.get_or_insert_with(year, || {
+            let rules = self.rules.iter().filter_map(|rule| rule.date_in(year));
+            let layers = self.layers.iter().filter_map(|layer| layer.date_in(year));
+            let mut dates: Vec<NaiveDate> = rules.chain(layers).collect();
+            dates.sort();
+            dates.dedup();
+            dates.into()
+        })
+    }
+
+    /// Checks if any rule produces `date`.
+    pub(crate) fn contains(&self, date: &NaiveDate) -> bool {
+        self.months & 1 << date.month() != 0
+            && self.dates_in_year(date.year()).binary_search(date).is_ok()
+    }
+
+    /// Counts the rule dates in `start_date..=end_date` accepted by `filter`.
+    pub(crate) fn count_in_range(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        filter: impl Fn(&NaiveDate) -> bool,
+    ) -> i64 {
+        if self.is_empty() || start_date > end_date {
+            return 0;
+        }
+        (start_date.year()..=end_date.year())
+            .map(|year| {
+                self.dates_in_year(year)
+                    .iter()
+                    .filter(|date| **date >= start_date && **date <= end_date && filter(date))
+                    .count() as i64
+            })
+            .sum()
+    }
+
+    /// Returns a date no later than the first rule date after `date` accepted by
+    /// `filter`.
+    ///
+    /// Only the current and next year are searched; if neither has a match, the start
+    /// of the year after is returned as a conservative bound. Callers use this to
+    /// limit how far they can skip ahead, so an early answer is always safe.
+    pub(crate) fn next_after(
+        &self,
+        date: NaiveDate,
+        filter: impl Fn(&NaiveDate) -> bool,
+    ) -> Option<NaiveDate> {
+        if self.is_empty() {
+            return None;
+        }
+        let year = date.year();
+        for year in year..=year + 1 {
+            let dates = self.dates_in_year(year);
+            let after = dates.partition_point(|rule_date| *rule_date <= date);
+            if let Some(rule_date) = dates[after..].iter().find(|rule_date| filter(rule_date)) {
+                return Some(*rule_date);
+            }
+        }
+        NaiveDate::from_ymd_opt(year + 2, 1, 1)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn cached_years(&self) -> usize {
+        self.cache.lock().entries.len()
+    }
+}
+
+impl PartialEq for RuleSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.rules == other.rules && self.layers == other.layers
+    }
+}
+
+impl Eq for RuleSet {}
+
+impl fmt::Debug for RuleSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.rules.iter())
+            .entries(self.layers.iter())
+            .finish()
+    }
+}
+
+impl Serialize for RuleSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.layers.is_empty() {
+            return self.rules.serialize(serializer);
+        }
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("rules", &*self.rules)?;
+        map.serialize_entry("layers", &*self.layers)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for RuleSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct RuleSetVisitor;
+
+        impl<'de> Visitor<'de> for RuleSetVisitor {
+            type Value = RuleSet;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a list of rules or a map of rules and layers")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut set = RuleSet::default();
+                while let Some(rule) = seq.next_element()? {
+                    set.insert(rule);
+                }
+                Ok(set)
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut set = RuleSet::default();
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "rules" => {
+                            for rule in map.next_value::<Vec<HolidayRule>>()? {
+                                set.insert(rule);
+                            }
+                        }
+                        "layers" => {
+                            for layer in map.next_value::<Vec<HolidayLayer>>()? {
+                                set.insert_layer(layer);
+                            }
+                        }
+                        _ => return Err(de::Error::unknown_field(&key, &["rules", "layers"])),
+                    }
+                }
+                Ok(set)
+            }
+        }
+
+        deserializer.deserialize_any(RuleSetVisitor)
+    }
+}
+
+/// A least-recently-used map from year to that year's rule dates.
+struct RuleCache(Mutex<CacheState>);
+
+struct CacheState {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<i32, (Arc<[NaiveDate]>, u64)>,
+}
+
+impl RuleCache {
+    fn with_capacity(capacity: usize) -> Self {
+        RuleCache(Mutex::new(CacheState {
+            capacity,
+            tick: 0,
+            entries: HashMap::new(),
+        }))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CacheState> {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn get_or_insert_with(
+        &self,
+        year: i32,
+        compute: impl FnOnce() -> Arc<[NaiveDate]>,
+    ) -> Arc<[NaiveDate]> {
+        let mut state = self.lock();
+        if state.capacity == 0 {
+            drop(state);
+            return compute();
+        }
+        state.tick += 1;
+        let tick = state.tick;
+        if let Some(entry) = state.entries.get_mut(&year) {
+            entry.1 = tick;
+            return entry.0.clone();
+        }
+        let dates = compute();
+        if state.entries.len() >= state.capacity {
+            if let Some(oldest) = state
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(year, _)| *year)
+            {
+                state.entries.remove(&oldest);
+            }
+        }
+        state.entries.insert(year, (dates.clone(), tick));
+        dates
+    }
+
+    fn clear(&mut self) {
+        self.0
+            .get_mut()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entries
+            .clear();
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        let state = self
+            .0
+            .get_mut()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        state.capacity = capacity;
+        state.entries.clear();
+    }
+}
+
+impl Default for RuleCache {
+    fn default() -> Self {
+        RuleCache::with_capacity(DEFAULT_RULE_CACHE_CAPACITY)
+    }
+}
+
+impl Clone for RuleCache {
+    fn clone(&self) -> Self {
+        RuleCache::with_capacity(self.lock().capacity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_dates() {
+        let leap_day = HolidayRule::FixedDate { month: 2, day: 29 };
+        assert_eq!(leap_day.date_in(2024), NaiveDate::from_ymd_opt(2024, 2, 29));
+        assert_eq!(leap_day.date_in(2023), None);
+
+        let fifth_monday = HolidayRule::NthWeekday {
+            month: 1,
+            weekday: Weekday::Mon,
+            nth: 5,
+        };
+        assert_eq!(
+            fifth_monday.date_in(2024),
+            NaiveDate::from_ymd_opt(2024, 1, 29)
+        );
+        assert_eq!(fifth_monday.date_in(2025), None);
+
+        let fifth_last_monday = HolidayRule::NthWeekday {
+            month: 1,
+            weekday: Weekday::Mon,
+            nth: -5,
+        };
+        assert_eq!(
+            fifth_last_monday.date_in(2024),
+            NaiveDate::from_ymd_opt(2024, 1, 1)
+        );
+        assert_eq!(fifth_last_monday.date_in(2025), None);
+
+        let zeroth = HolidayRule::NthWeekday {
+            month: 1,
+            weekday: Weekday::Mon,
+            nth: 0,
+        };
+        assert_eq!(zeroth.date_in(2024), None);
+    }
+
+    #[test]
+    fn test_cache_is_bounded_lru() {
+        let mut rules = RuleSet::default();
+        rules.set_cache_capacity(3);
+        rules.insert(HolidayRule::FixedDate { month: 1, day: 1 });
+        for year in 2000..2005 {
+            rules.dates_in_year(year);
+        }
+        assert_eq!(rules.cached_years(), 3);
+
+        // 2002 is refreshed, so 2003 is the least recently used when 2010 arrives.
+        rules.dates_in_year(2002);
+        rules.dates_in_year(2010);
+        let cached: Vec<i32> = {
+            let mut years: Vec<i32> = rules.cache.lock().entries.keys().copied().collect();
+            years.sort();
+            years
+        };
+        assert_eq!(cached, vec![2002, 2004, 2010]);
+    }
+}
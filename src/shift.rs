@@ -0,0 +1,288 @@
+//! Shift-level scheduling for calendars that run several shifts per working date.
+
+use crate::{WorkCalendar, WorkCalendarError, SEARCH_HORIZON_DAYS};
+use chrono::{Datelike, NaiveDate, Weekday};
+
+/// A work calendar that counts shifts rather than whole days.
+///
+/// Each weekday has a number of shifts, numbered from 0 within the day. A date has
+/// its weekday's shifts if the calendar treats it as a working date, and none
+/// otherwise, so a holiday zeroes out that day's shifts. Like
+/// [`RangeCounter`](crate::RangeCounter), a shift calendar captures the calendar as
+/// it was when the shift calendar was created.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::{ShiftCalendar, WorkCalendar};
+/// use chrono::{NaiveDate, Weekday};
+///
+/// let mut calendar = WorkCalendar::new();
+/// calendar.add_work_day(Weekday::Sat);
+/// let mut shifts = ShiftCalendar::new(&calendar, 2);
+/// shifts.set_shifts(Weekday::Sat, 1);
+///
+/// let friday = NaiveDate::from_ymd_opt(2023, 9, 8).unwrap();
+/// let monday = NaiveDate::from_ymd_opt(2023, 9, 11).unwrap();
+/// assert_eq!(shifts.shifts_between(friday, monday), 5);
+///
+/// // Starting with Friday's second shift, four shifts end with Monday's second.
+/// assert_eq!(shifts.compute_end_after_shifts(friday, 1, 4).unwrap(), (monday, 1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ShiftCalendar {
+    calendar: WorkCalendar,
+    /// Shifts per day, indexed by `Weekday::num_days_from_monday`.
+    shifts: [u32; 7],
+}
+
+impl ShiftCalendar {
+    /// Creates a shift calendar over `calendar` with `shifts_per_day` shifts on every
+    /// working date.
+    pub fn new(calendar: &WorkCalendar, shifts_per_day: u32) -> Self {
+        ShiftCalendar {
+            calendar: calendar.clone(),
+            shifts: [shifts_per_day; 7],
+        }
+    }
+
+    /// Sets the number of shifts on one weekday.
+    ///
+    /// The shifts only run on dates the calendar treats as working dates.
+    pub fn set_shifts(&mut self, weekday: Weekday, shifts: u32) {
+        self.shifts[weekday.num_days_from_monday() as usize] = shifts;
+    }
+
+    /// Returns the calendar captured when the shift calendar was created.
+    pub fn calendar(&self) -> &WorkCalendar {
+        &self.calendar
+    }
+
+    /// Returns the number of shifts on `date`, which is zero on non-working dates.
+    pub fn shifts_on(&self, date: NaiveDate) -> u32 {
+        if self.calendar.is_working_date(&date) {
+            self.shifts[date.weekday().num_days_from_monday() as usize]
+        } else {
+            0
+        }
+    }
+
+    /// Counts the shifts from `start` to `end`, inclusive.
+    ///
+    /// # Returns
+    ///
+    /// The number of shifts, or zero if `start` is after `end`.
+    pub fn shifts_between(&self, start: NaiveDate, end: NaiveDate) -> i64 {
+        std::iter::successors(Some(start), |date| date.succ_opt())
+            .take_while(|date| *date <= end)
+            .map(|date| i64::from(self.shifts_on(date)))
+            .sum()
+    }
+
+    /// Finds the date and shift on which `shifts` shifts of work complete.
+    ///
+    /// Work begins with shift `starting_shift` on `start` and that shift counts as the
+    /// first, mirroring [`WorkCalendar::compute_end`]. If `start` has no such shift,
+    /// work begins with the first shift of the next date that has any. Zero shifts
+    /// end where they start.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The date work begins.
+    /// * `starting_shift` - The zero-based shift on `start` that work begins with.
+    /// * `shifts` - Number of shifts of work.
+    ///
+    /// # Returns
+    ///
+    /// The date and zero-based shift of the last shift worked.
+    ///
+    /// # Errors
+    ///
+    /// * [`WorkCalendarError::NegativeShifts`] if `shifts` is negative.
+    /// * [`WorkCalendarError::NoWorkDaysDefined`] if no work day of the calendar has
+    ///   any shifts.
+    /// * [`WorkCalendarError::NoWorkingDaysInHorizon`] if no date has shifts within
+    ///   [`SEARCH_HORIZON_DAYS`] of the last one that did, for example because the
+    ///   work week in effect has only days without shifts.
+    /// * The errors of [`WorkCalendar::next_work_day`] if the end cannot be reached.
+    pub fn compute_end_after_shifts(
+        &self,
+        start: NaiveDate,
+        starting_shift: u32,
+        shifts: i64,
+    ) -> Result<(NaiveDate, u32), WorkCalendarError> {
+        let name = || self.calendar.name().map(String::from);
+        if shifts < 0 {
+            return Err(WorkCalendarError::NegativeShifts { calendar: name() });
+        }
+        if shifts == 0 {
+            return Ok((start, starting_shift));
+        }
+        let any_shifts = self.calendar.work_weeks().any(|week| {
+            week.iter()
+                .any(|day| self.shifts[day.num_days_from_monday() as usize] > 0)
+        });
+        if !any_shifts {
+            return Err(WorkCalendarError::NoWorkDaysDefined { calendar: name() });
+        }
+        let mut remaining = shifts;
+        let mut date = start;
+        let mut last_with_shifts = start;
+        let mut first = starting_shift;
+        loop {
+            let available = i64::from(self.shifts_on(date).saturating_sub(first));
+            if remaining <= available {
+                return Ok((date, first + remaining as u32 - 1));
+            }
+            remaining -= available;
+            if self.shifts_on(date) > 0 {
+                last_with_shifts = date;
+            } else if (date - last_with_shifts).num_days() >= SEARCH_HORIZON_DAYS {
+                return Err(WorkCalendarError::NoWorkingDaysInHorizon {
+                    calendar: name(),
+                    searched_until: date,
+                });
+            }
+            date = self.calendar.try_add_work_days(date, 1)?;
+            first = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{date, random_calendar, TestRng};
+    use chrono::Duration;
+
+    fn plant() -> ShiftCalendar {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("Plant");
+        calendar.add_work_day(Weekday::Sat);
+        // Monday, September 4, 2023 (Labor Day).
+        calendar.add_holiday(date(2023, 9, 4));
+        let mut shifts = ShiftCalendar::new(&calendar, 2);
+        shifts.set_shifts(Weekday::Sat, 1);
+        shifts
+    }
+
+    #[test]
+    fn test_shifts_between() {
+        let shifts = plant();
+        assert_eq!(shifts.shifts_on(date(2023, 9, 1)), 2);
+        assert_eq!(shifts.shifts_on(date(2023, 9, 2)), 1);
+        assert_eq!(shifts.shifts_on(date(2023, 9, 3)), 0);
+        assert_eq!(shifts.shifts_on(date(2023, 9, 4)), 0);
+        // The holiday week: four two-shift days and a one-shift Saturday.
+        assert_eq!(shifts.shifts_between(date(2023, 9, 3), date(2023, 9, 9)), 9);
+        assert_eq!(
+            shifts.shifts_between(date(2023, 9, 10), date(2023, 9, 16)),
+            11
+        );
+        assert_eq!(shifts.shifts_between(date(2023, 9, 5), date(2023, 9, 5)), 2);
+        assert_eq!(shifts.shifts_between(date(2023, 9, 9), date(2023, 9, 5)), 0);
+    }
+
+    #[test]
+    fn test_compute_end_after_shifts() {
+        let shifts = plant();
+        let cases = [
+            (date(2023, 9, 5), 0, 1, (date(2023, 9, 5), 0)),
+            (date(2023, 9, 5), 0, 2, (date(2023, 9, 5), 1)),
+            (date(2023, 9, 5), 0, 3, (date(2023, 9, 6), 0)),
+            (date(2023, 9, 5), 1, 1, (date(2023, 9, 5), 1)),
+            (date(2023, 9, 5), 1, 2, (date(2023, 9, 6), 0)),
+            // Saturday has one shift, then Sunday none.
+            (date(2023, 9, 8), 1, 2, (date(2023, 9, 9), 0)),
+            (date(2023, 9, 8), 1, 3, (date(2023, 9, 11), 0)),
+            // A shift Saturday does not have starts on Monday.
+            (date(2023, 9, 9), 1, 1, (date(2023, 9, 11), 0)),
+            // Starting on the holiday.
+            (date(2023, 9, 4), 0, 1, (date(2023, 9, 5), 0)),
+            (date(2023, 9, 2), 0, 4, (date(2023, 9, 6), 0)),
+            // Zero shifts end where they start.
+            (date(2023, 9, 3), 5, 0, (date(2023, 9, 3), 5)),
+            (date(2023, 9, 4), 0, 20, (date(2023, 9, 16), 0)),
+        ];
+        for (start, starting_shift, count, expected) in cases {
+            assert_eq!(
+                shifts.compute_end_after_shifts(start, starting_shift, count),
+                Ok(expected),
+                "{} shift {} + {}",
+                start,
+                starting_shift,
+                count
+            );
+        }
+    }
+
+    #[test]
+    fn test_compute_end_after_shifts_matches_count() {
+        let mut rng = TestRng(0x2b9e_44c1_0d7a_f365);
+        for _ in 0..50 {
+            let calendar = random_calendar(&mut rng);
+            let mut shifts = ShiftCalendar::new(&calendar, 1 + rng.below(3) as u32);
+            shifts.set_shifts(
+                Weekday::try_from(rng.below(7) as u8).unwrap(),
+                rng.below(4) as u32,
+            );
+            let start = date(2022, 1, 1) + Duration::days(rng.below(1000) as i64);
+            let count = 1 + rng.below(200) as i64;
+            let (end, last) = match shifts.compute_end_after_shifts(start, 0, count) {
+                Ok(end) => end,
+                Err(error) => {
+                    assert_eq!(
+                        error,
+                        WorkCalendarError::NoWorkDaysDefined { calendar: None }
+                    );
+                    continue;
+                }
+            };
+            assert!(last < shifts.shifts_on(end));
+            let unused = i64::from(shifts.shifts_on(end) - 1 - last);
+            assert_eq!(shifts.shifts_between(start, end) - unused, count);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let shifts = plant();
+        assert_eq!(
+            shifts.compute_end_after_shifts(date(2023, 9, 5), 0, -1),
+            Err(WorkCalendarError::NegativeShifts {
+                calendar: Some("Plant".to_string())
+            })
+        );
+        assert_eq!(
+            WorkCalendarError::NegativeShifts { calendar: None }.to_string(),
+            "Shift count must be non-negative"
+        );
+
+        let mut idle = plant();
+        for n in 0..5 {
+            idle.set_shifts(Weekday::try_from(n).unwrap(), 0);
+        }
+        idle.set_shifts(Weekday::Sat, 0);
+        assert_eq!(
+            idle.compute_end_after_shifts(date(2023, 9, 5), 0, 1),
+            Err(WorkCalendarError::NoWorkDaysDefined {
+                calendar: Some("Plant".to_string())
+            })
+        );
+        assert_eq!(idle.shifts_between(date(2023, 9, 1), date(2023, 9, 30)), 0);
+
+        // Earlier work weeks have shifts, but the one in effect does not.
+        let mut calendar = WorkCalendar::new();
+        calendar
+            .set_work_days_effective(date(2024, 1, 1), "Sat")
+            .unwrap();
+        let mut weekends = ShiftCalendar::new(&calendar, 2);
+        weekends.set_shifts(Weekday::Sat, 0);
+        let start = date(2024, 6, 3);
+        assert!(matches!(
+            weekends.compute_end_after_shifts(start, 0, 1),
+            Err(WorkCalendarError::NoWorkingDaysInHorizon { calendar: None, searched_until })
+                if (searched_until - start).num_days() >= SEARCH_HORIZON_DAYS
+        ));
+    }
+}
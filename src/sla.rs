@@ -0,0 +1,475 @@
+//! Service-level clocks that run only during business hours on working dates.
+
+use crate::{DayHalf, WorkCalendar, WorkCalendarError};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+
+/// Measures service-level deadlines in business hours.
+///
+/// The clock runs from the opening time to the closing time of each working date of
+/// its calendar and is paused at all other times: overnight, on weekends, and on
+/// holidays. Every weekday starts with the same hours; use [`SlaClock::set_hours`] to
+/// give a weekday different ones. Like [`RangeCounter`](crate::RangeCounter), the
+/// clock captures the calendar as it was when the clock was created.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::{SlaClock, WorkCalendar};
+/// use chrono::{Duration, NaiveDate, NaiveTime};
+///
+/// let calendar = WorkCalendar::new();
+/// let clock = SlaClock::new(
+///     &calendar,
+///     NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+///     NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+/// )
+/// .unwrap();
+///
+/// // Opened late on a Friday, a one-hour response is due Monday morning.
+/// let opened = NaiveDate::from_ymd_opt(2023, 9, 8).unwrap().and_hms_opt(16, 50, 0).unwrap();
+/// let due = clock.deadline(opened, Duration::hours(1)).unwrap();
+/// assert_eq!(due, NaiveDate::from_ymd_opt(2023, 9, 11).unwrap().and_hms_opt(9, 50, 0).unwrap());
+/// assert_eq!(clock.elapsed(opened, due), Duration::hours(1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SlaClock {
+    calendar: WorkCalendar,
+    /// Opening and closing times, indexed by `Weekday::num_days_from_monday`.
+    hours: [(NaiveTime, NaiveTime); 7],
+}
+
+impl SlaClock {
+    /// Creates a clock over `calendar` that runs from `open` to `close` on every
+    /// working date.
+    ///
+    /// # Errors
+    ///
+    /// [`WorkCalendarError::InvalidBusinessHours`] if `open` is not before `close`.
+    /// Hours that span midnight are not supported.
+    pub fn new(
+        calendar: &WorkCalendar,
+        open: NaiveTime,
+        close: NaiveTime,
+    ) -> Result<Self, WorkCalendarError> {
+        check_hours(open, close)?;
+        Ok(SlaClock {
+            calendar: calendar.clone(),
+            hours: [(open, close); 7],
+        })
+    }
+
+    /// Sets the business hours for one weekday, such as a Friday that closes early.
+    ///
+    /// The hours apply only on dates the calendar treats as working dates.
+    ///
+    /// # Errors
+    ///
+    /// [`WorkCalendarError::InvalidBusinessHours`] if `open` is not before `close`.
+    pub fn set_hours(
+        &mut self,
+        weekday: Weekday,
+        open: NaiveTime,
+        close: NaiveTime,
+    ) -> Result<(), WorkCalendarError> {
+        check_hours(open, close)?;
+        self.hours[weekday.num_days_from_monday() as usize] = (open, close);
+        Ok(())
+    }
+
+    /// Returns the calendar captured when the clock was created.
+    pub fn calendar(&self) -> &WorkCalendar {
+        &self.calendar
+    }
+
+    /// Returns the opening and closing times on `date`, or `None` if it is not a
+    /// working date.
+    ///
+    /// On a half-day holiday the clock runs for only the half of the day that is not
+    /// off, split at the midpoint of the day's hours: from the midpoint to the close if
+    /// the morning is off, and from the opening to the midpoint if the afternoon is.
+    pub fn hours_on(&self, date: NaiveDate) -> Option<(NaiveTime, NaiveTime)> {
+        if !self.calendar.is_working_date(&date) {
+            return None;
+        }
+        let (open, close) = self.hours[date.weekday().num_days_from_monday() as usize];
+        let midday = open + (close - open) / 2;
+        Some(match self.calendar.holiday_half(&date) {
+            Some(DayHalf::Morning) => (midday, close),
+            Some(DayHalf::Afternoon) => (open, midday),
+            None => (open, close),
+        })
+    }
+
+    /// Computes when `sla` of business time, counted from `opened_at`, runs out.
+    ///
+    /// A ticket opened outside business hours starts the clock at the next opening.
+    /// A deadline that lands exactly on a closing time is that closing time, not the
+    /// next opening. A zero `sla` gives the moment the clock next runs.
+    ///
+    /// # Arguments
+    ///
+    /// * `opened_at` - When the clock starts.
+    /// * `sla` - The business time allowed.
+    ///
+    /// # Errors
+    ///
+    /// * [`WorkCalendarError::NegativeSla`] if `sla` is negative.
+    /// * The errors of [`WorkCalendar::next_work_day`] if the clock cannot run long
+    ///   enough, for example because the calendar has no work days.
+    pub fn deadline(
+        &self,
+        opened_at: NaiveDateTime,
+        sla: Duration,
+    ) -> Result<NaiveDateTime, WorkCalendarError> {
+        if sla < Duration::zero() {
+            return Err(WorkCalendarError::NegativeSla {
+                calendar: self.calendar.name().map(String::from),
+            });
+        }
+        let mut remaining = sla;
+        let mut date = opened_at.date();
+        let mut from = opened_at.time();
+        loop {
+            if let Some((open, close)) = self.hours_on(date) {
+                let start = from.max(open);
+                if start < close {
+                    let available = close - start;
+                    if remaining <= available {
+                        return Ok(date.and_time(start) + remaining);
+                    }
+                    remaining -= available;
+                }
+            }
+            date = self.calendar.try_add_work_days(date, 1)?;
+            from = NaiveTime::MIN;
+        }
+    }
+
+    /// Measures the business time between `opened_at` and `now`.
+    ///
+    /// # Returns
+    ///
+    /// The business time in the range, or zero if `now` is not after `opened_at`.
+    pub fn elapsed(&self, opened_at: NaiveDateTime, now: NaiveDateTime) -> Duration {
+        std::iter::successors(Some(opened_at.date()), |date| date.succ_opt())
+            .take_while(|date| *date <= now.date())
+            .filter_map(|date| {
+                let (open, close) = self.hours_on(date)?;
+                let start = date.and_time(open).max(opened_at);
+                let end = date.and_time(close).min(now);
+                (start < end).then_some(end - start)
+            })
+            .fold(Duration::zero(), |total, hours| total + hours)
+    }
+}
+
+fn check_hours(open: NaiveTime, close: NaiveTime) -> Result<(), WorkCalendarError> {
+    if open < close {
+        Ok(())
+    } else {
+        Err(WorkCalendarError::InvalidBusinessHours { open, close })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{date, random_calendar, TestRng};
+
+    fn at(y: i32, m: u32, d: u32, h: u32, min: u32) -> NaiveDateTime {
+        date(y, m, d).and_hms_opt(h, min, 0).unwrap()
+    }
+
+    fn time(h: u32, min: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, min, 0).unwrap()
+    }
+
+    fn clock() -> SlaClock {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("Support");
+        // Monday, September 18, 2023.
+        calendar.add_holiday(date(2023, 9, 18));
+        SlaClock::new(&calendar, time(9, 0), time(17, 0)).unwrap()
+    }
+
+    #[test]
+    fn test_deadline_boundaries() {
+        let clock = clock();
+        let cases = [
+            // Inside business hours.
+            (
+                at(2023, 9, 6, 10, 0),
+                Duration::hours(1),
+                at(2023, 9, 6, 11, 0),
+            ),
+            // Friday 16:50 with one hour rolls over the weekend.
+            (
+                at(2023, 9, 8, 16, 50),
+                Duration::hours(1),
+                at(2023, 9, 11, 9, 50),
+            ),
+            // Before opening starts at the opening.
+            (
+                at(2023, 9, 6, 7, 30),
+                Duration::hours(1),
+                at(2023, 9, 6, 10, 0),
+            ),
+            // After closing starts at the next opening.
+            (
+                at(2023, 9, 6, 18, 0),
+                Duration::hours(1),
+                at(2023, 9, 7, 10, 0),
+            ),
+            // Opened on a weekend.
+            (
+                at(2023, 9, 9, 12, 0),
+                Duration::hours(1),
+                at(2023, 9, 11, 10, 0),
+            ),
+            // Exactly at opening and exactly at closing.
+            (
+                at(2023, 9, 6, 9, 0),
+                Duration::hours(8),
+                at(2023, 9, 6, 17, 0),
+            ),
+            (
+                at(2023, 9, 6, 17, 0),
+                Duration::minutes(1),
+                at(2023, 9, 7, 9, 1),
+            ),
+            // Running out exactly at closing stays on that day.
+            (
+                at(2023, 9, 6, 16, 0),
+                Duration::hours(1),
+                at(2023, 9, 6, 17, 0),
+            ),
+            // Eight business hours from mid-morning is mid-morning the next day.
+            (
+                at(2023, 9, 6, 10, 30),
+                Duration::hours(8),
+                at(2023, 9, 7, 10, 30),
+            ),
+            // Skips the holiday on Monday the 18th.
+            (
+                at(2023, 9, 15, 16, 0),
+                Duration::hours(2),
+                at(2023, 9, 19, 10, 0),
+            ),
+            (
+                at(2023, 9, 18, 10, 0),
+                Duration::hours(1),
+                at(2023, 9, 19, 10, 0),
+            ),
+            // A zero SLA is due when the clock next runs.
+            (
+                at(2023, 9, 6, 12, 0),
+                Duration::zero(),
+                at(2023, 9, 6, 12, 0),
+            ),
+            (
+                at(2023, 9, 9, 12, 0),
+                Duration::zero(),
+                at(2023, 9, 11, 9, 0),
+            ),
+            // A full week of business hours.
+            (
+                at(2023, 9, 4, 9, 0),
+                Duration::hours(40),
+                at(2023, 9, 8, 17, 0),
+            ),
+        ];
+        for (opened, sla, expected) in cases {
+            assert_eq!(
+                clock.deadline(opened, sla),
+                Ok(expected),
+                "{} + {}",
+                opened,
+                sla
+            );
+        }
+    }
+
+    #[test]
+    fn test_per_day_hours() {
+        let mut clock = clock();
+        clock
+            .set_hours(Weekday::Fri, time(9, 0), time(13, 0))
+            .unwrap();
+        assert_eq!(
+            clock.hours_on(date(2023, 9, 8)),
+            Some((time(9, 0), time(13, 0)))
+        );
+        assert_eq!(
+            clock.hours_on(date(2023, 9, 7)),
+            Some((time(9, 0), time(17, 0)))
+        );
+        assert_eq!(clock.hours_on(date(2023, 9, 9)), None);
+        assert_eq!(clock.hours_on(date(2023, 9, 18)), None);
+
+        assert_eq!(
+            clock.deadline(at(2023, 9, 8, 12, 30), Duration::hours(1)),
+            Ok(at(2023, 9, 11, 9, 30))
+        );
+        assert_eq!(
+            clock.deadline(at(2023, 9, 8, 14, 0), Duration::hours(1)),
+            Ok(at(2023, 9, 11, 10, 0))
+        );
+        assert_eq!(
+            clock.elapsed(at(2023, 9, 7, 16, 0), at(2023, 9, 11, 10, 0)),
+            Duration::hours(6)
+        );
+    }
+
+    #[test]
+    fn test_elapsed() {
+        let clock = clock();
+        let cases = [
+            (
+                at(2023, 9, 6, 10, 0),
+                at(2023, 9, 6, 11, 30),
+                Duration::minutes(90),
+            ),
+            // Paused overnight and over the weekend.
+            (
+                at(2023, 9, 8, 16, 50),
+                at(2023, 9, 11, 9, 50),
+                Duration::hours(1),
+            ),
+            (
+                at(2023, 9, 8, 18, 0),
+                at(2023, 9, 11, 8, 0),
+                Duration::zero(),
+            ),
+            // Paused on the holiday.
+            (
+                at(2023, 9, 15, 16, 0),
+                at(2023, 9, 19, 10, 0),
+                Duration::hours(2),
+            ),
+            (
+                at(2023, 9, 4, 0, 0),
+                at(2023, 9, 11, 0, 0),
+                Duration::hours(40),
+            ),
+            // `now` before `opened_at`.
+            (
+                at(2023, 9, 6, 12, 0),
+                at(2023, 9, 6, 11, 0),
+                Duration::zero(),
+            ),
+            (
+                at(2023, 9, 6, 12, 0),
+                at(2023, 9, 6, 12, 0),
+                Duration::zero(),
+            ),
+        ];
+        for (opened, now, expected) in cases {
+            assert_eq!(
+                clock.elapsed(opened, now),
+                expected,
+                "{} to {}",
+                opened,
+                now
+            );
+        }
+    }
+
+    #[test]
+    fn test_elapsed_inverts_deadline() {
+        let mut rng = TestRng(0x7d2c_19a4_e06b_5f31);
+        for _ in 0..50 {
+            let calendar = random_calendar(&mut rng);
+            if calendar.work_days.is_empty() {
+                continue;
+            }
+            let open = time(6 + rng.below(4) as u32, 15 * rng.below(4) as u32);
+            let close = time(14 + rng.below(6) as u32, 15 * rng.below(4) as u32);
+            let clock = SlaClock::new(&calendar, open, close).unwrap();
+            let opened =
+                at(2022, 1, 1, 0, 0) + Duration::minutes(rng.below(3 * 365 * 24 * 60) as i64);
+            let sla = Duration::minutes(rng.below(80 * 60) as i64);
+            let due = clock.deadline(opened, sla).unwrap();
+            assert!(due >= opened);
+            assert_eq!(clock.elapsed(opened, due), sla, "{} + {}", opened, sla);
+        }
+    }
+
+    #[test]
+    fn test_half_day_holidays() {
+        let mut calendar = WorkCalendar::new();
+        // Wednesday and Thursday, September 6 and 7, 2023.
+        calendar.add_partial_holiday_half(date(2023, 9, 6), DayHalf::Afternoon);
+        calendar.add_partial_holiday_half(date(2023, 9, 7), DayHalf::Morning);
+        let clock = SlaClock::new(&calendar, time(9, 0), time(17, 0)).unwrap();
+        assert_eq!(
+            clock.hours_on(date(2023, 9, 6)),
+            Some((time(9, 0), time(13, 0)))
+        );
+        assert_eq!(
+            clock.hours_on(date(2023, 9, 7)),
+            Some((time(13, 0), time(17, 0)))
+        );
+
+        // Wednesday's clock stops at 13:00 and Thursday's starts then.
+        assert_eq!(
+            clock.deadline(at(2023, 9, 6, 12, 0), Duration::hours(2)),
+            Ok(at(2023, 9, 7, 14, 0))
+        );
+        assert_eq!(
+            clock.deadline(at(2023, 9, 6, 14, 0), Duration::hours(4)),
+            Ok(at(2023, 9, 7, 17, 0))
+        );
+        assert_eq!(
+            clock.elapsed(at(2023, 9, 6, 0, 0), at(2023, 9, 8, 0, 0)),
+            Duration::hours(8)
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let calendar = WorkCalendar::new();
+        assert_eq!(
+            SlaClock::new(&calendar, time(17, 0), time(9, 0)).unwrap_err(),
+            WorkCalendarError::InvalidBusinessHours {
+                open: time(17, 0),
+                close: time(9, 0)
+            }
+        );
+        let mut clock = SlaClock::new(&calendar, time(9, 0), time(17, 0)).unwrap();
+        let error = clock
+            .set_hours(Weekday::Fri, time(9, 0), time(9, 0))
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Business hours must open before they close; got 09:00:00 to 09:00:00"
+        );
+
+        let clock = self::clock();
+        assert_eq!(
+            clock.deadline(at(2023, 9, 6, 10, 0), Duration::minutes(-1)),
+            Err(WorkCalendarError::NegativeSla {
+                calendar: Some("Support".to_string())
+            })
+        );
+
+        let mut idle = WorkCalendar::new();
+        for day in [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ] {
+            idle.remove_work_day(&day);
+        }
+        let clock = SlaClock::new(&idle, time(9, 0), time(17, 0)).unwrap();
+        assert_eq!(
+            clock.deadline(at(2023, 9, 6, 10, 0), Duration::hours(1)),
+            Err(WorkCalendarError::NoWorkDaysDefined { calendar: None })
+        );
+        assert_eq!(
+            clock.elapsed(at(2023, 9, 1, 0, 0), at(2023, 10, 1, 0, 0)),
+            Duration::zero()
+        );
+    }
+}
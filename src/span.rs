@@ -0,0 +1,185 @@
+//! Operator sugar for work-day arithmetic.
+//!
+//! ```
+//! use workdays::{WorkCalendar, WorkDays};
+//! use chrono::NaiveDate;
+//!
+//! let calendar = WorkCalendar::new();
+//! let start = NaiveDate::from_ymd_opt(2023, 8, 21).unwrap(); // Monday
+//!
+//! let end = start + WorkDays(10).on(&calendar);
+//! assert_eq!(end, NaiveDate::from_ymd_opt(2023, 9, 4).unwrap());
+//! assert_eq!(end - WorkDays(10).on(&calendar), start);
+//! assert_eq!(calendar.span(10) + start, end);
+//! ```
+
+use crate::WorkCalendar;
+use chrono::NaiveDate;
+use std::ops::{Add, Neg, Sub};
+
+/// A number of work days, independent of any calendar.
+///
+/// Bind it to a calendar with [`WorkDays::on`] to add it to or subtract it from a date.
+/// Negative spans move backwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct WorkDays(pub i64);
+
+/// A number of work days bound to the calendar that defines them.
+///
+/// Adding this to a `NaiveDate` (in either order) is equivalent to
+/// [`WorkCalendar::checked_add_work_days`], and subtracting it is equivalent to
+/// [`WorkCalendar::checked_sub_work_days`].
+///
+/// # Panics
+///
+/// The operators panic if the result is outside the representable date range or no
+/// working date can be found. Use [`BoundWorkDays::checked_add`] and
+/// [`BoundWorkDays::checked_sub`] to handle those cases instead.
+#[derive(Debug, Clone, Copy)]
+pub struct BoundWorkDays<'a> {
+    days: i64,
+    calendar: &'a WorkCalendar,
+}
+
+impl WorkDays {
+    /// Binds this span to a calendar so it can be used with date operators.
+    ///
+    /// # Arguments
+    ///
+    /// * `calendar` - The calendar used to decide which dates are work days.
+    pub fn on(self, calendar: &WorkCalendar) -> BoundWorkDays<'_> {
+        BoundWorkDays {
+            days: self.0,
+            calendar,
+        }
+    }
+
+    /// Negates the span, returning `None` if it is `WorkDays(i64::MIN)`, whose
+    /// negation does not fit.
+    pub fn checked_neg(self) -> Option<WorkDays> {
+        self.0.checked_neg().map(WorkDays)
+    }
+}
+
+/// # Panics
+///
+/// Panics on `WorkDays(i64::MIN)`, whose negation does not fit. Use
+/// [`WorkDays::checked_neg`] to handle that case instead.
+impl Neg for WorkDays {
+    type Output = WorkDays;
+
+    fn neg(self) -> WorkDays {
+        self.checked_neg()
+            .expect("work-day span negation overflowed")
+    }
+}
+
+impl<'a> BoundWorkDays<'a> {
+    /// Returns the number of work days in the span.
+    pub fn days(&self) -> WorkDays {
+        WorkDays(self.days)
+    }
+
+    /// Returns the calendar the span is bound to.
+    pub fn calendar(&self) -> &'a WorkCalendar {
+        self.calendar
+    }
+
+    /// Adds the span to `date`, returning `None` instead of panicking.
+    pub fn checked_add(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.calendar.checked_add_work_days(date, self.days)
+    }
+
+    /// Subtracts the span from `date`, returning `None` instead of panicking.
+    pub fn checked_sub(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.calendar.checked_sub_work_days(date, self.days)
+    }
+}
+
+/// # Panics
+///
+/// Panics if the span is `i64::MIN` work days, like negating a [`WorkDays`].
+impl<'a> Neg for BoundWorkDays<'a> {
+    type Output = BoundWorkDays<'a>;
+
+    fn neg(self) -> BoundWorkDays<'a> {
+        (-self.days()).on(self.calendar)
+    }
+}
+
+impl Add<BoundWorkDays<'_>> for NaiveDate {
+    type Output = NaiveDate;
+
+    fn add(self, span: BoundWorkDays<'_>) -> NaiveDate {
+        span.checked_add(self)
+            .expect("work-day addition out of range or no working date found")
+    }
+}
+
+impl Add<NaiveDate> for BoundWorkDays<'_> {
+    type Output = NaiveDate;
+
+    fn add(self, date: NaiveDate) -> NaiveDate {
+        date + self
+    }
+}
+
+impl Sub<BoundWorkDays<'_>> for NaiveDate {
+    type Output = NaiveDate;
+
+    fn sub(self, span: BoundWorkDays<'_>) -> NaiveDate {
+        span.checked_sub(self)
+            .expect("work-day subtraction out of range or no working date found")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_operators_match_methods() {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(NaiveDate::from_ymd_opt(2023, 8, 23).unwrap());
+        let start = NaiveDate::from_ymd_opt(2023, 8, 18).unwrap();
+
+        for offset in 0..14 {
+            let date = start + Duration::days(offset);
+            for n in -12..=12 {
+                let expected = calendar.checked_add_work_days(date, n).unwrap();
+                assert_eq!(date + WorkDays(n).on(&calendar), expected);
+                assert_eq!(WorkDays(n).on(&calendar) + date, expected);
+                assert_eq!(calendar.span(n) + date, expected);
+                assert_eq!(date - WorkDays(-n).on(&calendar), expected);
+                assert_eq!(date + (-WorkDays(-n)).on(&calendar), expected);
+                assert_eq!(date + -calendar.span(-n), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_checked_variants() {
+        let calendar = WorkCalendar::new();
+        assert_eq!(WorkDays(1).on(&calendar).checked_add(NaiveDate::MAX), None);
+        assert_eq!(WorkDays(1).on(&calendar).checked_sub(NaiveDate::MIN), None);
+        assert_eq!(calendar.span(3).days(), WorkDays(3));
+        assert_eq!(WorkDays(3).checked_neg(), Some(WorkDays(-3)));
+        assert_eq!(WorkDays(i64::MAX).checked_neg(), Some(WorkDays(-i64::MAX)));
+        assert_eq!(WorkDays(i64::MIN).checked_neg(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_overflow_panics() {
+        let calendar = WorkCalendar::new();
+        let _ = NaiveDate::MAX + WorkDays(1).on(&calendar);
+    }
+
+    #[test]
+    #[should_panic(expected = "negation overflowed")]
+    fn test_negation_overflow_panics() {
+        let calendar = WorkCalendar::new();
+        let _ = -calendar.span(i64::MIN);
+    }
+}
@@ -0,0 +1,347 @@
+//! Month-oriented queries in calendar systems other than the Gregorian calendar.
+
+use crate::WorkCalendar;
+use chrono::{Datelike, NaiveDate};
+use std::{ops::RangeInclusive, sync::Arc};
+
+/// A calendar system that divides the day line into years and months.
+///
+/// Work-day arithmetic always runs on [`NaiveDate`], which serves as a universal day
+/// line; a calendar system only says how its own years, months, and days map onto it.
+/// Implement this trait, for example on top of a Hijri (Umm al-Qura) conversion crate,
+/// to use the `*_in` month helpers on [`WorkCalendar`] with that system's months.
+/// [`Gregorian`] is provided.
+///
+/// # Examples
+///
+/// A fixed calendar of thirteen 28-day months, with the remaining day or two of each
+/// Gregorian year appended to the thirteenth month:
+///
+/// ```
+/// use workdays::{CalendarSystem, WorkCalendar};
+/// use chrono::{Datelike, NaiveDate};
+///
+/// struct Thirteen;
+///
+/// impl CalendarSystem for Thirteen {
+///     fn to_naive(&self, year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+///         if day == 0 || day > self.days_in_month(year, month)? {
+///             return None;
+///         }
+///         NaiveDate::from_yo_opt(year, (month - 1) * 28 + day)
+///     }
+///
+///     fn to_ymd(&self, date: NaiveDate) -> Option<(i32, u32, u32)> {
+///         let month = ((date.ordinal() - 1) / 28 + 1).min(13);
+///         Some((date.year(), month, date.ordinal() - (month - 1) * 28))
+///     }
+///
+///     fn days_in_month(&self, year: i32, month: u32) -> Option<u32> {
+///         match month {
+///             1..=12 => Some(28),
+///             13 => Some(NaiveDate::from_yo_opt(year, 366).map_or(29, |_| 30)),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// let calendar = WorkCalendar::new();
+/// assert_eq!(calendar.work_days_in_month_in(&Thirteen, 2024, 1), 20);
+/// assert_eq!(
+///     calendar.nth_work_day_of_month_in(&Thirteen, 2024, 2, 1),
+///     NaiveDate::from_ymd_opt(2024, 1, 29)
+/// );
+/// ```
+pub trait CalendarSystem {
+    /// Returns the day-line date of `day` of `month` in `year`, or `None` if that day
+    /// does not exist in this system.
+    fn to_naive(&self, year: i32, month: u32, day: u32) -> Option<NaiveDate>;
+
+    /// Returns the `(year, month, day)` of `date` in this system, or `None` if the
+    /// date lies outside the range the system supports.
+    fn to_ymd(&self, date: NaiveDate) -> Option<(i32, u32, u32)>;
+
+    /// Returns the number of days in `month` of `year`, or `None` if the month does
+    /// not exist.
+    fn days_in_month(&self, year: i32, month: u32) -> Option<u32>;
+}
+
+/// The proleptic Gregorian calendar used by `chrono`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Gregorian;
+
+impl CalendarSystem for Gregorian {
+    fn to_naive(&self, year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(year, month, day)
+    }
+
+    fn to_ymd(&self, date: NaiveDate) -> Option<(i32, u32, u32)> {
+        Some((date.year(), date.month(), date.day()))
+    }
+
+    fn days_in_month(&self, year: i32, month: u32) -> Option<u32> {
+        let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+        let next = match month {
+            12 => NaiveDate::from_ymd_opt(year.checked_add(1)?, 1, 1),
+            _ => NaiveDate::from_ymd_opt(year, month + 1, 1),
+        };
+        match next {
+            Some(next) => u32::try_from((next - first).num_days()).ok(),
+            // December of the last representable year.
+            None => Some(31),
+        }
+    }
+}
+
+impl WorkCalendar {
+    /// Returns the number of work days in a month of `system`.
+    ///
+    /// Same as [`WorkCalendar::work_days_in_month`] when `system` is [`Gregorian`],
+    /// though not memoized. Returns 0 if the month does not exist.
+    pub fn work_days_in_month_in<S: CalendarSystem + ?Sized>(
+        &self,
+        system: &S,
+        year: i32,
+        month: u32,
+    ) -> i64 {
+        match month_bounds(system, year, month) {
+            Some((first, last)) => self.work_days_between(first, last),
+            None => 0,
+        }
+    }
+
+    /// Returns the `n`th working date of a Gregorian month.
+    ///
+    /// A positive `n` counts from the start of the month, so 1 is the first working
+    /// date; a negative `n` counts from the end, so -1 is the last. Returns `None` if
+    /// `n` is zero, the month does not exist, or the month has fewer than `|n|`
+    /// working dates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// assert_eq!(
+    ///     calendar.nth_work_day_of_month(2023, 12, 1),
+    ///     NaiveDate::from_ymd_opt(2023, 12, 1)
+    /// );
+    /// assert_eq!(
+    ///     calendar.nth_work_day_of_month(2023, 12, -1),
+    ///     NaiveDate::from_ymd_opt(2023, 12, 29)
+    /// );
+    /// assert_eq!(calendar.nth_work_day_of_month(2023, 12, 22), None);
+    /// ```
+    pub fn nth_work_day_of_month(&self, year: i32, month: u32, n: i64) -> Option<NaiveDate> {
+        self.nth_work_day_of_month_in(&Gregorian, year, month, n)
+    }
+
+    /// Returns the `n`th working date of a month of `system`.
+    ///
+    /// See [`WorkCalendar::nth_work_day_of_month`] for how `n` is counted.
+    pub fn nth_work_day_of_month_in<S: CalendarSystem + ?Sized>(
+        &self,
+        system: &S,
+        year: i32,
+        month: u32,
+        n: i64,
+    ) -> Option<NaiveDate> {
+        let (first, last) = month_bounds(system, year, month)?;
+        self.nth_working_date_within(first, last, n)
+    }
+
+    /// Returns the `n`th working date from `first` to `last`, inclusive, counting from
+    /// the end for negative `n`.
+    pub(crate) fn nth_working_date_within(
+        &self,
+        first: NaiveDate,
+        last: NaiveDate,
+        n: i64,
+    ) -> Option<NaiveDate> {
+        let found = if n > 0 {
+            self.checked_add_work_days(first.pred_opt()?, n)?
+        } else if n < 0 {
+            self.checked_add_work_days(last.succ_opt()?, n)?
+        } else {
+            return None;
+        };
+        (first..=last).contains(&found).then_some(found)
+    }
+
+    /// Adds a holiday on `day` of `month` of `system` for each year in `years`,
+    /// skipping years in which that day does not exist.
+    ///
+    /// This is the counterpart of a fixed-date [`HolidayRule`](crate::HolidayRule) for
+    /// other calendar systems, such as an Islamic holiday on a fixed Hijri date.
+    /// Because such dates drift against the Gregorian year, they are added as explicit
+    /// holidays over a bounded range of the system's years.
+    ///
+    /// # Returns
+    ///
+    /// The number of holidays that were not already in the calendar.
+    pub fn add_annual_holidays_in<S: CalendarSystem + ?Sized>(
+        &mut self,
+        system: &S,
+        month: u32,
+        day: u32,
+        years: RangeInclusive<i32>,
+    ) -> usize {
+        let holidays = Arc::make_mut(&mut self.holidays);
+        let added = years
+            .filter_map(|year| system.to_naive(year, month, day))
+            .filter(|date| holidays.insert(*date))
+            .count();
+        if added > 0 {
+            self.month_memo.invalidate();
+        }
+        added
+    }
+}
+
+/// Returns the first and last dates of a month of `system`.
+pub(crate) fn month_bounds<S: CalendarSystem + ?Sized>(
+    system: &S,
+    year: i32,
+    month: u32,
+) -> Option<(NaiveDate, NaiveDate)> {
+    let first = system.to_naive(year, month, 1)?;
+    let last = system.to_naive(year, month, system.days_in_month(year, month)?)?;
+    Some((first, last))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{random_calendar, TestRng};
+
+    /// Thirteen 28-day months, with the rest of the Gregorian year in the last one.
+    struct Thirteen;
+
+    impl CalendarSystem for Thirteen {
+        fn to_naive(&self, year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+            if day == 0 || day > self.days_in_month(year, month)? {
+                return None;
+            }
+            NaiveDate::from_yo_opt(year, (month - 1) * 28 + day)
+        }
+
+        fn to_ymd(&self, date: NaiveDate) -> Option<(i32, u32, u32)> {
+            let month = ((date.ordinal() - 1) / 28 + 1).min(13);
+            Some((date.year(), month, date.ordinal() - (month - 1) * 28))
+        }
+
+        fn days_in_month(&self, year: i32, month: u32) -> Option<u32> {
+            match month {
+                1..=12 => Some(28),
+                13 => Some(NaiveDate::from_yo_opt(year, 366).map_or(29, |_| 30)),
+                _ => None,
+            }
+        }
+    }
+
+    /// Brute-force `nth_work_day_of_month_in`.
+    fn naive_nth(
+        calendar: &WorkCalendar,
+        system: &dyn CalendarSystem,
+        year: i32,
+        month: u32,
+        n: i64,
+    ) -> Option<NaiveDate> {
+        let (first, last) = month_bounds(system, year, month)?;
+        let working: Vec<NaiveDate> = first
+            .iter_days()
+            .take_while(|date| *date <= last)
+            .filter(|date| calendar.is_working_date(date))
+            .collect();
+        let index = if n > 0 {
+            usize::try_from(n - 1).ok()?
+        } else {
+            working.len().checked_sub(usize::try_from(-n).ok()?)?
+        };
+        working.get(index).copied()
+    }
+
+    #[test]
+    fn test_gregorian_system() {
+        for (year, month, days) in [(2023, 2, 28), (2024, 2, 29), (2023, 12, 31), (2024, 4, 30)] {
+            assert_eq!(Gregorian.days_in_month(year, month), Some(days));
+        }
+        assert_eq!(Gregorian.days_in_month(2024, 13), None);
+        assert_eq!(Gregorian.days_in_month(NaiveDate::MAX.year(), 12), Some(31));
+
+        let mut rng = TestRng(0x94d0_49bb_1331_11eb);
+        let calendar = random_calendar(&mut rng);
+        for year in 2019..=2029 {
+            for month in 1..=12 {
+                assert_eq!(
+                    calendar.work_days_in_month_in(&Gregorian, year, month),
+                    calendar.work_days_in_month(year, month)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_nth_work_day_matches_brute_force() {
+        let mut rng = TestRng(0xbf58_476d_1ce4_e5b9);
+        let systems: [&dyn CalendarSystem; 2] = [&Gregorian, &Thirteen];
+        for _ in 0..10 {
+            let calendar = random_calendar(&mut rng);
+            for system in systems {
+                for _ in 0..50 {
+                    let year = 2019 + rng.below(10) as i32;
+                    let month = rng.below(14) as u32;
+                    let n = rng.below(61) as i64 - 30;
+                    assert_eq!(
+                        calendar.nth_work_day_of_month_in(system, year, month, n),
+                        naive_nth(&calendar, system, year, month, n),
+                        "{}-{} #{}",
+                        year,
+                        month,
+                        n
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_months_partition_the_year() {
+        let mut rng = TestRng(0x2545_f491_4f6c_dd1d);
+        let calendar = random_calendar(&mut rng);
+        for year in [2023, 2024] {
+            let total: i64 = (1..=13)
+                .map(|month| calendar.work_days_in_month_in(&Thirteen, year, month))
+                .sum();
+            assert_eq!(total, calendar.work_days_in_year(year));
+            assert_eq!(calendar.work_days_in_month_in(&Thirteen, year, 14), 0);
+        }
+        let last = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        assert_eq!(Thirteen.to_ymd(last), Some((2024, 13, 30)));
+        assert_eq!(Thirteen.to_naive(2024, 13, 30), Some(last));
+    }
+
+    #[test]
+    fn test_add_annual_holidays_in() {
+        let mut calendar = WorkCalendar::new();
+        let before = calendar.work_days_in_month(2024, 1);
+        assert_eq!(
+            calendar.add_annual_holidays_in(&Thirteen, 2, 1, 2023..=2025),
+            3
+        );
+        assert_eq!(
+            calendar.add_annual_holidays_in(&Thirteen, 2, 1, 2023..=2025),
+            0
+        );
+        // Day 30 of month 13 exists only in leap years.
+        assert_eq!(
+            calendar.add_annual_holidays_in(&Thirteen, 13, 30, 2023..=2025),
+            1
+        );
+        // 2024-01-29, a Monday, is the first day of month 2.
+        assert!(calendar.is_holiday(&NaiveDate::from_ymd_opt(2024, 1, 29).unwrap()));
+        assert_eq!(calendar.work_days_in_month(2024, 1), before - 1);
+    }
+}
@@ -0,0 +1,274 @@
+//! Capacity and availability across several people's calendars.
+
+use crate::CalendarLike;
+use chrono::NaiveDate;
+
+/// Aggregate capacity of a team over a date range, returned by [`team_capacity`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CapacityReport {
+    /// The total number of person-days: each person's working dates, summed.
+    pub total_person_days: i64,
+    /// Each person's working dates in the range, in the order the calendars were given.
+    pub per_person: Vec<i64>,
+    /// The number of people working on each calendar date of the range, as returned
+    /// by [`availability_between`].
+    pub headcount: Vec<(NaiveDate, usize)>,
+    /// The positions of calendars with no work days at all. They contribute nothing,
+    /// which usually means a misconfigured calendar rather than a person on leave.
+    pub without_work_days: Vec<usize>,
+}
+
+/// Computes a team's capacity from `start` to `end`, inclusive, from one calendar per
+/// person.
+///
+/// Each person's count is their calendar's
+/// [`work_days_between`](CalendarLike::work_days_between), and
+/// [`CapacityReport::total_person_days`] is the sum of those counts. A calendar with
+/// no work days counts zero days and is listed in
+/// [`CapacityReport::without_work_days`] rather than failing the whole report.
+///
+/// # Arguments
+///
+/// * `calendars` - One calendar per person.
+/// * `start` - The first date of the range.
+/// * `end` - The last date of the range.
+///
+/// # Returns
+///
+/// The report. If `start` is after `end`, every count is zero and the headcount
+/// series is empty.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::{team_capacity, WorkCalendar};
+/// use chrono::NaiveDate;
+///
+/// let team = WorkCalendar::new();
+/// let mut on_leave = team.clone();
+/// on_leave.add_holiday(NaiveDate::from_ymd_opt(2023, 9, 5).unwrap());
+///
+/// let report = team_capacity(
+///     &[&team, &on_leave],
+///     NaiveDate::from_ymd_opt(2023, 9, 4).unwrap(),
+///     NaiveDate::from_ymd_opt(2023, 9, 8).unwrap(),
+/// );
+/// assert_eq!(report.total_person_days, 9);
+/// assert_eq!(report.per_person, [5, 4]);
+/// assert_eq!(report.headcount[1].1, 1);
+/// ```
+pub fn team_capacity<C: CalendarLike + ?Sized>(
+    calendars: &[&C],
+    start: NaiveDate,
+    end: NaiveDate,
+) -> CapacityReport {
+    let per_person: Vec<i64> = calendars
+        .iter()
+        .map(|calendar| calendar.work_days_between(start, end))
+        .collect();
+    let without_work_days = calendars
+        .iter()
+        .enumerate()
+        .filter(|(_, calendar)| !calendar.has_work_days())
+        .map(|(i, _)| i)
+        .collect();
+    CapacityReport {
+        total_person_days: per_person.iter().sum(),
+        per_person,
+        headcount: availability_between(calendars, start, end),
+        without_work_days,
+    }
+}
+
+/// Counts the people working on `date`, given one calendar per person.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::{availability_on, WorkCalendar};
+/// use chrono::NaiveDate;
+///
+/// let team = WorkCalendar::new();
+/// let mut on_leave = team.clone();
+/// let date = NaiveDate::from_ymd_opt(2023, 9, 5).unwrap();
+/// on_leave.add_holiday(date);
+/// assert_eq!(availability_on(&[&team, &on_leave, &team], date), 2);
+/// ```
+pub fn availability_on<C: CalendarLike + ?Sized>(calendars: &[&C], date: NaiveDate) -> usize {
+    calendars
+        .iter()
+        .filter(|calendar| calendar.is_working_date(&date))
+        .count()
+}
+
+/// Counts the people working on each calendar date from `start` to `end`, inclusive.
+///
+/// Every date in the range appears, in order, including weekends and holidays on
+/// which nobody works, so the series can be charted without gaps. Each calendar is
+/// asked about each date once.
+///
+/// # Returns
+///
+/// One `(date, headcount)` pair per date, or an empty vector if `start` is after
+/// `end`.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::{availability_between, WorkCalendar};
+/// use chrono::NaiveDate;
+///
+/// let team = WorkCalendar::new();
+/// let mut on_leave = team.clone();
+/// on_leave.add_holiday(NaiveDate::from_ymd_opt(2023, 9, 8).unwrap());
+///
+/// let series = availability_between(
+///     &[&team, &on_leave],
+///     NaiveDate::from_ymd_opt(2023, 9, 7).unwrap(),
+///     NaiveDate::from_ymd_opt(2023, 9, 9).unwrap(),
+/// );
+/// let counts: Vec<usize> = series.iter().map(|(_, n)| *n).collect();
+/// assert_eq!(counts, [2, 1, 0]);
+/// ```
+pub fn availability_between<C: CalendarLike + ?Sized>(
+    calendars: &[&C],
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Vec<(NaiveDate, usize)> {
+    // `NaiveDate::iter_days` stops before `NaiveDate::MAX`, so step by hand.
+    std::iter::successors(Some(start), |date| date.succ_opt())
+        .take_while(|date| *date <= end)
+        .map(|date| (date, availability_on(calendars, date)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{date, random_calendar, TestRng};
+    use crate::WorkCalendar;
+    use chrono::{Duration, Weekday};
+
+    #[test]
+    fn test_team_capacity() {
+        let mut base = WorkCalendar::new();
+        base.add_holiday(date(2023, 12, 25));
+        let mut alice = base.clone();
+        for day in 18..=22 {
+            alice.add_holiday(date(2023, 12, day));
+        }
+        let mut bob = base.clone();
+        bob.add_work_day(Weekday::Sat);
+        let mut idle = base.clone();
+        for n in 0..7 {
+            idle.remove_work_day(&Weekday::try_from(n).unwrap());
+        }
+
+        let report = team_capacity(
+            &[&base, &alice, &idle, &bob],
+            date(2023, 12, 18),
+            date(2023, 12, 31),
+        );
+        assert_eq!(report.per_person, [9, 4, 0, 11]);
+        assert_eq!(report.total_person_days, 24);
+        assert_eq!(report.without_work_days, [2]);
+        assert_eq!(report.headcount.len(), 14);
+        assert_eq!(report.headcount[0], (date(2023, 12, 18), 2));
+        assert_eq!(report.headcount[5], (date(2023, 12, 23), 1));
+        assert_eq!(report.headcount[7], (date(2023, 12, 25), 0));
+        assert_eq!(report.headcount[8], (date(2023, 12, 26), 3));
+        let summed: usize = report.headcount.iter().map(|(_, n)| n).sum();
+        assert_eq!(summed as i64, report.total_person_days);
+
+        let empty = team_capacity(&[&base], date(2023, 12, 31), date(2023, 12, 1));
+        assert_eq!(
+            empty,
+            CapacityReport {
+                per_person: vec![0],
+                ..CapacityReport::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_team_capacity_matches_individual_counts() {
+        let mut rng = TestRng(0x4f1b_bcdc_bfa5_3e0b);
+        let team: Vec<WorkCalendar> = (0..8).map(|_| random_calendar(&mut rng)).collect();
+        let frozen: Vec<_> = team
+            .iter()
+            .map(|calendar| calendar.clone().freeze())
+            .collect();
+        let refs: Vec<&WorkCalendar> = team.iter().collect();
+        let dyn_refs: Vec<&dyn CalendarLike> = frozen
+            .iter()
+            .map(|calendar| calendar as &dyn CalendarLike)
+            .collect();
+        for _ in 0..20 {
+            let start = date(2019, 6, 1) + Duration::days(rng.below(3650) as i64);
+            let end = start + Duration::days(rng.below(400) as i64);
+            let report = team_capacity(&refs, start, end);
+            let expected: Vec<i64> = team
+                .iter()
+                .map(|calendar| calendar.work_days_between(start, end))
+                .collect();
+            assert_eq!(report.per_person, expected);
+            assert_eq!(report.total_person_days, expected.iter().sum::<i64>());
+            assert_eq!(team_capacity(&dyn_refs, start, end), report);
+        }
+    }
+
+    #[test]
+    fn test_availability() {
+        let base = WorkCalendar::new();
+        let mut team: Vec<WorkCalendar> = vec![base.clone(); 5];
+        // Two of five out on Friday the 8th, one on Monday the 11th.
+        team[0].add_holiday(date(2023, 9, 8));
+        team[1].add_holiday(date(2023, 9, 8));
+        team[2].add_holiday(date(2023, 9, 11));
+        team[3].add_work_day(Weekday::Sat);
+        let refs: Vec<&WorkCalendar> = team.iter().collect();
+
+        assert_eq!(availability_on(&refs, date(2023, 9, 7)), 5);
+        assert_eq!(availability_on(&refs, date(2023, 9, 8)), 3);
+        assert_eq!(availability_on(&refs, date(2023, 9, 9)), 1);
+        assert_eq!(availability_on::<WorkCalendar>(&[], date(2023, 9, 8)), 0);
+
+        let series = availability_between(&refs, date(2023, 9, 7), date(2023, 9, 12));
+        assert_eq!(
+            series,
+            [
+                (date(2023, 9, 7), 5),
+                (date(2023, 9, 8), 3),
+                (date(2023, 9, 9), 1),
+                (date(2023, 9, 10), 0),
+                (date(2023, 9, 11), 4),
+                (date(2023, 9, 12), 5),
+            ]
+        );
+        // Dates with at least 60% of the team in.
+        let quorum: Vec<NaiveDate> = series
+            .iter()
+            .filter(|(_, n)| *n * 10 >= refs.len() * 6)
+            .map(|(date, _)| *date)
+            .collect();
+        assert_eq!(
+            quorum,
+            [
+                date(2023, 9, 7),
+                date(2023, 9, 8),
+                date(2023, 9, 11),
+                date(2023, 9, 12)
+            ]
+        );
+
+        assert_eq!(
+            availability_between(&refs, date(2023, 9, 12), date(2023, 9, 7)),
+            []
+        );
+        let last = availability_between(&refs, NaiveDate::MAX, NaiveDate::MAX);
+        assert_eq!(
+            last,
+            [(NaiveDate::MAX, availability_on(&refs, NaiveDate::MAX))]
+        );
+    }
+}
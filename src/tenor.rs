@@ -0,0 +1,298 @@
+//! Parsing of market tenors such as `3M` or `10B` and adding them to dates.
+
+use crate::{AdjustmentConvention, WorkCalendar, WorkCalendarError};
+use chrono::{Days, NaiveDate};
+
+/// A parsed tenor, with weeks folded into days and years into months.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tenor {
+    /// Calendar days, from `D` and `W`.
+    Days(i64),
+    /// Calendar months, from `M` and `Y`.
+    Months(i32),
+    /// Working dates, from `B`.
+    BusinessDays(i64),
+}
+
+/// Parses a tenor: an optional `+` or `-`, a non-zero count, and a unit letter.
+fn parse_tenor(input: &str) -> Result<Tenor, WorkCalendarError> {
+    let invalid = |reason| WorkCalendarError::InvalidTenor {
+        tenor: input.to_string(),
+        reason,
+    };
+    let trimmed = input.trim();
+    let (negative, unsigned) = match trimmed.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+    };
+    let unit = unsigned
+        .chars()
+        .last()
+        .ok_or_else(|| invalid("expected a count and a unit"))?;
+    let digits = &unsigned[..unsigned.len() - unit.len_utf8()];
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid("expected a count of digits before the unit"));
+    }
+    let count: i64 = digits.parse().map_err(|_| invalid("count is too large"))?;
+    if count == 0 {
+        return Err(invalid("count must not be zero"));
+    }
+    let count = if negative { -count } else { count };
+    let too_large = || invalid("count is too large");
+    let months = |per_unit: i32| {
+        i32::try_from(count)
+            .ok()
+            .and_then(|count| count.checked_mul(per_unit))
+            .ok_or_else(too_large)
+    };
+    match unit.to_ascii_uppercase() {
+        'D' => Ok(Tenor::Days(count)),
+        'W' => count.checked_mul(7).map(Tenor::Days).ok_or_else(too_large),
+        'M' => months(1).map(Tenor::Months),
+        'Y' => months(12).map(Tenor::Months),
+        'B' => Ok(Tenor::BusinessDays(count)),
+        _ => Err(invalid("unit must be one of D, W, M, Y or B")),
+    }
+}
+
+impl WorkCalendar {
+    /// Adds a market tenor such as `"2W"`, `"6M"` or `"10B"` to `date`.
+    ///
+    /// A tenor is an optional `+` or `-`, a non-zero count, and one of these units,
+    /// in either case:
+    ///
+    /// | Unit | Meaning        | Arithmetic                                           |
+    /// |------|----------------|------------------------------------------------------|
+    /// | `D`  | calendar days  | add the days, then adjust                            |
+    /// | `W`  | weeks          | add seven calendar days per week, then adjust        |
+    /// | `M`  | months         | [`WorkCalendar::add_tenor_months`] with end-of-month |
+    /// | `Y`  | years          | twelve months per year, as for `M`                   |
+    /// | `B`  | business days  | step working dates; no adjustment                    |
+    ///
+    /// Month and year tenors apply the end-of-month rule, as market tenors do: from the
+    /// last working date of a month, they land on the last working date of the target
+    /// month. Business-day tenors count as
+    /// [`WorkCalendar::checked_add_work_days`] does, so the result is always a working
+    /// date and `convention` is not used.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The start date.
+    /// * `tenor` - The tenor, e.g. `"3M"` or `"-2b"`.
+    /// * `convention` - How to move a calendar-day, week, month or year result that is
+    ///   not a working date.
+    ///
+    /// # Errors
+    ///
+    /// * [`WorkCalendarError::InvalidTenor`] if `tenor` is malformed, has an unknown
+    ///   unit, or has a count of zero or one too large to apply.
+    /// * [`WorkCalendarError::DateOutOfRange`] if the result cannot be represented.
+    /// * The errors of [`WorkCalendar::adjust`] for the adjustment, and of
+    ///   [`WorkCalendar::next_work_day`] for business-day tenors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::{AdjustmentConvention, WorkCalendar};
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let convention = AdjustmentConvention::ModifiedFollowing;
+    /// let trade = NaiveDate::from_ymd_opt(2023, 9, 15).unwrap(); // Friday
+    ///
+    /// assert_eq!(
+    ///     calendar.add_tenor(trade, "2W", convention).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2023, 9, 29).unwrap()
+    /// );
+    /// assert_eq!(
+    ///     calendar.add_tenor(trade, "10b", convention).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2023, 9, 29).unwrap()
+    /// );
+    /// // October 15 is a Sunday.
+    /// assert_eq!(
+    ///     calendar.add_tenor(trade, "1M", convention).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2023, 10, 16).unwrap()
+    /// );
+    /// assert!(calendar.add_tenor(trade, "3Q", convention).is_err());
+    /// ```
+    pub fn add_tenor(
+        &self,
+        date: NaiveDate,
+        tenor: &str,
+        convention: AdjustmentConvention,
+    ) -> Result<NaiveDate, WorkCalendarError> {
+        match parse_tenor(tenor)? {
+            Tenor::Days(days) => {
+                let magnitude = Days::new(days.unsigned_abs());
+                let shifted = if days < 0 {
+                    date.checked_sub_days(magnitude)
+                } else {
+                    date.checked_add_days(magnitude)
+                };
+                let shifted = shifted.ok_or_else(|| WorkCalendarError::DateOutOfRange {
+                    calendar: self.name().map(String::from),
+                })?;
+                self.adjust(shifted, convention)
+            }
+            Tenor::Months(months) => self.add_tenor_months(date, months, true, convention),
+            Tenor::BusinessDays(days) => self.try_add_work_days(date, days),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{date, random_calendar, TestRng};
+
+    #[test]
+    fn test_parse_tenor() {
+        let cases = [
+            ("3D", Tenor::Days(3)),
+            ("3d", Tenor::Days(3)),
+            ("+2W", Tenor::Days(14)),
+            ("-2w", Tenor::Days(-14)),
+            (" 6M ", Tenor::Months(6)),
+            ("1Y", Tenor::Months(12)),
+            ("-10y", Tenor::Months(-120)),
+            ("10B", Tenor::BusinessDays(10)),
+            ("007b", Tenor::BusinessDays(7)),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(parse_tenor(input), Ok(expected), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_tenor_errors() {
+        let cases = [
+            ("", "expected a count and a unit"),
+            ("-", "expected a count and a unit"),
+            ("M", "expected a count of digits before the unit"),
+            ("+-3M", "expected a count of digits before the unit"),
+            ("1.5Y", "expected a count of digits before the unit"),
+            ("3 M", "expected a count of digits before the unit"),
+            ("3MM", "expected a count of digits before the unit"),
+            ("3Q", "unit must be one of D, W, M, Y or B"),
+            ("3é", "unit must be one of D, W, M, Y or B"),
+            ("0D", "count must not be zero"),
+            ("-0B", "count must not be zero"),
+            ("99999999999999999999D", "count is too large"),
+            ("2000000000000000000W", "count is too large"),
+            ("3000000000M", "count is too large"),
+            ("200000000Y", "count is too large"),
+        ];
+        for (input, reason) in cases {
+            assert_eq!(
+                parse_tenor(input),
+                Err(WorkCalendarError::InvalidTenor {
+                    tenor: input.to_string(),
+                    reason
+                }),
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_add_tenor_units() {
+        use AdjustmentConvention::*;
+
+        let mut calendar = WorkCalendar::new();
+        calendar.add_holiday(date(2023, 12, 25));
+        let friday = date(2023, 12, 22);
+        let cases = [
+            // Saturday moves to the Tuesday after the Christmas holiday.
+            ("1D", Following, date(2023, 12, 26)),
+            ("1D", Preceding, date(2023, 12, 22)),
+            ("3D", Following, date(2023, 12, 26)),
+            ("3D", Unadjusted, date(2023, 12, 25)),
+            ("-1W", Following, date(2023, 12, 15)),
+            ("1B", Unadjusted, date(2023, 12, 26)),
+            ("-5B", Following, date(2023, 12, 15)),
+            // January 22, 2024 is a Monday.
+            ("1M", ModifiedFollowing, date(2024, 1, 22)),
+            ("-1Y", ModifiedFollowing, date(2022, 12, 22)),
+        ];
+        for (tenor, convention, expected) in cases {
+            assert_eq!(
+                calendar.add_tenor(friday, tenor, convention),
+                Ok(expected),
+                "{} {:?}",
+                tenor,
+                convention
+            );
+        }
+
+        // Month and year tenors keep to the end of the month.
+        let month_end = date(2023, 2, 28);
+        assert_eq!(
+            calendar.add_tenor(month_end, "1M", Unadjusted),
+            Ok(date(2023, 3, 31))
+        );
+        assert_eq!(
+            calendar.add_tenor(month_end, "1Y", Unadjusted),
+            Ok(date(2024, 2, 29))
+        );
+        assert_eq!(
+            calendar.add_tenor(date(2024, 2, 29), "-1y", Unadjusted),
+            Ok(date(2023, 2, 28))
+        );
+    }
+
+    #[test]
+    fn test_add_tenor_errors() {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("Desk");
+        let out_of_range = Err(WorkCalendarError::DateOutOfRange {
+            calendar: Some("Desk".to_string()),
+        });
+        let convention = AdjustmentConvention::Following;
+        assert_eq!(
+            calendar.add_tenor(NaiveDate::MAX, "1D", convention),
+            out_of_range
+        );
+        assert_eq!(
+            calendar.add_tenor(NaiveDate::MIN, "-1W", convention),
+            out_of_range
+        );
+        assert_eq!(
+            calendar.add_tenor(NaiveDate::MAX, "1M", convention),
+            out_of_range
+        );
+        assert_eq!(
+            calendar.add_tenor(date(2023, 1, 2), "9223372036854775807D", convention),
+            out_of_range
+        );
+        assert!(matches!(
+            calendar.add_tenor(date(2023, 1, 2), "0M", convention),
+            Err(WorkCalendarError::InvalidTenor { .. })
+        ));
+    }
+
+    #[test]
+    fn test_add_tenor_matches_primitives_randomized() {
+        let mut rng = TestRng(0x2c71_e94b_08d3_a65f);
+        for _ in 0..200 {
+            let calendar = random_calendar(&mut rng);
+            let start = date(2020, 1, 1) + chrono::Duration::days(rng.below(3000) as i64);
+            let count = rng.below(40) as i64 + 1;
+            let count = if rng.below(2) == 0 { count } else { -count };
+            let convention = AdjustmentConvention::ModifiedFollowing;
+
+            assert_eq!(
+                calendar.add_tenor(start, &format!("{}b", count), convention),
+                calendar.try_add_work_days(start, count)
+            );
+            assert_eq!(
+                calendar.add_tenor(start, &format!("{}W", count), convention),
+                calendar.adjust(start + chrono::Duration::weeks(count), convention)
+            );
+            assert_eq!(
+                calendar.add_tenor(start, &format!("{}Y", count), convention),
+                calendar.add_tenor_months(start, count as i32 * 12, true, convention)
+            );
+        }
+    }
+}
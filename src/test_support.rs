@@ -0,0 +1,127 @@
+//! Helpers shared by the crate's tests.
+
+use crate::{Holiday, HolidayRule, WorkCalendar};
+use chrono::{Duration, NaiveDate, Weekday};
+
+/// Returns the date `y-m-d`, which must exist.
+pub fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+/// Small deterministic xorshift generator for randomized tests.
+pub struct TestRng(pub u64);
+
+impl TestRng {
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
+
+/// Builds a calendar with a random non-empty work week, holidays, and working-date
+/// overrides between 2020 and 2028, sometimes including a dense cluster of
+/// consecutive holidays, plus a few random recurring holiday rules and holiday layers
+/// and sometimes a change of work week partway through.
+pub fn random_calendar(rng: &mut TestRng) -> WorkCalendar {
+    let mut calendar = WorkCalendar::new();
+    calendar.work_days = std::iter::empty().collect();
+    while calendar.work_days.is_empty() {
+        for n in 0..7u8 {
+            if rng.below(2) == 0 {
+                calendar.add_work_day(Weekday::try_from(n).unwrap());
+            }
+        }
+    }
+    let base = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+    for _ in 0..rng.below(60) {
+        calendar.add_holiday(base + Duration::days(rng.below(3000) as i64));
+    }
+    for _ in 0..rng.below(20) {
+        calendar.add_working_date(base + Duration::days(rng.below(3000) as i64));
+    }
+    if rng.below(3) == 0 {
+        let cluster_start = base + Duration::days(rng.below(3000) as i64);
+        for offset in 0..rng.below(40) as i64 {
+            calendar.add_holiday(cluster_start + Duration::days(offset));
+        }
+    }
+    for _ in 0..rng.below(4) {
+        calendar.add_recurring_holiday(random_rule(rng));
+    }
+    for _ in 0..rng.below(3) {
+        let from = base + Duration::days(rng.below(3000) as i64);
+        let to = (rng.below(2) == 0).then(|| from + Duration::days(rng.below(1500) as i64));
+        let holiday = if rng.below(2) == 0 {
+            Holiday::Rule(random_rule(rng))
+        } else {
+            Holiday::Date(base + Duration::days(rng.below(3000) as i64))
+        };
+        calendar.add_holiday_effective(holiday, from, to).unwrap();
+    }
+    if rng.below(4) == 0 {
+        let mut days = Vec::new();
+        while days.is_empty() {
+            days = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]
+                .into_iter()
+                .filter(|_| rng.below(2) == 0)
+                .collect();
+        }
+        let from = base + Duration::days(rng.below(3000) as i64);
+        calendar
+            .set_work_days_effective(from, &days.join(","))
+            .unwrap();
+    }
+    calendar
+}
+
+/// Builds a random recurring holiday rule, including ones that skip some years.
+pub fn random_rule(rng: &mut TestRng) -> HolidayRule {
+    let month = rng.below(12) as u32 + 1;
+    if rng.below(2) == 0 {
+        HolidayRule::FixedDate {
+            month,
+            day: rng.below(31) as u32 + 1,
+        }
+    } else {
+        HolidayRule::NthWeekday {
+            month,
+            weekday: Weekday::try_from(rng.below(7) as u8).unwrap(),
+            nth: rng.below(11) as i8 - 5,
+        }
+    }
+}
+
+/// The test binary's allocator: the system allocator, counting allocations made on
+/// each thread so tests can assert that a call does not allocate.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+unsafe impl std::alloc::GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        std::alloc::System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+        std::alloc::System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Returns the number of allocations `f` makes on the current thread, with its result.
+pub fn count_allocations<T>(f: impl FnOnce() -> T) -> (usize, T) {
+    let before = ALLOCATIONS.with(|count| count.get());
+    let result = f();
+    (ALLOCATIONS.with(|count| count.get()) - before, result)
+}
@@ -0,0 +1,137 @@
+//! Interoperability with the `time` crate, available with the `time` feature.
+//!
+//! Each method converts its arguments to `chrono` dates, calls the matching `chrono`
+//! method, and converts the result back, so both APIs share one implementation.
+
+use crate::{WorkCalendar, WorkCalendarError};
+use chrono::{Datelike, NaiveDate};
+
+impl WorkCalendar {
+    /// Checks if a `time::Date` is a working date.
+    ///
+    /// Same as [`WorkCalendar::is_working_date`]. Dates beyond the range `chrono` can
+    /// represent, which only exist with `time`'s `large-dates` feature, are never
+    /// working dates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use time::{Date, Month};
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let monday = Date::from_calendar_date(2023, Month::August, 21).unwrap();
+    /// let saturday = Date::from_calendar_date(2023, Month::August, 26).unwrap();
+    /// assert!(calendar.is_working_time_date(monday));
+    /// assert!(!calendar.is_working_time_date(saturday));
+    /// ```
+    pub fn is_working_time_date(&self, date: time::Date) -> bool {
+        to_naive(date).map_or(false, |date| self.is_working_date(&date))
+    }
+
+    /// Computes the end date and calendar duration for a `time::Date` start date.
+    ///
+    /// Same as [`WorkCalendar::compute_end_date`].
+    ///
+    /// # Errors
+    ///
+    /// Any error from [`WorkCalendar::compute_end_date`], or
+    /// [`WorkCalendarError::DateOutOfRange`] if either date cannot be represented by
+    /// the other crate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use time::{Date, Duration, Month};
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let start = Date::from_calendar_date(2023, Month::August, 21).unwrap();
+    /// let (end, span) = calendar.compute_end_date_time(start, 6).unwrap();
+    /// assert_eq!(end, Date::from_calendar_date(2023, Month::August, 28).unwrap());
+    /// assert_eq!(span, Duration::days(7));
+    /// ```
+    pub fn compute_end_date_time(
+        &self,
+        start_date: time::Date,
+        days_worked: i64,
+    ) -> Result<(time::Date, time::Duration), WorkCalendarError> {
+        let out_of_range = || WorkCalendarError::DateOutOfRange {
+            calendar: self.name().map(String::from),
+        };
+        let start_date = to_naive(start_date).ok_or_else(out_of_range)?;
+        let (end_date, span) = self.compute_end_date(start_date, days_worked)?;
+        let end_date = to_time(end_date).ok_or_else(out_of_range)?;
+        Ok((end_date, time::Duration::days(span.num_days())))
+    }
+}
+
+/// Converts a `time::Date` to a `chrono` date, if `chrono` can represent it.
+fn to_naive(date: time::Date) -> Option<NaiveDate> {
+    NaiveDate::from_yo_opt(date.year(), u32::from(date.ordinal()))
+}
+
+/// Converts a `chrono` date to a `time::Date`, if `time` can represent it.
+fn to_time(date: NaiveDate) -> Option<time::Date> {
+    let ordinal = u16::try_from(date.ordinal()).ok()?;
+    time::Date::from_ordinal_date(date.year(), ordinal).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{random_calendar, TestRng};
+    use chrono::Duration;
+
+    #[test]
+    fn test_conversions_round_trip() {
+        let mut date = to_naive(time::Date::MIN).unwrap();
+        let last = to_naive(time::Date::MAX).unwrap();
+        while date <= last {
+            let converted = to_time(date).unwrap();
+            assert_eq!(converted.to_string(), date.to_string());
+            assert_eq!(to_naive(converted), Some(date));
+            date += Duration::days(997);
+        }
+        assert_eq!(to_time(last + Duration::days(1)), None);
+        assert_eq!(to_time(NaiveDate::MAX), None);
+    }
+
+    #[test]
+    fn test_time_api_matches_chrono_api() {
+        let mut rng = TestRng(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..20 {
+            let calendar = random_calendar(&mut rng);
+            let base = NaiveDate::from_ymd_opt(2019, 6, 1).unwrap();
+            for _ in 0..200 {
+                let date = base + Duration::days(rng.below(3650) as i64);
+                let time_date = to_time(date).unwrap();
+                assert_eq!(
+                    calendar.is_working_time_date(time_date),
+                    calendar.is_working_date(&date)
+                );
+
+                let days_worked = rng.below(400) as i64 - 5;
+                let expected = calendar
+                    .compute_end_date(date, days_worked)
+                    .map(|(end, span)| (to_time(end).unwrap(), span.num_days()));
+                let actual = calendar
+                    .compute_end_date_time(time_date, days_worked)
+                    .map(|(end, span)| (end, span.whole_days()));
+                assert_eq!(actual, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_end_date_beyond_time_range() {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("Ops");
+        assert_eq!(
+            calendar.compute_end_date_time(time::Date::MAX, 10),
+            Err(WorkCalendarError::DateOutOfRange {
+                calendar: Some("Ops".to_string())
+            })
+        );
+    }
+}
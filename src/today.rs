@@ -0,0 +1,223 @@
+//! Work-day queries relative to today, with an injectable source of today's date.
+
+use crate::{WorkCalendar, WorkCalendarError};
+use chrono::{Local, NaiveDate};
+use std::{cmp::Ordering, fmt};
+
+/// A source of today's date.
+///
+/// [`SystemToday`] reads the system's local date. Tests can pass a fixed `NaiveDate`
+/// or any `Fn() -> NaiveDate` closure instead, so results do not depend on when or
+/// in which time zone they run.
+pub trait Today {
+    /// Returns today's date.
+    fn today(&self) -> NaiveDate;
+}
+
+/// Today's date in the system's local time zone.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SystemToday;
+
+impl Today for SystemToday {
+    fn today(&self) -> NaiveDate {
+        Local::now().date_naive()
+    }
+}
+
+/// A fixed date, for tests and replays.
+impl Today for NaiveDate {
+    fn today(&self) -> NaiveDate {
+        *self
+    }
+}
+
+impl<F: Fn() -> NaiveDate> Today for F {
+    fn today(&self) -> NaiveDate {
+        self()
+    }
+}
+
+/// A work calendar paired with a source of today's date.
+///
+/// Every query asks the clock for today's date when it runs, so a long-lived value
+/// follows the date as it changes. Like [`RangeCounter`](crate::RangeCounter), it
+/// captures the calendar as it was when it was created.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::{TodayCalendar, WorkCalendar};
+/// use chrono::NaiveDate;
+///
+/// let calendar = WorkCalendar::new();
+/// let friday = NaiveDate::from_ymd_opt(2025, 6, 27).unwrap();
+/// let today = TodayCalendar::with_clock(&calendar, friday);
+///
+/// assert!(today.is_working_today());
+/// assert_eq!(
+///     today.next_work_day_from_today().unwrap(),
+///     NaiveDate::from_ymd_opt(2025, 6, 30).unwrap()
+/// );
+/// assert_eq!(today.work_days_until(NaiveDate::from_ymd_opt(2025, 7, 3).unwrap()), 4);
+///
+/// // The system clock is the default.
+/// let live = TodayCalendar::new(&calendar);
+/// let _ = live.is_working_today();
+/// ```
+#[derive(Clone)]
+pub struct TodayCalendar<T = SystemToday> {
+    calendar: WorkCalendar,
+    clock: T,
+}
+
+impl TodayCalendar {
+    /// Creates a today-relative view of `calendar` that reads the system's local date.
+    pub fn new(calendar: &WorkCalendar) -> Self {
+        TodayCalendar::with_clock(calendar, SystemToday)
+    }
+}
+
+impl<T: Today> TodayCalendar<T> {
+    /// Creates a today-relative view of `calendar` that asks `clock` for today's date.
+    ///
+    /// # Arguments
+    ///
+    /// * `calendar` - The calendar to query.
+    /// * `clock` - The source of today's date: [`SystemToday`], a fixed `NaiveDate`, or
+    ///   a `Fn() -> NaiveDate` closure.
+    pub fn with_clock(calendar: &WorkCalendar, clock: T) -> Self {
+        TodayCalendar {
+            calendar: calendar.clone(),
+            clock,
+        }
+    }
+
+    /// Returns the calendar captured when the view was created.
+    pub fn calendar(&self) -> &WorkCalendar {
+        &self.calendar
+    }
+
+    /// Returns today's date according to the clock.
+    pub fn today(&self) -> NaiveDate {
+        self.clock.today()
+    }
+
+    /// Checks if today is a working date.
+    pub fn is_working_today(&self) -> bool {
+        self.calendar.is_working_date(&self.today())
+    }
+
+    /// Counts the working dates left before `deadline`.
+    ///
+    /// Today is not counted, since it is already under way, but the deadline is. A
+    /// deadline in the past gives the negated number of working dates since it, so a
+    /// deadline on the previous working date is -1.
+    ///
+    /// # Arguments
+    ///
+    /// * `deadline` - The last date on which work can be done.
+    ///
+    /// # Returns
+    ///
+    /// The number of working dates after today up to and including `deadline`, or
+    /// zero if `deadline` is today.
+    pub fn work_days_until(&self, deadline: NaiveDate) -> i64 {
+        let today = self.today();
+        // The earlier of the two dates always has a successor.
+        match deadline.cmp(&today) {
+            Ordering::Greater => self
+                .calendar
+                .work_days_between(today.succ_opt().unwrap(), deadline),
+            Ordering::Less => -self
+                .calendar
+                .work_days_between(deadline.succ_opt().unwrap(), today),
+            Ordering::Equal => 0,
+        }
+    }
+
+    /// Returns the first working date after today.
+    ///
+    /// # Errors
+    ///
+    /// The errors of [`WorkCalendar::next_work_day`].
+    pub fn next_work_day_from_today(&self) -> Result<NaiveDate, WorkCalendarError> {
+        self.calendar.next_work_day(self.today())
+    }
+}
+
+impl<T> fmt::Debug for TodayCalendar<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TodayCalendar")
+            .field("calendar", &self.calendar)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::date;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_fixed_clock() {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("Ops");
+        calendar.add_holiday(date(2025, 7, 4));
+        let today = TodayCalendar::with_clock(&calendar, date(2025, 7, 3));
+
+        assert_eq!(today.today(), date(2025, 7, 3));
+        assert!(today.is_working_today());
+        assert_eq!(today.next_work_day_from_today(), Ok(date(2025, 7, 7)));
+        let cases = [
+            (date(2025, 7, 3), 0),
+            (date(2025, 7, 4), 0),
+            (date(2025, 7, 7), 1),
+            (date(2025, 7, 11), 5),
+            (date(2025, 7, 2), -1),
+            (date(2025, 6, 27), -4),
+        ];
+        for (deadline, expected) in cases {
+            assert_eq!(today.work_days_until(deadline), expected, "{}", deadline);
+        }
+
+        let holiday = TodayCalendar::with_clock(&calendar, date(2025, 7, 4));
+        assert!(!holiday.is_working_today());
+        assert_eq!(holiday.work_days_until(date(2025, 7, 3)), 0);
+        assert_eq!(holiday.work_days_until(date(2025, 7, 2)), -1);
+    }
+
+    #[test]
+    fn test_closure_clock_is_read_on_every_query() {
+        let calendar = WorkCalendar::new();
+        let now = Cell::new(date(2025, 6, 27));
+        let today = TodayCalendar::with_clock(&calendar, || now.get());
+        assert!(today.is_working_today());
+        now.set(date(2025, 6, 28));
+        assert!(!today.is_working_today());
+        assert_eq!(today.next_work_day_from_today(), Ok(date(2025, 6, 30)));
+        assert_eq!(today.work_days_until(date(2025, 6, 30)), 1);
+    }
+
+    #[test]
+    fn test_range_edges() {
+        let calendar = WorkCalendar::new();
+        let today = TodayCalendar::with_clock(&calendar, NaiveDate::MAX);
+        assert_eq!(today.work_days_until(NaiveDate::MAX), 0);
+        let first = TodayCalendar::with_clock(&calendar, NaiveDate::MIN);
+        assert_eq!(
+            first.work_days_until(date(1, 1, 1)),
+            calendar.work_days_between(NaiveDate::MIN.succ_opt().unwrap(), date(1, 1, 1))
+        );
+    }
+
+    #[test]
+    fn test_system_clock() {
+        let before = Local::now().date_naive();
+        let today = TodayCalendar::new(&WorkCalendar::new());
+        let date = today.today();
+        // The date may roll over between the calls.
+        assert!(date == before || date == Local::now().date_naive());
+        assert!(format!("{:?}", today).starts_with("TodayCalendar"));
+    }
+}
@@ -0,0 +1,165 @@
+//! Translating a work-day count quoted on one calendar into another.
+
+use crate::{CalendarLike, WorkCalendarError};
+use chrono::NaiveDate;
+
+/// Translates `days` work days on `from`, starting at `start`, into work days on `to`.
+///
+/// The end date is [`from.compute_end(start, days)`](CalendarLike::compute_end), so the
+/// start date counts as the first of the `days` if it is a working date on `from`. The
+/// translated count is [`to.work_days_between(start, end)`](CalendarLike::work_days_between):
+/// every working date of `to` in that calendar span, both ends included. Each
+/// calendar decides the start date's status for itself, so a start that is a working
+/// date on `to` but not on `from` adds a day to the count, and one that is a working
+/// date on `from` but not on `to` removes one.
+///
+/// # Arguments
+///
+/// * `from` - The calendar the count is quoted on.
+/// * `to` - The calendar to translate the count into.
+/// * `start` - The date work starts.
+/// * `days` - The number of work days on `from`.
+///
+/// # Errors
+///
+/// The errors of `from.compute_end(start, days)`.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::{translate_duration, WorkCalendar};
+/// use chrono::NaiveDate;
+///
+/// let mut vendor = WorkCalendar::new();
+/// vendor.set_work_days("Mon,Tue,Wed,Thu,Fri,Sat").unwrap();
+/// let ours = WorkCalendar::new();
+///
+/// // 15 vendor days from Monday, September 4, 2023 end on Wednesday the 20th, which
+/// // is 13 of our days.
+/// let start = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+/// assert_eq!(translate_duration(&vendor, &ours, start, 15).unwrap(), 13);
+/// ```
+pub fn translate_duration<F, T>(
+    from: &F,
+    to: &T,
+    start: NaiveDate,
+    days: i64,
+) -> Result<i64, WorkCalendarError>
+where
+    F: CalendarLike + ?Sized,
+    T: CalendarLike + ?Sized,
+{
+    translate_duration_with_end(from, to, start, days).map(|(_, days)| days)
+}
+
+/// Like [`translate_duration`], but also returns the end date.
+///
+/// # Returns
+///
+/// The end date on `from` and the number of working dates of `to` from `start` to it,
+/// inclusive.
+///
+/// # Errors
+///
+/// The errors of `from.compute_end(start, days)`.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::{translate_duration_with_end, WorkCalendar};
+/// use chrono::NaiveDate;
+///
+/// let mut vendor = WorkCalendar::new();
+/// vendor.set_work_days("Mon,Tue,Wed,Thu,Fri,Sat").unwrap();
+/// let ours = WorkCalendar::new();
+///
+/// let start = NaiveDate::from_ymd_opt(2023, 9, 4).unwrap();
+/// let (end, days) = translate_duration_with_end(&vendor, &ours, start, 15).unwrap();
+/// assert_eq!(end, NaiveDate::from_ymd_opt(2023, 9, 20).unwrap());
+/// assert_eq!(days, 13);
+/// ```
+pub fn translate_duration_with_end<F, T>(
+    from: &F,
+    to: &T,
+    start: NaiveDate,
+    days: i64,
+) -> Result<(NaiveDate, i64), WorkCalendarError>
+where
+    F: CalendarLike + ?Sized,
+    T: CalendarLike + ?Sized,
+{
+    let end = from.compute_end(start, days)?.date;
+    Ok((end, to.work_days_between(start, end)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{date, random_calendar, TestRng};
+    use crate::WorkCalendar;
+    use chrono::Duration;
+
+    #[test]
+    fn test_start_date_status_differs() {
+        let mut vendor = WorkCalendar::new();
+        vendor.set_work_days("Mon,Tue,Wed,Thu,Fri,Sat").unwrap();
+        let mut ours = WorkCalendar::new();
+        ours.add_holiday(date(2023, 9, 4));
+
+        // Saturday is a vendor day but not ours: the vendor counts it, we don't.
+        let saturday = date(2023, 9, 2);
+        assert_eq!(
+            translate_duration_with_end(&vendor, &ours, saturday, 3),
+            Ok((date(2023, 9, 5), 1))
+        );
+        // The other way round, the vendor's count starts on Monday and ours on Saturday.
+        assert_eq!(
+            translate_duration_with_end(&ours, &vendor, saturday, 3),
+            Ok((date(2023, 9, 7), 5))
+        );
+
+        // Monday is a vendor day but our holiday.
+        assert_eq!(
+            translate_duration_with_end(&vendor, &ours, date(2023, 9, 4), 1),
+            Ok((date(2023, 9, 4), 0))
+        );
+    }
+
+    #[test]
+    fn test_same_calendar_is_identity() {
+        let mut rng = TestRng(0x48e1_d7b2_9c05_3fa6);
+        for _ in 0..50 {
+            let calendar = random_calendar(&mut rng);
+            let start = date(2022, 1, 1) + Duration::days(rng.below(1000) as i64);
+            if !calendar.is_working_date(&start) {
+                continue;
+            }
+            let days = rng.below(200) as i64;
+            assert_eq!(
+                translate_duration(&calendar, &calendar, start, days),
+                Ok(days),
+                "{:?}, {} + {}",
+                calendar,
+                start,
+                days
+            );
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let vendor = WorkCalendar::new();
+        let frozen = WorkCalendar::new().freeze();
+        assert_eq!(
+            translate_duration(&vendor, &frozen, date(2023, 9, 4), -1),
+            Err(WorkCalendarError::NegativeDaysWorked { calendar: None })
+        );
+        assert_eq!(
+            translate_duration(&vendor, &frozen, date(2023, 9, 2), 0),
+            Err(WorkCalendarError::NonWorkingStart {
+                calendar: None,
+                date: date(2023, 9, 2)
+            })
+        );
+    }
+}
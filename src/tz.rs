@@ -0,0 +1,150 @@
+//! Time-zone-aware wrappers, available with the `tz` feature.
+
+use crate::{WorkCalendar, WorkCalendarError};
+use chrono::{DateTime, Duration, LocalResult, NaiveDateTime, Offset, TimeZone};
+
+impl WorkCalendar {
+    /// Checks if the local date of `instant`, in its own time zone, is a working date.
+    ///
+    /// The date is taken from the wall clock in `instant`'s zone, not from UTC, so
+    /// 23:30 on a Friday in New York is a Friday even though it is already Saturday in
+    /// UTC.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::TimeZone;
+    /// use chrono_tz::America::New_York;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let friday_night = New_York.with_ymd_and_hms(2024, 1, 5, 23, 30, 0).unwrap();
+    /// assert!(calendar.is_working_instant(&friday_night));
+    /// ```
+    pub fn is_working_instant<Tz: TimeZone>(&self, instant: &DateTime<Tz>) -> bool {
+        self.is_working_date(&instant.date_naive())
+    }
+
+    /// Computes the end of `days_worked` work days starting on the local date of
+    /// `start`, keeping `start`'s wall-clock time and time zone.
+    ///
+    /// The date math is exactly [`WorkCalendar::compute_end`] on the local date. The
+    /// wall-clock time is then reattached on the end date in `start`'s zone:
+    ///
+    /// * If that time occurs twice on the end date because clocks were set back, the
+    ///   earlier of the two instants is returned.
+    /// * If that time is skipped on the end date because clocks were set forward, it
+    ///   is moved forward by the length of the gap, so 02:30 on a day whose clocks jump
+    ///   from 02:00 to 03:00 becomes 03:30.
+    ///
+    /// # Errors
+    ///
+    /// Any error from [`WorkCalendar::compute_end`], or
+    /// [`WorkCalendarError::DateOutOfRange`] if the end time cannot be represented in
+    /// the zone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use workdays::WorkCalendar;
+    /// use chrono::TimeZone;
+    /// use chrono_tz::Europe::London;
+    ///
+    /// let calendar = WorkCalendar::new();
+    /// let start = London.with_ymd_and_hms(2024, 3, 29, 9, 0, 0).unwrap();
+    /// let end = calendar.compute_end_datetime(&start, 2).unwrap();
+    /// assert_eq!(end, London.with_ymd_and_hms(2024, 4, 1, 9, 0, 0).unwrap());
+    /// ```
+    pub fn compute_end_datetime<Tz: TimeZone>(
+        &self,
+        start: &DateTime<Tz>,
+        days_worked: i64,
+    ) -> Result<DateTime<Tz>, WorkCalendarError> {
+        let end = self.compute_end(start.date_naive(), days_worked)?;
+        let local = end.date.and_time(start.time());
+        resolve_local(&start.timezone(), &local).ok_or_else(|| WorkCalendarError::DateOutOfRange {
+            calendar: self.name().map(String::from),
+        })
+    }
+}
+
+/// Maps a wall-clock time to an instant: the earlier instant when it is ambiguous,
+/// and the time shifted forward by the gap when it does not exist.
+fn resolve_local<Tz: TimeZone>(zone: &Tz, local: &NaiveDateTime) -> Option<DateTime<Tz>> {
+    match zone.from_local_datetime(local) {
+        LocalResult::Single(instant) => Some(instant),
+        LocalResult::Ambiguous(earliest, _) => Some(earliest),
+        LocalResult::None => {
+            // Read the wall-clock time with the offset in force before the gap; the
+            // resulting instant lies just after it.
+            let before = local.checked_sub_signed(Duration::days(1))?;
+            let offset = zone.offset_from_local_datetime(&before).earliest()?;
+            let offset = Duration::seconds(i64::from(offset.fix().local_minus_utc()));
+            let utc = local.checked_sub_signed(offset)?;
+            Some(zone.from_utc_datetime(&utc))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Utc, Weekday};
+    use chrono_tz::{America::New_York, Pacific::Auckland};
+
+    fn every_day() -> WorkCalendar {
+        let mut calendar = WorkCalendar::new();
+        calendar.add_work_day(Weekday::Sat);
+        calendar.add_work_day(Weekday::Sun);
+        calendar
+    }
+
+    #[test]
+    fn test_local_date_not_utc_date() {
+        let calendar = WorkCalendar::new();
+        // Friday 23:30 in New York is Saturday 04:30 UTC.
+        let friday = New_York.with_ymd_and_hms(2024, 1, 5, 23, 30, 0).unwrap();
+        assert!(calendar.is_working_instant(&friday));
+        assert!(!calendar.is_working_instant(&friday.with_timezone(&Utc)));
+        // Monday 08:00 in Auckland is still Sunday in UTC.
+        let monday = Auckland.with_ymd_and_hms(2024, 1, 8, 8, 0, 0).unwrap();
+        assert!(calendar.is_working_instant(&monday));
+        assert!(!calendar.is_working_instant(&monday.with_timezone(&Utc)));
+
+        let end = calendar.compute_end_datetime(&friday, 2).unwrap();
+        assert_eq!(
+            end,
+            New_York.with_ymd_and_hms(2024, 1, 8, 23, 30, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_skipped_time_moves_forward() {
+        // Clocks in New York jump from 02:00 to 03:00 on 2024-03-10.
+        let start = New_York.with_ymd_and_hms(2024, 3, 9, 2, 30, 0).unwrap();
+        let end = every_day().compute_end_datetime(&start, 2).unwrap();
+        assert_eq!(
+            end,
+            New_York.with_ymd_and_hms(2024, 3, 10, 3, 30, 0).unwrap()
+        );
+        assert_eq!(end.offset().fix().local_minus_utc(), -4 * 3600);
+    }
+
+    #[test]
+    fn test_repeated_time_takes_earlier_instant() {
+        // Clocks in New York fall back from 02:00 to 01:00 on 2024-11-03.
+        let start = New_York.with_ymd_and_hms(2024, 11, 2, 1, 30, 0).unwrap();
+        let end = every_day().compute_end_datetime(&start, 2).unwrap();
+        assert_eq!(end.naive_local(), start.naive_local() + Duration::days(1));
+        assert_eq!(end.offset().fix().local_minus_utc(), -4 * 3600);
+    }
+
+    #[test]
+    fn test_errors_pass_through() {
+        let start = New_York.with_ymd_and_hms(2024, 1, 5, 9, 0, 0).unwrap();
+        assert!(matches!(
+            WorkCalendar::new().compute_end_datetime(&start, -1),
+            Err(WorkCalendarError::NegativeDaysWorked { .. })
+        ));
+    }
+}
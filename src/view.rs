@@ -0,0 +1,344 @@
+//! Borrowed views of a calendar that observe only some holiday categories.
+
+use crate::{CalendarLike, CategoryFilter, EndDate, WorkCalendar, WorkCalendarError};
+use chrono::NaiveDate;
+
+/// A calendar seen through a [`CategoryFilter`].
+///
+/// A view borrows its calendar rather than copying it, so creating one is cheap and
+/// every view made from a calendar answers from its current state: hand
+/// `calendar.view(...)` to each subsystem and keep a single calendar as the source of
+/// truth. Queries apply the filter as they go rather than copying the calendar, so
+/// they allocate no more than the same queries on the calendar itself. The borrow checker keeps the calendar from changing while a view of it is
+/// alive, and views created after a change see it.
+///
+/// Views implement [`CalendarLike`], so they work with any function that takes a
+/// calendar type.
+///
+/// # Examples
+///
+/// ```
+/// use workdays::{CalendarLike, CategoryFilter, WorkCalendar};
+/// use chrono::NaiveDate;
+///
+/// let mut calendar = WorkCalendar::new();
+/// let bank_holiday = NaiveDate::from_ymd_opt(2025, 8, 25).unwrap();
+/// calendar.add_holiday_with_category(bank_holiday, "optional");
+///
+/// let settlement = calendar.view(CategoryFilter::all());
+/// let staffing = calendar.view(CategoryFilter::exclude(&["optional"]));
+/// assert!(!settlement.is_working_date(&bank_holiday));
+/// assert!(staffing.is_working_date(&bank_holiday));
+///
+/// fn load(calendar: &impl CalendarLike, start: NaiveDate) -> i64 {
+///     calendar.work_days_between(start, start + chrono::Duration::days(6))
+/// }
+/// assert_eq!(load(&settlement, bank_holiday), 4);
+/// assert_eq!(load(&staffing, bank_holiday), 5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CalendarView<'a> {
+    calendar: &'a WorkCalendar,
+    filter: CategoryFilter,
+}
+
+impl WorkCalendar {
+    /// Returns a view of this calendar that observes only the holidays `filter`
+    /// accepts.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - The holiday categories the view observes.
+    pub fn view(&self, filter: CategoryFilter) -> CalendarView<'_> {
+        CalendarView {
+            calendar: self,
+            filter,
+        }
+    }
+}
+
+impl<'a> CalendarView<'a> {
+    /// Returns the underlying calendar.
+    pub fn calendar(&self) -> &'a WorkCalendar {
+        self.calendar
+    }
+
+    /// Returns the filter the view applies.
+    pub fn filter(&self) -> &CategoryFilter {
+        &self.filter
+    }
+
+    /// Returns the underlying calendar's name, if it has one.
+    pub fn name(&self) -> Option<&'a str> {
+        self.calendar.name()
+    }
+
+    /// Checks if `date` is a holiday the view observes.
+    pub fn is_holiday(&self, date: &NaiveDate) -> bool {
+        self.calendar.is_holiday_filtered(date, &self.filter)
+    }
+
+    /// Checks if `date` is a working date, ignoring holidays the view does not
+    /// observe.
+    pub fn is_working_date(&self, date: &NaiveDate) -> bool {
+        self.calendar.is_working_date_filtered(date, &self.filter)
+    }
+
+    /// Like [`WorkCalendar::work_days_between`], with the view's filter applied.
+    pub fn work_days_between(&self, start_date: NaiveDate, end_date: NaiveDate) -> i64 {
+        self.calendar
+            .work_days_between_filtered(start_date, end_date, &self.filter)
+    }
+
+    /// Like [`WorkCalendar::compute_end`], with the view's filter applied.
+    ///
+    /// # Errors
+    ///
+    /// The errors of [`WorkCalendar::compute_end`].
+    pub fn compute_end(
+        &self,
+        start_date: NaiveDate,
+        days_worked: i64,
+    ) -> Result<EndDate, WorkCalendarError> {
+        self.calendar
+            .compute_end_filtered(start_date, days_worked, &self.filter)
+    }
+
+    /// Like [`WorkCalendar::checked_add_work_days`], with the view's filter applied.
+    pub fn checked_add_work_days(&self, date: NaiveDate, n: i64) -> Option<NaiveDate> {
+        self.calendar
+            .checked_add_work_days_with(date, n, &self.observed())
+    }
+
+    /// Like [`WorkCalendar::checked_sub_work_days`], with the view's filter applied.
+    pub fn checked_sub_work_days(&self, date: NaiveDate, n: i64) -> Option<NaiveDate> {
+        self.calendar
+            .checked_add_work_days_with(date, n.checked_neg()?, &self.observed())
+    }
+
+    /// Like [`WorkCalendar::next_work_day`], with the view's filter applied.
+    ///
+    /// # Errors
+    ///
+    /// The errors of [`WorkCalendar::next_work_day`].
+    pub fn next_work_day(&self, date: NaiveDate) -> Result<NaiveDate, WorkCalendarError> {
+        self.calendar
+            .try_add_work_days_with(date, 1, &self.observed())
+    }
+
+    /// Like [`WorkCalendar::previous_work_day`], with the view's filter applied.
+    ///
+    /// # Errors
+    ///
+    /// The errors of [`WorkCalendar::previous_work_day`].
+    pub fn previous_work_day(&self, date: NaiveDate) -> Result<NaiveDate, WorkCalendarError> {
+        self.calendar
+            .try_add_work_days_with(date, -1, &self.observed())
+    }
+
+    /// Copies the view into a standalone calendar without the holidays it does not
+    /// observe, for use after the original changes.
+    pub fn to_calendar(&self) -> WorkCalendar {
+        self.calendar.with_filter(&self.filter).into_owned()
+    }
+
+    /// Returns the predicate the calendar's counting and searching take to observe
+    /// only the holidays the view's filter accepts.
+    fn observed(&self) -> impl Fn(&NaiveDate) -> bool + '_ {
+        |date| self.calendar.is_holiday_filtered(date, &self.filter)
+    }
+}
+
+impl CalendarLike for CalendarView<'_> {
+    fn name(&self) -> Option<&str> {
+        CalendarView::name(self)
+    }
+
+    fn is_working_date(&self, date: &NaiveDate) -> bool {
+        CalendarView::is_working_date(self, date)
+    }
+
+    fn has_work_days(&self) -> bool {
+        self.calendar.has_any_work_days()
+    }
+
+    fn work_days_between(&self, start_date: NaiveDate, end_date: NaiveDate) -> i64 {
+        CalendarView::work_days_between(self, start_date, end_date)
+    }
+
+    fn compute_end(
+        &self,
+        start_date: NaiveDate,
+        days_worked: i64,
+    ) -> Result<EndDate, WorkCalendarError> {
+        CalendarView::compute_end(self, start_date, days_worked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{count_allocations, date};
+    use crate::HolidayRule;
+
+    fn bank() -> WorkCalendar {
+        let mut calendar = WorkCalendar::new();
+        calendar.set_name("Bank");
+        calendar.add_holiday(date(2025, 12, 22));
+        calendar.add_holiday_with_category(date(2025, 12, 25), "public");
+        calendar.add_holiday_with_category(date(2025, 12, 26), "optional");
+        calendar.add_recurring_holiday_with_category(
+            HolidayRule::FixedDate { month: 12, day: 24 },
+            "optional",
+        );
+        calendar
+    }
+
+    /// Two subsystems sharing one calendar through differently filtered views.
+    struct Subsystems<'a> {
+        settlement: CalendarView<'a>,
+        staffing: CalendarView<'a>,
+    }
+
+    impl<'a> Subsystems<'a> {
+        fn new(calendar: &'a WorkCalendar) -> Self {
+            Subsystems {
+                settlement: calendar.view(CategoryFilter::all()),
+                staffing: calendar.view(CategoryFilter::exclude(&["optional"])),
+            }
+        }
+    }
+
+    fn week_load(calendar: &impl CalendarLike) -> i64 {
+        calendar.work_days_between(date(2025, 12, 22), date(2025, 12, 26))
+    }
+
+    #[test]
+    fn test_view_matches_filtered_calls() {
+        let calendar = bank();
+        let filter = CategoryFilter::exclude(&["optional"]);
+        let view = calendar.view(filter.clone());
+        assert_eq!(view.name(), Some("Bank"));
+        assert_eq!(view.filter(), &filter);
+        for offset in 0..14 {
+            let day = date(2025, 12, 20) + chrono::Duration::days(offset);
+            assert_eq!(
+                view.is_holiday(&day),
+                calendar.is_holiday_filtered(&day, &filter)
+            );
+            assert_eq!(
+                view.is_working_date(&day),
+                view.to_calendar().is_working_date(&day)
+            );
+        }
+        assert_eq!(
+            view.work_days_between(date(2025, 12, 22), date(2025, 12, 26)),
+            3
+        );
+        assert_eq!(
+            view.compute_end(date(2025, 12, 22), 2).unwrap().date,
+            date(2025, 12, 24)
+        );
+        assert_eq!(
+            view.checked_add_work_days(date(2025, 12, 23), 2),
+            Some(date(2025, 12, 26))
+        );
+        assert_eq!(
+            view.checked_sub_work_days(date(2025, 12, 29), 1),
+            Some(date(2025, 12, 26))
+        );
+        assert_eq!(
+            view.next_work_day(date(2025, 12, 25)),
+            Ok(date(2025, 12, 26))
+        );
+        assert_eq!(
+            view.previous_work_day(date(2025, 12, 29)),
+            Ok(date(2025, 12, 26))
+        );
+        assert_eq!(
+            CalendarLike::compute_end(&view, date(2025, 12, 22), -1),
+            Err(WorkCalendarError::NegativeDaysWorked {
+                calendar: Some("Bank".to_string())
+            })
+        );
+    }
+
+    #[test]
+    fn test_repeated_queries_do_not_allocate() {
+        let calendar = bank();
+        let view = calendar.view(CategoryFilter::exclude(&["optional"]));
+        let queries = || {
+            (
+                view.work_days_between(date(2025, 12, 1), date(2026, 1, 31)),
+                view.compute_end(date(2025, 12, 22), 30),
+                view.checked_add_work_days(date(2025, 12, 23), 2),
+                view.checked_sub_work_days(date(2025, 12, 29), 1),
+                view.next_work_day(date(2025, 12, 25)),
+                view.previous_work_day(date(2025, 12, 29)),
+            )
+        };
+        // The first run fills the calendar's rule cache for the years involved.
+        let expected = queries();
+        let (allocations, results) = count_allocations(|| {
+            (0..100)
+                .map(|_| queries())
+                .filter(|result| *result == expected)
+                .count()
+        });
+        assert_eq!(results, 100);
+        assert_eq!(allocations, 0);
+    }
+
+    #[test]
+    fn test_views_share_one_source_of_truth() {
+        let mut calendar = bank();
+        {
+            let subsystems = Subsystems::new(&calendar);
+            assert_eq!(week_load(&subsystems.settlement), 1);
+            assert_eq!(week_load(&subsystems.staffing), 3);
+            // Views are cheap to clone and still borrow the same calendar.
+            let copy = subsystems.staffing.clone();
+            assert!(std::ptr::eq(
+                copy.calendar(),
+                subsystems.staffing.calendar()
+            ));
+        }
+
+        // Once the views are gone the calendar can change, and new views see it.
+        calendar.add_holiday(date(2025, 12, 23));
+        calendar.add_holiday_with_category(date(2025, 12, 26), "public");
+        let subsystems = Subsystems::new(&calendar);
+        assert_eq!(week_load(&subsystems.settlement), 0);
+        assert_eq!(week_load(&subsystems.staffing), 1);
+    }
+
+    #[test]
+    fn test_views_across_threads() {
+        let calendar = bank();
+        let views = [
+            calendar.view(CategoryFilter::all()),
+            calendar.view(CategoryFilter::only(&["public"])),
+            calendar.view(CategoryFilter::only(&["optional"])),
+        ];
+        let loads: Vec<i64> = std::thread::scope(|scope| {
+            let handles: Vec<_> = views
+                .iter()
+                .map(|view| scope.spawn(move || week_load(view)))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        assert_eq!(loads, [1, 3, 2]);
+    }
+
+    #[test]
+    fn test_to_calendar_outlives_the_borrow() {
+        let mut calendar = bank();
+        let staffing = calendar
+            .view(CategoryFilter::exclude(&["optional"]))
+            .to_calendar();
+        calendar.add_holiday(date(2025, 12, 23));
+        assert!(staffing.is_working_date(&date(2025, 12, 23)));
+        assert!(staffing.is_working_date(&date(2025, 12, 26)));
+        assert!(!calendar.is_working_date(&date(2025, 12, 23)));
+    }
+}
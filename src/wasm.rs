@@ -0,0 +1,229 @@
+//! JavaScript bindings, available with the `wasm` feature.
+//!
+//! The crate builds only as a Rust library by default, so build the WebAssembly
+//! module as a `cdylib` explicitly and pass it to `wasm-bindgen`:
+//!
+//! ```text
+//! cargo rustc --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/workdays.wasm
+//! ```
+
+use crate::{WorkCalendar, WorkCalendarError};
+use chrono::{Duration, NaiveDate};
+use std::str::FromStr;
+use wasm_bindgen::{prelude::*, JsCast};
+
+const MILLIS_PER_DAY: f64 = 86_400_000.0;
+
+/// A [`WorkCalendar`] exposed to JavaScript as the `JsWorkCalendar` class.
+///
+/// Dates may be passed as ISO 8601 strings (`"2024-01-31"`), `Date` objects, or epoch
+/// milliseconds; `Date` objects and timestamps are read in UTC. Dates are returned as
+/// ISO 8601 strings. Failures throw an `Error` whose `name` identifies the
+/// [`WorkCalendarError`] variant, such as `"NoWorkDaysDefined"`, or is
+/// `"InvalidDate"` or `"InvalidConfig"` for bad input.
+///
+/// ```js
+/// const calendar = JsWorkCalendar.fromConfig('{"holidays": ["2023-12-25"]}');
+/// calendar.computeEndDate("2023-12-22", 2); // "2023-12-26"
+/// ```
+#[wasm_bindgen]
+#[derive(Debug, Clone, Default)]
+pub struct JsWorkCalendar {
+    calendar: WorkCalendar,
+}
+
+#[wasm_bindgen]
+impl JsWorkCalendar {
+    /// Creates a calendar working Monday to Friday with no holidays.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> JsWorkCalendar {
+        JsWorkCalendar::default()
+    }
+
+    /// Parses a YAML or JSON configuration, as accepted by `WorkCalendar::from_str`.
+    #[wasm_bindgen(js_name = fromConfig)]
+    pub fn from_config(config: &str) -> Result<JsWorkCalendar, JsValue> {
+        WorkCalendar::from_str(config)
+            .map(|calendar| JsWorkCalendar { calendar })
+            .map_err(|error| js_error("InvalidConfig", &error.to_string()))
+    }
+
+    /// Adds a holiday.
+    #[wasm_bindgen(js_name = addHoliday)]
+    pub fn add_holiday(&mut self, date: JsValue) -> Result<(), JsValue> {
+        self.calendar.add_holiday(to_date(&date)?);
+        Ok(())
+    }
+
+    /// Returns the date on which the `days`-th work day from `start` falls.
+    #[wasm_bindgen(js_name = computeEndDate)]
+    pub fn compute_end_date(&self, start: JsValue, days: f64) -> Result<String, JsValue> {
+        if days.fract() != 0.0 || days.abs() > MAX_SAFE_INTEGER {
+            return Err(js_error(
+                "InvalidDays",
+                &format!("days must be an integer, got {}", days),
+            ));
+        }
+        self.calendar
+            .compute_end(to_date(&start)?, days as i64)
+            .map(|end| end.date.to_string())
+            .map_err(|error| from_calendar_error(&error))
+    }
+
+    /// Counts the working dates between two dates, inclusive.
+    #[wasm_bindgen(js_name = workDaysBetween)]
+    pub fn work_days_between(&self, start: JsValue, end: JsValue) -> Result<f64, JsValue> {
+        let days = self
+            .calendar
+            .work_days_between(to_date(&start)?, to_date(&end)?);
+        Ok(days as f64)
+    }
+
+    /// Checks if a date is a working date.
+    #[wasm_bindgen(js_name = isWorkingDate)]
+    pub fn is_working_date(&self, date: JsValue) -> Result<bool, JsValue> {
+        Ok(self.calendar.is_working_date(&to_date(&date)?))
+    }
+}
+
+impl From<WorkCalendar> for JsWorkCalendar {
+    fn from(calendar: WorkCalendar) -> Self {
+        JsWorkCalendar { calendar }
+    }
+}
+
+/// `Number.MAX_SAFE_INTEGER`: larger numbers cannot be converted exactly.
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// Reads a date from an ISO 8601 string, a `Date`, or epoch milliseconds.
+fn to_date(value: &JsValue) -> Result<NaiveDate, JsValue> {
+    let millis = if let Some(text) = value.as_string() {
+        return NaiveDate::parse_from_str(&text, "%Y-%m-%d")
+            .map_err(|_| js_error("InvalidDate", &format!("Invalid date: {}", text)));
+    } else if let Some(date) = value.dyn_ref::<js_sys::Date>() {
+        date.get_time()
+    } else if let Some(millis) = value.as_f64() {
+        millis
+    } else {
+        return Err(js_error(
+            "InvalidDate",
+            "Expected an ISO date string, a Date, or epoch milliseconds",
+        ));
+    };
+    if !millis.is_finite() || millis.abs() > MAX_SAFE_INTEGER {
+        return Err(js_error("InvalidDate", "Invalid date"));
+    }
+    let days = (millis / MILLIS_PER_DAY).floor() as i64;
+    NaiveDate::from_ymd_opt(1970, 1, 1)
+        .and_then(|epoch| epoch.checked_add_signed(Duration::days(days)))
+        .ok_or_else(|| js_error("InvalidDate", "Date out of range"))
+}
+
+fn from_calendar_error(error: &WorkCalendarError) -> JsValue {
+    let name = match error {
+        WorkCalendarError::NegativeDaysWorked { .. } => "NegativeDaysWorked",
+        WorkCalendarError::NoWorkDaysDefined { .. } => "NoWorkDaysDefined",
+        WorkCalendarError::NoWorkingDaysInHorizon { .. } => "NoWorkingDaysInHorizon",
+        WorkCalendarError::DateOutOfRange { .. } => "DateOutOfRange",
+        WorkCalendarError::GlobalCalendarNotSet => "GlobalCalendarNotSet",
+        WorkCalendarError::InvalidWeekdays(_) => "InvalidWeekdays",
+        WorkCalendarError::InvalidRelativeDate(_) => "InvalidRelativeDate",
+        WorkCalendarError::Io { .. } => "Io",
+        WorkCalendarError::InvalidBitmap { .. } => "InvalidBitmap",
+        WorkCalendarError::InvalidAnchor { .. } => "InvalidAnchor",
+        WorkCalendarError::InvalidSprintLength { .. } => "InvalidSprintLength",
+        WorkCalendarError::DuplicateTask(_) => "DuplicateTask",
+        WorkCalendarError::UnknownDependency { .. } => "UnknownDependency",
+        WorkCalendarError::DependencyCycle(_) => "DependencyCycle",
+        WorkCalendarError::InvalidBufferFraction(_) => "InvalidBufferFraction",
+        WorkCalendarError::InvalidBusinessHours { .. } => "InvalidBusinessHours",
+        WorkCalendarError::NegativeSla { .. } => "NegativeSla",
+        WorkCalendarError::NegativeShifts { .. } => "NegativeShifts",
+        WorkCalendarError::InvalidHours(_) => "InvalidHours",
+        WorkCalendarError::InvalidHoursPerDay(_) => "InvalidHoursPerDay",
+        WorkCalendarError::InvalidEffectiveWindow { .. } => "InvalidEffectiveWindow",
+        WorkCalendarError::InvalidExpression { .. } => "InvalidExpression",
+        WorkCalendarError::UnknownAnchor { .. } => "UnknownAnchor",
+        WorkCalendarError::DuplicateCalendar(_) => "DuplicateCalendar",
+        WorkCalendarError::UnknownCalendar(_) => "UnknownCalendar",
+        WorkCalendarError::InvalidCalendarFile { .. } => "InvalidCalendarFile",
+        WorkCalendarError::CalendarFileConfig { .. } => "CalendarFileConfig",
+        WorkCalendarError::DuplicateLayer(_) => "DuplicateLayer",
+        WorkCalendarError::ConfigParse(_) => "ConfigParse",
+        WorkCalendarError::NonWorkingStart { .. } => "NonWorkingStart",
+        WorkCalendarError::InvalidTenor { .. } => "InvalidTenor",
+        WorkCalendarError::ConfigSyntax { .. } => "ConfigSyntax",
+        WorkCalendarError::InvalidHolidayRange { .. } => "InvalidHolidayRange",
+        WorkCalendarError::InvalidConfig { .. } => "InvalidConfig",
+        WorkCalendarError::NoValidWorkDays { .. } => "NoValidWorkDays",
+        WorkCalendarError::InvalidIcs { .. } => "InvalidIcs",
+        // Unreachable within this crate, but keeps the mapping in step with the
+        // non-exhaustive enum should a variant be added without a name here.
+        #[allow(unreachable_patterns)]
+        _ => "WorkCalendarError",
+    };
+    js_error(name, &error.to_string())
+}
+
+fn js_error(name: &str, message: &str) -> JsValue {
+    let error = js_sys::Error::new(message);
+    error.set_name(name);
+    error.into()
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    fn error_name(error: JsValue) -> String {
+        error.unchecked_into::<js_sys::Error>().name().into()
+    }
+
+    #[wasm_bindgen_test]
+    fn test_main_flows() {
+        let mut calendar =
+            JsWorkCalendar::from_config(r#"{"work_days": ["Mon", "Tue", "Wed", "Thu", "Fri"]}"#)
+                .unwrap();
+        calendar.add_holiday("2023-12-25".into()).unwrap();
+
+        assert_eq!(
+            calendar.compute_end_date("2023-12-22".into(), 2.0).unwrap(),
+            "2023-12-26"
+        );
+        assert_eq!(
+            calendar
+                .work_days_between("2023-12-18".into(), "2023-12-31".into())
+                .unwrap(),
+            9.0
+        );
+        assert!(!calendar.is_working_date("2023-12-25".into()).unwrap());
+        assert!(calendar.is_working_date("2023-12-26".into()).unwrap());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_dates_and_timestamps() {
+        let calendar = JsWorkCalendar::new();
+        let date = js_sys::Date::new(&"2023-12-23T00:00:00Z".into());
+        assert!(!calendar.is_working_date(date.into()).unwrap());
+        // 2023-12-26T12:00:00Z
+        assert!(calendar
+            .is_working_date(1_703_592_000_000.0.into())
+            .unwrap());
+        assert!(calendar.is_working_date((-86_400_000.0).into()).unwrap());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_errors_carry_variant_names() {
+        let calendar = JsWorkCalendar::new();
+        let negative = calendar.compute_end_date("2024-01-01".into(), -1.0);
+        assert_eq!(error_name(negative.unwrap_err()), "NegativeDaysWorked");
+        let fractional = calendar.compute_end_date("2024-01-01".into(), 1.5);
+        assert_eq!(error_name(fractional.unwrap_err()), "InvalidDays");
+        let bad_date = calendar.is_working_date("2024-02-30".into());
+        assert_eq!(error_name(bad_date.unwrap_err()), "InvalidDate");
+        let bad_config = JsWorkCalendar::from_config(r#"{"work_days": []}"#);
+        assert_eq!(error_name(bad_config.unwrap_err()), "InvalidConfig");
+    }
+}
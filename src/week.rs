@@ -0,0 +1,155 @@
+//! The weekdays a calendar works, with a cached summary.
+
+use chrono::{NaiveDate, Weekday};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::Arc,
+};
+
+/// Work weeks keyed by the date from which each replaces the one before it.
+pub(crate) type WeekChanges = BTreeMap<NaiveDate, WorkWeek>;
+
+/// Returns the week in effect on `date`: the latest change on or before it, or `base`
+/// if there is none.
+pub(crate) fn week_on<'a>(
+    base: &'a WorkWeek,
+    changes: &'a WeekChanges,
+    date: NaiveDate,
+) -> &'a WorkWeek {
+    changes
+        .range(..=date)
+        .next_back()
+        .map_or(base, |(_, week)| week)
+}
+
+/// A calendar's work days together with their weekday bitmask and count.
+///
+/// The summary is recomputed by every method that changes the set, and deserializing
+/// goes through the same constructor, so it can never disagree with the days it
+/// describes. Serializes as the list of weekdays from Monday to Sunday, so the output
+/// does not depend on the set's iteration order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct WorkWeek {
+    days: Arc<HashSet<Weekday>>,
+    /// Bit `n` is set when the weekday `n` days after Monday is a work day.
+    mask: u8,
+    days_per_week: u8,
+}
+
+impl WorkWeek {
+    /// Returns the bit for `day` in [`WorkWeek::mask`].
+    pub(crate) fn bit(day: Weekday) -> u8 {
+        1 << day.num_days_from_monday()
+    }
+
+    /// Checks if `day` is a work day.
+    pub(crate) fn contains(&self, day: Weekday) -> bool {
+        self.mask & Self::bit(day) != 0
+    }
+
+    /// Returns the weekday bitmask; see [`WorkWeek::bit`].
+    pub(crate) fn mask(&self) -> u8 {
+        self.mask
+    }
+
+    /// Returns the number of work days in a full week.
+    pub(crate) fn days_per_week(&self) -> i64 {
+        i64::from(self.days_per_week)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.mask == 0
+    }
+
+    /// Returns the work days in unspecified order.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = Weekday> + '_ {
+        self.days.iter().copied()
+    }
+
+    /// Returns the work days in order from Monday to Sunday.
+    pub(crate) fn sorted(&self) -> Vec<Weekday> {
+        let mut days: Vec<Weekday> = self.iter().collect();
+        days.sort_by_key(|day| day.num_days_from_monday());
+        days
+    }
+
+    /// Returns the work days as a set.
+    pub(crate) fn as_set(&self) -> &HashSet<Weekday> {
+        &self.days
+    }
+
+    /// Checks if both weeks share the same underlying set.
+    pub(crate) fn ptr_eq(&self, other: &WorkWeek) -> bool {
+        Arc::ptr_eq(&self.days, &other.days)
+    }
+
+    pub(crate) fn insert(&mut self, day: Weekday) {
+        if !self.contains(day) {
+            Arc::make_mut(&mut self.days).insert(day);
+            self.sync();
+        }
+    }
+
+    pub(crate) fn remove(&mut self, day: Weekday) {
+        if self.contains(day) {
+            Arc::make_mut(&mut self.days).remove(&day);
+            self.sync();
+        }
+    }
+
+    /// Keeps only the days that are also work days in `other`.
+    pub(crate) fn retain_common(&mut self, other: &WorkWeek) {
+        if self.mask & !other.mask != 0 {
+            Arc::make_mut(&mut self.days).retain(|day| other.contains(*day));
+            self.sync();
+        }
+    }
+
+    fn sync(&mut self) {
+        self.mask = self.days.iter().fold(0, |mask, day| mask | Self::bit(*day));
+        self.days_per_week = self.days.len() as u8;
+    }
+}
+
+impl From<HashSet<Weekday>> for WorkWeek {
+    fn from(days: HashSet<Weekday>) -> Self {
+        let mut week = WorkWeek {
+            days: Arc::new(days),
+            mask: 0,
+            days_per_week: 0,
+        };
+        week.sync();
+        week
+    }
+}
+
+impl FromIterator<Weekday> for WorkWeek {
+    fn from_iter<I: IntoIterator<Item = Weekday>>(days: I) -> Self {
+        WorkWeek::from(days.into_iter().collect::<HashSet<_>>())
+    }
+}
+
+impl Serialize for WorkWeek {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.sorted().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for WorkWeek {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        HashSet::deserialize(deserializer).map(WorkWeek::from)
+    }
+}
+
+#[cfg(test)]
+impl WorkWeek {
+    /// Checks that the cached summary matches the set.
+    pub(crate) fn is_consistent(&self) -> bool {
+        let mask = (0..7u8)
+            .map(|n| Weekday::try_from(n).unwrap())
+            .filter(|day| self.days.contains(day))
+            .fold(0, |mask, day| mask | Self::bit(day));
+        self.mask == mask && self.days_per_week() == self.days.len() as i64
+    }
+}